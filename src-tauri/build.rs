@@ -1,8 +1,56 @@
 use std::env;
 use std::path::PathBuf;
 
+// Every command in generate_handler! must be listed here and granted by a group in permissions/,
+// otherwise the webviews are denied access to it
+const COMMANDS: &[&str] = &[
+    "get_config", "save_config", "get_setup_status", "detect_steam_setup", "find_ludusavi", "download_ludusavi", "check_backup_folder",
+    "validate_steam_api_key", "complete_setup", "set_read_only_mode", "browse_file", "browse_folder",
+    "test_ludusavi", "get_ludusavi_manifest", "get_ludusavi_customization", "save_ludusavi_custom_game", "remove_ludusavi_custom_game",
+    "set_ludusavi_roots", "get_all_achievements", "get_game_achievements",
+    "get_merged_game_achievements", "update_achievement_status", "sync_achievements", "sync_achievements_across_pcs",
+    "get_lan_peers", "add_manual_achievement", "export_achievements", "export_game_achievements", "choose_export_destination",
+    "export_activity_timeline", "search_steam_games", "check_game_sources", "add_game_from_source", "remove_game_from_tracking",
+    "find_duplicate_games", "consolidate_game_sources", "delete_game_source",
+    "detect_achievement_dlc", "get_achievement_dlc_groups", "set_achievement_dlc",
+    "get_completion_order", "get_achievement_tags", "set_achievement_tags",
+    "scan_achievement_screenshots", "get_achievement_screenshots", "get_achievement_view_prefs", "set_achievement_view_prefs",
+    "get_game_metadata", "set_game_metadata", "delete_game_metadata", "set_game_status", "get_game_steam_ids", "set_game_steam_id",
+    "set_game_favorite", "get_recent_unlocks", "get_unlock_statistics", "get_last_session_recap",
+    "export_sound_pack", "import_sound_pack", "list_sound_packs", "delete_sound_pack",
+    "list_achievement_source_plugins", "get_achievement_source_plugins_folder", "get_achievement_source_health", "list_scripts",
+    "get_missed_events", "get_active_operations", "take_launch_request", "get_diagnostics", "simulate_game_session", "get_scripts_folder", "test_unlock_webhook",
+    "get_all_exclusions", "add_exclusion",
+    "remove_exclusion", "add_exclusions", "remove_exclusions", "exclude_non_game_apps", "export_exclusions", "import_exclusions", "get_steam_categories", "sync_steam_category_exclusions",
+    "get_steam_executables", "get_steam_library", "get_ignored_executables", "ignore_executable", "unignore_executable", "fetch_achievement_icon",
+    "download_all_achievement_icons", "test_overlay", "test_rarity_notification",
+    "sync_settings_to_overlay", "get_achievement_duration", "set_achievement_duration",
+    "reset_game_monitoring", "stop_game_monitoring", "get_current_game",
+    "play_windows_notification_sound", "debug_log", "read_audio_file", "check_backup_exists",
+    "get_output_location", "restore_from_backup",
+    "get_backup_key_status", "generate_backup_key", "set_backup_key_passphrase",
+    "export_backup_recovery_phrase", "verify_backup_key", "recover_backup_key",
+    "list_achievement_backups", "delete_achievement_backup", "rename_achievement_backup",
+    "list_game_snapshots", "list_backup_versions", "compare_backup_snapshots",
+    "list_snapshot_restore_files", "restore_snapshot_files", "restore_game_saves", "restore_backup_version", "backup_game_now", "preview_backup",
+    "backup_all_games", "next_scheduled_backup", "cancel_backup", "list_dedup_snapshots",
+    "extract_dedup_snapshot", "delete_dedup_snapshot", "get_dedup_stats", "get_backup_stats", "get_storage_dashboard", "run_backup_verification",
+    "get_backup_history", "get_recent_backups", "get_backup_freshness", "get_uninstalled_games", "archive_uninstalled_game",
+    "discover_save_paths", "confirm_save_path", "start_cloud_auth", "complete_cloud_auth",
+    "sign_out_cloud_destination", "test_cloud_destination", "get_dropbox_authorize_url",
+    "complete_dropbox_auth", "set_cloud_destination_enabled", "set_cloud_destination_secret",
+    "test_syncthing_connection", "get_pending_uploads", "list_google_drive_folders",
+    "create_google_drive_folder", "upload_game_backup_to_cloud", "get_cloud_conflict", "resolve_cloud_conflict",
+    "ludusavi_cloud_sync", "get_ludusavi_cloud_status", "get_cloud_quota",
+    "check_for_update", "download_update", "get_update_status", "rollback_update",
+];
+
 fn main() {
-    tauri_build::build();
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(COMMANDS)),
+    )
+    .expect("failed to run tauri-build");
 
     // Copy steam_api64.dll to the output directory
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{filenames, user_folders};
+use crate::app_log;
+
+const EXPORT_DIR: &str = "Steam Backup Monitor";
+// Backups live in one folder per app id so renamed games and games whose names sanitize alike never clash
+const BACKUPS_DIR: &str = "Achievements";
+const BACKUP_FILE: &str = "achievements.json";
+const METADATA_FILE: &str = "metadata.json";
+
+/// Written next to each backup so the folder can be identified without the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupMetadata {
+    pub game_name: String,
+    pub app_id: u32,
+    pub source: String,
+    pub timestamp: i64,
+    pub unlocked: usize,
+}
+
+/// A backup as shown in the backup manager
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupSummary {
+    pub app_id: u32,
+    pub game_name: String,
+    pub source: String,
+    pub unlocked: usize,
+    pub modified: i64,
+    pub path: String,
+}
+
+fn app_dir(app_id: u32) -> PathBuf {
+    Path::new(EXPORT_DIR).join(BACKUPS_DIR).join(app_id.to_string())
+}
+
+/// Write a backup in the Steam UnlockTime format along with its metadata, replacing the previous one
+pub fn save(metadata: &BackupMetadata, contents: &[u8]) -> Result<PathBuf, String> {
+    let dir = app_dir(metadata.app_id);
+    let path = user_folders::write_file(&dir.join(BACKUP_FILE), contents)?;
+
+    let json = serde_json::to_string_pretty(metadata).map_err(|e| format!("Failed to serialize backup metadata: {}", e))?;
+    user_folders::write_file(&dir.join(METADATA_FILE), json.as_bytes())?;
+    Ok(path)
+}
+
+/// Existing backup for a game
+pub fn find(app_id: u32) -> Option<PathBuf> {
+    user_folders::find_file(&app_dir(app_id).join(BACKUP_FILE))
+}
+
+pub fn metadata(app_id: u32) -> Option<BackupMetadata> {
+    user_folders::find_file(&app_dir(app_id).join(METADATA_FILE))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// Every backup under the output folder, newest first
+pub fn list() -> Vec<BackupSummary> {
+    let backups_dir = user_folders::output_root().join(EXPORT_DIR).join(BACKUPS_DIR);
+    let Ok(entries) = fs::read_dir(&backups_dir) else { return Vec::new() };
+
+    let mut backups: Vec<BackupSummary> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str().and_then(|name| name.parse::<u32>().ok()))
+        .filter_map(|app_id| {
+            let path = find(app_id)?;
+            let modified = fs::metadata(&path).ok()?.modified().ok()?
+                .duration_since(std::time::UNIX_EPOCH).ok()?
+                .as_secs() as i64;
+            // Fall back to the file itself if the metadata went missing
+            let metadata = metadata(app_id).unwrap_or_else(|| BackupMetadata {
+                game_name: app_id.to_string(),
+                app_id,
+                source: "Unknown".to_string(),
+                timestamp: modified,
+                unlocked: count_entries(&path),
+            });
+            Some(BackupSummary {
+                app_id,
+                game_name: metadata.game_name,
+                source: metadata.source,
+                unlocked: metadata.unlocked,
+                modified,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.modified.cmp(&a.modified));
+    backups
+}
+
+fn count_entries(path: &Path) -> usize {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&contents).ok())
+        .map(|entries| entries.len())
+        .unwrap_or(0)
+}
+
+/// Remove a game's backup folder
+pub fn delete(app_id: u32) -> Result<(), String> {
+    let dir = find(app_id)
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .ok_or_else(|| format!("No backup for app {}", app_id))?;
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))?;
+    app_log!("[Backups] Deleted backup for app {}", app_id);
+    Ok(())
+}
+
+/// Change the game name a backup is shown under. The folder stays keyed by app id.
+pub fn rename(app_id: u32, game_name: &str) -> Result<BackupMetadata, String> {
+    let game_name = game_name.trim();
+    if game_name.is_empty() {
+        return Err("Game name can't be empty".to_string());
+    }
+    let path = find(app_id).ok_or_else(|| format!("No backup for app {}", app_id))?;
+
+    let mut metadata = metadata(app_id).unwrap_or_else(|| BackupMetadata {
+        game_name: String::new(),
+        app_id,
+        source: "Unknown".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        unlocked: count_entries(&path),
+    });
+    metadata.game_name = game_name.to_string();
+
+    let json = serde_json::to_string_pretty(&metadata).map_err(|e| format!("Failed to serialize backup metadata: {}", e))?;
+    let metadata_path = path.with_file_name(METADATA_FILE);
+    fs::write(&metadata_path, json).map_err(|e| format!("Failed to write {}: {}", metadata_path.display(), e))?;
+    Ok(metadata)
+}
+
+/// Backup for a game, first moving one written by an older version (named after the game) into its app id folder
+pub fn find_or_migrate(app_id: u32, game_name: &str) -> Option<PathBuf> {
+    if let Some(path) = find(app_id) {
+        return Some(path);
+    }
+
+    let export_dir = user_folders::output_root().join(EXPORT_DIR);
+    let stems = [
+        filenames::lookup_stem(&export_dir, game_name, Some(app_id)),
+        filenames::lookup_stem(&export_dir, game_name, None),
+        filenames::legacy_file_stem(game_name),
+    ];
+    let legacy = stems.iter()
+        .find_map(|stem| user_folders::find_file(&Path::new(EXPORT_DIR).join(format!("{}.json", stem))))?;
+
+    match migrate(&legacy, app_id, game_name) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            app_log!("[Backups] Leaving {} in place: {}", legacy.display(), e);
+            None
+        }
+    }
+}
+
+/// Move every old backup whose file name carries an app id. Name-only files are moved by
+/// `find_or_migrate` once a game with that name is added, since only then is the app id known.
+pub fn migrate_all() -> usize {
+    let export_dir = user_folders::output_root().join(EXPORT_DIR);
+    let Ok(entries) = fs::read_dir(&export_dir) else { return 0 };
+
+    let mut migrated = 0;
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else { continue };
+        let Some((name, app_id)) = split_stem(&stem) else { continue };
+        if find(app_id).is_some() {
+            continue; // a newer backup already exists
+        }
+
+        let game_name = filenames::stem_owner(&export_dir, &stem).unwrap_or(name);
+        match migrate(&path, app_id, &game_name) {
+            Ok(_) => migrated += 1,
+            Err(e) => app_log!("[Backups] Leaving {} in place: {}", path.display(), e),
+        }
+    }
+    migrated
+}
+
+// "Name [123]" or "Name [123] ~hash", as written by filenames::claim_stem
+fn split_stem(stem: &str) -> Option<(String, u32)> {
+    let start = stem.rfind(" [")?;
+    let rest = &stem[start + 2..];
+    let end = rest.find(']')?;
+    let app_id = rest[..end].parse().ok()?;
+    Some((stem[..start].to_string(), app_id))
+}
+
+fn migrate(legacy: &Path, app_id: u32, game_name: &str) -> Result<PathBuf, String> {
+    let contents = fs::read(legacy).map_err(|e| format!("Failed to read {}: {}", legacy.display(), e))?;
+
+    // Other exports (CSV, the app's own JSON list) share the folder; only UnlockTime maps are backups
+    let entries: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&contents)
+        .map_err(|_| "not an achievement backup".to_string())?;
+
+    let timestamp = fs::metadata(legacy)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|age| age.as_secs() as i64)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+    let path = save(&BackupMetadata {
+        game_name: game_name.to_string(),
+        app_id,
+        source: "Unknown".to_string(),
+        timestamp,
+        unlocked: entries.len(),
+    }, &contents)?;
+
+    fs::remove_file(legacy).map_err(|e| format!("Failed to remove {}: {}", legacy.display(), e))?;
+    app_log!("[Backups] Moved {} to {}", legacy.display(), path.display());
+    Ok(path)
+}
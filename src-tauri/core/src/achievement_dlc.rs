@@ -0,0 +1,105 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::achievements::Achievement;
+
+/// Stands in for the DLC of achievements that are clearly DLC but can't be matched to one
+pub const UNKNOWN_DLC: u32 = 0;
+
+/// A DLC of a game as listed on the Steam store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DlcInfo {
+    pub dlc_app_id: u32,
+    pub name: String,
+}
+
+/// Completion of the base game or one of its DLCs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DlcGroup {
+    pub dlc_app_id: Option<u32>, // None for the base game
+    pub name: String,
+    pub total: usize,
+    pub unlocked: usize,
+    pub achievement_ids: Vec<String>,
+}
+
+/// Guess which DLC each achievement belongs to. Steam schemas don't say, so this looks for a DLC's
+/// name in the achievement text, then for "DLC2"-style prefixes in the API name.
+/// Achievements left out are base-game ones.
+pub fn classify(achievements: &[Achievement], game_name: &str, dlcs: &[DlcInfo]) -> HashMap<String, u32> {
+    let mut dlcs: Vec<&DlcInfo> = dlcs.iter().collect();
+    dlcs.sort_by_key(|dlc| dlc.dlc_app_id); // app ids are handed out in release order
+
+    // "Hollow Knight - Godmaster" is written as "Godmaster" in achievement descriptions
+    let mut short_names: Vec<(String, u32)> = dlcs.iter()
+        .map(|dlc| (short_name(&dlc.name, game_name).to_lowercase(), dlc.dlc_app_id))
+        .filter(|(name, _)| name.chars().count() >= 4)
+        .collect();
+    short_names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len())); // "Act II Part 2" before "Act II"
+
+    let numbered = Regex::new(r"(?:^|_)DLC_?(\d+)(?:_|$)").expect("valid regex");
+    let tagged = Regex::new(r"(?:^|_)DLC(?:_|$)").expect("valid regex");
+
+    let mut assignments = HashMap::new();
+    for achievement in achievements {
+        let text = format!("{} {}", achievement.display_name, achievement.description).to_lowercase();
+        let by_name = short_names.iter().find(|(name, _)| text.contains(name.as_str())).map(|(_, id)| *id);
+
+        let api_name = achievement.achievement_id.to_uppercase();
+        let by_prefix = || {
+            if let Some(captures) = numbered.captures(&api_name) {
+                let index: usize = captures[1].parse().ok()?;
+                return Some(index.checked_sub(1).and_then(|i| dlcs.get(i)).map_or(UNKNOWN_DLC, |dlc| dlc.dlc_app_id));
+            }
+            tagged.is_match(&api_name)
+                .then(|| if dlcs.len() == 1 { dlcs[0].dlc_app_id } else { UNKNOWN_DLC })
+        };
+
+        if let Some(dlc_app_id) = by_name.or_else(by_prefix) {
+            assignments.insert(achievement.achievement_id.clone(), dlc_app_id);
+        }
+    }
+    assignments
+}
+
+// DLC name without the game's name in front
+fn short_name<'a>(dlc_name: &'a str, game_name: &str) -> &'a str {
+    let trimmed = dlc_name.get(..game_name.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(game_name))
+        .map_or(dlc_name, |_| &dlc_name[game_name.len()..]);
+    trimmed.trim_start_matches([' ', '-', ':', '–', '—']).trim()
+}
+
+/// Split a game's achievements into the base game and each DLC. `assignments` maps achievement ids
+/// to a DLC app id, or to None where the user marked one as base game.
+pub fn group(achievements: &[Achievement], assignments: &HashMap<String, Option<u32>>, dlcs: &[DlcInfo]) -> Vec<DlcGroup> {
+    let mut groups = vec![DlcGroup { dlc_app_id: None, name: "Base game".to_string(), total: 0, unlocked: 0, achievement_ids: Vec::new() }];
+    groups.extend(dlcs.iter().map(|dlc| DlcGroup {
+        dlc_app_id: Some(dlc.dlc_app_id),
+        name: dlc.name.clone(),
+        total: 0,
+        unlocked: 0,
+        achievement_ids: Vec::new(),
+    }));
+
+    for achievement in achievements {
+        let dlc_app_id = assignments.get(&achievement.achievement_id).copied().flatten();
+        let index = match groups.iter().position(|g| g.dlc_app_id == dlc_app_id) {
+            Some(index) => index,
+            None => {
+                groups.push(DlcGroup { dlc_app_id, name: "Other DLC".to_string(), total: 0, unlocked: 0, achievement_ids: Vec::new() });
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[index];
+        group.total += 1;
+        if achievement.achieved {
+            group.unlocked += 1;
+        }
+        group.achievement_ids.push(achievement.achievement_id.clone());
+    }
+
+    // Most DLCs (soundtracks, cosmetics) have no achievements of their own
+    groups.retain(|g| g.dlc_app_id.is_none() || g.total > 0);
+    groups
+}
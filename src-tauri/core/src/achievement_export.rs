@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use crate::achievements::Achievement;
+
+/// File formats a game's achievements can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// `{"<id>": {"UnlockTime": <timestamp>}}`, unlocked achievements only
+    #[default]
+    SteamUnlockTime,
+    /// Goldberg/GSE achievements.json: `{"<id>": {"earned": true, "earned_time": <timestamp>}}`
+    Goldberg,
+    /// One row per achievement, locked ones included
+    Csv,
+    /// The app's own achievement records, locked ones included
+    Native,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            _ => "json",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::SteamUnlockTime => "Steam UnlockTime",
+            ExportFormat::Goldberg => "Goldberg",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Native => "Steam Backup Manager JSON",
+        }
+    }
+}
+
+/// What an export wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub format: ExportFormat,
+    pub achievements_written: usize,
+    pub unlocked_written: usize,
+    pub bytes_written: usize,
+}
+
+/// Render achievements in `format`. Returns the file contents and how many achievements went in.
+pub fn render(format: ExportFormat, achievements: &[Achievement]) -> Result<(String, usize), String> {
+    let unlocked: Vec<&Achievement> = achievements.iter().filter(|a| a.achieved).collect();
+
+    let (contents, written) = match format {
+        ExportFormat::SteamUnlockTime => {
+            let map: serde_json::Map<String, serde_json::Value> = unlocked.iter()
+                .map(|a| (a.achievement_id.clone(), serde_json::json!({ "UnlockTime": a.unlock_time.unwrap_or(0) })))
+                .collect();
+            (to_json(&map)?, unlocked.len())
+        }
+        ExportFormat::Goldberg => {
+            let map: serde_json::Map<String, serde_json::Value> = unlocked.iter()
+                .map(|a| (a.achievement_id.clone(), serde_json::json!({ "earned": true, "earned_time": a.unlock_time.unwrap_or(0) })))
+                .collect();
+            (to_json(&map)?, unlocked.len())
+        }
+        ExportFormat::Csv => {
+            let mut csv = String::from("achievement_id,display_name,description,achieved,unlock_time,source,global_unlock_percentage\r\n");
+            for a in achievements {
+                let fields = [
+                    csv_field(&a.achievement_id),
+                    csv_field(&a.display_name),
+                    csv_field(&a.description),
+                    a.achieved.to_string(),
+                    a.unlock_time.map(|t| t.to_string()).unwrap_or_default(),
+                    csv_field(&a.source),
+                    a.global_unlock_percentage.map(|p| p.to_string()).unwrap_or_default(),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push_str("\r\n");
+            }
+            (csv, achievements.len())
+        }
+        ExportFormat::Native => (to_json(&achievements)?, achievements.len()),
+    };
+    Ok((contents, written))
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize to JSON: {}", e))
+}
+
+// Quote fields containing separators, quotes or line breaks (RFC 4180)
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -4,6 +4,9 @@ use std::path::PathBuf;
 use crate::achievements::{Achievement, AchievementDatabase};
 use chrono::Utc;
 use crate::steam_achievements::SteamAchievementClient;
+use crate::steam_accounts;
+use crate::librarycache;
+use crate::app_log;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SteamAchievement {
@@ -36,43 +39,12 @@ impl AchievementScanner {
     }
 
     fn find_steam_userdata(steam_path: &PathBuf, user_id: Option<String>) -> Result<PathBuf, String> {
-        let userdata_path = steam_path.join("userdata");
-
-        if !userdata_path.exists() {
-            return Err("Steam userdata folder not found".to_string());
-        }
-
-        // If user ID is provided, use it directly
-        if let Some(id) = user_id {
-            let user_path = userdata_path.join(&id);
-            if user_path.exists() && user_path.is_dir() {
-                println!("  Using configured Steam user ID: {}", id);
-                return Ok(user_path);
-            } else {
-                return Err(format!("Steam user ID '{}' not found", id));
-            }
-        }
-
-        // Otherwise, find the first valid user directory (excluding "0" and "ac")
-        let user_dirs: Vec<_> = fs::read_dir(&userdata_path)
-            .map_err(|e| format!("Failed to read userdata: {}", e))?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry.path().is_dir()
-                    && entry.file_name() != "0"
-                    && entry.file_name() != "ac"
-            })
-            .collect();
-
-        if user_dirs.is_empty() {
-            return Err("No Steam user found".to_string());
-        }
-
-        let selected_user = user_dirs[0].path();
-        if let Some(user_name) = selected_user.file_name() {
-            println!("  Auto-detected Steam user ID: {:?} (configure this in Settings if incorrect)", user_name);
+        let user_path = steam_accounts::userdata_dir(steam_path, user_id.as_deref())?;
+        if let Some(account) = user_path.file_name() {
+            let source = if user_id.as_deref().is_some_and(|id| !id.trim().is_empty()) { "configured" } else { "most recently active" };
+            app_log!("  Using {} Steam user ID: {:?}", source, account);
         }
-        Ok(selected_user)
+        Ok(user_path)
     }
 
     /// Scan Steam's official achievement files from librarycache
@@ -133,70 +105,13 @@ impl AchievementScanner {
         }
 
         // STEP 2: Read library cache to see which ones are unlocked
-        let contents = fs::read_to_string(path)
-            .map_err(|e| format!("Failed to read librarycache file: {}", e))?;
-
-        // Parse the nested JSON array structure
-        let json: serde_json::Value = serde_json::from_str(&contents)
-            .map_err(|e| format!("Failed to parse librarycache JSON: {}", e))?;
-
-        // Find the "achievements" entry in the array
-        let achievements_entry = json.as_array()
-            .and_then(|arr| {
-                arr.iter().find(|item| {
-                    item.as_array()
-                        .and_then(|inner| inner.get(0))
-                        .and_then(|v| v.as_str())
-                        .map(|s| s == "achievements")
-                        .unwrap_or(false)
-                })
-            })
-            .ok_or_else(|| "No achievements entry found".to_string())?;
-
-        let achievement_data = achievements_entry.as_array()
-            .and_then(|arr| arr.get(1))
-            .and_then(|v| v.get("data"))
-            .ok_or_else(|| "Invalid achievement data structure".to_string())?;
+        let cache = librarycache::read(path)?;
+        app_log!("  ✓ Read {} achievements from librarycache ({} layout)", cache.achievements.len(), cache.format);
 
         // STEP 3: Build a map of unlocked achievements from library cache
-        let mut unlocked_map: std::collections::HashMap<String, (bool, Option<i64>)> = std::collections::HashMap::new();
-
-        // Process vecHighlight (visible achievements - both achieved and unachieved)
-        if let Some(vec_highlight) = achievement_data.get("vecHighlight").and_then(|v| v.as_array()) {
-            for ach in vec_highlight {
-                if let Some(ach_id) = ach.get("strID").and_then(|v| v.as_str()) {
-                    let achieved = ach.get("bAchieved").and_then(|v| v.as_bool()).unwrap_or(false);
-                    let unlock_time = ach.get("rtUnlocked").and_then(|v| v.as_i64()).filter(|&t| t > 0);
-                    unlocked_map.insert(ach_id.to_string(), (achieved, unlock_time));
-                }
-            }
-        }
-
-        // Process vecUnachieved (remaining unachieved achievements)
-        if let Some(vec_unachieved) = achievement_data.get("vecUnachieved").and_then(|v| v.as_array()) {
-            for ach in vec_unachieved {
-                if let Some(ach_id) = ach.get("strID").and_then(|v| v.as_str()) {
-                    unlocked_map.insert(ach_id.to_string(), (false, None));
-                }
-            }
-        }
-
-        // Process vecAchievedHidden (achieved hidden achievements)
-        if let Some(vec_achieved_hidden) = achievement_data.get("vecAchievedHidden").and_then(|v| v.as_array()) {
-            for ach in vec_achieved_hidden {
-                if let Some(ach_id) = ach.get("strID").and_then(|v| v.as_str()) {
-                    let unlock_time = ach.get("rtUnlocked").and_then(|v| v.as_i64()).filter(|&t| t > 0);
-                    let achieved = ach.get("bAchieved").and_then(|v| v.as_bool()).unwrap_or(true); // Default true for vecAchievedHidden
-
-                    // Only insert/update if this achievement is unlocked OR not already in map
-                    if achieved {
-                        unlocked_map.insert(ach_id.to_string(), (true, unlock_time));
-                    } else if !unlocked_map.contains_key(ach_id) {
-                        unlocked_map.insert(ach_id.to_string(), (false, None));
-                    }
-                }
-            }
-        }
+        let unlocked_map: std::collections::HashMap<String, (bool, Option<i64>)> = cache.achievements.into_iter()
+            .map(|(id, cached)| (id, (cached.achieved, cached.unlock_time)))
+            .collect();
 
         // STEP 4: Insert ALL achievements from Steam schema, marking as unlocked based on library cache
         let game_name = game_name.to_string();
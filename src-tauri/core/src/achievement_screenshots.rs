@@ -0,0 +1,111 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::achievements::Achievement;
+
+/// Screenshots are served to the webview as `screenshot://localhost/<id>`
+pub const SCREENSHOT_SCHEME: &str = "screenshot";
+/// A screenshot taken this close to an unlock (before or after) is shown with the achievement
+pub const MATCH_WINDOW_SECS: i64 = 120;
+
+/// A screenshot in the Steam screenshots folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct Screenshot {
+    pub path: PathBuf,
+    pub thumbnail: Option<PathBuf>,
+    pub taken_at: i64,
+}
+
+/// A screenshot matched to an achievement, as stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementScreenshot {
+    pub id: i64,
+    pub app_id: u32,
+    pub achievement_id: String,
+    pub path: String,
+    pub thumbnail_path: Option<String>,
+    pub taken_at: i64,
+    // Filled in by the app: where the webview loads the thumbnail and the full image from
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub full_url: String,
+}
+
+/// Where Steam saves an account's screenshots of a game (F12)
+pub fn screenshots_dir(userdata_dir: &Path, app_id: u32) -> PathBuf {
+    userdata_dir.join("760").join("remote").join(app_id.to_string()).join("screenshots")
+}
+
+/// When a screenshot was taken, from Steam's `YYYYMMDDHHMMSS_N` file name (local time)
+pub fn parse_taken_at(file_stem: &str) -> Option<i64> {
+    let stamp = file_stem.split('_').next()?;
+    let naive = NaiveDateTime::parse_from_str(stamp, "%Y%m%d%H%M%S").ok()?;
+    Local.from_local_datetime(&naive).earliest().map(|time| time.timestamp())
+}
+
+/// Screenshots in a folder, oldest first. Files not named by Steam fall back to their modified time.
+pub fn list_screenshots(dir: &Path) -> Vec<Screenshot> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut screenshots: Vec<Screenshot> = entries
+        .filter_map(|e| e.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file() && path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ["jpg", "jpeg", "png"].iter().any(|e| ext.eq_ignore_ascii_case(e)))
+        })
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            let taken_at = parse_taken_at(&stem).or_else(|| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                modified.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs() as i64)
+            })?;
+            let thumbnail = path.file_name()
+                .map(|name| dir.join("thumbnails").join(name))
+                .filter(|thumbnail| thumbnail.is_file());
+            Some(Screenshot { path, thumbnail, taken_at })
+        })
+        .collect();
+    screenshots.sort_by_key(|s| s.taken_at);
+    screenshots
+}
+
+/// Pair unlocked achievements with the screenshots taken within `window_secs` of their unlock
+pub fn match_unlocks<'a>(achievements: &[Achievement], screenshots: &'a [Screenshot], window_secs: i64) -> Vec<(String, &'a Screenshot)> {
+    let mut matches = Vec::new();
+    for achievement in achievements.iter().filter(|a| a.achieved) {
+        let Some(unlock_time) = achievement.unlock_time.filter(|t| *t > 0) else { continue };
+        for screenshot in screenshots {
+            if (screenshot.taken_at - unlock_time).abs() <= window_secs {
+                matches.push((achievement.achievement_id.clone(), screenshot));
+            }
+        }
+    }
+    matches
+}
+
+/// Address the webview loads a matched screenshot from; `full` skips Steam's thumbnail
+pub fn screenshot_url(id: i64, full: bool) -> String {
+    let path = if full { format!("{}/full", id) } else { id.to_string() };
+    // WebView2 serves custom schemes as http://<scheme>.localhost
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}", SCREENSHOT_SCHEME, path)
+    } else {
+        format!("{}://localhost/{}", SCREENSHOT_SCHEME, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_steam_screenshot_names() {
+        let expected = Local.with_ymd_and_hms(2024, 3, 9, 21, 5, 7).earliest().unwrap().timestamp();
+        assert_eq!(parse_taken_at("20240309210507_1"), Some(expected));
+        assert_eq!(parse_taken_at("Screenshot 2024-03-09"), None);
+    }
+}
@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::achievements::{Achievement, AchievementDatabase};
+use crate::steam_achievements::{SteamAchievementClient, SteamAchievementSchema};
+use crate::app_log;
+
+/// Unlock state of one achievement as read from a save format
+#[derive(Debug, Clone)]
+pub struct ParsedAchievement {
+    pub achievement_id: String, // Steam API name
+    pub achieved: bool,
+    pub unlock_time: Option<i64>,
+}
+
+/// A place achievements are stored (an emulator or launcher format).
+/// Mapping to Steam's schema, rarity and storing is shared, so a source only finds and parses files.
+pub trait AchievementSource: Send + Sync {
+    /// Shown in the UI and stored as the achievement source
+    fn name(&self) -> &str;
+
+    /// Files that may hold the game's achievements, most likely first
+    fn probe_paths(&self, app_id: u32) -> Vec<PathBuf>;
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<ParsedAchievement>, String>;
+}
+
+/// Find the first existing file of a source, parse it and store the results with names from the Steam schema.
+/// Returns the number of unlocked achievements.
+pub async fn scan_source(
+    source: &dyn AchievementSource,
+    app_id: u32,
+    game_name: &str,
+    db_path: PathBuf,
+    steam_client: &SteamAchievementClient,
+) -> Result<usize, String> {
+    let path = source.probe_paths(app_id)
+        .into_iter()
+        .find(|p| p.is_file())
+        .ok_or_else(|| format!("No {} achievements found", source.name()))?;
+    let parsed = source.parse_file(&path)?;
+    if parsed.is_empty() {
+        return Err(format!("No achievements found in {}", path.display()));
+    }
+    app_log!("  Found {} achievements at: {:?}", source.name(), path);
+
+    let schema: HashMap<String, SteamAchievementSchema> = steam_client
+        .get_achievement_schema(app_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| (a.name.clone(), a))
+        .collect();
+    let global_percentages = steam_client.get_global_achievement_percentages(app_id).await.ok();
+
+    let source_name = source.name().to_string();
+    let game_name = game_name.to_string();
+    tokio::task::spawn_blocking(move || {
+        let db = AchievementDatabase::new(db_path)?;
+        let now = chrono::Utc::now().timestamp();
+        let mut unlocked = 0;
+
+        for parsed in parsed {
+            let schema_entry = schema.get(&parsed.achievement_id);
+            let display_name = schema_entry.map_or_else(|| parsed.achievement_id.clone(), |a| a.display_name.clone());
+            db.insert_or_update_achievement(&Achievement {
+                id: None,
+                app_id,
+                game_name: game_name.clone(),
+                global_unlock_percentage: global_percentages.as_ref()
+                    .and_then(|p| p.get(&parsed.achievement_id))
+                    .copied(),
+                achievement_id: parsed.achievement_id,
+                display_name,
+                description: schema_entry.and_then(|a| a.description.clone()).unwrap_or_default(),
+                icon_url: schema_entry.and_then(|a| a.icon.clone()),
+                icon_gray_url: schema_entry.and_then(|a| a.icon_gray.clone()),
+                hidden: schema_entry.is_some_and(|a| a.hidden.unwrap_or(0) != 0),
+                achieved: parsed.achieved,
+                unlock_time: parsed.unlock_time.filter(|_| parsed.achieved),
+                source: source_name.clone(),
+                last_updated: now,
+            })?;
+            if parsed.achieved {
+                unlocked += 1;
+            }
+        }
+        Ok(unlocked)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// Manifest-based plugins: a JSON file describing where a format lives and how to read it
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestFormat {
+    /// { "ACH_ID": { "<achieved_field>": true, "<time_field>": 123 } }
+    JsonObject,
+    /// [ { "<id_field>": "ACH_ID", "<achieved_field>": 1, "<time_field>": 123 } ]
+    JsonArray,
+    /// [ACH_ID] sections with <achieved_field>= and <time_field>= keys
+    Ini,
+}
+
+/// achievement-sources/<name>.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceManifest {
+    pub name: String,
+    /// Candidate files; %VAR% is replaced from the environment and {app_id} with the game's app id
+    pub paths: Vec<String>,
+    pub format: ManifestFormat,
+    #[serde(default = "default_id_field")]
+    pub id_field: String,
+    #[serde(default = "default_achieved_field")]
+    pub achieved_field: String,
+    #[serde(default = "default_time_field")]
+    pub time_field: String,
+}
+
+fn default_id_field() -> String {
+    "name".to_string()
+}
+
+fn default_achieved_field() -> String {
+    "earned".to_string()
+}
+
+fn default_time_field() -> String {
+    "earned_time".to_string()
+}
+
+/// A loaded plugin, or the reason its manifest was rejected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginStatus {
+    pub file: String,
+    pub name: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct ManifestSource {
+    manifest: SourceManifest,
+}
+
+// Built-in source names a plugin can't take over
+const RESERVED_SOURCE_NAMES: [&str; 4] = ["Online-fix", "Steamtools", "Goldberg", "Steam Web API"];
+
+impl ManifestSource {
+    pub fn new(manifest: SourceManifest) -> Result<Self, String> {
+        if manifest.name.trim().is_empty() {
+            return Err("name is empty".to_string());
+        }
+        if RESERVED_SOURCE_NAMES.iter().any(|n| n.eq_ignore_ascii_case(manifest.name.trim())) {
+            return Err(format!("\"{}\" is a built-in source", manifest.name));
+        }
+        if manifest.paths.is_empty() {
+            return Err("no paths to probe".to_string());
+        }
+        if manifest.paths.iter().any(|p| !p.contains("{app_id}")) {
+            return Err("every path must contain {app_id}".to_string());
+        }
+        Ok(Self { manifest })
+    }
+
+    fn expand(template: &str, app_id: u32) -> Option<PathBuf> {
+        let mut expanded = template.replace("{app_id}", &app_id.to_string());
+        while let Some(start) = expanded.find('%') {
+            let end = start + 1 + expanded[start + 1..].find('%')?;
+            let value = std::env::var(&expanded[start + 1..end]).ok()?;
+            expanded.replace_range(start..=end, &value);
+        }
+        Some(PathBuf::from(expanded))
+    }
+
+    fn parse_json(&self, contents: &str) -> Result<Vec<ParsedAchievement>, String> {
+        let value: serde_json::Value = serde_json::from_str(contents)
+            .map_err(|e| format!("Invalid JSON: {}", e))?;
+        let entries: Vec<(String, &serde_json::Value)> = match self.manifest.format {
+            ManifestFormat::JsonObject => value.as_object()
+                .ok_or("Expected a JSON object")?
+                .iter()
+                .map(|(id, entry)| (id.clone(), entry))
+                .collect(),
+            _ => value.as_array()
+                .ok_or("Expected a JSON array")?
+                .iter()
+                .filter_map(|entry| {
+                    let id = entry.get(&self.manifest.id_field).and_then(|v| v.as_str())?;
+                    Some((id.to_string(), entry))
+                })
+                .collect(),
+        };
+
+        Ok(entries.into_iter()
+            .map(|(achievement_id, entry)| ParsedAchievement {
+                achievement_id,
+                achieved: entry.get(&self.manifest.achieved_field).is_some_and(json_truthy),
+                unlock_time: entry.get(&self.manifest.time_field).and_then(json_timestamp),
+            })
+            .collect())
+    }
+
+    fn parse_ini(&self, contents: &str) -> Vec<ParsedAchievement> {
+        let mut parsed: Vec<ParsedAchievement> = Vec::new();
+        for line in contents.lines().map(|l| l.trim()) {
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                parsed.push(ParsedAchievement {
+                    achievement_id: section.trim().to_string(),
+                    achieved: false,
+                    unlock_time: None,
+                });
+            } else if let (Some((key, value)), Some(current)) = (line.split_once('='), parsed.last_mut()) {
+                let (key, value) = (key.trim(), value.trim());
+                if key.eq_ignore_ascii_case(&self.manifest.achieved_field) {
+                    current.achieved = matches!(value.to_lowercase().as_str(), "1" | "true" | "yes");
+                } else if key.eq_ignore_ascii_case(&self.manifest.time_field) {
+                    current.unlock_time = value.parse().ok().filter(|t: &i64| *t > 0);
+                }
+            }
+        }
+        parsed
+    }
+}
+
+fn json_truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        serde_json::Value::String(s) => matches!(s.to_lowercase().as_str(), "1" | "true" | "yes"),
+        _ => false,
+    }
+}
+
+fn json_timestamp(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .filter(|t| *t > 0)
+}
+
+impl AchievementSource for ManifestSource {
+    fn name(&self) -> &str {
+        self.manifest.name.trim()
+    }
+
+    fn probe_paths(&self, app_id: u32) -> Vec<PathBuf> {
+        self.manifest.paths.iter().filter_map(|p| Self::expand(p, app_id)).collect()
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<ParsedAchievement>, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        // Some emulators write UTF-16 or a BOM; lossy UTF-8 is enough for ids and numbers
+        let contents = String::from_utf8_lossy(&bytes);
+        let contents = contents.trim_start_matches('\u{feff}');
+        match self.manifest.format {
+            ManifestFormat::Ini => Ok(self.parse_ini(contents)),
+            _ => self.parse_json(contents),
+        }
+    }
+}
+
+pub fn plugins_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("achievement-sources")
+}
+
+/// Load every manifest in the plugins folder; broken ones are reported instead of stopping the others
+pub fn load_plugins() -> (Vec<Box<dyn AchievementSource>>, Vec<PluginStatus>) {
+    let mut sources: Vec<Box<dyn AchievementSource>> = Vec::new();
+    let mut statuses = Vec::new();
+
+    let Ok(entries) = fs::read_dir(plugins_dir()) else { return (sources, statuses) };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let result = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str::<SourceManifest>(&contents).map_err(|e| e.to_string()))
+            .and_then(ManifestSource::new)
+            .and_then(|source| {
+                if sources.iter().any(|s| s.name().eq_ignore_ascii_case(source.name())) {
+                    Err(format!("another plugin is already called \"{}\"", source.name()))
+                } else {
+                    Ok(source)
+                }
+            });
+
+        match result {
+            Ok(source) => {
+                statuses.push(PluginStatus { file, name: Some(source.name().to_string()), error: None });
+                sources.push(Box::new(source));
+            }
+            Err(e) => {
+                app_log!("[Plugins] Skipping {}: {}", file, e);
+                statuses.push(PluginStatus { file, name: None, error: Some(e) });
+            }
+        }
+    }
+
+    (sources, statuses)
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::achievements::{Achievement, AchievementDatabase};
+use crate::syncthing;
+use crate::app_log;
+
+/// What each machine publishes into the shared sync folder
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncFile {
+    machine: String,
+    exported_at: i64,
+    achievements: Vec<Achievement>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementSyncResult {
+    pub machines: Vec<String>, // other machines found in the folder
+    pub inserted: usize,
+    pub updated: usize,
+    pub exported: usize,
+}
+
+/// Name used for this PC's file in the sync folder
+pub fn machine_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "this-pc".to_string())
+}
+
+/// Merge a row from another machine into the local one; returns the row to store when anything changed.
+/// Unlocks are never lost: an achievement unlocked anywhere stays unlocked with the earliest unlock time.
+/// Descriptive fields come from whichever row was updated most recently; the local source is kept.
+fn merge(local: &Achievement, remote: &Achievement) -> Option<Achievement> {
+    let mut merged = if remote.last_updated > local.last_updated {
+        Achievement { id: local.id, source: local.source.clone(), ..remote.clone() }
+    } else {
+        local.clone()
+    };
+
+    merged.achieved = local.achieved || remote.achieved;
+    merged.unlock_time = [local, remote].iter()
+        .filter(|a| a.achieved)
+        .filter_map(|a| a.unlock_time)
+        .min()
+        .or(if merged.achieved { merged.unlock_time } else { None });
+    merged.last_updated = local.last_updated.max(remote.last_updated);
+
+    let changed = merged.achieved != local.achieved
+        || merged.unlock_time != local.unlock_time
+        || merged.display_name != local.display_name
+        || merged.description != local.description
+        || merged.icon_url != local.icon_url
+        || merged.icon_gray_url != local.icon_gray_url
+        || merged.hidden != local.hidden
+        || merged.global_unlock_percentage != local.global_unlock_percentage;
+
+    changed.then_some(merged)
+}
+
+// Matched by game and achievement only, the other PC may read the same game from a different source
+fn load_local(db: &AchievementDatabase) -> Result<HashMap<(u32, String), Achievement>, String> {
+    let mut local: HashMap<(u32, String), Achievement> = HashMap::new();
+    for achievement in db.get_all_achievements()? {
+        let key = (achievement.app_id, achievement.achievement_id.clone());
+        if !local.get(&key).is_some_and(|existing| existing.last_updated >= achievement.last_updated) {
+            local.insert(key, achievement);
+        }
+    }
+    Ok(local)
+}
+
+fn merge_rows(
+    db: &AchievementDatabase,
+    local: &mut HashMap<(u32, String), Achievement>,
+    remote: Vec<Achievement>,
+) -> Result<(usize, usize), String> {
+    let (mut inserted, mut updated) = (0, 0);
+    for achievement in remote {
+        let key = (achievement.app_id, achievement.achievement_id.clone());
+        let row = match local.get(&key) {
+            Some(existing) => match merge(existing, &achievement) {
+                Some(merged) => {
+                    updated += 1;
+                    merged
+                }
+                None => continue,
+            },
+            None => {
+                inserted += 1;
+                Achievement { id: None, ..achievement }
+            }
+        };
+        db.insert_or_update_achievement(&row)?;
+        local.insert(key, row);
+    }
+    Ok((inserted, updated))
+}
+
+/// Merge achievements received from another machine; returns (inserted, updated)
+pub fn merge_into(db: &AchievementDatabase, remote: Vec<Achievement>) -> Result<(usize, usize), String> {
+    let mut local = load_local(db)?;
+    merge_rows(db, &mut local, remote)
+}
+
+/// Import every other machine's export from `folder`, then publish the merged local state
+pub fn sync_with_folder(db: &AchievementDatabase, folder: &Path) -> Result<AchievementSyncResult, String> {
+    fs::create_dir_all(folder)
+        .map_err(|e| format!("Failed to open sync folder {}: {}", folder.display(), e))?;
+
+    let machine = machine_name();
+    let own_file = format!("achievements-{}.json", machine);
+    let mut result = AchievementSyncResult {
+        machines: Vec::new(),
+        inserted: 0,
+        updated: 0,
+        exported: 0,
+    };
+
+    let mut local = load_local(db)?;
+    let entries = fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read sync folder: {}", e))?;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == own_file || !name.starts_with("achievements-") || !name.ends_with(".json") {
+            continue;
+        }
+
+        // A half-synced or foreign file shouldn't stop the rest of the sync
+        let remote: SyncFile = match fs::read_to_string(entry.path())
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(remote) => remote,
+            Err(e) => {
+                app_log!("[AchievementSync] Skipping {}: {}", name, e);
+                continue;
+            }
+        };
+
+        let (inserted, updated) = merge_rows(db, &mut local, remote.achievements)?;
+        result.inserted += inserted;
+        result.updated += updated;
+        result.machines.push(remote.machine);
+    }
+
+    let export = SyncFile {
+        machine,
+        exported_at: chrono::Utc::now().timestamp(),
+        achievements: local.into_values().collect(),
+    };
+    result.exported = export.achievements.len();
+    let json = serde_json::to_string(&export)
+        .map_err(|e| format!("Failed to serialize achievements: {}", e))?;
+    syncthing::write_atomic(&folder.join(own_file), json.as_bytes())?;
+
+    app_log!(
+        "[AchievementSync] Merged {} machine(s): {} new, {} updated, {} exported",
+        result.machines.len(), result.inserted, result.updated, result.exported
+    );
+    Ok(result)
+}
@@ -0,0 +1,1833 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use rusqlite::{Connection, params};
+use crate::achievement_dlc::DlcInfo;
+use crate::achievement_screenshots::{AchievementScreenshot, Screenshot};
+use crate::backup_freshness::BackupFreshness;
+use crate::steam_categories::CategorySyncResult;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Achievement {
+    pub id: Option<i64>,
+    pub app_id: u32,
+    pub game_name: String,
+    pub achievement_id: String,
+    pub display_name: String,
+    pub description: String,
+    pub icon_url: Option<String>,
+    pub icon_gray_url: Option<String>,
+    pub hidden: bool,
+    pub achieved: bool,
+    pub unlock_time: Option<i64>,
+    pub source: String, // "Steam", "Goldberg", "CODEX", etc.
+    pub last_updated: i64,
+    pub global_unlock_percentage: Option<f32>, // Global unlock percentage from Steam API
+}
+
+/// One achievement combined across every source that reported it. `source` is the one whose state won.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedAchievement {
+    #[serde(flatten)]
+    pub achievement: Achievement,
+    pub sources: Vec<String>,
+}
+
+/// Union a game's achievements across sources. Best unlock state wins: unlocked beats locked,
+/// and among unlocked rows the earliest unlock time is kept.
+pub fn merge_sources(achievements: Vec<Achievement>) -> Vec<MergedAchievement> {
+    let mut merged: Vec<MergedAchievement> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for achievement in achievements {
+        let Some(&i) = index.get(&achievement.achievement_id) else {
+            index.insert(achievement.achievement_id.clone(), merged.len());
+            merged.push(MergedAchievement { sources: vec![achievement.source.clone()], achievement });
+            continue;
+        };
+
+        let entry = &mut merged[i];
+        if !entry.sources.contains(&achievement.source) {
+            entry.sources.push(achievement.source.clone());
+        }
+
+        let current = &entry.achievement;
+        let better = match (achievement.achieved, current.achieved) {
+            (true, false) => true,
+            (true, true) => achievement.unlock_time.unwrap_or(i64::MAX) < current.unlock_time.unwrap_or(i64::MAX),
+            _ => false,
+        };
+        let mut other = achievement;
+        if better {
+            std::mem::swap(&mut entry.achievement, &mut other);
+        }
+
+        // Some sources have no icons or rarity; borrow them from the others
+        let winner = &mut entry.achievement;
+        winner.icon_url = winner.icon_url.take().or(other.icon_url);
+        winner.icon_gray_url = winner.icon_gray_url.take().or(other.icon_gray_url);
+        winner.global_unlock_percentage = winner.global_unlock_percentage.or(other.global_unlock_percentage);
+        winner.last_updated = winner.last_updated.max(other.last_updated);
+    }
+    merged
+}
+
+/// One source's rows for a game that was scanned from more than one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSet {
+    pub source: String,
+    pub total: usize,
+    pub unlocked: usize,
+    pub last_updated: i64,
+    pub missing_unlocks: Vec<String>, // unlocked in another source but not in this one
+}
+
+/// A game whose achievements are stored under several sources, e.g. left over after switching emulators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGame {
+    pub app_id: u32,
+    pub game_name: String,
+    pub sources: Vec<SourceSet>, // most recently updated first
+    pub suggested_source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameAchievementSummary {
+    pub app_id: u32,
+    pub game_name: String,
+    pub total_achievements: i32,
+    pub unlocked_achievements: i32,
+    pub source: String,
+    pub last_updated: i64,
+    pub metadata: Option<GameMetadata>,
+    pub status: GameStatus,
+    pub status_manual: bool, // set by the user rather than derived from progress
+    pub favorite: bool,
+}
+
+/// Order of a game's achievement list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementSort {
+    #[default]
+    Default, // schema order
+    Rarity, // rarest first
+    UnlockDate, // most recent first
+    LockedFirst,
+}
+
+/// Which achievements of a game are listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementFilter {
+    #[default]
+    All,
+    Locked,
+    Unlocked,
+}
+
+/// How the user last left a game's achievement list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementViewPrefs {
+    pub sort_by: AchievementSort,
+    pub filter: AchievementFilter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameStatus {
+    Backlog,
+    Playing,
+    Finished,
+    Completed, // every achievement unlocked
+    Abandoned,
+}
+
+impl GameStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GameStatus::Backlog => "backlog",
+            GameStatus::Playing => "playing",
+            GameStatus::Finished => "finished",
+            GameStatus::Completed => "completed",
+            GameStatus::Abandoned => "abandoned",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "backlog" => Some(GameStatus::Backlog),
+            "playing" => Some(GameStatus::Playing),
+            "finished" => Some(GameStatus::Finished),
+            "completed" => Some(GameStatus::Completed),
+            "abandoned" => Some(GameStatus::Abandoned),
+            _ => None,
+        }
+    }
+
+    /// Status shown for a game: unlocking everything always advances it to completed,
+    /// otherwise a manual status wins over the one derived from progress
+    pub fn effective(manual: Option<GameStatus>, unlocked: i32, total: i32) -> GameStatus {
+        if total > 0 && unlocked >= total {
+            return GameStatus::Completed;
+        }
+        match manual {
+            // A game can't stay completed once new achievements are added
+            Some(GameStatus::Completed) | None if unlocked > 0 => GameStatus::Playing,
+            Some(GameStatus::Completed) | None => GameStatus::Backlog,
+            Some(status) => status,
+        }
+    }
+}
+
+/// User annotations for a tracked game
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameMetadata {
+    pub app_id: u32,
+    pub notes: String,
+    pub custom_cover_path: Option<String>,
+    pub sort_title: Option<String>, // used instead of the game name when ordering the library
+    pub platform_tags: Vec<String>, // "Steam Deck", "VR", "Controller"...
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exclusion {
+    pub id: Option<i64>,
+    pub app_id: u32,
+    pub name: String,
+    pub added_at: i64,
+    pub category: Option<String>, // Steam category it was imported from, None when added by hand
+}
+
+/// An exclusion as written to an exported exclusion list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExclusionEntry {
+    pub app_id: u32,
+    #[serde(default)]
+    pub name: String,
+}
+
+/// An executable that no longer counts as its Steam game running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoredExecutable {
+    pub exe_name: String,
+    pub app_id: u32,
+    pub game_name: String,
+    pub added_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupHistoryEntry {
+    pub id: Option<i64>,
+    pub game_name: String,
+    pub app_id: Option<u32>,
+    pub kind: String, // "backup", "pre-restore", ...
+    pub timestamp: i64,
+    pub success: bool,
+    pub files_count: Option<i64>,
+    pub total_size: Option<String>,
+    pub location: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A Steam game whose appmanifest disappeared since it was last seen installed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UninstalledGame {
+    pub app_id: u32,
+    pub name: String,
+    pub uninstalled_at: i64,
+    pub archived: bool, // its saves got a final backup
+}
+
+/// A finished play session of a Steam game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaySession {
+    pub app_id: u32,
+    pub game_name: String,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub unlocked_count: u32,
+}
+
+/// One entry of the recently-unlocked activity feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockFeedEntry {
+    pub id: Option<i64>,
+    pub app_id: u32,
+    pub game_name: String,
+    pub achievement_id: String,
+    pub display_name: String,
+    pub description: String,
+    pub icon_url: Option<String>,
+    pub unlock_time: i64,
+    pub source: String,
+    pub global_unlock_percentage: Option<f32>,
+}
+
+pub struct AchievementDatabase {
+    conn: Connection,
+}
+
+impl AchievementDatabase {
+    pub fn new(db_path: PathBuf) -> Result<Self, String> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+
+        let db = AchievementDatabase { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    fn init_schema(&self) -> Result<(), String> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievements (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id INTEGER NOT NULL,
+                game_name TEXT NOT NULL,
+                achievement_id TEXT NOT NULL,
+                display_name TEXT NOT NULL,
+                description TEXT,
+                icon_url TEXT,
+                icon_gray_url TEXT,
+                hidden INTEGER DEFAULT 0,
+                achieved INTEGER DEFAULT 0,
+                unlock_time INTEGER,
+                source TEXT NOT NULL,
+                last_updated INTEGER NOT NULL,
+                global_unlock_percentage REAL,
+                UNIQUE(app_id, achievement_id, source)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create achievements table: {}", e))?;
+
+        // Add column if it doesn't exist (for existing databases)
+        let _ = self.conn.execute(
+            "ALTER TABLE achievements ADD COLUMN global_unlock_percentage REAL",
+            [],
+        );
+
+        // Create index for faster queries
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_app_id ON achievements(app_id)",
+            [],
+        ).map_err(|e| format!("Failed to create index: {}", e))?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_achieved ON achievements(achieved)",
+            [],
+        ).map_err(|e| format!("Failed to create index: {}", e))?;
+
+        // Create exclusions table for apps/games to ignore
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS exclusions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id INTEGER NOT NULL UNIQUE,
+                name TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create exclusions table: {}", e))?;
+
+        // Add column if it doesn't exist (for existing databases)
+        let _ = self.conn.execute(
+            "ALTER TABLE exclusions ADD COLUMN category TEXT",
+            [],
+        );
+
+        // Create backup history table (backups, safety snapshots, restores)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS backup_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                game_name TEXT NOT NULL,
+                app_id INTEGER,
+                kind TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                success INTEGER NOT NULL,
+                files_count INTEGER,
+                total_size TEXT,
+                location TEXT,
+                error TEXT
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create backup_history table: {}", e))?;
+
+        // Per-game notes and custom metadata
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_metadata (
+                app_id INTEGER PRIMARY KEY,
+                notes TEXT NOT NULL DEFAULT '',
+                custom_cover_path TEXT,
+                sort_title TEXT,
+                platform_tags TEXT NOT NULL DEFAULT '[]',
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create game_metadata table: {}", e))?;
+
+        // Activity feed of unlocks seen by the watcher
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS unlock_feed (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id INTEGER NOT NULL,
+                game_name TEXT NOT NULL,
+                achievement_id TEXT NOT NULL,
+                display_name TEXT NOT NULL,
+                description TEXT,
+                icon_url TEXT,
+                unlock_time INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                global_unlock_percentage REAL,
+                UNIQUE(app_id, achievement_id)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create unlock_feed table: {}", e))?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_unlock_feed_time ON unlock_feed(unlock_time)",
+            [],
+        ).map_err(|e| format!("Failed to create index: {}", e))?;
+
+        // Start an empty feed with the unlocks already known so it isn't blank until the next unlock
+        let feed_count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM unlock_feed",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(0);
+
+        if feed_count == 0 {
+            let _ = self.conn.execute(
+                "INSERT OR IGNORE INTO unlock_feed (
+                    app_id, game_name, achievement_id, display_name, description, icon_url,
+                    unlock_time, source, global_unlock_percentage
+                )
+                SELECT app_id, game_name, achievement_id, display_name, description, icon_url,
+                       unlock_time, source, global_unlock_percentage
+                FROM achievements
+                WHERE achieved = 1 AND unlock_time IS NOT NULL AND unlock_time > 0",
+                [],
+            );
+        }
+
+        // Completion milestones already announced, so each fires once per game
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS milestones (
+                app_id INTEGER NOT NULL,
+                milestone INTEGER NOT NULL,
+                reached_at INTEGER NOT NULL,
+                PRIMARY KEY (app_id, milestone)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create milestones table: {}", e))?;
+
+        // Starred games, shown on the pinned shelf and synced first
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS favorites (
+                app_id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create favorites table: {}", e))?;
+
+        // Completion status set by the user, games without a row get one derived from progress
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_status (
+                app_id INTEGER PRIMARY KEY,
+                status TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create game_status table: {}", e))?;
+
+        // The user's own labels on achievements ("missable", "grindy"...), used for the suggested completion order
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievement_tags (
+                app_id INTEGER NOT NULL,
+                achievement_id TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '[]',
+                PRIMARY KEY (app_id, achievement_id)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create achievement_tags table: {}", e))?;
+
+        // Steam screenshots taken around an achievement's unlock
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievement_screenshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id INTEGER NOT NULL,
+                achievement_id TEXT NOT NULL,
+                path TEXT NOT NULL,
+                thumbnail_path TEXT,
+                taken_at INTEGER NOT NULL,
+                UNIQUE(app_id, achievement_id, path)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create achievement_screenshots table: {}", e))?;
+
+        // Sort and filter per game, as JSON so new options don't need a migration
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievement_view_prefs (
+                app_id INTEGER PRIMARY KEY,
+                prefs TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create achievement_view_prefs table: {}", e))?;
+
+        // DLCs found on the store and which achievements belong to them; achievements without a row are base game
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_dlc (
+                app_id INTEGER NOT NULL,
+                dlc_app_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (app_id, dlc_app_id)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create game_dlc table: {}", e))?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievement_dlc (
+                app_id INTEGER NOT NULL,
+                achievement_id TEXT NOT NULL,
+                dlc_app_id INTEGER,
+                manual INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (app_id, achievement_id)
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create achievement_dlc table: {}", e))?;
+
+        // Executables of Steam games that shouldn't trigger game detection (launchers, tools)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS ignored_executables (
+                exe_name TEXT PRIMARY KEY COLLATE NOCASE,
+                app_id INTEGER NOT NULL,
+                game_name TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create ignored_executables table: {}", e))?;
+
+        // Steam games seen installed, so an uninstall is noticed even when it happened while the app was closed
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS installed_games (
+                app_id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                last_seen INTEGER NOT NULL,
+                uninstalled_at INTEGER,
+                archived INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create installed_games table: {}", e))?;
+
+        // SteamID64 to scan a game with instead of the global one (family sharing, second accounts)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_steam_ids (
+                app_id INTEGER PRIMARY KEY,
+                steam_id_64 TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create game_steam_ids table: {}", e))?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS play_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_id INTEGER NOT NULL,
+                game_name TEXT NOT NULL,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER NOT NULL,
+                unlocked_count INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        ).map_err(|e| format!("Failed to create play_sessions table: {}", e))?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_backup_history_game ON backup_history(game_name, timestamp)",
+            [],
+        ).map_err(|e| format!("Failed to create index: {}", e))?;
+
+        // Pre-populate with default exclusions if table is empty
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM exclusions",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(0);
+
+        if count == 0 {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+
+            // Add default exclusions
+            let _ = self.conn.execute(
+                "INSERT INTO exclusions (app_id, name, added_at) VALUES (?1, ?2, ?3)",
+                params![388080, "Borderless Gaming", now],
+            );
+            let _ = self.conn.execute(
+                "INSERT INTO exclusions (app_id, name, added_at) VALUES (?1, ?2, ?3)",
+                params![431960, "Wallpaper Engine", now],
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn insert_or_update_achievement(&self, achievement: &Achievement) -> Result<(), String> {
+        self.conn.execute(
+            "INSERT INTO achievements (
+                app_id, game_name, achievement_id, display_name, description,
+                icon_url, icon_gray_url, hidden, achieved, unlock_time, source, last_updated, global_unlock_percentage
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            ON CONFLICT(app_id, achievement_id, source) DO UPDATE SET
+                display_name = excluded.display_name,
+                description = excluded.description,
+                icon_url = excluded.icon_url,
+                icon_gray_url = excluded.icon_gray_url,
+                hidden = excluded.hidden,
+                achieved = excluded.achieved,
+                unlock_time = excluded.unlock_time,
+                last_updated = excluded.last_updated,
+                global_unlock_percentage = excluded.global_unlock_percentage",
+            params![
+                achievement.app_id,
+                achievement.game_name,
+                achievement.achievement_id,
+                achievement.display_name,
+                achievement.description,
+                achievement.icon_url,
+                achievement.icon_gray_url,
+                achievement.hidden as i32,
+                achievement.achieved as i32,
+                achievement.unlock_time,
+                achievement.source,
+                achievement.last_updated,
+                achievement.global_unlock_percentage,
+            ],
+        ).map_err(|e| format!("Failed to insert/update achievement: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn get_game_achievements(&self, app_id: u32) -> Result<Vec<Achievement>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, game_name, achievement_id, display_name, description,
+                    icon_url, icon_gray_url, hidden, achieved, unlock_time, source, last_updated, global_unlock_percentage
+             FROM achievements WHERE app_id = ?1
+             ORDER BY achievement_id"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let achievements = stmt.query_map([app_id], |row| {
+            Ok(Achievement {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                game_name: row.get(2)?,
+                achievement_id: row.get(3)?,
+                display_name: row.get(4)?,
+                description: row.get(5)?,
+                icon_url: row.get(6)?,
+                icon_gray_url: row.get(7)?,
+                hidden: row.get::<_, i32>(8)? != 0,
+                achieved: row.get::<_, i32>(9)? != 0,
+                unlock_time: row.get(10)?,
+                source: row.get(11)?,
+                last_updated: row.get(12)?,
+                global_unlock_percentage: row.get(13)?,
+            })
+        }).map_err(|e| format!("Failed to query achievements: {}", e))?;
+
+        achievements.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect achievements: {}", e))
+    }
+
+    pub fn get_all_achievements(&self) -> Result<Vec<Achievement>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, game_name, achievement_id, display_name, description,
+                    icon_url, icon_gray_url, hidden, achieved, unlock_time, source, last_updated, global_unlock_percentage
+             FROM achievements
+             ORDER BY app_id, achievement_id"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let achievements = stmt.query_map([], |row| {
+            Ok(Achievement {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                game_name: row.get(2)?,
+                achievement_id: row.get(3)?,
+                display_name: row.get(4)?,
+                description: row.get(5)?,
+                icon_url: row.get(6)?,
+                icon_gray_url: row.get(7)?,
+                hidden: row.get::<_, i32>(8)? != 0,
+                achieved: row.get::<_, i32>(9)? != 0,
+                unlock_time: row.get(10)?,
+                source: row.get(11)?,
+                last_updated: row.get(12)?,
+                global_unlock_percentage: row.get(13)?,
+            })
+        }).map_err(|e| format!("Failed to query achievements: {}", e))?;
+
+        achievements.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect achievements: {}", e))
+    }
+
+    /// One entry per game and source
+    pub fn get_all_games(&self) -> Result<Vec<GameAchievementSummary>, String> {
+        self.query_games(
+            "a.game_name, a.source,
+                    COUNT(*) as total,
+                    SUM(CASE WHEN a.achieved = 1 THEN 1 ELSE 0 END) as unlocked",
+            "a.app_id, a.source",
+        )
+    }
+
+    /// One entry per game, each achievement counted once across sources and unlocked if any source has it
+    pub fn get_all_games_merged(&self) -> Result<Vec<GameAchievementSummary>, String> {
+        let mut games = self.query_games(
+            "MIN(a.game_name), GROUP_CONCAT(DISTINCT a.source),
+                    COUNT(DISTINCT a.achievement_id) as total,
+                    COUNT(DISTINCT CASE WHEN a.achieved = 1 THEN a.achievement_id END) as unlocked",
+            "a.app_id",
+        )?;
+        for game in &mut games {
+            game.source = game.source.replace(',', ", ");
+        }
+        Ok(games)
+    }
+
+    fn query_games(&self, columns: &str, group_by: &str) -> Result<Vec<GameAchievementSummary>, String> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.app_id, {},
+                    MAX(a.last_updated) as last_updated,
+                    m.notes, m.custom_cover_path, m.sort_title, m.platform_tags, m.updated_at,
+                    s.status,
+                    f.app_id IS NOT NULL as favorite
+             FROM achievements a
+             LEFT JOIN game_metadata m ON m.app_id = a.app_id
+             LEFT JOIN game_status s ON s.app_id = a.app_id
+             LEFT JOIN favorites f ON f.app_id = a.app_id
+             GROUP BY {}
+             ORDER BY COALESCE(NULLIF(m.sort_title, ''), a.game_name) COLLATE NOCASE",
+            columns, group_by
+        )).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let games = stmt.query_map([], |row| {
+            let app_id: u32 = row.get(0)?;
+            let metadata = match row.get::<_, Option<i64>>(10)? {
+                Some(updated_at) => Some(GameMetadata {
+                    app_id,
+                    notes: row.get(6)?,
+                    custom_cover_path: row.get(7)?,
+                    sort_title: row.get(8)?,
+                    platform_tags: parse_tags(&row.get::<_, String>(9)?),
+                    updated_at,
+                }),
+                None => None,
+            };
+            let total: i32 = row.get(3)?;
+            let unlocked: i32 = row.get(4)?;
+            let manual = row.get::<_, Option<String>>(11)?.and_then(|s| GameStatus::parse(&s));
+            Ok(GameAchievementSummary {
+                app_id,
+                game_name: row.get(1)?,
+                source: row.get(2)?,
+                total_achievements: total,
+                unlocked_achievements: unlocked,
+                last_updated: row.get(5)?,
+                metadata,
+                status: GameStatus::effective(manual, unlocked, total),
+                status_manual: manual.is_some(),
+                favorite: row.get::<_, i32>(12)? != 0,
+            })
+        }).map_err(|e| format!("Failed to query games: {}", e))?;
+
+        games.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect games: {}", e))
+    }
+
+    pub fn export_to_json(&self) -> Result<String, String> {
+        let games = self.get_all_games()?;
+        let mut export_data = Vec::new();
+
+        for game in games {
+            let achievements = self.get_game_achievements(game.app_id)?;
+            export_data.push(serde_json::json!({
+                "game": game,
+                "achievements": achievements
+            }));
+        }
+
+        serde_json::to_string_pretty(&export_data)
+            .map_err(|e| format!("Failed to serialize to JSON: {}", e))
+    }
+
+    pub fn delete_game_achievements(&self, app_id: u32) -> Result<(), String> {
+        self.conn.execute(
+            "DELETE FROM achievements WHERE app_id = ?1",
+            [app_id],
+        ).map_err(|e| format!("Failed to delete achievements: {}", e))?;
+        Ok(())
+    }
+
+    /// Games with achievements under more than one source, and how the sources disagree
+    pub fn find_duplicate_games(&self) -> Result<Vec<DuplicateGame>, String> {
+        let mut by_game: HashMap<u32, Vec<Achievement>> = HashMap::new();
+        for achievement in self.get_all_achievements()? {
+            by_game.entry(achievement.app_id).or_default().push(achievement);
+        }
+
+        let mut duplicates = Vec::new();
+        for (app_id, rows) in by_game {
+            let mut by_source: HashMap<&str, Vec<&Achievement>> = HashMap::new();
+            for row in &rows {
+                by_source.entry(row.source.as_str()).or_default().push(row);
+            }
+            if by_source.len() < 2 {
+                continue;
+            }
+
+            let mut unlocked_anywhere: Vec<&str> = rows.iter()
+                .filter(|a| a.achieved)
+                .map(|a| a.achievement_id.as_str())
+                .collect();
+            unlocked_anywhere.sort_unstable();
+            unlocked_anywhere.dedup();
+
+            let mut sources: Vec<SourceSet> = by_source.iter()
+                .map(|(source, set)| SourceSet {
+                    source: source.to_string(),
+                    total: set.len(),
+                    unlocked: set.iter().filter(|a| a.achieved).count(),
+                    last_updated: set.iter().map(|a| a.last_updated).max().unwrap_or(0),
+                    missing_unlocks: unlocked_anywhere.iter()
+                        .filter(|id| !set.iter().any(|a| a.achieved && a.achievement_id == **id))
+                        .map(|id| id.to_string())
+                        .collect(),
+                })
+                .collect();
+            sources.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+
+            duplicates.push(DuplicateGame {
+                app_id,
+                game_name: rows[0].game_name.clone(),
+                suggested_source: sources[0].source.clone(),
+                sources,
+            });
+        }
+        duplicates.sort_by(|a, b| a.game_name.to_lowercase().cmp(&b.game_name.to_lowercase()));
+        Ok(duplicates)
+    }
+
+    /// Fold every source of a game into `keep_source` (best unlock state wins) and drop the other rows.
+    /// Returns how many rows were removed.
+    pub fn consolidate_game_sources(&self, app_id: u32, keep_source: &str) -> Result<usize, String> {
+        let rows = self.get_game_achievements(app_id)?;
+        let game_name = rows.iter()
+            .find(|a| a.source == keep_source)
+            .map(|a| a.game_name.clone())
+            .ok_or_else(|| format!("No {} achievements for app {}", keep_source, app_id))?;
+
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for merged in merge_sources(rows) {
+            let mut achievement = merged.achievement;
+            achievement.source = keep_source.to_string();
+            achievement.game_name = game_name.clone();
+            self.insert_or_update_achievement(&achievement)?;
+        }
+        let removed = self.conn.execute(
+            "DELETE FROM achievements WHERE app_id = ?1 AND source != ?2",
+            params![app_id, keep_source],
+        ).map_err(|e| format!("Failed to delete redundant achievements: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to commit consolidation: {}", e))?;
+        Ok(removed)
+    }
+
+    /// Drop one source's rows for a game. Returns how many were removed.
+    pub fn delete_game_source(&self, app_id: u32, source: &str) -> Result<usize, String> {
+        self.conn.execute(
+            "DELETE FROM achievements WHERE app_id = ?1 AND source = ?2",
+            params![app_id, source],
+        ).map_err(|e| format!("Failed to delete achievements: {}", e))
+    }
+
+    pub fn update_achievement_status(&self, id: i64, achieved: bool, unlock_time: Option<i64>) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "UPDATE achievements SET achieved = ?1, unlock_time = ?2, last_updated = ?3 WHERE id = ?4",
+            params![achieved as i32, unlock_time, now, id],
+        ).map_err(|e| format!("Failed to update achievement status: {}", e))?;
+
+        Ok(())
+    }
+
+    // Exclusions management
+    pub fn add_exclusion(&self, app_id: u32, name: String) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO exclusions (app_id, name, added_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id) DO UPDATE SET name = excluded.name, category = NULL",
+            params![app_id, name, now],
+        ).map_err(|e| format!("Failed to add exclusion: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Make the category-imported exclusions match `entries` (app id, name, category). Exclusions
+    /// added by hand are left alone, and a game excluded by hand stays that way.
+    pub fn sync_category_exclusions(&self, entries: &[(u32, String, String)]) -> Result<CategorySyncResult, String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let imported: Vec<u32> = {
+            let mut stmt = tx.prepare("SELECT app_id FROM exclusions WHERE category IS NOT NULL")
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+            let rows = stmt.query_map([], |row| row.get(0))
+                .map_err(|e| format!("Failed to query exclusions: {}", e))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to collect exclusions: {}", e))?
+        };
+
+        let mut result = CategorySyncResult::default();
+        for app_id in &imported {
+            if !entries.iter().any(|(id, _, _)| id == app_id) {
+                tx.execute("DELETE FROM exclusions WHERE app_id = ?1 AND category IS NOT NULL", [app_id])
+                    .map_err(|e| format!("Failed to remove exclusion: {}", e))?;
+                result.removed += 1;
+            }
+        }
+
+        for (app_id, name, category) in entries {
+            let changed = tx.execute(
+                "INSERT INTO exclusions (app_id, name, added_at, category) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(app_id) DO UPDATE SET category = excluded.category WHERE exclusions.category IS NOT NULL",
+                params![app_id, name, now, category],
+            ).map_err(|e| format!("Failed to add exclusion: {}", e))?;
+            if changed > 0 && !imported.contains(app_id) {
+                result.added += 1;
+            }
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit exclusions: {}", e))?;
+        Ok(result)
+    }
+
+    /// Exclude several apps by hand in one transaction, returning how many weren't excluded before
+    pub fn add_exclusions(&self, entries: &[ExclusionEntry]) -> Result<usize, String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let mut added = 0;
+        for entry in entries {
+            let existed: bool = tx.query_row(
+                "SELECT COUNT(*) FROM exclusions WHERE app_id = ?1",
+                [entry.app_id],
+                |row| row.get::<_, i64>(0),
+            ).map_err(|e| format!("Failed to check exclusion: {}", e))? > 0;
+
+            tx.execute(
+                "INSERT INTO exclusions (app_id, name, added_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(app_id) DO UPDATE SET name = excluded.name, category = NULL",
+                params![entry.app_id, entry.name, now],
+            ).map_err(|e| format!("Failed to add exclusion: {}", e))?;
+            if !existed {
+                added += 1;
+            }
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit exclusions: {}", e))?;
+        Ok(added)
+    }
+
+    /// Remove several exclusions in one transaction, returning how many were removed
+    pub fn remove_exclusions(&self, app_ids: &[u32]) -> Result<usize, String> {
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let mut removed = 0;
+        for app_id in app_ids {
+            removed += tx.execute("DELETE FROM exclusions WHERE app_id = ?1", [app_id])
+                .map_err(|e| format!("Failed to remove exclusion: {}", e))?;
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit exclusions: {}", e))?;
+        Ok(removed)
+    }
+
+    pub fn remove_exclusion(&self, app_id: u32) -> Result<(), String> {
+        self.conn.execute(
+            "DELETE FROM exclusions WHERE app_id = ?1",
+            [app_id],
+        ).map_err(|e| format!("Failed to remove exclusion: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_all_exclusions(&self) -> Result<Vec<Exclusion>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, name, added_at, category FROM exclusions ORDER BY name"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let exclusions = stmt.query_map([], |row| {
+            Ok(Exclusion {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                name: row.get(2)?,
+                added_at: row.get(3)?,
+                category: row.get(4)?,
+            })
+        }).map_err(|e| format!("Failed to query exclusions: {}", e))?;
+
+        exclusions.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect exclusions: {}", e))
+    }
+
+    pub fn is_excluded(&self, app_id: u32) -> Result<bool, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COUNT(*) FROM exclusions WHERE app_id = ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let count: i64 = stmt.query_row([app_id], |row| row.get(0))
+            .map_err(|e| format!("Failed to check exclusion: {}", e))?;
+
+        Ok(count > 0)
+    }
+
+    // Ignored executables
+    pub fn ignore_executable(&self, exe_name: &str, app_id: u32, game_name: &str) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO ignored_executables (exe_name, app_id, game_name, added_at) VALUES (?1, ?2, ?3, ?4)",
+            params![exe_name, app_id, game_name, now],
+        ).map_err(|e| format!("Failed to ignore executable: {}", e))?;
+        Ok(())
+    }
+
+    pub fn unignore_executable(&self, exe_name: &str) -> Result<(), String> {
+        self.conn.execute(
+            "DELETE FROM ignored_executables WHERE exe_name = ?1",
+            [exe_name],
+        ).map_err(|e| format!("Failed to remove ignored executable: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_ignored_executables(&self) -> Result<Vec<IgnoredExecutable>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT exe_name, app_id, game_name, added_at FROM ignored_executables ORDER BY game_name, exe_name"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let ignored = stmt.query_map([], |row| {
+            Ok(IgnoredExecutable {
+                exe_name: row.get(0)?,
+                app_id: row.get(1)?,
+                game_name: row.get(2)?,
+                added_at: row.get(3)?,
+            })
+        }).map_err(|e| format!("Failed to query ignored executables: {}", e))?;
+
+        ignored.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect ignored executables: {}", e))
+    }
+
+    pub fn is_executable_ignored(&self, exe_name: &str) -> Result<bool, String> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM ignored_executables WHERE exe_name = ?1",
+            [exe_name],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to check ignored executable: {}", e))?;
+
+        Ok(count > 0)
+    }
+
+    // Backup history
+    pub fn add_backup_history(&self, entry: &BackupHistoryEntry) -> Result<i64, String> {
+        self.conn.execute(
+            "INSERT INTO backup_history (
+                game_name, app_id, kind, timestamp, success, files_count, total_size, location, error
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.game_name,
+                entry.app_id,
+                entry.kind,
+                entry.timestamp,
+                entry.success as i32,
+                entry.files_count,
+                entry.total_size,
+                entry.location,
+                entry.error,
+            ],
+        ).map_err(|e| format!("Failed to add backup history: {}", e))?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// History of one game, newest first. Entries recorded without an app id are matched by the game's name.
+    pub fn get_backup_history(&self, app_id: u32, limit: u32) -> Result<Vec<BackupHistoryEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, game_name, app_id, kind, timestamp, success, files_count, total_size, location, error
+             FROM backup_history
+             WHERE app_id = ?1
+                OR (app_id IS NULL AND game_name IN (
+                    SELECT game_name FROM backup_history WHERE app_id = ?1
+                    UNION SELECT game_name FROM achievements WHERE app_id = ?1
+                ))
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?2"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map(params![app_id, limit], Self::backup_history_row)
+            .map_err(|e| format!("Failed to query backup history: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read backup history: {}", e))
+    }
+
+    /// Latest history entries of every game, newest first
+    pub fn get_recent_backup_history(&self, limit: u32) -> Result<Vec<BackupHistoryEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, game_name, app_id, kind, timestamp, success, files_count, total_size, location, error
+             FROM backup_history
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map([limit], Self::backup_history_row)
+            .map_err(|e| format!("Failed to query backup history: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read backup history: {}", e))
+    }
+
+    /// Backup totals per game; `since` is the start of the window `recent_attempts` counts in
+    pub fn get_backup_freshness(&self, since: i64) -> Result<Vec<BackupFreshness>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT game_name, MAX(app_id), SUM(success), SUM(1 - success), MAX(timestamp),
+                    MAX(CASE WHEN success = 1 THEN timestamp END),
+                    SUM(CASE WHEN timestamp >= ?1 THEN 1 ELSE 0 END)
+             FROM backup_history
+             WHERE kind = 'backup'
+             GROUP BY game_name
+             ORDER BY game_name"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map([since], |row| {
+            Ok(BackupFreshness {
+                game_name: row.get(0)?,
+                app_id: row.get(1)?,
+                successful_backups: row.get(2)?,
+                failed_backups: row.get(3)?,
+                last_attempt: row.get(4)?,
+                last_success: row.get(5)?,
+                recent_attempts: row.get(6)?,
+                stale: false,
+            })
+        }).map_err(|e| format!("Failed to query backup freshness: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read backup freshness: {}", e))
+    }
+
+    fn backup_history_row(row: &rusqlite::Row) -> rusqlite::Result<BackupHistoryEntry> {
+        Ok(BackupHistoryEntry {
+            id: row.get(0)?,
+            game_name: row.get(1)?,
+            app_id: row.get(2)?,
+            kind: row.get(3)?,
+            timestamp: row.get(4)?,
+            success: row.get::<_, i32>(5)? != 0,
+            files_count: row.get(6)?,
+            total_size: row.get(7)?,
+            location: row.get(8)?,
+            error: row.get(9)?,
+        })
+    }
+
+    /// History entries recorded in `[since, until)`, oldest first
+    pub fn get_backup_history_between(&self, since: i64, until: i64) -> Result<Vec<BackupHistoryEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, game_name, app_id, kind, timestamp, success, files_count, total_size, location, error
+             FROM backup_history
+             WHERE timestamp >= ?1 AND timestamp < ?2
+             ORDER BY timestamp, id"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map(params![since, until], Self::backup_history_row)
+            .map_err(|e| format!("Failed to query backup history: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read backup history: {}", e))
+    }
+
+    /// Most recent successful upload per game and destination (location is "<destination id>|<remote id>")
+    pub fn get_latest_uploads(&self) -> Result<Vec<BackupHistoryEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, game_name, app_id, kind, timestamp, success, files_count, total_size, location, error
+             FROM backup_history
+             WHERE id IN (
+                 SELECT MAX(id) FROM backup_history
+                 WHERE kind = 'upload' AND success = 1 AND location IS NOT NULL
+                 GROUP BY game_name, substr(location, 1, instr(location, '|') - 1)
+             )
+             ORDER BY game_name"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map([], Self::backup_history_row)
+            .map_err(|e| format!("Failed to query uploads: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read uploads: {}", e))
+    }
+
+    // Game metadata
+    pub fn get_game_metadata(&self, app_id: u32) -> Result<Option<GameMetadata>, String> {
+        let result = self.conn.query_row(
+            "SELECT app_id, notes, custom_cover_path, sort_title, platform_tags, updated_at
+             FROM game_metadata WHERE app_id = ?1",
+            [app_id],
+            |row| Ok(GameMetadata {
+                app_id: row.get(0)?,
+                notes: row.get(1)?,
+                custom_cover_path: row.get(2)?,
+                sort_title: row.get(3)?,
+                platform_tags: parse_tags(&row.get::<_, String>(4)?),
+                updated_at: row.get(5)?,
+            }),
+        );
+
+        match result {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Failed to read game metadata: {}", e)),
+        }
+    }
+
+    pub fn set_game_metadata(&self, metadata: &GameMetadata) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let tags = serde_json::to_string(&metadata.platform_tags)
+            .map_err(|e| format!("Failed to serialize tags: {}", e))?;
+
+        self.conn.execute(
+            "INSERT INTO game_metadata (app_id, notes, custom_cover_path, sort_title, platform_tags, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(app_id) DO UPDATE SET
+                notes = excluded.notes,
+                custom_cover_path = excluded.custom_cover_path,
+                sort_title = excluded.sort_title,
+                platform_tags = excluded.platform_tags,
+                updated_at = excluded.updated_at",
+            params![metadata.app_id, metadata.notes, metadata.custom_cover_path, metadata.sort_title, tags, now],
+        ).map_err(|e| format!("Failed to save game metadata: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn delete_game_metadata(&self, app_id: u32) -> Result<(), String> {
+        self.conn.execute(
+            "DELETE FROM game_metadata WHERE app_id = ?1",
+            [app_id],
+        ).map_err(|e| format!("Failed to delete game metadata: {}", e))?;
+        Ok(())
+    }
+
+    // Completion status
+    pub fn set_game_status(&self, app_id: u32, status: GameStatus) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO game_status (app_id, status, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id) DO UPDATE SET status = excluded.status, updated_at = excluded.updated_at",
+            params![app_id, status.as_str(), now],
+        ).map_err(|e| format!("Failed to save game status: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Go back to the status derived from achievement progress
+    pub fn clear_game_status(&self, app_id: u32) -> Result<(), String> {
+        self.conn.execute(
+            "DELETE FROM game_status WHERE app_id = ?1",
+            [app_id],
+        ).map_err(|e| format!("Failed to clear game status: {}", e))?;
+        Ok(())
+    }
+
+    // Installed games
+    /// Record the games installed right now and return the ones that were installed last time but aren't anymore.
+    /// A reinstalled game is tracked again from scratch. Games in a library that can't be reached are left as they are.
+    pub fn record_installed_games(&self, installed: &[(u32, String)], unreachable: &[u32]) -> Result<Vec<(u32, String)>, String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, name FROM installed_games WHERE uninstalled_at IS NULL"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let known = stmt.query_map([], |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to query installed games: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read installed games: {}", e))?;
+
+        for (app_id, name) in installed {
+            self.conn.execute(
+                "INSERT INTO installed_games (app_id, name, last_seen) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(app_id) DO UPDATE SET name = excluded.name, last_seen = excluded.last_seen,
+                    uninstalled_at = NULL, archived = 0",
+                params![app_id, name, now],
+            ).map_err(|e| format!("Failed to save installed game: {}", e))?;
+        }
+
+        let gone: Vec<(u32, String)> = known.into_iter()
+            .filter(|(app_id, _)| !installed.iter().any(|(id, _)| id == app_id) && !unreachable.contains(app_id))
+            .collect();
+        for (app_id, _) in &gone {
+            self.conn.execute(
+                "UPDATE installed_games SET uninstalled_at = ?2 WHERE app_id = ?1",
+                params![app_id, now],
+            ).map_err(|e| format!("Failed to mark game uninstalled: {}", e))?;
+        }
+
+        Ok(gone)
+    }
+
+    pub fn mark_game_archived(&self, app_id: u32) -> Result<(), String> {
+        self.conn.execute(
+            "UPDATE installed_games SET archived = 1 WHERE app_id = ?1",
+            [app_id],
+        ).map_err(|e| format!("Failed to mark game archived: {}", e))?;
+        Ok(())
+    }
+
+    /// Uninstalled games, most recently uninstalled first
+    pub fn get_uninstalled_games(&self) -> Result<Vec<UninstalledGame>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, name, uninstalled_at, archived FROM installed_games
+             WHERE uninstalled_at IS NOT NULL
+             ORDER BY uninstalled_at DESC"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let games = stmt.query_map([], |row| Ok(UninstalledGame {
+            app_id: row.get(0)?,
+            name: row.get(1)?,
+            uninstalled_at: row.get(2)?,
+            archived: row.get::<_, i64>(3)? != 0,
+        })).map_err(|e| format!("Failed to query uninstalled games: {}", e))?;
+
+        games.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect uninstalled games: {}", e))
+    }
+
+    // Per-game SteamID overrides
+    pub fn set_steam_id_override(&self, app_id: u32, steam_id_64: Option<&str>) -> Result<(), String> {
+        let Some(steam_id_64) = steam_id_64 else {
+            self.conn.execute(
+                "DELETE FROM game_steam_ids WHERE app_id = ?1",
+                [app_id],
+            ).map_err(|e| format!("Failed to clear SteamID override: {}", e))?;
+            return Ok(());
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO game_steam_ids (app_id, steam_id_64, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id) DO UPDATE SET steam_id_64 = excluded.steam_id_64, updated_at = excluded.updated_at",
+            params![app_id, steam_id_64, now],
+        ).map_err(|e| format!("Failed to save SteamID override: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn get_steam_id_overrides(&self) -> Result<HashMap<u32, String>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, steam_id_64 FROM game_steam_ids"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let overrides = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query SteamID overrides: {}", e))?;
+
+        overrides.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| format!("Failed to collect SteamID overrides: {}", e))
+    }
+
+    // Play sessions
+    pub fn add_play_session(&self, session: &PlaySession) -> Result<(), String> {
+        self.conn.execute(
+            "INSERT INTO play_sessions (app_id, game_name, started_at, ended_at, unlocked_count)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![session.app_id, session.game_name, session.started_at, session.ended_at, session.unlocked_count],
+        ).map_err(|e| format!("Failed to add play session: {}", e))?;
+        Ok(())
+    }
+
+    /// Sessions that started in `[since, until)`, oldest first
+    pub fn get_play_sessions(&self, since: i64, until: i64) -> Result<Vec<PlaySession>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, game_name, started_at, ended_at, unlocked_count
+             FROM play_sessions
+             WHERE started_at >= ?1 AND started_at < ?2
+             ORDER BY started_at"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let sessions = stmt.query_map(params![since, until], |row| {
+            Ok(PlaySession {
+                app_id: row.get(0)?,
+                game_name: row.get(1)?,
+                started_at: row.get(2)?,
+                ended_at: row.get(3)?,
+                unlocked_count: row.get(4)?,
+            })
+        }).map_err(|e| format!("Failed to query play sessions: {}", e))?;
+
+        sessions.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect play sessions: {}", e))
+    }
+
+    // Favorites
+    pub fn set_favorite(&self, app_id: u32, name: &str, favorite: bool) -> Result<(), String> {
+        if !favorite {
+            self.conn.execute(
+                "DELETE FROM favorites WHERE app_id = ?1",
+                [app_id],
+            ).map_err(|e| format!("Failed to remove favorite: {}", e))?;
+            return Ok(());
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT INTO favorites (app_id, name, added_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id) DO UPDATE SET name = excluded.name",
+            params![app_id, name, now],
+        ).map_err(|e| format!("Failed to add favorite: {}", e))?;
+
+        Ok(())
+    }
+
+    /// (app_id, name) of every favorite, oldest first
+    pub fn get_favorites(&self) -> Result<Vec<(u32, String)>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, name FROM favorites ORDER BY added_at"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let favorites = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query favorites: {}", e))?;
+
+        favorites.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect favorites: {}", e))
+    }
+
+    // DLC grouping
+
+    /// Store the DLCs detected for a game, replacing earlier detections but keeping the user's corrections
+    pub fn save_game_dlc(&self, app_id: u32, dlcs: &[DlcInfo], assignments: &HashMap<String, u32>) -> Result<(), String> {
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        self.conn.execute("DELETE FROM game_dlc WHERE app_id = ?1", [app_id])
+            .map_err(|e| format!("Failed to clear DLCs: {}", e))?;
+        for dlc in dlcs {
+            self.conn.execute(
+                "INSERT INTO game_dlc (app_id, dlc_app_id, name) VALUES (?1, ?2, ?3)",
+                params![app_id, dlc.dlc_app_id, dlc.name],
+            ).map_err(|e| format!("Failed to save DLC: {}", e))?;
+        }
+
+        self.conn.execute("DELETE FROM achievement_dlc WHERE app_id = ?1 AND manual = 0", [app_id])
+            .map_err(|e| format!("Failed to clear DLC achievements: {}", e))?;
+        for (achievement_id, dlc_app_id) in assignments {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO achievement_dlc (app_id, achievement_id, dlc_app_id, manual) VALUES (?1, ?2, ?3, 0)",
+                params![app_id, achievement_id, dlc_app_id],
+            ).map_err(|e| format!("Failed to save DLC achievement: {}", e))?;
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit DLCs: {}", e))
+    }
+
+    pub fn get_game_dlc(&self, app_id: u32) -> Result<Vec<DlcInfo>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dlc_app_id, name FROM game_dlc WHERE app_id = ?1 ORDER BY dlc_app_id"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let dlcs = stmt.query_map([app_id], |row| Ok(DlcInfo { dlc_app_id: row.get(0)?, name: row.get(1)? }))
+            .map_err(|e| format!("Failed to query DLCs: {}", e))?;
+
+        dlcs.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect DLCs: {}", e))
+    }
+
+    /// achievement_id -> DLC app id, or None where the user marked a DLC-looking achievement as base game
+    pub fn get_achievement_dlc(&self, app_id: u32) -> Result<HashMap<String, Option<u32>>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT achievement_id, dlc_app_id FROM achievement_dlc WHERE app_id = ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let rows = stmt.query_map([app_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query DLC achievements: {}", e))?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| format!("Failed to collect DLC achievements: {}", e))
+    }
+
+    /// Correct the DLC of one achievement; None moves it to the base game. Survives re-detection.
+    pub fn set_achievement_dlc(&self, app_id: u32, achievement_id: &str, dlc_app_id: Option<u32>) -> Result<(), String> {
+        self.conn.execute(
+            "INSERT INTO achievement_dlc (app_id, achievement_id, dlc_app_id, manual) VALUES (?1, ?2, ?3, 1)
+             ON CONFLICT(app_id, achievement_id) DO UPDATE SET dlc_app_id = excluded.dlc_app_id, manual = 1",
+            params![app_id, achievement_id, dlc_app_id],
+        ).map_err(|e| format!("Failed to set DLC: {}", e))?;
+        Ok(())
+    }
+
+    // Achievement tags
+
+    /// achievement_id -> tags for a game's tagged achievements
+    pub fn get_achievement_tags(&self, app_id: u32) -> Result<HashMap<String, Vec<String>>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT achievement_id, tags FROM achievement_tags WHERE app_id = ?1"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let rows = stmt.query_map([app_id], |row| Ok((row.get(0)?, parse_tags(&row.get::<_, String>(1)?))))
+            .map_err(|e| format!("Failed to query achievement tags: {}", e))?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| format!("Failed to collect achievement tags: {}", e))
+    }
+
+    /// Replace an achievement's tags; an empty list removes them
+    pub fn set_achievement_tags(&self, app_id: u32, achievement_id: &str, tags: &[String]) -> Result<(), String> {
+        if tags.is_empty() {
+            self.conn.execute(
+                "DELETE FROM achievement_tags WHERE app_id = ?1 AND achievement_id = ?2",
+                params![app_id, achievement_id],
+            ).map_err(|e| format!("Failed to clear achievement tags: {}", e))?;
+            return Ok(());
+        }
+
+        let json = serde_json::to_string(tags)
+            .map_err(|e| format!("Failed to serialize tags: {}", e))?;
+        self.conn.execute(
+            "INSERT INTO achievement_tags (app_id, achievement_id, tags) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id, achievement_id) DO UPDATE SET tags = excluded.tags",
+            params![app_id, achievement_id, json],
+        ).map_err(|e| format!("Failed to save achievement tags: {}", e))?;
+        Ok(())
+    }
+
+    /// Replace a game's matched screenshots with a fresh scan; returns how many were stored
+    pub fn replace_achievement_screenshots(&self, app_id: u32, matches: &[(String, &Screenshot)]) -> Result<usize, String> {
+        let tx = self.conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        tx.execute("DELETE FROM achievement_screenshots WHERE app_id = ?1", [app_id])
+            .map_err(|e| format!("Failed to clear screenshots: {}", e))?;
+        for (achievement_id, screenshot) in matches {
+            tx.execute(
+                "INSERT OR IGNORE INTO achievement_screenshots (app_id, achievement_id, path, thumbnail_path, taken_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    app_id,
+                    achievement_id,
+                    screenshot.path.to_string_lossy(),
+                    screenshot.thumbnail.as_ref().map(|p| p.to_string_lossy().to_string()),
+                    screenshot.taken_at,
+                ],
+            ).map_err(|e| format!("Failed to save screenshot: {}", e))?;
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit screenshots: {}", e))?;
+        Ok(matches.len())
+    }
+
+    pub fn get_achievement_screenshots(&self, app_id: u32) -> Result<Vec<AchievementScreenshot>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, achievement_id, path, thumbnail_path, taken_at
+             FROM achievement_screenshots WHERE app_id = ?1 ORDER BY taken_at"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let screenshots = stmt.query_map([app_id], Self::row_to_screenshot)
+            .map_err(|e| format!("Failed to query screenshots: {}", e))?;
+
+        screenshots.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect screenshots: {}", e))
+    }
+
+    pub fn get_achievement_screenshot(&self, id: i64) -> Result<Option<AchievementScreenshot>, String> {
+        let result = self.conn.query_row(
+            "SELECT id, app_id, achievement_id, path, thumbnail_path, taken_at
+             FROM achievement_screenshots WHERE id = ?1",
+            [id],
+            Self::row_to_screenshot,
+        );
+
+        match result {
+            Ok(screenshot) => Ok(Some(screenshot)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Failed to load screenshot: {}", e)),
+        }
+    }
+
+    fn row_to_screenshot(row: &rusqlite::Row) -> rusqlite::Result<AchievementScreenshot> {
+        Ok(AchievementScreenshot {
+            id: row.get(0)?,
+            app_id: row.get(1)?,
+            achievement_id: row.get(2)?,
+            path: row.get(3)?,
+            thumbnail_path: row.get(4)?,
+            taken_at: row.get(5)?,
+            url: String::new(),
+            full_url: String::new(),
+        })
+    }
+
+    /// A game's saved sort and filter, or the defaults if it has none
+    pub fn get_achievement_view_prefs(&self, app_id: u32) -> Result<AchievementViewPrefs, String> {
+        let result = self.conn.query_row(
+            "SELECT prefs FROM achievement_view_prefs WHERE app_id = ?1",
+            [app_id],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(json) => Ok(serde_json::from_str(&json).unwrap_or_default()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(AchievementViewPrefs::default()),
+            Err(e) => Err(format!("Failed to load view preferences: {}", e)),
+        }
+    }
+
+    pub fn set_achievement_view_prefs(&self, app_id: u32, prefs: &AchievementViewPrefs) -> Result<(), String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let json = serde_json::to_string(prefs)
+            .map_err(|e| format!("Failed to serialize view preferences: {}", e))?;
+
+        self.conn.execute(
+            "INSERT INTO achievement_view_prefs (app_id, prefs, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(app_id) DO UPDATE SET prefs = excluded.prefs, updated_at = excluded.updated_at",
+            params![app_id, json, now],
+        ).map_err(|e| format!("Failed to save view preferences: {}", e))?;
+        Ok(())
+    }
+
+    // Activity feed
+    pub fn add_unlock_to_feed(&self, entry: &UnlockFeedEntry) -> Result<(), String> {
+        self.conn.execute(
+            "INSERT INTO unlock_feed (
+                app_id, game_name, achievement_id, display_name, description, icon_url,
+                unlock_time, source, global_unlock_percentage
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(app_id, achievement_id) DO UPDATE SET
+                display_name = excluded.display_name,
+                description = excluded.description,
+                icon_url = excluded.icon_url,
+                unlock_time = excluded.unlock_time,
+                source = excluded.source,
+                global_unlock_percentage = excluded.global_unlock_percentage",
+            params![
+                entry.app_id,
+                entry.game_name,
+                entry.achievement_id,
+                entry.display_name,
+                entry.description,
+                entry.icon_url,
+                entry.unlock_time,
+                entry.source,
+                entry.global_unlock_percentage,
+            ],
+        ).map_err(|e| format!("Failed to add unlock to feed: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Achievements of every game unlocked in `[since, until)`, oldest first. An achievement several sources
+    /// report is listed once, with its earliest unlock time.
+    pub fn get_all_unlocks_between(&self, since: i64, until: i64) -> Result<Vec<UnlockFeedEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app_id, game_name, achievement_id, display_name, description, icon_url,
+                    MIN(unlock_time), source, global_unlock_percentage
+             FROM achievements
+             WHERE achieved = 1 AND unlock_time >= ?1 AND unlock_time < ?2
+             GROUP BY app_id, achievement_id
+             ORDER BY MIN(unlock_time)"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map(params![since, until], |row| {
+            Ok(UnlockFeedEntry {
+                id: None,
+                app_id: row.get(0)?,
+                game_name: row.get(1)?,
+                achievement_id: row.get(2)?,
+                display_name: row.get(3)?,
+                description: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                icon_url: row.get(5)?,
+                unlock_time: row.get(6)?,
+                source: row.get(7)?,
+                global_unlock_percentage: row.get(8)?,
+            })
+        }).map_err(|e| format!("Failed to query unlocks: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect unlocks: {}", e))
+    }
+
+    /// Every distinct icon URL of the tracked achievements, unlocked and locked versions
+    pub fn get_icon_urls(&self) -> Result<Vec<String>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT icon_url FROM achievements WHERE icon_url LIKE 'http%'
+             UNION
+             SELECT icon_gray_url FROM achievements WHERE icon_gray_url LIKE 'http%'"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let urls = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| format!("Failed to query icon URLs: {}", e))?;
+
+        urls.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect icon URLs: {}", e))
+    }
+
+    /// Newest unlocks across all games, one page at a time
+    pub fn get_recent_unlocks(&self, limit: u32, offset: u32) -> Result<Vec<UnlockFeedEntry>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, game_name, achievement_id, display_name, description, icon_url,
+                    unlock_time, source, global_unlock_percentage
+             FROM unlock_feed
+             ORDER BY unlock_time DESC, id DESC
+             LIMIT ?1 OFFSET ?2"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let entries = stmt.query_map(params![limit, offset], |row| {
+            Ok(UnlockFeedEntry {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                game_name: row.get(2)?,
+                achievement_id: row.get(3)?,
+                display_name: row.get(4)?,
+                description: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                icon_url: row.get(6)?,
+                unlock_time: row.get(7)?,
+                source: row.get(8)?,
+                global_unlock_percentage: row.get(9)?,
+            })
+        }).map_err(|e| format!("Failed to query unlock feed: {}", e))?;
+
+        entries.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect unlock feed: {}", e))
+    }
+
+    /// Unlock time of every unlocked achievement, counted once even when several sources report it
+    pub fn get_unlock_times(&self) -> Result<Vec<i64>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT MIN(unlock_time) FROM achievements
+             WHERE achieved = 1 AND unlock_time IS NOT NULL AND unlock_time > 0
+             GROUP BY app_id, achievement_id"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let times = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| format!("Failed to query unlock times: {}", e))?;
+
+        times.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect unlock times: {}", e))
+    }
+
+    /// Achievements of a game unlocked in [from, to], one row per achievement even with several sources
+    pub fn get_unlocks_between(&self, app_id: u32, from: i64, to: i64) -> Result<Vec<Achievement>, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, app_id, game_name, achievement_id, display_name, description,
+                    icon_url, icon_gray_url, hidden, achieved, MIN(unlock_time), source, last_updated,
+                    global_unlock_percentage
+             FROM achievements
+             WHERE app_id = ?1 AND achieved = 1 AND unlock_time BETWEEN ?2 AND ?3
+             GROUP BY achievement_id
+             ORDER BY MIN(unlock_time)"
+        ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let achievements = stmt.query_map(params![app_id, from, to], |row| {
+            Ok(Achievement {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                game_name: row.get(2)?,
+                achievement_id: row.get(3)?,
+                display_name: row.get(4)?,
+                description: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                icon_url: row.get(6)?,
+                icon_gray_url: row.get(7)?,
+                hidden: row.get::<_, i32>(8)? != 0,
+                achieved: row.get::<_, i32>(9)? != 0,
+                unlock_time: row.get(10)?,
+                source: row.get(11)?,
+                last_updated: row.get(12)?,
+                global_unlock_percentage: row.get(13)?,
+            })
+        }).map_err(|e| format!("Failed to query session unlocks: {}", e))?;
+
+        achievements.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to collect session unlocks: {}", e))
+    }
+
+    /// (unlocked, total) for a game, counting each achievement once across sources
+    pub fn get_game_progress(&self, app_id: u32) -> Result<(u32, u32), String> {
+        self.conn.query_row(
+            "SELECT COUNT(DISTINCT CASE WHEN achieved = 1 THEN achievement_id END), COUNT(DISTINCT achievement_id)
+             FROM achievements WHERE app_id = ?1",
+            [app_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(|e| format!("Failed to read game progress: {}", e))
+    }
+
+    /// Remember that a game reached a milestone; false if it was already recorded
+    pub fn record_milestone(&self, app_id: u32, milestone: u32) -> Result<bool, String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO milestones (app_id, milestone, reached_at) VALUES (?1, ?2, ?3)",
+            params![app_id, milestone, now],
+        ).map_err(|e| format!("Failed to record milestone: {}", e))?;
+
+        Ok(inserted > 0)
+    }
+}
+
+fn parse_tags(json: &str) -> Vec<String> {
+    serde_json::from_str(json).unwrap_or_default()
+}
@@ -0,0 +1,183 @@
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use crate::achievement_export::csv_field;
+use crate::achievements::AchievementDatabase;
+
+/// File formats the activity timeline can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl TimelineFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TimelineFormat::Json => "json",
+            TimelineFormat::Csv => "csv",
+        }
+    }
+}
+
+/// One thing that happened: a play session, a backup history entry or an unlock
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEvent {
+    pub timestamp: i64,
+    pub kind: String, // "session", "unlock", or the history kind ("backup", "restore", "upload", ...)
+    pub game_name: String,
+    pub app_id: Option<u32>,
+    pub success: Option<bool>, // history entries only
+    pub duration_secs: Option<i64>, // sessions only
+    pub detail: String,
+}
+
+/// First day of a "YYYY-MM" month and of the month after it
+pub fn month_bounds(month: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let invalid = || format!("Invalid month '{}', expected YYYY-MM", month);
+    let start = NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d").map_err(|_| invalid())?;
+    let end = if start.month() == 12 {
+        NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+    };
+    Ok((start, end.ok_or_else(invalid)?))
+}
+
+/// `[since, until)` in unix seconds for a month in local time
+pub fn month_range(month: &str) -> Result<(i64, i64), String> {
+    let (start, end) = month_bounds(month)?;
+    let local = |date: NaiveDate| {
+        date.and_hms_opt(0, 0, 0)
+            .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+            .map(|t| t.timestamp())
+            .ok_or_else(|| format!("Invalid month '{}'", month))
+    };
+    Ok((local(start)?, local(end)?))
+}
+
+/// Sessions, backup history and unlocks in `[since, until)`, optionally of one game, oldest first
+pub fn collect(db: &AchievementDatabase, since: i64, until: i64, game_name: Option<&str>) -> Result<Vec<TimelineEvent>, String> {
+    let mut events = Vec::new();
+
+    for session in db.get_play_sessions(since, until)? {
+        events.push(TimelineEvent {
+            timestamp: session.started_at,
+            kind: "session".to_string(),
+            game_name: session.game_name,
+            app_id: Some(session.app_id),
+            success: None,
+            duration_secs: Some((session.ended_at - session.started_at).max(0)),
+            detail: format!("{} achievement(s) unlocked", session.unlocked_count),
+        });
+    }
+
+    for entry in db.get_backup_history_between(since, until)? {
+        let detail = match (&entry.error, entry.files_count) {
+            (Some(error), _) => error.clone(),
+            (None, Some(count)) => format!("{} file(s)", count),
+            (None, None) => String::new(),
+        };
+        events.push(TimelineEvent {
+            timestamp: entry.timestamp,
+            kind: entry.kind,
+            game_name: entry.game_name,
+            app_id: entry.app_id,
+            success: Some(entry.success),
+            duration_secs: None,
+            detail,
+        });
+    }
+
+    for unlock in db.get_all_unlocks_between(since, until)? {
+        events.push(TimelineEvent {
+            timestamp: unlock.unlock_time,
+            kind: "unlock".to_string(),
+            game_name: unlock.game_name,
+            app_id: Some(unlock.app_id),
+            success: None,
+            duration_secs: None,
+            detail: unlock.display_name,
+        });
+    }
+
+    if let Some(game_name) = game_name {
+        events.retain(|event| event.game_name.eq_ignore_ascii_case(game_name));
+    }
+    events.sort_by_key(|event| event.timestamp);
+    Ok(events)
+}
+
+/// File contents for the events in `format`
+pub fn render(format: TimelineFormat, events: &[TimelineEvent]) -> Result<String, String> {
+    match format {
+        TimelineFormat::Json => serde_json::to_string_pretty(events).map_err(|e| format!("Failed to serialize to JSON: {}", e)),
+        TimelineFormat::Csv => {
+            let mut csv = String::from("timestamp,time_utc,kind,game_name,app_id,success,duration_secs,detail\r\n");
+            for event in events {
+                let time = Utc.timestamp_opt(event.timestamp, 0)
+                    .single()
+                    .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+                    .unwrap_or_default();
+                let fields = [
+                    event.timestamp.to_string(),
+                    time,
+                    csv_field(&event.kind),
+                    csv_field(&event.game_name),
+                    event.app_id.map(|id| id.to_string()).unwrap_or_default(),
+                    event.success.map(|s| s.to_string()).unwrap_or_default(),
+                    event.duration_secs.map(|d| d.to_string()).unwrap_or_default(),
+                    csv_field(&event.detail),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push_str("\r\n");
+            }
+            Ok(csv)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_bounds() {
+        let date = |y, m| NaiveDate::from_ymd_opt(y, m, 1).unwrap();
+        assert_eq!(month_bounds("2024-05").unwrap(), (date(2024, 5), date(2024, 6)));
+        assert_eq!(month_bounds("2024-12").unwrap(), (date(2024, 12), date(2025, 1)));
+        assert!(month_bounds("2024-13").is_err());
+        assert!(month_bounds("May 2024").is_err());
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let events = vec![
+            TimelineEvent {
+                timestamp: 1714594500,
+                kind: "session".to_string(),
+                game_name: "Portal 2".to_string(),
+                app_id: Some(620),
+                success: None,
+                duration_secs: Some(3600),
+                detail: "2 achievement(s) unlocked".to_string(),
+            },
+            TimelineEvent {
+                timestamp: 1714598100,
+                kind: "unlock".to_string(),
+                game_name: "Portal 2".to_string(),
+                app_id: Some(620),
+                success: None,
+                duration_secs: None,
+                detail: "Wake Up Call, \"Door\"".to_string(),
+            },
+        ];
+
+        let csv = render(TimelineFormat::Csv, &events).unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "timestamp,time_utc,kind,game_name,app_id,success,duration_secs,detail");
+        assert_eq!(lines[1], "1714594500,2024-05-01T20:15:00Z,session,Portal 2,620,,3600,2 achievement(s) unlocked");
+        assert_eq!(lines[2], "1714598100,2024-05-01T21:15:00Z,unlock,Portal 2,620,,,\"Wake Up Call, \"\"Door\"\"\"");
+    }
+}
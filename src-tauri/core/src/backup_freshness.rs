@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// Backup totals of one game, summed from the "backup" entries of the backup history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFreshness {
+    pub game_name: String,
+    pub app_id: Option<u32>,
+    pub successful_backups: u32,
+    pub failed_backups: u32,
+    pub last_attempt: i64,
+    pub last_success: Option<i64>,
+    pub recent_attempts: u32, // attempts inside the stale window; every game exit makes one
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// Start of the window a game has to have been backed up in
+pub fn window_start(now: i64, stale_days: u32) -> i64 {
+    now - stale_days as i64 * DAY_SECS
+}
+
+/// A game is stale when it was played (a backup was attempted) at least `min_plays` times in the last
+/// `stale_days` days without any of those backups succeeding. `stale_days` 0 turns the check off.
+pub fn is_stale(freshness: &BackupFreshness, now: i64, stale_days: u32, min_plays: u32) -> bool {
+    if stale_days == 0 {
+        return false;
+    }
+    let cutoff = window_start(now, stale_days);
+    freshness.recent_attempts >= min_plays.max(1)
+        && freshness.last_success.map_or(true, |last| last < cutoff)
+}
+
+pub fn mark_stale(entries: &mut [BackupFreshness], now: i64, stale_days: u32, min_plays: u32) {
+    for entry in entries {
+        entry.stale = is_stale(entry, now, stale_days, min_plays);
+    }
+}
+
+/// "3 days ago", "never"...
+pub fn age_label(last_success: Option<i64>, now: i64) -> String {
+    match last_success {
+        None => "never".to_string(),
+        Some(last) => match (now - last) / DAY_SECS {
+            0 => "today".to_string(),
+            1 => "yesterday".to_string(),
+            days => format!("{} days ago", days),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn freshness(recent_attempts: u32, last_success: Option<i64>) -> BackupFreshness {
+        BackupFreshness {
+            game_name: "Game".to_string(),
+            app_id: Some(10),
+            successful_backups: 0,
+            failed_backups: recent_attempts,
+            last_attempt: 0,
+            last_success,
+            recent_attempts,
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let now = 100 * DAY_SECS;
+        // Played often, last good backup 10 days ago
+        assert!(is_stale(&freshness(4, Some(now - 10 * DAY_SECS)), now, 7, 3));
+        // Played often, never backed up
+        assert!(is_stale(&freshness(3, None), now, 7, 3));
+        // Backed up recently
+        assert!(!is_stale(&freshness(4, Some(now - DAY_SECS)), now, 7, 3));
+        // Not played enough to matter
+        assert!(!is_stale(&freshness(2, None), now, 7, 3));
+        // Check turned off
+        assert!(!is_stale(&freshness(4, None), now, 0, 3));
+    }
+}
@@ -0,0 +1,258 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{LocalFree, HLOCAL};
+use windows::Win32::Security::Cryptography::{
+    BCryptGenRandom, CryptProtectData, CryptUnprotectData, BCRYPT_ALG_HANDLE,
+    BCRYPT_USE_SYSTEM_PREFERRED_RNG, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const KEY_LEN: usize = 32;
+/// PBKDF2-HMAC-SHA256 rounds for passphrase keys
+const PASSPHRASE_ITERATIONS: u32 = 310_000;
+// Bytes of SHA-256(key) appended to the recovery phrase to catch typos
+const PHRASE_CHECKSUM_LEN: usize = 3;
+// Crockford base32: no I, L, O or U to misread when writing the phrase down
+const PHRASE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+pub type BackupKey = [u8; KEY_LEN];
+
+/// What the config remembers about the backup key. The key itself is only kept DPAPI-protected
+/// in `key_path()`, so this is enough to check a key but not to recreate it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupKeyInfo {
+    pub check: String,
+    /// Hex salt when the key was derived from a passphrase
+    #[serde(default)]
+    pub salt: Option<String>,
+    pub created_at: i64,
+}
+
+/// Shown in settings and checked before restores
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupKeyStatus {
+    pub configured: bool,
+    pub from_passphrase: bool,
+    pub created_at: Option<i64>,
+    /// The stored key decrypts for this Windows user and matches the configured one
+    pub key_available: bool,
+    pub error: Option<String>,
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Where the DPAPI-protected key is kept, next to config.json
+pub fn key_path() -> PathBuf {
+    dirs::config_dir()
+        .expect("Could not find config directory")
+        .join("steam-backup-manager")
+        .join("backup.key")
+}
+
+fn random_bytes(buffer: &mut [u8]) -> Result<(), String> {
+    unsafe {
+        BCryptGenRandom(BCRYPT_ALG_HANDLE::default(), buffer, BCRYPT_USE_SYSTEM_PREFERRED_RNG)
+            .ok()
+            .map_err(|e| format!("Failed to generate random bytes: {}", e))
+    }
+}
+
+/// A new random key
+pub fn generate_key() -> Result<BackupKey, String> {
+    let mut key = [0u8; KEY_LEN];
+    random_bytes(&mut key)?;
+    Ok(key)
+}
+
+/// A new random salt for `derive_key`, hex encoded for the config
+pub fn new_salt() -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    random_bytes(&mut salt)?;
+    Ok(hex(&salt))
+}
+
+/// PBKDF2-HMAC-SHA256 of a passphrase. One SHA-256 block is exactly one key, so only block 1 is needed.
+pub fn derive_key(passphrase: &str, salt: &str) -> BackupKey {
+    let prf = HmacSha256::new_from_slice(passphrase.as_bytes()).expect("HMAC accepts any key length");
+
+    let mut mac = prf.clone();
+    mac.update(salt.as_bytes());
+    mac.update(&1u32.to_be_bytes());
+    let mut block: BackupKey = mac.finalize().into_bytes().into();
+    let mut key = block;
+
+    for _ in 1..PASSPHRASE_ITERATIONS {
+        let mut mac = prf.clone();
+        mac.update(&block);
+        block = mac.finalize().into_bytes().into();
+        key.iter_mut().zip(block.iter()).for_each(|(k, b)| *k ^= b);
+    }
+    key
+}
+
+/// Fingerprint stored in the config to recognise the key without storing it
+pub fn key_check(key: &BackupKey) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(b"steam-backup-manager:key-check");
+    hex(&mac.finalize().into_bytes())
+}
+
+/// The key as 14 groups of 4 characters, the last ones a checksum
+pub fn recovery_phrase(key: &BackupKey) -> String {
+    let mut bytes = key.to_vec();
+    bytes.extend_from_slice(&Sha256::digest(key)[..PHRASE_CHECKSUM_LEN]);
+
+    let mut chars = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            chars.push(PHRASE_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    chars.as_bytes()
+        .chunks(4)
+        .map(|group| String::from_utf8_lossy(group).to_string())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Read a recovery phrase back. Case, spaces and dashes don't matter, and O/I/L are read as 0/1/1.
+pub fn parse_recovery_phrase(phrase: &str) -> Result<BackupKey, String> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in phrase.chars().filter(|c| !c.is_whitespace() && *c != '-') {
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let value = PHRASE_ALPHABET.iter().position(|&a| a as char == c)
+            .ok_or_else(|| format!("'{}' can't be part of a recovery phrase", c))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    if bytes.len() != KEY_LEN + PHRASE_CHECKSUM_LEN {
+        return Err("The recovery phrase is incomplete, it has 14 groups of 4 characters".to_string());
+    }
+    let (key, checksum) = bytes.split_at(KEY_LEN);
+    if Sha256::digest(key)[..PHRASE_CHECKSUM_LEN] != *checksum {
+        return Err("The recovery phrase has a typo".to_string());
+    }
+    let mut result = [0u8; KEY_LEN];
+    result.copy_from_slice(key);
+    Ok(result)
+}
+
+/// Save the key protected with DPAPI, so only this Windows user can read it
+pub fn store_key(key: &BackupKey) -> Result<(), String> {
+    let input = CRYPT_INTEGER_BLOB { cbData: key.len() as u32, pbData: key.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    let description: Vec<u16> = "Steam Backup Manager backup key".encode_utf16().chain(std::iter::once(0)).collect();
+
+    let protected = unsafe {
+        CryptProtectData(&input, PCWSTR(description.as_ptr()), None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .map_err(|e| format!("Failed to protect backup key: {}", e))?;
+        let protected = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as _));
+        protected
+    };
+
+    let path = key_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    fs::write(&path, protected).map_err(|e| format!("Failed to save backup key: {}", e))
+}
+
+/// Read the key saved with `store_key`
+pub fn load_key() -> Result<BackupKey, String> {
+    let mut protected = fs::read(key_path()).map_err(|_| "No backup key is stored on this PC".to_string())?;
+    let input = CRYPT_INTEGER_BLOB { cbData: protected.len() as u32, pbData: protected.as_mut_ptr() };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+
+    let bytes = unsafe {
+        CryptUnprotectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .map_err(|e| format!("The stored backup key can't be unlocked by this Windows user: {}", e))?;
+        let bytes = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as _));
+        bytes
+    };
+
+    bytes.try_into().map_err(|_| "The stored backup key is damaged".to_string())
+}
+
+/// Remove the stored key; a missing file is not an error
+pub fn delete_key() -> Result<(), String> {
+    match fs::remove_file(key_path()) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to delete backup key: {}", e)),
+        _ => Ok(()),
+    }
+}
+
+/// Check the stored key against the configured one
+pub fn verify_stored_key(info: &BackupKeyInfo) -> Result<BackupKey, String> {
+    let key = load_key()?;
+    if key_check(&key) != info.check {
+        return Err("The stored backup key doesn't match the one your backups use. Recover it with your recovery phrase.".to_string());
+    }
+    Ok(key)
+}
+
+/// Check a passphrase against a key that was derived from one
+pub fn verify_passphrase(info: &BackupKeyInfo, passphrase: &str) -> Result<BackupKey, String> {
+    let salt = info.salt.as_deref().ok_or("The backup key wasn't made from a passphrase")?;
+    let key = derive_key(passphrase, salt);
+    if key_check(&key) != info.check {
+        return Err("Wrong passphrase".to_string());
+    }
+    Ok(key)
+}
+
+pub fn status(info: Option<&BackupKeyInfo>) -> BackupKeyStatus {
+    let Some(info) = info else {
+        return BackupKeyStatus { configured: false, from_passphrase: false, created_at: None, key_available: false, error: None };
+    };
+    let verified = verify_stored_key(info);
+    BackupKeyStatus {
+        configured: true,
+        from_passphrase: info.salt.is_some(),
+        created_at: Some(info.created_at),
+        key_available: verified.is_ok(),
+        error: verified.err(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_phrase_round_trip() {
+        let key: BackupKey = std::array::from_fn(|i| (i * 7) as u8);
+        let phrase = recovery_phrase(&key);
+        assert_eq!(phrase.split('-').count(), 14);
+        assert_eq!(parse_recovery_phrase(&phrase), Ok(key));
+        assert_eq!(parse_recovery_phrase(&phrase.to_lowercase().replace('-', " ")), Ok(key));
+
+        let mut typo = phrase.into_bytes();
+        typo[0] = if typo[0] == b'A' { b'B' } else { b'A' };
+        assert!(parse_recovery_phrase(&String::from_utf8(typo).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// When every installed game gets backed up: daily at `time`, or weekly on `weekday`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibraryBackupSchedule {
+    pub time: String, // local "HH:MM"
+    #[serde(default)]
+    pub weekday: Option<u32>, // 0 = Monday ... 6 = Sunday, None = every day
+}
+
+impl LibraryBackupSchedule {
+    /// First scheduled run strictly after `after`, in local time. None if the schedule can't be parsed.
+    pub fn next_run_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let time = NaiveTime::parse_from_str(&self.time, "%H:%M").ok()?;
+        if self.weekday.is_some_and(|day| day > 6) {
+            return None;
+        }
+
+        (0..=7)
+            .map(|days| (after.date() + Duration::days(days)).and_time(time))
+            .filter(|run| self.weekday.map_or(true, |day| run.weekday().num_days_from_monday() == day))
+            .find(|run| *run > after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_next_run_after() {
+        let daily = LibraryBackupSchedule { time: "03:00".to_string(), weekday: None };
+        assert_eq!(daily.next_run_after(at("2024-05-01 02:59")), Some(at("2024-05-01 03:00")));
+        assert_eq!(daily.next_run_after(at("2024-05-01 03:00")), Some(at("2024-05-02 03:00")));
+
+        // 2024-05-01 is a Wednesday
+        let sundays = LibraryBackupSchedule { time: "22:30".to_string(), weekday: Some(6) };
+        assert_eq!(sundays.next_run_after(at("2024-05-01 12:00")), Some(at("2024-05-05 22:30")));
+        assert_eq!(sundays.next_run_after(at("2024-05-05 22:30")), Some(at("2024-05-12 22:30")));
+
+        let broken = LibraryBackupSchedule { time: "3am".to_string(), weekday: None };
+        assert_eq!(broken.next_run_after(at("2024-05-01 12:00")), None);
+        let bad_day = LibraryBackupSchedule { time: "03:00".to_string(), weekday: Some(7) };
+        assert_eq!(bad_day.next_run_after(at("2024-05-01 12:00")), None);
+    }
+}
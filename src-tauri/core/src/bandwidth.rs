@@ -0,0 +1,87 @@
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use crate::locks::LockExt;
+use std::time::{Duration, Instant};
+
+// Piece size for throttled reads and request bodies; small pieces keep the throttling smooth on slow caps
+const PIECE: usize = 64 * 1024;
+
+/// Caps the average upload rate of one transfer by sleeping after each chunk
+pub struct BandwidthLimiter {
+    bytes_per_sec: Option<u64>,
+    started: Instant,
+    sent: Mutex<u64>,
+}
+
+impl BandwidthLimiter {
+    /// `max_kbps` is in KiB/s; `None` or 0 means unlimited
+    pub fn new(max_kbps: Option<u32>) -> Self {
+        Self {
+            bytes_per_sec: max_kbps.filter(|k| *k > 0).map(|k| k as u64 * 1024),
+            started: Instant::now(),
+            sent: Mutex::new(0),
+        }
+    }
+
+    /// How long to wait so the bytes sent so far stay under the cap
+    fn delay_after(&self, bytes: u64) -> Option<Duration> {
+        let rate = self.bytes_per_sec?;
+        let mut sent = self.sent.lock_or_recover();
+        *sent += bytes;
+
+        let expected = Duration::from_secs_f64(*sent as f64 / rate as f64);
+        expected.checked_sub(self.started.elapsed())
+    }
+
+    pub async fn consume(&self, bytes: u64) {
+        if let Some(delay) = self.delay_after(bytes) {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    pub fn consume_blocking(&self, bytes: u64) {
+        if let Some(delay) = self.delay_after(bytes) {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// Reader wrapper for blocking uploads (SFTP/FTPS) that respects a limiter
+pub struct ThrottledReader<'a, R: Read> {
+    inner: R,
+    limiter: &'a BandwidthLimiter,
+}
+
+impl<'a, R: Read> ThrottledReader<'a, R> {
+    pub fn new(inner: R, limiter: &'a BandwidthLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(PIECE);
+        let read = self.inner.read(&mut buf[..len])?;
+        self.limiter.consume_blocking(read as u64);
+        Ok(read)
+    }
+}
+
+/// Request body for async uploads that hands `data` to the connection in small pieces, waiting
+/// on the limiter before each one, so the cap holds while a chunk is being sent. reqwest sends
+/// stream bodies chunked, so callers set Content-Length themselves.
+pub fn throttled_body(data: Vec<u8>, limiter: Arc<BandwidthLimiter>) -> reqwest::Body {
+    let stream = futures_util::stream::unfold((data, 0usize), move |(data, offset)| {
+        let limiter = limiter.clone();
+        async move {
+            if offset >= data.len() {
+                return None;
+            }
+            let end = (offset + PIECE).min(data.len());
+            limiter.consume((end - offset) as u64).await;
+            let piece = data[offset..end].to_vec();
+            Some((Ok::<_, std::io::Error>(piece), (data, end)))
+        }
+    });
+    reqwest::Body::wrap_stream(stream)
+}
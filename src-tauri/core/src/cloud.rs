@@ -0,0 +1,531 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::dropbox::DropboxClient;
+use crate::gdrive::GoogleDriveClient;
+use crate::ludusavi::LudusaviManager;
+use crate::network;
+use crate::onedrive::OneDriveClient;
+use crate::rclone;
+use crate::s3::S3Client;
+use crate::app_log;
+
+/// A configured off-site location backup archives are uploaded to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudDestination {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub max_upload_kbps: Option<u32>, // KiB/s, None = unlimited
+    #[serde(default)]
+    pub upload_window: Option<UploadWindow>,
+    #[serde(flatten)]
+    pub provider: CloudProvider,
+}
+
+impl CloudDestination {
+    /// Whether uploads may start right now (always true without a window)
+    pub fn can_upload_now(&self) -> bool {
+        self.upload_window.as_ref()
+            .map(|w| w.contains(chrono::Local::now().time()))
+            .unwrap_or(true)
+    }
+}
+
+/// Daily local time range uploads are allowed in, e.g. 02:00-07:00 (may wrap past midnight)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadWindow {
+    pub start: String, // "HH:MM"
+    pub end: String,
+}
+
+impl UploadWindow {
+    pub fn contains(&self, time: chrono::NaiveTime) -> bool {
+        let parse = |value: &str| chrono::NaiveTime::parse_from_str(value, "%H:%M").ok();
+        let (Some(start), Some(end)) = (parse(&self.start), parse(&self.end)) else {
+            // An unparseable window shouldn't block uploads forever
+            return true;
+        };
+
+        if start <= end {
+            time >= start && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+/// Upload held back until its destination's upload window opens
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingUpload {
+    pub game_name: String,
+    pub destination_id: String,
+    pub queued_at: i64,
+}
+
+fn pending_uploads_path() -> PathBuf {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager");
+    let _ = fs::create_dir_all(&dir);
+    dir.join("pending_uploads.json")
+}
+
+/// Pending uploads survive restarts so nothing is lost if the PC is off when the window opens
+pub fn load_pending_uploads() -> Vec<PendingUpload> {
+    fs::read_to_string(pending_uploads_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_pending_uploads(pending: &[PendingUpload]) {
+    if let Ok(json) = serde_json::to_string_pretty(pending) {
+        let _ = fs::write(pending_uploads_path(), json);
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "provider")]
+pub enum CloudProvider {
+    #[serde(rename = "googleDrive", rename_all = "camelCase")]
+    GoogleDrive {
+        client_id: String,
+        #[serde(default)]
+        client_secret: String,
+        #[serde(default)]
+        folder_id: Option<String>,
+    },
+    #[serde(rename = "oneDrive", rename_all = "camelCase")]
+    OneDrive {
+        client_id: String,
+    },
+    #[serde(rename = "dropbox", rename_all = "camelCase")]
+    Dropbox {
+        app_key: String,
+        #[serde(default)]
+        app_secret: String,
+        #[serde(default)]
+        folder_path: String, // inside the app folder
+    },
+    #[serde(rename = "s3", rename_all = "camelCase")]
+    S3 {
+        endpoint: String, // e.g. https://s3.us-west-004.backblazeb2.com
+        #[serde(default)]
+        region: String,
+        bucket: String,
+        access_key_id: String,
+        #[serde(default)]
+        prefix: String,
+        #[serde(default = "default_enabled")]
+        server_side_encryption: bool,
+    },
+    #[serde(rename = "sftp", rename_all = "camelCase")]
+    Sftp {
+        host: String,
+        #[serde(default = "default_sftp_port")]
+        port: u16,
+        username: String,
+        remote_path: String,
+        /// SHA-256 fingerprint of the server's host key, pinned on first connection
+        #[serde(default)]
+        host_key: Option<String>,
+    },
+    #[serde(rename = "ftps", rename_all = "camelCase")]
+    Ftps {
+        host: String,
+        #[serde(default = "default_ftp_port")]
+        port: u16,
+        username: String,
+        remote_path: String,
+    },
+    #[serde(rename = "webdav", rename_all = "camelCase")]
+    WebDav {
+        url: String,
+        username: String,
+    },
+    /// Any remote set up in rclone (`rclone config`), e.g. `b2:my-bucket/saves`
+    #[serde(rename = "rclone", rename_all = "camelCase")]
+    Rclone {
+        #[serde(default)]
+        rclone_path: String, // empty = rclone on PATH
+        remote: String,
+    },
+}
+
+fn default_sftp_port() -> u16 {
+    22
+}
+
+fn default_ftp_port() -> u16 {
+    21
+}
+
+impl CloudProvider {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CloudProvider::GoogleDrive { .. } => "Google Drive",
+            CloudProvider::OneDrive { .. } => "OneDrive",
+            CloudProvider::Dropbox { .. } => "Dropbox",
+            CloudProvider::S3 { .. } => "S3",
+            CloudProvider::Sftp { .. } => "SFTP",
+            CloudProvider::Ftps { .. } => "FTPS",
+            CloudProvider::WebDav { .. } => "WebDAV",
+            CloudProvider::Rclone { .. } => "rclone",
+        }
+    }
+
+    /// Network shares and rclone remotes get the backup folder mirrored file by file instead of an archive
+    pub fn is_mirror(&self) -> bool {
+        matches!(self, CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } | CloudProvider::Rclone { .. })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadResult {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub success: bool,
+    pub remote_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_id: Option<String>,
+    pub bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Pending OAuth device-code sign-in shown to the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    #[serde(alias = "verification_uri")] // Microsoft's spelling
+    pub verification_url: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageQuota {
+    pub used_bytes: u64,
+    pub total_bytes: Option<u64>, // None for unlimited plans
+}
+
+/// Zip a game's Ludusavi backup folder into a single archive for upload, named `<archive_name>.zip`
+pub fn package_game_backup(backup_path: &str, game_name: &str, archive_name: &str) -> Result<PathBuf, String> {
+    let folder_name = LudusaviManager::game_folder_name(game_name);
+    let game_dir = Path::new(backup_path).join(&folder_name);
+    if !game_dir.is_dir() {
+        return Err(format!("No backup folder found for {}", game_name));
+    }
+
+    let staging_dir = std::env::temp_dir().join("steam-backup-manager").join("uploads");
+    fs::create_dir_all(&staging_dir)
+        .map_err(|e| format!("Failed to create staging folder: {}", e))?;
+
+    let archive_path = staging_dir.join(format!("{}.zip", archive_name));
+
+    let file = fs::File::create(&archive_path)
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    add_dir_to_zip(&mut zip, &game_dir, &game_dir, &folder_name, options)?;
+
+    zip.finish().map_err(|e| format!("Failed to finish archive: {}", e))?;
+    Ok(archive_path)
+}
+
+fn add_dir_to_zip(
+    zip: &mut zip::ZipWriter<fs::File>,
+    root: &Path,
+    dir: &Path,
+    prefix: &str,
+    options: zip::write::FileOptions,
+) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        let name = format!("{}/{}", prefix, relative);
+
+        if path.is_dir() {
+            zip.add_directory(name, options)
+                .map_err(|e| format!("Failed to add folder to archive: {}", e))?;
+            add_dir_to_zip(zip, root, &path, prefix, options)?;
+        } else {
+            zip.start_file(name, options)
+                .map_err(|e| format!("Failed to add file to archive: {}", e))?;
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            zip.write_all(&bytes)
+                .map_err(|e| format!("Failed to write archive: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Upload a single archive to one destination
+pub async fn upload_archive(destination: &CloudDestination, game_name: &str, archive_path: &Path) -> UploadResult {
+    let remote_name = archive_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let bytes = fs::metadata(archive_path).map(|m| m.len()).unwrap_or(0);
+
+    let outcome = match &destination.provider {
+        CloudProvider::GoogleDrive { .. } => {
+            match GoogleDriveClient::for_destination(destination).await {
+                Ok(client) => client.upload_file(archive_path, &remote_name).await,
+                Err(e) => Err(e),
+            }
+        }
+        CloudProvider::OneDrive { .. } => {
+            match OneDriveClient::for_destination(destination).await {
+                Ok(client) => client.upload_file(archive_path, &remote_name).await,
+                Err(e) => Err(e),
+            }
+        }
+        CloudProvider::Dropbox { .. } => {
+            match DropboxClient::for_destination(destination).await {
+                Ok(client) => client.upload_file(archive_path, &remote_name).await,
+                Err(e) => Err(e),
+            }
+        }
+        CloudProvider::S3 { .. } => {
+            match S3Client::for_destination(destination) {
+                Ok(client) => client.upload_file(archive_path, &client.object_key(game_name, &remote_name)).await,
+                Err(e) => Err(e),
+            }
+        }
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } | CloudProvider::Rclone { .. } => {
+            Err(format!("{} mirrors backup folders instead of archives", destination.name))
+        }
+    };
+
+    match outcome {
+        Ok(remote_id) => {
+            app_log!("[Cloud] Uploaded {} to {}", remote_name, destination.name);
+            UploadResult {
+                destination_id: destination.id.clone(),
+                destination_name: destination.name.clone(),
+                success: true,
+                remote_name,
+                remote_id: Some(remote_id),
+                bytes,
+                error: None,
+            }
+        }
+        Err(e) => {
+            app_log!("[Cloud] Upload of {} to {} failed: {}", remote_name, destination.name, e);
+            UploadResult {
+                destination_id: destination.id.clone(),
+                destination_name: destination.name.clone(),
+                success: false,
+                remote_name,
+                remote_id: None,
+                bytes,
+                error: Some(e),
+            }
+        }
+    }
+}
+
+/// Mirror a game's backup folder to a network or rclone destination
+pub async fn mirror_game_backup(destination: &CloudDestination, backup_path: &str, game_name: &str) -> UploadResult {
+    let folder_name = LudusaviManager::game_folder_name(game_name);
+    let game_dir = Path::new(backup_path).join(&folder_name);
+
+    let outcome = if !game_dir.is_dir() {
+        Err(format!("No backup folder found for {}", game_name))
+    } else if matches!(destination.provider, CloudProvider::Rclone { .. }) {
+        rclone::copy_directory(destination, &game_dir, &folder_name).await
+    } else {
+        network::mirror_directory(destination, &game_dir, &folder_name).await
+    };
+
+    match outcome {
+        Ok(stats) => {
+            app_log!(
+                "[Cloud] Mirrored {} to {}: {} uploaded, {} unchanged",
+                folder_name, destination.name, stats.uploaded_files, stats.skipped_files
+            );
+            UploadResult {
+                destination_id: destination.id.clone(),
+                destination_name: destination.name.clone(),
+                success: true,
+                remote_name: folder_name,
+                remote_id: None,
+                bytes: stats.uploaded_bytes,
+                error: None,
+            }
+        }
+        Err(e) => {
+            app_log!("[Cloud] Mirror of {} to {} failed: {}", folder_name, destination.name, e);
+            UploadResult {
+                destination_id: destination.id.clone(),
+                destination_name: destination.name.clone(),
+                success: false,
+                remote_name: folder_name,
+                remote_id: None,
+                bytes: 0,
+                error: Some(e),
+            }
+        }
+    }
+}
+
+/// Package a game's backup once and push it to every given destination. `archive_name` (see
+/// filenames::render_backup_name) names the archive and so the object on archive destinations;
+/// mirrors keep the game's folder name.
+pub async fn upload_game_backup(
+    backup_path: &str,
+    game_name: &str,
+    archive_name: &str,
+    destinations: &[CloudDestination],
+) -> Result<Vec<UploadResult>, String> {
+    let mut results = Vec::new();
+
+    for destination in destinations.iter().filter(|d| d.provider.is_mirror()) {
+        results.push(mirror_game_backup(destination, backup_path, game_name).await);
+    }
+
+    let archive_destinations: Vec<&CloudDestination> = destinations.iter().filter(|d| !d.provider.is_mirror()).collect();
+    if archive_destinations.is_empty() {
+        return Ok(results);
+    }
+
+    let archive_path = package_game_backup(backup_path, game_name, archive_name)?;
+    for destination in archive_destinations {
+        results.push(upload_archive(destination, game_name, &archive_path).await);
+    }
+
+    let _ = fs::remove_file(&archive_path);
+    Ok(results)
+}
+
+/// Storage usage reported by the destination
+pub async fn get_quota(destination: &CloudDestination) -> Result<StorageQuota, String> {
+    match &destination.provider {
+        CloudProvider::GoogleDrive { .. } => {
+            GoogleDriveClient::for_destination(destination).await?.get_quota().await
+        }
+        CloudProvider::OneDrive { .. } => {
+            OneDriveClient::for_destination(destination).await?.get_quota().await
+        }
+        CloudProvider::Dropbox { .. } => {
+            DropboxClient::for_destination(destination).await?.get_quota().await
+        }
+        CloudProvider::S3 { .. } => {
+            S3Client::for_destination(destination)?.get_quota().await
+        }
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } | CloudProvider::Rclone { .. } => {
+            Err(format!("{} does not report storage usage", destination.provider.display_name()))
+        }
+    }
+}
+
+/// Whether an uploaded archive is still present on the destination
+pub async fn remote_file_exists(destination: &CloudDestination, remote_id: &str) -> Result<bool, String> {
+    match &destination.provider {
+        CloudProvider::GoogleDrive { .. } => {
+            GoogleDriveClient::for_destination(destination).await?.file_exists(remote_id).await
+        }
+        CloudProvider::OneDrive { .. } => {
+            OneDriveClient::for_destination(destination).await?.item_exists(remote_id).await
+        }
+        CloudProvider::Dropbox { .. } => {
+            DropboxClient::for_destination(destination).await?.file_exists(remote_id).await
+        }
+        CloudProvider::S3 { .. } => {
+            S3Client::for_destination(destination)?.object_exists(remote_id).await
+        }
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } | CloudProvider::Rclone { .. } => {
+            Err(format!("{} mirrors folders instead of storing archives", destination.provider.display_name()))
+        }
+    }
+}
+
+/// Begin the device-code sign-in for a destination
+pub async fn start_authorization(destination: &CloudDestination) -> Result<DeviceAuthorization, String> {
+    match &destination.provider {
+        CloudProvider::GoogleDrive { client_id, .. } => GoogleDriveClient::start_device_authorization(client_id).await,
+        CloudProvider::OneDrive { client_id } => OneDriveClient::start_device_authorization(client_id).await,
+        CloudProvider::Dropbox { .. } => Err("Dropbox sign-in uses an authorization code instead of a device code".to_string()),
+        CloudProvider::S3 { .. } => Err("S3 destinations use an access key instead of signing in".to_string()),
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } => {
+            Err("Network destinations use a password instead of signing in".to_string())
+        }
+        CloudProvider::Rclone { .. } => Err("rclone remotes are signed in with `rclone config`".to_string()),
+    }
+}
+
+/// Wait for the user to approve the sign-in and store the resulting token
+pub async fn complete_authorization(destination: &CloudDestination, authorization: &DeviceAuthorization) -> Result<(), String> {
+    match &destination.provider {
+        CloudProvider::GoogleDrive { client_id, client_secret, .. } => {
+            GoogleDriveClient::complete_device_authorization(&destination.id, client_id, client_secret, authorization).await
+        }
+        CloudProvider::OneDrive { client_id } => {
+            OneDriveClient::complete_device_authorization(&destination.id, client_id, authorization).await
+        }
+        CloudProvider::Dropbox { .. } => Err("Dropbox sign-in uses an authorization code instead of a device code".to_string()),
+        CloudProvider::S3 { .. } => Err("S3 destinations use an access key instead of signing in".to_string()),
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } => {
+            Err("Network destinations use a password instead of signing in".to_string())
+        }
+        CloudProvider::Rclone { .. } => Err("rclone remotes are signed in with `rclone config`".to_string()),
+    }
+}
+
+pub fn sign_out(destination: &CloudDestination) -> Result<(), String> {
+    match &destination.provider {
+        CloudProvider::GoogleDrive { .. } => GoogleDriveClient::sign_out(&destination.id),
+        CloudProvider::OneDrive { .. } => OneDriveClient::sign_out(&destination.id),
+        CloudProvider::Dropbox { .. } => DropboxClient::sign_out(&destination.id),
+        CloudProvider::S3 { .. } => S3Client::sign_out(&destination.id),
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } => {
+            network::forget_password(&destination.id)
+        }
+        // rclone keeps its own credentials in rclone.conf
+        CloudProvider::Rclone { .. } => Ok(()),
+    }
+}
+
+/// Save the password/secret key for destinations that don't use OAuth
+pub fn store_secret(destination: &CloudDestination, secret: &str) -> Result<(), String> {
+    match &destination.provider {
+        CloudProvider::S3 { .. } => S3Client::store_secret(&destination.id, secret),
+        CloudProvider::Sftp { .. } | CloudProvider::Ftps { .. } | CloudProvider::WebDav { .. } => {
+            network::store_password(&destination.id, secret)
+        }
+        CloudProvider::Rclone { .. } => Err("rclone remotes keep their credentials in rclone's own config".to_string()),
+        _ => Err(format!("{} signs in through the browser instead", destination.provider.display_name())),
+    }
+}
+
+/// Check that a destination is signed in and reachable; returns its quota when the provider has one
+pub async fn test_connection(destination: &CloudDestination) -> Result<Option<StorageQuota>, String> {
+    let quota = if matches!(destination.provider, CloudProvider::Rclone { .. }) {
+        rclone::test_remote(destination).await?;
+        None
+    } else if destination.provider.is_mirror() {
+        network::test_connection(destination).await?;
+        None
+    } else {
+        Some(get_quota(destination).await?)
+    };
+    app_log!("[Cloud] {} ({}) is reachable", destination.name, destination.provider.display_name());
+    Ok(quota)
+}
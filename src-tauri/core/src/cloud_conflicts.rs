@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use crate::cloud::CloudDestination;
+use crate::ludusavi::LudusaviManager;
+use crate::rclone::{self, RemoteFile};
+use crate::snapshots;
+
+// Some remotes and file systems round modification times, so closer than this counts as the same time
+const MTIME_TOLERANCE_SECS: i64 = 2;
+
+/// How a game's local backup folder compares to its copy on a destination
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictState {
+    InSync,
+    LocalNewer,
+    RemoteNewer,
+    /// Both sides have changes the other doesn't
+    Diverged,
+}
+
+/// What can be done about a conflict, passed back to resolve_cloud_conflict
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionAction {
+    /// Copy the local backup over the remote one
+    Upload,
+    /// Copy the remote backup over the local one; files only present locally are kept
+    Download,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudConflict {
+    pub game_name: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub state: ConflictState,
+    pub local_only: Vec<String>,
+    pub remote_only: Vec<String>,
+    pub local_newer: Vec<String>,
+    pub remote_newer: Vec<String>,
+    pub unchanged: usize,
+    pub local_latest: Option<i64>, // newest modification time on each side
+    pub remote_latest: Option<i64>,
+    pub actions: Vec<ResolutionAction>,
+}
+
+/// A file of the local backup folder
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    pub size: u64,
+    pub modified: i64,
+    pub sha256: Option<String>,
+}
+
+/// Every file of the game's backup folder, keyed by forward-slash path. Hashes are left out until compared.
+pub fn local_files(root: &Path) -> BTreeMap<String, LocalFile> {
+    let mut files = BTreeMap::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else { continue };
+            let modified = metadata.modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            files.insert(relative, LocalFile { size: metadata.len(), modified, sha256: None });
+        }
+    }
+
+    files
+}
+
+/// Compare both sides file by file. Checksums decide when both sides have one, otherwise size and
+/// modification time do; a changed file belongs to the side that modified it last.
+pub fn compare(
+    local: &BTreeMap<String, LocalFile>,
+    remote: &BTreeMap<String, RemoteFile>,
+) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>, usize) {
+    let (mut local_only, mut remote_only, mut local_newer, mut remote_newer, mut unchanged) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), 0);
+
+    for (path, file) in local {
+        let Some(other) = remote.get(path) else {
+            local_only.push(path.clone());
+            continue;
+        };
+        let same = match (&file.sha256, &other.sha256) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            _ => file.size == other.size && (file.modified - other.modified).abs() <= MTIME_TOLERANCE_SECS,
+        };
+        if same {
+            unchanged += 1;
+        } else if file.modified >= other.modified {
+            local_newer.push(path.clone());
+        } else {
+            remote_newer.push(path.clone());
+        }
+    }
+    remote_only.extend(remote.keys().filter(|path| !local.contains_key(*path)).cloned());
+
+    (local_only, remote_only, local_newer, remote_newer, unchanged)
+}
+
+pub fn state_of(local_ahead: bool, remote_ahead: bool) -> ConflictState {
+    match (local_ahead, remote_ahead) {
+        (false, false) => ConflictState::InSync,
+        (true, false) => ConflictState::LocalNewer,
+        (false, true) => ConflictState::RemoteNewer,
+        (true, true) => ConflictState::Diverged,
+    }
+}
+
+pub fn actions_for(state: ConflictState) -> Vec<ResolutionAction> {
+    match state {
+        ConflictState::InSync => Vec::new(),
+        ConflictState::LocalNewer => vec![ResolutionAction::Upload],
+        ConflictState::RemoteNewer => vec![ResolutionAction::Download],
+        ConflictState::Diverged => vec![ResolutionAction::Upload, ResolutionAction::Download],
+    }
+}
+
+/// Compare a game's local backup with its mirror on an rclone destination
+pub async fn describe(destination: &CloudDestination, backup_path: &str, game_name: &str) -> Result<CloudConflict, String> {
+    let folder_name = LudusaviManager::game_folder_name(game_name);
+    let local_dir = Path::new(backup_path).join(&folder_name);
+    let remote = rclone::list_files(destination, &folder_name).await?;
+    let mut local = local_files(&local_dir);
+
+    // Only hash the files a checksum can settle: same size on both sides and a remote hash to check against
+    for (path, file) in local.iter_mut() {
+        let comparable = remote.get(path).map_or(false, |other| other.sha256.is_some() && other.size == file.size);
+        if comparable {
+            file.sha256 = snapshots::hash_file(&local_dir.join(path)).ok();
+        }
+    }
+
+    let (local_only, remote_only, local_newer, remote_newer, unchanged) = compare(&local, &remote);
+    let state = state_of(
+        !local_only.is_empty() || !local_newer.is_empty(),
+        !remote_only.is_empty() || !remote_newer.is_empty(),
+    );
+
+    Ok(CloudConflict {
+        game_name: game_name.to_string(),
+        destination_id: destination.id.clone(),
+        destination_name: destination.name.clone(),
+        state,
+        local_only,
+        remote_only,
+        local_newer,
+        remote_newer,
+        unchanged,
+        local_latest: local.values().map(|f| f.modified).max(),
+        remote_latest: remote.values().map(|f| f.modified).max(),
+        actions: actions_for(state),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(size: u64, modified: i64, sha256: Option<&str>) -> LocalFile {
+        LocalFile { size, modified, sha256: sha256.map(str::to_string) }
+    }
+
+    fn remote(size: u64, modified: i64, sha256: Option<&str>) -> RemoteFile {
+        RemoteFile { size, modified, sha256: sha256.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_compare() {
+        let local_files = BTreeMap::from([
+            ("same.sav".to_string(), local(10, 100, None)),
+            ("rounded.sav".to_string(), local(10, 101, None)),
+            ("hashed.sav".to_string(), local(10, 500, Some("ABC"))),
+            ("mine.sav".to_string(), local(12, 300, None)),
+            ("theirs.sav".to_string(), local(10, 100, None)),
+            ("new.sav".to_string(), local(1, 100, None)),
+        ]);
+        let remote_files = BTreeMap::from([
+            ("same.sav".to_string(), remote(10, 100, None)),
+            ("rounded.sav".to_string(), remote(10, 100, None)),
+            ("hashed.sav".to_string(), remote(10, 100, Some("abc"))),
+            ("mine.sav".to_string(), remote(10, 200, None)),
+            ("theirs.sav".to_string(), remote(11, 200, None)),
+            ("cloud.sav".to_string(), remote(1, 100, None)),
+        ]);
+
+        let (local_only, remote_only, local_newer, remote_newer, unchanged) = compare(&local_files, &remote_files);
+        assert_eq!(local_only, vec!["new.sav"]);
+        assert_eq!(remote_only, vec!["cloud.sav"]);
+        assert_eq!(local_newer, vec!["mine.sav"]);
+        assert_eq!(remote_newer, vec!["theirs.sav"]);
+        assert_eq!(unchanged, 3);
+
+        assert_eq!(state_of(true, true), ConflictState::Diverged);
+        assert_eq!(actions_for(ConflictState::RemoteNewer), vec![ResolutionAction::Download]);
+        assert!(actions_for(ConflictState::InSync).is_empty());
+    }
+}
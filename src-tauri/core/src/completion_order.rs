@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use crate::achievements::Achievement;
+
+/// Achievements the user tagged with this are suggested before everything else
+pub const MISSABLE_TAG: &str = "missable";
+/// Pushed to the end: worth doing last whatever their rarity
+pub const GRINDY_TAG: &str = "grindy";
+
+/// A locked achievement in the suggested completion order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedAchievement {
+    #[serde(flatten)]
+    pub achievement: Achievement,
+    pub tags: Vec<String>,
+    pub reason: String,
+}
+
+// Lower goes first: missable, then normal, then grindy
+fn tag_rank(tags: &[String]) -> u8 {
+    if tags.iter().any(|t| t == MISSABLE_TAG) {
+        0
+    } else if tags.iter().any(|t| t == GRINDY_TAG) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Order a game's locked achievements for a completion run: missable ones first, then the
+/// most commonly unlocked (usually the easiest). Achievements without rarity data go last.
+pub fn suggest(achievements: Vec<Achievement>, tags: &HashMap<String, Vec<String>>) -> Vec<SuggestedAchievement> {
+    let mut suggestions: Vec<SuggestedAchievement> = achievements
+        .into_iter()
+        .filter(|a| !a.achieved)
+        .map(|achievement| {
+            let tags = tags.get(&achievement.achievement_id).cloned().unwrap_or_default();
+            let reason = match (tag_rank(&tags), achievement.global_unlock_percentage) {
+                (0, _) => "Missable - do it before it's too late".to_string(),
+                (2, _) => "Grindy - best left for last".to_string(),
+                (_, Some(percentage)) => format!("Unlocked by {:.1}% of players", percentage),
+                (_, None) => "No rarity data".to_string(),
+            };
+            SuggestedAchievement { achievement, tags, reason }
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        tag_rank(&a.tags).cmp(&tag_rank(&b.tags)).then_with(|| {
+            match (a.achievement.global_unlock_percentage, b.achievement.global_unlock_percentage) {
+                (Some(x), Some(y)) => y.partial_cmp(&x).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.achievement.display_name.cmp(&b.achievement.display_name),
+            }
+        })
+    });
+    suggestions
+}
@@ -0,0 +1,399 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::cloud::CloudDestination;
+use crate::syncthing::SyncthingSettings;
+use crate::lan_sync::LanSyncSettings;
+use crate::milestones::MilestoneSettings;
+use crate::shell_integration;
+use crate::filenames;
+use crate::backup_keys::{self, BackupKeyInfo};
+use crate::backup_schedule::LibraryBackupSchedule;
+use crate::ludusavi::{BackupCloudSync, BackupCompression, BackupFormat};
+use crate::webhooks::WebhookSettings;
+use crate::app_log;
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    pub ludusavi_path: String,
+    pub backup_path: String,
+    pub auto_start: bool,
+    pub notifications_enabled: bool,
+    pub game_executables: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steam_api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steam_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steam_id_64: Option<String>,
+    #[serde(default = "default_achievement_duration")]
+    pub achievement_duration: u32,
+    #[serde(default = "default_true")]
+    pub backup_on_shutdown: bool,
+    #[serde(default = "default_shutdown_backup_budget")]
+    pub shutdown_backup_budget_secs: u32,
+    #[serde(default)]
+    pub custom_save_paths: HashMap<String, Vec<String>>, // game_name -> save directories
+    #[serde(default = "default_save_watch_debounce")]
+    pub save_watch_debounce_secs: u32,
+    #[serde(default)]
+    pub cloud_destinations: Vec<CloudDestination>,
+    #[serde(default)]
+    pub syncthing: SyncthingSettings,
+    #[serde(default)]
+    pub dedup_store_enabled: bool, // Ludusavi then keeps only the latest full backup, older versions live in the store
+    #[serde(default = "default_verification_interval")]
+    pub verification_interval_hours: u32, // 0 = no scheduled verification
+    #[serde(default)]
+    pub verify_remote_uploads: bool,
+    #[serde(default)]
+    pub achievement_sync_folder: String, // shared folder for multi-PC achievement sync, empty = off
+    #[serde(default)]
+    pub lan_sync: LanSyncSettings,
+    #[serde(default)]
+    pub read_only_mode: bool, // family mode: view progress only, destructive commands are refused
+    #[serde(default)]
+    pub read_only_pin_hash: Option<String>,
+    #[serde(default)]
+    pub milestone_notifications: MilestoneSettings,
+    #[serde(default)]
+    pub scripting_enabled: bool, // run user scripts from the scripts folder on app events
+    #[serde(default)]
+    pub unlock_webhook: WebhookSettings, // POST every achievement unlock to a URL
+    #[serde(default)]
+    pub startup_delay_secs: u32, // wait this long after login before starting monitors, 0 = none
+    #[serde(default)]
+    pub wait_for_steam: bool, // after login, don't start monitors until Steam.exe is running
+    #[serde(default)]
+    pub ludusavi_cloud_sync: bool, // run Ludusavi's own cloud upload after backups and download before restores
+    #[serde(default)]
+    pub ludusavi_backup_cloud_sync: BackupCloudSync, // cloud sync flag passed to `ludusavi backup`
+    #[serde(default)]
+    pub ludusavi_backup_format: BackupFormat,
+    #[serde(default)]
+    pub ludusavi_compression: BackupCompression, // used by zip backups
+    #[serde(default)]
+    pub ludusavi_compression_level: Option<i32>, // None = Ludusavi's default level for the compression
+    #[serde(default = "default_true")]
+    pub verify_backups: bool, // check each new backup on disk against what Ludusavi reported
+    #[serde(default)]
+    pub backup_on_uninstall: bool, // back up and archive a Steam game's saves as soon as it's seen uninstalled
+    #[serde(default = "default_backup_name_template")]
+    pub backup_name_template: String, // name of uploaded archives and cloud objects: {game}, {date}, {appid}, {trigger}
+    #[serde(default)]
+    pub library_backup_schedule: Option<LibraryBackupSchedule>, // back up every installed game at a set time, None = off
+    #[serde(default = "default_low_disk_space")]
+    pub low_disk_space_mb: u32, // warn when the backup destination has less free space than this, 0 = off
+    #[serde(default)]
+    pub explorer_context_menu: bool, // "Back up saves" entry on the right-click menu of .exe files
+    #[serde(default)]
+    pub excluded_steam_categories: Vec<String>, // Steam library categories whose games are excluded from monitoring
+    #[serde(default)]
+    pub control_pipe_enabled: bool, // accept pause/resume/backup/status commands on a named pipe
+    #[serde(default)]
+    pub backup_key: Option<BackupKeyInfo>, // fingerprint of the backup encryption key, the key itself is in backup.key
+    #[serde(default = "default_ludusavi_timeout")]
+    pub ludusavi_timeout_secs: u32, // kill a Ludusavi backup/restore that runs longer than this, 0 = no limit
+    #[serde(default)]
+    pub backup_full_versions: u32, // full backups kept per game, 0 = Ludusavi's own retention settings
+    #[serde(default)]
+    pub backup_differential_versions: u32, // differential backups kept on top of each full one
+    #[serde(default)]
+    pub quiet_games: Vec<u32>, // app ids played without notifications, everything is shown in the recap at exit
+    #[serde(default)]
+    pub snapshot_on_game_start: bool, // back up a game's saves as it starts, to revert a session that broke them
+    #[serde(default)]
+    pub session_backup_minutes: HashMap<String, u32>, // game_name -> back up every this many minutes while it runs
+    #[serde(default = "default_stale_backup_days")]
+    pub stale_backup_days: u32, // warn when a regularly played game has no successful backup in this many days, 0 = off
+    #[serde(default = "default_stale_backup_min_plays")]
+    pub stale_backup_min_plays: u32, // sessions within that window that make a game "regularly played"
+    #[serde(default)]
+    pub game_backup_paths: HashMap<String, String>, // game_name -> backup folder used instead of backup_path
+}
+
+fn default_achievement_duration() -> u32 {
+    6
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_shutdown_backup_budget() -> u32 {
+    20
+}
+
+fn default_save_watch_debounce() -> u32 {
+    30
+}
+
+fn default_verification_interval() -> u32 {
+    24 * 7
+}
+
+fn default_ludusavi_timeout() -> u32 {
+    10 * 60
+}
+
+fn default_low_disk_space() -> u32 {
+    1024
+}
+
+fn default_stale_backup_days() -> u32 {
+    7
+}
+
+fn default_stale_backup_min_plays() -> u32 {
+    3
+}
+
+fn default_backup_name_template() -> String {
+    filenames::DEFAULT_BACKUP_NAME_TEMPLATE.to_string()
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            ludusavi_path: String::new(),
+            backup_path: String::new(),
+            auto_start: true,
+            notifications_enabled: true,
+            game_executables: HashMap::new(),
+            steam_api_key: None,
+            steam_user_id: None,
+            steam_id_64: None,
+            achievement_duration: 6,
+            backup_on_shutdown: true,
+            shutdown_backup_budget_secs: default_shutdown_backup_budget(),
+            custom_save_paths: HashMap::new(),
+            save_watch_debounce_secs: default_save_watch_debounce(),
+            cloud_destinations: Vec::new(),
+            syncthing: SyncthingSettings::default(),
+            dedup_store_enabled: false,
+            verification_interval_hours: default_verification_interval(),
+            verify_remote_uploads: false,
+            achievement_sync_folder: String::new(),
+            lan_sync: LanSyncSettings::default(),
+            read_only_mode: false,
+            read_only_pin_hash: None,
+            milestone_notifications: MilestoneSettings::default(),
+            scripting_enabled: false,
+            unlock_webhook: WebhookSettings::default(),
+            startup_delay_secs: 0,
+            wait_for_steam: false,
+            ludusavi_cloud_sync: false,
+            ludusavi_backup_cloud_sync: BackupCloudSync::default(),
+            ludusavi_backup_format: BackupFormat::default(),
+            ludusavi_compression: BackupCompression::default(),
+            ludusavi_compression_level: None,
+            verify_backups: true,
+            backup_on_uninstall: false,
+            backup_name_template: default_backup_name_template(),
+            library_backup_schedule: None,
+            low_disk_space_mb: default_low_disk_space(),
+            explorer_context_menu: false,
+            excluded_steam_categories: Vec::new(),
+            control_pipe_enabled: false,
+            backup_key: None,
+            ludusavi_timeout_secs: default_ludusavi_timeout(),
+            backup_full_versions: 0,
+            backup_differential_versions: 0,
+            quiet_games: Vec::new(),
+            snapshot_on_game_start: false,
+            session_backup_minutes: HashMap::new(),
+            stale_backup_days: default_stale_backup_days(),
+            stale_backup_min_plays: default_stale_backup_min_plays(),
+            game_backup_paths: HashMap::new(),
+        }
+    }
+}
+
+/// Passed by the Run key entry so a launch at login can be told apart from a manual one
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Whether Windows started the app at login
+pub fn launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// "salt$key": the PIN through the same PBKDF2 as backup passphrases, so a copied config can't be
+/// tried against all 10,000 four-digit PINs in an instant
+fn pin_hash(pin: &str, salt: &str) -> String {
+    format!("{}${}", salt, backup_keys::hex(&backup_keys::derive_key(pin, salt)))
+}
+
+impl AppConfig {
+    /// Hash for read_only_pin_hash with a new random salt, so the config file doesn't hold the PIN itself
+    pub fn hash_pin(pin: &str) -> Result<String, String> {
+        Ok(pin_hash(pin, &backup_keys::new_salt()?))
+    }
+
+    /// Where a game's backups go: its own folder when one is set, otherwise the global backup path
+    pub fn backup_path_for(&self, game_name: &str) -> String {
+        self.game_backup_paths.get(game_name)
+            .filter(|path| !path.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| self.backup_path.clone())
+    }
+
+    /// The global backup path followed by every distinct per-game backup folder
+    pub fn backup_roots(&self) -> Vec<String> {
+        let mut roots = vec![self.backup_path.clone()];
+        for path in self.game_backup_paths.values().filter(|path| !path.trim().is_empty()) {
+            if !roots.contains(path) {
+                roots.push(path.clone());
+            }
+        }
+        roots
+    }
+
+    /// True when no PIN is set or the given one matches; a hash without its salt never matches
+    pub fn read_only_pin_matches(&self, pin: Option<&str>) -> bool {
+        match &self.read_only_pin_hash {
+            Some(hash) => pin.is_some_and(|pin| {
+                hash.split_once('$').is_some_and(|(salt, _)| pin_hash(pin, salt) == *hash)
+            }),
+            None => true,
+        }
+    }
+}
+
+pub struct ConfigManager {
+    config_path: PathBuf,
+    config: AppConfig,
+}
+
+impl ConfigManager {
+    pub fn new() -> Self {
+        let config_path = Self::get_config_path();
+        let config = Self::load_from_file(&config_path);
+
+        // Entries written by older versions lack the autostart flag
+        #[cfg(target_os = "windows")]
+        if config.auto_start {
+            let _ = Self::enable_auto_start();
+        }
+        // Keep the context menu pointing at this executable after the app is moved or updated
+        if config.explorer_context_menu {
+            let _ = shell_integration::set_context_menu(true);
+        }
+
+        Self { config_path, config }
+    }
+    
+    fn get_config_path() -> PathBuf {
+        let config_dir = dirs::config_dir()
+            .expect("Could not find config directory")
+            .join("steam-backup-manager");
+        
+        fs::create_dir_all(&config_dir).ok();
+        config_dir.join("config.json")
+    }
+    
+    fn load_from_file(path: &PathBuf) -> AppConfig {
+        if let Ok(contents) = fs::read_to_string(path) {
+            serde_json::from_str(&contents).unwrap_or_default()
+        } else {
+            AppConfig::default()
+        }
+    }
+    
+    fn save_to_file(&self) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(&self.config)?;
+        fs::write(&self.config_path, json)?;
+        Ok(())
+    }
+    
+    pub fn get_all(&self) -> AppConfig {
+        self.config.clone()
+    }
+    
+    pub fn set_all(&mut self, config: AppConfig) {
+        // Handle auto-start registry changes if the setting changed
+        #[cfg(target_os = "windows")]
+        {
+            if config.auto_start != self.config.auto_start {
+                if config.auto_start {
+                    let _ = Self::enable_auto_start();
+                } else {
+                    let _ = Self::disable_auto_start();
+                }
+            }
+        }
+        if config.explorer_context_menu != self.config.explorer_context_menu {
+            if let Err(e) = shell_integration::set_context_menu(config.explorer_context_menu) {
+                app_log!("[Shell] {}", e);
+            }
+        }
+
+        self.config = config;
+        self.save_to_file().ok();
+    }
+
+    #[cfg(target_os = "windows")]
+    fn enable_auto_start() -> Result<(), Box<dyn std::error::Error>> {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let run_key = hkcu.open_subkey_with_flags("Software\\Microsoft\\Windows\\CurrentVersion\\Run", KEY_WRITE)?;
+
+        // Get the current executable path
+        let exe_path = std::env::current_exe()?;
+        let exe_path_str = exe_path.to_string_lossy().to_string();
+
+        // Set the registry value
+        let command = format!("\"{}\" {}", exe_path_str, AUTOSTART_ARG);
+        run_key.set_value("Steam Backup Manager", &command)?;
+        app_log!("Auto-start enabled: {}", exe_path_str);
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn disable_auto_start() -> Result<(), Box<dyn std::error::Error>> {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let run_key = hkcu.open_subkey_with_flags("Software\\Microsoft\\Windows\\CurrentVersion\\Run", KEY_WRITE)?;
+
+        // Delete the registry value
+        run_key.delete_value("Steam Backup Manager")?;
+        app_log!("Auto-start disabled");
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn enable_auto_start() -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn disable_auto_start() -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_pin_matches() {
+        let mut config = AppConfig::default();
+        assert!(config.read_only_pin_matches(None));
+
+        config.read_only_pin_hash = Some(pin_hash("1234", "00112233445566778899aabbccddeeff"));
+        assert!(config.read_only_pin_matches(Some("1234")));
+        assert!(!config.read_only_pin_matches(Some("4321")));
+        assert!(!config.read_only_pin_matches(None));
+
+        // A bare hash has no salt to check against
+        let unsalted = pin_hash("1234", "00112233445566778899aabbccddeeff").split_once('$').unwrap().1.to_string();
+        config.read_only_pin_hash = Some(unsalted);
+        assert!(!config.read_only_pin_matches(Some("1234")));
+    }
+}
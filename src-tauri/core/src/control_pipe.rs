@@ -0,0 +1,127 @@
+use serde::Serialize;
+use std::future::Future;
+
+/// Scripts, AutoHotkey and launchers connect here, write one command per line and read one reply line
+pub const PIPE_NAME: &str = r"\\.\pipe\SteamSaveMonitor";
+
+/// A command sent over the control pipe
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    /// Stop game detection and automatic backups until resumed
+    Pause,
+    Resume,
+    /// Back up a game by Ludusavi name or Steam app id
+    Backup { game: String },
+    Status,
+}
+
+/// Reply to `status`, sent as JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct ControlStatus {
+    pub paused: bool,
+    pub monitoring: bool,
+    pub current_game: Option<String>,
+    pub app_id: Option<u32>,
+}
+
+/// Parse a command line: `pause`, `resume`, `backup <game>` or `status`
+pub fn parse_command(line: &str) -> Result<ControlCommand, String> {
+    let line = line.trim();
+    let (verb, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim().trim_matches('"');
+
+    match verb.to_ascii_lowercase().as_str() {
+        "pause" => Ok(ControlCommand::Pause),
+        "resume" => Ok(ControlCommand::Resume),
+        "status" => Ok(ControlCommand::Status),
+        "backup" if !rest.is_empty() => Ok(ControlCommand::Backup { game: rest.to_string() }),
+        "backup" => Err("Usage: backup <game name or app id>".to_string()),
+        "" => Err("Empty command".to_string()),
+        other => Err(format!("Unknown command '{}'. Use pause, resume, backup <game> or status", other)),
+    }
+}
+
+// "OK <message>" or "ERR <message>", always on one line
+fn reply(result: Result<String, String>) -> String {
+    let (prefix, message) = match result {
+        Ok(message) => ("OK", message),
+        Err(message) => ("ERR", message),
+    };
+    let message = message.replace(['\r', '\n'], " ");
+    if message.is_empty() {
+        format!("{}\n", prefix)
+    } else {
+        format!("{} {}\n", prefix, message)
+    }
+}
+
+/// Accept connections on the control pipe until the pipe can't be created. Each command is
+/// handed to `handler`, whose result is written back.
+#[cfg(target_os = "windows")]
+pub async fn serve<F, Fut>(handler: F) -> Result<(), String>
+where
+    F: Fn(ControlCommand) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<String, String>> + Send,
+{
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(PIPE_NAME)
+        .map_err(|e| format!("Failed to create control pipe: {}", e))?;
+
+    loop {
+        server.connect().await.map_err(|e| format!("Control pipe connection failed: {}", e))?;
+        let client = server;
+        // The next client gets a fresh instance while this one is served
+        server = ServerOptions::new()
+            .create(PIPE_NAME)
+            .map_err(|e| format!("Failed to create control pipe: {}", e))?;
+
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            let (reader, mut writer) = tokio::io::split(client);
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let result = match parse_command(&line) {
+                    Ok(command) => handler(command).await,
+                    Err(e) => Err(e),
+                };
+                if writer.write_all(reply(result).as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub async fn serve<F, Fut>(_handler: F) -> Result<(), String>
+where
+    F: Fn(ControlCommand) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<String, String>> + Send,
+{
+    Err("The control pipe is only available on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse_command("PAUSE\r"), Ok(ControlCommand::Pause));
+        assert_eq!(parse_command("status"), Ok(ControlCommand::Status));
+        assert_eq!(parse_command("backup \"Hollow Knight\""), Ok(ControlCommand::Backup { game: "Hollow Knight".to_string() }));
+        assert_eq!(parse_command("backup 367520"), Ok(ControlCommand::Backup { game: "367520".to_string() }));
+        assert!(parse_command("backup").is_err());
+        assert!(parse_command("restore x").is_err());
+    }
+
+    #[test]
+    fn test_reply_is_one_line() {
+        assert_eq!(reply(Ok(String::new())), "OK\n");
+        assert_eq!(reply(Err("bad\nthing".to_string())), "ERR bad thing\n");
+    }
+}
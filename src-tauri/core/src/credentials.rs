@@ -0,0 +1,69 @@
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::Security::Credentials::{
+    CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+};
+
+// Prefix for all entries we create in the Windows Credential Manager
+const TARGET_PREFIX: &str = "SteamBackupManager";
+
+fn target_name(key: &str) -> Vec<u16> {
+    format!("{}:{}", TARGET_PREFIX, key)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Store a secret (OAuth token, password...) in the Windows Credential Manager
+pub fn store_secret(key: &str, secret: &str) -> Result<(), String> {
+    let mut target = target_name(key);
+    let mut user_name: Vec<u16> = "steam-backup-manager".encode_utf16().chain(std::iter::once(0)).collect();
+    let blob = secret.as_bytes();
+
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_GENERIC,
+        TargetName: PWSTR(target.as_mut_ptr()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_ptr() as *mut u8,
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        UserName: PWSTR(user_name.as_mut_ptr()),
+        ..Default::default()
+    };
+
+    unsafe {
+        CredWriteW(&credential, 0)
+            .map_err(|e| format!("Failed to store credential: {}", e))
+    }
+}
+
+/// Read a secret previously stored with `store_secret`
+pub fn load_secret(key: &str) -> Option<String> {
+    let target = target_name(key);
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+
+    unsafe {
+        if CredReadW(PCWSTR(target.as_ptr()), CRED_TYPE_GENERIC, 0, &mut credential).is_err() || credential.is_null() {
+            return None;
+        }
+
+        let blob = std::slice::from_raw_parts(
+            (*credential).CredentialBlob,
+            (*credential).CredentialBlobSize as usize,
+        );
+        let secret = String::from_utf8(blob.to_vec()).ok();
+        CredFree(credential as *const std::ffi::c_void);
+        secret
+    }
+}
+
+/// Remove a stored secret; missing entries are not an error
+pub fn delete_secret(key: &str) -> Result<(), String> {
+    let target = target_name(key);
+
+    unsafe {
+        if CredDeleteW(PCWSTR(target.as_ptr()), CRED_TYPE_GENERIC, 0).is_err() && load_secret(key).is_some() {
+            return Err(format!("Failed to delete credential {}", key));
+        }
+    }
+
+    Ok(())
+}
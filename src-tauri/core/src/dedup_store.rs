@@ -0,0 +1,632 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use crate::ludusavi::LudusaviManager;
+use crate::restore::{self, RestorableFile};
+use crate::snapshots::{SnapshotInfo, SnapshotManager, BASE_SNAPSHOT_ID};
+use crate::app_log;
+
+/// Folder (inside the backup path) holding the deduplicated store
+pub const DEDUP_DIR: &str = ".dedup";
+
+// Content-defined chunk sizes: boundaries follow the data, so an edit only changes nearby chunks
+const MIN_CHUNK: usize = 256 * 1024;
+const MAX_CHUNK: usize = 4 * 1024 * 1024;
+const CHUNK_MASK: u64 = (1 << 20) - 1; // ~1 MiB average
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupFile {
+    pub path: String,
+    pub size: u64,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupManifest {
+    pub game_name: String,
+    pub snapshot_id: String,
+    pub created_at: i64,
+    pub files: Vec<DedupFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupSnapshotInfo {
+    pub snapshot_id: String,
+    pub created_at: i64,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DedupStats {
+    pub snapshot_count: usize,
+    pub blob_count: usize,
+    pub stored_bytes: u64,  // actual disk usage of the blobs
+    pub logical_bytes: u64, // what the snapshots would take as plain copies
+}
+
+/// Gear table for the rolling hash, generated once with splitmix64
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u64; 256];
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        for entry in table.iter_mut() {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            *entry = z ^ (z >> 31);
+        }
+        table
+    })
+}
+
+/// Id for the base folder's current contents. Ludusavi rewrites that folder in place on each new
+/// full backup, so it's told apart by when that backup was taken, e.g. "base-20240131T120000Z".
+fn base_snapshot_id(taken: i64) -> String {
+    let stamp = chrono::DateTime::from_timestamp(taken, 0).unwrap_or_default();
+    format!("base-{}", stamp.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// Where a version's file sits inside its backup, e.g. "drive-C/save.dat", whether it's stored in
+/// the game folder itself, a "backup-<timestamp>" folder or a zip archive
+fn version_relative_path(game_dir: &Path, stored: &Path) -> String {
+    let relative = match restore::stored_archive(stored) {
+        Some(archive) => stored.strip_prefix(archive).unwrap_or(stored),
+        None => {
+            let in_game_dir = stored.strip_prefix(game_dir).unwrap_or(stored);
+            let mut components = in_game_dir.components();
+            match components.next() {
+                Some(first) if first.as_os_str().to_string_lossy().starts_with("backup-") => components.as_path(),
+                _ => in_game_dir,
+            }
+        }
+    };
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Only plain relative paths may be extracted: no "..", root, or drive prefix like "C:"
+fn is_safe_relative_path(path: &str) -> bool {
+    let mut components = Path::new(path).components().peekable();
+    components.peek().is_some()
+        && components.all(|c| matches!(c, Component::Normal(part) if !part.to_string_lossy().contains(':')))
+}
+
+/// Content-addressed store: files are split into chunks stored once by SHA-256,
+/// and each snapshot is a small manifest listing the chunks of its files
+pub struct DedupStore {
+    backup_path: PathBuf,
+    root: PathBuf,
+}
+
+impl DedupStore {
+    pub fn new(backup_path: String) -> Self {
+        let backup_path = PathBuf::from(backup_path);
+        Self {
+            root: backup_path.join(DEDUP_DIR),
+            backup_path,
+        }
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.root.join("blobs").join(&hash[..2]).join(hash)
+    }
+
+    fn manifests_dir(&self, game_name: &str) -> PathBuf {
+        self.root.join("manifests").join(LudusaviManager::game_folder_name(game_name))
+    }
+
+    fn manifest_path(&self, game_name: &str, snapshot_id: &str) -> PathBuf {
+        self.manifests_dir(game_name).join(format!("{}.json", snapshot_id))
+    }
+
+    /// Add every Ludusavi version of a game that isn't in the store yet, folders and zip archives
+    /// alike; returns the ids added. Each version is chunked once, keyed by its id. The app's own
+    /// safety snapshots stay in their folder with their own retention. Ludusavi writes versions
+    /// while holding the backup slot, so run this under it too: the versions are complete then, and
+    /// garbage collection can't drop chunks a new manifest is about to use.
+    pub fn ingest_new_snapshots(&self, game_name: &str) -> Result<Vec<String>, String> {
+        let snapshots = SnapshotManager::new(self.backup_path.to_string_lossy().to_string());
+        let mut versions = snapshots.list_archive_versions(game_name);
+        versions.extend(snapshots.list_versions(game_name).unwrap_or_default());
+        if versions.is_empty() {
+            return Err(format!("No backups found for {}", game_name));
+        }
+        let game_dir = snapshots.game_dir(game_name);
+        let mut added = Vec::new();
+
+        for info in versions {
+            let (snapshot_id, created_at) = if info.id == BASE_SNAPSHOT_ID {
+                let taken = restore::full_backup_time(&game_dir, BASE_SNAPSHOT_ID).unwrap_or(info.created_at);
+                (base_snapshot_id(taken), taken)
+            } else {
+                (info.id.clone(), info.created_at)
+            };
+            if self.manifest_path(game_name, &snapshot_id).exists() {
+                continue;
+            }
+
+            let mut manifest = self.store_version(&snapshots, game_name, &info)?;
+            manifest.snapshot_id = snapshot_id;
+            manifest.created_at = created_at;
+            self.write_manifest(&manifest)?;
+            app_log!("[Dedup] Stored {} snapshot {} ({} files)", game_name, manifest.snapshot_id, manifest.files.len());
+            added.push(manifest.snapshot_id);
+        }
+
+        Ok(added)
+    }
+
+    /// Chunk every file of a Ludusavi version into the store; the manifest isn't written yet. A
+    /// differential version only holds what changed, so it's resolved against its full backup
+    /// through mapping.yaml like a restore would, and the stored snapshot stands on its own.
+    fn store_version(&self, snapshots: &SnapshotManager, game_name: &str, info: &SnapshotInfo) -> Result<DedupManifest, String> {
+        let files = match restore::restorable_files(snapshots, game_name, &info.id) {
+            Ok(files) => files,
+            Err(e) => {
+                // Without a mapping entry all we have is what's on disk
+                app_log!("[Dedup] {}, storing {} as it is", e, info.id);
+                return if info.id.ends_with(".zip") {
+                    self.store_archive(game_name, info)
+                } else {
+                    self.store_snapshot(game_name, info, &snapshots.snapshot_dir(game_name, &info.id)?)
+                };
+            }
+        };
+
+        let game_dir = snapshots.game_dir(game_name);
+        let selected: Vec<&RestorableFile> = files.iter().collect();
+        let mut archives = restore::open_archives(&selected)?;
+        let mut manifest = DedupManifest {
+            game_name: game_name.to_string(),
+            snapshot_id: info.id.clone(),
+            created_at: info.created_at,
+            files: Vec::new(),
+        };
+        for file in &files {
+            let stored = Path::new(&file.stored_path);
+            let path = version_relative_path(&game_dir, stored);
+            let (size, chunks) = match restore::stored_archive(stored) {
+                Some(archive_path) => {
+                    let archive = archives.get_mut(archive_path)
+                        .ok_or_else(|| format!("{} wasn't opened", archive_path.display()))?;
+                    let mut entry = archive.by_name(&path)
+                        .map_err(|e| format!("Failed to read {}: {}", file.stored_path, e))?;
+                    self.store_reader(&mut entry, &file.stored_path)?
+                }
+                None => self.store_file(stored)?,
+            };
+            manifest.files.push(DedupFile { path, size, chunks });
+        }
+        Ok(manifest)
+    }
+
+    /// Chunk every file of a snapshot folder into the store; the manifest isn't written yet
+    fn store_snapshot(&self, game_name: &str, info: &SnapshotInfo, root: &Path) -> Result<DedupManifest, String> {
+        let files = SnapshotManager::list_files(root, !info.id.starts_with("backup-"))?;
+
+        let mut manifest = DedupManifest {
+            game_name: game_name.to_string(),
+            snapshot_id: info.id.clone(),
+            created_at: info.created_at,
+            files: Vec::new(),
+        };
+        for relative in files.keys() {
+            let (size, chunks) = self.store_file(&root.join(relative))?;
+            manifest.files.push(DedupFile { path: relative.clone(), size, chunks });
+        }
+        Ok(manifest)
+    }
+
+    /// Chunk every file of a zip-format version into the store; the manifest isn't written yet
+    fn store_archive(&self, game_name: &str, info: &SnapshotInfo) -> Result<DedupManifest, String> {
+        let opened = fs::File::open(&info.path)
+            .map_err(|e| format!("Failed to open {}: {}", info.path, e))?;
+        let mut archive = zip::ZipArchive::new(opened)
+            .map_err(|e| format!("{} is not a readable zip: {}", info.path, e))?;
+
+        let mut manifest = DedupManifest {
+            game_name: game_name.to_string(),
+            snapshot_id: info.id.clone(),
+            created_at: info.created_at,
+            files: Vec::new(),
+        };
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)
+                .map_err(|e| format!("Failed to read {}: {}", info.path, e))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let path = entry.name().replace('\\', "/");
+            let label = format!("{} in {}", path, info.path);
+            let (size, chunks) = self.store_reader(&mut entry, &label)?;
+            manifest.files.push(DedupFile { path, size, chunks });
+        }
+        Ok(manifest)
+    }
+
+    /// Chunk a file and store any chunks we don't have yet
+    fn store_file(&self, path: &Path) -> Result<(u64, Vec<String>), String> {
+        let mut file = fs::File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        self.store_reader(&mut file, &path.display().to_string())
+    }
+
+    fn store_reader(&self, reader: &mut impl Read, label: &str) -> Result<(u64, Vec<String>), String> {
+        let gear = gear_table();
+
+        let mut chunks = Vec::new();
+        let mut current: Vec<u8> = Vec::with_capacity(MAX_CHUNK);
+        let mut hash: u64 = 0;
+        let mut size: u64 = 0;
+        let mut buffer = vec![0u8; 64 * 1024];
+
+        loop {
+            let read = reader.read(&mut buffer)
+                .map_err(|e| format!("Failed to read {}: {}", label, e))?;
+            if read == 0 {
+                break;
+            }
+            size += read as u64;
+
+            for &byte in &buffer[..read] {
+                current.push(byte);
+                hash = (hash << 1).wrapping_add(gear[byte as usize]);
+                if (current.len() >= MIN_CHUNK && hash & CHUNK_MASK == 0) || current.len() >= MAX_CHUNK {
+                    chunks.push(self.store_chunk(&current)?);
+                    current.clear();
+                    hash = 0;
+                }
+            }
+        }
+
+        if !current.is_empty() {
+            chunks.push(self.store_chunk(&current)?);
+        }
+
+        Ok((size, chunks))
+    }
+
+    fn store_chunk(&self, data: &[u8]) -> Result<String, String> {
+        let hash = format!("{:x}", Sha256::digest(data));
+        let path = self.blob_path(&hash);
+        if path.exists() {
+            return Ok(hash);
+        }
+
+        let dir = path.parent().unwrap_or(&self.root);
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        // Write under a temp name first so an interrupted backup never leaves a truncated blob
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, data)
+            .and_then(|_| fs::rename(&temp_path, &path))
+            .map_err(|e| format!("Failed to store chunk {}: {}", hash, e))?;
+        Ok(hash)
+    }
+
+    fn write_manifest(&self, manifest: &DedupManifest) -> Result<(), String> {
+        let path = self.manifest_path(&manifest.game_name, &manifest.snapshot_id);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        fs::write(&path, json)
+            .map_err(|e| format!("Failed to write manifest: {}", e))
+    }
+
+    fn read_manifest(path: &Path) -> Result<DedupManifest, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    fn all_manifests(&self) -> Vec<DedupManifest> {
+        let mut manifests = Vec::new();
+        let Ok(games) = fs::read_dir(self.root.join("manifests")) else {
+            return manifests;
+        };
+        for game in games.filter_map(|e| e.ok()) {
+            let Ok(entries) = fs::read_dir(game.path()) else { continue };
+            for entry in entries.filter_map(|e| e.ok()) {
+                if let Ok(manifest) = Self::read_manifest(&entry.path()) {
+                    manifests.push(manifest);
+                }
+            }
+        }
+        manifests
+    }
+
+    pub fn list_snapshots(&self, game_name: &str) -> Result<Vec<DedupSnapshotInfo>, String> {
+        let mut snapshots = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.manifests_dir(game_name)) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let manifest = Self::read_manifest(&entry.path())?;
+                snapshots.push(DedupSnapshotInfo {
+                    snapshot_id: manifest.snapshot_id,
+                    created_at: manifest.created_at,
+                    file_count: manifest.files.len(),
+                    total_bytes: manifest.files.iter().map(|f| f.size).sum(),
+                });
+            }
+        }
+        snapshots.sort_by_key(|s| s.created_at);
+        Ok(snapshots)
+    }
+
+    /// Rebuild a stored snapshot's files into `target_dir`
+    pub fn extract_snapshot(&self, game_name: &str, snapshot_id: &str, target_dir: &Path) -> Result<usize, String> {
+        if !SnapshotManager::is_plain_id(snapshot_id) {
+            return Err(format!("Invalid snapshot id: {}", snapshot_id));
+        }
+        let manifest = Self::read_manifest(&self.manifest_path(game_name, snapshot_id))?;
+        // Checked up front so a bad manifest doesn't leave a partly extracted snapshot behind
+        if let Some(file) = manifest.files.iter().find(|f| !is_safe_relative_path(&f.path)) {
+            return Err(format!("Refusing to extract unsafe path {}", file.path));
+        }
+
+        for file in &manifest.files {
+            let target = target_dir.join(&file.path);
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir)
+                    .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+            }
+            self.write_chunks(file, &target)?;
+        }
+
+        Ok(manifest.files.len())
+    }
+
+    // Stream a file's chunks to `target` one at a time, so large saves don't need to fit in memory
+    fn write_chunks(&self, file: &DedupFile, target: &Path) -> Result<(), String> {
+        let mut out = io::BufWriter::new(fs::File::create(target)
+            .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?);
+        for chunk in &file.chunks {
+            let mut blob = fs::File::open(self.blob_path(chunk))
+                .map_err(|e| format!("Missing chunk {} for {}: {}", chunk, file.path, e))?;
+            io::copy(&mut blob, &mut out)
+                .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        }
+        out.flush()
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+    }
+
+    /// Remove a stored snapshot and any chunks no other snapshot uses
+    pub fn delete_snapshot(&self, game_name: &str, snapshot_id: &str) -> Result<u64, String> {
+        if !SnapshotManager::is_plain_id(snapshot_id) {
+            return Err(format!("Invalid snapshot id: {}", snapshot_id));
+        }
+        fs::remove_file(self.manifest_path(game_name, snapshot_id))
+            .map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+        self.collect_garbage()
+    }
+
+    /// Delete all but the newest `keep` stored snapshots of a game, then their unused chunks; returns the bytes freed
+    pub fn prune(&self, game_name: &str, keep: usize) -> Result<u64, String> {
+        let snapshots = self.list_snapshots(game_name)?;
+        let excess = snapshots.len().saturating_sub(keep);
+        if excess == 0 {
+            return Ok(0);
+        }
+        for snapshot in &snapshots[..excess] {
+            fs::remove_file(self.manifest_path(game_name, &snapshot.snapshot_id))
+                .map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+        }
+        app_log!("[Dedup] Pruned {} old {} snapshot(s)", excess, game_name);
+        self.collect_garbage()
+    }
+
+    /// Delete unreferenced blobs; returns the bytes freed
+    pub fn collect_garbage(&self) -> Result<u64, String> {
+        let referenced: HashSet<String> = self.all_manifests()
+            .into_iter()
+            .flat_map(|m| m.files.into_iter().flat_map(|f| f.chunks))
+            .collect();
+
+        let mut freed = 0;
+        let Ok(prefixes) = fs::read_dir(self.root.join("blobs")) else {
+            return Ok(0);
+        };
+        for prefix in prefixes.filter_map(|e| e.ok()) {
+            let Ok(blobs) = fs::read_dir(prefix.path()) else { continue };
+            for blob in blobs.filter_map(|e| e.ok()) {
+                let name = blob.file_name().to_string_lossy().to_string();
+                if !referenced.contains(&name) {
+                    freed += blob.metadata().map(|m| m.len()).unwrap_or(0);
+                    let _ = fs::remove_file(blob.path());
+                }
+            }
+        }
+
+        app_log!("[Dedup] Garbage collection freed {} bytes", freed);
+        Ok(freed)
+    }
+
+    /// Re-hash every referenced chunk; returns (game, snapshot, problem) for missing or corrupted ones
+    pub fn verify_blobs(&self) -> Vec<(String, String, String)> {
+        let mut checked: HashMap<String, Option<String>> = HashMap::new();
+        let mut problems = Vec::new();
+
+        for manifest in self.all_manifests() {
+            for file in &manifest.files {
+                for chunk in &file.chunks {
+                    let problem = checked.entry(chunk.clone()).or_insert_with(|| {
+                        match fs::read(self.blob_path(chunk)) {
+                            Ok(data) if format!("{:x}", Sha256::digest(&data)) == *chunk => None,
+                            Ok(_) => Some(format!("chunk {} is corrupted", chunk)),
+                            Err(_) => Some(format!("chunk {} is missing", chunk)),
+                        }
+                    });
+                    if let Some(problem) = problem {
+                        problems.push((
+                            manifest.game_name.clone(),
+                            manifest.snapshot_id.clone(),
+                            format!("{}: {}", file.path, problem),
+                        ));
+                    }
+                }
+            }
+        }
+
+        problems
+    }
+
+    pub fn stats(&self) -> DedupStats {
+        let manifests = self.all_manifests();
+        let mut stats = DedupStats {
+            snapshot_count: manifests.len(),
+            blob_count: 0,
+            stored_bytes: 0,
+            logical_bytes: manifests.iter().flat_map(|m| m.files.iter()).map(|f| f.size).sum(),
+        };
+
+        if let Ok(prefixes) = fs::read_dir(self.root.join("blobs")) {
+            for prefix in prefixes.filter_map(|e| e.ok()) {
+                let Ok(blobs) = fs::read_dir(prefix.path()) else { continue };
+                for blob in blobs.filter_map(|e| e.ok()) {
+                    stats.blob_count += 1;
+                    stats.stored_bytes += blob.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, data) in files {
+            zip.start_file(*name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(data.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_ingest_zip_versions() {
+        let backup_path = std::env::temp_dir().join(format!("sbm-dedup-zip-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&backup_path);
+        let game_dir = backup_path.join(LudusaviManager::game_folder_name("Portal 2"));
+        fs::create_dir_all(&game_dir).unwrap();
+        fs::write(game_dir.join("mapping.yaml"), "name: Portal 2\n").unwrap();
+
+        let first = game_dir.join("backup-20240101T120000Z.zip");
+        write_zip(&first, &[("drive-C/save.dat", "first"), ("drive-C/settings.cfg", "same")]);
+        write_zip(&game_dir.join("backup-20240102T120000Z.zip"), &[("drive-C/save.dat", "second"), ("drive-C/settings.cfg", "same")]);
+
+        let store = DedupStore::new(backup_path.to_string_lossy().to_string());
+        let added = store.ingest_new_snapshots("Portal 2").unwrap();
+        assert_eq!(added, vec!["backup-20240101T120000Z.zip", "backup-20240102T120000Z.zip"]);
+
+        // Ludusavi keeps one version with the store on, so the older archive goes away on the next backup
+        fs::remove_file(&first).unwrap();
+        write_zip(&game_dir.join("backup-20240103T120000Z.zip"), &[("drive-C/save.dat", "third")]);
+        assert_eq!(store.ingest_new_snapshots("Portal 2").unwrap(), vec!["backup-20240103T120000Z.zip"]);
+        assert!(store.ingest_new_snapshots("Portal 2").unwrap().is_empty());
+
+        let stored: Vec<String> = store.list_snapshots("Portal 2").unwrap().into_iter().map(|s| s.snapshot_id).collect();
+        assert_eq!(stored, vec!["backup-20240101T120000Z.zip", "backup-20240102T120000Z.zip", "backup-20240103T120000Z.zip"]);
+
+        let target = backup_path.join("extracted");
+        assert_eq!(store.extract_snapshot("Portal 2", "backup-20240101T120000Z.zip", &target).unwrap(), 2);
+        assert_eq!(fs::read(target.join("drive-C/save.dat")).unwrap(), b"first");
+        assert_eq!(fs::read(target.join("drive-C/settings.cfg")).unwrap(), b"same");
+
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+
+    #[test]
+    fn test_ingest_differential_folder() {
+        let backup_path = std::env::temp_dir().join(format!("sbm-dedup-diff-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&backup_path);
+        let game_dir = backup_path.join(LudusaviManager::game_folder_name("Celeste"));
+        let differential = game_dir.join("backup-20240102T120000Z");
+        fs::create_dir_all(game_dir.join("drive-C/Saves")).unwrap();
+        fs::create_dir_all(differential.join("drive-C/Saves")).unwrap();
+        fs::write(game_dir.join("drive-C/Saves/save.dat"), "first").unwrap();
+        fs::write(game_dir.join("drive-C/Saves/settings.cfg"), "same").unwrap();
+        fs::write(differential.join("drive-C/Saves/save.dat"), "second").unwrap();
+        fs::write(game_dir.join("mapping.yaml"), r#"name: Celeste
+drives:
+  drive-C: "C:"
+backups:
+  - name: "."
+    when: "2024-01-01T12:00:00Z"
+    files:
+      "C:/Saves/save.dat":
+        size: 5
+      "C:/Saves/settings.cfg":
+        size: 4
+    children:
+      - name: backup-20240102T120000Z
+        when: "2024-01-02T12:00:00Z"
+        files:
+          "C:/Saves/save.dat":
+            size: 6
+          "C:/Saves/settings.cfg": ~
+"#).unwrap();
+
+        let store = DedupStore::new(backup_path.to_string_lossy().to_string());
+        let mut added = store.ingest_new_snapshots("Celeste").unwrap();
+        added.sort();
+        assert_eq!(added, vec!["backup-20240102T120000Z", "base-20240101T120000Z"]);
+        // Each version is only chunked once, the base included
+        assert!(store.ingest_new_snapshots("Celeste").unwrap().is_empty());
+
+        // The differential stands on its own: its changed file over the base, without the removed one
+        let target = backup_path.join("extracted-diff");
+        assert_eq!(store.extract_snapshot("Celeste", "backup-20240102T120000Z", &target).unwrap(), 1);
+        assert_eq!(fs::read(target.join("drive-C/Saves/save.dat")).unwrap(), b"second");
+        assert!(!target.join("drive-C/Saves/settings.cfg").exists());
+
+        let target = backup_path.join("extracted-base");
+        assert_eq!(store.extract_snapshot("Celeste", "base-20240101T120000Z", &target).unwrap(), 2);
+        assert_eq!(fs::read(target.join("drive-C/Saves/save.dat")).unwrap(), b"first");
+        assert_eq!(fs::read(target.join("drive-C/Saves/settings.cfg")).unwrap(), b"same");
+
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+
+    #[test]
+    fn test_extract_rejects_unsafe_paths() {
+        let backup_path = std::env::temp_dir().join(format!("sbm-dedup-paths-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&backup_path);
+        let store = DedupStore::new(backup_path.to_string_lossy().to_string());
+        let chunk = store.store_chunk(b"data").unwrap();
+        let target = backup_path.join("extracted");
+
+        for path in ["../escape.dat", "drive-C/../../escape.dat", "/escape.dat", "C:/escape.dat", "C:escape.dat", ""] {
+            let manifest = DedupManifest {
+                game_name: "Celeste".to_string(),
+                snapshot_id: "backup-20240101T120000Z".to_string(),
+                created_at: 0,
+                files: vec![
+                    DedupFile { path: "drive-C/save.dat".to_string(), size: 4, chunks: vec![chunk.clone()] },
+                    DedupFile { path: path.to_string(), size: 4, chunks: vec![chunk.clone()] },
+                ],
+            };
+            store.write_manifest(&manifest).unwrap();
+            assert!(store.extract_snapshot("Celeste", "backup-20240101T120000Z", &target).is_err(), "{:?} was extracted", path);
+            assert!(!target.exists());
+        }
+        for id in ["../backup-20240101T120000Z", "C:backup", ""] {
+            assert!(store.extract_snapshot("Celeste", id, &target).is_err());
+        }
+
+        let _ = fs::remove_dir_all(&backup_path);
+    }
+}
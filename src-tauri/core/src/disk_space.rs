@@ -0,0 +1,161 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::dedup_store;
+use crate::snapshots::SnapshotManager;
+use windows::core::PCWSTR;
+use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+use crate::app_log;
+
+/// Space on the volume (local drive or network share) holding a path
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub used_bytes: u64,
+}
+
+/// What get_backup_stats reports about the backup destination
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupStats {
+    pub backup_path: String,
+    pub backup_size_bytes: u64,
+    pub disk: Option<DiskUsage>,
+    pub low_space_threshold_mb: u32,
+    pub low_space: bool,
+}
+
+/// How much one game's backups take up, for the storage dashboard
+#[derive(Debug, Clone, Serialize)]
+pub struct GameStorage {
+    pub game_name: String,
+    pub backup_path: String, // root the game is backed up to, differs for games with their own folder
+    pub size_bytes: u64,
+    pub versions: usize,
+    pub last_backup_at: Option<i64>,
+    pub last_backup_age_secs: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageDashboard {
+    pub games: Vec<GameStorage>, // largest first
+    pub dedup_bytes: u64,        // deduplicated stores, which aren't split by game
+    pub total_bytes: u64,
+    pub disk: Option<DiskUsage>,
+}
+
+/// Free and total space for the volume holding `path`. Works for UNC paths, so a NAS share is
+/// measured on the NAS rather than on the local drive.
+pub fn usage(path: &str) -> Result<DiskUsage, String> {
+    // The backup folder may not exist yet; ask about the nearest folder that does
+    let existing = Path::new(path)
+        .ancestors()
+        .find(|p| p.is_dir())
+        .ok_or_else(|| format!("{} is not reachable", path))?;
+
+    let wide: Vec<u16> = existing.as_os_str().to_string_lossy()
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut free_to_caller = 0u64;
+    let mut total = 0u64;
+
+    unsafe {
+        GetDiskFreeSpaceExW(PCWSTR(wide.as_ptr()), Some(&mut free_to_caller), Some(&mut total), None)
+            .map_err(|e| format!("Failed to read free space for {}: {}", existing.display(), e))?;
+    }
+
+    Ok(DiskUsage {
+        total_bytes: total,
+        free_bytes: free_to_caller,
+        used_bytes: total.saturating_sub(free_to_caller),
+    })
+}
+
+/// Whether free space has dropped below the warning threshold. A threshold of 0 turns the warning off.
+pub fn is_low(usage: &DiskUsage, threshold_mb: u32) -> bool {
+    threshold_mb > 0 && usage.free_bytes < threshold_mb as u64 * 1024 * 1024
+}
+
+/// Total size of everything under a folder
+pub fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else { return 0 };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Size of the backups and space left on their volume. Walks the whole backup folder, so run it off the async runtime.
+pub fn backup_stats(backup_path: &str, threshold_mb: u32) -> BackupStats {
+    let disk = match usage(backup_path) {
+        Ok(disk) => Some(disk),
+        Err(e) => {
+            app_log!("[DiskSpace] {}", e);
+            None
+        }
+    };
+
+    BackupStats {
+        backup_path: backup_path.to_string(),
+        backup_size_bytes: directory_size(Path::new(backup_path)),
+        low_space: disk.as_ref().is_some_and(|d| is_low(d, threshold_mb)),
+        disk,
+        low_space_threshold_mb: threshold_mb,
+    }
+}
+
+/// Size, version count and newest backup of one game's folder under `backup_path`
+pub fn game_storage(backup_path: &str, game_name: &str, now: i64) -> GameStorage {
+    let snapshots = SnapshotManager::new(backup_path.to_string());
+    let game_dir = snapshots.game_dir(game_name);
+    let mut versions = snapshots.list_versions(game_name).unwrap_or_default();
+    // Zip-format backups are single archives next to mapping.yaml
+    versions.extend(snapshots.list_archive_versions(game_name));
+
+    let last_backup_at = versions.iter().map(|v| v.created_at).max();
+    GameStorage {
+        game_name: game_name.to_string(),
+        backup_path: backup_path.to_string(),
+        size_bytes: directory_size(&game_dir),
+        versions: versions.len(),
+        last_backup_at,
+        last_backup_age_secs: last_backup_at.map(|at| (now - at).max(0)),
+    }
+}
+
+/// Per-game usage of every game in the backup folder and in the games' own backup folders.
+/// Walks all of them, so run it off the async runtime.
+pub fn storage_dashboard(backup_path: &str, game_backup_paths: &HashMap<String, String>, now: i64) -> StorageDashboard {
+    let mut games: Vec<GameStorage> = SnapshotManager::new(backup_path.to_string())
+        .list_games()
+        .iter()
+        .map(|game_name| game_storage(backup_path, game_name, now))
+        .collect();
+    for (game_name, path) in game_backup_paths.iter().filter(|(_, path)| !path.trim().is_empty() && path.as_str() != backup_path) {
+        if SnapshotManager::new(path.clone()).game_dir(game_name).is_dir() {
+            games.push(game_storage(path, game_name, now));
+        }
+    }
+    games.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.game_name.cmp(&b.game_name)));
+
+    let mut roots: Vec<&str> = vec![backup_path];
+    for path in game_backup_paths.values().filter(|path| !path.trim().is_empty()) {
+        if !roots.contains(&path.as_str()) {
+            roots.push(path);
+        }
+    }
+    let dedup_bytes: u64 = roots.iter().map(|root| directory_size(&Path::new(root).join(dedup_store::DEDUP_DIR))).sum();
+
+    StorageDashboard {
+        total_bytes: games.iter().map(|g| g.size_bytes).sum::<u64>() + dedup_bytes,
+        dedup_bytes,
+        disk: usage(backup_path).ok(),
+        games,
+    }
+}
@@ -0,0 +1,321 @@
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cloud::{CloudDestination, CloudProvider, StorageQuota};
+use crate::bandwidth::{throttled_body, BandwidthLimiter};
+use crate::credentials;
+use crate::app_log;
+
+const AUTHORIZE_URL: &str = "https://www.dropbox.com/oauth2/authorize";
+const TOKEN_URL: &str = "https://api.dropboxapi.com/oauth2/token";
+const API_URL: &str = "https://api.dropboxapi.com/2";
+const CONTENT_URL: &str = "https://content.dropboxapi.com/2";
+// Single requests are limited to 150 MB, larger archives go through an upload session
+const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionStart {
+    session_id: String,
+}
+
+fn credential_key(destination_id: &str) -> String {
+    format!("dropbox:{}", destination_id)
+}
+
+/// Dropbox uploader (app folder access) using the authorization-code flow
+pub struct DropboxClient {
+    http_client: reqwest::Client,
+    access_token: String,
+    folder_path: String,
+    limiter: Arc<BandwidthLimiter>,
+}
+
+impl DropboxClient {
+    /// Page where the user approves access and gets a code to paste back
+    pub fn authorize_url(app_key: &str) -> String {
+        format!(
+            "{}?client_id={}&response_type=code&token_access_type=offline",
+            AUTHORIZE_URL,
+            urlencoding::encode(app_key)
+        )
+    }
+
+    /// Exchange the pasted authorization code and store the refresh token
+    pub async fn complete_authorization(
+        destination_id: &str,
+        app_key: &str,
+        app_secret: &str,
+        code: &str,
+    ) -> Result<(), String> {
+        let token: TokenResponse = reqwest::Client::new()
+            .post(TOKEN_URL)
+            .form(&[
+                ("code", code.trim()),
+                ("grant_type", "authorization_code"),
+                ("client_id", app_key),
+                ("client_secret", app_secret),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Dropbox: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+        let refresh_token = token.refresh_token.ok_or_else(|| {
+            format!(
+                "Authorization failed: {}",
+                token.error_description.or(token.error).unwrap_or_else(|| "no refresh token returned".to_string())
+            )
+        })?;
+
+        credentials::store_secret(&credential_key(destination_id), &refresh_token)?;
+        app_log!("[Dropbox] Authorized destination {}", destination_id);
+        Ok(())
+    }
+
+    /// Remove the stored refresh token for a destination
+    pub fn sign_out(destination_id: &str) -> Result<(), String> {
+        credentials::delete_secret(&credential_key(destination_id))
+    }
+
+    /// Build a client with a fresh access token for a configured destination
+    pub async fn for_destination(destination: &CloudDestination) -> Result<Self, String> {
+        let (app_key, app_secret, folder_path) = match &destination.provider {
+            CloudProvider::Dropbox { app_key, app_secret, folder_path } => (app_key, app_secret, folder_path),
+            _ => return Err(format!("{} is not a Dropbox destination", destination.name)),
+        };
+
+        let refresh_token = credentials::load_secret(&credential_key(&destination.id))
+            .ok_or_else(|| format!("{} is not connected. Sign in to Dropbox first.", destination.name))?;
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        let token: TokenResponse = http_client
+            .post(TOKEN_URL)
+            .form(&[
+                ("refresh_token", refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+                ("client_id", app_key.as_str()),
+                ("client_secret", app_secret.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Dropbox: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+        let access_token = token.access_token.ok_or_else(|| {
+            format!(
+                "Dropbox sign-in expired, please reconnect ({})",
+                token.error_description.or(token.error).unwrap_or_default()
+            )
+        })?;
+
+        // Paths are relative to the app folder and must start with a slash
+        let folder_path = format!("/{}", folder_path.trim_matches('/'));
+
+        Ok(Self {
+            http_client,
+            access_token,
+            folder_path,
+            limiter: Arc::new(BandwidthLimiter::new(destination.max_upload_kbps)),
+        })
+    }
+
+    pub async fn get_quota(&self) -> Result<StorageQuota, String> {
+        let response = self.http_client
+            .post(format!("{}/users/get_space_usage", API_URL))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to read quota: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let usage: serde_json::Value = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse quota: {}", e))?;
+
+        Ok(StorageQuota {
+            used_bytes: usage.get("used").and_then(|v| v.as_u64()).unwrap_or(0),
+            total_bytes: usage.pointer("/allocation/allocated").and_then(|v| v.as_u64()),
+        })
+    }
+
+    /// Whether an uploaded file (by id) is still in the app folder
+    pub async fn file_exists(&self, file_id: &str) -> Result<bool, String> {
+        let response = self.http_client
+            .post(format!("{}/files/get_metadata", API_URL))
+            .bearer_auth(&self.access_token)
+            .json(&serde_json::json!({ "path": file_id }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to check file: {}", e))?;
+
+        // Lookup errors come back as 409 with a `path/not_found` summary
+        if response.status().as_u16() == 409 {
+            let body = response.text().await.unwrap_or_default();
+            return if body.contains("not_found") {
+                Ok(false)
+            } else {
+                Err(format!("Dropbox error: {}", body))
+            };
+        }
+        Self::check_response(response).await.map(|_| true)
+    }
+
+    /// Upload an archive, using a chunked session when it doesn't fit in one request; returns the file id
+    pub async fn upload_file(&self, path: &Path, remote_name: &str) -> Result<String, String> {
+        let total = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read archive: {}", e))?
+            .len();
+
+        if let Ok(quota) = self.get_quota().await {
+            if let Some(limit) = quota.total_bytes {
+                if quota.used_bytes + total > limit {
+                    return Err("Not enough Dropbox storage left for this backup".to_string());
+                }
+            }
+        }
+
+        let remote_path = if self.folder_path == "/" {
+            format!("/{}", remote_name)
+        } else {
+            format!("{}/{}", self.folder_path, remote_name)
+        };
+        let commit = serde_json::json!({ "path": remote_path, "mode": "add", "autorename": true });
+
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open archive: {}", e))?;
+
+        if total <= CHUNK_SIZE {
+            let mut bytes = Vec::with_capacity(total as usize);
+            file.read_to_end(&mut bytes)
+                .map_err(|e| format!("Failed to read archive: {}", e))?;
+            let metadata = self.content_request("files/upload", &commit, bytes).await?;
+            return Self::file_id(&metadata);
+        }
+
+        let first_chunk = Self::read_chunk(&mut file, 0, CHUNK_SIZE)?;
+        let session: SessionStart = self
+            .content_request("files/upload_session/start", &serde_json::json!({ "close": false }), first_chunk)
+            .await
+            .and_then(|v| serde_json::from_value(v).map_err(|e| format!("Failed to parse upload session: {}", e)))?;
+
+        let mut offset = CHUNK_SIZE;
+        while total - offset > CHUNK_SIZE {
+            let chunk = Self::read_chunk(&mut file, offset, CHUNK_SIZE)?;
+            let cursor = serde_json::json!({
+                "cursor": { "session_id": session.session_id, "offset": offset },
+                "close": false
+            });
+            self.content_request("files/upload_session/append_v2", &cursor, chunk).await?;
+            offset += CHUNK_SIZE;
+        }
+
+        let last_chunk = Self::read_chunk(&mut file, offset, total - offset)?;
+        let finish = serde_json::json!({
+            "cursor": { "session_id": session.session_id, "offset": offset },
+            "commit": commit
+        });
+        let metadata = self.content_request("files/upload_session/finish", &finish, last_chunk).await?;
+        Self::file_id(&metadata)
+    }
+
+    /// POST to the content endpoint, retrying transient failures a few times
+    async fn content_request(&self, endpoint: &str, arg: &serde_json::Value, body: Vec<u8>) -> Result<serde_json::Value, String> {
+        let body_len = body.len() as u64;
+        let mut retries = 0;
+        loop {
+            let result = self.http_client
+                .post(format!("{}/{}", CONTENT_URL, endpoint))
+                .bearer_auth(&self.access_token)
+                .header("Dropbox-API-Arg", arg.to_string())
+                .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                .header(reqwest::header::CONTENT_LENGTH, body_len)
+                .body(throttled_body(body.clone(), self.limiter.clone()))
+                .send()
+                .await;
+
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    // append_v2 returns an empty body
+                    return Ok(response.json().await.unwrap_or(serde_json::Value::Null));
+                }
+                Ok(response) if (response.status().is_server_error() || response.status().as_u16() == 429) && retries < 3 => {
+                    retries += 1;
+                    let wait = response.headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.parse::<u64>().ok())
+                        .unwrap_or(2 * retries);
+                    tokio::time::sleep(Duration::from_secs(wait)).await;
+                }
+                Ok(response) => return Err(Self::describe_error(response).await),
+                Err(e) if retries < 3 => {
+                    app_log!("[Dropbox] {} failed, retrying: {}", endpoint, e);
+                    retries += 1;
+                    tokio::time::sleep(Duration::from_secs(2 * retries)).await;
+                }
+                Err(e) => return Err(format!("Upload failed: {}", e)),
+            }
+        }
+    }
+
+    fn read_chunk(file: &mut std::fs::File, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+        let mut chunk = vec![0u8; len as usize];
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut chunk))
+            .map_err(|e| format!("Failed to read archive: {}", e))?;
+        Ok(chunk)
+    }
+
+    fn file_id(metadata: &serde_json::Value) -> Result<String, String> {
+        metadata.get("id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Upload finished without a file id".to_string())
+    }
+
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, String> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Self::describe_error(response).await)
+        }
+    }
+
+    /// Turn Dropbox API errors into readable messages
+    async fn describe_error(response: reqwest::Response) -> String {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let summary = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v.get("error_summary").and_then(|s| s.as_str()).map(|s| s.to_string()))
+            .unwrap_or(body);
+
+        if summary.contains("insufficient_space") {
+            "Dropbox storage is full".to_string()
+        } else if summary.contains("expired_access_token") || summary.contains("invalid_access_token") {
+            "Dropbox sign-in expired, please reconnect".to_string()
+        } else if status.as_u16() == 429 {
+            "Dropbox rate limit reached, try again later".to_string()
+        } else {
+            format!("Dropbox error {}: {}", status, summary)
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::app_log;
+
+// NTFS allows 255 UTF-16 units per name; leave room for the app id, hash and timestamp suffixes
+const MAX_STEM_UNITS: usize = 150;
+// Paths at or above this length need the \\?\ prefix (MAX_PATH minus room for an 8.3 name)
+const LONG_PATH_THRESHOLD: usize = 248;
+// Records which game owns each file name in a folder
+const OWNERS_FILE: &str = ".file-names.json";
+// Names uploaded archives the way they were named before templates existed
+pub const DEFAULT_BACKUP_NAME_TEMPLATE: &str = "{game} {date}";
+
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Make a single path component safe for Windows while keeping Unicode (CJK, ™, accents...) intact
+pub fn sanitize_component(name: &str) -> String {
+    let replaced: String = name.chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            _ => c,
+        })
+        .collect();
+
+    // Collapse runs of whitespace (including full-width spaces) and drop what Windows strips anyway
+    let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut cleaned = truncate_utf16(&collapsed, MAX_STEM_UNITS)
+        .trim_end_matches(['.', ' '])
+        .to_string();
+
+    if cleaned.is_empty() {
+        cleaned = "_".to_string();
+    }
+
+    // "CON", "nul.txt" etc. can't be used as file names even with an extension
+    let base = cleaned.split('.').next().unwrap_or_default().trim_end().to_uppercase();
+    if RESERVED_NAMES.contains(&base.as_str()) {
+        cleaned.insert(0, '_');
+    }
+
+    cleaned
+}
+
+/// Cut at a character boundary so the result fits in `max_units` UTF-16 code units
+fn truncate_utf16(value: &str, max_units: usize) -> &str {
+    let mut units = 0;
+    for (index, c) in value.char_indices() {
+        units += c.len_utf16();
+        if units > max_units {
+            return &value[..index];
+        }
+    }
+    value
+}
+
+fn short_hash(value: &str) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))[..8].to_string()
+}
+
+/// File name stem for a game: the app id keeps it unique, otherwise a hash of the
+/// original name is added whenever sanitizing lost information
+pub fn game_file_stem(game_name: &str, app_id: Option<u32>) -> String {
+    let cleaned = sanitize_component(game_name);
+    match app_id {
+        Some(app_id) => format!("{} [{}]", cleaned, app_id),
+        None if cleaned != game_name => format!("{} ~{}", cleaned, short_hash(game_name)),
+        None => cleaned,
+    }
+}
+
+/// Stem used by older versions (only the reserved ASCII characters replaced), for finding existing files
+pub fn legacy_file_stem(game_name: &str) -> String {
+    game_name.chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Stem for a game given the folder's current owners; names are compared case-insensitively like NTFS does
+fn resolve_stem(owners: &HashMap<String, String>, game_name: &str, app_id: Option<u32>) -> String {
+    let stem = game_file_stem(game_name, app_id);
+    match owners.get(&stem.to_lowercase()) {
+        Some(owner) if owner != game_name => format!("{} ~{}", stem, short_hash(game_name)),
+        _ => stem,
+    }
+}
+
+fn load_owners(dir: &Path) -> HashMap<String, String> {
+    fs::read_to_string(dir.join(OWNERS_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Stem to write a game's file under in `dir`, recording the owner so another game can't overwrite it
+pub fn claim_stem(dir: &Path, game_name: &str, app_id: Option<u32>) -> String {
+    let mut owners = load_owners(dir);
+    let stem = resolve_stem(&owners, game_name, app_id);
+
+    let key = stem.to_lowercase();
+    if owners.get(&key).map(|owner| owner.as_str()) != Some(game_name) {
+        if owners.contains_key(&game_file_stem(game_name, app_id).to_lowercase()) {
+            app_log!("[Files] \"{}\" collides with another game's file name, using {}", game_name, stem);
+        }
+        owners.insert(key, game_name.to_string());
+        if fs::create_dir_all(dir).is_ok() {
+            if let Ok(json) = serde_json::to_string_pretty(&owners) {
+                let _ = fs::write(long_path(&dir.join(OWNERS_FILE)), json);
+            }
+        }
+    }
+
+    stem
+}
+
+/// Stem a game's file would have in `dir`, without claiming it
+pub fn lookup_stem(dir: &Path, game_name: &str, app_id: Option<u32>) -> String {
+    resolve_stem(&load_owners(dir), game_name, app_id)
+}
+
+/// Game that claimed `stem` in `dir`, if any
+pub fn stem_owner(dir: &Path, stem: &str) -> Option<String> {
+    load_owners(dir).remove(&stem.to_lowercase())
+}
+
+/// `\\?\` form of a long absolute path so Win32 APIs don't stop at MAX_PATH
+fn with_long_prefix(path: &str) -> String {
+    if path.len() < LONG_PATH_THRESHOLD || path.starts_with(r"\\?\") {
+        return path.to_string();
+    }
+    let path = path.replace('/', "\\");
+    match path.strip_prefix(r"\\") {
+        Some(unc) => format!(r"\\?\UNC\{}", unc),
+        None if path.as_bytes().get(1) == Some(&b':') => format!(r"\\?\{}", path),
+        None => path,
+    }
+}
+
+/// Fill a backup naming template. `{game}`, `{date}`, `{appid}` (empty when unknown) and `{trigger}`
+/// are replaced and the result made safe to use as a file name.
+pub fn render_backup_name(template: &str, game_name: &str, app_id: Option<u32>, trigger: &str, date: &str) -> String {
+    let template = if template.trim().is_empty() { DEFAULT_BACKUP_NAME_TEMPLATE } else { template };
+    // The game goes in last so a name containing "{date}" is left as it is
+    let rendered = template
+        .replace("{appid}", &app_id.map(|id| id.to_string()).unwrap_or_default())
+        .replace("{trigger}", trigger)
+        .replace("{date}", date)
+        .replace("{game}", game_name);
+    sanitize_component(&rendered)
+}
+
+pub fn long_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(with_long_prefix(&path.to_string_lossy()))
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_keeps_unicode() {
+        assert_eq!(sanitize_component("ファイナルファンタジーXIV"), "ファイナルファンタジーXIV");
+        assert_eq!(sanitize_component("Tom Clancy's Rainbow Six® Siege™"), "Tom Clancy's Rainbow Six® Siege™");
+        assert_eq!(sanitize_component("Pokémon"), "Pokémon");
+    }
+
+    #[test]
+    fn test_sanitize_replaces_reserved_characters() {
+        assert_eq!(sanitize_component("Half-Life 2: Episode One"), "Half-Life 2_ Episode One");
+        assert_eq!(sanitize_component("What?<>|*\"\\/"), "What_________");
+        assert_eq!(sanitize_component("Tab\tand\nnewline"), "Tab_and_newline");
+    }
+
+    #[test]
+    fn test_sanitize_trailing_dots_spaces_and_empty() {
+        assert_eq!(sanitize_component("Game..."), "Game");
+        assert_eq!(sanitize_component("Game   Two  "), "Game Two");
+        assert_eq!(sanitize_component("..."), "_");
+        assert_eq!(sanitize_component(""), "_");
+    }
+
+    #[test]
+    fn test_sanitize_reserved_device_names() {
+        assert_eq!(sanitize_component("CON"), "_CON");
+        assert_eq!(sanitize_component("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize_component("Console Wars"), "Console Wars");
+    }
+
+    #[test]
+    fn test_sanitize_truncates_on_char_boundary() {
+        let long_name = "超".repeat(400);
+        let cleaned = sanitize_component(&long_name);
+        assert_eq!(cleaned.encode_utf16().count(), MAX_STEM_UNITS);
+
+        // Surrogate pairs are never split
+        let emoji = "🎮".repeat(200);
+        let cleaned = sanitize_component(&emoji);
+        assert!(cleaned.encode_utf16().count() <= MAX_STEM_UNITS);
+        assert!(cleaned.chars().all(|c| c == '🎮'));
+    }
+
+    #[test]
+    fn test_stems_do_not_collide_after_sanitizing() {
+        // Both sanitize to "A_B" but must not share a file
+        assert_ne!(game_file_stem("A:B", None), game_file_stem("A?B", None));
+        assert_eq!(game_file_stem("Portal 2", None), "Portal 2");
+        assert_eq!(game_file_stem("Portal 2", Some(620)), "Portal 2 [620]");
+        assert_ne!(game_file_stem("A:B", Some(1)), game_file_stem("A:B", Some(2)));
+    }
+
+    #[test]
+    fn test_case_insensitive_collision_detection() {
+        let mut owners = HashMap::new();
+        owners.insert("portal".to_string(), "Portal".to_string());
+
+        assert_eq!(resolve_stem(&owners, "Portal", None), "Portal");
+        let other = resolve_stem(&owners, "PORTAL", None);
+        assert_ne!(other.to_lowercase(), "portal");
+        assert!(other.starts_with("PORTAL ~"));
+    }
+
+    #[test]
+    fn test_claim_stem_records_owner() {
+        let dir = std::env::temp_dir().join(format!("sbm-filenames-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(claim_stem(&dir, "Portal", None), "Portal");
+        assert_eq!(claim_stem(&dir, "Portal", None), "Portal");
+        assert_ne!(claim_stem(&dir, "portal", None), "portal");
+        assert_eq!(lookup_stem(&dir, "Portal", None), "Portal");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_legacy_stem_matches_old_behaviour() {
+        assert_eq!(legacy_file_stem("Half-Life 2: Episode One"), "Half-Life 2_ Episode One");
+        assert_eq!(legacy_file_stem("Game..."), "Game...");
+    }
+
+    #[test]
+    fn test_render_backup_name() {
+        let date = "2024-05-01_20-15-00";
+        assert_eq!(render_backup_name(DEFAULT_BACKUP_NAME_TEMPLATE, "Portal 2", Some(620), "backup", date), "Portal 2 2024-05-01_20-15-00");
+        assert_eq!(render_backup_name("{date} {game} [{appid}] {trigger}", "Portal 2", Some(620), "manual", date), "2024-05-01_20-15-00 Portal 2 [620] manual");
+        assert_eq!(render_backup_name("{game} {appid} {date}", "Half-Life 2: Episode One", None, "backup", date), "Half-Life 2_ Episode One 2024-05-01_20-15-00");
+        assert_eq!(render_backup_name("{trigger}/{game}", "{date}", None, "scheduled", date), "scheduled_{date}");
+        assert_eq!(render_backup_name("  ", "Portal 2", None, "backup", date), "Portal 2 2024-05-01_20-15-00");
+    }
+
+    #[test]
+    fn test_long_path_prefix() {
+        let short = r"C:\Users\me\Documents\file.json";
+        assert_eq!(with_long_prefix(short), short);
+
+        let long = format!(r"C:\Users\me\Documents\{}\file.json", "a".repeat(250));
+        assert_eq!(with_long_prefix(&long), format!(r"\\?\{}", long));
+        assert_eq!(with_long_prefix(&format!(r"\\?\{}", long)), format!(r"\\?\{}", long));
+
+        let unc = format!(r"\\server\share\{}", "b".repeat(250));
+        assert_eq!(with_long_prefix(&unc), format!(r"\\?\UNC\server\share\{}", "b".repeat(250)));
+
+        // Relative paths can't take the prefix
+        let relative = "c".repeat(300);
+        assert_eq!(with_long_prefix(&relative), relative);
+    }
+}
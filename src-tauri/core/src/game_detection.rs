@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use crate::app_log;
+
+/// A running game, whichever strategy found it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub name: String,
+    pub app_id: Option<u32>, // None for games matched by executable only
+    pub exe_path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    Started(GameInfo),
+    Ended(GameInfo),
+}
+
+/// One way of telling which game is running (Steam library, configured executables, ...)
+pub trait DetectionStrategy: Send {
+    fn name(&self) -> &'static str;
+    /// Strategies are polled from the highest priority down. While one has a game running,
+    /// the ones below it aren't polled, so the same game isn't reported twice.
+    fn priority(&self) -> u8;
+    /// Look at the running processes and report at most one change since the last poll
+    fn poll(&mut self) -> Option<GameEvent>;
+    /// Whether a game this strategy reported is still running
+    fn is_active(&self) -> bool;
+}
+
+/// Polls every strategy on one task and reports their games on a single channel.
+/// Polling stops when this is dropped.
+pub struct GameDetection {
+    stopped: Arc<AtomicBool>,
+}
+
+impl GameDetection {
+    pub fn start(mut strategies: Vec<Box<dyn DetectionStrategy>>, interval: Duration) -> (Self, mpsc::Receiver<GameEvent>) {
+        strategies.sort_by_key(|s| Reverse(s.priority()));
+        let names: Vec<&str> = strategies.iter().map(|s| s.name()).collect();
+        app_log!("[GameDetection] Starting with: {}", names.join(", "));
+
+        let (tx, rx) = mpsc::channel(16);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let stop_flag = stopped.clone();
+
+        tokio::spawn(async move {
+            while !stop_flag.load(Ordering::Relaxed) {
+                for event in poll_strategies(&mut strategies) {
+                    if stop_flag.load(Ordering::Relaxed) || tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                tokio::time::sleep(interval).await;
+            }
+            app_log!("[GameDetection] Stopped");
+        });
+
+        (Self { stopped }, rx)
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for GameDetection {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn poll_strategies(strategies: &mut [Box<dyn DetectionStrategy>]) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    for strategy in strategies.iter_mut() {
+        if let Some(event) = strategy.poll() {
+            events.push(event);
+        }
+        if strategy.is_active() {
+            break;
+        }
+    }
+    events
+}
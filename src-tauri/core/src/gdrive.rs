@@ -0,0 +1,411 @@
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cloud::{CloudDestination, CloudProvider, DeviceAuthorization, StorageQuota};
+use crate::bandwidth::{throttled_body, BandwidthLimiter};
+use crate::credentials;
+use crate::app_log;
+
+const DEVICE_CODE_URL: &str = "https://oauth2.googleapis.com/device/code";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const DRIVE_API: &str = "https://www.googleapis.com/drive/v3";
+const UPLOAD_API: &str = "https://www.googleapis.com/upload/drive/v3/files";
+// Only files created by this app are visible to it
+const DRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive.file";
+// Resumable upload chunks must be multiples of 256 KiB
+const CHUNK_SIZE: u64 = 32 * 256 * 1024;
+const FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveFolder {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileListResponse {
+    files: Vec<DriveFolder>,
+}
+
+fn credential_key(destination_id: &str) -> String {
+    format!("gdrive:{}", destination_id)
+}
+
+/// Google Drive uploader using the OAuth device-code flow
+pub struct GoogleDriveClient {
+    http_client: reqwest::Client,
+    access_token: String,
+    folder_id: Option<String>,
+    limiter: Arc<BandwidthLimiter>,
+}
+
+impl GoogleDriveClient {
+    /// Start the device-code flow; the user enters `user_code` at `verification_url`
+    pub async fn start_device_authorization(client_id: &str) -> Result<DeviceAuthorization, String> {
+        let response = reqwest::Client::new()
+            .post(DEVICE_CODE_URL)
+            .form(&[("client_id", client_id), ("scope", DRIVE_SCOPE)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Google: {}", e))?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Google rejected the authorization request: {}", body));
+        }
+
+        response.json::<DeviceAuthorization>()
+            .await
+            .map_err(|e| format!("Failed to parse authorization response: {}", e))
+    }
+
+    /// Poll until the user approves the device code, then store the refresh token
+    pub async fn complete_device_authorization(
+        destination_id: &str,
+        client_id: &str,
+        client_secret: &str,
+        authorization: &DeviceAuthorization,
+    ) -> Result<(), String> {
+        let http_client = reqwest::Client::new();
+        let mut interval = authorization.interval.max(5);
+        let deadline = std::time::Instant::now() + Duration::from_secs(authorization.expires_in);
+
+        while std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            let token: TokenResponse = http_client
+                .post(TOKEN_URL)
+                .form(&[
+                    ("client_id", client_id),
+                    ("client_secret", client_secret),
+                    ("device_code", authorization.device_code.as_str()),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await
+                .map_err(|e| format!("Failed to contact Google: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+            match token.error.as_deref() {
+                None => {
+                    let refresh_token = token.refresh_token
+                        .ok_or_else(|| "Google did not return a refresh token".to_string())?;
+                    credentials::store_secret(&credential_key(destination_id), &refresh_token)?;
+                    app_log!("[GoogleDrive] Authorized destination {}", destination_id);
+                    return Ok(());
+                }
+                Some("authorization_pending") => continue,
+                Some("slow_down") => interval += 5,
+                Some(error) => {
+                    return Err(format!(
+                        "Authorization failed: {}",
+                        token.error_description.unwrap_or_else(|| error.to_string())
+                    ));
+                }
+            }
+        }
+
+        Err("Authorization code expired before it was approved".to_string())
+    }
+
+    /// Remove the stored refresh token for a destination
+    pub fn sign_out(destination_id: &str) -> Result<(), String> {
+        credentials::delete_secret(&credential_key(destination_id))
+    }
+
+    /// Build a client with a fresh access token for a configured destination
+    pub async fn for_destination(destination: &CloudDestination) -> Result<Self, String> {
+        let (client_id, client_secret, folder_id) = match &destination.provider {
+            CloudProvider::GoogleDrive { client_id, client_secret, folder_id } => (client_id, client_secret, folder_id),
+            _ => return Err(format!("{} is not a Google Drive destination", destination.name)),
+        };
+
+        let refresh_token = credentials::load_secret(&credential_key(&destination.id))
+            .ok_or_else(|| format!("{} is not connected. Sign in to Google Drive first.", destination.name))?;
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        let token: TokenResponse = http_client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Google: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+        let access_token = token.access_token.ok_or_else(|| {
+            format!(
+                "Google Drive sign-in expired, please reconnect ({})",
+                token.error_description.or(token.error).unwrap_or_default()
+            )
+        })?;
+
+        Ok(Self {
+            http_client,
+            access_token,
+            folder_id: folder_id.clone(),
+            limiter: Arc::new(BandwidthLimiter::new(destination.max_upload_kbps)),
+        })
+    }
+
+    /// Folders this app can see (drive.file scope only exposes app-created folders)
+    pub async fn list_folders(&self) -> Result<Vec<DriveFolder>, String> {
+        let query = format!("mimeType='{}' and trashed=false", FOLDER_MIME_TYPE);
+        let response = self.http_client
+            .get(format!("{}/files", DRIVE_API))
+            .bearer_auth(&self.access_token)
+            .query(&[("q", query.as_str()), ("fields", "files(id,name)")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list folders: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let list: FileListResponse = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse folder list: {}", e))?;
+        Ok(list.files)
+    }
+
+    pub async fn create_folder(&self, name: &str) -> Result<DriveFolder, String> {
+        let response = self.http_client
+            .post(format!("{}/files", DRIVE_API))
+            .bearer_auth(&self.access_token)
+            .query(&[("fields", "id,name")])
+            .json(&serde_json::json!({ "name": name, "mimeType": FOLDER_MIME_TYPE }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to create folder: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        response.json()
+            .await
+            .map_err(|e| format!("Failed to parse folder response: {}", e))
+    }
+
+    pub async fn get_quota(&self) -> Result<StorageQuota, String> {
+        let response = self.http_client
+            .get(format!("{}/about", DRIVE_API))
+            .bearer_auth(&self.access_token)
+            .query(&[("fields", "storageQuota")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to read quota: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let about: serde_json::Value = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse quota: {}", e))?;
+
+        // Drive reports quota values as strings
+        let read = |field: &str| {
+            about.get("storageQuota")
+                .and_then(|q| q.get(field))
+                .and_then(|v| v.as_str())
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+
+        Ok(StorageQuota {
+            used_bytes: read("usage").unwrap_or(0),
+            total_bytes: read("limit"),
+        })
+    }
+
+    /// Whether an uploaded file is still there (not deleted or in the trash)
+    pub async fn file_exists(&self, file_id: &str) -> Result<bool, String> {
+        let response = self.http_client
+            .get(format!("{}/files/{}", DRIVE_API, urlencoding::encode(file_id)))
+            .bearer_auth(&self.access_token)
+            .query(&[("fields", "id,trashed")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to check file: {}", e))?;
+
+        if response.status().as_u16() == 404 {
+            return Ok(false);
+        }
+        let response = Self::check_response(response).await?;
+        let file: serde_json::Value = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse file metadata: {}", e))?;
+        Ok(!file.get("trashed").and_then(|v| v.as_bool()).unwrap_or(false))
+    }
+
+    /// Resumable upload; returns the Drive file id
+    pub async fn upload_file(&self, path: &Path, remote_name: &str) -> Result<String, String> {
+        let total = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read archive: {}", e))?
+            .len();
+
+        // Check quota up front so users get a clear error instead of a failed chunk
+        if let Ok(quota) = self.get_quota().await {
+            if let Some(limit) = quota.total_bytes {
+                if quota.used_bytes + total > limit {
+                    return Err("Not enough Google Drive storage left for this backup".to_string());
+                }
+            }
+        }
+
+        let mut metadata = serde_json::json!({ "name": remote_name });
+        if let Some(ref folder_id) = self.folder_id {
+            metadata["parents"] = serde_json::json!([folder_id]);
+        }
+
+        let response = self.http_client
+            .post(UPLOAD_API)
+            .bearer_auth(&self.access_token)
+            .query(&[("uploadType", "resumable")])
+            .header("X-Upload-Content-Type", "application/zip")
+            .header("X-Upload-Content-Length", total.to_string())
+            .json(&metadata)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to start upload: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let session_url = response.headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Google Drive did not return an upload session".to_string())?;
+
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open archive: {}", e))?;
+        let mut offset: u64 = 0;
+        let mut retries = 0;
+
+        while offset < total {
+            let chunk_len = CHUNK_SIZE.min(total - offset);
+            let mut chunk = vec![0u8; chunk_len as usize];
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.read_exact(&mut chunk))
+                .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+            let result = self.http_client
+                .put(&session_url)
+                .header(reqwest::header::CONTENT_RANGE, format!("bytes {}-{}/{}", offset, offset + chunk_len - 1, total))
+                .header(reqwest::header::CONTENT_LENGTH, chunk_len)
+                .body(throttled_body(chunk, self.limiter.clone()))
+                .send()
+                .await;
+
+            match result {
+                Ok(response) if response.status().as_u16() == 308 => {
+                    // Without a Range header Drive has received nothing yet, so start over from 0
+                    let confirmed = Self::confirmed_offset(&response).unwrap_or(0);
+                    if confirmed > offset {
+                        retries = 0;
+                    } else if retries < 3 {
+                        retries += 1;
+                    } else {
+                        return Err(format!("Google Drive kept the upload at {} of {} bytes", confirmed, total));
+                    }
+                    offset = confirmed;
+                }
+                Ok(response) if response.status().is_success() => {
+                    let file_info: serde_json::Value = response.json()
+                        .await
+                        .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+                    return file_info.get("id")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| "Upload finished without a file id".to_string());
+                }
+                Ok(response) if response.status().is_server_error() && retries < 3 => {
+                    app_log!("[GoogleDrive] Chunk upload failed with {}, resuming", response.status());
+                    retries += 1;
+                    tokio::time::sleep(Duration::from_secs(2 * retries)).await;
+                    offset = self.query_upload_offset(&session_url, total).await.unwrap_or(offset);
+                }
+                Ok(response) => return Err(Self::describe_error(response).await),
+                Err(e) if retries < 3 => {
+                    app_log!("[GoogleDrive] Chunk upload failed, resuming: {}", e);
+                    retries += 1;
+                    tokio::time::sleep(Duration::from_secs(2 * retries)).await;
+                    offset = self.query_upload_offset(&session_url, total).await.unwrap_or(offset);
+                }
+                Err(e) => return Err(format!("Upload failed: {}", e)),
+            }
+        }
+
+        // Zero-byte archives finish with the final empty request
+        let response = self.http_client
+            .put(&session_url)
+            .header(reqwest::header::CONTENT_RANGE, format!("bytes */{}", total))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to finish upload: {}", e))?;
+        let response = Self::check_response(response).await?;
+        let file_info: serde_json::Value = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+        file_info.get("id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Upload finished without a file id".to_string())
+    }
+
+    /// Ask the session how much data it has received so far
+    async fn query_upload_offset(&self, session_url: &str, total: u64) -> Option<u64> {
+        let response = self.http_client
+            .put(session_url)
+            .header(reqwest::header::CONTENT_RANGE, format!("bytes */{}", total))
+            .send()
+            .await
+            .ok()?;
+        Self::confirmed_offset(&response).or(Some(0))
+    }
+
+    /// Parse the "Range: bytes=0-N" header of a 308 response
+    fn confirmed_offset(response: &reqwest::Response) -> Option<u64> {
+        response.headers()
+            .get(reqwest::header::RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit('-').next())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|last| last + 1)
+    }
+
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, String> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Self::describe_error(response).await)
+        }
+    }
+
+    /// Turn Drive API errors into readable messages (quota problems in particular)
+    async fn describe_error(response: reqwest::Response) -> String {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let reason = body.pointer("/error/errors/0/reason").and_then(|v| v.as_str()).unwrap_or("");
+        let message = body.pointer("/error/message").and_then(|v| v.as_str()).unwrap_or("");
+
+        match reason {
+            "storageQuotaExceeded" => "Google Drive storage is full".to_string(),
+            "userRateLimitExceeded" | "rateLimitExceeded" => "Google Drive rate limit reached, try again later".to_string(),
+            _ => format!("Google Drive error {}: {}", status, message),
+        }
+    }
+}
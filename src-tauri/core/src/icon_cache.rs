@@ -0,0 +1,104 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// URI scheme the app registers to serve cached icons to the webview
+pub const ICON_SCHEME: &str = "icon";
+
+pub fn icons_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("icons")
+}
+
+// Named after the URL's hash so every URL maps to a safe, stable file name
+fn file_name(url: &str) -> String {
+    let ext = if url.ends_with(".png") { "png" } else { "jpg" };
+    format!("{:x}.{}", Sha256::digest(url.as_bytes()), ext)
+}
+
+/// Cached file name of an icon, if it has been downloaded
+pub fn cached_name(url: &str) -> Option<String> {
+    let name = file_name(url);
+    icons_dir().join(&name).is_file().then_some(name)
+}
+
+/// Bytes the icon cache takes up
+pub fn cache_size() -> u64 {
+    let Ok(entries) = fs::read_dir(icons_dir()) else { return 0 };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Download an icon unless it's already cached. Returns the cached file name.
+pub async fn fetch(url: &str) -> Result<String, String> {
+    let name = file_name(url);
+    let path = icons_dir().join(&name);
+    if path.exists() {
+        return Ok(name);
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    // Fetch the image from Steam CDN with retries
+    let mut last_error = String::new();
+    for attempt in 1..=3 {
+        match client.get(url).send().await.and_then(|r| r.error_for_status()) {
+            Ok(response) => {
+                let bytes = response
+                    .bytes()
+                    .await
+                    .map_err(|e| format!("Failed to read icon bytes: {}", e))?;
+
+                fs::create_dir_all(icons_dir()).map_err(|e| format!("Failed to create icon cache: {}", e))?;
+                // Written under another name first so a half-written icon is never served
+                let partial = path.with_extension("part");
+                fs::write(&partial, &bytes).map_err(|e| format!("Failed to cache icon: {}", e))?;
+                fs::rename(&partial, &path).map_err(|e| format!("Failed to cache icon: {}", e))?;
+                return Ok(name);
+            }
+            Err(e) => {
+                last_error = format!("Attempt {}/3 failed: {}", attempt, e);
+                if attempt < 3 {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                }
+            }
+        }
+    }
+
+    Err(format!("Failed to fetch icon after 3 attempts: {}", last_error))
+}
+
+/// Bytes and MIME type of a cached icon. Only names `fetch` hands out are accepted.
+pub fn read(name: &str) -> Option<(Vec<u8>, &'static str)> {
+    let (hash, ext) = name.split_once('.')?;
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mime = match ext {
+        "png" => "image/png",
+        "jpg" => "image/jpeg",
+        _ => return None,
+    };
+    fs::read(icons_dir().join(name)).ok().map(|bytes| (bytes, mime))
+}
+
+/// Address the webview loads a cached icon from
+pub fn icon_url(name: &str) -> String {
+    // WebView2 serves custom schemes as http://<scheme>.localhost
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}", ICON_SCHEME, name)
+    } else {
+        format!("{}://localhost/{}", ICON_SCHEME, name)
+    }
+}
@@ -0,0 +1,325 @@
+use hmac::{Hmac, Mac};
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use crate::achievement_sync;
+use crate::backup_keys::hex;
+use crate::locks::LockExt;
+use crate::achievements::{Achievement, AchievementDatabase};
+use crate::snapshots::SnapshotManager;
+use crate::app_log;
+
+const SERVICE_TYPE: &str = "_steambackup._tcp.local.";
+// Messages older than this are rejected so captured ones can't be replayed later
+const MAX_MESSAGE_AGE_SECS: i64 = 300;
+// Anyone on the LAN can connect before proving they know the secret, so each connection gets a
+// bounded message, a deadline to send it, and only a few may be open at once
+const MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+const MESSAGE_READ_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_OPEN_CONNECTIONS: usize = 4;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanSyncSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub shared_secret: String, // same on every PC, only peers that know it are accepted
+}
+
+fn default_port() -> u16 {
+    47810
+}
+
+impl Default for LanSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            shared_secret: String::new(),
+        }
+    }
+}
+
+/// Latest backup state of one game, as advertised to peers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupIndexEntry {
+    pub game_name: String,
+    pub latest_backup: i64,
+    pub snapshot_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanPeer {
+    pub machine: String,
+    pub address: String,
+    pub last_seen: i64,
+    pub backups: Vec<BackupIndexEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum PeerPayload {
+    Achievements(Vec<Achievement>),
+    BackupIndex(Vec<BackupIndexEntry>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PeerMessage {
+    machine: String,
+    sent_at: i64,
+    payload: serde_json::Value,
+    signature: String,
+}
+
+fn message_mac(secret: &str, machine: &str, sent_at: i64, payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}\n{}\n{}", machine, sent_at, payload).as_bytes());
+    mac
+}
+
+fn sign(secret: &str, machine: &str, sent_at: i64, payload: &str) -> String {
+    hex(&message_mac(secret, machine, sent_at, payload).finalize().into_bytes())
+}
+
+/// Check a message's signature in constant time, so response timing doesn't help forge one
+fn verify(secret: &str, machine: &str, sent_at: i64, payload: &str, signature: &str) -> bool {
+    let bytes: Option<Vec<u8>> = (0..signature.len())
+        .step_by(2)
+        .map(|i| signature.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect();
+    bytes.is_some_and(|bytes| message_mac(secret, machine, sent_at, payload).verify_slice(&bytes).is_ok())
+}
+
+/// Backup index of every game in the backup folder
+pub fn build_backup_index(backup_path: &str) -> Vec<BackupIndexEntry> {
+    let snapshots = SnapshotManager::new(backup_path.to_string());
+    snapshots.list_games()
+        .into_iter()
+        .filter_map(|game_name| {
+            let list = snapshots.list_snapshots(&game_name).ok()?;
+            Some(BackupIndexEntry {
+                latest_backup: list.iter().map(|s| s.created_at).max().unwrap_or(0),
+                snapshot_count: list.len(),
+                game_name,
+            })
+        })
+        .collect()
+}
+
+/// Finds other instances over mDNS and exchanges signed updates with them over TCP
+pub struct LanSync {
+    settings: LanSyncSettings,
+    machine: String,
+    peers: Arc<Mutex<HashMap<String, LanPeer>>>,
+    daemon: ServiceDaemon,
+    service_name: String,
+    listener: tokio::task::JoinHandle<()>,
+}
+
+impl LanSync {
+    pub async fn start(settings: LanSyncSettings, db_path: PathBuf) -> Result<Arc<Self>, String> {
+        if settings.shared_secret.len() < 8 {
+            return Err("LAN sync needs a shared secret of at least 8 characters".to_string());
+        }
+
+        let machine = achievement_sync::machine_name();
+        let listener = TcpListener::bind(("0.0.0.0", settings.port))
+            .await
+            .map_err(|e| format!("Failed to listen on port {}: {}", settings.port, e))?;
+
+        let daemon = ServiceDaemon::new().map_err(|e| format!("Failed to start mDNS: {}", e))?;
+        let host_name = format!("{}.local.", machine.to_lowercase());
+        let properties = [("machine", machine.as_str())];
+        let service = ServiceInfo::new(SERVICE_TYPE, &machine, &host_name, "", settings.port, &properties[..])
+            .map_err(|e| format!("Failed to describe mDNS service: {}", e))?
+            .enable_addr_auto();
+        let service_name = service.get_fullname().to_string();
+        daemon.register(service).map_err(|e| format!("Failed to announce on the LAN: {}", e))?;
+        let browser = daemon.browse(SERVICE_TYPE).map_err(|e| format!("Failed to browse the LAN: {}", e))?;
+
+        let peers: Arc<Mutex<HashMap<String, LanPeer>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        // Discovery events arrive on a blocking channel
+        let peers_clone = peers.clone();
+        let own_name = machine.clone();
+        std::thread::spawn(move || {
+            while let Ok(event) = browser.recv() {
+                match event {
+                    ServiceEvent::ServiceResolved(info) => {
+                        let Some(peer_machine) = info.get_property_val_str("machine").map(|s| s.to_string()) else {
+                            continue;
+                        };
+                        if peer_machine == own_name {
+                            continue;
+                        }
+                        let Some(ip) = info.get_addresses().iter().find(|ip| ip.is_ipv4()).copied() else {
+                            continue;
+                        };
+                        let address = SocketAddr::new(ip, info.get_port()).to_string();
+                        app_log!("[LanSync] Found {} at {}", peer_machine, address);
+
+                        let mut peers = peers_clone.lock_or_recover();
+                        let peer = peers.entry(peer_machine.clone()).or_insert_with(|| LanPeer {
+                            machine: peer_machine,
+                            address: address.clone(),
+                            last_seen: 0,
+                            backups: Vec::new(),
+                        });
+                        peer.address = address;
+                        peer.last_seen = chrono::Utc::now().timestamp();
+                    }
+                    ServiceEvent::ServiceRemoved(_, fullname) => {
+                        let mut peers = peers_clone.lock_or_recover();
+                        peers.retain(|name, _| !fullname.starts_with(&format!("{}.", name)));
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        let accept_peers = peers.clone();
+        let accept_settings = settings.clone();
+        let accept_db = db_path;
+        let open_connections = Arc::new(tokio::sync::Semaphore::new(MAX_OPEN_CONNECTIONS));
+        let listener = tokio::spawn(async move {
+            loop {
+                let (stream, remote) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        // e.g. out of file handles; retrying at once would only spin
+                        app_log!("[LanSync] Failed to accept a connection: {}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+                let Ok(permit) = open_connections.clone().try_acquire_owned() else {
+                    app_log!("[LanSync] Too many open connections, dropping {}", remote);
+                    continue;
+                };
+                let peers = accept_peers.clone();
+                let settings = accept_settings.clone();
+                let db_path = accept_db.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::handle_connection(stream, &settings, &db_path, &peers).await {
+                        app_log!("[LanSync] Rejected message from {}: {}", remote, e);
+                    }
+                    drop(permit);
+                });
+            }
+        });
+
+        app_log!("[LanSync] Listening on port {} as {}", settings.port, machine);
+        Ok(Arc::new(Self {
+            settings,
+            machine,
+            peers,
+            daemon,
+            service_name,
+            listener,
+        }))
+    }
+
+    pub fn stop(&self) {
+        self.listener.abort();
+        let _ = self.daemon.unregister(&self.service_name);
+        let _ = self.daemon.shutdown();
+        app_log!("[LanSync] Stopped");
+    }
+
+    pub fn peers(&self) -> Vec<LanPeer> {
+        let mut peers: Vec<LanPeer> = self.peers.lock_or_recover().values().cloned().collect();
+        peers.sort_by(|a, b| a.machine.cmp(&b.machine));
+        peers
+    }
+
+    /// Send an update to every known peer; returns how many accepted it
+    pub async fn push(&self, payload: &PeerPayload) -> usize {
+        let payload = match serde_json::to_value(payload) {
+            Ok(value) => value,
+            Err(_) => return 0,
+        };
+        let sent_at = chrono::Utc::now().timestamp();
+        let signature = sign(&self.settings.shared_secret, &self.machine, sent_at, &payload.to_string());
+        let message = PeerMessage {
+            machine: self.machine.clone(),
+            sent_at,
+            payload,
+            signature,
+        };
+        let Ok(mut line) = serde_json::to_vec(&message) else { return 0 };
+        line.push(b'\n');
+
+        let mut delivered = 0;
+        for peer in self.peers() {
+            let result = tokio::time::timeout(Duration::from_secs(10), async {
+                let mut stream = TcpStream::connect(&peer.address).await?;
+                stream.write_all(&line).await?;
+                stream.shutdown().await
+            }).await;
+
+            match result {
+                Ok(Ok(())) => delivered += 1,
+                Ok(Err(e)) => app_log!("[LanSync] Could not reach {}: {}", peer.machine, e),
+                Err(_) => app_log!("[LanSync] Timed out sending to {}", peer.machine),
+            }
+        }
+        delivered
+    }
+
+    async fn handle_connection(
+        stream: TcpStream,
+        settings: &LanSyncSettings,
+        db_path: &Path,
+        peers: &Arc<Mutex<HashMap<String, LanPeer>>>,
+    ) -> Result<(), String> {
+        let mut reader = BufReader::new(stream).take(MAX_MESSAGE_BYTES as u64);
+        let mut line = String::new();
+        tokio::time::timeout(MESSAGE_READ_TIMEOUT, reader.read_line(&mut line))
+            .await
+            .map_err(|_| "timed out waiting for the message".to_string())?
+            .map_err(|e| e.to_string())?;
+
+        let message: PeerMessage = serde_json::from_str(&line).map_err(|e| format!("Invalid message: {}", e))?;
+        if !verify(&settings.shared_secret, &message.machine, message.sent_at, &message.payload.to_string(), &message.signature) {
+            return Err("bad signature (shared secret differs?)".to_string());
+        }
+        if (chrono::Utc::now().timestamp() - message.sent_at).abs() > MAX_MESSAGE_AGE_SECS {
+            return Err("message too old, check both clocks".to_string());
+        }
+
+        let payload: PeerPayload = serde_json::from_value(message.payload).map_err(|e| format!("Unknown payload: {}", e))?;
+        match payload {
+            PeerPayload::Achievements(achievements) => {
+                let db_path = db_path.to_path_buf();
+                let count = achievements.len();
+                let changed = tokio::task::spawn_blocking(move || {
+                    let db = AchievementDatabase::new(db_path)?;
+                    achievement_sync::merge_into(&db, achievements).map(|(inserted, updated)| inserted + updated)
+                })
+                .await
+                .map_err(|e| e.to_string())??;
+                app_log!("[LanSync] {} sent {} achievement(s), {} changed here", message.machine, count, changed);
+            }
+            PeerPayload::BackupIndex(backups) => {
+                let mut peers = peers.lock_or_recover();
+                if let Some(peer) = peers.get_mut(&message.machine) {
+                    peer.backups = backups;
+                    peer.last_seen = chrono::Utc::now().timestamp();
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Monitoring, backup, achievement and save-scanning logic shared by the desktop app and other frontends.
+//! Nothing in here depends on Tauri; the app crate only wires these modules to commands and windows.
+
+// Shared helpers
+pub mod locks;
+pub mod logging;
+
+// Settings
+pub mod config;
+pub mod setup_wizard;
+pub mod credentials;
+pub mod backup_keys;
+pub mod shell_integration;
+pub mod control_pipe;
+pub mod steam_accounts;
+
+// Game and system monitoring
+pub mod game_detection;
+pub mod steam_monitor;
+pub mod steam_categories;
+pub mod steam_appinfo;
+pub mod process_monitor;
+pub mod save_watcher;
+pub mod supervisor;
+pub mod power_events;
+
+// Backups, restores and storage
+pub mod ludusavi;
+pub mod ludusavi_config;
+pub mod filenames;
+pub mod snapshots;
+pub mod dedup_store;
+pub mod verification;
+pub mod backup_freshness;
+pub mod backup_schedule;
+pub mod restore;
+pub mod user_folders;
+pub mod cloud;
+pub mod cloud_conflicts;
+pub mod gdrive;
+pub mod onedrive;
+pub mod dropbox;
+pub mod s3;
+pub mod network;
+pub mod rclone;
+pub mod bandwidth;
+pub mod disk_space;
+pub mod syncthing;
+pub mod lan_sync;
+
+// Achievements
+pub mod achievements;
+pub mod achievement_scanner;
+pub mod librarycache;
+pub mod steam_achievements;
+pub mod achievement_sources;
+pub mod achievement_sync;
+pub mod achievement_export;
+pub mod achievement_backups;
+pub mod achievement_dlc;
+pub mod achievement_screenshots;
+pub mod completion_order;
+pub mod unlock_stats;
+pub mod session_recap;
+pub mod activity_timeline;
+pub mod milestones;
+pub mod sound_packs;
+pub mod icon_cache;
+
+// Save location scanning
+pub mod save_discovery;
+
+// User automation
+pub mod scripting;
+pub mod webhooks;
+
+// Self-update safety net
+pub mod update_rollback;
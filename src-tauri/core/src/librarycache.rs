@@ -0,0 +1,180 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Lists of achievements Steam keeps in the "achievements" section, with whether an entry
+/// counts as achieved when it has no bAchieved of its own
+const ACHIEVEMENT_LISTS: [(&str, bool); 4] = [
+    ("vecHighlight", false),
+    ("vecUnachieved", false),
+    ("vecAchievedHidden", true),
+    ("vecAchieved", true),
+];
+
+/// How a librarycache/<appid>.json file is laid out; it changed between Steam client versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryCacheFormat {
+    /// `[["achievements", {"version": n, "data": {...}}], ...]`
+    PairArray,
+    /// `{"achievements": {"version": n, "data": {...}}, ...}`
+    Object,
+}
+
+impl std::fmt::Display for LibraryCacheFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryCacheFormat::PairArray => write!(f, "section array"),
+            LibraryCacheFormat::Object => write!(f, "section object"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedAchievement {
+    pub achieved: bool,
+    pub unlock_time: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LibraryCache {
+    pub format: LibraryCacheFormat,
+    pub achievements: HashMap<String, CachedAchievement>, // achievement api name -> state
+}
+
+impl LibraryCache {
+    /// Unlocked achievements with their unlock time, when Steam recorded one
+    pub fn unlocked(&self) -> impl Iterator<Item = (&String, Option<i64>)> {
+        self.achievements.iter()
+            .filter(|(_, achievement)| achievement.achieved)
+            .map(|(id, achievement)| (id, achievement.unlock_time))
+    }
+}
+
+pub fn read(path: &Path) -> Result<LibraryCache, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read librarycache file: {}", e))?;
+    parse(&contents).map_err(|e| format!("{} ({})", e, path.display()))
+}
+
+/// Parse a librarycache file in any of the known layouts
+pub fn parse(contents: &str) -> Result<LibraryCache, String> {
+    let json: Value = serde_json::from_str(contents)
+        .map_err(|e| format!("Failed to parse librarycache JSON: {}", e))?;
+
+    let (format, section) = match &json {
+        Value::Array(sections) => {
+            let section = sections.iter()
+                .filter_map(|item| item.as_array())
+                .find(|pair| pair.first().and_then(|key| key.as_str()) == Some("achievements"))
+                .and_then(|pair| pair.get(1));
+            let section = section.ok_or_else(|| {
+                let keys: Vec<&str> = sections.iter()
+                    .filter_map(|item| item.as_array()?.first()?.as_str())
+                    .collect();
+                format!("No achievements section in the librarycache file (sections: {})", list_or_none(&keys))
+            })?;
+            (LibraryCacheFormat::PairArray, section)
+        }
+        Value::Object(sections) => {
+            let section = sections.get("achievements").ok_or_else(|| {
+                let keys: Vec<&str> = sections.keys().map(String::as_str).collect();
+                format!("No achievements section in the librarycache file (sections: {})", list_or_none(&keys))
+            })?;
+            (LibraryCacheFormat::Object, section)
+        }
+        _ => return Err("Unrecognised librarycache layout: expected an array or object of sections".to_string()),
+    };
+
+    let data = section_data(section)?;
+    let lists: Vec<(&Vec<Value>, bool)> = ACHIEVEMENT_LISTS.iter()
+        .filter_map(|(name, achieved_by_default)| Some((data.get(*name)?.as_array()?, *achieved_by_default)))
+        .collect();
+    if lists.is_empty() {
+        let keys: Vec<&str> = data.as_object().map(|o| o.keys().map(String::as_str).collect()).unwrap_or_default();
+        return Err(format!(
+            "The achievements section has none of the known achievement lists (found: {})",
+            list_or_none(&keys)
+        ));
+    }
+
+    let mut achievements: HashMap<String, CachedAchievement> = HashMap::new();
+    for (list, achieved_by_default) in lists {
+        for entry in list {
+            let Some(id) = ["strID", "id", "name"].iter().find_map(|key| entry.get(*key)?.as_str()) else { continue };
+            let achieved = entry.get("bAchieved").or_else(|| entry.get("achieved"))
+                .and_then(json_bool)
+                .unwrap_or(achieved_by_default);
+            let unlock_time = entry.get("rtUnlocked").or_else(|| entry.get("unlock_time"))
+                .and_then(Value::as_i64)
+                .filter(|&t| t > 0);
+
+            // An achieved entry wins over the same achievement listed as unachieved elsewhere
+            let cached = CachedAchievement { achieved, unlock_time: if achieved { unlock_time } else { None } };
+            match achievements.get(id) {
+                Some(existing) if existing.achieved && !achieved => {}
+                _ => {
+                    achievements.insert(id.to_string(), cached);
+                }
+            }
+        }
+    }
+
+    Ok(LibraryCache { format, achievements })
+}
+
+// The section's payload: usually under "data", sometimes stored as a JSON string or inline
+fn section_data(section: &Value) -> Result<Value, String> {
+    let data = section.get("data").unwrap_or(section);
+    match data {
+        Value::String(encoded) => serde_json::from_str(encoded)
+            .map_err(|e| format!("The achievements section holds a string that isn't JSON: {}", e)),
+        Value::Object(_) => Ok(data.clone()),
+        _ => Err("The achievements section has no data object".to_string()),
+    }
+}
+
+fn json_bool(value: &Value) -> Option<bool> {
+    value.as_bool().or_else(|| value.as_i64().map(|n| n != 0))
+}
+
+fn list_or_none(keys: &[&str]) -> String {
+    if keys.is_empty() {
+        "none".to_string()
+    } else {
+        keys.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layouts() {
+        let pair_array = r#"[["friends", {"data": {}}], ["achievements", {"version": 2, "data": {
+            "vecHighlight": [{"strID": "WIN", "bAchieved": true, "rtUnlocked": 1700000000}, {"strID": "LOSE", "bAchieved": false}],
+            "vecUnachieved": [{"strID": "HARD"}],
+            "vecAchievedHidden": [{"strID": "SECRET", "rtUnlocked": 1700000500}]
+        }}]]"#;
+        let cache = parse(pair_array).unwrap();
+        assert_eq!(cache.format, LibraryCacheFormat::PairArray);
+        assert_eq!(cache.achievements.len(), 4);
+        assert_eq!(cache.achievements["WIN"], CachedAchievement { achieved: true, unlock_time: Some(1700000000) });
+        assert!(!cache.achievements["HARD"].achieved);
+        assert!(cache.achievements["SECRET"].achieved);
+
+        let object = r#"{"achievements": {"version": 3, "data": "{\"vecAchieved\": [{\"id\": \"WIN\", \"achieved\": 1, \"unlock_time\": 5}]}"}}"#;
+        let cache = parse(object).unwrap();
+        assert_eq!(cache.format, LibraryCacheFormat::Object);
+        assert_eq!(cache.unlocked().collect::<Vec<_>>(), vec![(&"WIN".to_string(), Some(5))]);
+    }
+
+    #[test]
+    fn test_parse_reports_missing_section() {
+        let error = parse(r#"[["friends", {}], ["news", {}]]"#).unwrap_err();
+        assert!(error.contains("friends, news"), "{}", error);
+        let error = parse(r#"{"achievements": {"data": {"vecSomethingNew": []}}}"#).unwrap_err();
+        assert!(error.contains("vecSomethingNew"), "{}", error);
+    }
+}
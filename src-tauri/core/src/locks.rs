@@ -0,0 +1,16 @@
+use std::sync::{Mutex, MutexGuard};
+
+/// Locking that survives a panic in another thread. A poisoned mutex still holds valid data
+/// for everything this app stores, so one failed task shouldn't lock every later command out.
+pub trait LockExt<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| {
+            println!("[Locks] Recovering state left locked by a panicked thread");
+            poisoned.into_inner()
+        })
+    }
+}
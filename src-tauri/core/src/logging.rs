@@ -0,0 +1,51 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use crate::user_folders;
+
+// Keeps lines from different threads whole
+static LOG_FILE: Mutex<()> = Mutex::new(());
+
+// Past this the log is moved to the .old file and started over, so an app left running in the
+// tray for weeks doesn't keep growing it
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// The debug log main() starts on every launch
+pub fn log_path() -> PathBuf {
+    user_folders::output_root().join("Steam Backup Manager Debug.log")
+}
+
+// The previous log, kept so what happened before a restart or a rotation can still be read
+fn old_log_path() -> PathBuf {
+    log_path().with_extension("old.log")
+}
+
+/// Start this launch's log, moving the last one to the .old file
+pub fn start() {
+    let _guard = LOG_FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _ = fs::rename(log_path(), old_log_path());
+    let _ = fs::write(log_path(), format!("[{}] App starting...\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")));
+}
+
+/// Print a line and append it to the debug log, so it's still there after a run without a console
+pub fn write(line: &str) {
+    println!("{}", line);
+    // Not lock_or_recover: that logs too
+    let _guard = LOG_FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = log_path();
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > MAX_LOG_BYTES) {
+        let _ = fs::rename(&path, old_log_path());
+    }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "[{}] {}", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"), line);
+    }
+}
+
+/// `println!` that also ends up in the debug log
+#[macro_export]
+macro_rules! app_log {
+    ($($arg:tt)*) => {
+        $crate::logging::write(&format!($($arg)*))
+    };
+}
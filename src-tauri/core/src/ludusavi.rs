@@ -0,0 +1,901 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use crate::verification;
+use crate::app_log;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupResult {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_found: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_backed_up: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Ludusavi backed up locally but didn't sync its cloud (a conflict or a failed upload)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_warning: Option<String>,
+    /// Whether the written backup was checked against what Ludusavi reported; None when not checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_error: Option<String>,
+}
+
+/// Outcome of `ludusavi restore` for one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreResult {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_found: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_restored: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What `ludusavi backup --preview` found for a game, without writing a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupPreview {
+    pub game_name: String,
+    pub found: bool,
+    pub files: Vec<PreviewFile>,
+    pub total_bytes: i64,
+    pub total_size: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewFile {
+    pub path: String,
+    pub bytes: i64,
+    /// "New", "Different" or "Same" compared to the last backup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LudusaviApiResponse {
+    overall: OverallStats,
+    games: HashMap<String, GameData>,
+    #[serde(default)]
+    errors: ApiErrors,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ApiErrors {
+    #[serde(rename = "cloudConflict")]
+    cloud_conflict: Option<serde_json::Value>,
+    #[serde(rename = "cloudSyncFailed")]
+    cloud_sync_failed: Option<serde_json::Value>,
+}
+
+impl ApiErrors {
+    fn cloud_warning(&self) -> Option<String> {
+        if self.cloud_conflict.is_some() {
+            Some("The local and cloud backups were out of sync, so Ludusavi didn't upload. Resolve it with a cloud upload or download.".to_string())
+        } else if self.cloud_sync_failed.is_some() {
+            Some("Ludusavi couldn't sync the backup to its cloud".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OverallStats {
+    #[allow(dead_code)]
+    #[serde(rename = "totalGames")]
+    total_games: i32,
+    #[allow(dead_code)]
+    #[serde(rename = "totalBytes")]
+    total_bytes: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GameData {
+    decision: String,
+    files: Option<HashMap<String, FileData>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileData {
+    bytes: i64,
+    #[serde(default)]
+    failed: bool,
+    #[serde(default)]
+    ignored: bool,
+    #[serde(default)]
+    change: Option<String>,
+}
+
+// Files and bytes Ludusavi processed for a game; None when it skipped or doesn't know the game
+fn game_totals(response: &LudusaviApiResponse, game_name: &str) -> Option<(usize, i64, usize)> {
+    let game_data = response.games.get(game_name).filter(|g| g.decision != "Ignored")?;
+    let files = game_data.files.as_ref();
+    let file_count = files.map(|f| f.len()).unwrap_or(0);
+    let total_bytes: i64 = files.map(|files| files.values().map(|f| f.bytes).sum()).unwrap_or(0);
+    let failed = files.map(|files| files.values().filter(|f| f.failed).count()).unwrap_or(0);
+    Some((file_count, total_bytes, failed))
+}
+
+/// Which way `ludusavi cloud` moves backups
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudDirection {
+    Upload,
+    Download,
+}
+
+impl CloudDirection {
+    fn command(&self) -> &'static str {
+        match self {
+            CloudDirection::Upload => "upload",
+            CloudDirection::Download => "download",
+        }
+    }
+}
+
+/// Whether `ludusavi backup` syncs to the cloud set up in Ludusavi once the backup is done
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupCloudSync {
+    /// Follow the "synchronize automatically" setting in Ludusavi
+    #[default]
+    LudusaviDefault,
+    /// `--cloud-sync`: upload unless the local and cloud backups were already out of sync
+    Always,
+    /// `--no-cloud-sync`
+    Never,
+}
+
+/// `--format` of `ludusavi backup`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFormat {
+    /// Whatever is set in Ludusavi
+    #[default]
+    LudusaviDefault,
+    /// Plain copies of the files; snapshot comparison and selective restores need this one
+    Simple,
+    /// One archive per backup
+    Zip,
+}
+
+/// `--compression` of zip backups
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupCompression {
+    #[default]
+    LudusaviDefault,
+    None,
+    Deflate,
+    Bzip2,
+    Zstd,
+}
+
+impl BackupCompression {
+    fn arg(self) -> Option<&'static str> {
+        match self {
+            BackupCompression::LudusaviDefault => None,
+            BackupCompression::None => Some("none"),
+            BackupCompression::Deflate => Some("deflate"),
+            BackupCompression::Bzip2 => Some("bzip2"),
+            BackupCompression::Zstd => Some("zstd"),
+        }
+    }
+
+    /// Levels Ludusavi accepts for this compression
+    pub fn level_range(self) -> Option<(i32, i32)> {
+        match self {
+            BackupCompression::Deflate | BackupCompression::Bzip2 => Some((1, 9)),
+            BackupCompression::Zstd => Some((-7, 22)),
+            BackupCompression::LudusaviDefault | BackupCompression::None => None,
+        }
+    }
+}
+
+/// Outcome of a `ludusavi cloud upload/download` run, using the cloud set up in Ludusavi itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LudusaviCloudResult {
+    pub success: bool,
+    pub changed_files: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// How a game's local backup compares to Ludusavi's cloud copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LudusaviCloudStatus {
+    pub game_name: String,
+    pub pending_uploads: usize,
+    pub pending_downloads: usize,
+    pub in_sync: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One of a game's backups as `ludusavi backups` lists it; `name` is what `restore --backup` takes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LudusaviBackup {
+    pub name: String, // "." for the base backup of the simple format, otherwise "backup-<timestamp>"
+    pub when: String, // RFC 3339
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BackupsApiResponse {
+    #[serde(default)]
+    games: HashMap<String, GameBackups>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GameBackups {
+    #[serde(default)]
+    backups: Vec<LudusaviBackup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CloudApiResponse {
+    #[serde(default)]
+    errors: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    cloud: HashMap<String, serde_json::Value>, // changed cloud path -> { "change": ... }
+}
+
+/// Stops a running Ludusavi backup or restore from elsewhere, e.g. the app's cancel button.
+/// Cancelling before the command starts stops it as soon as it does.
+#[derive(Debug, Clone)]
+pub struct CancelToken(Arc<watch::Sender<bool>>);
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self(Arc::new(watch::channel(false).0))
+    }
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.send_replace(true);
+    }
+
+    pub async fn cancelled(&self) {
+        let mut receiver = self.0.subscribe();
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestCache {
+    games: Vec<String>,
+    timestamp: u64,
+}
+
+pub struct LudusaviManager {
+    ludusavi_path: String,
+    backup_path: String,
+    timeout: Option<Duration>,
+    cancel: Option<CancelToken>,
+    retention: Option<(u32, u32)>, // (full, differential) versions kept per game
+    cloud_sync: BackupCloudSync,
+    format: BackupFormat,
+    compression: BackupCompression,
+    compression_level: Option<i32>,
+    verify: bool,
+}
+
+impl LudusaviManager {
+    pub fn new(ludusavi_path: String, backup_path: String) -> Self {
+        Self {
+            ludusavi_path,
+            backup_path,
+            timeout: None,
+            cancel: None,
+            retention: None,
+            cloud_sync: BackupCloudSync::default(),
+            format: BackupFormat::default(),
+            compression: BackupCompression::default(),
+            compression_level: None,
+            verify: false,
+        }
+    }
+
+    /// Kill backups, restores and cloud runs that take longer than this; 0 means no limit
+    pub fn with_timeout(mut self, timeout_secs: u32) -> Self {
+        self.timeout = (timeout_secs > 0).then(|| Duration::from_secs(timeout_secs as u64));
+        self
+    }
+
+    /// Keep this many full backups per game (each with up to `differential` differential ones), Ludusavi prunes
+    /// the oldest. 0 full versions leaves retention to Ludusavi's own settings.
+    pub fn with_retention(mut self, full: u32, differential: u32) -> Self {
+        self.retention = (full > 0).then_some((full, differential));
+        self
+    }
+
+    /// Pass Ludusavi's cloud sync flag to backups run by this manager
+    pub fn with_cloud_sync(mut self, cloud_sync: BackupCloudSync) -> Self {
+        self.cloud_sync = cloud_sync;
+        self
+    }
+
+    /// Backup format and zip compression; a level outside the compression's range is left to Ludusavi
+    pub fn with_format(mut self, format: BackupFormat, compression: BackupCompression, level: Option<i32>) -> Self {
+        self.format = format;
+        self.compression = compression;
+        self.compression_level = level.filter(|level| {
+            compression.level_range().is_some_and(|(min, max)| (min..=max).contains(level))
+        });
+        self
+    }
+
+    /// Check each successful backup on disk against what Ludusavi reported, see verification::verify_new_backup
+    pub fn with_verification(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Let `token` stop the backups, restores and cloud runs of this manager
+    pub fn with_cancel(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    // Run Ludusavi, killing it when it hangs past the timeout or is cancelled
+    async fn run(&self, args: &[&str]) -> Result<Output, String> {
+        let child = tokio::process::Command::new(&self.ludusavi_path)
+            .args(args)
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW flag for Windows
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to run Ludusavi: {}", e))?;
+
+        let timeout = async {
+            match self.timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        let cancelled = async {
+            match &self.cancel {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        // Dropping the unfinished wait kills the process (kill_on_drop)
+        tokio::select! {
+            output = child.wait_with_output() => output.map_err(|e| format!("Failed to run Ludusavi: {}", e)),
+            _ = timeout => {
+                app_log!("Ludusavi didn't finish within {}s, stopping it", self.timeout.unwrap_or_default().as_secs());
+                Err(format!("Ludusavi didn't finish within {} seconds and was stopped", self.timeout.unwrap_or_default().as_secs()))
+            }
+            _ = cancelled => {
+                app_log!("Ludusavi run cancelled: {:?}", args);
+                Err("Cancelled".to_string())
+            }
+        }
+    }
+    
+    fn get_cache_path() -> PathBuf {
+        let cache_dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("steam-backup-manager");
+        
+        fs::create_dir_all(&cache_dir).ok();
+        cache_dir.join("ludusavi_manifest_cache.json")
+    }
+    
+    fn load_cache() -> Option<ManifestCache> {
+        let cache_path = Self::get_cache_path();
+        if let Ok(contents) = fs::read_to_string(&cache_path) {
+            if let Ok(cache) = serde_json::from_str::<ManifestCache>(&contents) {
+                // Cache is valid if it's less than 24 hours old
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                
+                if now - cache.timestamp < 86400 {
+                    return Some(cache);
+                }
+            }
+        }
+        None
+    }
+    
+    fn save_cache(games: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        let cache = ManifestCache {
+            games: games.to_vec(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs(),
+        };
+        
+        let cache_path = Self::get_cache_path();
+        let json = serde_json::to_string(&cache)?;
+        fs::write(&cache_path, json)?;
+        Ok(())
+    }
+    
+    fn clear_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let cache_path = Self::get_cache_path();
+        if cache_path.exists() {
+            fs::remove_file(&cache_path)?;
+        }
+        Ok(())
+    }
+    
+    pub async fn test_connection(&self) -> Result<serde_json::Value, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Ok(serde_json::json!({
+                "success": false,
+                "error": "Ludusavi executable not found at specified path"
+            }));
+        }
+        
+        match Command::new(&self.ludusavi_path)
+            .arg("--version")
+            .output()
+        {
+            Ok(output) => Ok(serde_json::json!({
+                "success": output.status.success()
+            })),
+            Err(e) => Ok(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })),
+        }
+    }
+    
+    pub async fn backup(&self, game_name: &str) -> Result<BackupResult, String> {
+        self.backup_to(game_name, &self.backup_path).await
+    }
+
+    /// Back up a game into an explicit folder instead of the configured backup path
+    pub async fn backup_to(&self, game_name: &str, backup_path: &str) -> Result<BackupResult, String> {
+        let mut args = vec!["backup", "--api", "--force", game_name];
+        
+        if !backup_path.is_empty() {
+            args.push("--path");
+            args.push(backup_path);
+        }
+
+        let limits = self.retention.map(|(full, differential)| (full.to_string(), differential.to_string()));
+        if let Some((full, differential)) = &limits {
+            args.extend(["--full-limit", full.as_str(), "--differential-limit", differential.as_str()]);
+        }
+
+        match self.cloud_sync {
+            BackupCloudSync::LudusaviDefault => {}
+            BackupCloudSync::Always => args.push("--cloud-sync"),
+            BackupCloudSync::Never => args.push("--no-cloud-sync"),
+        }
+
+        match self.format {
+            BackupFormat::LudusaviDefault => {}
+            BackupFormat::Simple => args.extend(["--format", "simple"]),
+            BackupFormat::Zip => args.extend(["--format", "zip"]),
+        }
+        // Compression only means something for zip backups
+        let level = self.compression_level.map(|level| level.to_string());
+        if self.format != BackupFormat::Simple {
+            if let Some(compression) = self.compression.arg() {
+                args.extend(["--compression", compression]);
+            }
+            if let Some(level) = &level {
+                args.extend(["--compression-level", level.as_str()]);
+            }
+        }
+        
+        app_log!("Running Ludusavi: {:?} {:?}", self.ludusavi_path, args);
+        
+        match self.run(&args).await {
+            Ok(output) => {
+                if !output.status.success() {
+                    let error = String::from_utf8_lossy(&output.stderr).to_string();
+                    app_log!("Ludusavi stderr: {}", error);
+                    return Ok(BackupResult {
+                        success: false,
+                        not_found: None,
+                        files_backed_up: None,
+                        total_size: None,
+                        error: Some(error),
+                        cloud_warning: None,
+                        verified: None,
+                        verification_error: None,
+                    });
+                }
+                
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                app_log!("Ludusavi stdout: {}", stdout);
+                
+                let response: LudusaviApiResponse = serde_json::from_str(&stdout)
+                    .map_err(|e| format!("Failed to parse response: {}", e))?;
+                
+                if let Some((file_count, total_bytes, _)) = game_totals(&response, game_name) {
+                    let (verified, verification_error) = if self.verify {
+                        self.verify_backup(&response, game_name, backup_path).await
+                    } else {
+                        (None, None)
+                    };
+                    Ok(BackupResult {
+                        success: true,
+                        not_found: None,
+                        files_backed_up: Some(file_count),
+                        total_size: Some(Self::format_bytes(total_bytes)),
+                        error: None,
+                        cloud_warning: response.errors.cloud_warning(),
+                        verified,
+                        verification_error,
+                    })
+                } else {
+                    Ok(BackupResult {
+                        success: false,
+                        not_found: Some(true),
+                        files_backed_up: None,
+                        total_size: None,
+                        error: None,
+                        cloud_warning: None,
+                        verified: None,
+                        verification_error: None,
+                    })
+                }
+            }
+            Err(e) => Ok(BackupResult {
+                success: false,
+                not_found: None,
+                files_backed_up: None,
+                total_size: None,
+                error: Some(e),
+                cloud_warning: None,
+                verified: None,
+                verification_error: None,
+            }),
+        }
+    }
+    
+    // Compare the backup on disk with the files Ludusavi reported; without a backup path Ludusavi picked
+    // the folder itself and there's nothing to check against
+    async fn verify_backup(&self, response: &LudusaviApiResponse, game_name: &str, backup_path: &str) -> (Option<bool>, Option<String>) {
+        if backup_path.is_empty() {
+            return (None, None);
+        }
+        let reported: HashMap<String, u64> = response.games.get(game_name)
+            .and_then(|game| game.files.as_ref())
+            .map(|files| files.iter()
+                .filter(|(_, file)| !file.failed && !file.ignored)
+                .map(|(path, file)| (path.clone(), file.bytes.max(0) as u64))
+                .collect())
+            .unwrap_or_default();
+        let game_dir = Path::new(backup_path).join(Self::game_folder_name(game_name));
+        // Every format keeps a mapping.yaml in the game folder; without one the backup went somewhere
+        // this can't follow, so say it wasn't checked rather than that it failed
+        if !game_dir.join("mapping.yaml").is_file() {
+            let reason = format!("no Ludusavi backup mapping in {}", game_dir.display());
+            app_log!("[Ludusavi] Backup of {} can't be verified: {}", game_name, reason);
+            return (None, Some(reason));
+        }
+
+        let outcome = tokio::task::spawn_blocking(move || verification::verify_new_backup(&game_dir, &reported))
+            .await
+            .unwrap_or_else(|e| Err(format!("Verification stopped: {}", e)));
+        match outcome {
+            Ok(files) => {
+                app_log!("[Ludusavi] Verified backup of {} ({} files)", game_name, files);
+                (Some(true), None)
+            }
+            Err(e) => {
+                app_log!("[Ludusavi] Backup of {} failed verification: {}", game_name, e);
+                (Some(false), Some(e))
+            }
+        }
+    }
+
+    /// List the files a backup of the game would pick up, to check Ludusavi finds the right saves
+    pub async fn preview(&self, game_name: &str) -> Result<BackupPreview, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Err("Ludusavi executable not found at specified path".to_string());
+        }
+
+        let mut args = vec!["backup", "--preview", "--api"];
+        if !self.backup_path.is_empty() {
+            args.push("--path");
+            args.push(&self.backup_path);
+        }
+        args.push(game_name);
+
+        app_log!("Running Ludusavi: {:?} {:?}", self.ludusavi_path, args);
+        let output = self.run(&args).await?;
+        if !output.status.success() {
+            return Err(format!("Ludusavi preview failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        let response: LudusaviApiResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let game = response.games.get(game_name).filter(|g| g.decision != "Ignored");
+        let mut files: Vec<PreviewFile> = game
+            .and_then(|g| g.files.as_ref())
+            .map(|files| files.iter().map(|(path, file)| PreviewFile {
+                path: path.clone(),
+                bytes: file.bytes,
+                change: file.change.clone(),
+            }).collect())
+            .unwrap_or_default();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let total_bytes = files.iter().map(|f| f.bytes).sum();
+
+        Ok(BackupPreview {
+            game_name: game_name.to_string(),
+            found: game.is_some(),
+            files,
+            total_bytes,
+            total_size: Self::format_bytes(total_bytes),
+        })
+    }
+
+    /// The backups Ludusavi keeps for a game in the backup path, via `ludusavi backups`
+    pub async fn list_backups(&self, game_name: &str) -> Result<Vec<LudusaviBackup>, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Err("Ludusavi executable not found at specified path".to_string());
+        }
+
+        let mut args = vec!["backups", "--api"];
+        if !self.backup_path.is_empty() {
+            args.push("--path");
+            args.push(&self.backup_path);
+        }
+        args.push(game_name);
+
+        let output = self.run(&args).await?;
+        if !output.status.success() {
+            return Err(format!("Ludusavi couldn't list backups: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        let response: BackupsApiResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        Ok(response.games.into_iter()
+            .find(|(name, _)| name == game_name)
+            .map(|(_, game)| game.backups)
+            .unwrap_or_default())
+    }
+
+    /// Put a game's saves back from its latest backup in the backup path
+    pub async fn restore(&self, game_name: &str) -> Result<RestoreResult, String> {
+        self.restore_backup(game_name, None).await
+    }
+
+    /// Put a game's saves back from one of its backups, a name from list_backups; None = the latest
+    pub async fn restore_backup(&self, game_name: &str, backup: Option<&str>) -> Result<RestoreResult, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Err("Ludusavi executable not found at specified path".to_string());
+        }
+
+        // --force skips Ludusavi's confirmation prompt
+        let mut args = vec!["restore", "--api", "--force"];
+        if !self.backup_path.is_empty() {
+            args.push("--path");
+            args.push(&self.backup_path);
+        }
+        if let Some(backup) = backup {
+            args.push("--backup");
+            args.push(backup);
+        }
+        args.push(game_name);
+
+        app_log!("Running Ludusavi: {:?} {:?}", self.ludusavi_path, args);
+        let output = self.run(&args).await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).to_string();
+            app_log!("Ludusavi stderr: {}", error);
+            return Ok(RestoreResult {
+                success: false,
+                not_found: None,
+                files_restored: None,
+                total_size: None,
+                error: Some(error),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        app_log!("Ludusavi stdout: {}", stdout);
+
+        let response: LudusaviApiResponse = serde_json::from_str(&stdout)
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        Ok(match game_totals(&response, game_name) {
+            Some((file_count, total_bytes, failed)) => RestoreResult {
+                success: failed == 0,
+                not_found: None,
+                files_restored: Some(file_count - failed),
+                total_size: Some(Self::format_bytes(total_bytes)),
+                error: (failed > 0).then(|| format!("{} file(s) couldn't be restored", failed)),
+            },
+            None => RestoreResult {
+                success: false,
+                not_found: Some(true),
+                files_restored: None,
+                total_size: None,
+                error: None,
+            },
+        })
+    }
+
+    /// Run `ludusavi cloud upload/download` for one game, or for every game when `game_name` is None
+    pub async fn cloud_sync(&self, direction: CloudDirection, game_name: Option<&str>) -> Result<LudusaviCloudResult, String> {
+        self.run_cloud(direction, game_name, false).await
+    }
+
+    /// Dry runs in both directions to see what's waiting to move
+    pub async fn cloud_status(&self, game_name: &str) -> Result<LudusaviCloudStatus, String> {
+        let upload = self.run_cloud(CloudDirection::Upload, Some(game_name), true).await?;
+        let download = self.run_cloud(CloudDirection::Download, Some(game_name), true).await?;
+        Ok(LudusaviCloudStatus {
+            game_name: game_name.to_string(),
+            pending_uploads: upload.changed_files,
+            pending_downloads: download.changed_files,
+            in_sync: upload.success && download.success && upload.changed_files == 0 && download.changed_files == 0,
+            error: upload.error.or(download.error),
+        })
+    }
+
+    async fn run_cloud(&self, direction: CloudDirection, game_name: Option<&str>, dry_run: bool) -> Result<LudusaviCloudResult, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Err("Ludusavi executable not found at specified path".to_string());
+        }
+
+        // --force skips Ludusavi's confirmation prompt
+        let mut args = vec!["cloud", direction.command(), "--api", "--force"];
+        if dry_run {
+            args.push("--preview");
+        }
+        if !self.backup_path.is_empty() {
+            args.push("--local");
+            args.push(&self.backup_path);
+        }
+        if let Some(game_name) = game_name {
+            args.push(game_name);
+        }
+
+        app_log!("Running Ludusavi: {:?} {:?}", self.ludusavi_path, args);
+        let output = self.run(&args).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let response: Option<CloudApiResponse> = serde_json::from_str(&stdout).ok();
+        let error = match &response {
+            Some(response) if !response.errors.is_empty() => Some(serde_json::to_string(&response.errors).unwrap_or_default()),
+            _ if !output.status.success() => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            _ => None,
+        };
+
+        Ok(LudusaviCloudResult {
+            success: error.is_none(),
+            changed_files: response.map(|r| r.cloud.len()).unwrap_or(0),
+            error,
+        })
+    }
+
+    // The whole manifest, custom games included, keyed by title. Goes through run() like every other
+    // Ludusavi call so a hanging run is stopped and the runtime isn't blocked meanwhile.
+    async fn load_manifest(&self) -> Result<HashMap<String, serde_json::Value>, String> {
+        if !Path::new(&self.ludusavi_path).exists() {
+            return Err("Ludusavi executable not found at specified path".to_string());
+        }
+
+        let output = self.run(&["manifest", "show", "--api"]).await?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to get manifest: {}", error));
+        }
+
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("Failed to parse manifest: {}", e))
+    }
+
+    pub async fn get_manifest_games(&self) -> Result<Vec<String>, String> {
+        // Try to load from cache first
+        if let Some(cache) = Self::load_cache() {
+            app_log!("Using cached manifest with {} games", cache.games.len());
+            return Ok(cache.games);
+        }
+
+        app_log!("Loading manifest from Ludusavi (this may take a moment)...");
+        let manifest = self.load_manifest().await?;
+        
+        let mut games: Vec<String> = manifest.keys().cloned().collect();
+        games.sort();
+        
+        // Save to cache
+        let _ = Self::save_cache(&games);
+        
+        Ok(games)
+    }
+
+    /// Ludusavi's title for each of the given Steam apps, matched by the manifest's Steam id and otherwise by
+    /// name (ignoring case). Apps Ludusavi doesn't know are left out.
+    pub async fn match_steam_apps(&self, apps: &[(u32, String)]) -> Result<HashMap<u32, String>, String> {
+        let manifest = self.load_manifest().await?;
+
+        let mut by_steam_id: HashMap<u64, &str> = HashMap::new();
+        let mut by_name: HashMap<String, &str> = HashMap::new();
+        for (title, entry) in &manifest {
+            if let Some(id) = entry.get("steam").and_then(|steam| steam.get("id")).and_then(|id| id.as_u64()) {
+                by_steam_id.insert(id, title);
+            }
+            by_name.insert(title.to_lowercase(), title);
+        }
+
+        Ok(apps.iter()
+            .filter_map(|(app_id, name)| {
+                let title = by_steam_id.get(&(*app_id as u64)).or_else(|| by_name.get(&name.to_lowercase()))?;
+                Some((*app_id, title.to_string()))
+            })
+            .collect())
+    }
+
+    /// Full manifest entry (files, registry, installDir...) for a single game, if Ludusavi knows it
+    pub async fn get_manifest_entry(&self, game_name: &str) -> Result<Option<serde_json::Value>, String> {
+        Ok(self.load_manifest().await?.remove(game_name))
+    }
+
+    pub fn extract_exe_name(path: &str) -> String {
+        Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_lowercase()
+    }
+    
+    /// Folder name Ludusavi uses for a game inside the backup path
+    pub fn game_folder_name(game_name: &str) -> String {
+        let mut escaped: String = game_name.chars()
+            .map(|c| match c {
+                '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '\0' => '_',
+                _ => c
+            })
+            .collect();
+
+        // Leading/trailing dots are also replaced by Ludusavi
+        if escaped.starts_with('.') {
+            escaped.replace_range(..1, "_");
+        }
+        if escaped.ends_with('.') {
+            let len = escaped.len();
+            escaped.replace_range(len - 1.., "_");
+        }
+
+        escaped
+    }
+
+    pub async fn clear_manifest_cache() -> Result<(), String> {
+        Self::clear_cache().map_err(|e| e.to_string())
+    }
+    
+    pub fn format_bytes(bytes: i64) -> String {
+        if bytes == 0 {
+            return "0 Bytes".to_string();
+        }
+        
+        let k = 1024_f64;
+        let sizes = ["Bytes", "KB", "MB", "GB"];
+        let i = (bytes as f64).log(k).floor() as usize;
+        let size = (bytes as f64) / k.powi(i as i32);
+        
+        format!("{:.2} {}", size, sizes[i.min(sizes.len() - 1)])
+    }
+}
\ No newline at end of file
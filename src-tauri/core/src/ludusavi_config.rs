@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::app_log;
+
+/// A folder Ludusavi scans for installed games (a Steam library, an Epic folder...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LudusaviRoot {
+    pub path: String,
+    pub store: String, // "steam", "epic", "gog", "otherHome", "other"...
+}
+
+/// A game defined in Ludusavi's own settings, either new or overriding/extending the manifest entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LudusaviCustomGame {
+    pub name: String,
+    #[serde(default = "default_integration")]
+    pub integration: String, // "override" or "extend"
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub registry: Vec<String>,
+}
+
+fn default_integration() -> String {
+    "override".to_string()
+}
+
+/// The roots and custom games from Ludusavi's config.yaml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LudusaviCustomization {
+    pub config_path: String,
+    pub roots: Vec<LudusaviRoot>,
+    pub custom_games: Vec<LudusaviCustomGame>,
+}
+
+/// Ludusavi keeps its settings next to the executable in portable mode, otherwise in %APPDATA%\ludusavi
+pub fn config_path(ludusavi_path: &str) -> PathBuf {
+    if let Some(exe_dir) = Path::new(ludusavi_path).parent() {
+        if exe_dir.join("ludusavi.portable").exists() {
+            return exe_dir.join("config.yaml");
+        }
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ludusavi")
+        .join("config.yaml")
+}
+
+fn load(path: &Path) -> Result<Mapping, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read Ludusavi settings at {}: {}", path.display(), e))?;
+    match serde_yaml::from_str(&contents).map_err(|e| format!("Failed to parse Ludusavi settings: {}", e))? {
+        Value::Mapping(mapping) => Ok(mapping),
+        _ => Err(format!("{} is not a Ludusavi settings file", path.display())),
+    }
+}
+
+// Everything else in the file (backup path, filters, redirects...) is written back untouched
+fn store(path: &Path, config: &Mapping) -> Result<(), String> {
+    let yaml = serde_yaml::to_string(config)
+        .map_err(|e| format!("Failed to serialize Ludusavi settings: {}", e))?;
+    // Keep the previous version in case Ludusavi rejects the edit
+    let _ = fs::copy(path, path.with_extension("yaml.bak"));
+    fs::write(path, yaml).map_err(|e| format!("Failed to write Ludusavi settings: {}", e))
+}
+
+fn list<T: for<'de> Deserialize<'de>>(config: &Mapping, key: &str) -> Vec<T> {
+    config.get(key)
+        .and_then(|value| value.as_sequence())
+        .map(|items| items.iter().filter_map(|item| serde_yaml::from_value(item.clone()).ok()).collect())
+        .unwrap_or_default()
+}
+
+pub fn read(ludusavi_path: &str) -> Result<LudusaviCustomization, String> {
+    let path = config_path(ludusavi_path);
+    let config = load(&path)?;
+    Ok(LudusaviCustomization {
+        config_path: path.to_string_lossy().to_string(),
+        roots: list(&config, "roots"),
+        custom_games: list(&config, "customGames"),
+    })
+}
+
+/// Add a custom game, or update the one with the same name. Fields this app doesn't edit are kept.
+pub fn save_custom_game(ludusavi_path: &str, game: &LudusaviCustomGame) -> Result<(), String> {
+    if game.name.trim().is_empty() {
+        return Err("A custom game needs a name".to_string());
+    }
+    if !matches!(game.integration.as_str(), "override" | "extend") {
+        return Err(format!("Unknown integration \"{}\", expected override or extend", game.integration));
+    }
+
+    let path = config_path(ludusavi_path);
+    let mut config = load(&path)?;
+    let games = sequence_mut(&mut config, "customGames");
+
+    let fields = serde_yaml::to_value(game).map_err(|e| format!("Failed to serialize custom game: {}", e))?;
+    let Value::Mapping(fields) = fields else {
+        return Err("Failed to serialize custom game".to_string());
+    };
+
+    let existing = games.iter_mut()
+        .filter_map(|item| item.as_mapping_mut())
+        .find(|item| item.get("name").and_then(|n| n.as_str()) == Some(game.name.as_str()));
+    match existing {
+        Some(entry) => {
+            for (key, value) in fields {
+                entry.insert(key, value);
+            }
+        }
+        None => games.push(Value::Mapping(fields)),
+    }
+
+    store(&path, &config)?;
+    app_log!("[Ludusavi] Saved custom game {}", game.name);
+    Ok(())
+}
+
+/// Make Ludusavi back up `folders` for a game whether or not its manifest knows the game. An existing
+/// custom game gains the folders; a new one extends the manifest entry. Returns whether anything changed.
+pub fn add_custom_game_files(ludusavi_path: &str, name: &str, folders: &[String]) -> Result<bool, String> {
+    let config = load(&config_path(ludusavi_path))?;
+    let mut game = list::<LudusaviCustomGame>(&config, "customGames")
+        .into_iter()
+        .find(|game| game.name == name)
+        .unwrap_or_else(|| LudusaviCustomGame {
+            name: name.to_string(),
+            integration: "extend".to_string(),
+            files: Vec::new(),
+            registry: Vec::new(),
+        });
+
+    let missing: Vec<String> = folders.iter()
+        .filter(|folder| !game.files.iter().any(|file| file.eq_ignore_ascii_case(folder)))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(false);
+    }
+    game.files.extend(missing);
+    save_custom_game(ludusavi_path, &game)?;
+    Ok(true)
+}
+
+pub fn remove_custom_game(ludusavi_path: &str, name: &str) -> Result<(), String> {
+    let path = config_path(ludusavi_path);
+    let mut config = load(&path)?;
+    let games = sequence_mut(&mut config, "customGames");
+    let before = games.len();
+    games.retain(|item| item.get("name").and_then(|n| n.as_str()) != Some(name));
+    if games.len() == before {
+        return Err(format!("{} is not a Ludusavi custom game", name));
+    }
+
+    store(&path, &config)?;
+    app_log!("[Ludusavi] Removed custom game {}", name);
+    Ok(())
+}
+
+/// Replace the roots Ludusavi scans; extra settings on a root with an unchanged path are kept
+pub fn set_roots(ludusavi_path: &str, roots: &[LudusaviRoot]) -> Result<(), String> {
+    let path = config_path(ludusavi_path);
+    let mut config = load(&path)?;
+    let existing = std::mem::take(sequence_mut(&mut config, "roots"));
+
+    let updated: Vec<Value> = roots.iter()
+        .map(|root| {
+            let mut entry = existing.iter()
+                .filter_map(|item| item.as_mapping())
+                .find(|item| item.get("path").and_then(|p| p.as_str()) == Some(root.path.as_str()))
+                .cloned()
+                .unwrap_or_default();
+            entry.insert(Value::from("path"), Value::from(root.path.clone()));
+            entry.insert(Value::from("store"), Value::from(root.store.clone()));
+            Value::Mapping(entry)
+        })
+        .collect();
+    *sequence_mut(&mut config, "roots") = updated;
+
+    store(&path, &config)?;
+    app_log!("[Ludusavi] Saved {} roots", roots.len());
+    Ok(())
+}
+
+// The list under `key`, created when the file doesn't have one yet
+fn sequence_mut<'a>(config: &'a mut Mapping, key: &str) -> &'a mut Vec<Value> {
+    let value = config.entry(Value::from(key)).or_insert_with(|| Value::Sequence(Vec::new()));
+    if !value.is_sequence() {
+        *value = Value::Sequence(Vec::new());
+    }
+    value.as_sequence_mut().unwrap()
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use crate::achievements::AchievementDatabase;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_thresholds")]
+    pub thresholds: Vec<u32>, // completion percentages, 100 = perfect game
+    #[serde(default)]
+    pub sound_path: Option<String>, // custom sound for milestones, Windows sound if unset
+    #[serde(default)]
+    pub perfect_sound_path: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_thresholds() -> Vec<u32> {
+    vec![50, 75, 100]
+}
+
+impl Default for MilestoneSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            thresholds: default_thresholds(),
+            sound_path: None,
+            perfect_sound_path: None,
+        }
+    }
+}
+
+/// A completion milestone crossed by the latest unlock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MilestoneEvent {
+    pub app_id: u32,
+    pub game_name: String,
+    pub milestone: u32,
+    pub unlocked: u32,
+    pub total: u32,
+    pub perfect: bool,
+}
+
+/// Check a game's completion after an unlock. Every milestone reached is recorded so it only fires once;
+/// only one crossed by this unlock is returned, so existing progress doesn't fire when the feature is new.
+pub fn check_after_unlock(db: &AchievementDatabase, settings: &MilestoneSettings, app_id: u32, game_name: &str) -> Result<Option<MilestoneEvent>, String> {
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let (unlocked, total) = db.get_game_progress(app_id)?;
+    if total == 0 || unlocked == 0 {
+        return Ok(None);
+    }
+
+    // A perfect game is always a milestone
+    let mut thresholds: Vec<u32> = settings.thresholds.iter().map(|t| (*t).clamp(1, 100)).collect();
+    thresholds.push(100);
+    thresholds.sort_unstable();
+    thresholds.dedup();
+
+    let reached = |count: u32, threshold: u32| count * 100 >= threshold * total;
+    let mut crossed = None;
+    for threshold in thresholds {
+        if !reached(unlocked, threshold) {
+            break;
+        }
+        if db.record_milestone(app_id, threshold)? && !reached(unlocked - 1, threshold) {
+            crossed = Some(threshold);
+        }
+    }
+
+    Ok(crossed.map(|milestone| MilestoneEvent {
+        app_id,
+        game_name: game_name.to_string(),
+        milestone,
+        unlocked,
+        total,
+        perfect: unlocked >= total,
+    }))
+}
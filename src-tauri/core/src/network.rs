@@ -0,0 +1,472 @@
+use std::fs;
+use std::io::Read;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use crate::bandwidth::{throttled_body, BandwidthLimiter, ThrottledReader};
+use crate::cloud::{CloudDestination, CloudProvider};
+use crate::credentials;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Default, Clone)]
+pub struct MirrorStats {
+    pub uploaded_files: usize,
+    pub skipped_files: usize,
+    pub uploaded_bytes: u64,
+}
+
+/// A local file to mirror, relative to the game's backup folder
+struct LocalFile {
+    relative: String, // forward slashes
+    path: PathBuf,
+    size: u64,
+    modified: i64,
+}
+
+fn credential_key(destination_id: &str) -> String {
+    format!("network:{}", destination_id)
+}
+
+pub fn store_password(destination_id: &str, password: &str) -> Result<(), String> {
+    credentials::store_secret(&credential_key(destination_id), password)
+}
+
+pub fn forget_password(destination_id: &str) -> Result<(), String> {
+    credentials::delete_secret(&credential_key(destination_id))
+}
+
+fn load_password(destination: &CloudDestination) -> Result<String, String> {
+    credentials::load_secret(&credential_key(&destination.id))
+        .ok_or_else(|| format!("No password saved for {}", destination.name))
+}
+
+fn join_remote(base: &str, relative: &str) -> String {
+    let base = base.trim_end_matches('/');
+    if relative.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}/{}", base, relative.trim_start_matches('/'))
+    }
+}
+
+fn collect_local_files(root: &Path) -> Vec<LocalFile> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else { continue };
+            let modified = metadata.modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            files.push(LocalFile { relative, path, size: metadata.len(), modified });
+        }
+    }
+
+    files.sort_by(|a, b| a.relative.cmp(&b.relative));
+    files
+}
+
+/// Parent folders of every file, shallowest first, so they can be created in order
+fn remote_dirs(files: &[LocalFile]) -> Vec<String> {
+    let mut dirs: Vec<String> = Vec::new();
+    for file in files {
+        let mut current = String::new();
+        let parts: Vec<&str> = file.relative.split('/').collect();
+        for part in &parts[..parts.len() - 1] {
+            current = if current.is_empty() { part.to_string() } else { format!("{}/{}", current, part) };
+            if !dirs.contains(&current) {
+                dirs.push(current.clone());
+            }
+        }
+    }
+    dirs
+}
+
+/// Copy new or changed files from `local_dir` to `<remote path>/<remote_subdir>` on a network destination
+pub async fn mirror_directory(destination: &CloudDestination, local_dir: &Path, remote_subdir: &str) -> Result<MirrorStats, String> {
+    let files = collect_local_files(local_dir);
+    let password = load_password(destination)?;
+    let limiter = Arc::new(BandwidthLimiter::new(destination.max_upload_kbps));
+
+    match &destination.provider {
+        CloudProvider::Sftp { host, port, username, remote_path, host_key } => {
+            let (host, port, username, host_key) = (host.clone(), *port, username.clone(), host_key.clone());
+            let remote_root = join_remote(remote_path, remote_subdir);
+            tokio::task::spawn_blocking(move || {
+                let session = connect_sftp(&host, port, &username, &password, host_key.as_deref())?;
+                mirror_sftp(&session, &remote_root, &files, &limiter)
+            })
+                .await
+                .map_err(|e| format!("SFTP mirror failed: {}", e))?
+        }
+        CloudProvider::Ftps { host, port, username, remote_path } => {
+            let (host, port, username) = (host.clone(), *port, username.clone());
+            let remote_root = join_remote(remote_path, remote_subdir);
+            tokio::task::spawn_blocking(move || mirror_ftps(&host, port, &username, &password, &remote_root, &files, &limiter))
+                .await
+                .map_err(|e| format!("FTPS mirror failed: {}", e))?
+        }
+        CloudProvider::WebDav { url, username } => {
+            mirror_webdav(&join_remote(url, remote_subdir), username, &password, &files, &limiter).await
+        }
+        _ => Err(format!("{} is not a network destination", destination.name)),
+    }
+}
+
+/// Connect and log in without transferring anything
+pub async fn test_connection(destination: &CloudDestination) -> Result<(), String> {
+    let password = load_password(destination)?;
+
+    match &destination.provider {
+        CloudProvider::Sftp { host, port, username, remote_path, host_key } => {
+            let (host, port, username, remote_path, host_key) =
+                (host.clone(), *port, username.clone(), remote_path.clone(), host_key.clone());
+            tokio::task::spawn_blocking(move || {
+                let session = connect_sftp(&host, port, &username, &password, host_key.as_deref())?;
+                let sftp = session.sftp().map_err(|e| format!("SFTP subsystem unavailable: {}", e))?;
+                ensure_sftp_dir(&sftp, &remote_path)
+            })
+            .await
+            .map_err(|e| format!("SFTP test failed: {}", e))?
+        }
+        CloudProvider::Ftps { host, port, username, remote_path } => {
+            let (host, port, username, remote_path) = (host.clone(), *port, username.clone(), remote_path.clone());
+            tokio::task::spawn_blocking(move || {
+                let mut ftp = connect_ftps(&host, port, &username, &password)?;
+                let result = ensure_ftp_dir(&mut ftp, &remote_path);
+                let _ = ftp.quit();
+                result
+            })
+            .await
+            .map_err(|e| format!("FTPS test failed: {}", e))?
+        }
+        CloudProvider::WebDav { url, username } => {
+            let response = webdav_client(url)?
+                .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), url)
+                .basic_auth(username, Some(&password))
+                .header("Depth", "0")
+                .send()
+                .await
+                .map_err(|e| format!("Failed to contact {}: {}", url, e))?;
+            match response.status().as_u16() {
+                200..=299 => Ok(()),
+                401 | 403 => Err("WebDAV server rejected the username or password".to_string()),
+                404 => Err(format!("{} does not exist on the server", url)),
+                status => Err(format!("WebDAV server returned {}", status)),
+            }
+        }
+        _ => Err(format!("{} is not a network destination", destination.name)),
+    }
+}
+
+fn resolve(host: &str, port: u16) -> Result<std::net::SocketAddr, String> {
+    (host, port).to_socket_addrs()
+        .map_err(|e| format!("Could not resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("Could not resolve {}", host))
+}
+
+// --- SFTP ---
+
+fn sftp_handshake(host: &str, port: u16) -> Result<ssh2::Session, String> {
+    let tcp = TcpStream::connect_timeout(&resolve(host, port)?, CONNECT_TIMEOUT)
+        .map_err(|e| format!("Could not connect to {}:{}: {}", host, port, e))?;
+
+    let mut session = ssh2::Session::new().map_err(|e| e.to_string())?;
+    session.set_tcp_stream(tcp);
+    session.handshake().map_err(|e| format!("SSH handshake failed: {}", e))?;
+    Ok(session)
+}
+
+fn host_key_fingerprint(session: &ssh2::Session) -> Result<String, String> {
+    session.host_key_hash(ssh2::HashType::Sha256)
+        .map(crate::backup_keys::hex)
+        .ok_or_else(|| "SFTP server did not present a host key".to_string())
+}
+
+/// Fingerprint of an SFTP server's host key, for pinning on first use. Only does the handshake:
+/// nothing is sent to the server before the key has been pinned.
+pub async fn fetch_sftp_host_key(host: &str, port: u16) -> Result<String, String> {
+    let host = host.to_string();
+    tokio::task::spawn_blocking(move || host_key_fingerprint(&sftp_handshake(&host, port)?))
+        .await
+        .map_err(|e| format!("SFTP handshake failed: {}", e))?
+}
+
+/// Log in once the server's host key matches the pinned fingerprint. The password is never sent
+/// to a server whose key hasn't been pinned or has changed.
+fn connect_sftp(host: &str, port: u16, username: &str, password: &str, pinned: Option<&str>) -> Result<ssh2::Session, String> {
+    let session = sftp_handshake(host, port)?;
+    let fingerprint = host_key_fingerprint(&session)?;
+    match pinned {
+        None => return Err(format!("The host key of {} hasn't been trusted yet; test the destination first", host)),
+        Some(pinned) if !pinned.eq_ignore_ascii_case(&fingerprint) => {
+            return Err(format!(
+                "The host key of {} has changed (expected {}, got {}); refusing to log in",
+                host, pinned, fingerprint
+            ));
+        }
+        Some(_) => {}
+    }
+    session.userauth_password(username, password)
+        .map_err(|_| "SFTP server rejected the username or password".to_string())?;
+    Ok(session)
+}
+
+fn ensure_sftp_dir(sftp: &ssh2::Sftp, remote_dir: &str) -> Result<(), String> {
+    let mut current = String::new();
+    for part in remote_dir.split('/').filter(|p| !p.is_empty()) {
+        current = format!("{}/{}", current, part);
+        let path = Path::new(&current);
+        if sftp.stat(path).is_err() {
+            sftp.mkdir(path, 0o755)
+                .map_err(|e| format!("Failed to create {}: {}", current, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn mirror_sftp(
+    session: &ssh2::Session,
+    remote_root: &str,
+    files: &[LocalFile],
+    limiter: &BandwidthLimiter,
+) -> Result<MirrorStats, String> {
+    let sftp = session.sftp().map_err(|e| format!("SFTP subsystem unavailable: {}", e))?;
+
+    ensure_sftp_dir(&sftp, remote_root)?;
+    for dir in remote_dirs(files) {
+        ensure_sftp_dir(&sftp, &join_remote(remote_root, &dir))?;
+    }
+
+    let mut stats = MirrorStats::default();
+    for file in files {
+        let remote = join_remote(remote_root, &file.relative);
+        let remote_path = Path::new(&remote);
+
+        // Same size and mtime means the file was mirrored before
+        if let Ok(stat) = sftp.stat(remote_path) {
+            if stat.size == Some(file.size) && stat.mtime == Some(file.modified as u64) {
+                stats.skipped_files += 1;
+                continue;
+            }
+        }
+
+        let mut local = fs::File::open(&file.path)
+            .map_err(|e| format!("Failed to read {}: {}", file.path.display(), e))?;
+        let mut remote_file = sftp.create(remote_path)
+            .map_err(|e| format!("Failed to create {}: {}", remote, e))?;
+        std::io::copy(&mut ThrottledReader::new(&mut local, limiter), &mut remote_file)
+            .map_err(|e| format!("Failed to upload {}: {}", remote, e))?;
+        drop(remote_file);
+
+        let _ = sftp.setstat(remote_path, ssh2::FileStat {
+            size: None,
+            uid: None,
+            gid: None,
+            perm: None,
+            atime: Some(file.modified as u64),
+            mtime: Some(file.modified as u64),
+        });
+
+        stats.uploaded_files += 1;
+        stats.uploaded_bytes += file.size;
+    }
+
+    Ok(stats)
+}
+
+// --- FTPS (explicit TLS) ---
+
+fn connect_ftps(host: &str, port: u16, username: &str, password: &str) -> Result<suppaftp::NativeTlsFtpStream, String> {
+    let ftp = suppaftp::NativeTlsFtpStream::connect_timeout(resolve(host, port)?, CONNECT_TIMEOUT)
+        .map_err(|e| format!("Could not connect to {}:{}: {}", host, port, e))?;
+
+    let connector = suppaftp::native_tls::TlsConnector::new().map_err(|e| e.to_string())?;
+    let mut ftp = ftp.into_secure(suppaftp::NativeTlsConnector::from(connector), host)
+        .map_err(|e| format!("TLS negotiation failed: {}", e))?;
+
+    ftp.login(username, password)
+        .map_err(|_| "FTP server rejected the username or password".to_string())?;
+    ftp.transfer_type(suppaftp::types::FileType::Binary)
+        .map_err(|e| e.to_string())?;
+    Ok(ftp)
+}
+
+fn ensure_ftp_dir(ftp: &mut suppaftp::NativeTlsFtpStream, remote_dir: &str) -> Result<(), String> {
+    let mut current = String::new();
+    for part in remote_dir.split('/').filter(|p| !p.is_empty()) {
+        current = format!("{}/{}", current, part);
+        if ftp.cwd(&current).is_err() {
+            ftp.mkdir(&current)
+                .map_err(|e| format!("Failed to create {}: {}", current, e))?;
+        }
+    }
+    ftp.cwd("/").map_err(|e| e.to_string())
+}
+
+fn mirror_ftps(
+    host: &str,
+    port: u16,
+    username: &str,
+    password: &str,
+    remote_root: &str,
+    files: &[LocalFile],
+    limiter: &BandwidthLimiter,
+) -> Result<MirrorStats, String> {
+    let mut ftp = connect_ftps(host, port, username, password)?;
+    let result = upload_ftp_files(&mut ftp, remote_root, files, limiter);
+    let _ = ftp.quit();
+    result
+}
+
+fn upload_ftp_files(
+    ftp: &mut suppaftp::NativeTlsFtpStream,
+    remote_root: &str,
+    files: &[LocalFile],
+    limiter: &BandwidthLimiter,
+) -> Result<MirrorStats, String> {
+    ensure_ftp_dir(ftp, remote_root)?;
+    for dir in remote_dirs(files) {
+        ensure_ftp_dir(ftp, &join_remote(remote_root, &dir))?;
+    }
+
+    let mut stats = MirrorStats::default();
+    for file in files {
+        let remote = join_remote(remote_root, &file.relative);
+
+        // FTP can't set mtimes portably, so re-upload only when the remote copy is older or a different size
+        let same_size = ftp.size(&remote).map(|s| s as u64 == file.size).unwrap_or(false);
+        let up_to_date = same_size && ftp.mdtm(&remote)
+            .map(|t| t.and_utc().timestamp() >= file.modified)
+            .unwrap_or(false);
+        if up_to_date {
+            stats.skipped_files += 1;
+            continue;
+        }
+
+        let mut local = fs::File::open(&file.path)
+            .map_err(|e| format!("Failed to read {}: {}", file.path.display(), e))?;
+        ftp.put_file(&remote, &mut ThrottledReader::new(&mut local, limiter))
+            .map_err(|e| format!("Failed to upload {}: {}", remote, e))?;
+
+        stats.uploaded_files += 1;
+        stats.uploaded_bytes += file.size;
+    }
+
+    Ok(stats)
+}
+
+// --- WebDAV ---
+
+/// Client for a WebDAV server. The password goes out as basic auth, so only https is allowed,
+/// redirects included.
+fn webdav_client(url: &str) -> Result<reqwest::Client, String> {
+    if !url.trim_start().to_ascii_lowercase().starts_with("https://") {
+        return Err(format!("WebDAV needs an https:// address, {} would send the password unencrypted", url));
+    }
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .https_only(true)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+fn webdav_url(base: &str, relative: &str) -> String {
+    let encoded: Vec<String> = relative.split('/')
+        .filter(|p| !p.is_empty())
+        .map(|p| urlencoding::encode(p).to_string())
+        .collect();
+    join_remote(base, &encoded.join("/"))
+}
+
+async fn mirror_webdav(
+    remote_root: &str,
+    username: &str,
+    password: &str,
+    files: &[LocalFile],
+    limiter: &Arc<BandwidthLimiter>,
+) -> Result<MirrorStats, String> {
+    let client = webdav_client(remote_root)?;
+    let mkcol = reqwest::Method::from_bytes(b"MKCOL").unwrap();
+
+    // Create the root path one level at a time; 405 means it already exists
+    let mut collections = vec![String::new()];
+    collections.extend(remote_dirs(files));
+    for dir in collections {
+        let url = format!("{}/", webdav_url(remote_root, &dir));
+        let response = client.request(mkcol.clone(), &url)
+            .basic_auth(username, Some(password))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", url, e))?;
+        match response.status().as_u16() {
+            200..=299 | 405 => {}
+            401 | 403 => return Err("WebDAV server rejected the username or password".to_string()),
+            status => return Err(format!("Failed to create {} ({})", url, status)),
+        }
+    }
+
+    let mut stats = MirrorStats::default();
+    for file in files {
+        let url = webdav_url(remote_root, &file.relative);
+
+        let existing = client.head(&url)
+            .basic_auth(username, Some(password))
+            .send()
+            .await
+            .ok()
+            .filter(|r| r.status().is_success());
+        if let Some(response) = existing {
+            let remote_size = response.headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            let remote_modified = response.headers()
+                .get(reqwest::header::LAST_MODIFIED)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+                .map(|t| t.timestamp());
+            if remote_size == Some(file.size) && remote_modified.map(|t| t >= file.modified).unwrap_or(false) {
+                stats.skipped_files += 1;
+                continue;
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(file.size as usize);
+        fs::File::open(&file.path)
+            .and_then(|mut f| f.read_to_end(&mut bytes))
+            .map_err(|e| format!("Failed to read {}: {}", file.path.display(), e))?;
+
+        let response = client.put(&url)
+            .basic_auth(username, Some(password))
+            .header(reqwest::header::CONTENT_LENGTH, bytes.len())
+            .body(throttled_body(bytes, limiter.clone()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upload {}: {}", url, e))?;
+        if !response.status().is_success() {
+            return Err(match response.status().as_u16() {
+                507 => "WebDAV server is out of space".to_string(),
+                status => format!("Failed to upload {} ({})", url, status),
+            });
+        }
+
+        stats.uploaded_files += 1;
+        stats.uploaded_bytes += file.size;
+    }
+
+    Ok(stats)
+}
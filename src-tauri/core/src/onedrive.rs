@@ -0,0 +1,350 @@
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cloud::{CloudDestination, CloudProvider, DeviceAuthorization, StorageQuota};
+use crate::bandwidth::{throttled_body, BandwidthLimiter};
+use crate::credentials;
+use crate::app_log;
+
+const AUTH_BASE: &str = "https://login.microsoftonline.com/common/oauth2/v2.0";
+const GRAPH_API: &str = "https://graph.microsoft.com/v1.0";
+// App folder scope: we only ever see Apps/<app name> in the user's OneDrive
+const ONEDRIVE_SCOPE: &str = "Files.ReadWrite.AppFolder offline_access";
+// Upload session chunks must be multiples of 320 KiB
+const CHUNK_SIZE: u64 = 32 * 320 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadSession {
+    upload_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadSessionStatus {
+    #[serde(default)]
+    next_expected_ranges: Vec<String>,
+}
+
+fn credential_key(destination_id: &str) -> String {
+    format!("onedrive:{}", destination_id)
+}
+
+/// OneDrive uploader (Microsoft Graph) limited to the app folder
+pub struct OneDriveClient {
+    http_client: reqwest::Client,
+    access_token: String,
+    limiter: Arc<BandwidthLimiter>,
+}
+
+impl OneDriveClient {
+    /// Start the device-code flow; the user enters `user_code` at `verification_url`
+    pub async fn start_device_authorization(client_id: &str) -> Result<DeviceAuthorization, String> {
+        let response = reqwest::Client::new()
+            .post(format!("{}/devicecode", AUTH_BASE))
+            .form(&[("client_id", client_id), ("scope", ONEDRIVE_SCOPE)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Microsoft: {}", e))?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Microsoft rejected the authorization request: {}", body));
+        }
+
+        response.json::<DeviceAuthorization>()
+            .await
+            .map_err(|e| format!("Failed to parse authorization response: {}", e))
+    }
+
+    /// Poll until the user approves the device code, then store the refresh token
+    pub async fn complete_device_authorization(
+        destination_id: &str,
+        client_id: &str,
+        authorization: &DeviceAuthorization,
+    ) -> Result<(), String> {
+        let http_client = reqwest::Client::new();
+        let mut interval = authorization.interval.max(5);
+        let deadline = std::time::Instant::now() + Duration::from_secs(authorization.expires_in);
+
+        while std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            let token: TokenResponse = http_client
+                .post(format!("{}/token", AUTH_BASE))
+                .form(&[
+                    ("client_id", client_id),
+                    ("device_code", authorization.device_code.as_str()),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await
+                .map_err(|e| format!("Failed to contact Microsoft: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+            match token.error.as_deref() {
+                None => {
+                    let refresh_token = token.refresh_token
+                        .ok_or_else(|| "Microsoft did not return a refresh token".to_string())?;
+                    credentials::store_secret(&credential_key(destination_id), &refresh_token)?;
+                    app_log!("[OneDrive] Authorized destination {}", destination_id);
+                    return Ok(());
+                }
+                Some("authorization_pending") => continue,
+                Some("slow_down") => interval += 5,
+                Some(error) => {
+                    return Err(format!(
+                        "Authorization failed: {}",
+                        token.error_description.unwrap_or_else(|| error.to_string())
+                    ));
+                }
+            }
+        }
+
+        Err("Authorization code expired before it was approved".to_string())
+    }
+
+    /// Remove the stored refresh token for a destination
+    pub fn sign_out(destination_id: &str) -> Result<(), String> {
+        credentials::delete_secret(&credential_key(destination_id))
+    }
+
+    /// Build a client with a fresh access token for a configured destination
+    pub async fn for_destination(destination: &CloudDestination) -> Result<Self, String> {
+        let client_id = match &destination.provider {
+            CloudProvider::OneDrive { client_id } => client_id,
+            _ => return Err(format!("{} is not a OneDrive destination", destination.name)),
+        };
+
+        let refresh_token = credentials::load_secret(&credential_key(&destination.id))
+            .ok_or_else(|| format!("{} is not connected. Sign in to OneDrive first.", destination.name))?;
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        let token: TokenResponse = http_client
+            .post(format!("{}/token", AUTH_BASE))
+            .form(&[
+                ("client_id", client_id.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+                ("scope", ONEDRIVE_SCOPE),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to contact Microsoft: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+        let access_token = token.access_token.ok_or_else(|| {
+            format!(
+                "OneDrive sign-in expired, please reconnect ({})",
+                token.error_description.or(token.error).unwrap_or_default()
+            )
+        })?;
+
+        // Microsoft rotates refresh tokens, keep the newest one
+        if let Some(new_refresh_token) = token.refresh_token {
+            let _ = credentials::store_secret(&credential_key(&destination.id), &new_refresh_token);
+        }
+
+        Ok(Self {
+            http_client,
+            access_token,
+            limiter: Arc::new(BandwidthLimiter::new(destination.max_upload_kbps)),
+        })
+    }
+
+    pub async fn get_quota(&self) -> Result<StorageQuota, String> {
+        let response = self.http_client
+            .get(format!("{}/me/drive", GRAPH_API))
+            .bearer_auth(&self.access_token)
+            .query(&[("$select", "quota")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to read quota: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let drive: serde_json::Value = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse quota: {}", e))?;
+
+        Ok(StorageQuota {
+            used_bytes: drive.pointer("/quota/used").and_then(|v| v.as_u64()).unwrap_or(0),
+            total_bytes: drive.pointer("/quota/total").and_then(|v| v.as_u64()),
+        })
+    }
+
+    /// Whether an uploaded drive item still exists
+    pub async fn item_exists(&self, item_id: &str) -> Result<bool, String> {
+        let response = self.http_client
+            .get(format!("{}/me/drive/items/{}", GRAPH_API, urlencoding::encode(item_id)))
+            .bearer_auth(&self.access_token)
+            .query(&[("$select", "id")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to check file: {}", e))?;
+
+        if response.status().as_u16() == 404 {
+            return Ok(false);
+        }
+        Self::check_response(response).await.map(|_| true)
+    }
+
+    /// Upload into the app folder via an upload session; returns the drive item id
+    pub async fn upload_file(&self, path: &Path, remote_name: &str) -> Result<String, String> {
+        let total = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read archive: {}", e))?
+            .len();
+
+        if let Ok(quota) = self.get_quota().await {
+            if let Some(limit) = quota.total_bytes {
+                if quota.used_bytes + total > limit {
+                    return Err("Not enough OneDrive storage left for this backup".to_string());
+                }
+            }
+        }
+
+        // An archive with the same name already uploaded (e.g. from another PC) is kept; ours gets renamed
+        let session_url = format!(
+            "{}/me/drive/special/approot:/{}:/createUploadSession",
+            GRAPH_API,
+            urlencoding::encode(remote_name)
+        );
+        let response = self.http_client
+            .post(session_url)
+            .bearer_auth(&self.access_token)
+            .json(&serde_json::json!({
+                "item": { "@microsoft.graph.conflictBehavior": "rename" }
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to start upload: {}", e))?;
+
+        let response = Self::check_response(response).await?;
+        let session: UploadSession = response.json()
+            .await
+            .map_err(|e| format!("Failed to parse upload session: {}", e))?;
+
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open archive: {}", e))?;
+        let mut offset: u64 = 0;
+        let mut retries = 0;
+
+        loop {
+            let chunk_len = CHUNK_SIZE.min(total - offset);
+            let mut chunk = vec![0u8; chunk_len as usize];
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.read_exact(&mut chunk))
+                .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+            // The upload URL is pre-authenticated; sending the bearer token is rejected
+            let result = self.http_client
+                .put(&session.upload_url)
+                .header(reqwest::header::CONTENT_RANGE, format!("bytes {}-{}/{}", offset, offset + chunk_len - 1, total))
+                .header(reqwest::header::CONTENT_LENGTH, chunk_len)
+                .body(throttled_body(chunk, self.limiter.clone()))
+                .send()
+                .await;
+
+            match result {
+                Ok(response) if response.status().as_u16() == 202 => {
+                    let status: UploadSessionStatus = response.json().await.unwrap_or(UploadSessionStatus { next_expected_ranges: Vec::new() });
+                    offset = Self::next_offset(&status).unwrap_or(offset + chunk_len);
+                    retries = 0;
+                }
+                Ok(response) if response.status().is_success() => {
+                    let item: serde_json::Value = response.json()
+                        .await
+                        .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+                    let _ = self.http_client.delete(&session.upload_url).send().await;
+                    return item.get("id")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| "Upload finished without an item id".to_string());
+                }
+                Ok(response) if (response.status().is_server_error() || matches!(response.status().as_u16(), 416 | 429)) && retries < 3 => {
+                    retries += 1;
+                    let wait = response.headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.parse::<u64>().ok())
+                        .unwrap_or(2 * retries);
+                    app_log!("[OneDrive] Chunk upload failed with {}, resuming in {}s", response.status(), wait);
+                    tokio::time::sleep(Duration::from_secs(wait)).await;
+                    offset = self.query_upload_offset(&session.upload_url).await.unwrap_or(offset);
+                }
+                Ok(response) => {
+                    let _ = self.http_client.delete(&session.upload_url).send().await;
+                    return Err(Self::describe_error(response).await);
+                }
+                Err(e) if retries < 3 => {
+                    app_log!("[OneDrive] Chunk upload failed, resuming: {}", e);
+                    retries += 1;
+                    tokio::time::sleep(Duration::from_secs(2 * retries)).await;
+                    offset = self.query_upload_offset(&session.upload_url).await.unwrap_or(offset);
+                }
+                Err(e) => return Err(format!("Upload failed: {}", e)),
+            }
+        }
+    }
+
+    /// Ask the session which byte range it expects next
+    async fn query_upload_offset(&self, upload_url: &str) -> Option<u64> {
+        let status: UploadSessionStatus = self.http_client
+            .get(upload_url)
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+        Self::next_offset(&status)
+    }
+
+    /// Parse the start of the first "N-" or "N-M" expected range
+    fn next_offset(status: &UploadSessionStatus) -> Option<u64> {
+        status.next_expected_ranges.first()
+            .and_then(|range| range.split('-').next())
+            .and_then(|start| start.parse::<u64>().ok())
+    }
+
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, String> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Self::describe_error(response).await)
+        }
+    }
+
+    /// Turn Graph API errors into readable messages
+    async fn describe_error(response: reqwest::Response) -> String {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let code = body.pointer("/error/code").and_then(|v| v.as_str()).unwrap_or("");
+        let message = body.pointer("/error/message").and_then(|v| v.as_str()).unwrap_or("");
+
+        match code {
+            "quotaLimitReached" | "insufficientStorage" => "OneDrive storage is full".to_string(),
+            "nameAlreadyExists" => "An archive with this name already exists on OneDrive".to_string(),
+            "activityLimitReached" => "OneDrive rate limit reached, try again later".to_string(),
+            "unauthenticated" | "InvalidAuthenticationToken" => "OneDrive sign-in expired, please reconnect".to_string(),
+            _ => format!("OneDrive error {}: {}", status, message),
+        }
+    }
+}
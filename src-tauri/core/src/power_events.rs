@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use windows::core::w;
+use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Shutdown::{ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage,
+    ENDSESSION_LOGOFF, MSG, PBT_APMSUSPEND, WINDOW_EX_STYLE, WINDOW_STYLE, WM_POWERBROADCAST,
+    WM_QUERYENDSESSION, WNDCLASSW,
+};
+use crate::app_log;
+
+/// Upper bound for how long the window procedure blocks waiting for a backup to finish
+const MAX_BLOCK_DURATION: Duration = Duration::from_secs(60);
+
+/// Extra time past the backup budget for the handler to give up and report back
+const BLOCK_GRACE: Duration = Duration::from_secs(5);
+
+// The configured shutdown backup budget, see set_block_budget
+static BLOCK_BUDGET_SECS: AtomicU64 = AtomicU64::new(MAX_BLOCK_DURATION.as_secs());
+
+/// Keep Windows waiting no longer than the configured shutdown backup budget (never past MAX_BLOCK_DURATION)
+pub fn set_block_budget(secs: u32) {
+    BLOCK_BUDGET_SECS.store(secs as u64, Ordering::Relaxed);
+}
+
+fn block_duration() -> Duration {
+    (Duration::from_secs(BLOCK_BUDGET_SECS.load(Ordering::Relaxed)) + BLOCK_GRACE).min(MAX_BLOCK_DURATION)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerEventKind {
+    Shutdown,
+    Logoff,
+    Suspend,
+}
+
+impl std::fmt::Display for PowerEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerEventKind::Shutdown => write!(f, "shutdown"),
+            PowerEventKind::Logoff => write!(f, "logoff"),
+            PowerEventKind::Suspend => write!(f, "sleep"),
+        }
+    }
+}
+
+/// A session-end or suspend notification. The receiver must send on `done`
+/// once it has finished (or given up), which lets Windows end the session.
+/// Nobody waits on `done` for a suspend, so sending may fail then.
+pub struct PowerEvent {
+    pub kind: PowerEventKind,
+    pub done: Sender<()>,
+}
+
+static EVENT_SENDER: OnceLock<Mutex<Sender<PowerEvent>>> = OnceLock::new();
+
+/// Create a hidden top-level window on its own thread that listens for
+/// WM_QUERYENDSESSION and WM_POWERBROADCAST and forwards them to `sender`
+pub fn start_power_listener(sender: Sender<PowerEvent>) -> Result<(), String> {
+    EVENT_SENDER
+        .set(Mutex::new(sender))
+        .map_err(|_| "Power event listener already started".to_string())?;
+
+    std::thread::spawn(|| unsafe {
+        let instance: HINSTANCE = match GetModuleHandleW(None) {
+            Ok(module) => module.into(),
+            Err(e) => {
+                app_log!("[PowerEvents] Failed to get module handle: {}", e);
+                return;
+            }
+        };
+
+        let class_name = w!("SteamBackupManagerPowerEvents");
+        let wc = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance,
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+
+        if RegisterClassW(&wc) == 0 {
+            app_log!("[PowerEvents] Failed to register window class");
+            return;
+        }
+
+        // Message-only windows don't receive session-end broadcasts, so use a never-shown top-level window
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            class_name,
+            w!("Steam Backup Manager Power Events"),
+            WINDOW_STYLE::default(),
+            0, 0, 0, 0,
+            None,
+            None,
+            instance,
+            None,
+        );
+
+        if hwnd.0 == 0 {
+            app_log!("[PowerEvents] Failed to create power event window");
+            return;
+        }
+
+        app_log!("✓ Listening for shutdown, logoff and sleep events");
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+
+    Ok(())
+}
+
+/// Forward an event; with `wait`, block until the handler reports completion
+fn dispatch(kind: PowerEventKind, wait: bool) {
+    let Some(sender) = EVENT_SENDER.get() else {
+        return;
+    };
+
+    let (done_tx, done_rx) = channel();
+    let sent = match sender.lock() {
+        Ok(sender) => sender.send(PowerEvent { kind, done: done_tx }).is_ok(),
+        Err(_) => false,
+    };
+
+    if sent && wait && done_rx.recv_timeout(block_duration()).is_err() {
+        app_log!("[PowerEvents] Backup before {} did not finish in time", kind);
+    }
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_QUERYENDSESSION => {
+            let kind = if (lparam.0 as u32) & ENDSESSION_LOGOFF != 0 {
+                PowerEventKind::Logoff
+            } else {
+                PowerEventKind::Shutdown
+            };
+
+            app_log!("[PowerEvents] Session ending ({})", kind);
+
+            // Tell Windows why we are holding up the shutdown
+            let _ = ShutdownBlockReasonCreate(hwnd, w!("Backing up game saves..."));
+            dispatch(kind, true);
+            let _ = ShutdownBlockReasonDestroy(hwnd);
+
+            // Never veto the session end
+            LRESULT(1)
+        }
+        WM_POWERBROADCAST => {
+            if wparam.0 as u32 == PBT_APMSUSPEND {
+                // Windows only gives a couple of seconds here and suspends regardless, so the
+                // backup is started without holding up the message loop
+                app_log!("[PowerEvents] System is going to sleep");
+                dispatch(PowerEventKind::Suspend, false);
+            }
+            LRESULT(1)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
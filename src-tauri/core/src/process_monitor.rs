@@ -1,23 +1,12 @@
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use sysinfo::System;
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameInfo {
-    pub name: String,
-    pub exe_path: String,
-}
-
-pub enum GameEvent {
-    Started(GameInfo),
-    Ended(GameInfo),
-}
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+use crate::game_detection::{DetectionStrategy, GameEvent, GameInfo};
 
 pub struct ProcessMonitor {
     game_executables: HashMap<String, String>, // game_name -> exe_path
     current_games: HashSet<String>,
-    system: System,
+    system: Option<System>, // created on the first check, not when the monitor is built
 }
 
 impl ProcessMonitor {
@@ -25,12 +14,14 @@ impl ProcessMonitor {
         Self {
             game_executables,
             current_games: HashSet::new(),
-            system: System::new_all(),
+            system: None,
         }
     }
     
-    pub async fn check_processes(&mut self) -> Option<GameEvent> {
-        self.system.refresh_processes();
+    pub fn check_processes(&mut self) -> Option<GameEvent> {
+        // Only names and exe paths are compared, so skip CPU, memory, disk and the rest
+        let system = self.system.get_or_insert_with(System::new);
+        system.refresh_processes_specifics(ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet));
 
         let mut running_games = HashSet::new();
 
@@ -49,7 +40,7 @@ impl ProcessMonitor {
             
             // Check if this game's executable is running
             // Look for either exact path match or just the exe name match
-            let is_running = self.system.processes().iter().any(|(_, process)| {
+            let is_running = system.processes().iter().any(|(_, process)| {
                 let process_name = process.name().to_lowercase();
                 let process_exe = process.exe()
                     .and_then(|p| p.to_str())
@@ -73,7 +64,8 @@ impl ProcessMonitor {
                     println!("Game detected: {}", game_name);
                     let event = GameEvent::Started(GameInfo {
                         name: game_name.clone(),
-                        exe_path: exe_path.clone(),
+                        app_id: None,
+                        exe_path: Some(exe_path.clone()),
                     });
                     self.current_games.insert(game_name.clone());
                     return Some(event);
@@ -85,13 +77,12 @@ impl ProcessMonitor {
         for game_name in self.current_games.clone() {
             if !running_games.contains(&game_name) {
                 println!("[ProcessMonitor] Game ended: {}", game_name);
-                let exe_path = self.game_executables.get(&game_name)
-                    .cloned()
-                    .unwrap_or_default();
+                let exe_path = self.game_executables.get(&game_name).cloned();
                 
                 self.current_games.remove(&game_name);
                 return Some(GameEvent::Ended(GameInfo {
                     name: game_name,
+                    app_id: None,
                     exe_path,
                 }));
             }
@@ -99,4 +90,23 @@ impl ProcessMonitor {
         
         None
     }
+}
+
+impl DetectionStrategy for ProcessMonitor {
+    fn name(&self) -> &'static str {
+        "Process"
+    }
+
+    // Executables the user picked by hand take precedence over Steam's guess for the same game
+    fn priority(&self) -> u8 {
+        2
+    }
+
+    fn poll(&mut self) -> Option<GameEvent> {
+        self.check_processes()
+    }
+
+    fn is_active(&self) -> bool {
+        !self.current_games.is_empty()
+    }
 }
\ No newline at end of file
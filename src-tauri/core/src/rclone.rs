@@ -0,0 +1,197 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::cloud::{CloudDestination, CloudProvider};
+use crate::network::MirrorStats;
+
+/// Totals rclone prints in its final `--use-json-log` stats line
+#[derive(Debug, Default, Deserialize)]
+struct RcloneStats {
+    #[serde(default)]
+    bytes: u64,
+    #[serde(default)]
+    transfers: usize,
+    #[serde(default)]
+    checks: usize, // files already up to date
+}
+
+#[derive(Debug, Deserialize)]
+struct RcloneLogLine {
+    stats: Option<RcloneStats>,
+}
+
+/// One entry of `rclone lsjson`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LsJsonEntry {
+    path: String,
+    size: i64,
+    mod_time: String,
+    #[serde(default)]
+    is_dir: bool,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+}
+
+/// A file on the remote, relative to the listed folder
+#[derive(Debug, Clone)]
+pub struct RemoteFile {
+    pub size: u64,
+    pub modified: i64,
+    pub sha256: Option<String>, // only on remotes that can give one
+}
+
+// The rclone executable and remote of an rclone destination
+fn remote_of(destination: &CloudDestination) -> Result<(&str, &str), String> {
+    let CloudProvider::Rclone { rclone_path, remote } = &destination.provider else {
+        return Err(format!("{} is not an rclone destination", destination.name));
+    };
+    if remote.trim().is_empty() {
+        return Err(format!("No rclone remote set for {}", destination.name));
+    }
+    // An empty path means rclone is on PATH
+    let rclone_path = if rclone_path.trim().is_empty() { "rclone" } else { rclone_path.as_str() };
+    Ok((rclone_path, remote.trim()))
+}
+
+/// `remote:path` joined with a sub folder; a bare `remote:` gets no slash after the colon
+pub fn join_remote(remote: &str, relative: &str) -> String {
+    let remote = remote.trim_end_matches('/');
+    let relative = relative.trim_start_matches('/');
+    if relative.is_empty() {
+        remote.to_string()
+    } else if remote.ends_with(':') {
+        format!("{}{}", remote, relative)
+    } else {
+        format!("{}/{}", remote, relative)
+    }
+}
+
+async fn run(rclone_path: &str, args: &[String]) -> Result<std::process::Output, String> {
+    let output = tokio::process::Command::new(rclone_path)
+        .args(args)
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW flag for Windows
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run rclone ({}): {}", rclone_path, e))?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // With --use-json-log the useful part is the last "msg"; plain output is passed through
+        let message = stderr.lines().rev()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .find(|line| line.get("level").and_then(|l| l.as_str()) == Some("error"))
+            .and_then(|line| line.get("msg").and_then(|m| m.as_str()).map(str::to_string))
+            .unwrap_or_else(|| stderr.trim().to_string());
+        Err(format!("rclone failed: {}", message))
+    }
+}
+
+/// Copy new or changed files from `local_dir` to `<remote>/<remote_subdir>` with `rclone copy`
+pub async fn copy_directory(destination: &CloudDestination, local_dir: &Path, remote_subdir: &str) -> Result<MirrorStats, String> {
+    let (rclone_path, remote) = remote_of(destination)?;
+
+    let mut args = vec![
+        "copy".to_string(),
+        local_dir.to_string_lossy().to_string(),
+        join_remote(remote, remote_subdir),
+        "--use-json-log".to_string(),
+        "--stats".to_string(),
+        "0".to_string(),
+        "--stats-log-level".to_string(),
+        "NOTICE".to_string(),
+    ];
+    if let Some(kbps) = destination.max_upload_kbps.filter(|&kbps| kbps > 0) {
+        args.push("--bwlimit".to_string());
+        args.push(format!("{}K", kbps));
+    }
+
+    let output = run(rclone_path, &args).await?;
+
+    // rclone logs to stderr; the last line carrying "stats" has the totals
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stats = stderr.lines().rev()
+        .filter_map(|line| serde_json::from_str::<RcloneLogLine>(line).ok())
+        .find_map(|line| line.stats)
+        .unwrap_or_default();
+
+    Ok(MirrorStats {
+        uploaded_files: stats.transfers,
+        skipped_files: stats.checks,
+        uploaded_bytes: stats.bytes,
+    })
+}
+
+/// Every file under `<remote>/<remote_subdir>`; a folder that doesn't exist yet is empty
+pub async fn list_files(destination: &CloudDestination, remote_subdir: &str) -> Result<BTreeMap<String, RemoteFile>, String> {
+    let (rclone_path, remote) = remote_of(destination)?;
+    let args = vec![
+        "lsjson".to_string(),
+        join_remote(remote, remote_subdir),
+        "--recursive".to_string(),
+        "--files-only".to_string(),
+        "--hash".to_string(),
+        "--hash-type".to_string(),
+        "sha256".to_string(),
+        "--use-json-log".to_string(),
+    ];
+
+    let output = match run(rclone_path, &args).await {
+        Ok(output) => output,
+        Err(e) if e.contains("directory not found") => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    let entries: Vec<LsJsonEntry> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone listing: {}", e))?;
+
+    Ok(entries.into_iter()
+        .filter(|entry| !entry.is_dir)
+        .map(|entry| {
+            let modified = chrono::DateTime::parse_from_rfc3339(&entry.mod_time)
+                .map(|t| t.timestamp())
+                .unwrap_or(0);
+            let sha256 = entry.hashes.get("sha256").filter(|h| !h.is_empty()).cloned();
+            (entry.path, RemoteFile { size: entry.size.max(0) as u64, modified, sha256 })
+        })
+        .collect())
+}
+
+/// Copy new or changed files from `<remote>/<remote_subdir>` down into `local_dir` with `rclone copy`
+pub async fn copy_from_remote(destination: &CloudDestination, remote_subdir: &str, local_dir: &Path) -> Result<(), String> {
+    let (rclone_path, remote) = remote_of(destination)?;
+    let args = vec![
+        "copy".to_string(),
+        join_remote(remote, remote_subdir),
+        local_dir.to_string_lossy().to_string(),
+        "--use-json-log".to_string(),
+    ];
+    run(rclone_path, &args).await.map(|_| ())
+}
+
+/// List the top of the remote without transferring anything
+pub async fn test_remote(destination: &CloudDestination) -> Result<(), String> {
+    let (rclone_path, remote) = remote_of(destination)?;
+    let args = vec![
+        "lsf".to_string(),
+        remote.to_string(),
+        "--max-depth".to_string(),
+        "1".to_string(),
+        "--use-json-log".to_string(),
+    ];
+    run(rclone_path, &args).await.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_remote() {
+        assert_eq!(join_remote("gdrive:", "Game"), "gdrive:Game");
+        assert_eq!(join_remote("b2:bucket/saves/", "Game"), "b2:bucket/saves/Game");
+        assert_eq!(join_remote("b2:bucket", ""), "b2:bucket");
+    }
+}
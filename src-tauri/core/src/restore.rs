@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::snapshots::{SnapshotInfo, SnapshotManager, BASE_SNAPSHOT_ID};
+use crate::app_log;
+
+/// The parts of Ludusavi's mapping.yaml we need to map stored files back to their original paths
+#[derive(Debug, Deserialize)]
+struct Mapping {
+    #[serde(default)]
+    drives: BTreeMap<String, String>, // "drive-C" -> "C:"
+    #[serde(default)]
+    backups: Vec<FullBackup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FullBackup {
+    name: String,
+    #[serde(default)]
+    when: Option<String>,
+    #[serde(default)]
+    files: BTreeMap<String, MappingFile>,
+    #[serde(default)]
+    children: Vec<DifferentialBackup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DifferentialBackup {
+    name: String,
+    #[serde(default)]
+    files: BTreeMap<String, Option<MappingFile>>, // None = removed since the full backup
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MappingFile {
+    #[serde(default)]
+    size: u64,
+}
+
+/// A file a snapshot can put back, with where it came from and where it is stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorableFile {
+    pub original_path: String,
+    pub stored_path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectiveRestoreResult {
+    pub game_name: String,
+    pub snapshot_id: String,
+    pub restored: Vec<String>,
+    pub failed: Vec<String>,
+    pub safety_snapshot_id: Option<String>,
+}
+
+/// Every file a snapshot contains, keyed by original path.
+/// Differential snapshots are resolved against their full backup like Ludusavi does on restore.
+pub fn restorable_files(snapshots: &SnapshotManager, game_name: &str, snapshot_id: &str) -> Result<Vec<RestorableFile>, String> {
+    // Versions share the game folder's mapping, app snapshots carry their own
+    let mapping_dir = if snapshot_id.starts_with("backup-") {
+        snapshots.game_dir(game_name)
+    } else {
+        snapshots.snapshot_dir(game_name, snapshot_id)?
+    };
+    let lookup_id = if snapshot_id.starts_with("backup-") { snapshot_id } else { BASE_SNAPSHOT_ID };
+    mapping_files(&mapping_dir, Some(lookup_id))?
+        .map(|(_, files)| files)
+        .ok_or_else(|| format!("Snapshot {} is not listed in the backup mapping for {}", snapshot_id, game_name))
+}
+
+/// The files of the newest backup in a game folder (the latest full backup or its latest differential)
+/// and that backup's name, e.g. "." or "backup-20240131T120000Z.zip"
+pub fn latest_backup_files(game_dir: &Path) -> Result<(String, Vec<RestorableFile>), String> {
+    mapping_files(game_dir, None)?
+        .ok_or_else(|| format!("No backups listed in {}", game_dir.join("mapping.yaml").display()))
+}
+
+/// When the full backup `name` in a game folder was taken, as Unix seconds; None if the mapping doesn't say
+pub fn full_backup_time(mapping_dir: &Path, name: &str) -> Option<i64> {
+    let mapping = read_mapping(mapping_dir).ok()?;
+    let when = mapping.backups.into_iter().find(|full| full.name == name)?.when?;
+    chrono::DateTime::parse_from_rfc3339(&when).ok().map(|when| when.timestamp())
+}
+
+fn read_mapping(mapping_dir: &Path) -> Result<Mapping, String> {
+    let mapping_path = mapping_dir.join("mapping.yaml");
+    let contents = fs::read_to_string(&mapping_path)
+        .map_err(|e| format!("Failed to read {}: {}", mapping_path.display(), e))?;
+    serde_yaml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", mapping_path.display(), e))
+}
+
+// Resolve a backup of the mapping in `mapping_dir` (the newest when `lookup_id` is None); None if it isn't listed
+fn mapping_files(mapping_dir: &Path, lookup_id: Option<&str>) -> Result<Option<(String, Vec<RestorableFile>)>, String> {
+    let mapping = read_mapping(mapping_dir)?;
+
+    // Ludusavi appends backups, so the newest is the last differential of the last full backup
+    let latest = mapping.backups.last()
+        .map(|full| full.children.last().map_or(full.name.as_str(), |child| child.name.as_str()));
+    let Some(lookup_id) = lookup_id.or(latest) else {
+        return Ok(None);
+    };
+    let folder_of = |name: &str| if name == BASE_SNAPSHOT_ID { mapping_dir.to_path_buf() } else { mapping_dir.join(name) };
+
+    let mut files: BTreeMap<String, (PathBuf, u64)> = BTreeMap::new();
+    let mut found = false;
+    for full in &mapping.backups {
+        let child = full.children.iter().find(|c| c.name == lookup_id);
+        if full.name != lookup_id && child.is_none() {
+            continue;
+        }
+        found = true;
+
+        let full_folder = folder_of(&full.name);
+        for (original, file) in &full.files {
+            files.insert(original.clone(), (full_folder.join(stored_relative_path(&mapping.drives, original)), file.size));
+        }
+        if let Some(child) = child {
+            let child_folder = folder_of(&child.name);
+            for (original, file) in &child.files {
+                match file {
+                    Some(file) => {
+                        files.insert(original.clone(), (child_folder.join(stored_relative_path(&mapping.drives, original)), file.size));
+                    }
+                    None => {
+                        files.remove(original);
+                    }
+                }
+            }
+        }
+        break;
+    }
+
+    if !found {
+        return Ok(None);
+    }
+
+    let files = files.into_iter()
+        .map(|(original_path, (stored, size))| RestorableFile {
+            original_path,
+            stored_path: stored.to_string_lossy().to_string(),
+            size,
+        })
+        .collect();
+    Ok(Some((lookup_id.to_string(), files)))
+}
+
+/// "C:/Users/me/save.dat" -> "drive-C/Users/me/save.dat", using the mapping's drive table
+fn stored_relative_path(drives: &BTreeMap<String, String>, original: &str) -> PathBuf {
+    for (folder, drive) in drives {
+        if let Some(rest) = original.strip_prefix(drive.as_str()) {
+            return Path::new(folder).join(rest.trim_start_matches('/'));
+        }
+    }
+    // Paths without a drive letter are stored under "drive-0"
+    Path::new("drive-0").join(original.trim_start_matches('/'))
+}
+
+/// Pick the snapshot files matching the selection; a selected folder matches everything below it
+pub fn select_files<'a>(files: &'a [RestorableFile], selection: &[String]) -> Vec<&'a RestorableFile> {
+    let normalize = |path: &str| path.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    let selection: Vec<String> = selection.iter().map(|s| normalize(s)).filter(|s| !s.is_empty()).collect();
+
+    files.iter()
+        .filter(|file| {
+            let path = normalize(&file.original_path);
+            selection.iter().any(|selected| path == *selected || path.starts_with(&format!("{}/", selected)))
+        })
+        .collect()
+}
+
+/// Whether a restore may go ahead after trying to keep the current saves. A game without saves has
+/// nothing to lose; any other failure refuses the restore, which otherwise couldn't be undone.
+pub fn require_safety_snapshot(game_name: &str, snapshot: Result<Option<SnapshotInfo>, String>) -> Result<Option<SnapshotInfo>, String> {
+    snapshot.map_err(|e| format!("Not restoring {}: the current saves couldn't be kept first ({})", game_name, e))
+}
+
+/// Zip backups the selected files are stored in, by archive path
+pub type OpenArchives = HashMap<PathBuf, zip::ZipArchive<fs::File>>;
+
+/// The zip archive a stored file is inside of. Zip backups show up as a path through the archive,
+/// e.g. ".../backup-20240131T120000Z.zip/drive-C/save.dat"; None for files stored in a folder.
+pub fn stored_archive(stored: &Path) -> Option<&Path> {
+    stored.ancestors().find(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) && p.is_file())
+}
+
+/// Open the archives of any zip-stored files up front, so an unreadable backup is refused before
+/// the current saves are touched
+pub fn open_archives(files: &[&RestorableFile]) -> Result<OpenArchives, String> {
+    let mut archives = OpenArchives::new();
+    for file in files {
+        let Some(archive_path) = stored_archive(Path::new(&file.stored_path)) else { continue };
+        if archives.contains_key(archive_path) {
+            continue;
+        }
+        let opened = fs::File::open(archive_path)
+            .map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+        let zip = zip::ZipArchive::new(opened)
+            .map_err(|e| format!("{} is not a readable zip: {}", archive_path.display(), e))?;
+        archives.insert(archive_path.to_path_buf(), zip);
+    }
+    Ok(archives)
+}
+
+// Copy a stored file to `target`, extracting it when it's inside one of `archives`
+fn copy_stored(stored: &Path, target: &Path, archives: &mut OpenArchives) -> io::Result<u64> {
+    let Some(archive_path) = stored_archive(stored) else {
+        return fs::copy(stored, target);
+    };
+    let archive = archives.get_mut(archive_path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{} wasn't opened", archive_path.display())))?;
+    let entry_name = stored.strip_prefix(archive_path).unwrap_or(stored).to_string_lossy().replace('\\', "/");
+    let mut entry = archive.by_name(&entry_name)?;
+
+    // Extract next to the target first, so a damaged entry doesn't leave half a save behind
+    let partial = target.with_file_name(format!("{}.partial", target.file_name().unwrap_or_default().to_string_lossy()));
+    let copied = fs::File::create(&partial).and_then(|mut out| io::copy(&mut entry, &mut out));
+    match copied {
+        Ok(bytes) => fs::rename(&partial, target).map(|_| bytes),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Copy the selected files back to their original locations, extracting zip-stored ones from
+/// `archives` (see open_archives); returns (restored, failed) paths
+pub fn restore_files(files: &[&RestorableFile], archives: &mut OpenArchives) -> (Vec<String>, Vec<String>) {
+    let mut restored = Vec::new();
+    let mut failed = Vec::new();
+
+    for file in files {
+        let target = Path::new(&file.original_path);
+        let result = target.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| copy_stored(Path::new(&file.stored_path), target, archives));
+
+        match result {
+            Ok(_) => {
+                app_log!("[Restore] Restored {}", file.original_path);
+                restored.push(file.original_path.clone());
+            }
+            Err(e) => {
+                app_log!("[Restore] Failed to restore {}: {}", file.original_path, e);
+                failed.push(format!("{}: {}", file.original_path, e));
+            }
+        }
+    }
+
+    (restored, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_safety_snapshot() {
+        let snapshot = SnapshotInfo {
+            id: "pre-restore-20240501T120000Z".to_string(),
+            game_name: "Portal 2".to_string(),
+            path: String::new(),
+            created_at: 1714564800,
+            file_count: 3,
+            total_bytes: 1024,
+        };
+        let kept = require_safety_snapshot("Portal 2", Ok(Some(snapshot))).unwrap();
+        assert_eq!(kept.unwrap().id, "pre-restore-20240501T120000Z");
+
+        // Nothing on disk yet, nothing to lose
+        assert!(require_safety_snapshot("Portal 2", Ok(None)).unwrap().is_none());
+
+        let refused = require_safety_snapshot("Portal 2", Err("Ludusavi timed out".to_string()));
+        assert!(refused.unwrap_err().contains("Ludusavi timed out"));
+    }
+
+    #[test]
+    fn test_restore_files_from_zip_backup() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("sbm-restore-zip-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let archive_path = dir.join("backup-20240501T120000Z.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        zip.start_file("drive-C/Saves/slot1.sav", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"slot one").unwrap();
+        zip.finish().unwrap();
+
+        let file = RestorableFile {
+            original_path: dir.join("restored").join("slot1.sav").to_string_lossy().to_string(),
+            stored_path: archive_path.join("drive-C").join("Saves").join("slot1.sav").to_string_lossy().to_string(),
+            size: 8,
+        };
+        let missing = RestorableFile {
+            original_path: dir.join("restored").join("slot2.sav").to_string_lossy().to_string(),
+            stored_path: archive_path.join("drive-C").join("Saves").join("slot2.sav").to_string_lossy().to_string(),
+            size: 8,
+        };
+        assert_eq!(stored_archive(Path::new(&file.stored_path)), Some(archive_path.as_path()));
+
+        let selected = vec![&file, &missing];
+        let mut archives = open_archives(&selected).unwrap();
+        let (restored, failed) = restore_files(&selected, &mut archives);
+        assert_eq!(restored, vec![file.original_path.clone()]);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(fs::read(&file.original_path).unwrap(), b"slot one");
+        assert!(!Path::new(&missing.original_path).exists());
+
+        // A damaged archive is refused before anything is restored
+        fs::write(&archive_path, b"not a zip").unwrap();
+        assert!(open_archives(&selected).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePathCandidate {
+    pub path: String,
+    pub source: String, // "manifest", "process" or "heuristic"
+    pub exists: bool,
+    pub file_count: usize,
+    pub last_modified: Option<i64>,
+}
+
+// Folder names games commonly use for save data
+const SAVE_FOLDER_NAMES: [&str; 8] = ["save", "saves", "savegames", "savegame", "saved", "savedata", "profiles", "userdata"];
+
+/// Suggests likely save locations for a game from the Ludusavi manifest, the running process and common conventions.
+/// The process only contributes its exe folder and working directory; its open file handles aren't inspected.
+pub struct SavePathDiscovery {
+    game_name: String,
+    install_dir: Option<PathBuf>,
+}
+
+impl SavePathDiscovery {
+    pub fn new(game_name: &str) -> Self {
+        Self {
+            game_name: game_name.to_string(),
+            install_dir: None,
+        }
+    }
+
+    pub fn discover(
+        &mut self,
+        manifest_entry: Option<&serde_json::Value>,
+        game_executables: &HashMap<String, String>,
+    ) -> Vec<SavePathCandidate> {
+        let mut candidates: Vec<SavePathCandidate> = Vec::new();
+
+        // Running process first, it tells us where the game is installed (<base> in the manifest)
+        for dir in self.process_directories(game_executables) {
+            for path in Self::save_like_subfolders(&dir) {
+                Self::push_candidate(&mut candidates, path, "process");
+            }
+        }
+
+        if let Some(entry) = manifest_entry {
+            if let Some(files) = entry.get("files").and_then(|f| f.as_object()) {
+                for raw_path in files.keys() {
+                    for path in self.expand_manifest_path(raw_path) {
+                        Self::push_candidate(&mut candidates, path, "manifest");
+                    }
+                }
+            }
+        }
+
+        for path in self.heuristic_paths() {
+            Self::push_candidate(&mut candidates, path, "heuristic");
+        }
+
+        // Existing, recently written folders are the most likely answer
+        candidates.sort_by(|a, b| {
+            b.exists.cmp(&a.exists).then(b.last_modified.cmp(&a.last_modified))
+        });
+        candidates
+    }
+
+    fn push_candidate(candidates: &mut Vec<SavePathCandidate>, path: PathBuf, source: &str) {
+        let path_str = path.to_string_lossy().to_string();
+        if candidates.iter().any(|c| c.path.eq_ignore_ascii_case(&path_str)) {
+            return;
+        }
+
+        let (file_count, last_modified) = Self::folder_stats(&path, 0);
+        candidates.push(SavePathCandidate {
+            path: path_str,
+            source: source.to_string(),
+            exists: path.exists(),
+            file_count,
+            last_modified,
+        });
+    }
+
+    /// Working directory and exe folder of the game's running process, if any. Saves the game has open
+    /// elsewhere (AppData, Documents) are left to the manifest and heuristic suggestions.
+    fn process_directories(&mut self, game_executables: &HashMap<String, String>) -> Vec<PathBuf> {
+        let exe_name = game_executables.get(&self.game_name)
+            .and_then(|path| Path::new(path).file_name())
+            .map(|name| name.to_string_lossy().to_lowercase());
+
+        let Some(exe_name) = exe_name else {
+            return Vec::new();
+        };
+
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessRefreshKind::new()
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_cwd(UpdateKind::OnlyIfNotSet),
+        );
+
+        let mut dirs = Vec::new();
+        for process in system.processes().values() {
+            if process.name().to_lowercase() != exe_name {
+                continue;
+            }
+
+            if let Some(exe_dir) = process.exe().and_then(|exe| exe.parent()) {
+                self.install_dir = Some(exe_dir.to_path_buf());
+                dirs.push(exe_dir.to_path_buf());
+            }
+            if let Some(cwd) = process.cwd() {
+                if !dirs.iter().any(|d| d == cwd) {
+                    dirs.push(cwd.to_path_buf());
+                }
+            }
+        }
+
+        dirs
+    }
+
+    /// Resolve Ludusavi placeholders and wildcards into concrete paths
+    fn expand_manifest_path(&self, raw_path: &str) -> Vec<PathBuf> {
+        let home = dirs::home_dir().unwrap_or_default();
+        let replacements: Vec<(&str, String)> = vec![
+            ("<winAppData>", dirs::data_dir().unwrap_or_default().to_string_lossy().to_string()),
+            ("<winLocalAppDataLow>", home.join("AppData").join("LocalLow").to_string_lossy().to_string()),
+            ("<winLocalAppData>", dirs::data_local_dir().unwrap_or_default().to_string_lossy().to_string()),
+            ("<winDocuments>", dirs::document_dir().unwrap_or_default().to_string_lossy().to_string()),
+            ("<winPublic>", r"C:\Users\Public".to_string()),
+            ("<winProgramData>", r"C:\ProgramData".to_string()),
+            ("<winDir>", r"C:\Windows".to_string()),
+            ("<home>", home.to_string_lossy().to_string()),
+            ("<osUserName>", std::env::var("USERNAME").unwrap_or_default()),
+            ("<storeUserId>", "*".to_string()),
+            ("<storeGameId>", "*".to_string()),
+            ("<game>", self.game_name.clone()),
+        ];
+
+        let mut path = raw_path.to_string();
+
+        // <base> and <root> need the install location
+        if path.contains("<base>") || path.contains("<root>") {
+            match &self.install_dir {
+                Some(dir) => {
+                    path = path.replace("<base>", &dir.to_string_lossy());
+                    let root = dir.parent().unwrap_or(dir);
+                    path = path.replace("<root>", &root.to_string_lossy());
+                }
+                None => return Vec::new(),
+            }
+        }
+
+        for (placeholder, value) in &replacements {
+            if value.is_empty() && path.contains(placeholder) {
+                return Vec::new();
+            }
+            path = path.replace(placeholder, value);
+        }
+
+        // Skip anything we couldn't resolve (xdg paths etc.)
+        if path.contains('<') {
+            return Vec::new();
+        }
+
+        // Manifest entries may point at files; the folder is what we back up
+        Self::expand_wildcards(&path.replace('/', "\\"))
+            .into_iter()
+            .map(|p| if p.is_file() { p.parent().map(|d| d.to_path_buf()).unwrap_or(p) } else { p })
+            .collect()
+    }
+
+    /// Expand `*` components by listing directories, keeping the literal path when nothing matches
+    fn expand_wildcards(path: &str) -> Vec<PathBuf> {
+        if !path.contains('*') {
+            return vec![PathBuf::from(path)];
+        }
+
+        let mut current: Vec<PathBuf> = vec![PathBuf::new()];
+        for component in path.split('\\').filter(|c| !c.is_empty()) {
+            let mut next = Vec::new();
+            for base in &current {
+                if component.contains('*') {
+                    let pattern = format!("^(?i){}$", regex::escape(component).replace(r"\*", ".*"));
+                    let Ok(re) = regex::Regex::new(&pattern) else { continue };
+                    if let Ok(entries) = fs::read_dir(base) {
+                        for entry in entries.filter_map(|e| e.ok()) {
+                            if re.is_match(&entry.file_name().to_string_lossy()) {
+                                next.push(entry.path());
+                            }
+                        }
+                    }
+                } else if base.as_os_str().is_empty() {
+                    // Drive letters need a trailing separator to be treated as roots
+                    next.push(PathBuf::from(format!("{}\\", component)));
+                } else {
+                    next.push(base.join(component));
+                }
+            }
+            current = next;
+        }
+
+        current
+    }
+
+    /// Common locations named after the game
+    fn heuristic_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let name = &self.game_name;
+
+        if let Some(docs) = dirs::document_dir() {
+            paths.push(docs.join("My Games").join(name));
+            paths.push(docs.join(name));
+        }
+        if let Some(roaming) = dirs::data_dir() {
+            paths.push(roaming.join(name));
+        }
+        if let Some(local) = dirs::data_local_dir() {
+            paths.push(local.join(name));
+        }
+        if let Some(home) = dirs::home_dir() {
+            paths.push(home.join("Saved Games").join(name));
+
+            // Unity games use LocalLow\<Company>\<Game>
+            if let Ok(companies) = fs::read_dir(home.join("AppData").join("LocalLow")) {
+                for company in companies.filter_map(|e| e.ok()) {
+                    let candidate = company.path().join(name);
+                    if candidate.is_dir() {
+                        paths.push(candidate);
+                    }
+                }
+            }
+        }
+
+        // Only suggest heuristic guesses that actually exist
+        paths.into_iter().filter(|p| p.is_dir()).collect()
+    }
+
+    fn save_like_subfolders(dir: &Path) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_lowercase();
+                if entry.path().is_dir() && SAVE_FOLDER_NAMES.contains(&name.as_str()) {
+                    found.push(entry.path());
+                }
+            }
+        }
+        found
+    }
+
+    /// File count and newest modification time (limited depth to stay fast)
+    fn folder_stats(dir: &Path, depth: usize) -> (usize, Option<i64>) {
+        let mut count = 0;
+        let mut newest: Option<i64> = None;
+
+        if depth > 4 {
+            return (count, newest);
+        }
+
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.is_dir() {
+                    let (sub_count, sub_newest) = Self::folder_stats(&path, depth + 1);
+                    count += sub_count;
+                    newest = newest.max(sub_newest);
+                } else {
+                    count += 1;
+                    let modified = entry.metadata()
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs() as i64);
+                    newest = newest.max(modified);
+                }
+            }
+        }
+
+        (count, newest)
+    }
+}
@@ -0,0 +1,108 @@
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use crate::app_log;
+
+/// Watches user-configured save directories and reports a game once its files
+/// have stopped changing for the debounce period
+pub struct SaveDirectoryWatcher {
+    _watchers: Vec<RecommendedWatcher>,
+    dirty_games: Arc<Mutex<HashMap<String, Instant>>>, // game_name -> last write seen
+}
+
+impl SaveDirectoryWatcher {
+    /// Start watching every configured directory. Games whose saves settled are sent on the returned channel.
+    pub fn start(custom_save_paths: &HashMap<String, Vec<String>>, debounce: Duration) -> (Self, mpsc::Receiver<String>) {
+        let dirty_games: Arc<Mutex<HashMap<String, Instant>>> = Arc::new(Mutex::new(HashMap::new()));
+        let mut watchers = Vec::new();
+
+        for (game_name, paths) in custom_save_paths {
+            for path in paths {
+                let dir = PathBuf::from(path);
+                if !dir.exists() {
+                    app_log!("[SaveWatcher] Save folder for {} does not exist: {}", game_name, path);
+                    continue;
+                }
+
+                let dirty = dirty_games.clone();
+                let name = game_name.clone();
+                let watcher = RecommendedWatcher::new(
+                    move |res: Result<Event, notify::Error>| {
+                        if let Ok(event) = res {
+                            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                                if let Ok(mut dirty) = dirty.lock() {
+                                    dirty.insert(name.clone(), Instant::now());
+                                }
+                            }
+                        }
+                    },
+                    Config::default(),
+                );
+
+                match watcher {
+                    Ok(mut watcher) => match watcher.watch(&dir, RecursiveMode::Recursive) {
+                        Ok(()) => {
+                            app_log!("[SaveWatcher] Watching {} saves at: {}", game_name, path);
+                            watchers.push(watcher);
+                        }
+                        Err(e) => app_log!("[SaveWatcher] Failed to watch {}: {}", path, e),
+                    },
+                    Err(e) => app_log!("[SaveWatcher] Failed to create watcher for {}: {}", game_name, e),
+                }
+            }
+        }
+
+        let (tx, rx) = mpsc::channel(16);
+
+        // Flush games whose saves have been quiet for the debounce period.
+        // The task ends once the watcher (and with it the dirty map) is dropped.
+        let weak_dirty = Arc::downgrade(&dirty_games);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+
+                let Some(dirty) = weak_dirty.upgrade() else {
+                    break;
+                };
+
+                let settled: Vec<String> = {
+                    let mut dirty = match dirty.lock() {
+                        Ok(dirty) => dirty,
+                        Err(_) => break,
+                    };
+                    let settled: Vec<String> = dirty.iter()
+                        .filter(|(_, last_write)| last_write.elapsed() >= debounce)
+                        .map(|(name, _)| name.clone())
+                        .collect();
+                    for name in &settled {
+                        dirty.remove(name);
+                    }
+                    settled
+                };
+
+                for game_name in settled {
+                    app_log!("[SaveWatcher] Saves for {} settled, requesting backup", game_name);
+                    if tx.send(game_name).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        (
+            Self {
+                _watchers: watchers,
+                dirty_games,
+            },
+            rx,
+        )
+    }
+
+    /// Number of games with unsaved (not yet backed up) changes
+    pub fn pending_count(&self) -> usize {
+        self.dirty_games.lock().map(|d| d.len()).unwrap_or(0)
+    }
+}
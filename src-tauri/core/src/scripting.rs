@@ -0,0 +1,201 @@
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use crate::app_log;
+
+// Keeps a runaway loop in a user script from spinning forever
+const MAX_OPERATIONS: u64 = 5_000_000;
+const HTTP_TIMEOUT_SECS: u64 = 15;
+
+/// Something a user script can react to; each maps to a hook function of the same name
+#[derive(Debug, Clone)]
+pub enum ScriptEvent {
+    GameStarted { game_name: String, app_id: Option<u32> },
+    GameEnded { game_name: String, app_id: Option<u32> },
+    BackupFinished {
+        game_name: String,
+        app_id: Option<u32>,
+        success: bool,
+        files_backed_up: usize,
+        total_size: String,
+        backup_path: String,
+        error: Option<String>,
+    },
+    AchievementUnlocked {
+        game_name: String,
+        app_id: u32,
+        achievement_id: String,
+        display_name: String,
+        description: String,
+        unlock_time: i64,
+        global_unlock_percentage: Option<f64>,
+    },
+}
+
+impl ScriptEvent {
+    /// Function a script defines to handle this event, e.g. `fn on_backup_finished(event) { ... }`
+    pub fn hook_name(&self) -> &'static str {
+        match self {
+            ScriptEvent::GameStarted { .. } => "on_game_started",
+            ScriptEvent::GameEnded { .. } => "on_game_ended",
+            ScriptEvent::BackupFinished { .. } => "on_backup_finished",
+            ScriptEvent::AchievementUnlocked { .. } => "on_achievement_unlocked",
+        }
+    }
+
+    fn to_map(&self) -> Map {
+        let mut map = Map::new();
+        let mut set = |key: &str, value: Dynamic| {
+            map.insert(key.into(), value);
+        };
+        let app_id_value = |app_id: Option<u32>| app_id.map_or(Dynamic::UNIT, |id| Dynamic::from(id as i64));
+
+        match self {
+            ScriptEvent::GameStarted { game_name, app_id } | ScriptEvent::GameEnded { game_name, app_id } => {
+                set("game_name", game_name.clone().into());
+                set("app_id", app_id_value(*app_id));
+            }
+            ScriptEvent::BackupFinished { game_name, app_id, success, files_backed_up, total_size, backup_path, error } => {
+                set("game_name", game_name.clone().into());
+                set("app_id", app_id_value(*app_id));
+                set("success", (*success).into());
+                set("files_backed_up", (*files_backed_up as i64).into());
+                set("total_size", total_size.clone().into());
+                set("backup_path", backup_path.clone().into());
+                set("error", error.clone().map_or(Dynamic::UNIT, Dynamic::from));
+            }
+            ScriptEvent::AchievementUnlocked {
+                game_name, app_id, achievement_id, display_name, description, unlock_time, global_unlock_percentage,
+            } => {
+                set("game_name", game_name.clone().into());
+                set("app_id", (*app_id as i64).into());
+                set("achievement_id", achievement_id.clone().into());
+                set("display_name", display_name.clone().into());
+                set("description", description.clone().into());
+                set("unlock_time", (*unlock_time).into());
+                set("global_unlock_percentage", global_unlock_percentage.map_or(Dynamic::UNIT, Dynamic::from));
+            }
+        }
+        set("event", self.hook_name().trim_start_matches("on_").into());
+        map
+    }
+}
+
+pub fn scripts_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("scripts")
+}
+
+/// `*.rhai` files in the scripts folder, in name order so users can control the run order
+pub fn list_scripts() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(scripts_dir()) else { return Vec::new() };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("rhai")))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn script_error(message: String) -> Box<EvalAltResult> {
+    message.into()
+}
+
+/// Run a program without a shell and return its exit code
+fn run_command(program: &str, args: Array) -> Result<i64, Box<EvalAltResult>> {
+    let args: Vec<String> = args.into_iter().map(|a| a.to_string()).collect();
+    let status = Command::new(program)
+        .args(&args)
+        .status()
+        .map_err(|e| script_error(format!("Failed to run {}: {}", program, e)))?;
+    Ok(status.code().unwrap_or(-1) as i64)
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), Box<EvalAltResult>> {
+    let path = PathBuf::from(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| script_error(format!("Failed to create {}: {}", parent.display(), e)))?;
+    }
+    fs::write(&path, contents).map_err(|e| script_error(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+/// POST a body and return the status code; bodies that parse as JSON are sent as JSON
+fn http_post(url: &str, body: &str) -> Result<i64, Box<EvalAltResult>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| script_error(format!("Failed to create HTTP client: {}", e)))?;
+    let content_type = if serde_json::from_str::<serde_json::Value>(body).is_ok() {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    let response = client.post(url)
+        .header("Content-Type", content_type)
+        .body(body.to_string())
+        .send()
+        .map_err(|e| script_error(format!("POST to {} failed: {}", url, e)))?;
+    Ok(response.status().as_u16() as i64)
+}
+
+fn build_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| app_log!("[Scripts] {}", text));
+    engine.register_fn("log", |text: &str| app_log!("[Scripts] {}", text));
+    engine.register_fn("run_command", run_command);
+    engine.register_fn("write_file", write_file);
+    engine.register_fn("http_post", http_post);
+    engine
+}
+
+/// Run the event's hook in every script that defines it. Returns the scripts that ran and the ones that failed.
+pub fn run_hooks(event: &ScriptEvent) -> (Vec<String>, Vec<(String, String)>) {
+    let engine = build_engine();
+    let hook = event.hook_name();
+    let mut ran = Vec::new();
+    let mut failed = Vec::new();
+
+    for path in list_scripts() {
+        let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let ast = match engine.compile_file(path.clone()) {
+            Ok(ast) => ast,
+            Err(e) => {
+                failed.push((file, e.to_string()));
+                continue;
+            }
+        };
+        if !ast.iter_functions().any(|f| f.name == hook && f.params.len() == 1) {
+            continue;
+        }
+
+        let mut scope = Scope::new();
+        match engine.call_fn::<Dynamic>(&mut scope, &ast, hook, (Dynamic::from_map(event.to_map()),)) {
+            Ok(_) => ran.push(file),
+            Err(e) => failed.push((file, e.to_string())),
+        }
+    }
+
+    (ran, failed)
+}
+
+/// Run hooks on a background thread so slow scripts never hold up monitoring or backups
+pub fn dispatch(event: ScriptEvent) {
+    if list_scripts().is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        let (ran, failed) = run_hooks(&event);
+        if !ran.is_empty() {
+            app_log!("[Scripts] {} ran in: {}", event.hook_name(), ran.join(", "));
+        }
+        for (file, error) in failed {
+            app_log!("[Scripts] {} failed in {}: {}", event.hook_name(), file, error);
+        }
+    });
+}
+
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use crate::achievements::{Achievement, AchievementDatabase};
+
+/// What happened during one play session of a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecap {
+    pub game_name: String,
+    pub app_id: u32,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub playtime_secs: i64,
+    pub unlocked_count: usize,
+    pub rarest: Option<Achievement>, // lowest global unlock percentage among this session's unlocks
+    pub unlocks: Vec<Achievement>,
+}
+
+impl SessionRecap {
+    pub fn build(db: &AchievementDatabase, game_name: &str, app_id: u32, started_at: i64, ended_at: i64) -> Result<Self, String> {
+        let unlocks = db.get_unlocks_between(app_id, started_at, ended_at)?;
+        let rarest = unlocks.iter()
+            .filter(|a| a.global_unlock_percentage.is_some())
+            .min_by(|a, b| a.global_unlock_percentage.partial_cmp(&b.global_unlock_percentage).unwrap_or(std::cmp::Ordering::Equal))
+            .cloned();
+
+        Ok(Self {
+            game_name: game_name.to_string(),
+            app_id,
+            started_at,
+            ended_at,
+            playtime_secs: (ended_at - started_at).max(0),
+            unlocked_count: unlocks.len(),
+            rarest,
+            unlocks,
+        })
+    }
+
+    /// "2h 05m" / "12m"
+    pub fn playtime_label(&self) -> String {
+        let minutes = self.playtime_secs / 60;
+        if minutes >= 60 {
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        } else {
+            format!("{}m", minutes)
+        }
+    }
+
+    /// Notification body, e.g. "🏆 3 achievements in 1h 20m\nRarest: Flawless (2.1%)"
+    pub fn summary(&self) -> String {
+        let mut body = format!(
+            "🏆 {} achievement{} in {}",
+            self.unlocked_count,
+            if self.unlocked_count == 1 { "" } else { "s" },
+            self.playtime_label()
+        );
+        if let Some(rarest) = &self.rarest {
+            body.push_str(&format!(
+                "\nRarest: {} ({:.1}%)",
+                rarest.display_name,
+                rarest.global_unlock_percentage.unwrap_or_default()
+            ));
+        }
+        body
+    }
+}
@@ -0,0 +1,298 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::config::AppConfig;
+use crate::disk_space;
+use crate::steam_accounts::{self, STEAM_ID64_BASE};
+use crate::steam_monitor::SteamMonitor;
+use crate::app_log;
+
+const LUDUSAVI_RELEASE_URL: &str = "https://api.github.com/repos/mtkennerly/ludusavi/releases/latest";
+
+/// Whether the first-run setup still has to run, and which required settings are missing
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupStatus {
+    pub needs_setup: bool,
+    pub missing: Vec<String>, // config fields, e.g. "ludusaviPath"
+}
+
+pub fn status(config: &AppConfig) -> SetupStatus {
+    let mut missing = Vec::new();
+    if config.ludusavi_path.is_empty() || !Path::new(&config.ludusavi_path).exists() {
+        missing.push("ludusaviPath".to_string());
+    }
+    if config.backup_path.is_empty() {
+        missing.push("backupPath".to_string());
+    }
+    SetupStatus { needs_setup: !missing.is_empty(), missing }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SteamAccount {
+    pub account_id: String, // name of the userdata folder, what steamUserId holds
+    pub steam_id_64: String,
+    pub most_recent: bool,
+}
+
+/// Step 1: where Steam is and who uses it
+#[derive(Debug, Clone, Serialize)]
+pub struct SteamDetection {
+    pub steam_path: Option<String>,
+    pub accounts: Vec<SteamAccount>,
+    pub suggested_user_id: Option<String>,
+}
+
+pub fn detect_steam() -> SteamDetection {
+    let Ok(steam_path) = SteamMonitor::find_steam_path() else {
+        return SteamDetection { steam_path: None, accounts: Vec::new(), suggested_user_id: None };
+    };
+
+    let accounts = steam_accounts::login_users(&steam_path)
+        .into_iter()
+        .filter_map(|user| {
+            let steam_id_64 = user.account_id.parse::<u64>().ok()? + STEAM_ID64_BASE;
+            Some(SteamAccount { account_id: user.account_id, steam_id_64: steam_id_64.to_string(), most_recent: user.most_recent })
+        })
+        .collect();
+
+    SteamDetection {
+        suggested_user_id: steam_accounts::detect_active_user(&steam_path),
+        steam_path: Some(steam_path.to_string_lossy().to_string()),
+        accounts,
+    }
+}
+
+/// Where download_ludusavi puts ludusavi.exe
+pub fn ludusavi_install_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("ludusavi")
+}
+
+/// Step 2: an existing Ludusavi, downloaded by an earlier setup, on PATH or in Program Files
+pub fn find_ludusavi() -> Option<PathBuf> {
+    let mut candidates = vec![ludusavi_install_dir().join("ludusavi.exe")];
+    if let Some(path) = std::env::var_os("PATH") {
+        candidates.extend(std::env::split_paths(&path).map(|dir| dir.join("ludusavi.exe")));
+    }
+    candidates.push(PathBuf::from(r"C:\Program Files\Ludusavi\ludusavi.exe"));
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// The 64-bit Windows zip among a Ludusavi release's assets
+fn pick_windows_asset(assets: &[ReleaseAsset]) -> Option<&ReleaseAsset> {
+    assets.iter().find(|asset| asset.name.to_lowercase().ends_with("win64.zip"))
+}
+
+/// Download the latest Ludusavi release from GitHub into ludusavi_install_dir. Returns the exe path.
+pub async fn download_ludusavi() -> Result<PathBuf, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .connect_timeout(Duration::from_secs(10))
+        .user_agent("steam-backup-manager") // GitHub refuses API requests without one
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let release: Release = client.get(LUDUSAVI_RELEASE_URL)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to look up the latest Ludusavi release: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to read the Ludusavi release: {}", e))?;
+    let asset = pick_windows_asset(&release.assets)
+        .ok_or_else(|| format!("Ludusavi {} has no Windows download", release.tag_name))?;
+    app_log!("[Setup] Downloading {}", asset.name);
+
+    let bytes = client.get(&asset.browser_download_url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download Ludusavi: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download Ludusavi: {}", e))?;
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes.as_ref()))
+        .map_err(|e| format!("Failed to open the Ludusavi download: {}", e))?;
+    let mut exe = archive.by_name("ludusavi.exe")
+        .map_err(|_| "The Ludusavi download doesn't contain ludusavi.exe".to_string())?;
+    let mut contents = Vec::new();
+    exe.read_to_end(&mut contents)
+        .map_err(|e| format!("Failed to unpack Ludusavi: {}", e))?;
+
+    let dir = ludusavi_install_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join("ludusavi.exe");
+    // Written under another name first so a failed download never leaves a broken exe behind
+    let partial = path.with_extension("part");
+    fs::write(&partial, &contents).map_err(|e| format!("Failed to save Ludusavi: {}", e))?;
+    fs::rename(&partial, &path).map_err(|e| format!("Failed to save Ludusavi: {}", e))?;
+    Ok(path)
+}
+
+/// Step 3: a backup folder and whether its drive has room
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupFolderCheck {
+    pub path: String,
+    pub exists: bool,
+    pub free_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    pub enough_space: bool, // at least the low disk space warning threshold is free
+    pub error: Option<String>,
+}
+
+/// "Steam Save Backups" in the user's Documents folder
+pub fn default_backup_folder() -> PathBuf {
+    dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Steam Save Backups")
+}
+
+pub fn check_backup_folder(path: &str, min_free_mb: u32) -> BackupFolderCheck {
+    let exists = Path::new(path).is_dir();
+    match disk_space::usage(path) {
+        Ok(usage) => BackupFolderCheck {
+            path: path.to_string(),
+            exists,
+            free_bytes: Some(usage.free_bytes),
+            total_bytes: Some(usage.total_bytes),
+            enough_space: !disk_space::is_low(&usage, min_free_mb),
+            error: None,
+        },
+        Err(e) => BackupFolderCheck {
+            path: path.to_string(),
+            exists,
+            free_bytes: None,
+            total_bytes: None,
+            enough_space: false,
+            error: Some(e),
+        },
+    }
+}
+
+/// Step 4: whether Steam accepts the Web API key
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyCheck {
+    pub valid: bool,
+    pub persona_name: Option<String>, // of the chosen account, proves the key and the account go together
+    pub error: Option<String>,
+}
+
+fn looks_like_api_key(key: &str) -> bool {
+    key.len() == 32 && key.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub async fn validate_steam_api_key(api_key: &str, steam_id_64: Option<u64>) -> Result<ApiKeyCheck, String> {
+    let api_key = api_key.trim();
+    if !looks_like_api_key(api_key) {
+        return Ok(ApiKeyCheck {
+            valid: false,
+            persona_name: None,
+            error: Some("Steam Web API keys are 32 hexadecimal characters".to_string()),
+        });
+    }
+
+    let url = match steam_id_64 {
+        Some(id) => format!("https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/?key={}&steamids={}", api_key, id),
+        None => format!("https://api.steampowered.com/ISteamWebAPIUtil/GetSupportedAPIList/v1/?key={}", api_key),
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let response = client.get(&url).send().await
+        .map_err(|e| format!("Failed to reach the Steam Web API: {}", e))?;
+
+    // Steam answers an unknown or revoked key with 401/403
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Ok(ApiKeyCheck { valid: false, persona_name: None, error: Some("Steam rejected the key".to_string()) });
+    }
+    if !status.is_success() {
+        return Err(format!("Steam Web API returned {}", status));
+    }
+
+    let body: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to read the Steam Web API response: {}", e))?;
+    let persona_name = body["response"]["players"][0]["personaname"].as_str().map(str::to_string);
+    Ok(ApiKeyCheck { valid: true, persona_name, error: None })
+}
+
+/// Everything the wizard asked for
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupAnswers {
+    pub ludusavi_path: String,
+    pub backup_path: String,
+    #[serde(default)]
+    pub steam_user_id: Option<String>,
+    #[serde(default)]
+    pub steam_api_key: Option<String>,
+}
+
+/// Check the answers and fold them into `config`; every other setting keeps its value. The backup
+/// folder is created if needed.
+pub fn apply(mut config: AppConfig, answers: SetupAnswers) -> Result<AppConfig, String> {
+    let ludusavi_path = answers.ludusavi_path.trim();
+    if !Path::new(ludusavi_path).is_file() {
+        return Err(format!("Ludusavi not found at {}", ludusavi_path));
+    }
+    let backup_path = answers.backup_path.trim();
+    if backup_path.is_empty() {
+        return Err("Choose a backup folder".to_string());
+    }
+    fs::create_dir_all(backup_path).map_err(|e| format!("Failed to create {}: {}", backup_path, e))?;
+
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    config.ludusavi_path = ludusavi_path.to_string();
+    config.backup_path = backup_path.to_string();
+    config.steam_user_id = non_empty(answers.steam_user_id);
+    config.steam_api_key = non_empty(answers.steam_api_key);
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset { name: name.to_string(), browser_download_url: format!("https://example.com/{}", name) }
+    }
+
+    #[test]
+    fn test_pick_windows_asset() {
+        let assets = vec![
+            asset("ludusavi-v0.25.0-linux.tar.gz"),
+            asset("ludusavi-v0.25.0-win32.zip"),
+            asset("ludusavi-v0.25.0-win64.zip"),
+            asset("ludusavi-v0.25.0-mac.tar.gz"),
+        ];
+        assert_eq!(pick_windows_asset(&assets).unwrap().name, "ludusavi-v0.25.0-win64.zip");
+        assert!(pick_windows_asset(&assets[..2]).is_none());
+    }
+
+    #[test]
+    fn test_looks_like_api_key() {
+        assert!(looks_like_api_key("0123456789ABCDEF0123456789abcdef"));
+        assert!(!looks_like_api_key("0123456789ABCDEF"));
+        assert!(!looks_like_api_key("0123456789ABCDEF0123456789abcdeg"));
+    }
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+/// `steamsavemonitor://backup/<appid>` and `steamsavemonitor://achievements/<appid>`
+pub const PROTOCOL_SCHEME: &str = "steamsavemonitor";
+/// Passed by the Explorer context-menu entry on executables
+pub const BACKUP_EXE_ARG: &str = "--backup-exe";
+
+#[cfg(target_os = "windows")]
+const CONTEXT_MENU_KEY: &str = "Software\\Classes\\SystemFileAssociations\\.exe\\shell\\SteamSaveMonitor";
+
+/// What a link or context-menu launch asked the app to do
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum LaunchRequest {
+    /// Back up the game and show it
+    Backup { app_id: u32 },
+    /// Open the game's achievements
+    Achievements { app_id: u32 },
+    /// Back up the game whose configured executable was right-clicked
+    BackupExecutable { path: String },
+}
+
+/// Parse a `steamsavemonitor://` link. Browsers may add a trailing slash.
+pub fn parse_url(url: &str) -> Option<LaunchRequest> {
+    let (scheme, rest) = url.split_once(':')?;
+    if !scheme.eq_ignore_ascii_case(PROTOCOL_SCHEME) {
+        return None;
+    }
+
+    let mut parts = rest.trim_start_matches('/').trim_end_matches('/').split('/');
+    let action = parts.next()?.to_ascii_lowercase();
+    let app_id = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    match action.as_str() {
+        "backup" => Some(LaunchRequest::Backup { app_id }),
+        "achievements" => Some(LaunchRequest::Achievements { app_id }),
+        _ => None,
+    }
+}
+
+/// The launch request in a command line, from a link or the context menu
+pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Option<LaunchRequest> {
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == BACKUP_EXE_ARG {
+            return args.next().map(|path| LaunchRequest::BackupExecutable { path });
+        }
+        if let Some(request) = parse_url(&arg) {
+            return Some(request);
+        }
+    }
+    None
+}
+
+/// Point the URL protocol at this executable. Run on every start so it follows the app when it moves.
+#[cfg(target_os = "windows")]
+pub fn register_protocol() -> Result<(), String> {
+    let exe = current_exe()?;
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let (key, _) = hkcu.create_subkey(format!("Software\\Classes\\{}", PROTOCOL_SCHEME))
+        .map_err(|e| format!("Failed to register protocol: {}", e))?;
+
+    key.set_value("", &"URL:Steam Save Monitor").map_err(|e| e.to_string())?;
+    key.set_value("URL Protocol", &"").map_err(|e| e.to_string())?;
+    let (icon, _) = key.create_subkey("DefaultIcon").map_err(|e| e.to_string())?;
+    icon.set_value("", &format!("\"{}\",0", exe)).map_err(|e| e.to_string())?;
+    let (command, _) = key.create_subkey("shell\\open\\command").map_err(|e| e.to_string())?;
+    command.set_value("", &format!("\"{}\" \"%1\"", exe)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Add or remove "Back up saves" on the Explorer context menu of .exe files
+#[cfg(target_os = "windows")]
+pub fn set_context_menu(enabled: bool) -> Result<(), String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    if !enabled {
+        return match hkcu.delete_subkey_all(CONTEXT_MENU_KEY) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove context menu entry: {}", e)),
+            _ => Ok(()),
+        };
+    }
+
+    let exe = current_exe()?;
+    let (key, _) = hkcu.create_subkey(CONTEXT_MENU_KEY)
+        .map_err(|e| format!("Failed to add context menu entry: {}", e))?;
+    key.set_value("", &"Back up saves with Steam Save Monitor").map_err(|e| e.to_string())?;
+    key.set_value("Icon", &format!("\"{}\",0", exe)).map_err(|e| e.to_string())?;
+    let (command, _) = key.create_subkey("command").map_err(|e| e.to_string())?;
+    command.set_value("", &format!("\"{}\" {} \"%1\"", exe, BACKUP_EXE_ARG)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn current_exe() -> Result<String, String> {
+    std::env::current_exe()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to find the app executable: {}", e))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn register_protocol() -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn set_context_menu(_enabled: bool) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        assert_eq!(parse_url("steamsavemonitor://backup/570"), Some(LaunchRequest::Backup { app_id: 570 }));
+        assert_eq!(parse_url("SteamSaveMonitor://Achievements/570/"), Some(LaunchRequest::Achievements { app_id: 570 }));
+        assert_eq!(parse_url("steamsavemonitor://backup/dota"), None);
+        assert_eq!(parse_url("steamsavemonitor://restore/570"), None);
+        assert_eq!(parse_url("https://backup/570"), None);
+    }
+
+    #[test]
+    fn test_context_menu_args() {
+        let args = ["app.exe", BACKUP_EXE_ARG, "C:\\Games\\game.exe"].map(String::from);
+        assert_eq!(from_args(args), Some(LaunchRequest::BackupExecutable { path: "C:\\Games\\game.exe".to_string() }));
+        assert_eq!(from_args(["app.exe", "--autostart"].map(String::from)), None);
+    }
+}
@@ -0,0 +1,471 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use crate::ludusavi::LudusaviManager;
+use crate::app_log;
+
+/// Ludusavi's id for the base full backup stored directly in the game folder
+pub const BASE_SNAPSHOT_ID: &str = ".";
+
+/// Folder (inside the backup path) holding snapshots taken by the app itself, e.g. before restores
+const APP_SNAPSHOTS_DIR: &str = ".snapshots";
+/// Suffix of an app snapshot folder that Ludusavi is still writing; renamed away once it's complete
+const PARTIAL_SUFFIX: &str = ".partial";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub game_name: String,
+    pub path: String,
+    pub created_at: i64,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    pub path: String,
+    pub size: u64,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub old_hash: String,
+    pub new_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotComparison {
+    pub game_name: String,
+    pub from_id: String,
+    pub to_id: String,
+    pub added: Vec<SnapshotFile>,
+    pub removed: Vec<SnapshotFile>,
+    pub changed: Vec<FileChange>,
+    pub unchanged_count: usize,
+}
+
+/// Reads the snapshots Ludusavi keeps for a game inside the backup folder
+pub struct SnapshotManager {
+    backup_path: PathBuf,
+}
+
+impl SnapshotManager {
+    pub fn new(backup_path: String) -> Self {
+        Self {
+            backup_path: PathBuf::from(backup_path),
+        }
+    }
+
+    /// Folder Ludusavi uses for a game's backups
+    pub fn game_dir(&self, game_name: &str) -> PathBuf {
+        self.backup_path.join(LudusaviManager::game_folder_name(game_name))
+    }
+
+    /// Names of the games with a Ludusavi backup folder, read from their mapping.yaml
+    pub fn list_games(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.backup_path) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir() && !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|e| {
+                let mapping = fs::read_to_string(e.path().join("mapping.yaml")).ok()?;
+                mapping.lines()
+                    .find_map(|line| line.strip_prefix("name:"))
+                    .map(|name| name.trim().trim_matches(['"', '\'']).to_string())
+                    .or_else(|| Some(e.file_name().to_string_lossy().to_string()))
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// List all snapshots (base backup plus any timestamped versions) for a game, oldest first
+    pub fn list_snapshots(&self, game_name: &str) -> Result<Vec<SnapshotInfo>, String> {
+        let game_dir = self.game_dir(game_name);
+        let mut snapshots = Vec::new();
+
+        if game_dir.is_dir() {
+            // The base backup lives directly in the game folder
+            let base_files = Self::list_files(&game_dir, true)?;
+            if !base_files.is_empty() {
+                snapshots.push(Self::build_info(game_name, BASE_SNAPSHOT_ID, &game_dir, &base_files));
+            }
+
+            // Additional versions are stored as "backup-<timestamp>" folders
+            let entries = fs::read_dir(&game_dir)
+                .map_err(|e| format!("Failed to read backup folder: {}", e))?;
+
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                if path.is_dir() && name.starts_with("backup-") {
+                    let files = Self::list_files(&path, false)?;
+                    snapshots.push(Self::build_info(game_name, &name, &path, &files));
+                }
+            }
+        }
+
+        // Snapshots taken by the app (pre-restore etc.) are separate Ludusavi backup roots
+        for (id, path) in self.app_snapshot_dirs(game_name) {
+            let files = Self::list_files(&path, true)?;
+            snapshots.push(Self::build_info(game_name, &id, &path, &files));
+        }
+
+        if snapshots.is_empty() {
+            return Err(format!("No backups found for {}", game_name));
+        }
+
+        snapshots.sort_by_key(|s| s.created_at);
+        Ok(snapshots)
+    }
+
+    /// The versions Ludusavi keeps in the game folder, oldest first, without the app's own safety snapshots
+    pub fn list_versions(&self, game_name: &str) -> Result<Vec<SnapshotInfo>, String> {
+        let versions: Vec<SnapshotInfo> = self.list_snapshots(game_name)?
+            .into_iter()
+            .filter(|s| s.id == BASE_SNAPSHOT_ID || s.id.starts_with("backup-"))
+            .collect();
+        if versions.is_empty() {
+            return Err(format!("No backups found for {}", game_name));
+        }
+        Ok(versions)
+    }
+
+    /// Back up the current save state into a new app-managed snapshot before it gets overwritten.
+    /// None when the game has no saves yet, so there's nothing to keep.
+    pub async fn create_safety_snapshot(&self, ludusavi: &LudusaviManager, game_name: &str, trigger: &str) -> Result<Option<SnapshotInfo>, String> {
+        let id = format!("{}-{}", trigger, chrono::Utc::now().format("%Y%m%dT%H%M%SZ"));
+        let snapshot_root = self.backup_path.join(APP_SNAPSHOTS_DIR).join(&id);
+        let partial_root = self.backup_path.join(APP_SNAPSHOTS_DIR).join(format!("{}{}", id, PARTIAL_SUFFIX));
+
+        fs::create_dir_all(&partial_root)
+            .map_err(|e| format!("Failed to create snapshot folder: {}", e))?;
+
+        let result = match ludusavi.backup_to(game_name, &partial_root.to_string_lossy()).await {
+            Ok(result) => result,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&partial_root);
+                return Err(e);
+            }
+        };
+        if !result.success {
+            let _ = fs::remove_dir_all(&partial_root);
+            if result.not_found.unwrap_or(false) {
+                return Ok(None);
+            }
+            return Err(format!(
+                "Failed to snapshot {}: {}",
+                game_name,
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            ));
+        }
+
+        fs::rename(&partial_root, &snapshot_root)
+            .map_err(|e| format!("Failed to finish snapshot {}: {}", id, e))?;
+
+        let game_dir = snapshot_root.join(LudusaviManager::game_folder_name(game_name));
+        let files = Self::list_files(&game_dir, true)?;
+        app_log!("Created {} snapshot for {} ({} files)", trigger, game_name, files.len());
+
+        Ok(Some(Self::build_info(game_name, &id, &game_dir, &files)))
+    }
+
+    /// Delete all but the newest `keep` app-managed snapshots of a game taken for `trigger`
+    pub fn prune_safety_snapshots(&self, game_name: &str, trigger: &str, keep: usize) -> Result<usize, String> {
+        let prefix = format!("{}-", trigger);
+        let mut ids: Vec<String> = self.app_snapshot_dirs(game_name)
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| id.starts_with(&prefix))
+            .collect();
+        // The timestamp in the id sorts chronologically
+        ids.sort();
+
+        let excess = ids.len().saturating_sub(keep);
+        for id in &ids[..excess] {
+            self.delete_safety_snapshot(id)?;
+        }
+        Ok(excess)
+    }
+
+    /// Whether a snapshot id names one of the app's own snapshots rather than a Ludusavi version
+    pub fn is_safety_snapshot(id: &str) -> bool {
+        id != BASE_SNAPSHOT_ID && !id.starts_with("backup-")
+    }
+
+    /// Delete one app-managed snapshot; Ludusavi's own versions are left to Ludusavi
+    pub fn delete_safety_snapshot(&self, id: &str) -> Result<(), String> {
+        if !Self::is_safety_snapshot(id) || !Self::is_plain_id(id) {
+            return Err(format!("Invalid snapshot id: {}", id));
+        }
+        let snapshot_root = self.backup_path.join(APP_SNAPSHOTS_DIR).join(id);
+        fs::remove_dir_all(&snapshot_root)
+            .map_err(|e| format!("Failed to delete snapshot {}: {}", id, e))
+    }
+
+    /// App-managed snapshots that contain this game, as (id, game folder)
+    fn app_snapshot_dirs(&self, game_name: &str) -> Vec<(String, PathBuf)> {
+        let folder_name = LudusaviManager::game_folder_name(game_name);
+        let mut dirs = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(self.backup_path.join(APP_SNAPSHOTS_DIR)) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                if entry.file_name().to_string_lossy().ends_with(PARTIAL_SUFFIX) {
+                    continue;
+                }
+                let game_dir = entry.path().join(&folder_name);
+                if game_dir.is_dir() {
+                    dirs.push((entry.file_name().to_string_lossy().to_string(), game_dir));
+                }
+            }
+        }
+
+        dirs
+    }
+
+    /// Hash every file of a snapshot, keyed by its path relative to the snapshot root
+    pub fn snapshot_files(&self, game_name: &str, snapshot_id: &str) -> Result<BTreeMap<String, SnapshotFile>, String> {
+        let root = self.snapshot_dir(game_name, snapshot_id)?;
+        let files = Self::list_files(&root, !snapshot_id.starts_with("backup-"))?;
+
+        let mut hashed = BTreeMap::new();
+        for (relative, size) in files {
+            let hash = hash_file(&root.join(&relative))?;
+            hashed.insert(relative.clone(), SnapshotFile {
+                path: relative,
+                size,
+                hash,
+            });
+        }
+
+        Ok(hashed)
+    }
+
+    /// Compare two snapshots of a game and report added, removed and changed files
+    pub fn compare(&self, game_name: &str, from_id: &str, to_id: &str) -> Result<SnapshotComparison, String> {
+        let from_files = self.snapshot_files(game_name, from_id)?;
+        let to_files = self.snapshot_files(game_name, to_id)?;
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+        let mut unchanged_count = 0;
+
+        for (path, new_file) in &to_files {
+            match from_files.get(path) {
+                Some(old_file) if old_file.hash == new_file.hash => unchanged_count += 1,
+                Some(old_file) => changed.push(FileChange {
+                    path: path.clone(),
+                    old_size: old_file.size,
+                    new_size: new_file.size,
+                    old_hash: old_file.hash.clone(),
+                    new_hash: new_file.hash.clone(),
+                }),
+                None => added.push(new_file.clone()),
+            }
+        }
+
+        for (path, old_file) in &from_files {
+            if !to_files.contains_key(path) {
+                removed.push(old_file.clone());
+            }
+        }
+
+        Ok(SnapshotComparison {
+            game_name: game_name.to_string(),
+            from_id: from_id.to_string(),
+            to_id: to_id.to_string(),
+            added,
+            removed,
+            changed,
+            unchanged_count,
+        })
+    }
+
+    /// Folder holding a snapshot's files
+    pub fn snapshot_dir(&self, game_name: &str, snapshot_id: &str) -> Result<PathBuf, String> {
+        let game_dir = self.game_dir(game_name);
+
+        if snapshot_id == BASE_SNAPSHOT_ID {
+            return Ok(game_dir);
+        }
+
+        if !Self::is_plain_id(snapshot_id) {
+            return Err(format!("Invalid snapshot id: {}", snapshot_id));
+        }
+
+        let root = if snapshot_id.starts_with("backup-") {
+            game_dir.join(snapshot_id)
+        } else {
+            self.backup_path
+                .join(APP_SNAPSHOTS_DIR)
+                .join(snapshot_id)
+                .join(LudusaviManager::game_folder_name(game_name))
+        };
+        if !root.is_dir() {
+            return Err(format!("Snapshot {} not found for {}", snapshot_id, game_name));
+        }
+
+        Ok(root)
+    }
+
+    /// A version Ludusavi stored as a zip archive in the game folder, which list_versions doesn't see
+    pub fn archive_version(&self, game_name: &str, id: &str) -> Option<SnapshotInfo> {
+        if !Self::is_plain_id(id) {
+            return None;
+        }
+        let path = self.game_dir(game_name).join(id);
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).ok()?).ok()?;
+        let mut files = BTreeMap::new();
+        for i in 0..archive.len() {
+            let Ok(entry) = archive.by_index_raw(i) else { continue };
+            if !entry.is_dir() {
+                files.insert(entry.name().to_string(), entry.size());
+            }
+        }
+        Some(Self::build_info(game_name, id, &path, &files))
+    }
+
+    /// The versions Ludusavi stored as "backup-<timestamp>.zip" archives in the game folder, oldest first
+    pub fn list_archive_versions(&self, game_name: &str) -> Vec<SnapshotInfo> {
+        let mut versions = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.game_dir(game_name)) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with("backup-") && name.ends_with(".zip") {
+                    versions.extend(self.archive_version(game_name, &name));
+                }
+            }
+        }
+        versions.sort_by_key(|v| v.created_at);
+        versions
+    }
+
+    // Only names like "backup-20240131T120000Z.zip" or "pre-restore-20240131T120000Z", so ids can't
+    // escape the backup folder through separators, "..", or a drive prefix like "C:"
+    pub(crate) fn is_plain_id(id: &str) -> bool {
+        !id.is_empty()
+            && !id.starts_with('.')
+            && !id.contains("..")
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    }
+
+    fn build_info(game_name: &str, id: &str, path: &Path, files: &BTreeMap<String, u64>) -> SnapshotInfo {
+        let created_at = Self::parse_snapshot_time(id).unwrap_or_else(|| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        });
+
+        SnapshotInfo {
+            id: id.to_string(),
+            game_name: game_name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            created_at,
+            file_count: files.len(),
+            total_bytes: files.values().sum(),
+        }
+    }
+
+    /// Snapshot ids embed a timestamp like "backup-20240131T120000Z" or "pre-restore-20240131T120000Z"
+    fn parse_snapshot_time(id: &str) -> Option<i64> {
+        let re = regex::Regex::new(r"\d{8}T\d{6}Z").ok()?;
+        let stamp = re.find(id)?.as_str();
+        chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%SZ")
+            .ok()
+            .map(|dt| dt.and_utc().timestamp())
+    }
+
+    /// Walk a snapshot folder and return relative path -> size.
+    /// For the base snapshot the version folders and Ludusavi's metadata files are skipped.
+    pub fn list_files(root: &Path, is_base: bool) -> Result<BTreeMap<String, u64>, String> {
+        let mut files = BTreeMap::new();
+        Self::walk(root, root, is_base, &mut files)?;
+        Ok(files)
+    }
+
+    fn walk(root: &Path, dir: &Path, is_base: bool, files: &mut BTreeMap<String, u64>) -> Result<(), String> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if dir == root && is_base && (name.starts_with("backup-") || name == "mapping.yaml") {
+                continue;
+            }
+
+            if path.is_dir() {
+                Self::walk(root, &path, is_base, files)?;
+            } else if let Ok(metadata) = entry.metadata() {
+                let relative = path.strip_prefix(root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                files.insert(relative, metadata.len());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// SHA-256 of a file, streamed so large saves don't need to fit in memory
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_plain_id() {
+        assert!(SnapshotManager::is_plain_id("backup-20240131T120000Z"));
+        assert!(SnapshotManager::is_plain_id("backup-20240131T120000Z.zip"));
+        assert!(SnapshotManager::is_plain_id("pre-restore-20240131T120000Z"));
+
+        for id in ["", ".", "..", "../x", "backup-..", "a/b", "/abs", "a\\b", "C:", "C:foo", "backup-1:2", ".snapshots"] {
+            assert!(!SnapshotManager::is_plain_id(id), "{:?} should be rejected", id);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_dir_rejects_escaping_ids() {
+        let snapshots = SnapshotManager::new(std::env::temp_dir().join("sbm-snapshot-ids").to_string_lossy().to_string());
+        for id in ["..", "../other", "backup-x/../..", "x\\..\\..", "C:foo"] {
+            assert!(snapshots.snapshot_dir("Portal 2", id).unwrap_err().starts_with("Invalid snapshot id"));
+            assert!(snapshots.archive_version("Portal 2", id).is_none());
+            assert!(snapshots.delete_safety_snapshot(id).is_err());
+        }
+    }
+}
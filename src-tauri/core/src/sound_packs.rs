@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use crate::filenames;
+use crate::app_log;
+
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST_NAME: &str = "pack.json";
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_PACK_BYTES: u64 = 50 * 1024 * 1024;
+
+const TIERS: [&str; 5] = ["Common", "Uncommon", "Rare", "Ultra Rare", "Legendary"];
+const COLOR_FIELDS: [&str; 5] = ["borderColor", "backgroundColor", "glowColor", "titleColor", "descriptionColor"];
+const POSITIONS: [&str; 5] = ["top-left", "top-right", "bottom-left", "bottom-right", "center"];
+
+// Which file types each per-rarity path may point to
+const SOUND_EXTENSIONS: [&str; 5] = ["mp3", "wav", "ogg", "flac", "aac"];
+const FONT_EXTENSIONS: [&str; 4] = ["ttf", "otf", "woff", "woff2"];
+const ICON_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// pack.json inside a sound pack (.sbmpack, a zip with the referenced files under files/)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackManifest {
+    format_version: u32,
+    name: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    description: String,
+    created_at: i64,
+    settings: serde_json::Value, // the frontend's RaritySettings, file paths relative to the pack
+}
+
+/// An installed sound pack, with its settings pointing at the installed files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundPackInfo {
+    pub name: String,
+    pub author: String,
+    pub description: String,
+    pub created_at: i64,
+    pub directory: String,
+    pub settings: serde_json::Value,
+}
+
+fn packs_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("sound-packs")
+}
+
+fn extension_of(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn allowed_extensions(field: &str) -> &'static [&'static str] {
+    match field {
+        "soundPath" => &SOUND_EXTENSIONS,
+        "fontPath" => &FONT_EXTENSIONS,
+        _ => &ICON_EXTENSIONS,
+    }
+}
+
+// The icon is either an emoji or a path to an image
+fn icon_is_file(icon: &str) -> bool {
+    ICON_EXTENSIONS.contains(&extension_of(icon).as_str()) && (icon.contains('/') || icon.contains('\\'))
+}
+
+/// File-valued settings of a tier: (field, path)
+fn file_fields(tier: &serde_json::Value) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+    for field in ["soundPath", "fontPath"] {
+        if let Some(path) = tier.get(field).and_then(|v| v.as_str()).filter(|p| !p.is_empty()) {
+            fields.push((field, path.to_string()));
+        }
+    }
+    if let Some(icon) = tier.get("icon").and_then(|v| v.as_str()).filter(|i| icon_is_file(i)) {
+        fields.push(("icon", icon.to_string()));
+    }
+    fields
+}
+
+fn is_hex_color(value: &str) -> bool {
+    let hex = value.strip_prefix('#').unwrap_or("");
+    matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Check the shape of rarity settings so a bad pack can't break the overlay
+fn validate_settings(settings: &serde_json::Value) -> Result<(), String> {
+    let object = settings.as_object().ok_or("Pack settings are not an object")?;
+    for tier_name in TIERS {
+        let tier = object.get(tier_name)
+            .and_then(|t| t.as_object())
+            .ok_or_else(|| format!("Pack is missing the {} rarity", tier_name))?;
+
+        for field in COLOR_FIELDS {
+            let color = tier.get(field).and_then(|v| v.as_str()).unwrap_or("");
+            if !is_hex_color(color) {
+                return Err(format!("{} {} is not a color: \"{}\"", tier_name, field, color));
+            }
+        }
+
+        let position = tier.get("position").and_then(|v| v.as_str()).unwrap_or("");
+        if !POSITIONS.contains(&position) {
+            return Err(format!("{} has an unknown position \"{}\"", tier_name, position));
+        }
+        let scaling = tier.get("scaling").and_then(|v| v.as_u64()).unwrap_or(0);
+        if !(20..=300).contains(&scaling) {
+            return Err(format!("{} scaling {} is out of range", tier_name, scaling));
+        }
+        let opacity = tier.get("backgroundOpacity").and_then(|v| v.as_u64()).unwrap_or(101);
+        if opacity > 100 {
+            return Err(format!("{} background opacity must be 0-100", tier_name));
+        }
+    }
+    Ok(())
+}
+
+/// Bundle the current rarity settings and every sound, font and icon file they use into `destination`
+pub fn export_pack(settings: &serde_json::Value, name: &str, author: &str, description: &str, destination: &Path) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("A sound pack needs a name".to_string());
+    }
+    validate_settings(settings)?;
+
+    let file = fs::File::create(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut packed_settings = settings.clone();
+    for tier_name in TIERS {
+        let tier = &settings[tier_name];
+        for (field, path) in file_fields(tier) {
+            let extension = extension_of(&path);
+            if !allowed_extensions(field).contains(&extension.as_str()) {
+                return Err(format!("{} {} has an unsupported file type: {}", tier_name, field, path));
+            }
+            let bytes = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            if bytes.len() as u64 > MAX_FILE_BYTES {
+                return Err(format!("{} is larger than {} MB", path, MAX_FILE_BYTES / 1024 / 1024));
+            }
+
+            let entry = format!("files/{}-{}.{}", tier_name.replace(' ', "-").to_lowercase(), field, extension);
+            zip.start_file(entry.as_str(), options).map_err(|e| format!("Failed to write pack: {}", e))?;
+            zip.write_all(&bytes).map_err(|e| format!("Failed to write pack: {}", e))?;
+            packed_settings[tier_name][field] = serde_json::Value::String(entry);
+        }
+    }
+    // Whether rarities are switched on stays a local choice
+    if let Some(object) = packed_settings.as_object_mut() {
+        object.remove("enabled");
+    }
+
+    let manifest = PackManifest {
+        format_version: FORMAT_VERSION,
+        name: name.to_string(),
+        author: author.trim().to_string(),
+        description: description.trim().to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        settings: packed_settings,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize pack: {}", e))?;
+    zip.start_file(MANIFEST_NAME, options).map_err(|e| format!("Failed to write pack: {}", e))?;
+    zip.write_all(json.as_bytes()).map_err(|e| format!("Failed to write pack: {}", e))?;
+    zip.finish().map_err(|e| format!("Failed to finish pack: {}", e))?;
+
+    app_log!("[SoundPacks] Exported \"{}\" to {}", name, destination.display());
+    Ok(destination.to_path_buf())
+}
+
+/// Validate a pack and install it; installing a pack with the same name replaces it
+pub fn import_pack(pack_path: &Path) -> Result<SoundPackInfo, String> {
+    let file = fs::File::open(pack_path)
+        .map_err(|e| format!("Failed to open {}: {}", pack_path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Not a sound pack: {}", e))?;
+
+    let manifest: PackManifest = {
+        let mut entry = archive.by_name(MANIFEST_NAME)
+            .map_err(|_| format!("Not a sound pack: {} is missing", MANIFEST_NAME))?;
+        if entry.size() > MAX_FILE_BYTES {
+            return Err("Pack manifest is too large".to_string());
+        }
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).map_err(|e| format!("Failed to read pack manifest: {}", e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid pack manifest: {}", e))?
+    };
+
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(format!("This pack needs a newer version of the app (format {})", manifest.format_version));
+    }
+    if manifest.name.trim().is_empty() {
+        return Err("Pack has no name".to_string());
+    }
+    validate_settings(&manifest.settings)?;
+
+    // Extract to a temporary folder first so a bad pack never replaces a working one
+    let target = packs_dir().join(filenames::sanitize_component(&manifest.name));
+    let staging = target.with_extension("importing");
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+
+    let result = (|| -> Result<serde_json::Value, String> {
+        let mut settings = manifest.settings.clone();
+        let mut total_bytes = 0;
+        for tier_name in TIERS {
+            for (field, entry_name) in file_fields(&manifest.settings[tier_name]) {
+                if !allowed_extensions(field).contains(&extension_of(&entry_name).as_str()) {
+                    return Err(format!("{} {} has an unsupported file type", tier_name, field));
+                }
+                let mut entry = archive.by_name(&entry_name)
+                    .map_err(|_| format!("Pack is missing {}", entry_name))?;
+                // Refuse "../" and absolute names
+                let relative = entry.enclosed_name()
+                    .map(|p| p.to_path_buf())
+                    .ok_or_else(|| format!("Unsafe file name in pack: {}", entry_name))?;
+                if entry.size() > MAX_FILE_BYTES {
+                    return Err(format!("{} is larger than {} MB", entry_name, MAX_FILE_BYTES / 1024 / 1024));
+                }
+                total_bytes += entry.size();
+                if total_bytes > MAX_PACK_BYTES {
+                    return Err("Pack is too large".to_string());
+                }
+
+                let path = staging.join(&relative);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+                }
+                let mut bytes = Vec::new();
+                (&mut entry).take(MAX_FILE_BYTES + 1).read_to_end(&mut bytes)
+                    .map_err(|e| format!("Failed to read {}: {}", entry_name, e))?;
+                if bytes.len() as u64 > MAX_FILE_BYTES {
+                    return Err(format!("{} is larger than {} MB", entry_name, MAX_FILE_BYTES / 1024 / 1024));
+                }
+                fs::write(&path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+                let installed = target.join(&relative);
+                settings[tier_name][field] = serde_json::Value::String(installed.to_string_lossy().to_string());
+            }
+        }
+        Ok(settings)
+    })();
+
+    let settings = match result {
+        Ok(settings) => settings,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    let installed_manifest = PackManifest { settings, ..manifest };
+    let json = serde_json::to_string_pretty(&installed_manifest)
+        .map_err(|e| format!("Failed to serialize pack: {}", e))?;
+    fs::write(staging.join(MANIFEST_NAME), json)
+        .map_err(|e| format!("Failed to write pack manifest: {}", e))?;
+
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(&staging, &target).map_err(|e| format!("Failed to install pack: {}", e))?;
+
+    app_log!("[SoundPacks] Installed \"{}\" to {}", installed_manifest.name, target.display());
+    Ok(to_info(installed_manifest, &target))
+}
+
+fn to_info(manifest: PackManifest, directory: &Path) -> SoundPackInfo {
+    SoundPackInfo {
+        name: manifest.name,
+        author: manifest.author,
+        description: manifest.description,
+        created_at: manifest.created_at,
+        directory: directory.to_string_lossy().to_string(),
+        settings: manifest.settings,
+    }
+}
+
+/// Installed packs, ready to switch to
+pub fn list_packs() -> Vec<SoundPackInfo> {
+    let Ok(entries) = fs::read_dir(packs_dir()) else { return Vec::new() };
+    let mut packs: Vec<SoundPackInfo> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let contents = fs::read_to_string(e.path().join(MANIFEST_NAME)).ok()?;
+            let manifest: PackManifest = serde_json::from_str(&contents).ok()?;
+            Some(to_info(manifest, &e.path()))
+        })
+        .collect();
+    packs.sort_by_key(|p| p.name.to_lowercase());
+    packs
+}
+
+pub fn delete_pack(name: &str) -> Result<(), String> {
+    let dir = packs_dir().join(filenames::sanitize_component(name));
+    if !dir.join(MANIFEST_NAME).exists() {
+        return Err(format!("Sound pack \"{}\" is not installed", name));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete sound pack: {}", e))
+}
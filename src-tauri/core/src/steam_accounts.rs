@@ -0,0 +1,128 @@
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// SteamID64 of account id 0; userdata folders are named after `steam_id_64 - STEAM_ID64_BASE`
+pub const STEAM_ID64_BASE: u64 = 76561197960265728;
+
+/// An account listed in loginusers.vdf
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoginUser {
+    pub account_id: String,
+    pub most_recent: bool,
+    pub timestamp: u64,
+}
+
+/// Parse the accounts in loginusers.vdf
+pub fn parse_login_users(content: &str) -> Vec<LoginUser> {
+    let block_re = Regex::new(r#""(\d{17})"\s*\{([^}]*)\}"#).expect("valid regex");
+    let most_recent_re = Regex::new(r#"(?i)"MostRecent"\s*"1""#).expect("valid regex");
+    let timestamp_re = Regex::new(r#"(?i)"Timestamp"\s*"(\d+)""#).expect("valid regex");
+
+    block_re.captures_iter(content)
+        .filter_map(|captures| {
+            let steam_id_64: u64 = captures[1].parse().ok()?;
+            let body = &captures[2];
+            Some(LoginUser {
+                account_id: steam_id_64.checked_sub(STEAM_ID64_BASE)?.to_string(),
+                most_recent: most_recent_re.is_match(body),
+                timestamp: timestamp_re.captures(body).and_then(|c| c[1].parse().ok()).unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Account folders under userdata ("0" and "ac" aren't accounts)
+fn account_dirs(steam_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(steam_path.join("userdata")) else { return Vec::new() };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.path().is_dir()
+                && entry.file_name() != "0"
+                && entry.file_name() != "ac"
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Accounts in loginusers.vdf that have a userdata folder, the most recently used first
+pub fn login_users(steam_path: &Path) -> Vec<LoginUser> {
+    let dirs = account_dirs(steam_path);
+    let has_dir = |id: &str| dirs.iter().any(|d| d.file_name().is_some_and(|n| n == id));
+
+    let Ok(content) = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")) else { return Vec::new() };
+    let mut users: Vec<LoginUser> = parse_login_users(&content).into_iter()
+        .filter(|u| has_dir(&u.account_id))
+        .collect();
+    users.sort_by_key(|u| std::cmp::Reverse((u.most_recent, u.timestamp)));
+    users
+}
+
+/// The account that last used Steam on this PC: the MostRecent login in loginusers.vdf, otherwise
+/// the userdata folder whose localconfig.vdf was written last
+pub fn detect_active_user(steam_path: &Path) -> Option<String> {
+    if let Some(user) = login_users(steam_path).into_iter().next() {
+        return Some(user.account_id);
+    }
+
+    account_dirs(steam_path).iter()
+        .max_by_key(|dir| {
+            fs::metadata(dir.join("config").join("localconfig.vdf"))
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        })
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+}
+
+/// The Steam account to use: the configured one if set, otherwise the detected active account
+pub fn resolve_user_id(steam_path: &Path, configured: Option<&str>) -> Option<String> {
+    match configured.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(id) => Some(id.to_string()),
+        None => detect_active_user(steam_path),
+    }
+}
+
+/// The userdata folder of the account to use
+pub fn userdata_dir(steam_path: &Path, configured: Option<&str>) -> Result<PathBuf, String> {
+    if !steam_path.join("userdata").exists() {
+        return Err("Steam userdata folder not found".to_string());
+    }
+
+    let user_id = resolve_user_id(steam_path, configured).ok_or("No Steam user found")?;
+    let user_path = steam_path.join("userdata").join(&user_id);
+    if !user_path.is_dir() {
+        return Err(format!("Steam user ID '{}' not found", user_id));
+    }
+    Ok(user_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_login_users() {
+        let content = r#""users"
+{
+	"76561198000000001"
+	{
+		"AccountName"		"first"
+		"MostRecent"		"0"
+		"Timestamp"		"1700000000"
+	}
+	"76561198000000002"
+	{
+		"AccountName"		"second"
+		"MostRecent"		"1"
+		"Timestamp"		"1600000000"
+	}
+}"#;
+        let users = parse_login_users(content);
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0], LoginUser { account_id: "39734273".to_string(), most_recent: false, timestamp: 1700000000 });
+        assert!(users[1].most_recent);
+        assert_eq!(users[1].account_id, "39734274");
+    }
+}
@@ -1,8 +1,36 @@
 use steamworks::Client;
 use crate::achievements::{Achievement};
+use crate::achievement_dlc::DlcInfo;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use scraper::{Html, Selector};
+use std::collections::HashMap;
+
+// The store API takes one app per request for details, so games with hundreds of cosmetic DLCs
+// only get names for the first ones
+const MAX_DLC_NAME_LOOKUPS: usize = 30;
+
+/// Check a SteamID64 typed by the user (17 digits, individual accounts start with 7656119)
+pub fn parse_steam_id_64(steam_id: &str) -> Result<u64, String> {
+    let steam_id = steam_id.trim();
+    if steam_id.len() != 17 || !steam_id.starts_with("7656119") {
+        return Err(format!("'{}' isn't a SteamID64 (17 digits starting with 7656119)", steam_id));
+    }
+    steam_id.parse::<u64>().map_err(|_| format!("'{}' isn't a SteamID64", steam_id))
+}
+
+#[derive(Debug, Deserialize)]
+struct StoreAppDetails {
+    success: bool,
+    data: Option<StoreAppData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StoreAppData {
+    name: String,
+    #[serde(default)]
+    dlc: Vec<u32>,
+}
 
 #[derive(Debug, Deserialize)]
 struct SteamApiResponse {
@@ -61,6 +89,7 @@ pub struct SteamAchievementClient {
     http_client: reqwest::Client,
     api_key: Option<String>,
     steam_id: Option<u64>,
+    steam_id_overrides: HashMap<u32, u64>, // app_id -> account that owns the game when it isn't the main one
 }
 
 impl SteamAchievementClient {
@@ -88,9 +117,18 @@ impl SteamAchievementClient {
             http_client,
             api_key,
             steam_id: steam_id_u64,
+            steam_id_overrides: HashMap::new(),
         })
     }
 
+    /// Scan these games with another account's SteamID64, invalid ids are ignored
+    pub fn with_steam_id_overrides(mut self, overrides: HashMap<u32, String>) -> Self {
+        self.steam_id_overrides = overrides.into_iter()
+            .filter_map(|(app_id, id)| parse_steam_id_64(&id).ok().map(|id| (app_id, id)))
+            .collect();
+        self
+    }
+
     /// Get achievement schema from Steam Web API
     pub async fn get_achievement_schema(&self, app_id: u32) -> Result<Vec<SteamAchievementSchema>, String> {
         // Check if API key is configured
@@ -323,6 +361,47 @@ impl SteamAchievementClient {
             .ok_or_else(|| "No achievement data found for this player/game".to_string())
     }
 
+    /// DLCs of a game from the Steam store, oldest first
+    pub async fn get_dlc_list(&self, app_id: u32) -> Result<Vec<DlcInfo>, String> {
+        let details = self.get_store_app_data(app_id).await?
+            .ok_or_else(|| format!("App {} not found on the Steam store", app_id))?;
+
+        let mut dlc_ids = details.dlc;
+        dlc_ids.sort_unstable();
+
+        let mut dlcs = Vec::with_capacity(dlc_ids.len());
+        for (i, dlc_app_id) in dlc_ids.into_iter().enumerate() {
+            let name = if i < MAX_DLC_NAME_LOOKUPS {
+                self.get_store_app_data(dlc_app_id).await.ok().flatten().map(|data| data.name)
+            } else {
+                None
+            };
+            dlcs.push(DlcInfo {
+                dlc_app_id,
+                name: name.unwrap_or_else(|| format!("DLC {}", dlc_app_id)),
+            });
+        }
+        Ok(dlcs)
+    }
+
+    async fn get_store_app_data(&self, app_id: u32) -> Result<Option<StoreAppData>, String> {
+        let url = format!(
+            "https://store.steampowered.com/api/appdetails?appids={}&filters=basic&l=english",
+            app_id
+        );
+
+        let mut response: std::collections::HashMap<String, StoreAppDetails> = self.http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch app details: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse app details: {}", e))?;
+
+        Ok(response.remove(&app_id.to_string()).filter(|d| d.success).and_then(|d| d.data))
+    }
+
     /// Search for Steam games by name
     pub async fn search_games(&self, query: &str) -> Result<Vec<SteamGameSearchResult>, String> {
         if query.trim().is_empty() {
@@ -394,9 +473,10 @@ impl SteamAchievementClient {
         let global_percentages = self.get_global_achievement_percentages(app_id).await.ok();
 
         // Try to get player's Steam ID
-        // Priority 1: Use Steam ID from config
-        // Priority 2: Use Steamworks SDK to get Steam ID
-        let steam_id = self.steam_id.or_else(|| {
+        // Priority 1: Use the game's own Steam ID override
+        // Priority 2: Use Steam ID from config
+        // Priority 3: Use Steamworks SDK to get Steam ID
+        let steam_id = self.steam_id_overrides.get(&app_id).copied().or(self.steam_id).or_else(|| {
             if let Some(ref client) = self.steam_client {
                 Some(client.user().steam_id().raw())
             } else {
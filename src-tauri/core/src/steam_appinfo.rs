@@ -0,0 +1,213 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::app_log;
+
+// appinfo.vdf format versions: 28 added a second hash per entry, 29 moved key names to a string table
+const MAGIC_V27: u32 = 0x07564427;
+const MAGIC_V28: u32 = 0x07564428;
+const MAGIC_V29: u32 = 0x07564429;
+
+/// App types that are played; everything else (tools, soundtracks, applications) isn't monitored
+const GAME_TYPES: [&str; 3] = ["game", "demo", "beta"];
+
+/// Name fragments of apps that are never games, for apps appinfo.vdf doesn't know about
+const NON_GAME_NAMES: [&str; 8] = [
+    "soundtrack", "dedicated server", "sdk", "redistributable", "steamworks common",
+    "steam linux runtime", "proton", "steamvr",
+];
+
+/// What Steam's appinfo cache knows about an app
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AppInfo {
+    pub name: Option<String>,
+    /// Lowercase: "game", "tool", "music", ...
+    pub app_type: Option<String>,
+    /// Windows executables from the launch options, relative to the install folder
+    pub executables: Vec<String>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.bytes(8).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
+
+    fn cstring(&mut self) -> Option<String> {
+        let len = self.data.get(self.pos..)?.iter().position(|&b| b == 0)?;
+        let text = String::from_utf8_lossy(self.bytes(len)?).to_string();
+        self.pos += 1;
+        Some(text)
+    }
+}
+
+// Name, type and launch executables from an entry's binary KeyValues
+fn parse_entry(reader: &mut Reader, strings: Option<&[String]>) -> Option<AppInfo> {
+    let mut info = AppInfo::default();
+    let mut launches: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new(); // id -> (executable, oslist)
+    let mut path: Vec<String> = Vec::new();
+    loop {
+        let kind = reader.u8()?;
+        if kind == 0x08 {
+            path.pop()?;
+            if path.is_empty() {
+                break; // end of the outermost object ends the entry
+            }
+            continue;
+        }
+
+        let key = match strings {
+            Some(strings) => strings.get(reader.u32()? as usize)?.clone(),
+            None => reader.cstring()?,
+        };
+        match kind {
+            0x00 => path.push(key),
+            0x01 => {
+                let value = reader.cstring()?;
+                // Keys below the entry's root object, e.g. ["config", "launch", "0"]
+                let section: Vec<String> = path.iter().skip(1).map(|k| k.to_lowercase()).collect();
+                let section: Vec<&str> = section.iter().map(String::as_str).collect();
+                match (section.as_slice(), key.to_lowercase().as_str()) {
+                    (["common"], "name") => info.name = Some(value),
+                    (["common"], "type") => info.app_type = Some(value.to_lowercase()),
+                    (["config", "launch", id], "executable") => launches.entry(id.to_string()).or_default().0 = Some(value),
+                    (["config", "launch", id, "config"], "oslist") => launches.entry(id.to_string()).or_default().1 = Some(value),
+                    _ => {}
+                }
+            }
+            0x02 | 0x03 | 0x04 | 0x06 => {
+                reader.bytes(4)?;
+            }
+            0x07 | 0x0A => {
+                reader.bytes(8)?;
+            }
+            _ => return None, // unknown value type, can't tell where it ends
+        }
+    }
+
+    for (executable, oslist) in launches.into_values() {
+        let Some(executable) = executable else { continue };
+        let windows = oslist.map_or(true, |os| os.to_lowercase().contains("windows"));
+        let executable = executable.replace('/', "\\");
+        if windows && executable.to_lowercase().ends_with(".exe") && !info.executables.contains(&executable) {
+            info.executables.push(executable);
+        }
+    }
+    Some(info)
+}
+
+/// Names, apps and executables from Steam's appinfo cache, for the given app ids.
+/// Apps missing from the cache, or a cache in a format this doesn't know, are left out.
+pub fn read_app_info(steam_path: &Path, app_ids: &HashSet<u32>) -> HashMap<u32, AppInfo> {
+    let mut apps = HashMap::new();
+    let Ok(data) = fs::read(steam_path.join("appcache").join("appinfo.vdf")) else { return apps };
+    let mut reader = Reader { data: &data, pos: 0 };
+
+    let Some(magic) = reader.u32() else { return apps };
+    if ![MAGIC_V27, MAGIC_V28, MAGIC_V29].contains(&magic) {
+        app_log!("[AppInfo] Unknown appinfo.vdf version {:#x}", magic);
+        return apps;
+    }
+    let _universe = reader.u32();
+
+    let strings = if magic == MAGIC_V29 {
+        match reader.u64().and_then(|offset| read_string_table(&data, offset as usize)) {
+            Some(strings) => Some(strings),
+            None => return apps,
+        }
+    } else {
+        None
+    };
+    let header_len = if magic == MAGIC_V27 { 40 } else { 60 };
+
+    while let Some(app_id) = reader.u32() {
+        if app_id == 0 {
+            break;
+        }
+        let Some(size) = reader.u32() else { break };
+        let next = reader.pos + size as usize;
+
+        if app_ids.contains(&app_id) {
+            let mut entry = Reader { data: &data[..next.min(data.len())], pos: reader.pos + header_len };
+            if let Some(info) = parse_entry(&mut entry, strings.as_deref()) {
+                apps.insert(app_id, info);
+            }
+        }
+        reader.pos = next;
+    }
+    apps
+}
+
+fn read_string_table(data: &[u8], offset: usize) -> Option<Vec<String>> {
+    let mut reader = Reader { data, pos: offset };
+    let count = reader.u32()?;
+    (0..count).map(|_| reader.cstring()).collect()
+}
+
+/// Whether an installed app should be watched for game sessions. `app_type` comes from appinfo.vdf
+/// when known; otherwise the name decides.
+pub fn is_game(app_type: Option<&str>, name: &str) -> bool {
+    match app_type {
+        Some(app_type) => GAME_TYPES.contains(&app_type),
+        None => {
+            let name = name.to_lowercase();
+            !NON_GAME_NAMES.iter().any(|fragment| name.contains(fragment))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Binary KeyValues with inline key names, as in appinfo.vdf before v29
+    fn object(key: &str, body: Vec<u8>) -> Vec<u8> {
+        let mut bytes = vec![0x00];
+        bytes.extend(key.bytes().chain([0]));
+        bytes.extend(body);
+        bytes.push(0x08);
+        bytes
+    }
+
+    fn string(key: &str, value: &str) -> Vec<u8> {
+        let mut bytes = vec![0x01];
+        bytes.extend(key.bytes().chain([0]));
+        bytes.extend(value.bytes().chain([0]));
+        bytes
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let common = [string("name", "Portal"), string("type", "Game")].concat();
+        let windows = [string("executable", "bin/portal.exe"), object("config", string("oslist", "windows"))].concat();
+        let linux = [string("executable", "portal.sh"), object("config", string("oslist", "linux"))].concat();
+        let launch = object("launch", [object("0", windows), object("1", linux)].concat());
+        let data = object("appinfo", [object("common", common), object("config", launch)].concat());
+
+        let info = parse_entry(&mut Reader { data: &data, pos: 0 }, None).unwrap();
+        assert_eq!(info, AppInfo {
+            name: Some("Portal".to_string()),
+            app_type: Some("game".to_string()),
+            executables: vec![r"bin\portal.exe".to_string()],
+        });
+    }
+}
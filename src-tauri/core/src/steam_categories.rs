@@ -0,0 +1,165 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::steam_accounts;
+use crate::steam_monitor;
+
+/// A category (collection) from the Steam library
+#[derive(Debug, Clone, Serialize)]
+pub struct SteamCategory {
+    pub name: String,
+    pub app_ids: Vec<u32>,
+}
+
+/// What a category exclusion sync changed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategorySyncResult {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Where Steam keeps an account's library categories
+pub fn sharedconfig_path(userdata_dir: &Path) -> PathBuf {
+    userdata_dir.join("7").join("remote").join("sharedconfig.vdf")
+}
+
+enum Token {
+    Open,
+    Close,
+    Text(String),
+}
+
+// Quoted strings and braces of a KeyValues file
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => text.extend(chars.next()),
+                        '"' => break,
+                        _ => text.push(c),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Categories in a sharedconfig.vdf, from the `apps/<appid>/tags` lists
+pub fn parse_categories(content: &str) -> Vec<SteamCategory> {
+    let mut categories: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut tokens = tokenize(content).into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Open => path.push(String::new()), // unnamed block, keep the stack balanced
+            Token::Close => {
+                path.pop();
+            }
+            Token::Text(key) => match tokens.peek() {
+                Some(Token::Open) => {
+                    tokens.next();
+                    path.push(key);
+                }
+                Some(Token::Text(_)) => {
+                    let Some(Token::Text(value)) = tokens.next() else { continue };
+                    if let [.., apps, app_id, tags] = path.as_slice() {
+                        if apps.eq_ignore_ascii_case("apps") && tags.eq_ignore_ascii_case("tags") {
+                            if let Ok(app_id) = app_id.parse() {
+                                let app_ids = categories.entry(value).or_default();
+                                if !app_ids.contains(&app_id) {
+                                    app_ids.push(app_id);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    categories.into_iter()
+        .map(|(name, app_ids)| SteamCategory { name, app_ids })
+        .collect()
+}
+
+/// Categories of the configured or most recently active Steam account
+pub fn read_categories(steam_path: &Path, steam_user_id: Option<&str>) -> Result<Vec<SteamCategory>, String> {
+    let path = sharedconfig_path(&steam_accounts::userdata_dir(steam_path, steam_user_id)?);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(parse_categories(&content))
+}
+
+/// Names of the games installed in any Steam library
+pub fn installed_app_names(steam_path: &Path) -> HashMap<u32, String> {
+    steam_monitor::installed_apps(steam_path)
+        .into_iter()
+        .map(|app| (app.app_id, app.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_categories() {
+        let content = r#""UserRoamingConfigStore"
+{
+	"Software"
+	{
+		"Valve"
+		{
+			"Steam"
+			{
+				"apps"
+				{
+					"431960"
+					{
+						"tags"
+						{
+							"0"		"Tools"
+						}
+					}
+					"620980"
+					{
+						"tags"
+						{
+							"0"		"VR"
+							"1"		"Tools"
+						}
+						"Hidden"		"1"
+					}
+				}
+			}
+		}
+	}
+}"#;
+        let categories = parse_categories(content);
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories[0].name, "Tools");
+        assert_eq!(categories[0].app_ids, vec![431960, 620980]);
+        assert_eq!(categories[1].name, "VR");
+        assert_eq!(categories[1].app_ids, vec![620980]);
+    }
+}
@@ -0,0 +1,521 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use serde::Serialize;
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+use std::collections::{HashMap, HashSet};
+use crate::achievements::AchievementDatabase;
+use crate::steam_accounts;
+use crate::steam_appinfo;
+use crate::game_detection::{DetectionStrategy, GameEvent, GameInfo};
+use crate::app_log;
+
+/// The Steam install folder plus every extra library listed in libraryfolders.vdf that can be reached
+pub fn library_folders(steam_path: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![steam_path.to_path_buf()];
+    for (path, _) in library_entries(steam_path) {
+        if path.exists() && !folders.contains(&path) {
+            folders.push(path);
+        }
+    }
+    folders
+}
+
+/// Apps libraryfolders.vdf lists in libraries that can't be reached right now (a drive that's
+/// unplugged or offline). Their appmanifests can't be read, which doesn't mean they're uninstalled.
+pub fn unreachable_library_apps(steam_path: &Path) -> Vec<u32> {
+    library_entries(steam_path).into_iter()
+        .filter(|(path, _)| !path.exists())
+        .flat_map(|(_, apps)| apps)
+        .collect()
+}
+
+// Each library in libraryfolders.vdf with the app ids Steam has installed there
+fn library_entries(steam_path: &Path) -> Vec<(PathBuf, Vec<u32>)> {
+    let libraryfolders_path = steam_path.join("steamapps").join("libraryfolders.vdf");
+    let Ok(contents) = fs::read_to_string(&libraryfolders_path) else { return Vec::new() };
+    let (Ok(path_re), Ok(apps_re), Ok(app_re)) = (
+        Regex::new(r#""path"\s+"([^"]+)""#),
+        Regex::new(r#""apps"\s*\{([^}]*)\}"#),
+        Regex::new(r#""(\d+)"\s+"\d+""#),
+    ) else {
+        return Vec::new();
+    };
+
+    let paths: Vec<_> = path_re.captures_iter(&contents).filter_map(|cap| cap.get(1)).collect();
+    paths.iter().enumerate()
+        .map(|(i, path_match)| {
+            // A library's apps block comes after its path and before the next library's
+            let end = paths.get(i + 1).map_or(contents.len(), |next| next.start());
+            let apps = apps_re.captures(&contents[path_match.end()..end])
+                .and_then(|cap| cap.get(1))
+                .map(|block| app_re.captures_iter(block.as_str())
+                    .filter_map(|cap| cap[1].parse().ok())
+                    .collect())
+                .unwrap_or_default();
+            (PathBuf::from(path_match.as_str().replace("\\\\", "\\")), apps)
+        })
+        .collect()
+}
+
+/// An executable found in an installed Steam game's folder
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedExecutable {
+    pub exe_name: String,
+    pub app_id: u32,
+    pub game_name: String,
+}
+
+/// An app installed in one of the Steam libraries, described by appinfo.vdf where Steam has it cached
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledApp {
+    pub app_id: u32,
+    pub name: String,
+    pub app_type: Option<String>,
+    pub install_dir: PathBuf,
+    pub executables: Vec<String>, // from the launch options, relative to install_dir
+    pub is_game: bool,
+}
+
+/// Every app installed in the Steam libraries, by name. The appmanifests say what's installed and
+/// where; names, types and executables come from Steam's appinfo cache when it has them.
+pub fn installed_apps(steam_path: &Path) -> Vec<InstalledApp> {
+    let mut installed = Vec::new();
+    for library_path in library_folders(steam_path) {
+        let steamapps_path = library_path.join("steamapps");
+        let Ok(entries) = fs::read_dir(&steamapps_path) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if filename.starts_with("appmanifest_") && filename.ends_with(".acf") {
+                installed.extend(SteamMonitor::parse_appmanifest(&entry.path(), &steamapps_path));
+            }
+        }
+    }
+
+    let app_ids: HashSet<u32> = installed.iter().map(|(app_id, _, _)| *app_id).collect();
+    let mut app_info = steam_appinfo::read_app_info(steam_path, &app_ids);
+    let mut apps: Vec<InstalledApp> = installed.into_iter()
+        .map(|(app_id, manifest_name, install_dir)| {
+            let info = app_info.remove(&app_id).unwrap_or_default();
+            let name = info.name.unwrap_or(manifest_name);
+            InstalledApp {
+                app_id,
+                is_game: steam_appinfo::is_game(info.app_type.as_deref(), &name),
+                name,
+                app_type: info.app_type,
+                install_dir,
+                executables: info.executables,
+            }
+        })
+        .collect();
+    apps.sort_by_key(|app| app.name.to_lowercase());
+    apps
+}
+
+// Lowercase folder path ending in a separator, so "Game" doesn't also match "Game 2"
+fn folder_key(path: &Path) -> String {
+    let mut key = path.to_string_lossy().to_lowercase().replace('/', "\\");
+    if !key.ends_with('\\') {
+        key.push('\\');
+    }
+    key
+}
+
+// The installed game whose folder holds this executable. Steam runs redistributable
+// installers from the game folder too, those aren't the game.
+fn app_for_exe(install_dirs: &[(String, u32)], exe: &Path) -> Option<u32> {
+    let exe = exe.to_string_lossy().to_lowercase().replace('/', "\\");
+    if exe.contains("\\_commonredist\\") {
+        return None;
+    }
+    install_dirs.iter()
+        .find(|(dir, _)| exe.starts_with(dir.as_str()))
+        .map(|(_, app_id)| *app_id)
+}
+
+pub struct SteamMonitor {
+    steam_path: PathBuf,
+    current_game: Option<GameInfo>,
+    last_running_appid: Option<u32>,
+    system: Option<System>, // created on the first check, not when the monitor is built
+    game_executables: HashMap<String, (u32, String)>, // exe_name -> (app_id, game_name)
+    game_names: HashMap<u32, String>,
+    install_dirs: Vec<(String, u32)>, // folder_key(install folder) -> app_id, deepest folders first
+    db_path: Option<PathBuf>,
+}
+
+impl SteamMonitor {
+    pub fn new() -> Result<Self, String> {
+        let steam_path = Self::find_steam_path()?;
+        println!("✓ Steam path detected: {}", steam_path.display());
+
+        let mut monitor = Self {
+            steam_path: steam_path.clone(),
+            current_game: None,
+            last_running_appid: None,
+            system: None,
+            game_executables: HashMap::new(),
+            game_names: HashMap::new(),
+            install_dirs: Vec::new(),
+            db_path: None,
+        };
+
+        // Build game executable map
+        monitor.load_steam_games();
+
+        Ok(monitor)
+    }
+
+    pub fn set_db_path(&mut self, db_path: PathBuf) {
+        self.db_path = Some(db_path);
+    }
+
+    /// Every executable that counts as its Steam game running
+    pub fn executables(&self) -> Vec<DetectedExecutable> {
+        let mut executables: Vec<DetectedExecutable> = self.game_executables.iter()
+            .map(|(exe_name, (app_id, game_name))| DetectedExecutable {
+                exe_name: exe_name.clone(),
+                app_id: *app_id,
+                game_name: game_name.clone(),
+            })
+            .collect();
+        executables.sort_by(|a, b| a.game_name.cmp(&b.game_name).then_with(|| a.exe_name.cmp(&b.exe_name)));
+        executables
+    }
+
+    fn load_steam_games(&mut self) {
+        println!("Scanning Steam libraries for installed games...");
+
+        let mut skipped = 0;
+        let mut crawled = 0;
+        for app in installed_apps(&self.steam_path) {
+            // Soundtracks, dedicated servers and SDKs have executables too, but aren't played
+            if !app.is_game {
+                skipped += 1;
+                continue;
+            }
+            self.game_names.insert(app.app_id, app.name.clone());
+            self.install_dirs.push((folder_key(&app.install_dir), app.app_id));
+
+            // The launch options name the real executables; the install folder is only searched without them
+            if app.executables.is_empty() {
+                crawled += 1;
+                self.scan_game_executables(&app.install_dir, app.app_id, &app.name);
+            }
+            for executable in &app.executables {
+                let exe_name = executable.rsplit(['\\', '/']).next().unwrap_or(executable);
+                self.game_executables.insert(exe_name.to_string(), (app.app_id, app.name.clone()));
+            }
+        }
+        self.install_dirs.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.len()));
+        if skipped > 0 {
+            app_log!("  Skipped {} installed Steam apps that aren't games", skipped);
+        }
+        if crawled > 0 {
+            app_log!("  Searched the install folders of {} games Steam has no launch options for", crawled);
+        }
+
+        println!("✓ Loaded {} Steam games for automatic detection", self.game_executables.len());
+
+        // Debug: Show some games
+        let mut games: Vec<_> = self.game_executables.iter().take(5).collect();
+        games.sort_by_key(|(exe, _)| exe.to_lowercase());
+        for (exe, (app_id, name)) in games {
+            println!("  - {} -> {} (AppID: {})", exe, name, app_id);
+        }
+        if self.game_executables.len() > 5 {
+            println!("  ... and {} more", self.game_executables.len() - 5);
+        }
+    }
+
+    // App id, name and install folder of an installed app
+    fn parse_appmanifest(manifest_path: &PathBuf, steamapps_path: &PathBuf) -> Option<(u32, String, PathBuf)> {
+        if let Ok(contents) = fs::read_to_string(manifest_path) {
+            // Extract app ID, name, and install directory
+            let app_id_re = match Regex::new(r#""appid"\s+"(\d+)""#) {
+                Ok(re) => re,
+                Err(_) => return None,
+            };
+            let name_re = match Regex::new(r#""name"\s+"([^"]+)""#) {
+                Ok(re) => re,
+                Err(_) => return None,
+            };
+            let installdir_re = match Regex::new(r#""installdir"\s+"([^"]+)""#) {
+                Ok(re) => re,
+                Err(_) => return None,
+            };
+
+            let app_id = match app_id_re.captures(&contents)
+                .and_then(|cap| cap.get(1))
+                .and_then(|m| m.as_str().parse::<u32>().ok()) {
+                Some(id) => id,
+                None => return None,
+            };
+
+            let name = match name_re.captures(&contents)
+                .and_then(|cap| cap.get(1))
+                .map(|m| m.as_str().to_string()) {
+                Some(n) => n,
+                None => return None,
+            };
+
+            let installdir = match installdir_re.captures(&contents)
+                .and_then(|cap| cap.get(1))
+                .map(|m| m.as_str().to_string()) {
+                Some(dir) => dir,
+                None => return None,
+            };
+
+            // Executables are looked for in the game directory
+            let game_path = steamapps_path.join("common").join(&installdir);
+            if game_path.exists() {
+                return Some((app_id, name, game_path));
+            }
+        }
+        None
+    }
+
+    fn scan_game_executables(&mut self, game_path: &PathBuf, app_id: u32, game_name: &str) {
+        // Recursively search for .exe files (up to 3 levels deep to avoid going too deep)
+        self.scan_directory_for_exes(game_path, app_id, game_name, 0, 3);
+    }
+
+    fn scan_directory_for_exes(&mut self, dir: &PathBuf, app_id: u32, game_name: &str, depth: usize, max_depth: usize) {
+        if depth > max_depth {
+            return;
+        }
+
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+
+                if path.is_file() {
+                    if let Some(ext) = path.extension() {
+                        if ext.eq_ignore_ascii_case("exe") {
+                            if let Some(filename) = path.file_name() {
+                                let exe_name = filename.to_string_lossy().to_string();
+                                // Skip common launchers and tools
+                                let lower = exe_name.to_lowercase();
+                                if !lower.contains("unins") &&
+                                   !lower.contains("crash") &&
+                                   !lower.contains("report") &&
+                                   !lower.contains("setup") &&
+                                   !lower.contains("launcher") &&
+                                   !lower.contains("redist") {
+                                    self.game_executables.insert(
+                                        exe_name.clone(),
+                                        (app_id, game_name.to_string())
+                                    );
+                                }
+                            }
+                        }
+                    }
+                } else if path.is_dir() && depth < max_depth {
+                    self.scan_directory_for_exes(&path, app_id, game_name, depth + 1, max_depth);
+                }
+            }
+        }
+    }
+
+    pub fn find_steam_path() -> Result<PathBuf, String> {
+        #[cfg(target_os = "windows")]
+        {
+            use std::process::Command;
+
+            let output = Command::new("reg")
+                .args(&[
+                    "query",
+                    "HKEY_CURRENT_USER\\Software\\Valve\\Steam",
+                    "/v",
+                    "SteamPath",
+                ])
+                .output();
+
+            if let Ok(output) = output {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                for line in stdout.lines() {
+                    if line.contains("SteamPath") {
+                        if let Some(path) = line.split("REG_SZ").nth(1) {
+                            let path = path.trim().replace("/", "\\");
+                            return Ok(PathBuf::from(path));
+                        }
+                    }
+                }
+            }
+        }
+
+        let common_paths = vec![
+            r"C:\Program Files (x86)\Steam",
+            r"C:\Program Files\Steam",
+        ];
+
+        for path_str in common_paths {
+            let path = PathBuf::from(path_str);
+            if path.exists() {
+                return Ok(path);
+            }
+        }
+
+        Err("Steam installation not found".to_string())
+    }
+
+    fn get_localconfig_path(&self) -> Result<PathBuf, String> {
+        let user_dir = steam_accounts::userdata_dir(&self.steam_path, None)?;
+        let localconfig = user_dir.join("config").join("localconfig.vdf");
+
+        if !localconfig.exists() {
+            return Err(format!(
+                "localconfig.vdf not found at: {}",
+                localconfig.display()
+            ));
+        }
+
+        Ok(localconfig)
+    }
+
+    fn get_running_game(&mut self) -> Option<GameInfo> {
+        // Refresh process list (names and exe paths only)
+        let system = self.system.get_or_insert_with(System::new);
+        system.refresh_processes_specifics(ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet));
+
+        // Check all running processes
+        for (_pid, process) in system.processes() {
+            let process_name = process.name();
+
+            // The install folder tells which game an executable belongs to, also when it was started
+            // directly instead of through Steam or shares its name with another game's executable
+            let by_path = process.exe()
+                .and_then(|exe| app_for_exe(&self.install_dirs, exe))
+                .and_then(|app_id| self.game_names.get(&app_id).map(|name| (app_id, name)));
+
+            // Check if this process matches any of our known Steam games
+            if let Some((app_id, game_name)) = by_path.or_else(|| self.game_executables.get(process_name).map(|(app_id, name)| (*app_id, name))) {
+                // Check if this app is excluded or the executable ignored (from database if available)
+                if let Some(ref db_path) = self.db_path {
+                    if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
+                        if let Ok(is_excluded) = db.is_excluded(app_id) {
+                            if is_excluded {
+                                continue;
+                            }
+                        }
+                        if db.is_executable_ignored(process_name).unwrap_or(false) {
+                            continue;
+                        }
+                    }
+                }
+
+                return Some(GameInfo {
+                    name: game_name.clone(),
+                    app_id: Some(app_id),
+                    exe_path: process.exe().map(|exe| exe.to_string_lossy().to_string()),
+                });
+            }
+        }
+
+        None
+    }
+
+    fn get_game_name(&self, app_id: u32) -> String {
+        if let Some(name) = self.game_names.get(&app_id) {
+            return name.clone();
+        }
+
+        let steamapps_path = self.steam_path.join("steamapps");
+
+        if steamapps_path.exists() {
+            let manifest_path = steamapps_path.join(format!("appmanifest_{}.acf", app_id));
+
+            if manifest_path.exists() {
+                if let Ok(contents) = fs::read_to_string(&manifest_path) {
+                    // Simple regex to find "name"\t"Game Name"
+                    if let Ok(re) = Regex::new(r#""name"\s*"([^"]+)""#) {
+                        if let Some(captures) = re.captures(&contents) {
+                            if let Some(name) = captures.get(1) {
+                                return name.as_str().to_string();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        format!("App {}", app_id)
+    }
+
+    pub fn check_steam(&mut self) -> Option<GameEvent> {
+        let current_running = self.get_running_game();
+        let current_appid = current_running.as_ref().and_then(|g| g.app_id);
+
+        match (&self.last_running_appid, current_appid) {
+            (None, Some(app_id)) => {
+                if let Some(game) = current_running {
+                    app_log!("Game detected: {} (AppID: {})", game.name, app_id);
+                    self.last_running_appid = Some(app_id);
+                    self.current_game = Some(game.clone());
+                    Some(GameEvent::Started(game))
+                } else {
+                    None
+                }
+            }
+            (Some(old_app_id), None) => {
+                if let Some(old_game) = self.current_game.take() {
+                    app_log!("Game ended: {} (AppID: {})", old_game.name, old_app_id);
+                    self.last_running_appid = None;
+                    Some(GameEvent::Ended(old_game))
+                } else {
+                    self.last_running_appid = None;
+                    None
+                }
+            }
+            (Some(old_app_id), Some(new_app_id)) if old_app_id != &new_app_id => {
+                if let Some(old_game) = self.current_game.take() {
+                    println!(
+                        "Game switched: {} -> {}",
+                        old_game.name,
+                        current_running.as_ref().map(|g| g.name.as_str()).unwrap_or("Unknown")
+                    );
+                    self.last_running_appid = Some(new_app_id);
+                    self.current_game = current_running.clone();
+                    Some(GameEvent::Ended(old_game))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the Steam client process is up, as opposed to merely installed
+    pub fn steam_process_running() -> bool {
+        let mut system = System::new();
+        system.refresh_processes_specifics(ProcessRefreshKind::new());
+        let running = system.processes().values().any(|process| process.name().eq_ignore_ascii_case("steam.exe"));
+        running
+    }
+
+    pub fn is_steam_running(&self) -> bool {
+        let steam_exe = self.steam_path.join("Steam.exe");
+        steam_exe.exists()
+    }
+}
+
+unsafe impl Send for SteamMonitor {}
+
+impl DetectionStrategy for SteamMonitor {
+    fn name(&self) -> &'static str {
+        "Steam"
+    }
+
+    // Any installed Steam game, so it yields to the user's own executable list
+    fn priority(&self) -> u8 {
+        1
+    }
+
+    fn poll(&mut self) -> Option<GameEvent> {
+        self.check_steam()
+    }
+
+    fn is_active(&self) -> bool {
+        self.current_game.is_some()
+    }
+}
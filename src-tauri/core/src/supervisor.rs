@@ -0,0 +1,128 @@
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use crate::locks::LockExt;
+use crate::app_log;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+// A task that ran this long before failing starts over from the initial backoff
+const STABLE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// How a supervised task is doing, for the diagnostics command
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskHealth {
+    pub name: String,
+    pub running: bool, // false while waiting out the backoff after a failure
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    pub last_failure_at: Option<i64>,
+}
+
+struct SupervisedTask {
+    health: TaskHealth,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Keeps long-running monitor tasks alive. A task that panics or returns on its own
+/// is logged and started again with exponential backoff until it's stopped.
+#[derive(Clone, Default)]
+pub struct Supervisor {
+    tasks: Arc<Mutex<HashMap<String, SupervisedTask>>>,
+}
+
+// Aborting the supervising loop drops this, which takes the supervised task down with it
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl Supervisor {
+    /// Run the task built by `start`, replacing any task already running under `name`
+    pub fn supervise<F, Fut>(&self, name: &str, start: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.stop(name);
+        self.tasks.lock_or_recover().insert(name.to_string(), SupervisedTask {
+            health: TaskHealth {
+                name: name.to_string(),
+                running: true,
+                restarts: 0,
+                last_error: None,
+                last_failure_at: None,
+            },
+            handle: None,
+        });
+
+        let tasks = self.tasks.clone();
+        let task_name = name.to_string();
+        let handle = tokio::spawn(async move {
+            let mut backoff = INITIAL_BACKOFF;
+            loop {
+                let started = Instant::now();
+                let mut task = AbortOnDrop(tokio::spawn(start()));
+                let error = match (&mut task.0).await {
+                    Ok(()) => "stopped unexpectedly".to_string(),
+                    Err(e) if e.is_panic() => format!("panicked: {}", panic_message(e.into_panic())),
+                    Err(e) => e.to_string(),
+                };
+
+                if started.elapsed() >= STABLE_AFTER {
+                    backoff = INITIAL_BACKOFF;
+                }
+                app_log!("[Supervisor] {} {}, restarting in {}s", task_name, error, backoff.as_secs());
+                if let Some(entry) = tasks.lock_or_recover().get_mut(&task_name) {
+                    entry.health.running = false;
+                    entry.health.last_error = Some(error);
+                    entry.health.last_failure_at = Some(chrono::Utc::now().timestamp());
+                }
+
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+
+                if let Some(entry) = tasks.lock_or_recover().get_mut(&task_name) {
+                    entry.health.running = true;
+                    entry.health.restarts += 1;
+                }
+            }
+        });
+
+        if let Some(entry) = self.tasks.lock_or_recover().get_mut(name) {
+            entry.handle = Some(handle);
+        }
+    }
+
+    /// Stop a task on purpose; it won't be restarted
+    pub fn stop(&self, name: &str) {
+        if let Some(task) = self.tasks.lock_or_recover().remove(name) {
+            if let Some(handle) = task.handle {
+                handle.abort();
+            }
+        }
+    }
+
+    pub fn is_supervised(&self, name: &str) -> bool {
+        self.tasks.lock_or_recover().contains_key(name)
+    }
+
+    pub fn health(&self) -> Vec<TaskHealth> {
+        let mut health: Vec<TaskHealth> = self.tasks.lock_or_recover().values().map(|t| t.health.clone()).collect();
+        health.sort_by(|a, b| a.name.cmp(&b.name));
+        health
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
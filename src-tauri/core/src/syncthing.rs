@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use crate::app_log;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncthingSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub folder_id: String, // Syncthing folder that contains the backup path
+    #[serde(default = "default_true")]
+    pub pause_during_backup: bool,
+    #[serde(default = "default_true")]
+    pub rescan_after_backup: bool,
+}
+
+fn default_api_url() -> String {
+    "http://127.0.0.1:8384".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SyncthingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: default_api_url(),
+            api_key: String::new(),
+            folder_id: String::new(),
+            pause_during_backup: true,
+            rescan_after_backup: true,
+        }
+    }
+}
+
+/// Write a file via a temp name Syncthing ignores (`.syncthing.*.tmp`) and rename it into place,
+/// so peers never sync a half-written file
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let file_name = path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+    let temp_path = path.with_file_name(format!(".syncthing.{}.tmp", file_name));
+
+    fs::write(&temp_path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to move {} into place: {}", path.display(), e)
+    })
+}
+
+/// Small client for the local Syncthing REST API
+pub struct SyncthingClient {
+    http_client: reqwest::Client,
+    settings: SyncthingSettings,
+}
+
+impl SyncthingClient {
+    pub fn new(settings: SyncthingSettings) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { http_client, settings }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.settings.api_url.trim_end_matches('/'), path)
+    }
+
+    /// Check the API key and that the configured folder exists
+    pub async fn test_connection(&self) -> Result<String, String> {
+        let response = self.http_client
+            .get(self.url(&format!("/rest/config/folders/{}", urlencoding::encode(&self.settings.folder_id))))
+            .header("X-API-Key", &self.settings.api_key)
+            .send()
+            .await
+            .map_err(|e| format!("Could not reach Syncthing at {}: {}", self.settings.api_url, e))?;
+
+        match response.status().as_u16() {
+            200 => {
+                let folder: serde_json::Value = response.json().await.unwrap_or_default();
+                let label = folder.get("label").and_then(|v| v.as_str()).unwrap_or(&self.settings.folder_id);
+                Ok(format!("Connected to Syncthing folder \"{}\"", label))
+            }
+            401 | 403 => Err("Syncthing rejected the API key".to_string()),
+            404 => Err(format!("Syncthing has no folder with id {}", self.settings.folder_id)),
+            status => Err(format!("Syncthing returned {}", status)),
+        }
+    }
+
+    /// Pause or resume syncing of the backup folder
+    pub async fn set_paused(&self, paused: bool) -> Result<(), String> {
+        let response = self.http_client
+            .patch(self.url(&format!("/rest/config/folders/{}", urlencoding::encode(&self.settings.folder_id))))
+            .header("X-API-Key", &self.settings.api_key)
+            .json(&serde_json::json!({ "paused": paused }))
+            .send()
+            .await
+            .map_err(|e| format!("Could not reach Syncthing: {}", e))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Syncthing refused to {} the folder ({})", if paused { "pause" } else { "resume" }, response.status()))
+        }
+    }
+
+    /// Ask Syncthing to rescan the folder so peers see new snapshots right away
+    pub async fn rescan(&self) -> Result<(), String> {
+        let response = self.http_client
+            .post(self.url("/rest/db/scan"))
+            .header("X-API-Key", &self.settings.api_key)
+            .query(&[("folder", self.settings.folder_id.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("Could not reach Syncthing: {}", e))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Syncthing rescan failed ({})", response.status()))
+        }
+    }
+
+    /// Called before Ludusavi writes into the synced folder
+    pub async fn before_backup(&self) {
+        if self.settings.pause_during_backup {
+            if let Err(e) = self.set_paused(true).await {
+                app_log!("[Syncthing] {}", e);
+            }
+        }
+    }
+
+    /// Called once the backup finished (or failed)
+    pub async fn after_backup(&self) {
+        if self.settings.pause_during_backup {
+            if let Err(e) = self.set_paused(false).await {
+                app_log!("[Syncthing] {}", e);
+            }
+        }
+        if self.settings.rescan_after_backup {
+            match self.rescan().await {
+                Ok(()) => app_log!("[Syncthing] Rescan of {} triggered", self.settings.folder_id),
+                Err(e) => app_log!("[Syncthing] {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayCount {
+    pub date: String, // "2024-03-17", local time
+    pub count: u32,
+}
+
+/// Unlock activity for the calendar heatmap and hour-of-day chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockStatistics {
+    pub total_unlocks: u32,
+    pub by_day: Vec<DayCount>,
+    pub by_week: Vec<DayCount>, // keyed by the Monday starting the week
+    pub by_hour: Vec<u32>,      // 24 entries, local hour of day
+    pub busiest_day: Option<DayCount>,
+    pub first_unlock: Option<i64>,
+    pub last_unlock: Option<i64>,
+}
+
+/// Group unlock timestamps by local day, week and hour; `year` limits it to one calendar year
+pub fn compute(unlock_times: &[i64], year: Option<i32>) -> UnlockStatistics {
+    let mut by_day: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+    let mut by_week: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+    let mut by_hour = vec![0u32; 24];
+    let mut first_unlock: Option<i64> = None;
+    let mut last_unlock: Option<i64> = None;
+    let mut total_unlocks = 0;
+
+    for &timestamp in unlock_times {
+        let Some(local) = Local.timestamp_opt(timestamp, 0).single() else { continue };
+        let date = local.date_naive();
+        if year.is_some_and(|year| date.year() != year) {
+            continue;
+        }
+
+        total_unlocks += 1;
+        *by_day.entry(date).or_insert(0) += 1;
+        let week_start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        *by_week.entry(week_start).or_insert(0) += 1;
+        by_hour[local.hour() as usize] += 1;
+        first_unlock = Some(first_unlock.map_or(timestamp, |first| first.min(timestamp)));
+        last_unlock = Some(last_unlock.map_or(timestamp, |last| last.max(timestamp)));
+    }
+
+    let to_counts = |map: BTreeMap<NaiveDate, u32>| -> Vec<DayCount> {
+        map.into_iter()
+            .map(|(date, count)| DayCount { date: date.format("%Y-%m-%d").to_string(), count })
+            .collect()
+    };
+    let by_day = to_counts(by_day);
+    // Earliest day wins a tie
+    let busiest_day = by_day.iter()
+        .fold(None::<&DayCount>, |best, day| match best {
+            Some(best) if best.count >= day.count => Some(best),
+            _ => Some(day),
+        })
+        .cloned();
+
+    UnlockStatistics {
+        total_unlocks,
+        by_day,
+        by_week: to_counts(by_week),
+        by_hour,
+        busiest_day,
+        first_unlock,
+        last_unlock,
+    }
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::app_log;
+
+const STATE_FILE: &str = "update-state.json";
+const SKIP_FILE: &str = "skipped-version.txt";
+const ROLLBACK_DIR: &str = "rollback";
+
+/// A new version that was installed over `previous_version` and hasn't proven itself yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateState {
+    pub previous_version: String,
+    pub new_version: String,
+    pub installed_at: i64,
+    pub install_dir: PathBuf,
+    pub files: Vec<String>, // file names copied to the rollback folder
+    #[serde(default)]
+    pub launch_started: bool, // set on the new version's first launch
+    #[serde(default)]
+    pub confirmed: bool, // the new version started fine at least once
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartupCheck {
+    Normal,
+    /// First launch of a freshly installed version; call mark_healthy once it's up
+    FirstLaunch,
+    /// The last launch of the new version never became healthy; the old files are back in place
+    RolledBack(String),
+}
+
+pub fn updates_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+        .join("updates")
+}
+
+fn load_state() -> Option<UpdateState> {
+    fs::read_to_string(updates_dir().join(STATE_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+fn save_state(state: &UpdateState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| format!("Failed to serialize update state: {}", e))?;
+    fs::write(updates_dir().join(STATE_FILE), json).map_err(|e| format!("Failed to save update state: {}", e))
+}
+
+fn clear_state() {
+    let _ = fs::remove_file(updates_dir().join(STATE_FILE));
+}
+
+// The executable and the DLLs shipped next to it (steam_api64.dll) are what an update replaces
+fn owned_files(install_dir: &Path, exe: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(install_dir) else { return vec![exe.to_path_buf()] };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("dll")))
+        .collect();
+    files.push(exe.to_path_buf());
+    files
+}
+
+/// Copy the running version aside before an update is installed so it can be restored
+pub fn save_rollback_point(current_version: &str, new_version: &str) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate the executable: {}", e))?;
+    let install_dir = exe.parent().ok_or("Executable has no parent folder")?.to_path_buf();
+
+    let rollback_dir = updates_dir().join(ROLLBACK_DIR);
+    let _ = fs::remove_dir_all(&rollback_dir);
+    fs::create_dir_all(&rollback_dir).map_err(|e| format!("Failed to create rollback folder: {}", e))?;
+
+    let mut files = Vec::new();
+    for file in owned_files(&install_dir, &exe) {
+        let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+        fs::copy(&file, rollback_dir.join(&name)).map_err(|e| format!("Failed to back up {}: {}", name, e))?;
+        files.push(name);
+    }
+
+    save_state(&UpdateState {
+        previous_version: current_version.to_string(),
+        new_version: new_version.to_string(),
+        installed_at: chrono::Utc::now().timestamp(),
+        install_dir,
+        files,
+        launch_started: false,
+        confirmed: false,
+    })?;
+    app_log!("[Updater] Saved {} for rollback before updating to {}", current_version, new_version);
+    Ok(())
+}
+
+/// Version that can be restored with `rollback`, if a rollback point exists
+pub fn rollback_available() -> Option<String> {
+    load_state().map(|state| state.previous_version)
+}
+
+/// Put the saved files back. The running executable is renamed first since Windows won't overwrite it.
+pub fn rollback() -> Result<String, String> {
+    let state = load_state().ok_or("No previous version to roll back to")?;
+    let rollback_dir = updates_dir().join(ROLLBACK_DIR);
+
+    for name in &state.files {
+        let target = state.install_dir.join(name);
+        if target.exists() {
+            let aside = target.with_file_name(format!("{}.failed", name));
+            let _ = fs::remove_file(&aside);
+            fs::rename(&target, &aside).map_err(|e| format!("Failed to move {} aside: {}", name, e))?;
+        }
+        fs::copy(rollback_dir.join(name), &target).map_err(|e| format!("Failed to restore {}: {}", name, e))?;
+    }
+
+    // Don't offer the version that failed again
+    let _ = fs::write(updates_dir().join(SKIP_FILE), &state.new_version);
+    clear_state();
+    app_log!("[Updater] Rolled back from {} to {}", state.new_version, state.previous_version);
+    Ok(state.previous_version)
+}
+
+/// Version the user rolled back from, which update checks should skip
+pub fn skipped_version() -> Option<String> {
+    fs::read_to_string(updates_dir().join(SKIP_FILE)).ok().map(|v| v.trim().to_string())
+}
+
+/// Decide at startup whether the previous launch of a new version failed
+pub fn check_on_startup(current_version: &str) -> StartupCheck {
+    let Some(mut state) = load_state() else { return StartupCheck::Normal };
+
+    if state.new_version != current_version {
+        // The installer was cancelled or another version was installed by hand; the rollback point no longer applies
+        clear_state();
+        return StartupCheck::Normal;
+    }
+
+    if state.confirmed {
+        return StartupCheck::Normal;
+    }
+
+    if state.launch_started {
+        app_log!("[Updater] {} didn't finish starting last time, rolling back", current_version);
+        return match rollback() {
+            Ok(version) => StartupCheck::RolledBack(version),
+            Err(e) => {
+                app_log!("[Updater] Rollback failed: {}", e);
+                StartupCheck::Normal
+            }
+        };
+    }
+
+    state.launch_started = true;
+    if let Err(e) = save_state(&state) {
+        app_log!("[Updater] {}", e);
+    }
+    StartupCheck::FirstLaunch
+}
+
+/// The new version started fine; the rollback copy is kept until the next update
+pub fn mark_healthy() {
+    if let Some(mut state) = load_state() {
+        if !state.confirmed {
+            state.confirmed = true;
+            let _ = save_state(&state);
+            let _ = fs::remove_file(updates_dir().join(SKIP_FILE));
+            app_log!("[Updater] {} started successfully", state.new_version);
+        }
+    }
+}
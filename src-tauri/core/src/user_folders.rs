@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::filenames;
+use crate::locks::LockExt;
+use crate::syncthing;
+
+/// Where exports, backup dates and logs are written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputLocation {
+    pub path: String,
+    pub documents_path: Option<String>,
+    pub is_fallback: bool,
+    pub reason: Option<String>, // why Documents isn't used
+}
+
+struct State {
+    fallback_reason: Option<String>,
+    probed: bool,
+    notice_pending: bool,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    fallback_reason: None,
+    probed: false,
+    notice_pending: false,
+});
+
+fn documents_root() -> Option<PathBuf> {
+    dirs::document_dir()
+}
+
+fn fallback_root() -> PathBuf {
+    dirs::data_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager")
+}
+
+/// Controlled Folder Access and OneDrive Known Folder Move fail writes without the folder looking unusual,
+/// so the only reliable check is to actually write a file
+fn probe(root: &Path) -> Result<(), String> {
+    let dir = root.join("Steam Backup Monitor");
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    let probe_path = dir.join(".write-test");
+    fs::write(&probe_path, b"ok").map_err(|e| format!("cannot write to {}: {}", dir.display(), e))?;
+    let _ = fs::remove_file(&probe_path);
+    Ok(())
+}
+
+fn switch_to_fallback(state: &mut State, reason: String) {
+    if state.fallback_reason.is_none() {
+        println!("[Folders] Documents not writable ({}), using {}", reason, fallback_root().display());
+        state.fallback_reason = Some(reason);
+        state.notice_pending = true;
+    }
+}
+
+/// Folder that takes the place of Documents for everything the app writes
+pub fn output_root() -> PathBuf {
+    let mut state = STATE.lock_or_recover();
+    if !state.probed {
+        state.probed = true;
+        match documents_root() {
+            Some(docs) => {
+                if let Err(e) = probe(&docs) {
+                    switch_to_fallback(&mut state, e);
+                }
+            }
+            None => switch_to_fallback(&mut state, "no Documents folder".to_string()),
+        }
+    }
+
+    match (&state.fallback_reason, documents_root()) {
+        (None, Some(docs)) => docs,
+        _ => fallback_root(),
+    }
+}
+
+/// Write `relative` under the output root, moving to the app data folder if Documents refuses the write
+pub fn write_file(relative: &Path, contents: &[u8]) -> Result<PathBuf, String> {
+    let write_under = |root: &Path| -> Result<PathBuf, String> {
+        // Long game names under a deep Documents folder can pass MAX_PATH
+        let path = filenames::long_path(&root.join(relative));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        syncthing::write_atomic(&path, contents)?;
+        Ok(path)
+    };
+
+    let root = output_root();
+    match write_under(&root) {
+        Ok(path) => Ok(path),
+        Err(e) if root != fallback_root() => {
+            switch_to_fallback(&mut STATE.lock_or_recover(), e);
+            write_under(&fallback_root())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Existing file under the output root, or under Documents from before a fallback
+pub fn find_file(relative: &Path) -> Option<PathBuf> {
+    std::iter::once(output_root())
+        .chain(documents_root())
+        .chain(std::iter::once(fallback_root()))
+        .map(|root| filenames::long_path(&root.join(relative)))
+        .find(|path| path.exists())
+}
+
+pub fn location() -> OutputLocation {
+    let path = output_root();
+    let state = STATE.lock_or_recover();
+    OutputLocation {
+        path: path.to_string_lossy().to_string(),
+        documents_path: documents_root().map(|p| p.to_string_lossy().to_string()),
+        is_fallback: state.fallback_reason.is_some(),
+        reason: state.fallback_reason.clone(),
+    }
+}
+
+/// Reason for a fallback that hasn't been shown to the user yet
+pub fn take_fallback_notice() -> Option<String> {
+    let mut state = STATE.lock_or_recover();
+    if state.notice_pending {
+        state.notice_pending = false;
+        state.fallback_reason.clone()
+    } else {
+        None
+    }
+}
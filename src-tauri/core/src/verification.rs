@@ -0,0 +1,349 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::achievements::BackupHistoryEntry;
+use crate::cloud::{self, CloudDestination};
+use crate::dedup_store::DedupStore;
+use crate::ludusavi::LudusaviManager;
+use crate::restore;
+use crate::snapshots::{hash_file, SnapshotManager, BASE_SNAPSHOT_ID};
+use crate::syncthing;
+use crate::app_log;
+
+/// Folder (inside the backup path) holding the checksum index of every snapshot
+const CHECKSUMS_DIR: &str = ".checksums";
+
+/// Checksums recorded the first time a snapshot was seen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChecksumIndex {
+    indexed_at: i64,
+    files: BTreeMap<String, String>, // relative path -> sha256
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationIssue {
+    pub game_name: String,
+    pub snapshot_id: String,
+    pub kind: String, // "corrupted", "missing", "unreadable", "remote-missing"
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub snapshots_checked: usize,
+    pub files_checked: usize,
+    pub files_indexed: usize,
+    pub uploads_checked: usize,
+    pub issues: Vec<VerificationIssue>,
+}
+
+fn last_run_path() -> PathBuf {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("steam-backup-manager");
+    let _ = fs::create_dir_all(&dir);
+    dir.join("last_verification.txt")
+}
+
+/// Whether a scheduled run is due (`interval_hours` 0 disables the schedule)
+pub fn is_due(interval_hours: u32) -> bool {
+    if interval_hours == 0 {
+        return false;
+    }
+    let last_run = fs::read_to_string(last_run_path())
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .unwrap_or(0);
+    chrono::Utc::now().timestamp() - last_run >= interval_hours as i64 * 3600
+}
+
+pub fn save_last_run(timestamp: i64) {
+    let _ = fs::write(last_run_path(), timestamp.to_string());
+}
+
+/// Drops the current thread to background priority (lower CPU and I/O) while alive
+struct BackgroundPriority;
+
+impl BackgroundPriority {
+    fn enter() -> Self {
+        use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN};
+        unsafe {
+            let _ = SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN);
+        }
+        Self
+    }
+}
+
+impl Drop for BackgroundPriority {
+    fn drop(&mut self) {
+        use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_END};
+        unsafe {
+            let _ = SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END);
+        }
+    }
+}
+
+/// Check the backup Ludusavi just wrote into `game_dir` against what it reported backing up (original path -> bytes):
+/// same number of files, every file stored with its full size, and zip entries that read back with a valid CRC.
+/// Returns the number of files checked, or what looks truncated.
+pub fn verify_new_backup(game_dir: &Path, reported: &HashMap<String, u64>) -> Result<usize, String> {
+    let (name, files) = restore::latest_backup_files(game_dir)?;
+    // A zip backup is one archive named after the backup; it must open even when it holds no files
+    if name.to_ascii_lowercase().ends_with(".zip") {
+        let archive_path = game_dir.join(&name);
+        let opened = fs::File::open(&archive_path)
+            .map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+        zip::ZipArchive::new(opened)
+            .map_err(|e| format!("{} is not a readable zip: {}", archive_path.display(), e))?;
+    }
+    if files.len() != reported.len() {
+        return Err(format!("Ludusavi reported {} files but backup {} holds {}", reported.len(), name, files.len()));
+    }
+
+    let mut archives: HashMap<PathBuf, zip::ZipArchive<fs::File>> = HashMap::new();
+    for file in &files {
+        if let Some(&bytes) = reported.get(&file.original_path) {
+            if bytes != file.size {
+                return Err(format!("{}: Ludusavi read {} bytes but recorded {}", file.original_path, bytes, file.size));
+            }
+        }
+
+        let stored = Path::new(&file.stored_path);
+        let stored_size = match restore::stored_archive(stored) {
+            Some(archive_path) => {
+                if !archives.contains_key(archive_path) {
+                    let opened = fs::File::open(archive_path)
+                        .map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+                    let zip = zip::ZipArchive::new(opened)
+                        .map_err(|e| format!("{} is not a readable zip: {}", archive_path.display(), e))?;
+                    archives.insert(archive_path.to_path_buf(), zip);
+                }
+                let zip = archives.get_mut(archive_path).unwrap();
+                let entry_name = stored.strip_prefix(archive_path).unwrap_or(stored).to_string_lossy().replace('\\', "/");
+                let mut entry = zip.by_name(&entry_name)
+                    .map_err(|_| format!("{} is missing from {}", entry_name, archive_path.display()))?;
+                // Reading to the end checks the entry's CRC
+                io::copy(&mut entry, &mut io::sink())
+                    .map_err(|e| format!("{} in {} is damaged: {}", entry_name, archive_path.display(), e))?
+            }
+            None => fs::metadata(stored)
+                .map_err(|_| format!("{} is missing", stored.display()))?
+                .len(),
+        };
+        if stored_size != file.size {
+            return Err(format!("{} holds {} of {} bytes", stored.display(), stored_size, file.size));
+        }
+    }
+
+    Ok(files.len())
+}
+
+/// Re-checks stored snapshots against the checksums recorded when they were first seen
+pub struct BackupVerifier {
+    backup_path: PathBuf,
+    snapshots: SnapshotManager,
+}
+
+impl BackupVerifier {
+    pub fn new(backup_path: String) -> Self {
+        Self {
+            snapshots: SnapshotManager::new(backup_path.clone()),
+            backup_path: PathBuf::from(backup_path),
+        }
+    }
+
+    /// Verify every local snapshot (and the dedup store when present). Blocking, run it off the async runtime.
+    pub fn verify_local(&self) -> Result<VerificationReport, String> {
+        if !self.backup_path.is_dir() {
+            return Err(format!("Backup folder {} not found", self.backup_path.display()));
+        }
+        let _priority = BackgroundPriority::enter();
+
+        let mut report = VerificationReport {
+            started_at: chrono::Utc::now().timestamp(),
+            ..Default::default()
+        };
+
+        for game_name in self.snapshots.list_games() {
+            if let Err(e) = self.verify_game(&game_name, &mut report) {
+                app_log!("[Verify] Skipping {}: {}", game_name, e);
+            }
+        }
+
+        let dedup = DedupStore::new(self.backup_path.to_string_lossy().to_string());
+        for (game_name, snapshot_id, detail) in dedup.verify_blobs() {
+            report.issues.push(VerificationIssue {
+                game_name,
+                snapshot_id: format!("dedup:{}", snapshot_id),
+                kind: if detail.ends_with("missing") { "missing" } else { "corrupted" }.to_string(),
+                detail,
+            });
+        }
+
+        report.finished_at = chrono::Utc::now().timestamp();
+        app_log!(
+            "[Verify] Checked {} snapshots ({} files, {} newly indexed), {} issue(s)",
+            report.snapshots_checked, report.files_checked, report.files_indexed, report.issues.len()
+        );
+        Ok(report)
+    }
+
+    fn index_path(&self, game_name: &str, snapshot_id: &str) -> PathBuf {
+        let file_name = if snapshot_id == BASE_SNAPSHOT_ID { "base" } else { snapshot_id };
+        self.backup_path
+            .join(CHECKSUMS_DIR)
+            .join(LudusaviManager::game_folder_name(game_name))
+            .join(format!("{}.json", file_name))
+    }
+
+    fn verify_game(&self, game_name: &str, report: &mut VerificationReport) -> Result<(), String> {
+        let snapshots = self.snapshots.list_snapshots(game_name)?;
+
+        for snapshot in &snapshots {
+            let root = Path::new(&snapshot.path);
+            let index_path = self.index_path(game_name, &snapshot.id);
+            let index = fs::read_to_string(&index_path)
+                .ok()
+                .and_then(|contents| serde_json::from_str::<ChecksumIndex>(&contents).ok());
+
+            // Ludusavi rewrites the base backup in place (and mapping.yaml with it); versions never change
+            let rewritten = snapshot.id == BASE_SNAPSHOT_ID && index.as_ref().is_some_and(|index| {
+                modified_secs(&root.join("mapping.yaml")).is_some_and(|modified| modified >= index.indexed_at)
+            });
+
+            let updated = match index {
+                Some(index) if !rewritten => Self::check_snapshot(game_name, &snapshot.id, root, index, report)?,
+                _ => Self::build_index(root, &snapshot.id, report)?,
+            };
+            if let Some(index) = updated {
+                write_index(&index_path, &index)?;
+            }
+            report.snapshots_checked += 1;
+        }
+
+        // Forget indexes of snapshots Ludusavi has since rotated out
+        let index_dir = self.backup_path.join(CHECKSUMS_DIR).join(LudusaviManager::game_folder_name(game_name));
+        if let Ok(entries) = fs::read_dir(&index_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().trim_end_matches(".json").to_string();
+                let id = if name == "base" { BASE_SNAPSHOT_ID.to_string() } else { name };
+                if !snapshots.iter().any(|s| s.id == id) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_index(root: &Path, snapshot_id: &str, report: &mut VerificationReport) -> Result<Option<ChecksumIndex>, String> {
+        let mut files = BTreeMap::new();
+        for relative in SnapshotManager::list_files(root, !snapshot_id.starts_with("backup-"))?.into_keys() {
+            let hash = hash_file(&root.join(&relative))?;
+            files.insert(relative, hash);
+            report.files_indexed += 1;
+        }
+        Ok(Some(ChecksumIndex {
+            indexed_at: chrono::Utc::now().timestamp(),
+            files,
+        }))
+    }
+
+    /// Compare a snapshot with its index; returns an updated index when new files were added to it
+    fn check_snapshot(
+        game_name: &str,
+        snapshot_id: &str,
+        root: &Path,
+        mut index: ChecksumIndex,
+        report: &mut VerificationReport,
+    ) -> Result<Option<ChecksumIndex>, String> {
+        let on_disk = SnapshotManager::list_files(root, !snapshot_id.starts_with("backup-"))?;
+        let mut issue = |kind: &str, detail: String| {
+            app_log!("[Verify] {} {} {}: {}", game_name, snapshot_id, kind, detail);
+            report.issues.push(VerificationIssue {
+                game_name: game_name.to_string(),
+                snapshot_id: snapshot_id.to_string(),
+                kind: kind.to_string(),
+                detail,
+            });
+        };
+
+        let mut files_checked = 0;
+        for (relative, expected) in &index.files {
+            if !on_disk.contains_key(relative) {
+                issue("missing", relative.clone());
+                continue;
+            }
+            files_checked += 1;
+            match hash_file(&root.join(relative)) {
+                Ok(hash) if hash == *expected => {}
+                Ok(_) => issue("corrupted", relative.clone()),
+                Err(e) => issue("unreadable", e),
+            }
+        }
+        report.files_checked += files_checked;
+
+        // Files we haven't seen yet (e.g. index written mid-backup) are added, not flagged
+        let mut changed = false;
+        for relative in on_disk.keys().filter(|r| !index.files.contains_key(*r)).cloned().collect::<Vec<_>>() {
+            let hash = hash_file(&root.join(&relative))?;
+            index.files.insert(relative, hash);
+            report.files_indexed += 1;
+            changed = true;
+        }
+
+        Ok(changed.then_some(index))
+    }
+}
+
+fn modified_secs(path: &Path) -> Option<i64> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
+
+fn write_index(path: &Path, index: &ChecksumIndex) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize checksum index: {}", e))?;
+    syncthing::write_atomic(path, json.as_bytes())
+}
+
+/// Check that the latest archive uploaded to each destination is still there
+pub async fn verify_remote_uploads(
+    uploads: &[BackupHistoryEntry],
+    destinations: &[CloudDestination],
+    report: &mut VerificationReport,
+) {
+    for upload in uploads {
+        let Some((destination_id, remote_id)) = upload.location.as_deref().and_then(|l| l.split_once('|')) else {
+            continue;
+        };
+        // Destinations removed since the upload can't be checked
+        let Some(destination) = destinations.iter().find(|d| d.id == destination_id && d.enabled) else {
+            continue;
+        };
+
+        report.uploads_checked += 1;
+        match cloud::remote_file_exists(destination, remote_id).await {
+            Ok(true) => {}
+            Ok(false) => report.issues.push(VerificationIssue {
+                game_name: upload.game_name.clone(),
+                snapshot_id: remote_id.to_string(),
+                kind: "remote-missing".to_string(),
+                detail: format!("Latest upload is gone from {}", destination.name),
+            }),
+            // Being offline isn't bit-rot, just note it
+            Err(e) => app_log!("[Verify] Could not check {} on {}: {}", upload.game_name, destination.name, e),
+        }
+    }
+}
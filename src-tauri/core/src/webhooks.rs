@@ -0,0 +1,142 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const TIMEOUT_SECS: u64 = 15;
+/// Header carrying `sha256=<hex HMAC of "<timestamp>.<body>">` when a secret is set
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+/// Header carrying the unix time that was signed, so receivers can reject replays
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+
+/// Where achievement unlocks are posted (a Discord webhook, a self-hosted receiver...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub template: String, // JSON body with {{field}} placeholders, empty = every field of the unlock
+    #[serde(default)]
+    pub secret: String, // HMAC-SHA256 key, empty = unsigned
+}
+
+/// Fields of an unlock a template can use
+#[derive(Debug, Clone, Serialize)]
+pub struct UnlockPayload {
+    pub event: &'static str,
+    pub game_name: String,
+    pub app_id: u32,
+    pub achievement_id: String,
+    pub display_name: String,
+    pub description: String,
+    pub icon_url: Option<String>,
+    pub unlock_time: i64,
+    pub global_unlock_percentage: Option<f32>,
+}
+
+/// Build the body from the template. A string that is exactly `{{field}}` becomes the field's JSON value
+/// (number, null...); placeholders inside longer strings are replaced with the field as text.
+pub fn render(template: &str, fields: &Map<String, Value>) -> Result<String, String> {
+    if template.trim().is_empty() {
+        return serde_json::to_string(fields).map_err(|e| format!("Failed to serialize webhook body: {}", e));
+    }
+    let template: Value = serde_json::from_str(template)
+        .map_err(|e| format!("The webhook template isn't valid JSON: {}", e))?;
+    serde_json::to_string(&fill(template, fields)).map_err(|e| format!("Failed to serialize webhook body: {}", e))
+}
+
+fn fill(value: Value, fields: &Map<String, Value>) -> Value {
+    match value {
+        Value::String(text) => {
+            let whole = text.strip_prefix("{{").and_then(|t| t.strip_suffix("}}")).map(str::trim);
+            if let Some(field) = whole.filter(|field| fields.contains_key(*field)) {
+                return fields[field].clone();
+            }
+            let mut text = text;
+            for (key, field) in fields {
+                let replacement = match field {
+                    Value::String(s) => s.clone(),
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                };
+                text = text.replace(&format!("{{{{{}}}}}", key), &replacement);
+            }
+            Value::String(text)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| fill(item, fields)).collect()),
+        Value::Object(entries) => Value::Object(entries.into_iter().map(|(k, v)| (k, fill(v, fields))).collect()),
+        other => other,
+    }
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>`
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Post an unlock to the webhook, signed when a secret is set
+pub async fn send_unlock(settings: &WebhookSettings, payload: &UnlockPayload) -> Result<(), String> {
+    if settings.url.trim().is_empty() {
+        return Err("No webhook URL set".to_string());
+    }
+    let fields = match serde_json::to_value(payload) {
+        Ok(Value::Object(fields)) => fields,
+        _ => return Err("Failed to serialize the unlock".to_string()),
+    };
+    let body = render(&settings.template, &fields)?;
+
+    let mut request = reqwest::Client::new()
+        .post(settings.url.trim())
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .header("Content-Type", "application/json");
+    if !settings.secret.is_empty() {
+        let timestamp = chrono::Utc::now().timestamp();
+        request = request
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, format!("sha256={}", sign(&settings.secret, timestamp, &body)));
+    }
+
+    let response = request.body(body).send().await
+        .map_err(|e| format!("Webhook request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let fields = serde_json::json!({ "game_name": "Hades", "app_id": 1145360, "icon_url": null })
+            .as_object().unwrap().clone();
+        let body = render(r#"{"content": "🏆 {{game_name}} ({{app_id}})", "id": "{{app_id}}", "icon": "{{icon_url}}", "tags": ["{{ game_name }}"]}"#, &fields).unwrap();
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["content"], "🏆 Hades (1145360)");
+        assert_eq!(body["id"], 1145360);
+        assert_eq!(body["icon"], Value::Null);
+        assert_eq!(body["tags"][0], "Hades");
+
+        assert!(render("{not json", &fields).is_err());
+        assert_eq!(render("", &fields).unwrap(), serde_json::to_string(&fields).unwrap());
+    }
+
+    #[test]
+    fn test_sign() {
+        // Same as `printf '%s' '1700000000.{}' | openssl dgst -sha256 -hmac secret`
+        assert_eq!(
+            sign("secret", 1700000000, "{}"),
+            "b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+    }
+}
@@ -3,11 +3,15 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use steam_save_core::locks::LockExt;
+use steam_save_core::app_log;
 use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
-use crate::achievements::{Achievement, AchievementDatabase};
-use crate::achievement_scanner::AchievementScanner;
-use crate::steam_achievements::SteamAchievementClient;
+use steam_save_core::achievements::{Achievement, AchievementDatabase};
+use steam_save_core::achievement_scanner::AchievementScanner;
+use steam_save_core::steam_achievements::SteamAchievementClient;
+use steam_save_core::steam_accounts;
+use steam_save_core::librarycache;
 use crate::notifications::NotificationManager;
 use std::collections::HashMap as StdHashMap;
 
@@ -51,8 +55,77 @@ impl std::fmt::Display for AchievementSourceType {
     }
 }
 
+/// `source` of unlocks made up by simulate_game_session; they aren't kept in the activity feed
+pub const SIMULATED_SOURCE: &str = "Simulation";
+
+// Sources that keep achievements in a file the watcher can attach to, in priority order
+const FILE_SOURCES: [&str; 3] = ["Online-fix", "Steamtools", "Goldberg"];
+
+/// Whether a tracked game's achievement source still matches what's on disk
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceHealth {
+    pub app_id: u32,
+    pub game_name: String,
+    pub recorded_source: String, // the source the game was added with
+    pub recorded_file: Option<String>,
+    pub available_sources: Vec<String>, // file-based sources with a file for this game right now
+    pub watcher_source: Option<String>, // what the watcher would attach to when the game starts
+    pub can_attach: bool,
+    pub watching: bool,
+    pub problem: Option<String>,
+}
+
+// How often achievement files are checked for changes file events didn't report
+const POLL_INTERVAL_SECS: u64 = 5;
+// A change still unreported by a file event after this long means events are being missed
+const EVENT_GRACE_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchMode {
+    Events,
+    Polling,
+}
+
+struct WatchState {
+    mode: WatchMode,
+    last_event: Option<SystemTime>, // when a file event last reported a change
+    active: bool,                   // cleared when the game stops being watched
+}
+
+// Everything check_for_unlocks needs for one game, shared by the event handler and the poller
+struct UnlockCheck {
+    app_id: u32,
+    game_name: String,
+    file_path: PathBuf,
+    source_type: AchievementSourceType,
+    db_path: PathBuf,
+    steam_path: PathBuf,
+    event_sender: Option<Sender<AchievementUnlockEvent>>,
+    notification_manager: Arc<Mutex<NotificationManager>>,
+    steam_client: Arc<SteamAchievementClient>,
+}
+
+impl UnlockCheck {
+    async fn run(&self) {
+        if let Err(e) = AchievementWatcher::check_for_unlocks(
+            self.app_id,
+            &self.game_name,
+            &self.file_path,
+            &self.source_type,
+            &self.db_path,
+            &self.steam_path,
+            self.event_sender.clone(),
+            self.notification_manager.clone(),
+            self.steam_client.clone(),
+        ).await {
+            app_log!("  ✗ Error checking for unlocks: {}", e);
+        }
+    }
+}
+
 pub struct AchievementWatcher {
     watchers: Arc<Mutex<HashMap<u32, RecommendedWatcher>>>,
+    watch_states: Arc<Mutex<HashMap<u32, Arc<Mutex<WatchState>>>>>,
     watched_games: Arc<Mutex<HashMap<u32, GameAchievementSource>>>,
     pending_games: Arc<Mutex<HashMap<u32, (String, SystemTime)>>>, // app_id -> (game_name, last_check_time)
     db_path: PathBuf,
@@ -65,8 +138,14 @@ pub struct AchievementWatcher {
 
 impl AchievementWatcher {
     pub fn new(db_path: PathBuf, steam_path: PathBuf, steam_user_id: Option<String>, notification_manager: Arc<Mutex<NotificationManager>>, steam_client: Arc<SteamAchievementClient>) -> Self {
+        // The configured ID wins; otherwise follow whoever is signed in to Steam
+        let steam_user_id = steam_accounts::resolve_user_id(&steam_path, steam_user_id.as_deref());
+        if let Some(ref user_id) = steam_user_id {
+            app_log!("✓ Achievement watcher using Steam user ID {}", user_id);
+        }
         Self {
             watchers: Arc::new(Mutex::new(HashMap::new())),
+            watch_states: Arc::new(Mutex::new(HashMap::new())),
             watched_games: Arc::new(Mutex::new(HashMap::new())),
             pending_games: Arc::new(Mutex::new(HashMap::new())),
             db_path,
@@ -120,7 +199,7 @@ impl AchievementWatcher {
             });
         }
 
-        // Priority 2: LibraryCache - use configured Steam user ID
+        // Priority 2: LibraryCache - use the configured or detected Steam user ID
         if let Some(ref user_id) = self.steam_user_id {
             let userdata_path = self.steam_path.join("userdata").join(user_id);
             let librarycache_path = userdata_path
@@ -164,107 +243,112 @@ impl AchievementWatcher {
     }
 
     fn find_steam_userdata(&self) -> Result<PathBuf, String> {
-        let userdata_path = self.steam_path.join("userdata");
+        steam_accounts::userdata_dir(&self.steam_path, self.steam_user_id.as_deref())
+    }
 
-        if !userdata_path.exists() {
-            return Err("Steam userdata folder not found".to_string());
-        }
+    /// Find the file for a specific source by name
+    fn find_specific_source(&self, app_id: u32, game_name: &str, source_name: &str) -> Option<GameAchievementSource> {
+        app_log!("  🔍 Looking for {} file...", source_name);
 
-        let user_dirs: Vec<_> = std::fs::read_dir(&userdata_path)
-            .map_err(|e| format!("Failed to read userdata: {}", e))?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry.path().is_dir()
-                    && entry.file_name() != "0"
-                    && entry.file_name() != "ac"
-            })
-            .collect();
+        let source_type = match source_name {
+            "Online-fix" => AchievementSourceType::OnlineFix,
+            "Steamtools" => AchievementSourceType::LibraryCache,
+            "Goldberg" => AchievementSourceType::Goldberg,
+            _ => return None,
+        };
+        if matches!(source_type, AchievementSourceType::LibraryCache) && self.steam_user_id.is_none() {
+            app_log!("    ✗ No Steam user found - set your Steam user ID in Settings");
+            return None;
+        }
 
-        if user_dirs.is_empty() {
-            return Err("No Steam user found".to_string());
+        for path in self.source_files(app_id, source_name) {
+            app_log!("    Checking: {:?}", path);
+            if path.exists() {
+                return Some(GameAchievementSource {
+                    app_id,
+                    game_name: game_name.to_string(),
+                    file_path: path,
+                    source_type,
+                });
+            }
         }
 
-        Ok(user_dirs[0].path())
+        None
     }
 
-    /// Find the file for a specific source by name
-    fn find_specific_source(&self, app_id: u32, game_name: &str, source_name: &str) -> Option<GameAchievementSource> {
-        println!("  🔍 Looking for {} file...", source_name);
-
+    // Where a file-based source keeps a game's achievements, most likely location first
+    fn source_files(&self, app_id: u32, source_name: &str) -> Vec<PathBuf> {
         match source_name {
             "Online-fix" => {
                 let onlinefix_base = PathBuf::from(r"C:\Users\Public\Documents\OnlineFix")
                     .join(format!("{}", app_id));
-
-                let paths = vec![
+                vec![
                     onlinefix_base.join("Stats").join("Achievements.ini"),
                     onlinefix_base.join("stats").join("Achievements.ini"),
                     onlinefix_base.join("Stats").join("achievements.ini"),
                     onlinefix_base.join("stats").join("achievements.ini"),
-                ];
-
-                for path in paths {
-                    println!("    Checking: {:?}", path);
-                    if path.exists() {
-                        return Some(GameAchievementSource {
-                            app_id,
-                            game_name: game_name.to_string(),
-                            file_path: path,
-                            source_type: AchievementSourceType::OnlineFix,
-                        });
-                    }
-                }
+                ]
             }
-            "Steamtools" => {
-                if let Some(ref user_id) = self.steam_user_id {
-                    println!("    Using configured Steam user ID: {}", user_id);
-                    let userdata_path = self.steam_path.join("userdata").join(user_id);
-                    println!("    Userdata path: {:?}", userdata_path);
-
-                    let librarycache_path = userdata_path
+            "Steamtools" => self.steam_user_id.iter()
+                .map(|user_id| {
+                    self.steam_path.join("userdata").join(user_id)
                         .join("config")
                         .join("librarycache")
-                        .join(format!("{}.json", app_id));
-
-                    println!("    Checking: {:?}", librarycache_path);
-                    if librarycache_path.exists() {
-                        println!("    ✓ File exists!");
-                        return Some(GameAchievementSource {
-                            app_id,
-                            game_name: game_name.to_string(),
-                            file_path: librarycache_path,
-                            source_type: AchievementSourceType::LibraryCache,
-                        });
-                    } else {
-                        println!("    ✗ File does not exist at this path");
-                    }
-                } else {
-                    println!("    ✗ No Steam user ID configured in settings!");
-                }
-            }
-            "Goldberg" => {
-                if let Ok(appdata) = std::env::var("APPDATA") {
-                    let goldberg_paths = vec![
-                        PathBuf::from(&appdata).join("GSE Saves").join(format!("{}", app_id)).join("achievements.json"),
-                        PathBuf::from(&appdata).join("Goldberg SteamEmu Saves").join(format!("{}", app_id)).join("achievements.json"),
-                    ];
-
-                    for path in goldberg_paths {
-                        if path.exists() {
-                            return Some(GameAchievementSource {
-                                app_id,
-                                game_name: game_name.to_string(),
-                                file_path: path,
-                                source_type: AchievementSourceType::Goldberg,
-                            });
-                        }
-                    }
-                }
-            }
-            _ => {}
+                        .join(format!("{}.json", app_id))
+                })
+                .collect(),
+            "Goldberg" => std::env::var("APPDATA").into_iter()
+                .flat_map(|appdata| [
+                    PathBuf::from(&appdata).join("GSE Saves").join(format!("{}", app_id)).join("achievements.json"),
+                    PathBuf::from(&appdata).join("Goldberg SteamEmu Saves").join(format!("{}", app_id)).join("achievements.json"),
+                ])
+                .collect(),
+            _ => Vec::new(),
         }
+    }
 
-        None
+    /// Check that a tracked game's achievements can still be watched: which sources have a file now,
+    /// whether the one it was added with is among them and what the watcher would attach to
+    pub fn source_health(&self, app_id: u32, game_name: &str, recorded_source: &str, excluded: bool) -> SourceHealth {
+        let existing = |source: &str| self.source_files(app_id, source).into_iter().find(|path| path.exists());
+        let available: Vec<String> = FILE_SOURCES.iter()
+            .filter(|source| existing(**source).is_some())
+            .map(|source| source.to_string())
+            .collect();
+        let recorded_file = existing(recorded_source);
+        let file_based = FILE_SOURCES.contains(&recorded_source);
+
+        // Same order as start_watching_game: the recorded source, then the priority search
+        let watcher_source = if recorded_file.is_some() {
+            Some(recorded_source.to_string())
+        } else {
+            available.first().cloned()
+        };
+
+        let problem = if excluded {
+            Some("Excluded from monitoring".to_string())
+        } else if recorded_source == "Steamtools" && self.steam_user_id.is_none() {
+            Some("Tracked via Steamtools but no Steam user is set, so its librarycache file can't be found".to_string())
+        } else if file_based && recorded_file.is_none() {
+            Some(match &watcher_source {
+                Some(other) => format!("Tracked via {} but its file is gone; the watcher would use {} instead", recorded_source, other),
+                None => format!("Tracked via {} but its file is gone; unlocks won't be detected until an achievement file appears", recorded_source),
+            })
+        } else {
+            None
+        };
+
+        SourceHealth {
+            app_id,
+            game_name: game_name.to_string(),
+            recorded_source: recorded_source.to_string(),
+            recorded_file: recorded_file.map(|path| path.to_string_lossy().to_string()),
+            available_sources: available,
+            can_attach: !excluded && watcher_source.is_some(),
+            watcher_source,
+            watching: self.watched_games.lock_or_recover().contains_key(&app_id),
+            problem,
+        }
     }
 
     /// Start watching achievement file for a game
@@ -285,7 +369,7 @@ impl AchievementWatcher {
 
                         // Store in watched games
                         {
-                            let mut watched = self.watched_games.lock().unwrap();
+                            let mut watched = self.watched_games.lock_or_recover();
                             watched.insert(app_id, source);
                         }
                         return;
@@ -303,13 +387,13 @@ impl AchievementWatcher {
 
             // Store in watched games
             {
-                let mut watched = self.watched_games.lock().unwrap();
+                let mut watched = self.watched_games.lock_or_recover();
                 watched.insert(app_id, source);
             }
         } else {
             // No source found, add to pending list for periodic checking
             {
-                let mut pending = self.pending_games.lock().unwrap();
+                let mut pending = self.pending_games.lock_or_recover();
                 pending.insert(app_id, (game_name.clone(), SystemTime::now()));
             }
             println!("  ⏱ Will check periodically every 10 minutes for {} until a source is found.", game_name);
@@ -319,17 +403,20 @@ impl AchievementWatcher {
     /// Stop watching achievement file for a game
     pub fn stop_watching_game(&self, app_id: u32) {
         // Remove from watchers
-        let mut watchers = self.watchers.lock().unwrap();
+        let mut watchers = self.watchers.lock_or_recover();
         if let Some(_watcher) = watchers.remove(&app_id) {
             println!("  ✓ Stopped watching achievements for AppID: {}", app_id);
         }
+        if let Some(state) = self.watch_states.lock_or_recover().remove(&app_id) {
+            state.lock_or_recover().active = false;
+        }
 
         // Remove from watched games
-        let mut watched = self.watched_games.lock().unwrap();
+        let mut watched = self.watched_games.lock_or_recover();
         watched.remove(&app_id);
 
         // Remove from pending games
-        let mut pending = self.pending_games.lock().unwrap();
+        let mut pending = self.pending_games.lock_or_recover();
         pending.remove(&app_id);
     }
 
@@ -338,95 +425,194 @@ impl AchievementWatcher {
         println!("Stopping all achievement watchers...");
 
         // Clear all watchers
-        let mut watchers = self.watchers.lock().unwrap();
-        let count = watchers.len();
+        // Polled games have no watcher, so count the watch states
+        let mut watchers = self.watchers.lock_or_recover();
         watchers.clear();
+        let mut states = self.watch_states.lock_or_recover();
+        let count = states.len();
+        for (_, state) in states.drain() {
+            state.lock_or_recover().active = false;
+        }
 
         // Clear watched games
-        let mut watched = self.watched_games.lock().unwrap();
+        let mut watched = self.watched_games.lock_or_recover();
         watched.clear();
 
         // Clear pending games
-        let mut pending = self.pending_games.lock().unwrap();
+        let mut pending = self.pending_games.lock_or_recover();
         pending.clear();
 
         println!("  ✓ Stopped {} achievement watcher(s)", count);
     }
 
-    /// Set up file watcher for an achievement source
+    /// The achievement file being watched for a game, if one was found
+    pub fn watched_file(&self, app_id: u32) -> Option<PathBuf> {
+        self.watched_games.lock_or_recover().get(&app_id).map(|source| source.file_path.clone())
+    }
+
+    /// Unlock an achievement the way a detected unlock is handled (database, overlay, unlock event)
+    /// without a file change. The caller undoes the database change once the check is done.
+    pub fn simulate_unlock(&self, app_id: u32, game_name: &str, achievement: &Achievement) -> Result<AchievementUnlockEvent, String> {
+        let id = achievement.id.ok_or("Achievement has no database id")?;
+        let unlock_time = chrono::Utc::now().timestamp();
+
+        let db = AchievementDatabase::new(self.db_path.clone())?;
+        db.update_achievement_status(id, true, Some(unlock_time))?;
+        let recorded = db.get_game_achievements(app_id)?
+            .iter()
+            .any(|a| a.id == Some(id) && a.achieved);
+        if !recorded {
+            return Err(format!("The database didn't record the unlock of {}", achievement.display_name));
+        }
+
+        self.notification_manager.lock_or_recover().show_achievement_unlock(
+            game_name,
+            &achievement.display_name,
+            &achievement.description,
+            achievement.icon_url.as_deref(),
+            achievement.global_unlock_percentage,
+        );
+
+        let event = AchievementUnlockEvent {
+            app_id,
+            game_name: game_name.to_string(),
+            achievement_id: achievement.achievement_id.clone(),
+            display_name: achievement.display_name.clone(),
+            description: achievement.description.clone(),
+            icon_url: achievement.icon_url.clone(),
+            unlock_time,
+            source: SIMULATED_SOURCE.to_string(),
+            global_unlock_percentage: achievement.global_unlock_percentage,
+        };
+        self.event_sender.as_ref()
+            .ok_or("Unlock events aren't connected")?
+            .send(event.clone())
+            .map_err(|_| "The unlock event listener has stopped".to_string())?;
+        Ok(event)
+    }
+
+    /// Watch a game's achievement file. File events are used where they work; when the backend can't
+    /// watch the file or misses a change (network shares, exFAT), the game falls back to mtime polling.
     async fn setup_file_watcher(&self, source: GameAchievementSource, steam_client: Arc<SteamAchievementClient>) {
         let app_id = source.app_id;
-        let file_path = source.file_path.clone();
-        let db_path = self.db_path.clone();
-        let event_sender = self.event_sender.clone();
-        let source_type = source.source_type.clone();
-        let game_name = source.game_name.clone();
-        let notification_manager = self.notification_manager.clone();
+        let check = Arc::new(UnlockCheck {
+            app_id,
+            game_name: source.game_name.clone(),
+            file_path: source.file_path.clone(),
+            source_type: source.source_type.clone(),
+            db_path: self.db_path.clone(),
+            steam_path: self.steam_path.clone(),
+            event_sender: self.event_sender.clone(),
+            notification_manager: self.notification_manager.clone(),
+            steam_client,
+        });
+
+        let state = Arc::new(Mutex::new(WatchState { mode: WatchMode::Events, last_event: None, active: true }));
+        // A restarted watch replaces the old one; its poller sees `active` go false and exits
+        if let Some(previous) = self.watch_states.lock_or_recover().insert(app_id, state.clone()) {
+            previous.lock_or_recover().active = false;
+        }
+
+        match self.start_event_watcher(check.clone(), state.clone()) {
+            Ok(watcher) => {
+                app_log!("  ✓ Watching {} achievements at: {:?} (file events)", check.source_type, check.file_path);
+                self.watchers.lock_or_recover().insert(app_id, watcher);
+            }
+            Err(e) => {
+                app_log!("  ⚠ File events unavailable for {:?} ({}), polling every {}s instead", check.file_path, e, POLL_INTERVAL_SECS);
+                state.lock_or_recover().mode = WatchMode::Polling;
+            }
+        }
 
+        tokio::spawn(Self::poll_file(check, state, self.watchers.clone()));
+    }
+
+    fn start_event_watcher(&self, check: Arc<UnlockCheck>, state: Arc<Mutex<WatchState>>) -> Result<RecommendedWatcher, String> {
         // Create a channel to receive file system events
         let (tx, rx): (Sender<Result<Event, notify::Error>>, Receiver<Result<Event, notify::Error>>) = channel();
 
-        // Create file watcher
-        let mut watcher = match RecommendedWatcher::new(
+        let mut watcher = RecommendedWatcher::new(
             move |res| {
                 let _ = tx.send(res);
             },
             Config::default(),
-        ) {
-            Ok(w) => w,
-            Err(e) => {
-                println!("  ✗ Failed to create watcher for {}: {}", game_name, e);
-                return;
-            }
-        };
-
-        // Watch the file
-        if let Err(e) = watcher.watch(&file_path, RecursiveMode::NonRecursive) {
-            println!("  ✗ Failed to watch file {:?}: {}", file_path, e);
-            return;
-        }
+        ).map_err(|e| format!("failed to create watcher: {}", e))?;
 
-        println!("  ✓ Watching {} achievements at: {:?}", source_type, file_path);
-
-        // Store watcher
-        {
-            let mut watchers = self.watchers.lock().unwrap();
-            watchers.insert(app_id, watcher);
-        }
+        watcher.watch(&check.file_path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("failed to watch file: {}", e))?;
 
         // Spawn task to handle file change events
-        let steam_path = self.steam_path.clone();
+        let watchers = self.watchers.clone();
         tokio::spawn(async move {
             while let Ok(res) = rx.recv() {
                 match res {
                     Ok(event) => {
                         // Process modify, create, and write events (Windows sends different events)
                         if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Access(_)) {
-                            println!("  📝 Achievement file change detected for AppID: {} ({:?})", app_id, event.kind);
+                            state.lock_or_recover().last_event = Some(SystemTime::now());
+                            app_log!("  📝 Achievement file change detected for AppID: {} ({:?})", check.app_id, event.kind);
 
                             // Give the file a moment to finish writing (longer for JSON files)
                             tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
-
-                            // Check for unlocks
-                            if let Err(e) = Self::check_for_unlocks(
-                                app_id,
-                                &game_name,
-                                &file_path,
-                                &source_type,
-                                &db_path,
-                                &steam_path,
-                                event_sender.clone(),
-                                notification_manager.clone(),
-                                steam_client.clone(),
-                            ).await {
-                                println!("  ✗ Error checking for unlocks: {}", e);
-                            }
+                            check.run().await;
                         }
                     }
-                    Err(e) => println!("  ✗ Watch error: {}", e),
+                    Err(e) => {
+                        // The backend gave up on this file; the poller takes over
+                        app_log!("  ✗ Watch error for {}: {}, switching to polling every {}s", check.game_name, e, POLL_INTERVAL_SECS);
+                        state.lock_or_recover().mode = WatchMode::Polling;
+                        watchers.lock_or_recover().remove(&check.app_id);
+                        break;
+                    }
                 }
             }
         });
+
+        Ok(watcher)
+    }
+
+    /// Compare the file's mtime on a timer. In polling mode every change triggers a check; in events
+    /// mode a change that no event reported within the grace period means events are being missed.
+    async fn poll_file(check: Arc<UnlockCheck>, state: Arc<Mutex<WatchState>>, watchers: Arc<Mutex<HashMap<u32, RecommendedWatcher>>>) {
+        let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut last_modified = modified(&check.file_path);
+        let mut unreported_since: Option<SystemTime> = None;
+
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
+
+            let (mode, last_event) = {
+                let state = state.lock_or_recover();
+                if !state.active {
+                    break;
+                }
+                (state.mode, state.last_event)
+            };
+
+            let current = modified(&check.file_path);
+            if current != last_modified {
+                last_modified = current;
+                if mode == WatchMode::Polling {
+                    app_log!("  📝 Achievement file change polled for AppID: {}", check.app_id);
+                    check.run().await;
+                    continue;
+                }
+                unreported_since.get_or_insert_with(SystemTime::now);
+            }
+
+            let Some(changed_at) = unreported_since else { continue };
+            // The event for a change seen on this tick may arrive at any point since the previous one
+            let window_start = changed_at - std::time::Duration::from_secs(POLL_INTERVAL_SECS + 1);
+            if last_event.is_some_and(|event| event >= window_start) {
+                unreported_since = None;
+            } else if changed_at.elapsed().is_ok_and(|waited| waited.as_secs() >= EVENT_GRACE_SECS) {
+                app_log!("  ⚠ File events missed a change to {:?}, polling every {}s for {}", check.file_path, POLL_INTERVAL_SECS, check.game_name);
+                state.lock_or_recover().mode = WatchMode::Polling;
+                watchers.lock_or_recover().remove(&check.app_id);
+                unreported_since = None;
+                check.run().await;
+            }
+        }
     }
 
     /// Check for achievement unlocks by comparing file state vs database
@@ -515,7 +701,7 @@ impl AchievementWatcher {
                     }
 
                     // Show overlay notification (or Windows native as fallback) with the fetched percentage
-                    notification_manager.lock().unwrap().show_achievement_unlock(
+                    notification_manager.lock_or_recover().show_achievement_unlock(
                         game_name,
                         &db_ach.display_name,
                         &db_ach.description,
@@ -604,81 +790,15 @@ impl AchievementWatcher {
     ) -> Result<Vec<(String, i64)>, String> {
         println!("  🔍 Parsing library cache file: {:?}", file_path);
 
-        let contents = std::fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read LibraryCache file: {}", e))?;
-
-        let json: serde_json::Value = serde_json::from_str(&contents)
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        let achievements_entry = json.as_array()
-            .and_then(|arr| {
-                arr.iter().find(|item| {
-                    item.as_array()
-                        .and_then(|inner| inner.get(0))
-                        .and_then(|v| v.as_str())
-                        .map(|s| s == "achievements")
-                        .unwrap_or(false)
-                })
-            })
-            .ok_or_else(|| "No achievements entry found".to_string())?;
-
-        let achievement_data = achievements_entry.as_array()
-            .and_then(|arr| arr.get(1))
-            .and_then(|v| v.get("data"))
-            .ok_or_else(|| "Invalid achievement data structure".to_string())?;
+        let cache = librarycache::read(file_path)?;
+        app_log!("  📋 Found {} achievements ({} layout)", cache.achievements.len(), cache.format);
 
+        let now = chrono::Utc::now().timestamp();
         let mut unlocked = Vec::new();
-
-        // Process vecHighlight
-        if let Some(vec_highlight) = achievement_data.get("vecHighlight").and_then(|v| v.as_array()) {
-            println!("  📋 Found {} achievements in vecHighlight", vec_highlight.len());
-            for ach in vec_highlight {
-                let achievement_id = ach.get("strID")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-
-                let achieved = ach.get("bAchieved")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-
-                let unlock_time = ach.get("rtUnlocked")
-                    .and_then(|v| v.as_i64())
-                    .filter(|&t| t > 0)
-                    .unwrap_or_else(|| chrono::Utc::now().timestamp());
-
-                if achieved {
-                    if let Some(id) = achievement_id {
-                        println!("  ✓ Found unlocked: {} at {}", id, unlock_time);
-                        unlocked.push((id, unlock_time));
-                    }
-                }
-            }
-        }
-
-        // Process vecAchievedHidden
-        if let Some(vec_achieved_hidden) = achievement_data.get("vecAchievedHidden").and_then(|v| v.as_array()) {
-            println!("  📋 Found {} achievements in vecAchievedHidden", vec_achieved_hidden.len());
-            for ach in vec_achieved_hidden {
-                let achievement_id = ach.get("strID")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-
-                let achieved = ach.get("bAchieved")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(true); // Default to true for vecAchievedHidden
-
-                let unlock_time = ach.get("rtUnlocked")
-                    .and_then(|v| v.as_i64())
-                    .filter(|&t| t > 0)
-                    .unwrap_or_else(|| chrono::Utc::now().timestamp());
-
-                if achieved {
-                    if let Some(id) = achievement_id {
-                        println!("  ✓ Found unlocked (hidden): {} at {}", id, unlock_time);
-                        unlocked.push((id, unlock_time));
-                    }
-                }
-            }
+        for (id, unlock_time) in cache.unlocked() {
+            let unlock_time = unlock_time.unwrap_or(now);
+            app_log!("  ✓ Found unlocked: {} at {}", id, unlock_time);
+            unlocked.push((id.clone(), unlock_time));
         }
 
         println!("  📊 Total unlocked achievements found: {}", unlocked.len());
@@ -722,7 +842,7 @@ impl AchievementWatcher {
 
         // Collect games to check in a separate block
         let to_check = {
-            let pending = self.pending_games.lock().unwrap();
+            let pending = self.pending_games.lock_or_recover();
             let mut to_check = Vec::new();
 
             for (app_id, (game_name, last_check)) in pending.iter() {
@@ -746,17 +866,17 @@ impl AchievementWatcher {
 
                 // Move from pending to watched
                 {
-                    let mut pending = self.pending_games.lock().unwrap();
+                    let mut pending = self.pending_games.lock_or_recover();
                     pending.remove(&app_id);
                 }
 
                 {
-                    let mut watched = self.watched_games.lock().unwrap();
+                    let mut watched = self.watched_games.lock_or_recover();
                     watched.insert(app_id, source);
                 }
             } else {
                 // Still not found, update last check time
-                let mut pending = self.pending_games.lock().unwrap();
+                let mut pending = self.pending_games.lock_or_recover();
                 if let Some((_, ref mut last_check)) = pending.get_mut(&app_id) {
                     *last_check = now;
                 }
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use crate::achievement_watcher::AchievementUnlockEvent;
+use steam_save_core::shell_integration::LaunchRequest;
+
+/// The one channel every app event is emitted on
+pub const EVENT_CHANNEL: &str = "app-event";
+// How many events get_missed_events can replay
+const BUFFER_SIZE: usize = 500;
+
+/// Everything the backend tells the frontend about. Serialized as `{ "type": "...", "payload": {...} }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum AppEvent {
+    /// A Steam or process-monitored game started; `app_id` is None for process-monitored games
+    GameDetected { name: String, app_id: Option<u32> },
+    GameEnded { name: String, app_id: Option<u32> },
+    /// Ludusavi doesn't know the game, so it has to be added manually
+    GameNotFound { name: String },
+    /// A Steam game's appmanifest disappeared; `final_backup` is true when its saves are being backed up
+    /// automatically, otherwise archive_uninstalled_game does it on request
+    GameUninstalled { app_id: u32, name: String, final_backup: bool },
+    /// Ludusavi started backing up a game; cancel_backup stops it
+    BackupStarted { game_name: String, app_id: Option<u32> },
+    BackupFinished { game_name: String, app_id: Option<u32>, success: bool, error: Option<String> },
+    /// Games waiting for the running backup to finish, oldest first; empty once the queue is drained
+    BackupQueueChanged { queued: Vec<String> },
+    /// A backup was pushed to a cloud destination, or to the cloud set up in Ludusavi (`destination` "Ludusavi")
+    CloudUploadFinished { game_name: String, destination: String, success: bool, error: Option<String> },
+    /// The backup destination has less free space than the configured threshold
+    LowDiskSpace { backup_path: String, free_bytes: u64, threshold_mb: u32 },
+    /// Regularly played games without a successful backup in the configured number of days
+    StaleBackups { games: Vec<String> },
+    AchievementUnlocked(AchievementUnlockEvent),
+    /// A steamsavemonitor:// link or the Explorer context menu opened the app; fetch it with take_launch_request
+    LaunchRequested(LaunchRequest),
+    /// Monitoring was paused or resumed from the control pipe
+    MonitoringPaused { paused: bool },
+    /// Where a long-running command is at. Not replayed by get_missed_events; get_active_operations has the latest state.
+    Progress(ProgressEvent),
+}
+
+/// Progress of one run of a long-running command (an achievement sync, a backup...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressEvent {
+    pub operation_id: String, // unique per run, e.g. "backup-12"
+    pub operation: String, // "sync_achievements", "backup"...
+    pub stage: String,
+    pub current: u64,
+    pub total: Option<u64>, // None while the amount of work isn't known
+    pub message: String,
+    pub done: bool,
+}
+
+/// Operations that are still running, with their latest progress
+#[derive(Default)]
+pub struct ActiveOperations {
+    next_id: u64,
+    running: HashMap<String, ProgressEvent>,
+}
+
+impl ActiveOperations {
+    pub fn start(&mut self, operation: &str, message: String) -> ProgressEvent {
+        self.next_id += 1;
+        let progress = ProgressEvent {
+            operation_id: format!("{}-{}", operation, self.next_id),
+            operation: operation.to_string(),
+            stage: "starting".to_string(),
+            current: 0,
+            total: None,
+            message,
+            done: false,
+        };
+        self.running.insert(progress.operation_id.clone(), progress.clone());
+        progress
+    }
+
+    pub fn update(&mut self, progress: &ProgressEvent) {
+        if progress.done {
+            self.running.remove(&progress.operation_id);
+        } else {
+            self.running.insert(progress.operation_id.clone(), progress.clone());
+        }
+    }
+
+    /// Running operations, oldest first
+    pub fn list(&self) -> Vec<ProgressEvent> {
+        let mut running: Vec<ProgressEvent> = self.running.values().cloned().collect();
+        running.sort_by_key(|progress| {
+            progress.operation_id.rsplit('-').next().and_then(|n| n.parse::<u64>().ok()).unwrap_or(0)
+        });
+        running
+    }
+}
+
+/// An event with its position in the stream. `seq` increases by one per event and restarts at 1 with the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    pub timestamp: i64,
+    #[serde(flatten)]
+    pub event: AppEvent,
+}
+
+/// Numbers events and keeps the latest ones so a reloaded frontend can catch up
+pub struct EventBus {
+    next_seq: u64,
+    recent: VecDeque<SequencedEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            next_seq: 1,
+            recent: VecDeque::with_capacity(BUFFER_SIZE),
+        }
+    }
+}
+
+impl EventBus {
+    pub fn record(&mut self, event: AppEvent) -> SequencedEvent {
+        let sequenced = SequencedEvent {
+            seq: self.next_seq,
+            timestamp: chrono::Utc::now().timestamp(),
+            event,
+        };
+        self.next_seq += 1;
+        // A long sync would push everything else out of the buffer
+        if matches!(sequenced.event, AppEvent::Progress(_)) {
+            return sequenced;
+        }
+        if self.recent.len() == BUFFER_SIZE {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(sequenced.clone());
+        sequenced
+    }
+
+    /// `seq` of the latest event, 0 before the first
+    pub fn last_seq(&self) -> u64 {
+        self.next_seq - 1
+    }
+
+    /// Buffered events after `since`. A `since` from before a restart (at or past the next seq)
+    /// can't be compared, so everything buffered is returned.
+    pub fn since(&self, since: u64) -> Vec<SequencedEvent> {
+        let since = if since >= self.next_seq { 0 } else { since };
+        self.recent.iter().filter(|e| e.seq > since).cloned().collect()
+    }
+}
@@ -1,97 +1,388 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod config;
-mod steam_monitor;
-mod process_monitor;
-mod ludusavi;
 mod notifications;
-mod achievements;
-mod achievement_scanner;
-mod steam_achievements;
 mod achievement_watcher;
 mod overlay;
-
-use tauri::{CustomMenuItem, SystemTray, SystemTrayMenu, SystemTrayEvent, Manager, State, Window};
-use tauri::api::dialog;
+mod app_events;
+use steam_save_core::{
+    config, steam_monitor, process_monitor, game_detection, supervisor, ludusavi, achievements, achievement_scanner, steam_achievements,
+    ludusavi_config, snapshots, power_events, save_watcher, save_discovery, cloud, network, gdrive, dropbox,
+    rclone, syncthing, dedup_store, disk_space, verification, restore, achievement_sync, lan_sync, user_folders,
+    backup_freshness, cloud_conflicts, filenames, shell_integration, control_pipe, backup_keys, steam_accounts, steam_categories, achievement_export, achievement_backups, achievement_dlc, achievement_screenshots, completion_order, unlock_stats, session_recap, milestones, sound_packs, icon_cache, achievement_sources, scripting,
+    update_rollback, webhooks, setup_wizard, activity_timeline, logging, app_log,
+};
+
+use tauri::{Emitter, Listener, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Wry};
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconEvent};
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+use update_rollback::StartupCheck;
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use tokio::sync::RwLock;
+use chrono::TimeZone;
+use steam_save_core::locks::LockExt;
 use std::sync::mpsc::{channel, Sender};
 
 use config::{ConfigManager, AppConfig};
 use steam_monitor::SteamMonitor;
 use process_monitor::ProcessMonitor;
-use ludusavi::LudusaviManager;
+use game_detection::{DetectionStrategy, GameDetection, GameEvent, GameInfo};
+use supervisor::{Supervisor, TaskHealth};
+use ludusavi::{BackupCloudSync, BackupPreview, CancelToken, CloudDirection, LudusaviCloudResult, LudusaviCloudStatus, LudusaviManager, RestoreResult};
+use ludusavi_config::{LudusaviCustomGame, LudusaviCustomization, LudusaviRoot};
 use notifications::NotificationManager;
-use achievements::{AchievementDatabase, GameAchievementSummary, Achievement};
-use steam_achievements::{SteamAchievementClient, SteamGameSearchResult};
-use achievement_watcher::{AchievementWatcher, AchievementUnlockEvent};
+use achievements::{AchievementDatabase, GameAchievementSummary, GameMetadata, GameStatus, Achievement, MergedAchievement, DuplicateGame, BackupHistoryEntry, UnlockFeedEntry, ExclusionEntry, UninstalledGame, PlaySession};
+use steam_achievements::{SteamAchievementClient, SteamGameSearchResult, parse_steam_id_64};
+use achievement_watcher::{AchievementWatcher, AchievementUnlockEvent, SourceHealth, SIMULATED_SOURCE};
 use overlay::OverlayManager;
-use std::path::PathBuf;
+use snapshots::{SnapshotManager, SnapshotInfo, SnapshotComparison};
+use power_events::PowerEventKind;
+use save_watcher::SaveDirectoryWatcher;
+use save_discovery::{SavePathDiscovery, SavePathCandidate};
+use cloud::{CloudDestination, CloudProvider, DeviceAuthorization, PendingUpload, UploadResult, StorageQuota};
+use cloud_conflicts::{CloudConflict, ResolutionAction};
+use dropbox::DropboxClient;
+use achievement_export::{ExportFormat, ExportResult};
+use backup_freshness::BackupFreshness;
+use webhooks::{UnlockPayload, WebhookSettings};
+use achievement_backups::{BackupMetadata, BackupSummary};
+use achievement_dlc::DlcGroup;
+use completion_order::SuggestedAchievement;
+use syncthing::SyncthingClient;
+use dedup_store::{DedupStore, DedupSnapshotInfo, DedupStats};
+use disk_space::BackupStats;
+use verification::{BackupVerifier, VerificationReport};
+use restore::{RestorableFile, SelectiveRestoreResult};
+use achievement_sync::AchievementSyncResult;
+use lan_sync::{LanSync, LanPeer, PeerPayload};
+use user_folders::OutputLocation;
+use unlock_stats::UnlockStatistics;
+use session_recap::SessionRecap;
+use sound_packs::SoundPackInfo;
+use achievement_sources::PluginStatus;
+use scripting::ScriptEvent;
+use app_events::{ActiveOperations, AppEvent, EventBus, ProgressEvent, SequencedEvent};
+use shell_integration::LaunchRequest;
+use control_pipe::{ControlCommand, ControlStatus};
+use achievement_screenshots::AchievementScreenshot;
+use gdrive::{GoogleDriveClient, DriveFolder};
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
 #[derive(Clone)]
+// Fields read from async tasks and kept across .await use tokio's RwLock; the rest are only held briefly
 struct AppState {
     config: Arc<Mutex<ConfigManager>>,
-    steam_handle: Arc<Mutex<Option<mpsc::Sender<MonitorCommand>>>>,
-    process_handle: Arc<Mutex<Option<mpsc::Sender<bool>>>>,
+    supervisor: Supervisor,
     notification_manager: Arc<Mutex<NotificationManager>>,
     achievement_db_path: Arc<Mutex<Option<PathBuf>>>,
-    achievement_watcher: Arc<Mutex<Option<Arc<AchievementWatcher>>>>,
+    achievement_watcher: Arc<RwLock<Option<Arc<AchievementWatcher>>>>,
     overlay_manager: Arc<Mutex<OverlayManager>>,
     achievement_duration: Arc<Mutex<u32>>, // Duration in seconds
-    current_game: Arc<Mutex<Option<(String, u32)>>>, // (game_name, app_id)
+    current_game: Arc<RwLock<Option<(String, u32)>>>, // (game_name, app_id)
+    last_played_game: Arc<Mutex<Option<(String, Option<u32>, i64)>>>, // (game_name, app_id, ended_at)
+    save_watcher: Arc<Mutex<Option<SaveDirectoryWatcher>>>,
+    pending_uploads: Arc<Mutex<Vec<PendingUpload>>>,
+    lan_sync: Arc<Mutex<Option<Arc<LanSync>>>>,
+    session_started_at: Arc<Mutex<Option<i64>>>, // when the current Steam game was detected
+    last_session_recap: Arc<Mutex<Option<SessionRecap>>>,
+    event_bus: Arc<Mutex<EventBus>>,
+    pending_update: Arc<Mutex<Option<(Update, Vec<u8>)>>>, // downloaded and verified, installed on exit
+    pending_launch: Arc<Mutex<Option<LaunchRequest>>>, // link the frontend hasn't navigated to yet
+    monitoring_paused: Arc<Mutex<bool>>, // paused from the control pipe; start_monitors does nothing until resumed
+    control_pipe_running: Arc<Mutex<bool>>,
+    running_backups: Arc<Mutex<HashMap<String, CancelToken>>>, // game name -> stops its Ludusavi run
+    session_backups: Arc<Mutex<Option<(CancelToken, tokio::task::JoinHandle<()>)>>>, // mid-session backups of the running game
+    backup_slot: Arc<tokio::sync::Mutex<()>>, // held by the one backup allowed to run, see handle_game_backup
+    queued_backups: Arc<Mutex<Vec<String>>>, // games waiting for backup_slot, oldest first
+    active_operations: Arc<Mutex<ActiveOperations>>, // long-running commands and their latest progress
+    library_backup_pending: Arc<Mutex<bool>>, // scheduled library backup is due but waits for the running game to close
+    link_backups: Arc<Mutex<HashMap<String, i64>>>, // game name -> when a link or context menu last started its backup
+}
+
+// A game that ended this recently still gets a backup on shutdown/sleep
+const RECENT_GAME_WINDOW_SECS: i64 = 30 * 60;
+// A freshly updated version that stays up this long isn't rolled back
+const UPDATE_HEALTHY_AFTER_SECS: u64 = 120;
+// How often the game detection service looks at running processes
+const GAME_POLL_INTERVAL_SECS: u64 = 2;
+// With "wait for Steam" on, monitors start anyway if Steam hasn't come up by then
+const STEAM_WAIT_LIMIT: tokio::time::Duration = tokio::time::Duration::from_secs(10 * 60);
+// Name the game detection task is supervised under
+const GAME_DETECTION_TASK: &str = "Game detection";
+// How long a simulated session gives the watcher and the unlock listener to react
+const SIMULATION_STEP_WAIT: tokio::time::Duration = tokio::time::Duration::from_secs(3);
+// Snapshots taken as games start, the newest this many are kept per game
+const PRE_PLAY_SNAPSHOTS_KEPT: usize = 3;
+// A game backed up from a link or the context menu isn't backed up that way again for this long,
+// so a page repeating the link can't rotate the retained versions out
+const LINK_BACKUP_INTERVAL_SECS: i64 = 5 * 60;
+
+// Refuse destructive commands while read-only (family) mode is on
+fn ensure_writable(state: &AppState) -> Result<(), String> {
+    if state.config.lock_or_recover().get_all().read_only_mode {
+        Err("Read-only mode is on. Unlock it in settings to make changes.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Refuse to restore while the backup key is set up but can't be read, so a restore doesn't
+/// happen with a key that no longer matches
+fn ensure_backup_key(state: &AppState) -> Result<(), String> {
+    match state.config.lock_or_recover().get_all().backup_key {
+        Some(info) => backup_keys::verify_stored_key(&info).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+// Store a new key and remember its fingerprint; returns the recovery phrase to write down
+fn install_backup_key(state: &AppState, key: &backup_keys::BackupKey, salt: Option<String>) -> Result<String, String> {
+    backup_keys::store_key(key)?;
+    let mut config = state.config.lock_or_recover();
+    let mut cfg = config.get_all();
+    cfg.backup_key = Some(backup_keys::BackupKeyInfo {
+        check: backup_keys::key_check(key),
+        salt,
+        created_at: chrono::Utc::now().timestamp(),
+    });
+    config.set_all(cfg);
+    Ok(backup_keys::recovery_phrase(key))
+}
+
+// Replacing a key would leave backups made with the old one unreadable, so it has to be asked for.
+// Callers check ensure_writable first, like every other command that changes something.
+fn ensure_can_replace_backup_key(state: &AppState, replace: bool) -> Result<(), String> {
+    if !replace && state.config.lock_or_recover().get_all().backup_key.is_some() {
+        return Err("A backup key is already set up. Backups made with it can't be opened with a new key.".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_backup_key_status(state: State<'_, AppState>) -> backup_keys::BackupKeyStatus {
+    let info = state.config.lock_or_recover().get_all().backup_key;
+    backup_keys::status(info.as_ref())
+}
+
+/// Create a random backup key; returns its recovery phrase
+#[tauri::command]
+fn generate_backup_key(replace: bool, state: State<'_, AppState>) -> Result<String, String> {
+    ensure_writable(&state)?;
+    ensure_can_replace_backup_key(&state, replace)?;
+    let key = backup_keys::generate_key()?;
+    let phrase = install_backup_key(&state, &key, None)?;
+    app_log!("[Backup Key] Generated a new backup key");
+    Ok(phrase)
+}
+
+/// Derive the backup key from a passphrase; returns its recovery phrase
+#[tauri::command]
+async fn set_backup_key_passphrase(passphrase: String, replace: bool, state: State<'_, AppState>) -> Result<String, String> {
+    ensure_writable(&state)?;
+    ensure_can_replace_backup_key(&state, replace)?;
+    if passphrase.chars().count() < 12 {
+        return Err("Use a passphrase of at least 12 characters".to_string());
+    }
+    let salt = backup_keys::new_salt()?;
+    let derive_salt = salt.clone();
+    // Key stretching takes a moment, keep it off the async runtime
+    let key = tokio::task::spawn_blocking(move || backup_keys::derive_key(&passphrase, &derive_salt))
+        .await
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    let phrase = install_backup_key(&state, &key, Some(salt))?;
+    app_log!("[Backup Key] Derived the backup key from a passphrase");
+    Ok(phrase)
+}
+
+/// The recovery phrase of the stored key, to write down again
+#[tauri::command]
+fn export_backup_recovery_phrase(state: State<'_, AppState>) -> Result<String, String> {
+    let info = state.config.lock_or_recover().get_all().backup_key.ok_or("No backup key is set up")?;
+    let key = backup_keys::verify_stored_key(&info)?;
+    Ok(backup_keys::recovery_phrase(&key))
+}
+
+/// Check that the backup key can still be used: the stored key, or a passphrase when one is given
+#[tauri::command]
+async fn verify_backup_key(passphrase: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    let info = state.config.lock_or_recover().get_all().backup_key.ok_or("No backup key is set up")?;
+    match passphrase {
+        Some(passphrase) => tokio::task::spawn_blocking(move || backup_keys::verify_passphrase(&info, &passphrase).map(|_| ()))
+            .await
+            .map_err(|e| format!("Key derivation failed: {}", e))?,
+        None => backup_keys::verify_stored_key(&info).map(|_| ()),
+    }
+}
+
+/// Store the key again from its recovery phrase (new PC, reinstalled Windows). When a key is set up,
+/// the phrase has to be for that key.
+#[tauri::command]
+fn recover_backup_key(phrase: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let key = backup_keys::parse_recovery_phrase(&phrase)?;
+    let info = state.config.lock_or_recover().get_all().backup_key;
+    match info {
+        Some(info) if backup_keys::key_check(&key) != info.check => {
+            Err("This recovery phrase is for a different backup key".to_string())
+        }
+        Some(_) => backup_keys::store_key(&key),
+        None => install_backup_key(&state, &key, None).map(|_| ()),
+    }?;
+    app_log!("[Backup Key] Recovered the backup key from its recovery phrase");
+    Ok(())
 }
 
-enum MonitorCommand {
-    Stop,
-    Pause,
-    Resume,
+/// Turn read-only mode on (optionally with a PIN) or off. Once a PIN is set, every change needs it.
+#[tauri::command]
+fn set_read_only_mode(enabled: bool, pin: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    let mut config = state.config.lock_or_recover();
+    let mut cfg = config.get_all();
+
+    // Also checked when turning it on, or enabling again without a PIN would drop the old one
+    if !cfg.read_only_pin_matches(pin.as_deref()) {
+        return Err("Wrong PIN".to_string());
+    }
+    cfg.read_only_pin_hash = match pin.filter(|p| !p.is_empty()) {
+        Some(pin) if enabled => Some(AppConfig::hash_pin(&pin)?),
+        _ => None,
+    };
+    cfg.read_only_mode = enabled;
+    config.set_all(cfg);
+
+    app_log!("Read-only mode {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
 }
 
 #[tauri::command]
 async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
-    let config = state.config.lock().unwrap();
-    Ok(config.get_all())
+    let mut config = state.config.lock_or_recover().get_all();
+    // The webview never needs the PIN hash, and save_config keeps the stored one anyway
+    config.read_only_pin_hash = None;
+    Ok(config)
 }
 
 #[tauri::command]
 async fn save_config(
-    config: AppConfig,
+    mut config: AppConfig,
     state: State<'_, AppState>,
-    window: Window,
+    window: WebviewWindow,
 ) -> Result<(), String> {
+    ensure_writable(&state)?;
     {
-        let mut cfg = state.config.lock().unwrap();
+        let mut cfg = state.config.lock_or_recover();
+        // Family mode is only switched through its own commands so the PIN can't be bypassed
+        let current = cfg.get_all();
+        config.read_only_mode = current.read_only_mode;
+        config.read_only_pin_hash = current.read_only_pin_hash;
+        // The backup key is only changed through its own commands, which also store the key
+        config.backup_key = current.backup_key;
         cfg.set_all(config.clone());
     }
+    power_events::set_block_budget(config.shutdown_backup_budget_secs);
     
     // Restart monitors
     stop_monitors(&state).await;
-    start_monitors(&state, window).await;
+    start_monitors(&state, window.clone()).await;
+
+    if config.control_pipe_enabled {
+        start_control_pipe(&state, window.app_handle());
+    }
     
     Ok(())
 }
 
+/// Whether the first-run setup wizard should be shown
+#[tauri::command]
+fn get_setup_status(state: State<'_, AppState>) -> setup_wizard::SetupStatus {
+    setup_wizard::status(&state.config.lock_or_recover().get_all())
+}
+
+/// Setup: the Steam folder, its accounts and the one that used Steam last
+#[tauri::command]
+fn detect_steam_setup() -> setup_wizard::SteamDetection {
+    setup_wizard::detect_steam()
+}
+
+/// Setup: a Ludusavi that's already on this PC, so the download can be skipped
+#[tauri::command]
+fn find_ludusavi() -> Option<String> {
+    setup_wizard::find_ludusavi().map(|path| path.to_string_lossy().to_string())
+}
+
+/// Setup: download the latest Ludusavi; returns the path of ludusavi.exe
+#[tauri::command]
+async fn download_ludusavi(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<String, String> {
+    ensure_writable(&state)?;
+    let progress = OperationProgress::start(&state, &app, "download_ludusavi", "Downloading Ludusavi".to_string());
+    let path = setup_wizard::download_ludusavi().await?;
+    app_log!("[Setup] Ludusavi saved to {}", path.display());
+    progress.finish("Ludusavi downloaded".to_string());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Setup: free space for a backup folder, or for the proposed default when none is given
+#[tauri::command]
+fn check_backup_folder(path: Option<String>, state: State<'_, AppState>) -> setup_wizard::BackupFolderCheck {
+    let path = path
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| setup_wizard::default_backup_folder().to_string_lossy().to_string());
+    let min_free_mb = state.config.lock_or_recover().get_all().low_disk_space_mb;
+    setup_wizard::check_backup_folder(&path, min_free_mb)
+}
+
+/// Setup: whether Steam accepts the Web API key, checked against the chosen account when there is one
+#[tauri::command]
+async fn validate_steam_api_key(api_key: String, steam_user_id: Option<String>) -> Result<setup_wizard::ApiKeyCheck, String> {
+    let steam_id_64 = steam_user_id
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .map(|account_id| account_id + steam_accounts::STEAM_ID64_BASE);
+    setup_wizard::validate_steam_api_key(&api_key, steam_id_64).await
+}
+
+/// Finish the setup: write the answers into the config and start monitoring with it
+#[tauri::command]
+async fn complete_setup(
+    answers: setup_wizard::SetupAnswers,
+    state: State<'_, AppState>,
+    window: WebviewWindow,
+) -> Result<AppConfig, String> {
+    ensure_writable(&state)?;
+    let config = {
+        let mut cfg = state.config.lock_or_recover();
+        let config = setup_wizard::apply(cfg.get_all(), answers)?;
+        cfg.set_all(config.clone());
+        config
+    };
+    app_log!("[Setup] Setup finished, backing up to {}", config.backup_path);
+
+    stop_monitors(&state).await;
+    start_monitors(&state, window).await;
+    Ok(config)
+}
+
 #[tauri::command]
-async fn browse_file() -> Result<Option<String>, String> {
-    let path = dialog::blocking::FileDialogBuilder::new()
+async fn browse_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let path = app.dialog().file()
         .add_filter("All Files", &["*"])
         .add_filter("Executables", &["exe"])
         .add_filter("Audio", &["mp3", "wav", "ogg", "flac", "aac"])
         .add_filter("Fonts", &["ttf", "otf", "woff", "woff2"])
         .add_filter("Images", &["png", "jpg", "jpeg", "gif", "bmp", "svg", "ico"])
-        .pick_file();
+        .blocking_pick_file();
 
-    Ok(path.map(|p| p.to_string_lossy().to_string()))
+    Ok(path.and_then(|p| p.into_path().ok()).map(|p| p.to_string_lossy().to_string()))
 }
 
 #[tauri::command]
-async fn browse_folder() -> Result<Option<String>, String> {
-    let path = dialog::blocking::FileDialogBuilder::new()
-        .pick_folder();
+async fn browse_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let path = app.dialog().file()
+        .blocking_pick_folder();
     
-    Ok(path.map(|p| p.to_string_lossy().to_string()))
+    Ok(path.and_then(|p| p.into_path().ok()).map(|p| p.to_string_lossy().to_string()))
 }
 
 #[tauri::command]
@@ -102,43 +393,105 @@ async fn test_ludusavi(path: String) -> Result<serde_json::Value, String> {
 
 #[tauri::command]
 async fn get_ludusavi_manifest(state: State<'_, AppState>) -> Result<Vec<String>, String> {
-    let (ludusavi_path, backup_path) = {
-        let config = state.config.lock().unwrap();
+    let (ludusavi_path, backup_path, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
         let cfg = config.get_all();
 
         if cfg.ludusavi_path.is_empty() {
             return Err("Ludusavi path not configured".to_string());
         }
 
-        (cfg.ludusavi_path, cfg.backup_path)
+        (cfg.ludusavi_path, cfg.backup_path, cfg.ludusavi_timeout_secs)
     };
 
-    let manager = LudusaviManager::new(ludusavi_path, backup_path);
+    let manager = LudusaviManager::new(ludusavi_path, backup_path).with_timeout(ludusavi_timeout);
     manager.get_manifest_games().await
 }
 
+fn configured_ludusavi_path(state: &AppState) -> Result<String, String> {
+    let ludusavi_path = state.config.lock_or_recover().get_all().ludusavi_path;
+    if ludusavi_path.is_empty() {
+        return Err("Ludusavi path not configured".to_string());
+    }
+    Ok(ludusavi_path)
+}
+
+/// Roots and custom games from Ludusavi's own settings
+#[tauri::command]
+async fn get_ludusavi_customization(state: State<'_, AppState>) -> Result<LudusaviCustomization, String> {
+    ludusavi_config::read(&configured_ludusavi_path(&state)?)
+}
+
+/// Add or update a custom game in Ludusavi's settings, e.g. for a game Ludusavi couldn't find.
+/// With `backup_now` the game is backed up right after, so a game that was "not found" gets its backup.
+#[tauri::command]
+async fn save_ludusavi_custom_game(
+    game: LudusaviCustomGame,
+    backup_now: Option<bool>,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+    ludusavi_config::save_custom_game(&configured_ludusavi_path(&state)?, &game)?;
+
+    if backup_now.unwrap_or(false) && !state.running_backups.lock_or_recover().contains_key(&game.name) {
+        let app_id = find_app_id_by_name(&state, &game.name);
+        handle_game_backup(game.name, app_id, &state, app).await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn remove_ludusavi_custom_game(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    ludusavi_config::remove_custom_game(&configured_ludusavi_path(&state)?, &name)
+}
+
 #[tauri::command]
-async fn get_all_achievements(state: State<'_, AppState>) -> Result<Vec<GameAchievementSummary>, String> {
+async fn set_ludusavi_roots(roots: Vec<LudusaviRoot>, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    ludusavi_config::set_roots(&configured_ludusavi_path(&state)?, &roots)
+}
+
+#[tauri::command]
+async fn get_all_achievements(
+    status: Option<Vec<GameStatus>>,
+    favorites_only: Option<bool>,
+    merged: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GameAchievementSummary>, String> {
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
         }
     };
 
-    match db {
-        Some(db) => db.get_all_games(),
-        None => Err("Achievement database not initialized".to_string()),
+    // Merged view: one entry per game across all its sources
+    let mut games = match db {
+        Some(db) if merged.unwrap_or(false) => db.get_all_games_merged()?,
+        Some(db) => db.get_all_games()?,
+        None => return Err("Achievement database not initialized".to_string()),
+    };
+
+    // Optional status filter, e.g. only "playing" and "backlog"
+    if let Some(statuses) = status.filter(|s| !s.is_empty()) {
+        games.retain(|game| statuses.contains(&game.status));
+    }
+    // Pinned shelf
+    if favorites_only.unwrap_or(false) {
+        games.retain(|game| game.favorite);
     }
+    Ok(games)
 }
 
 #[tauri::command]
 async fn get_game_achievements(app_id: u32, state: State<'_, AppState>) -> Result<Vec<Achievement>, String> {
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -151,6 +504,23 @@ async fn get_game_achievements(app_id: u32, state: State<'_, AppState>) -> Resul
     }
 }
 
+/// A game's achievements with each one listed once, taking the best unlock state any source reports
+#[tauri::command]
+async fn get_merged_game_achievements(app_id: u32, state: State<'_, AppState>) -> Result<Vec<MergedAchievement>, String> {
+    let db = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        match &*path_guard {
+            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+            None => None,
+        }
+    };
+
+    match db {
+        Some(db) => Ok(achievements::merge_sources(db.get_game_achievements(app_id)?)),
+        None => Err("Achievement database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 async fn update_achievement_status(
     achievement_id: i64,
@@ -158,9 +528,11 @@ async fn update_achievement_status(
     unlock_time: Option<i64>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_writable(&state)?;
+
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -174,12 +546,14 @@ async fn update_achievement_status(
 }
 
 #[tauri::command]
-async fn sync_achievements(state: State<'_, AppState>) -> Result<String, String> {
+async fn sync_achievements(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<String, String> {
+    ensure_writable(&state)?;
     println!("Starting achievement synchronization...");
+    let mut progress = OperationProgress::start(&state, &app, "sync_achievements", "Reading the Steam library".to_string());
 
     // Get API key, user ID, and Steam64 ID from config
     let (api_key, steam_user_id, steam_id_64) = {
-        let config = state.config.lock().unwrap();
+        let config = state.config.lock_or_recover();
         let cfg = config.get_all();
         (cfg.steam_api_key, cfg.steam_user_id, cfg.steam_id_64)
     };
@@ -190,11 +564,12 @@ async fn sync_achievements(state: State<'_, AppState>) -> Result<String, String>
 
     // Initialize Steam achievement client (for API)
     let steam_client = SteamAchievementClient::new(api_key, steam_id_64.clone())
-        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?;
+        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?
+        .with_steam_id_overrides(steam_id_overrides(&state));
 
     // Get database path for opening connections as needed
     let db_path = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         path_guard.clone()
     };
 
@@ -205,9 +580,7 @@ async fn sync_achievements(state: State<'_, AppState>) -> Result<String, String>
 
     // Get all installed Steam games
     let library_folders = get_steam_library_folders()?;
-    let mut total_achievements = 0;
-    let mut games_scanned = 0;
-
+    let mut installed_games: Vec<(u32, String)> = Vec::new();
     for library_path in library_folders {
         let steamapps_path = library_path.join("steamapps");
         if !steamapps_path.exists() {
@@ -217,127 +590,245 @@ async fn sync_achievements(state: State<'_, AppState>) -> Result<String, String>
         // Read all appmanifest files
         if let Ok(entries) = std::fs::read_dir(&steamapps_path) {
             for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if let Some(filename) = path.file_name() {
-                    let filename_str = filename.to_string_lossy();
-                    if filename_str.starts_with("appmanifest_") && filename_str.ends_with(".acf") {
-                        if let Some((app_id, game_name)) = parse_appmanifest_basic(&path) {
-                            println!("Scanning achievements for: {} ({})", game_name, app_id);
-
-                            // PHASE 1: Scan all sources and collect results
-                            let mut source_results: Vec<(&str, usize)> = Vec::new();
-
-                            // PRIORITY 1: Try Online-fix
-                            if let Some(ref scanner) = local_scanner {
-                                match scanner.scan_onlinefix_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
-                                    Ok(count) => {
-                                        println!("  ℹ Online-fix: {} unlocked achievements", count);
-                                        source_results.push(("Online-fix", count));
-                                    }
-                                    Err(e) => {
-                                        if !e.contains("No achievements found") && !e.contains("does not exist") {
-                                            println!("  ⚠ Online-fix scan error: {}", e);
-                                        }
-                                    }
-                                }
-                            }
+                let filename_str = entry.file_name().to_string_lossy().to_string();
+                if filename_str.starts_with("appmanifest_") && filename_str.ends_with(".acf") {
+                    if let Some(game) = parse_appmanifest_basic(&entry.path()) {
+                        installed_games.push(game);
+                    }
+                }
+            }
+        }
+    }
 
-                            // PRIORITY 2: Try Steamtools (librarycache)
-                            if let Some(ref scanner) = local_scanner {
-                                match scanner.scan_steam_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
-                                    Ok(count) => {
-                                        println!("  ℹ Steamtools: {} unlocked achievements", count);
-                                        source_results.push(("Steamtools", count));
-                                    }
-                                    Err(e) => {
-                                        println!("  ⚠ Steamtools scan error: {}", e);
-                                    }
-                                }
-                            }
+    // Favorites are scanned first so they are current as early as possible
+    let favorites: Vec<u32> = AchievementDatabase::new(db_path.clone())
+        .and_then(|db| db.get_favorites())
+        .map(|favorites| favorites.into_iter().map(|(app_id, _)| app_id).collect())
+        .unwrap_or_default();
+    installed_games.sort_by_key(|(app_id, _)| !favorites.contains(app_id));
 
-                            // PRIORITY 3: Try Goldberg
-                            if let Some(ref scanner) = local_scanner {
-                                match scanner.scan_goldberg_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
-                                    Ok(count) => {
-                                        println!("  ℹ Goldberg: {} unlocked achievements", count);
-                                        source_results.push(("Goldberg", count));
-                                    }
-                                    Err(_) => {}
-                                }
-                            }
+    let (plugin_sources, _) = achievement_sources::load_plugins();
+    let mut total_achievements = 0;
+    let mut games_scanned = 0;
+    let games_total = installed_games.len() as u64;
 
-                            // PRIORITY 4: Try Steam API
-                            let achievements_result = steam_client.scan_achievements_for_game(app_id, &game_name).await;
-                            match achievements_result {
-                                Ok(achievements) if !achievements.is_empty() => {
-                                    if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
-                                        for ach in &achievements {
-                                            let _ = db.insert_or_update_achievement(ach);
-                                        }
-                                        let unlocked = achievements.iter().filter(|a| a.achieved).count();
-                                        println!("  ℹ Steam Web API: {} unlocked achievements", unlocked);
-                                        source_results.push(("Steam Web API", unlocked));
-                                    }
-                                }
-                                Ok(_) => {}
-                                Err(e) => {
-                                    if !e.contains("No achievements found") {
-                                        println!("  ⚠ Error scanning {}: {}", game_name, e);
-                                    }
-                                }
-                            }
+    for (index, (app_id, game_name)) in installed_games.into_iter().enumerate() {
+        app_log!("Scanning achievements for: {} ({})", game_name, app_id);
+        progress.update("scanning", index as u64, Some(games_total), game_name.clone());
+
+        // PHASE 1: Scan all sources and collect results
+        let mut source_results: Vec<(&str, usize)> = Vec::new();
+
+        // PRIORITY 1: Try Online-fix
+        if let Some(ref scanner) = local_scanner {
+            match scanner.scan_onlinefix_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
+                Ok(count) => {
+                    app_log!("  ℹ Online-fix: {} unlocked achievements", count);
+                    source_results.push(("Online-fix", count));
+                }
+                Err(e) => {
+                    if !e.contains("No achievements found") && !e.contains("does not exist") {
+                        app_log!("  ⚠ Online-fix scan error: {}", e);
+                    }
+                }
+            }
+        }
+
+        // PRIORITY 2: Try Steamtools (librarycache)
+        if let Some(ref scanner) = local_scanner {
+            match scanner.scan_steam_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
+                Ok(count) => {
+                    app_log!("  ℹ Steamtools: {} unlocked achievements", count);
+                    source_results.push(("Steamtools", count));
+                }
+                Err(e) => {
+                    app_log!("  ⚠ Steamtools scan error: {}", e);
+                }
+            }
+        }
+
+        // PRIORITY 3: Try Goldberg
+        if let Some(ref scanner) = local_scanner {
+            match scanner.scan_goldberg_achievements(app_id, &game_name, db_path.clone(), &steam_client).await {
+                Ok(count) => {
+                    app_log!("  ℹ Goldberg: {} unlocked achievements", count);
+                    source_results.push(("Goldberg", count));
+                }
+                Err(_) => {}
+            }
+        }
+
+        // Plugin sources
+        for source in &plugin_sources {
+            match achievement_sources::scan_source(source.as_ref(), app_id, &game_name, db_path.clone(), &steam_client).await {
+                Ok(count) => {
+                    app_log!("  ℹ {}: {} unlocked achievements", source.name(), count);
+                    source_results.push((source.name(), count));
+                }
+                Err(e) => {
+                    if !e.contains("No ") {
+                        app_log!("  ⚠ {} scan error: {}", source.name(), e);
+                    }
+                }
+            }
+        }
 
-                            // PHASE 2: Choose the best source if we found any
-                            if !source_results.is_empty() {
-                                let best_source = source_results.iter().max_by_key(|(_, count)| count).unwrap();
-                                println!("  ✓ Choosing {} with {} unlocked achievements", best_source.0, best_source.1);
-
-                                // PHASE 3: Delete all achievements for this game
-                                if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
-                                    let _ = db.delete_game_achievements(app_id);
-                                }
-
-                                // PHASE 4: Rescan only the winning source
-                                match best_source.0 {
-                                    "Online-fix" => {
-                                        if let Some(ref scanner) = local_scanner {
-                                            let _ = scanner.scan_onlinefix_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
-                                        }
-                                    }
-                                    "Steamtools" => {
-                                        if let Some(ref scanner) = local_scanner {
-                                            let _ = scanner.scan_steam_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
-                                        }
-                                    }
-                                    "Goldberg" => {
-                                        if let Some(ref scanner) = local_scanner {
-                                            let _ = scanner.scan_goldberg_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
-                                        }
-                                    }
-                                    "Steam Web API" => {
-                                        // Rescan and insert
-                                        if let Ok(achievements) = steam_client.scan_achievements_for_game(app_id, &game_name).await {
-                                            if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
-                                                for ach in &achievements {
-                                                    let _ = db.insert_or_update_achievement(ach);
-                                                }
-                                            }
-                                        }
-                                    }
-                                    _ => {}
-                                }
-
-                                total_achievements += best_source.1;
-                                games_scanned += 1;
+        // PRIORITY 4: Try Steam API
+        let achievements_result = steam_client.scan_achievements_for_game(app_id, &game_name).await;
+        match achievements_result {
+            Ok(achievements) if !achievements.is_empty() => {
+                if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
+                    for ach in &achievements {
+                        let _ = db.insert_or_update_achievement(ach);
+                    }
+                    let unlocked = achievements.iter().filter(|a| a.achieved).count();
+                    app_log!("  ℹ Steam Web API: {} unlocked achievements", unlocked);
+                    source_results.push(("Steam Web API", unlocked));
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                if !e.contains("No achievements found") {
+                    app_log!("  ⚠ Error scanning {}: {}", game_name, e);
+                }
+            }
+        }
+
+        // PHASE 2: Choose the best source if we found any
+        if !source_results.is_empty() {
+            let best_source = source_results.iter().max_by_key(|(_, count)| count).unwrap();
+            app_log!("  ✓ Choosing {} with {} unlocked achievements", best_source.0, best_source.1);
+
+            // PHASE 3: Delete all achievements for this game
+            if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
+                let _ = db.delete_game_achievements(app_id);
+            }
+
+            // PHASE 4: Rescan only the winning source
+            match best_source.0 {
+                "Online-fix" => {
+                    if let Some(ref scanner) = local_scanner {
+                        let _ = scanner.scan_onlinefix_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
+                    }
+                }
+                "Steamtools" => {
+                    if let Some(ref scanner) = local_scanner {
+                        let _ = scanner.scan_steam_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
+                    }
+                }
+                "Goldberg" => {
+                    if let Some(ref scanner) = local_scanner {
+                        let _ = scanner.scan_goldberg_achievements(app_id, &game_name, db_path.clone(), &steam_client).await;
+                    }
+                }
+                "Steam Web API" => {
+                    // Rescan and insert
+                    if let Ok(achievements) = steam_client.scan_achievements_for_game(app_id, &game_name).await {
+                        if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
+                            for ach in &achievements {
+                                let _ = db.insert_or_update_achievement(ach);
                             }
                         }
                     }
                 }
+                plugin_name => {
+                    if let Some(plugin) = plugin_sources.iter().find(|s| s.name() == plugin_name) {
+                        let _ = achievement_sources::scan_source(plugin.as_ref(), app_id, &game_name, db_path.clone(), &steam_client).await;
+                    }
+                }
             }
+
+            total_achievements += best_source.1;
+            games_scanned += 1;
+        }
+    }
+
+    // Local rescans only know this PC's unlocks, merge the other machines' back in
+    progress.update("merging", games_total, Some(games_total), "Merging achievements from other PCs".to_string());
+    if let Err(e) = run_achievement_sync(&state).await {
+        app_log!("[AchievementSync] {}", e);
+    }
+
+    let summary = format!("Scanned {} games, found {} achievements", games_scanned, total_achievements);
+    progress.finish(summary.clone());
+    Ok(summary)
+}
+
+// Merge achievements with the other PCs sharing the configured sync folder
+async fn run_achievement_sync(state: &AppState) -> Result<Option<AchievementSyncResult>, String> {
+    let sync_folder = {
+        let config = state.config.lock_or_recover();
+        config.get_all().achievement_sync_folder
+    };
+    if sync_folder.is_empty() {
+        return Ok(None);
+    }
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or_else(|| "Achievement database not initialized".to_string())?;
+
+    tokio::task::spawn_blocking(move || {
+        let db = AchievementDatabase::new(db_path)?;
+        achievement_sync::sync_with_folder(&db, &PathBuf::from(sync_folder)).map(Some)
+    })
+    .await
+    .map_err(|e| format!("Achievement sync failed: {}", e))?
+}
+
+#[tauri::command]
+fn get_lan_peers(state: State<'_, AppState>) -> Vec<LanPeer> {
+    state.lan_sync.lock_or_recover()
+        .as_ref()
+        .map(|lan| lan.peers())
+        .unwrap_or_default()
+}
+
+async fn push_backup_index(lan: &LanSync, backup_path: String) {
+    let index = tokio::task::spawn_blocking(move || lan_sync::build_backup_index(&backup_path))
+        .await
+        .unwrap_or_default();
+    lan.push(&PeerPayload::BackupIndex(index)).await;
+}
+
+// Send achievements updated since the last successful push to the LAN peers
+async fn push_lan_updates(state: &AppState, pushed_until: &mut i64) {
+    let lan = state.lan_sync.lock_or_recover().clone();
+    let db_path = state.achievement_db_path.lock_or_recover().clone();
+    let (Some(lan), Some(db_path)) = (lan, db_path) else {
+        return;
+    };
+
+    let since = *pushed_until;
+    let changed = tokio::task::spawn_blocking(move || {
+        AchievementDatabase::new(db_path)
+            .and_then(|db| db.get_all_achievements())
+            .map(|all| all.into_iter().filter(|a| a.achieved && a.last_updated > since).collect::<Vec<_>>())
+    }).await;
+
+    let changed = match changed {
+        Ok(Ok(changed)) => changed,
+        Ok(Err(e)) => {
+            app_log!("[LanSync] {}", e);
+            return;
         }
+        Err(_) => return,
+    };
+    if changed.is_empty() {
+        return;
     }
 
-    Ok(format!("Scanned {} games, found {} achievements", games_scanned, total_achievements))
+    let latest = changed.iter().map(|a| a.last_updated).max().unwrap_or(since);
+    // Keep the marker until someone received the update, so a peer that appears later still gets it
+    if lan.push(&PeerPayload::Achievements(changed)).await > 0 {
+        *pushed_until = latest;
+    }
+}
+
+#[tauri::command]
+async fn sync_achievements_across_pcs(state: State<'_, AppState>) -> Result<AchievementSyncResult, String> {
+    ensure_writable(&state)?;
+    run_achievement_sync(&state).await?
+        .ok_or_else(|| "No achievement sync folder configured".to_string())
 }
 
 #[tauri::command]
@@ -350,9 +841,10 @@ async fn add_manual_achievement(
     achieved: bool,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_writable(&state)?;
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -392,7 +884,7 @@ async fn add_manual_achievement(
 async fn export_achievements(state: State<'_, AppState>) -> Result<String, String> {
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -405,14 +897,20 @@ async fn export_achievements(state: State<'_, AppState>) -> Result<String, Strin
     }
 }
 
+/// Export one game's achievements. Without a destination the file goes to Documents\Steam Backup Monitor.
 #[tauri::command]
-async fn export_game_achievements(app_id: u32, game_name: String, state: State<'_, AppState>) -> Result<String, String> {
-    use std::fs;
-    use std::io::Write;
+async fn export_game_achievements(
+    app_id: u32,
+    game_name: String,
+    format: Option<ExportFormat>,
+    destination: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ExportResult, String> {
+    let format = format.unwrap_or_default();
 
     // Get database
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -424,75 +922,114 @@ async fn export_game_achievements(app_id: u32, game_name: String, state: State<'
         None => return Err("Achievement database not initialized".to_string()),
     };
 
-    // Get all achievements for this game
-    let all_achievements = db.get_game_achievements(app_id)?;
-
-    // Filter only unlocked achievements
-    let unlocked: Vec<_> = all_achievements.iter()
-        .filter(|a| a.achieved)
-        .collect();
-
-    // Save count before consuming iterator
-    let unlocked_count = unlocked.len();
-
-    // Convert to Steam API format
-    // Format: {"<achievement_id>": {"UnlockTime": <timestamp>}}
-    let mut steam_format = serde_json::Map::new();
-    for achievement in unlocked {
-        let mut achievement_data = serde_json::Map::new();
-        achievement_data.insert(
-            "UnlockTime".to_string(),
-            serde_json::Value::Number(
-                serde_json::Number::from(achievement.unlock_time.unwrap_or(0))
-            )
-        );
-        steam_format.insert(
-            achievement.achievement_id.clone(),
-            serde_json::Value::Object(achievement_data)
-        );
-    }
-
-    let json_string = serde_json::to_string_pretty(&steam_format)
-        .map_err(|e| format!("Failed to serialize to JSON: {}", e))?;
-
-    // Get Documents folder
-    let documents_dir = match dirs::document_dir() {
-        Some(dir) => dir,
-        None => return Err("Could not find Documents folder".to_string()),
+    let achievements = db.get_game_achievements(app_id)?;
+    let (contents, achievements_written) = achievement_export::render(format, &achievements)?;
+    let unlocked_written = match format {
+        ExportFormat::SteamUnlockTime | ExportFormat::Goldberg => achievements_written,
+        ExportFormat::Csv | ExportFormat::Native => achievements.iter().filter(|a| a.achieved).count(),
     };
 
-    // Create Steam Backup Monitor folder
-    let export_dir = documents_dir.join("Steam Backup Monitor");
-    if !export_dir.exists() {
-        fs::create_dir_all(&export_dir)
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
-    }
+    let file_path = match destination {
+        Some(destination) => {
+            let path = PathBuf::from(destination);
+            std::fs::write(&path, contents.as_bytes()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            path
+        }
+        // The Steam format is what restores read, so it goes to the game's backup folder
+        None if format == ExportFormat::SteamUnlockTime => {
+            let (path, _) = save_achievement_backup(app_id, &game_name, &achievements)?;
+            report_output_fallback(&state);
+            path
+        }
+        None => {
+            // Unicode-safe name with the app id, so games with similar names never share an export
+            let file_stem = filenames::claim_stem(&user_folders::output_root().join("Steam Backup Monitor"), &game_name, Some(app_id));
+
+            // Write to Documents\Steam Backup Monitor (or the app data fallback), overwriting any previous export
+            let relative_path = Path::new("Steam Backup Monitor").join(format!("{}.{}", file_stem, format.extension()));
+            let path = user_folders::write_file(&relative_path, contents.as_bytes())?;
+            report_output_fallback(&state);
+            path
+        }
+    };
 
-    // Sanitize game name for filename
-    let safe_game_name: String = game_name.chars()
-        .map(|c| match c {
-            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c
-        })
-        .collect();
+    app_log!("Exported {} achievements of {} as {} to {}", achievements_written, game_name, format.label(), file_path.display());
+    Ok(ExportResult {
+        path: file_path.to_string_lossy().to_string(),
+        format,
+        achievements_written,
+        unlocked_written,
+        bytes_written: contents.len(),
+    })
+}
 
-    // Create file path
-    let file_path = export_dir.join(format!("{}.json", safe_game_name));
+/// Ask where to save an export, suggesting a file name for the game and format
+#[tauri::command]
+async fn choose_export_destination(game_name: String, format: ExportFormat, app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file_name = format!("{}.{}", filenames::sanitize_component(&game_name), format.extension());
+    let path = app.dialog().file()
+        .set_file_name(file_name)
+        .add_filter(format.label(), &[format.extension()])
+        .blocking_save_file();
+
+    Ok(path.and_then(|p| p.into_path().ok()).map(|p| p.to_string_lossy().to_string()))
+}
 
-    // Write to file (overwrites if exists)
-    let mut file = fs::File::create(&file_path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+#[derive(Debug, Serialize)]
+struct TimelineExportResult {
+    path: String,
+    events_written: usize,
+    bytes_written: usize,
+}
 
-    file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+/// Export sessions, backups and unlocks as one chronological timeline, for a "YYYY-MM" month and/or
+/// one game. Without a destination the file goes to Documents\Steam Backup Monitor.
+#[tauri::command]
+async fn export_activity_timeline(
+    format: activity_timeline::TimelineFormat,
+    month: Option<String>,
+    game_name: Option<String>,
+    destination: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TimelineExportResult, String> {
+    let (since, until) = match month.as_deref() {
+        Some(month) => activity_timeline::month_range(month)?,
+        None => (i64::MIN, i64::MAX),
+    };
+    let db_path = state.achievement_db_path.lock_or_recover().clone().ok_or("Achievement database not initialized")?;
+    let events = activity_timeline::collect(&AchievementDatabase::new(db_path)?, since, until, game_name.as_deref())?;
+    let contents = activity_timeline::render(format, &events)?;
+
+    let file_path = match destination {
+        Some(destination) => {
+            let path = PathBuf::from(destination);
+            std::fs::write(&path, contents.as_bytes()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            path
+        }
+        None => {
+            let mut scope = month.clone().unwrap_or_else(|| "all time".to_string());
+            if let Some(ref game_name) = game_name {
+                scope = format!("{} {}", scope, game_name);
+            }
+            let file_name = format!("{}.{}", filenames::sanitize_component(&format!("Activity {}", scope)), format.extension());
+            let path = user_folders::write_file(&Path::new("Steam Backup Monitor").join(file_name), contents.as_bytes())?;
+            report_output_fallback(&state);
+            path
+        }
+    };
 
-    Ok(format!("Exported {} unlocked achievements to: {}", unlocked_count, file_path.display()))
+    app_log!("[Timeline] Exported {} events to {}", events.len(), file_path.display());
+    Ok(TimelineExportResult {
+        path: file_path.to_string_lossy().to_string(),
+        events_written: events.len(),
+        bytes_written: contents.len(),
+    })
 }
 
 #[tauri::command]
 async fn search_steam_games(query: String, state: State<'_, AppState>) -> Result<Vec<SteamGameSearchResult>, String> {
     let (api_key, steam_id_64) = {
-        let config = state.config.lock().unwrap();
+        let config = state.config.lock_or_recover();
         let cfg = config.get_all();
         (cfg.steam_api_key, cfg.steam_id_64)
     };
@@ -520,14 +1057,14 @@ async fn check_game_sources(
 
     // Get API key, user ID, and Steam64 ID from config
     let (api_key, steam_user_id, steam_id_64) = {
-        let config = state.config.lock().unwrap();
+        let config = state.config.lock_or_recover();
         let cfg = config.get_all();
         (cfg.steam_api_key, cfg.steam_user_id, cfg.steam_id_64)
     };
 
     // Get database path
     let db_path = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         path_guard.clone()
     };
 
@@ -538,7 +1075,8 @@ async fn check_game_sources(
 
     // Create Steam API client
     let steam_client = SteamAchievementClient::new(api_key.clone(), steam_id_64.clone())
-        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?;
+        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?
+        .with_steam_id_overrides(steam_id_overrides(&state));
 
     let steam_path = PathBuf::from(r"C:\Program Files (x86)\Steam");
 
@@ -628,10 +1166,37 @@ async fn check_game_sources(
         }
     }
 
-    // PRIORITY 4: Try Steam Web API
-    println!("  Fetching from Steam Web API...");
-    match steam_client.scan_achievements_for_game(app_id, &game_name).await {
-        Ok(achievements) if !achievements.is_empty() => {
+    // Sources added through plugin manifests
+    let (plugin_sources, _) = achievement_sources::load_plugins();
+    for source in &plugin_sources {
+        match achievement_sources::scan_source(source.as_ref(), app_id, &game_name, db_path.clone(), &steam_client).await {
+            Ok(count) => {
+                if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
+                    if let Ok(achievements) = db.get_game_achievements(app_id) {
+                        let total = achievements.len();
+                        app_log!("  ✓ {}: {} unlocked / {} total", source.name(), count, total);
+                        source_options.push(SourceOption {
+                            name: source.name().to_string(),
+                            unlocked_count: count,
+                            total_count: total,
+                        });
+                    }
+                    // Clear the database after checking
+                    let _ = db.delete_game_achievements(app_id);
+                }
+            }
+            Err(e) => {
+                if !e.contains("No ") {
+                    app_log!("  ⚠ {} scan error: {}", source.name(), e);
+                }
+            }
+        }
+    }
+
+    // PRIORITY 4: Try Steam Web API
+    app_log!("  Fetching from Steam Web API...");
+    match steam_client.scan_achievements_for_game(app_id, &game_name).await {
+        Ok(achievements) if !achievements.is_empty() => {
             if let Ok(db) = AchievementDatabase::new(db_path.clone()) {
                 for ach in &achievements {
                     let _ = db.insert_or_update_achievement(ach);
@@ -671,18 +1236,19 @@ async fn add_game_from_source(
     source: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
+    ensure_writable(&state)?;
     println!("Adding {} (app_id: {}) from {}...", game_name, app_id, source);
 
     // Get API key, user ID, and Steam64 ID from config
     let (api_key, steam_user_id, steam_id_64) = {
-        let config = state.config.lock().unwrap();
+        let config = state.config.lock_or_recover();
         let cfg = config.get_all();
         (cfg.steam_api_key, cfg.steam_user_id, cfg.steam_id_64)
     };
 
     // Get database path
     let db_path = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         path_guard.clone()
     };
 
@@ -693,7 +1259,8 @@ async fn add_game_from_source(
 
     // Create Steam API client
     let steam_client = SteamAchievementClient::new(api_key.clone(), steam_id_64.clone())
-        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?;
+        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?
+        .with_steam_id_overrides(steam_id_overrides(&state));
 
     let steam_path = PathBuf::from(r"C:\Program Files (x86)\Steam");
 
@@ -740,7 +1307,13 @@ async fn add_game_from_source(
                 Err(e) => return Err(format!("Failed to scan Steam API: {}", e)),
             }
         }
-        _ => return Err(format!("Unknown source: {}", source)),
+        plugin_name => {
+            let (plugin_sources, _) = achievement_sources::load_plugins();
+            match plugin_sources.iter().find(|s| s.name() == plugin_name) {
+                Some(plugin) => achievement_sources::scan_source(plugin.as_ref(), app_id, &game_name, db_path.clone(), &steam_client).await?,
+                None => return Err(format!("Unknown source: {}", source)),
+            }
+        }
     };
 
     Ok(format!("Added {} with {} unlocked achievements (from {})", game_name, unlocked_count, source))
@@ -751,9 +1324,11 @@ async fn remove_game_from_tracking(
     app_id: u32,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
+    ensure_writable(&state)?;
+
     // Open database connection
     let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
+        let path_guard = state.achievement_db_path.lock_or_recover();
         match &*path_guard {
             Some(path) => AchievementDatabase::new(path.clone()).ok(),
             None => None,
@@ -769,669 +1344,3587 @@ async fn remove_game_from_tracking(
     }
 }
 
+/// Games stored under more than one source, for the cleanup view
 #[tauri::command]
-async fn get_all_exclusions(state: State<'_, AppState>) -> Result<Vec<achievements::Exclusion>, String> {
-    let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
-        match &*path_guard {
-            Some(path) => AchievementDatabase::new(path.clone()).ok(),
-            None => None,
-        }
-    };
+async fn find_duplicate_games(state: State<'_, AppState>) -> Result<Vec<DuplicateGame>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.find_duplicate_games()
+}
 
-    match db {
-        Some(db) => db.get_all_exclusions(),
-        None => Err("Achievement database not initialized".to_string()),
-    }
+/// Merge a game's other sources into `keep_source` and remove them
+#[tauri::command]
+async fn consolidate_game_sources(app_id: u32, keep_source: String, state: State<'_, AppState>) -> Result<usize, String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let removed = AchievementDatabase::new(db_path)?.consolidate_game_sources(app_id, &keep_source)?;
+    app_log!("[Achievements] Consolidated app {} into {} ({} redundant rows removed)", app_id, keep_source, removed);
+    Ok(removed)
 }
 
 #[tauri::command]
-async fn add_exclusion(
-    app_id: u32,
-    name: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
-        match &*path_guard {
-            Some(path) => AchievementDatabase::new(path.clone()).ok(),
-            None => None,
-        }
-    };
+async fn delete_game_source(app_id: u32, source: String, state: State<'_, AppState>) -> Result<usize, String> {
+    ensure_writable(&state)?;
 
-    match db {
-        Some(db) => {
-            db.add_exclusion(app_id, name)?;
-            // No need to restart monitors - they check exclusions dynamically on each scan
-            println!("Added app_id {} to exclusions", app_id);
-            Ok(())
-        }
-        None => Err("Achievement database not initialized".to_string()),
-    }
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.delete_game_source(app_id, &source)
+}
+
+// Completion of the base game and each DLC, counting an achievement unlocked in any source
+fn achievement_dlc_groups(db: &AchievementDatabase, app_id: u32) -> Result<Vec<DlcGroup>, String> {
+    let achievements: Vec<Achievement> = achievements::merge_sources(db.get_game_achievements(app_id)?)
+        .into_iter()
+        .map(|merged| merged.achievement)
+        .collect();
+    Ok(achievement_dlc::group(&achievements, &db.get_achievement_dlc(app_id)?, &db.get_game_dlc(app_id)?))
 }
 
+/// Look up a game's DLCs on the Steam store and work out which achievements belong to them
 #[tauri::command]
-async fn remove_exclusion(
-    app_id: u32,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
-        match &*path_guard {
-            Some(path) => AchievementDatabase::new(path.clone()).ok(),
-            None => None,
-        }
+async fn detect_achievement_dlc(app_id: u32, state: State<'_, AppState>) -> Result<Vec<DlcGroup>, String> {
+    ensure_writable(&state)?;
+
+    let (api_key, steam_id_64) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.steam_api_key, cfg.steam_id_64)
     };
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
 
-    match db {
-        Some(db) => {
-            db.remove_exclusion(app_id)?;
-            // No need to restart monitors - they check exclusions dynamically on each scan
-            println!("Removed app_id {} from exclusions", app_id);
-            Ok(())
-        }
-        None => Err("Achievement database not initialized".to_string()),
-    }
+    let steam_client = SteamAchievementClient::new(api_key, steam_id_64)
+        .map_err(|e| format!("Failed to initialize Steam client: {}", e))?;
+    let dlcs = steam_client.get_dlc_list(app_id).await?;
+
+    let db = AchievementDatabase::new(db_path)?;
+    let achievements = db.get_game_achievements(app_id)?;
+    let game_name = achievements.first().map(|a| a.game_name.clone()).unwrap_or_default();
+    let assignments = achievement_dlc::classify(&achievements, &game_name, &dlcs);
+    app_log!("[DLC] {} has {} DLCs, {} achievements belong to them", game_name, dlcs.len(), assignments.len());
+
+    db.save_game_dlc(app_id, &dlcs, &assignments)?;
+    achievement_dlc_groups(&db, app_id)
 }
 
 #[tauri::command]
-async fn fetch_achievement_icon(url: String) -> Result<String, String> {
-    use base64::{Engine as _, engine::general_purpose};
-    use std::time::Duration;
-
-    // Create HTTP client with longer timeout
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .connect_timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
-
-    // Fetch the image from Steam CDN with retries
-    let mut last_error = String::new();
-    for attempt in 1..=3 {
-        match client.get(&url).send().await {
-            Ok(response) => {
-                // Get the image bytes
-                let bytes = response
-                    .bytes()
-                    .await
-                    .map_err(|e| format!("Failed to read icon bytes: {}", e))?;
-
-                // Convert to base64
-                let base64 = general_purpose::STANDARD.encode(&bytes);
-
-                // Determine MIME type from URL extension
-                let mime_type = if url.ends_with(".jpg") || url.ends_with(".jpeg") {
-                    "image/jpeg"
-                } else if url.ends_with(".png") {
-                    "image/png"
-                } else {
-                    "image/jpeg" // default
-                };
+async fn get_achievement_dlc_groups(app_id: u32, state: State<'_, AppState>) -> Result<Vec<DlcGroup>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    achievement_dlc_groups(&AchievementDatabase::new(db_path)?, app_id)
+}
 
-                // Return as data URL
-                return Ok(format!("data:{};base64,{}", mime_type, base64));
-            }
-            Err(e) => {
-                last_error = format!("Attempt {}/3 failed: {}", attempt, e);
-                if attempt < 3 {
-                    // Wait before retrying
-                    tokio::time::sleep(Duration::from_millis(500)).await;
-                }
-            }
-        }
-    }
+/// Move an achievement to a DLC, or to the base game with `dlc_app_id` None
+#[tauri::command]
+async fn set_achievement_dlc(app_id: u32, achievement_id: String, dlc_app_id: Option<u32>, state: State<'_, AppState>) -> Result<Vec<DlcGroup>, String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+    db.set_achievement_dlc(app_id, &achievement_id, dlc_app_id)?;
+    achievement_dlc_groups(&db, app_id)
+}
 
-    Err(format!("Failed to fetch icon after 3 attempts: {}", last_error))
+/// A game's locked achievements in a suggested completion order: missable first, then easiest first
+#[tauri::command]
+async fn get_completion_order(app_id: u32, state: State<'_, AppState>) -> Result<Vec<SuggestedAchievement>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+
+    // Locked means locked in every source
+    let achievements = achievements::merge_sources(db.get_game_achievements(app_id)?)
+        .into_iter()
+        .map(|merged| merged.achievement)
+        .collect();
+    Ok(completion_order::suggest(achievements, &db.get_achievement_tags(app_id)?))
 }
 
 #[tauri::command]
-fn play_windows_notification_sound() -> Result<(), String> {
-    use windows::Win32::Media::Audio::{PlaySoundA, SND_ALIAS, SND_ASYNC};
-    use windows::core::PCSTR;
-    use std::ffi::CString;
+async fn get_achievement_tags(app_id: u32, state: State<'_, AppState>) -> Result<HashMap<String, Vec<String>>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_achievement_tags(app_id)
+}
 
-    std::thread::spawn(move || {
-        unsafe {
-            let sound_alias = CString::new("SystemNotification").unwrap_or_default();
-            let _ = PlaySoundA(
-                PCSTR(sound_alias.as_ptr() as *const u8),
-                None,
-                SND_ALIAS | SND_ASYNC,
-            );
-        }
-    });
+#[tauri::command]
+async fn set_achievement_tags(app_id: u32, achievement_id: String, tags: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
 
-    Ok(())
+    let tags: Vec<String> = tags.iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.set_achievement_tags(app_id, &achievement_id, &tags)
 }
 
+/// Look through the game's Steam screenshots for ones taken around each unlock
 #[tauri::command]
-fn debug_log(message: String) {
-    println!("[OVERLAY DEBUG] {}", message);
+async fn scan_achievement_screenshots(app_id: u32, state: State<'_, AppState>) -> Result<usize, String> {
+    scan_screenshots(&state, app_id)
 }
 
+/// Screenshots matched to the game's achievements, with the URLs to show them
 #[tauri::command]
-fn check_backup_exists(game_name: String) -> Result<Option<String>, String> {
-    // Get Documents folder
-    let documents_dir = match dirs::document_dir() {
-        Some(dir) => dir,
-        None => return Err("Could not find Documents folder".to_string()),
-    };
-
-    // Check Steam Backup Monitor folder
-    let export_dir = documents_dir.join("Steam Backup Monitor");
-    if !export_dir.exists() {
-        return Ok(None);
+async fn get_achievement_screenshots(app_id: u32, state: State<'_, AppState>) -> Result<Vec<AchievementScreenshot>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let mut screenshots = AchievementDatabase::new(db_path)?.get_achievement_screenshots(app_id)?;
+    for screenshot in &mut screenshots {
+        screenshot.url = achievement_screenshots::screenshot_url(screenshot.id, false);
+        screenshot.full_url = achievement_screenshots::screenshot_url(screenshot.id, true);
     }
+    Ok(screenshots)
+}
 
-    // Sanitize game name for filename
-    let safe_game_name: String = game_name.chars()
-        .map(|c| match c {
-            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c
-        })
-        .collect();
-
-    // Check if backup file exists
-    let file_path = export_dir.join(format!("{}.json", safe_game_name));
-    if file_path.exists() {
-        Ok(Some(file_path.to_string_lossy().to_string()))
-    } else {
-        Ok(None)
+fn scan_screenshots(state: &AppState, app_id: u32) -> Result<usize, String> {
+    let steam_user_id = state.config.lock_or_recover().get_all().steam_user_id;
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+
+    let steam_path = SteamMonitor::find_steam_path()?;
+    let userdata = steam_accounts::userdata_dir(&steam_path, steam_user_id.as_deref())?;
+    let screenshots = achievement_screenshots::list_screenshots(&achievement_screenshots::screenshots_dir(&userdata, app_id));
+
+    let db = AchievementDatabase::new(db_path)?;
+    let achievements = db.get_game_achievements(app_id)?;
+    let matches = achievement_screenshots::match_unlocks(&achievements, &screenshots, achievement_screenshots::MATCH_WINDOW_SECS);
+    let count = db.replace_achievement_screenshots(app_id, &matches)?;
+    if count > 0 {
+        app_log!("[Screenshots] Matched {} screenshot(s) to achievements of app {}", count, app_id);
     }
+    Ok(count)
 }
 
+/// Sort and filter the game's achievement list was last shown with
 #[tauri::command]
-async fn restore_from_backup(
-    app_id: u32,
-    game_name: String,
-    backup_path: String,
-    state: State<'_, AppState>
-) -> Result<usize, String> {
-    use std::fs;
-
-    // Read backup file
-    let backup_content = fs::read_to_string(&backup_path)
-        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+async fn get_achievement_view_prefs(app_id: u32, state: State<'_, AppState>) -> Result<achievements::AchievementViewPrefs, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_achievement_view_prefs(app_id)
+}
 
-    // Parse JSON (Steam API format: {"achievement_id": {"UnlockTime": timestamp}})
-    let backup_data: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&backup_content)
-        .map_err(|e| format!("Failed to parse backup file: {}", e))?;
+#[tauri::command]
+async fn set_achievement_view_prefs(app_id: u32, prefs: achievements::AchievementViewPrefs, state: State<'_, AppState>) -> Result<(), String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.set_achievement_view_prefs(app_id, &prefs)
+}
 
-    // Get database
-    let db_path = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
-        path_guard.clone()
-    };
+#[tauri::command]
+async fn get_game_metadata(app_id: u32, state: State<'_, AppState>) -> Result<Option<GameMetadata>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_game_metadata(app_id)
+}
 
-    let db_path = match db_path {
-        Some(path) => path,
-        None => return Err("Achievement database not initialized".to_string()),
+#[tauri::command]
+async fn set_game_metadata(metadata: GameMetadata, state: State<'_, AppState>) -> Result<GameMetadata, String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+
+    // Blank fields are stored as unset, tags are trimmed and de-duplicated
+    let mut tags: Vec<String> = Vec::new();
+    for tag in metadata.platform_tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let metadata = GameMetadata {
+        notes: metadata.notes.trim_end().to_string(),
+        custom_cover_path: non_empty(metadata.custom_cover_path),
+        sort_title: non_empty(metadata.sort_title),
+        platform_tags: tags,
+        ..metadata
     };
 
-    let db = AchievementDatabase::new(db_path)
-        .map_err(|e| format!("Failed to open database: {}", e))?;
+    db.set_game_metadata(&metadata)?;
+    db.get_game_metadata(metadata.app_id)?
+        .ok_or_else(|| "Failed to read back game metadata".to_string())
+}
 
-    // Get all achievements for this game (they should already be in DB from the source scan)
-    let all_achievements = db.get_game_achievements(app_id)?;
+#[tauri::command]
+async fn delete_game_metadata(app_id: u32, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
 
-    let mut restored_count = 0;
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.delete_game_metadata(app_id)
+}
 
-    // Update achievements that are in the backup
-    for achievement in all_achievements {
-        if let Some(backup_entry) = backup_data.get(&achievement.achievement_id) {
-            if let Some(unlock_time_value) = backup_entry.get("UnlockTime") {
-                if let Some(unlock_time) = unlock_time_value.as_i64() {
-                    // Update achievement status to unlocked with the backup timestamp
-                    if let Some(id) = achievement.id {
-                        db.update_achievement_status(id, true, Some(unlock_time))
-                            .map_err(|e| format!("Failed to update achievement: {}", e))?;
-                        restored_count += 1;
-                    }
-                }
-            }
-        }
+/// Set a game's completion status by hand, or pass no status to let progress decide again
+#[tauri::command]
+async fn set_game_status(app_id: u32, status: Option<GameStatus>, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+    match status {
+        Some(status) => db.set_game_status(app_id, status),
+        None => db.clear_game_status(app_id),
     }
+}
 
-    Ok(restored_count)
+/// Games scanned with another account's SteamID64 than the one in settings
+fn steam_id_overrides(state: &AppState) -> HashMap<u32, String> {
+    state.achievement_db_path.lock_or_recover().clone()
+        .and_then(|path| AchievementDatabase::new(path).ok())
+        .and_then(|db| db.get_steam_id_overrides().ok())
+        .unwrap_or_default()
 }
 
 #[tauri::command]
-fn read_audio_file(file_path: String) -> Result<Vec<u8>, String> {
-    use std::fs;
+async fn get_game_steam_ids(state: State<'_, AppState>) -> Result<HashMap<u32, String>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_steam_id_overrides()
+}
 
-    println!("[OVERLAY DEBUG] Reading audio file: {}", file_path);
+/// Scan a game with another account's SteamID64 (family sharing, second account), or pass none to use the global one
+#[tauri::command]
+async fn set_game_steam_id(app_id: u32, steam_id_64: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
 
-    match fs::read(&file_path) {
-        Ok(bytes) => {
-            println!("[OVERLAY DEBUG] Successfully read {} bytes", bytes.len());
-            Ok(bytes)
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to read audio file: {}", e);
-            println!("[OVERLAY DEBUG] {}", error_msg);
-            Err(error_msg)
-        }
-    }
+    let steam_id_64 = match steam_id_64.filter(|id| !id.trim().is_empty()) {
+        Some(id) => Some(parse_steam_id_64(&id)?.to_string()),
+        None => None,
+    };
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.set_steam_id_override(app_id, steam_id_64.as_deref())
 }
 
 #[tauri::command]
-async fn test_overlay(state: State<'_, AppState>) -> Result<(), String> {
-    // Use NotificationManager to show achievement on overlay
-    state.notification_manager.lock().unwrap().show_achievement_unlock(
-        "Test Game",
-        "First Steps",
-        "Complete the tutorial",
-        Some("https://cdn.cloudflare.steamstatic.com/steamcommunity/public/images/apps/default_icon.jpg"),
-        Some(85.0) // Uncommon rarity for testing
-    );
+async fn set_game_favorite(app_id: u32, game_name: String, favorite: bool, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
 
-    Ok(())
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.set_favorite(app_id, &game_name, favorite)
 }
 
+/// Newest unlocks across all games for the activity feed
 #[tauri::command]
-async fn get_achievement_duration(state: State<'_, AppState>) -> Result<u32, String> {
-    let config = state.config.lock().unwrap();
-    Ok(config.get_all().achievement_duration)
+async fn get_recent_unlocks(limit: Option<u32>, offset: Option<u32>, state: State<'_, AppState>) -> Result<Vec<UnlockFeedEntry>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_recent_unlocks(limit.unwrap_or(50).min(500), offset.unwrap_or(0))
 }
 
+/// Unlock counts per day, week and hour of day for the activity heatmap
 #[tauri::command]
-async fn set_achievement_duration(duration: u32, state: State<'_, AppState>) -> Result<(), String> {
-    // Update in-memory duration for notification manager
-    *state.achievement_duration.lock().unwrap() = duration;
-
-    // Save to config file
-    let mut config = state.config.lock().unwrap();
-    let mut cfg = config.get_all();
-    cfg.achievement_duration = duration;
-    config.set_all(cfg);
+async fn get_unlock_statistics(year: Option<i32>, state: State<'_, AppState>) -> Result<UnlockStatistics, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let unlock_times = AchievementDatabase::new(db_path)?.get_unlock_times()?;
+    Ok(unlock_stats::compute(&unlock_times, year))
+}
 
-    println!("[Backend] Achievement duration set to {} seconds and saved to config", duration);
-    Ok(())
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdateInfo {
+    version: String,
+    current_version: String,
+    notes: Option<String>,
+    date: Option<String>,
 }
 
-#[tauri::command]
-async fn reset_game_monitoring(state: State<'_, AppState>, window: Window) -> Result<(), String> {
-    println!("Resetting game monitoring...");
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdateStatus {
+    current_version: String,
+    downloaded_version: Option<String>, // installed when the app exits
+    rollback_version: Option<String>,
+}
 
-    // Get current game info before stopping
-    let current_game = {
-        let game = state.current_game.lock().unwrap();
-        game.clone()
-    };
+/// The release public key in tauri.conf.json; None until one is committed, which keeps the updater off
+fn updater_pubkey(app: &tauri::AppHandle) -> Option<String> {
+    app.config().plugins.0.get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|key| key.as_str())
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
 
-    // Stop all monitors
-    stop_monitors(&state).await;
+async fn find_update(app: &tauri::AppHandle) -> Result<Option<Update>, String> {
+    // Without a key no bundle can be verified, so there's nothing an update could safely install
+    if updater_pubkey(app).is_none() {
+        return Err("Updates are turned off in this build: it has no release signing key".to_string());
+    }
+    let update = app.updater()
+        .map_err(|e| format!("Updater unavailable: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+    // A version that was rolled back isn't offered again
+    Ok(update.filter(|u| update_rollback::skipped_version().as_deref() != Some(u.version.as_str())))
+}
 
-    // Small delay to ensure clean stop
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+#[tauri::command]
+async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    Ok(find_update(&app).await?.map(|u| UpdateInfo {
+        version: u.version.clone(),
+        current_version: u.current_version.clone(),
+        notes: u.body.clone(),
+        date: u.date.map(|d| d.to_string()),
+    }))
+}
 
-    // Restart monitors
-    start_monitors(&state, window).await;
+/// Download and verify the latest update; it's installed the next time the app exits
+#[tauri::command]
+async fn download_update(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    ensure_writable(&state)?;
+    let update = find_update(&app).await?.ok_or("Already up to date")?;
+    // download() checks the bundle against the public key in tauri.conf.json, find_update made sure there is one
+    let bytes = update.download(|_, _| {}, || {})
+        .await
+        .map_err(|e| format!("Failed to download update {}: {}", update.version, e))?;
+    let version = update.version.clone();
+    app_log!("[Updater] Downloaded {}, installing on exit", version);
+    *state.pending_update.lock_or_recover() = Some((update, bytes));
+    Ok(version)
+}
 
-    if let Some((game_name, _)) = current_game {
-        println!("Game monitoring reset for: {}", game_name);
+#[tauri::command]
+fn get_update_status(state: State<'_, AppState>) -> UpdateStatus {
+    UpdateStatus {
+        current_version: env!("CARGO_PKG_VERSION").to_string(),
+        downloaded_version: state.pending_update.lock_or_recover().as_ref().map(|(u, _)| u.version.clone()),
+        rollback_version: update_rollback::rollback_available(),
     }
-
-    Ok(())
 }
 
+/// Restore the version that was running before the last update and restart into it
 #[tauri::command]
-async fn stop_game_monitoring(state: State<'_, AppState>) -> Result<(), String> {
-    let game_info = {
-        let mut current_game = state.current_game.lock().unwrap();
-        current_game.take()
-    };
-
-    if let Some((game_name, app_id)) = game_info {
-        println!("Stopping monitoring for: {} (AppID: {})", game_name, app_id);
+fn rollback_update(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    update_rollback::rollback()?;
+    app.restart()
+}
 
-        // Stop watching this specific game
-        if let Some(watcher) = state.achievement_watcher.lock().unwrap().as_ref() {
-            watcher.stop_watching_game(app_id);
-        }
+// Install a downloaded update as the app exits, keeping the current version around for rollback
+fn apply_pending_update(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else { return };
+    let Some((update, bytes)) = state.pending_update.lock_or_recover().take() else { return };
 
-        Ok(())
-    } else {
-        Err("No game is currently being monitored".to_string())
+    if let Err(e) = update_rollback::save_rollback_point(env!("CARGO_PKG_VERSION"), &update.version) {
+        // Without a rollback point a broken update couldn't be undone, so don't install it
+        app_log!("[Updater] Not installing {}: {}", update.version, e);
+        return;
+    }
+    if let Err(e) = update.install(bytes) {
+        app_log!("[Updater] Failed to install {}: {}", update.version, e);
     }
 }
 
 #[tauri::command]
-async fn get_current_game(state: State<'_, AppState>) -> Result<Option<(String, u32)>, String> {
-    let game = state.current_game.lock().unwrap();
-    Ok(game.clone())
+fn get_last_session_recap(state: State<'_, AppState>) -> Option<SessionRecap> {
+    state.last_session_recap.lock_or_recover().clone()
 }
 
+/// Save the current rarity sound and color settings, with their files, as a shareable pack
 #[tauri::command]
-async fn sync_settings_to_overlay(achievement_settings: serde_json::Value, rarity_settings: serde_json::Value, app: tauri::AppHandle) -> Result<(), String> {
-    // Emit settings to ALL windows (including overlay)
-    app.emit_all("achievement-settings-sync", &achievement_settings)
-        .map_err(|e| format!("Failed to emit achievement settings: {}", e))?;
+fn export_sound_pack(settings: serde_json::Value, name: String, author: String, description: String, path: String) -> Result<String, String> {
+    sound_packs::export_pack(&settings, &name, &author, &description, Path::new(&path))
+        .map(|path| path.to_string_lossy().to_string())
+}
 
-    app.emit_all("rarity-settings-sync", &rarity_settings)
-        .map_err(|e| format!("Failed to emit rarity settings: {}", e))?;
+#[tauri::command]
+fn import_sound_pack(path: String, state: State<'_, AppState>) -> Result<SoundPackInfo, String> {
+    ensure_writable(&state)?;
+    sound_packs::import_pack(Path::new(&path))
+}
+
+#[tauri::command]
+fn list_sound_packs() -> Vec<SoundPackInfo> {
+    sound_packs::list_packs()
+}
+
+#[tauri::command]
+fn delete_sound_pack(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    sound_packs::delete_pack(&name)
+}
+
+/// Achievement source plugins found in the plugins folder, including ones that failed to load
+#[tauri::command]
+fn list_achievement_source_plugins() -> Vec<PluginStatus> {
+    achievement_sources::load_plugins().1
+}
+
+#[tauri::command]
+fn get_achievement_source_plugins_folder() -> String {
+    let dir = achievement_sources::plugins_dir();
+    let _ = std::fs::create_dir_all(&dir);
+    dir.to_string_lossy().to_string()
+}
+
+/// For every tracked game, whether its achievement source is still on disk and the watcher can attach to it
+#[tauri::command]
+async fn get_achievement_source_health(state: State<'_, AppState>) -> Result<Vec<SourceHealth>, String> {
+    let watcher = state.achievement_watcher.read().await.clone()
+        .ok_or("Achievement watcher isn't running")?;
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+
+    let mut report: Vec<SourceHealth> = db.get_all_games()?
+        .into_iter()
+        .map(|game| {
+            let excluded = db.is_excluded(game.app_id).unwrap_or(false);
+            watcher.source_health(game.app_id, &game.game_name, &game.source, excluded)
+        })
+        .collect();
+    // Problems first
+    report.sort_by_key(|health| (health.problem.is_none(), health.game_name.to_lowercase()));
+    Ok(report)
+}
+
+/// Events after `since` (the last seq the frontend saw), for catching up after a reload
+#[tauri::command]
+fn get_missed_events(since: u64, state: State<'_, AppState>) -> Vec<SequencedEvent> {
+    state.event_bus.lock_or_recover().since(since)
+}
+
+/// Long-running commands still in progress, for progress bars after a reload
+#[tauri::command]
+fn get_active_operations(state: State<'_, AppState>) -> Vec<ProgressEvent> {
+    state.active_operations.lock_or_recover().list()
+}
+
+/// The link the app was last opened with, cleared once read so a reload doesn't navigate again
+#[tauri::command]
+fn take_launch_request(state: State<'_, AppState>) -> Option<LaunchRequest> {
+    state.pending_launch.lock_or_recover().take()
+}
+
+#[derive(Debug, Serialize)]
+struct Diagnostics {
+    version: String,
+    current_game: Option<String>,
+    monitors: Vec<TaskHealth>,
+}
+
+/// App version, the current game and whether the monitor tasks are alive
+#[tauri::command]
+async fn get_diagnostics(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<Diagnostics, String> {
+    Ok(Diagnostics {
+        version: app.package_info().version.to_string(),
+        current_game: state.current_game.read().await.as_ref().map(|(name, _)| name.clone()),
+        monitors: state.supervisor.health(),
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct SimulationStep {
+    name: String,
+    success: bool,
+    detail: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SimulationReport {
+    game_name: String,
+    steps: Vec<SimulationStep>,
+}
+
+impl SimulationReport {
+    fn step(&mut self, name: &str, result: Result<String, String>) {
+        let (success, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        app_log!("[Simulation] {}: {}", name, detail);
+        self.steps.push(SimulationStep { name: name.to_string(), success, detail });
+    }
+}
+
+// Events published after `since`, waiting up to SIMULATION_STEP_WAIT for one that `found` accepts
+async fn wait_for_event<T>(state: &AppState, since: u64, found: impl Fn(&AppEvent) -> Option<T>) -> Option<T> {
+    let deadline = tokio::time::Instant::now() + SIMULATION_STEP_WAIT;
+    loop {
+        let result = state.event_bus.lock_or_recover().since(since).iter().find_map(|e| found(&e.event));
+        if result.is_some() || tokio::time::Instant::now() >= deadline {
+            return result;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    }
+}
+
+/// Play a made-up session of a tracked game through the real handlers (game started, achievement
+/// unlock, game ended, backup) to check the whole setup without launching the game. The unlock is
+/// undone afterwards; the backup is real.
+#[tauri::command]
+async fn simulate_game_session(app_id: u32, app: tauri::AppHandle, state: State<'_, AppState>) -> Result<SimulationReport, String> {
+    ensure_writable(&state)?;
+    if let Some((running, _)) = state.current_game.read().await.as_ref() {
+        return Err(format!("{} is running, simulate a session after it's closed", running));
+    }
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone().ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+    let game_name = db.get_all_games()?
+        .into_iter()
+        .find(|g| g.app_id == app_id)
+        .map(|g| g.game_name)
+        .ok_or("Add the game to achievement tracking before simulating a session")?;
+    let achievements = db.get_game_achievements(app_id)?;
+    let achievement = achievements.iter()
+        .find(|a| !a.achieved)
+        .or(achievements.first())
+        .cloned()
+        .ok_or_else(|| format!("{} has no achievements to unlock", game_name))?;
+
+    let mut report = SimulationReport { game_name: game_name.clone(), steps: Vec::new() };
+    let game = GameInfo { name: game_name.clone(), app_id: Some(app_id), exe_path: None };
+
+    handle_game_started(&state, &app, game.clone()).await;
+    report.step("Game started", Ok("Game detected event, tray and notification sent".to_string()));
+
+    // start_watching_game runs in the background; give it a moment to find the file
+    tokio::time::sleep(SIMULATION_STEP_WAIT).await;
+    let watcher = state.achievement_watcher.read().await.clone();
+    report.step("Achievement watcher", match &watcher {
+        Some(watcher) => watcher.watched_file(app_id)
+            .map(|file| format!("Watching {}", file.display()))
+            .ok_or_else(|| format!("No achievement file found for {}, real unlocks won't be detected", game_name)),
+        None => Err("The achievement watcher isn't running".to_string()),
+    });
+
+    if let Some(watcher) = watcher {
+        let since = state.event_bus.lock_or_recover().last_seq();
+        let unlocked = watcher.simulate_unlock(app_id, &game_name, &achievement);
+        let recorded = unlocked.is_ok();
+        report.step("Achievement unlock", unlocked.map(|event| {
+            format!("{} saved to the database and shown on the overlay", event.display_name)
+        }));
+
+        if recorded {
+            let delivered = wait_for_event(&state, since, |event| match event {
+                AppEvent::AchievementUnlocked(e) if e.app_id == app_id && e.source == SIMULATED_SOURCE => Some(()),
+                _ => None,
+            }).await;
+            report.step("Unlock event", delivered
+                .map(|_| "The app received the unlock".to_string())
+                .ok_or_else(|| "The unlock never reached the app".to_string()));
+        }
+
+        if let Some(id) = achievement.id {
+            if let Err(e) = db.update_achievement_status(id, achievement.achieved, achievement.unlock_time) {
+                report.step("Undo unlock", Err(e));
+            }
+        }
+    }
+
+    let since = state.event_bus.lock_or_recover().last_seq();
+    handle_game_ended(&state, app.clone(), game).await;
+    let backup = wait_for_event(&state, since, |event| match event {
+        AppEvent::BackupFinished { game_name: name, success, error, .. } if *name == game_name => {
+            Some(if *success { Ok("Saves backed up with Ludusavi".to_string()) } else { Err(error.clone().unwrap_or_default()) })
+        }
+        AppEvent::GameNotFound { name } if *name == game_name => {
+            Some(Err(format!("Ludusavi doesn't know {}, add it in Ludusavi to back it up", name)))
+        }
+        _ => None,
+    }).await;
+    report.step("Game ended and backup", backup.unwrap_or_else(|| Err("No backup was made, check the Ludusavi and backup paths".to_string())));
+
+    Ok(report)
+}
+
+/// Script files that run on app events when scripting is enabled
+#[tauri::command]
+fn list_scripts() -> Vec<String> {
+    scripting::list_scripts()
+        .iter()
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect()
+}
+
+#[tauri::command]
+fn get_scripts_folder() -> String {
+    let dir = scripting::scripts_dir();
+    let _ = std::fs::create_dir_all(&dir);
+    dir.to_string_lossy().to_string()
+}
+
+/// Post a sample unlock with the given (possibly unsaved) webhook settings
+#[tauri::command]
+async fn test_unlock_webhook(settings: WebhookSettings) -> Result<(), String> {
+    let payload = UnlockPayload {
+        event: "achievement_unlocked",
+        game_name: "Test Game".to_string(),
+        app_id: 0,
+        achievement_id: "TEST_ACHIEVEMENT".to_string(),
+        display_name: "Test Achievement".to_string(),
+        description: "Webhook test from Game Save Monitor".to_string(),
+        icon_url: None,
+        unlock_time: chrono::Utc::now().timestamp(),
+        global_unlock_percentage: Some(12.5),
+    };
+    webhooks::send_unlock(&settings, &payload).await
+}
+
+#[tauri::command]
+async fn get_all_exclusions(state: State<'_, AppState>) -> Result<Vec<achievements::Exclusion>, String> {
+    let db = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        match &*path_guard {
+            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+            None => None,
+        }
+    };
+
+    match db {
+        Some(db) => db.get_all_exclusions(),
+        None => Err("Achievement database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn add_exclusion(
+    app_id: u32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let db = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        match &*path_guard {
+            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+            None => None,
+        }
+    };
+
+    match db {
+        Some(db) => {
+            db.add_exclusion(app_id, name)?;
+            // No need to restart monitors - they check exclusions dynamically on each scan
+            app_log!("Added app_id {} to exclusions", app_id);
+            Ok(())
+        }
+        None => Err("Achievement database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn remove_exclusion(
+    app_id: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+
+    let db = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        match &*path_guard {
+            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+            None => None,
+        }
+    };
+
+    match db {
+        Some(db) => {
+            db.remove_exclusion(app_id)?;
+            // No need to restart monitors - they check exclusions dynamically on each scan
+            app_log!("Removed app_id {} from exclusions", app_id);
+            Ok(())
+        }
+        None => Err("Achievement database not initialized".to_string()),
+    }
+}
+
+/// Exclude several apps at once, named from the Steam library where possible
+#[tauri::command]
+async fn add_exclusions(app_ids: Vec<u32>, state: State<'_, AppState>) -> Result<usize, String> {
+    ensure_writable(&state)?;
+    let names = tokio::task::spawn_blocking(|| {
+        SteamMonitor::find_steam_path().map(|steam_path| steam_categories::installed_app_names(&steam_path))
+    })
+    .await
+    .map_err(|e| format!("Steam library scan failed: {}", e))??;
+    let entries: Vec<ExclusionEntry> = app_ids.into_iter()
+        .map(|app_id| ExclusionEntry {
+            app_id,
+            name: names.get(&app_id).cloned().unwrap_or_else(|| format!("App {}", app_id)),
+        })
+        .collect();
+    bulk_add_exclusions(&state, &entries)
+}
+
+#[tauri::command]
+async fn remove_exclusions(app_ids: Vec<u32>, state: State<'_, AppState>) -> Result<usize, String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let removed = AchievementDatabase::new(db_path)?.remove_exclusions(&app_ids)?;
+    app_log!("[Exclusions] Removed {} of {} apps", removed, app_ids.len());
+    Ok(removed)
+}
+
+/// Exclude every installed app that appinfo.vdf doesn't list as a game (tools, soundtracks, SDKs...)
+#[tauri::command]
+async fn exclude_non_game_apps(state: State<'_, AppState>) -> Result<usize, String> {
+    ensure_writable(&state)?;
+    let apps = tokio::task::spawn_blocking(|| {
+        SteamMonitor::find_steam_path().map(|steam_path| steam_monitor::installed_apps(&steam_path))
+    })
+    .await
+    .map_err(|e| format!("Steam library scan failed: {}", e))??;
+    let entries: Vec<ExclusionEntry> = apps.into_iter()
+        .filter(|app| !app.is_game)
+        .map(|app| ExclusionEntry { app_id: app.app_id, name: app.name })
+        .collect();
+    bulk_add_exclusions(&state, &entries)
+}
+
+/// Save the exclusion list to a JSON file picked by the user. Returns None if the dialog was cancelled.
+#[tauri::command]
+async fn export_exclusions(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let entries: Vec<ExclusionEntry> = AchievementDatabase::new(db_path)?.get_all_exclusions()?
+        .into_iter()
+        .map(|exclusion| ExclusionEntry { app_id: exclusion.app_id, name: exclusion.name })
+        .collect();
+
+    let path = app.dialog().file()
+        .set_file_name("exclusions.json")
+        .add_filter("JSON", &["json"])
+        .blocking_save_file();
+    let Some(path) = path.and_then(|p| p.into_path().ok()) else { return Ok(None) };
+
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize exclusions: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    app_log!("[Exclusions] Exported {} exclusions to {}", entries.len(), path.display());
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+/// Add the exclusions from a file written by export_exclusions. Returns None if the dialog was cancelled.
+#[tauri::command]
+async fn import_exclusions(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<Option<usize>, String> {
+    ensure_writable(&state)?;
+    let path = app.dialog().file()
+        .add_filter("JSON", &["json"])
+        .blocking_pick_file();
+    let Some(path) = path.and_then(|p| p.into_path().ok()) else { return Ok(None) };
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut entries: Vec<ExclusionEntry> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Not an exclusion list: {}", e))?;
+    for entry in entries.iter_mut().filter(|entry| entry.name.trim().is_empty()) {
+        entry.name = format!("App {}", entry.app_id);
+    }
+    bulk_add_exclusions(&state, &entries).map(Some)
+}
+
+fn bulk_add_exclusions(state: &AppState, entries: &[ExclusionEntry]) -> Result<usize, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let added = AchievementDatabase::new(db_path)?.add_exclusions(entries)?;
+    // Monitors check exclusions on each scan, so nothing needs restarting
+    app_log!("[Exclusions] Added {} new of {} apps", added, entries.len());
+    Ok(added)
+}
+
+/// Executables that count as an installed Steam game running
+#[tauri::command]
+async fn get_steam_executables() -> Result<Vec<steam_monitor::DetectedExecutable>, String> {
+    tokio::task::spawn_blocking(|| SteamMonitor::new().map(|monitor| monitor.executables()))
+        .await
+        .map_err(|e| format!("Executable scan failed: {}", e))?
+}
+
+/// Installed Steam apps with the names, types and executables from appinfo.vdf
+#[tauri::command]
+async fn get_steam_library() -> Result<Vec<steam_monitor::InstalledApp>, String> {
+    tokio::task::spawn_blocking(|| {
+        SteamMonitor::find_steam_path().map(|steam_path| steam_monitor::installed_apps(&steam_path))
+    })
+    .await
+    .map_err(|e| format!("Steam library scan failed: {}", e))?
+}
+
+#[tauri::command]
+async fn get_ignored_executables(state: State<'_, AppState>) -> Result<Vec<achievements::IgnoredExecutable>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_ignored_executables()
+}
+
+/// Stop an executable from counting as its Steam game, e.g. a bundled tool or server
+#[tauri::command]
+async fn ignore_executable(
+    exe_name: String,
+    app_id: u32,
+    game_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.ignore_executable(&exe_name, app_id, &game_name)?;
+    // Like exclusions, the Steam monitor checks this on each scan
+    app_log!("Ignoring {} for {} (AppID: {})", exe_name, game_name, app_id);
+    Ok(())
+}
+
+#[tauri::command]
+async fn unignore_executable(exe_name: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.unignore_executable(&exe_name)?;
+    app_log!("No longer ignoring {}", exe_name);
+    Ok(())
+}
+
+/// Categories (collections) in the Steam library, for picking ones to exclude
+#[tauri::command]
+async fn get_steam_categories(state: State<'_, AppState>) -> Result<Vec<steam_categories::SteamCategory>, String> {
+    let steam_user_id = state.config.lock_or_recover().get_all().steam_user_id;
+    let steam_path = SteamMonitor::find_steam_path()?;
+    steam_categories::read_categories(&steam_path, steam_user_id.as_deref())
+}
+
+/// Re-import exclusions from the Steam categories picked in settings
+#[tauri::command]
+async fn sync_steam_category_exclusions(state: State<'_, AppState>) -> Result<steam_categories::CategorySyncResult, String> {
+    ensure_writable(&state)?;
+    sync_category_exclusions(&state)
+}
+
+// Make the exclusions imported from Steam categories match the categories picked in settings
+fn sync_category_exclusions(state: &AppState) -> Result<steam_categories::CategorySyncResult, String> {
+    let (categories, steam_user_id) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.excluded_steam_categories, cfg.steam_user_id)
+    };
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+
+    let mut entries: Vec<(u32, String, String)> = Vec::new();
+    if !categories.is_empty() {
+        let steam_path = SteamMonitor::find_steam_path()?;
+        let names = steam_categories::installed_app_names(&steam_path);
+        for category in steam_categories::read_categories(&steam_path, steam_user_id.as_deref())? {
+            if !categories.iter().any(|c| c.eq_ignore_ascii_case(&category.name)) {
+                continue;
+            }
+            for app_id in category.app_ids {
+                if entries.iter().any(|(id, _, _)| *id == app_id) {
+                    continue;
+                }
+                let name = names.get(&app_id).cloned().unwrap_or_else(|| format!("App {}", app_id));
+                entries.push((app_id, name, category.name.clone()));
+            }
+        }
+    }
+
+    let result = AchievementDatabase::new(db_path)?.sync_category_exclusions(&entries)?;
+    if result.added + result.removed > 0 {
+        app_log!("[Exclusions] Synced Steam categories: {} added, {} removed", result.added, result.removed);
+    }
+    Ok(result)
+}
+
+/// Cache an achievement icon on disk and return its URL on the icon protocol
+#[tauri::command]
+async fn fetch_achievement_icon(url: String) -> Result<String, String> {
+    let name = icon_cache::fetch(&url).await?;
+    Ok(icon_cache::icon_url(&name))
+}
+
+// Icons fetched at once while pre-downloading; the CDN is fine with a handful in parallel
+const ICON_DOWNLOAD_BATCH: usize = 8;
+
+#[derive(Debug, Serialize)]
+struct IconDownloadSummary {
+    total: usize,
+    downloaded: usize,
+    already_cached: usize,
+    failed: usize,
+    bytes_downloaded: u64,
+    cache_bytes: u64, // whole icon cache afterwards
+}
+
+/// Download the unlocked and locked icons of every tracked achievement, so the UI and the overlay
+/// show them instantly and offline
+#[tauri::command]
+async fn download_all_achievement_icons(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<IconDownloadSummary, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone().ok_or("Achievement database not initialized")?;
+    let urls = AchievementDatabase::new(db_path)?.get_icon_urls()?;
+    let (cached, missing): (Vec<String>, Vec<String>) = urls.into_iter().partition(|url| icon_cache::cached_name(url).is_some());
+
+    let mut summary = IconDownloadSummary {
+        total: cached.len() + missing.len(),
+        downloaded: 0,
+        already_cached: cached.len(),
+        failed: 0,
+        bytes_downloaded: 0,
+        cache_bytes: 0,
+    };
+    app_log!("[Icons] {} icons, {} to download", summary.total, missing.len());
+
+    let mut progress = OperationProgress::start(&state, &app, "download_icons", format!("Downloading {} icons", missing.len()));
+    for (batch_index, batch) in missing.chunks(ICON_DOWNLOAD_BATCH).enumerate() {
+        progress.update(
+            "downloading",
+            (batch_index * ICON_DOWNLOAD_BATCH) as u64,
+            Some(missing.len() as u64),
+            format!("Downloading icons ({} of {})", batch_index * ICON_DOWNLOAD_BATCH, missing.len()),
+        );
+        let handles: Vec<_> = batch.iter()
+            .cloned()
+            .map(|url| tauri::async_runtime::spawn(async move { icon_cache::fetch(&url).await }))
+            .collect();
+        for handle in handles {
+            match handle.await.map_err(|e| e.to_string()).and_then(|result| result) {
+                Ok(name) => {
+                    summary.downloaded += 1;
+                    summary.bytes_downloaded += std::fs::metadata(icon_cache::icons_dir().join(name)).map(|m| m.len()).unwrap_or(0);
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    app_log!("[Icons] {}", e);
+                }
+            }
+        }
+    }
+
+    summary.cache_bytes = icon_cache::cache_size();
+    progress.finish(format!("Downloaded {} icons", summary.downloaded));
+    Ok(summary)
+}
+
+// Serves files cached by fetch_achievement_icon; they never change, so the webview may cache them for good
+fn serve_cached_icon(request: &tauri::http::Request<Vec<u8>>) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{header, Response, StatusCode};
+
+    let name = request.uri().path().trim_start_matches('/');
+    let response = match icon_cache::read(name) {
+        Some((bytes, mime)) => Response::builder()
+            .header(header::CONTENT_TYPE, mime)
+            .header(header::CACHE_CONTROL, "max-age=31536000, immutable")
+            .body(bytes),
+        None => Response::builder().status(StatusCode::NOT_FOUND).body(Vec::new()),
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+// Serve a matched screenshot by its database id; only files the scan recorded can be read
+fn serve_screenshot(app: &tauri::AppHandle, request: &tauri::http::Request<Vec<u8>>) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{header, Response, StatusCode};
+
+    let path = request.uri().path().trim_start_matches('/');
+    let (id, full) = match path.strip_suffix("/full") {
+        Some(id) => (id, true),
+        None => (path, false),
+    };
+    let screenshot = id.parse::<i64>().ok().and_then(|id| {
+        let state = app.try_state::<AppState>()?;
+        let db_path = state.achievement_db_path.lock_or_recover().clone()?;
+        AchievementDatabase::new(db_path).ok()?.get_achievement_screenshot(id).ok()?
+    });
+    let file = screenshot.map(|s| if full { s.path } else { s.thumbnail_path.unwrap_or(s.path) });
+
+    let response = match file.and_then(|file| std::fs::read(&file).ok().map(|bytes| (file, bytes))) {
+        Some((file, bytes)) => {
+            let mime = if file.to_lowercase().ends_with(".png") { "image/png" } else { "image/jpeg" };
+            Response::builder()
+                .header(header::CONTENT_TYPE, mime)
+                .body(bytes)
+        }
+        None => Response::builder().status(StatusCode::NOT_FOUND).body(Vec::new()),
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+#[tauri::command]
+fn play_windows_notification_sound() -> Result<(), String> {
+    use windows::Win32::Media::Audio::{PlaySoundA, SND_ALIAS, SND_ASYNC};
+    use windows::core::PCSTR;
+    use std::ffi::CString;
+
+    std::thread::spawn(move || {
+        unsafe {
+            let sound_alias = CString::new("SystemNotification").unwrap_or_default();
+            let _ = PlaySoundA(
+                PCSTR(sound_alias.as_ptr() as *const u8),
+                None,
+                SND_ALIAS | SND_ASYNC,
+            );
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+fn debug_log(message: String) {
+    app_log!("[OVERLAY DEBUG] {}", message);
+}
+
+/// Folder used for exports, backup dates and logs (Documents, or the app data fallback)
+#[tauri::command]
+fn get_output_location() -> OutputLocation {
+    user_folders::location()
+}
+
+// Tell the user once when Documents turned out not to be writable
+fn report_output_fallback(state: &AppState) {
+    if let Some(reason) = user_folders::take_fallback_notice() {
+        state.notification_manager.lock_or_recover().show_error(
+            "Documents Folder Not Writable",
+            &format!("Saving exports to {} instead ({})", user_folders::output_root().display(), reason),
+        );
+    }
+}
+
+#[tauri::command]
+fn check_backup_exists(game_name: String, app_id: Option<u32>) -> Result<Option<String>, String> {
+    if let Some(app_id) = app_id {
+        return Ok(achievement_backups::find_or_migrate(app_id, &game_name).map(|path| path.to_string_lossy().to_string()));
+    }
+
+    // Without an app id only exports named after the game can be found
+    let export_dir = user_folders::output_root().join("Steam Backup Monitor");
+    let stems = [
+        filenames::lookup_stem(&export_dir, &game_name, None),
+        // Exports written before names carried the app id
+        filenames::legacy_file_stem(&game_name),
+    ];
+
+    // Check if backup file exists (also in Documents if exports moved to the fallback folder)
+    Ok(stems.iter()
+        .find_map(|stem| user_folders::find_file(&Path::new("Steam Backup Monitor").join(format!("{}.json", stem))))
+        .map(|path| path.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+async fn restore_from_backup(
+    app_id: u32,
+    game_name: String,
+    backup_path: String,
+    state: State<'_, AppState>
+) -> Result<usize, String> {
+    ensure_writable(&state)?;
+
+    use std::fs;
+
+    // Read backup file
+    let backup_content = fs::read_to_string(&backup_path)
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+    // Parse JSON (Steam API format: {"achievement_id": {"UnlockTime": timestamp}})
+    let backup_data: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&backup_content)
+        .map_err(|e| format!("Failed to parse backup file: {}", e))?;
+
+    // Get database
+    let db_path = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        path_guard.clone()
+    };
+
+    let db_path = match db_path {
+        Some(path) => path,
+        None => return Err("Achievement database not initialized".to_string()),
+    };
+
+    let db = AchievementDatabase::new(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    // Get all achievements for this game (they should already be in DB from the source scan)
+    let all_achievements = db.get_game_achievements(app_id)?;
+
+    // Safety snapshot of the current state first, so a bad restore can be undone
+    let snapshot_path = save_pre_restore_achievements(&game_name, &all_achievements)?;
+    let unlocked_before = all_achievements.iter().filter(|a| a.achieved).count();
+    db.add_backup_history(&BackupHistoryEntry {
+        id: None,
+        game_name: game_name.clone(),
+        app_id: Some(app_id),
+        kind: "pre-restore".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        success: true,
+        files_count: Some(unlocked_before as i64),
+        total_size: None,
+        location: Some(snapshot_path.to_string_lossy().to_string()),
+        error: None,
+    })?;
+
+    let mut restored_count = 0;
+
+    // Update achievements that are in the backup
+    for achievement in all_achievements {
+        if let Some(backup_entry) = backup_data.get(&achievement.achievement_id) {
+            if let Some(unlock_time_value) = backup_entry.get("UnlockTime") {
+                if let Some(unlock_time) = unlock_time_value.as_i64() {
+                    // Update achievement status to unlocked with the backup timestamp
+                    if let Some(id) = achievement.id {
+                        db.update_achievement_status(id, true, Some(unlock_time))
+                            .map_err(|e| format!("Failed to update achievement: {}", e))?;
+                        restored_count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(restored_count)
+}
+
+/// Achievement backups in the output folder, for managing them from the app
+#[tauri::command]
+fn list_achievement_backups() -> Vec<BackupSummary> {
+    achievement_backups::list()
+}
+
+#[tauri::command]
+fn delete_achievement_backup(app_id: u32, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    achievement_backups::delete(app_id)
+}
+
+#[tauri::command]
+fn rename_achievement_backup(app_id: u32, game_name: String, state: State<'_, AppState>) -> Result<BackupMetadata, String> {
+    ensure_writable(&state)?;
+    achievement_backups::rename(app_id, &game_name)
+}
+
+#[tauri::command]
+async fn list_game_snapshots(game_name: String, state: State<'_, AppState>) -> Result<Vec<SnapshotInfo>, String> {
+    let backup_path = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_path_for(&game_name)
+    };
+
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    SnapshotManager::new(backup_path).list_snapshots(&game_name)
+}
+
+/// The timestamped backups Ludusavi keeps for a game under the retention settings. Their ids are
+/// the names `ludusavi backups` reports, which restore_backup_version takes.
+#[tauri::command]
+async fn list_backup_versions(game_name: String, state: State<'_, AppState>) -> Result<Vec<SnapshotInfo>, String> {
+    let (ludusavi_path, backup_path, ludusavi_timeout) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.ludusavi_path.clone(), cfg.backup_path_for(&game_name), cfg.ludusavi_timeout_secs)
+    };
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    let snapshots = SnapshotManager::new(backup_path.clone());
+    if ludusavi_path.is_empty() {
+        return snapshots.list_versions(&game_name);
+    }
+
+    let backups = LudusaviManager::new(ludusavi_path, backup_path).with_timeout(ludusavi_timeout).list_backups(&game_name).await?;
+    if backups.is_empty() {
+        return Err(format!("No backups found for {}", game_name));
+    }
+
+    // File counts and sizes come from the backup on disk; zip backups are read from their archive
+    let folders = snapshots.list_versions(&game_name).unwrap_or_default();
+    let mut versions: Vec<SnapshotInfo> = backups.into_iter()
+        .map(|backup| {
+            folders.iter().find(|s| s.id == backup.name).cloned()
+                .or_else(|| snapshots.archive_version(&game_name, &backup.name))
+                .unwrap_or_else(|| SnapshotInfo {
+                    id: backup.name.clone(),
+                    game_name: game_name.clone(),
+                    path: snapshots.game_dir(&game_name).join(&backup.name).to_string_lossy().to_string(),
+                    created_at: chrono::DateTime::parse_from_rfc3339(&backup.when).map(|t| t.timestamp()).unwrap_or(0),
+                    file_count: 0,
+                    total_bytes: 0,
+                })
+        })
+        .collect();
+    versions.sort_by_key(|v| v.created_at);
+    Ok(versions)
+}
+
+#[tauri::command]
+async fn compare_backup_snapshots(
+    game_name: String,
+    from_snapshot: String,
+    to_snapshot: String,
+    state: State<'_, AppState>,
+) -> Result<SnapshotComparison, String> {
+    let backup_path = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_path_for(&game_name)
+    };
+
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    // Hashing large saves is slow, keep it off the async runtime
+    tokio::task::spawn_blocking(move || {
+        SnapshotManager::new(backup_path).compare(&game_name, &from_snapshot, &to_snapshot)
+    })
+    .await
+    .map_err(|e| format!("Snapshot comparison failed: {}", e))?
+}
+
+#[tauri::command]
+fn list_snapshot_restore_files(game_name: String, snapshot_id: String, state: State<'_, AppState>) -> Result<Vec<RestorableFile>, String> {
+    let backup_path = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_path_for(&game_name)
+    };
+
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    restore::restorable_files(&SnapshotManager::new(backup_path), &game_name, &snapshot_id)
+}
+
+/// Restore only the chosen files or folders (original paths) from a snapshot
+#[tauri::command]
+async fn restore_snapshot_files(
+    game_name: String,
+    snapshot_id: String,
+    files: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<SelectiveRestoreResult, String> {
+    ensure_writable(&state)?;
+    ensure_backup_key(&state)?;
+
+    let (ludusavi_path, backup_path, ludusavi_cloud_enabled, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.ludusavi_path.clone(), cfg.backup_path_for(&game_name), cfg.ludusavi_cloud_sync, cfg.ludusavi_timeout_secs)
+    };
+
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    let ludusavi = LudusaviManager::new(ludusavi_path, backup_path.clone()).with_timeout(ludusavi_timeout);
+
+    // Pull Ludusavi's cloud copy first so the snapshot being restored is the newest one
+    if ludusavi_cloud_enabled {
+        match ludusavi.cloud_sync(CloudDirection::Download, Some(&game_name)).await {
+            Ok(result) if !result.success => app_log!("[Ludusavi] Cloud download of {} failed: {}", game_name, result.error.unwrap_or_default()),
+            Err(e) => app_log!("[Ludusavi] Cloud download of {} failed: {}", game_name, e),
+            Ok(_) => {}
+        }
+    }
+
+    let snapshots = SnapshotManager::new(backup_path.clone());
+    let available = restore::restorable_files(&snapshots, &game_name, &snapshot_id)?;
+    let selected = restore::select_files(&available, &files);
+    if selected.is_empty() {
+        return Err(format!("None of the selected files are in snapshot {}", snapshot_id));
+    }
+    // Zip backups are read from their archive; one that won't open is refused before anything changes
+    let mut archives = restore::open_archives(&selected)?;
+
+    // Not while a backup runs, it would read the saves halfway through being replaced
+    let _slot = state.backup_slot.lock().await;
+
+    // Keep the current saves so the partial restore can be undone
+    let safety_snapshot_id = snapshot_before_restore(&state, &ludusavi, &snapshots, &game_name).await?;
+
+    let (restored, failed) = restore::restore_files(&selected, &mut archives);
+    record_backup_history(&state, BackupHistoryEntry {
+        id: None,
+        game_name: game_name.clone(),
+        app_id: None,
+        kind: "restore".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        success: failed.is_empty(),
+        files_count: Some(restored.len() as i64),
+        total_size: None,
+        location: Some(snapshot_id.clone()),
+        error: if failed.is_empty() { None } else { Some(failed.join("\n")) },
+    });
+
+    Ok(SelectiveRestoreResult {
+        game_name,
+        snapshot_id,
+        restored,
+        failed,
+        safety_snapshot_id,
+    })
+}
+
+/// Roll a game's saves back to its latest Ludusavi backup in the backup path
+#[tauri::command]
+async fn restore_game_saves(game_name: String, state: State<'_, AppState>) -> Result<RestoreResult, String> {
+    restore_saves(&state, game_name, None).await
+}
+
+/// Restore one particular backup of a game, an id from list_backup_versions
+#[tauri::command]
+async fn restore_backup_version(game_name: String, backup_id: String, state: State<'_, AppState>) -> Result<RestoreResult, String> {
+    restore_saves(&state, game_name, Some(&backup_id)).await
+}
+
+/// Keep the current saves in a safety snapshot before a restore overwrites them, listed in the backup
+/// history so it can be found again. Err when they couldn't be kept, the restore mustn't go ahead.
+async fn snapshot_before_restore(
+    state: &AppState,
+    ludusavi: &LudusaviManager,
+    snapshots: &SnapshotManager,
+    game_name: &str,
+) -> Result<Option<String>, String> {
+    let snapshot = snapshots.create_safety_snapshot(ludusavi, game_name, "pre-restore").await;
+    let Some(snapshot) = restore::require_safety_snapshot(game_name, snapshot)? else {
+        app_log!("[Restore] {} has no saves yet, nothing to keep", game_name);
+        return Ok(None);
+    };
+
+    record_backup_history(state, BackupHistoryEntry {
+        id: None,
+        game_name: game_name.to_string(),
+        app_id: None,
+        kind: "pre-restore".to_string(),
+        timestamp: snapshot.created_at,
+        success: true,
+        files_count: Some(snapshot.file_count as i64),
+        total_size: Some(snapshot.total_bytes.to_string()),
+        location: Some(snapshot.id.clone()),
+        error: None,
+    });
+    Ok(Some(snapshot.id))
+}
+
+/// Restore a game's latest backup, or the one named `backup_id`, keeping the current saves in a
+/// safety snapshot first
+async fn restore_saves(state: &AppState, game_name: String, backup_id: Option<&str>) -> Result<RestoreResult, String> {
+    ensure_writable(state)?;
+    ensure_backup_key(state)?;
+
+    let (ludusavi_path, backup_path, ludusavi_cloud_enabled, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.ludusavi_path.clone(), cfg.backup_path_for(&game_name), cfg.ludusavi_cloud_sync, cfg.ludusavi_timeout_secs)
+    };
+
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+
+    let ludusavi = LudusaviManager::new(ludusavi_path, backup_path.clone()).with_timeout(ludusavi_timeout);
+
+    // A typo'd or pruned id would otherwise only show up as a failed restore
+    if let Some(backup_id) = backup_id {
+        let backups = ludusavi.list_backups(&game_name).await?;
+        if !backups.iter().any(|b| b.name == backup_id) {
+            return Err(format!("{} has no backup {}", game_name, backup_id));
+        }
+    }
+
+    // Pull Ludusavi's cloud copy first so the newest backup is the one restored
+    if ludusavi_cloud_enabled && backup_id.is_none() {
+        match ludusavi.cloud_sync(CloudDirection::Download, Some(&game_name)).await {
+            Ok(result) if !result.success => app_log!("[Ludusavi] Cloud download of {} failed: {}", game_name, result.error.unwrap_or_default()),
+            Err(e) => app_log!("[Ludusavi] Cloud download of {} failed: {}", game_name, e),
+            Ok(_) => {}
+        }
+    }
+
+    // Not while a backup runs, it would read the saves halfway through being replaced
+    let _slot = state.backup_slot.lock().await;
+
+    // Keep the current saves so the restore can be undone
+    let safety_snapshot = snapshot_before_restore(state, &ludusavi, &SnapshotManager::new(backup_path), &game_name).await?;
+
+    let result = match ludusavi.restore_backup(&game_name, backup_id).await {
+        Ok(result) => result,
+        Err(e) => {
+            // The saves may be half replaced, the history points at the snapshot that undoes it
+            let error = match &safety_snapshot {
+                Some(id) => format!("{} (the saves from before are in snapshot {})", e, id),
+                None => e,
+            };
+            record_backup_history(state, BackupHistoryEntry {
+                id: None,
+                game_name: game_name.clone(),
+                app_id: None,
+                kind: "restore".to_string(),
+                timestamp: chrono::Utc::now().timestamp(),
+                success: false,
+                files_count: None,
+                total_size: None,
+                location: safety_snapshot,
+                error: Some(error.clone()),
+            });
+            return Err(error);
+        }
+    };
+    if result.not_found.unwrap_or(false) {
+        return Err(format!("No backup of {} found in the backup path", game_name));
+    }
+
+    record_backup_history(state, BackupHistoryEntry {
+        id: None,
+        game_name: game_name.clone(),
+        app_id: None,
+        kind: "restore".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        success: result.success,
+        files_count: result.files_restored.map(|count| count as i64),
+        total_size: None,
+        location: None,
+        error: result.error.clone(),
+    });
+
+    Ok(result)
+}
+
+/// Back up a game right away, the same way as when the monitor sees it close
+#[tauri::command]
+async fn backup_game_now(game_name: String, state: State<'_, AppState>, app: tauri::AppHandle) -> Result<(), String> {
+    ensure_writable(&state)?;
+    if state.config.lock_or_recover().get_all().ludusavi_path.is_empty() {
+        return Err("Ludusavi path not configured".to_string());
+    }
+    if state.running_backups.lock_or_recover().contains_key(&game_name) {
+        return Err(format!("{} is already being backed up", game_name));
+    }
+    if state.queued_backups.lock_or_recover().contains(&game_name) {
+        return Err(format!("{} is already waiting to be backed up", game_name));
+    }
+
+    let app_id = find_app_id_by_name(&state, &game_name);
+    handle_game_backup(game_name, app_id, &state, app).await;
+    Ok(())
+}
+
+/// Dry run of a backup: the files Ludusavi would save for the game, nothing is written
+#[tauri::command]
+async fn preview_backup(game_name: String, state: State<'_, AppState>) -> Result<BackupPreview, String> {
+    let ludusavi_path = configured_ludusavi_path(&state)?;
+    let (backup_path, ludusavi_timeout) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.backup_path_for(&game_name), cfg.ludusavi_timeout_secs)
+    };
+    LudusaviManager::new(ludusavi_path, backup_path).with_timeout(ludusavi_timeout).preview(&game_name).await
+}
+
+/// What backup_all_games went through; each game's result arrives as its own BackupFinished event
+#[derive(Debug, Serialize)]
+struct LibraryBackupSummary {
+    backed_up: usize, // games Ludusavi backed up successfully
+    failed: Vec<String>, // failed, or Ludusavi found no saves
+    excluded: usize,
+    not_in_manifest: Vec<String>, // installed games Ludusavi doesn't know
+}
+
+/// Back up every installed Steam game Ludusavi knows. The games take the backup slot one after another,
+/// so Ludusavi never runs twice at once and a game that closes meanwhile just waits its turn.
+#[tauri::command]
+async fn backup_all_games(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<LibraryBackupSummary, String> {
+    ensure_writable(&state)?;
+    run_library_backup(&state, &app).await
+}
+
+async fn run_library_backup(state: &AppState, app: &tauri::AppHandle) -> Result<LibraryBackupSummary, String> {
+    let ludusavi_path = configured_ludusavi_path(state)?;
+    let mut progress = OperationProgress::start(state, app, "backup_all", "Matching installed games to Ludusavi".to_string());
+
+    let installed: Vec<(u32, String)> = tokio::task::spawn_blocking(|| {
+        SteamMonitor::find_steam_path().map(|steam_path| steam_monitor::installed_apps(&steam_path))
+    })
+    .await
+    .map_err(|e| format!("Failed to read the Steam library: {}", e))??
+        .into_iter()
+        .filter(|installed| installed.is_game)
+        .map(|installed| (installed.app_id, installed.name))
+        .collect();
+    let excluded: Vec<u32> = state.achievement_db_path.lock_or_recover().clone()
+        .and_then(|path| AchievementDatabase::new(path).ok())
+        .and_then(|db| db.get_all_exclusions().ok())
+        .map(|exclusions| exclusions.into_iter().map(|e| e.app_id).collect())
+        .unwrap_or_default();
+    let (skipped, installed): (Vec<_>, Vec<_>) = installed.into_iter().partition(|(app_id, _)| excluded.contains(app_id));
+
+    let ludusavi_timeout = state.config.lock_or_recover().get_all().ludusavi_timeout_secs;
+    let titles = LudusaviManager::new(ludusavi_path, String::new()).with_timeout(ludusavi_timeout).match_steam_apps(&installed).await?;
+    let mut games: Vec<(u32, String)> = Vec::new();
+    let mut not_in_manifest = Vec::new();
+    for (app_id, name) in installed {
+        match titles.get(&app_id) {
+            Some(title) => games.push((app_id, title.clone())),
+            None => not_in_manifest.push(name),
+        }
+    }
+    games.sort_by(|a, b| a.1.cmp(&b.1));
+    not_in_manifest.sort();
+    app_log!("[Backup] Backing up {} installed games, {} unknown to Ludusavi", games.len(), not_in_manifest.len());
+
+    let total = games.len() as u64;
+    let mut backed_up = 0;
+    let mut failed = Vec::new();
+    for (index, (app_id, title)) in games.iter().enumerate() {
+        progress.update("backing_up", index as u64, Some(total), format!("Backing up {}", title));
+        let busy = state.running_backups.lock_or_recover().contains_key(title)
+            || state.queued_backups.lock_or_recover().contains(title);
+        if busy {
+            continue;
+        }
+        if handle_game_backup(title.clone(), Some(*app_id), state, app.clone()).await {
+            backed_up += 1;
+        } else {
+            failed.push(title.clone());
+        }
+    }
+
+    let summary = LibraryBackupSummary {
+        backed_up,
+        failed,
+        excluded: skipped.len(),
+        not_in_manifest,
+    };
+    progress.finish(format!("Backed up {} games", summary.backed_up));
+    Ok(summary)
+}
+
+#[derive(Debug, Serialize)]
+struct ScheduledBackupInfo {
+    next_run: Option<i64>, // unix seconds, None when no schedule is set
+    waiting_for_game: bool, // a run came due while a game was running and starts once it closes
+}
+
+/// When the scheduled library backup runs next
+#[tauri::command]
+fn next_scheduled_backup(state: State<'_, AppState>) -> ScheduledBackupInfo {
+    let schedule = state.config.lock_or_recover().get_all().library_backup_schedule;
+    let next_run = schedule
+        .and_then(|schedule| schedule.next_run_after(chrono::Local::now().naive_local()))
+        .and_then(|next| chrono::Local.from_local_datetime(&next).earliest())
+        .map(|next| next.timestamp());
+    ScheduledBackupInfo {
+        next_run,
+        waiting_for_game: *state.library_backup_pending.lock_or_recover(),
+    }
+}
+
+/// Called every minute: starts the scheduled library backup once it's due and no game is running
+async fn check_library_backup_schedule(state: &AppState, app_handle: &tauri::AppHandle, last_check: &mut chrono::NaiveDateTime) {
+    let now = chrono::Local::now().naive_local();
+    let schedule = state.config.lock_or_recover().get_all().library_backup_schedule;
+    let came_due = schedule
+        .as_ref()
+        .and_then(|schedule| schedule.next_run_after(*last_check))
+        .is_some_and(|next| next <= now);
+    *last_check = now;
+
+    {
+        let mut pending = state.library_backup_pending.lock_or_recover();
+        if schedule.is_none() {
+            *pending = false;
+            return;
+        }
+        if came_due {
+            *pending = true;
+        }
+        if !*pending {
+            return;
+        }
+    }
+
+    if let Some((game_name, _)) = state.current_game.read().await.as_ref() {
+        if came_due {
+            app_log!("[Schedule] Library backup due, waiting for {} to close", game_name);
+        }
+        return;
+    }
+
+    *state.library_backup_pending.lock_or_recover() = false;
+    app_log!("[Schedule] Starting scheduled library backup");
+    match run_library_backup(state, app_handle).await {
+        Ok(summary) => app_log!("[Schedule] Library backup finished: {} games", summary.backed_up),
+        Err(e) => app_log!("[Schedule] Library backup failed: {}", e),
+    }
+}
+
+/// Stop a running backup; Ludusavi is killed and the backup reported as failed
+#[tauri::command]
+fn cancel_backup(game_name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let running = state.running_backups.lock_or_recover();
+    let cancel = running.get(&game_name).ok_or_else(|| format!("{} isn't being backed up", game_name))?;
+    cancel.cancel();
+    app_log!("Cancelling backup of {}", game_name);
+    Ok(())
+}
+
+/// The dedup store a game's backups are ingested into: the one in its own backup folder when it has one
+fn dedup_store(state: &AppState, game_name: &str) -> Result<DedupStore, String> {
+    let backup_path = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_path_for(game_name)
+    };
+    if backup_path.is_empty() {
+        return Err("Backup path is not configured".to_string());
+    }
+    Ok(DedupStore::new(backup_path))
+}
+
+/// Add a game's new Ludusavi versions to its dedup store and prune it to `keep` snapshots.
+/// Callers hold backup_slot. Failures are only logged, the backup itself is already done.
+async fn ingest_into_dedup_store(game_name: &str, backup_path: &str, keep: Option<usize>) {
+    let store = DedupStore::new(backup_path.to_string());
+    let store_game_name = game_name.to_string();
+    let ingested = tokio::task::spawn_blocking(move || {
+        store.ingest_new_snapshots(&store_game_name)?;
+        match keep {
+            Some(keep) => store.prune(&store_game_name, keep).map(|_| ()),
+            None => Ok(()),
+        }
+    }).await;
+    match ingested {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => app_log!("[Dedup] Failed to store {}: {}", game_name, e),
+        Err(e) => app_log!("[Dedup] Failed to store {}: {}", game_name, e),
+    }
+}
+
+#[tauri::command]
+fn list_dedup_snapshots(game_name: String, state: State<'_, AppState>) -> Result<Vec<DedupSnapshotInfo>, String> {
+    dedup_store(&state, &game_name)?.list_snapshots(&game_name)
+}
+
+#[tauri::command]
+async fn extract_dedup_snapshot(
+    game_name: String,
+    snapshot_id: String,
+    target_dir: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    ensure_writable(&state)?;
+    ensure_backup_key(&state)?;
+    let store = dedup_store(&state, &game_name)?;
+    tokio::task::spawn_blocking(move || store.extract_snapshot(&game_name, &snapshot_id, std::path::Path::new(&target_dir)))
+        .await
+        .map_err(|e| format!("Extraction failed: {}", e))?
+}
+
+#[tauri::command]
+async fn delete_dedup_snapshot(game_name: String, snapshot_id: String, state: State<'_, AppState>) -> Result<u64, String> {
+    ensure_writable(&state)?;
+    let store = dedup_store(&state, &game_name)?;
+    // Garbage collection must not run while a backup is ingesting new chunks
+    let _slot = state.backup_slot.lock().await;
+    tokio::task::spawn_blocking(move || store.delete_snapshot(&game_name, &snapshot_id))
+        .await
+        .map_err(|e| format!("Delete failed: {}", e))?
+}
+
+#[tauri::command]
+async fn get_dedup_stats(state: State<'_, AppState>) -> Result<DedupStats, String> {
+    let roots = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_roots()
+    };
+    if roots[0].is_empty() {
+        return Err("Backup path is not configured".to_string());
+    }
+    // Games with their own backup folder have their own store there
+    tokio::task::spawn_blocking(move || {
+        roots.into_iter()
+            .map(|root| DedupStore::new(root).stats())
+            .fold(DedupStats::default(), |mut total, stats| {
+                total.snapshot_count += stats.snapshot_count;
+                total.blob_count += stats.blob_count;
+                total.stored_bytes += stats.stored_bytes;
+                total.logical_bytes += stats.logical_bytes;
+                total
+            })
+    })
+        .await
+        .map_err(|e| format!("Failed to read dedup store: {}", e))
+}
+
+/// Size of the backup folder and space left on its drive or share
+#[tauri::command]
+async fn get_backup_stats(state: State<'_, AppState>) -> Result<BackupStats, String> {
+    let (backup_path, threshold_mb) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.backup_path, cfg.low_disk_space_mb)
+    };
+    if backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+    tokio::task::spawn_blocking(move || disk_space::backup_stats(&backup_path, threshold_mb))
+        .await
+        .map_err(|e| format!("Failed to read backup stats: {}", e))
+}
+
+/// Disk usage, kept versions and last backup of every game with backups, largest first
+#[tauri::command]
+async fn get_storage_dashboard(state: State<'_, AppState>) -> Result<disk_space::StorageDashboard, String> {
+    let cfg = state.config.lock_or_recover().get_all();
+    if cfg.backup_path.is_empty() {
+        return Err("Backup path not configured".to_string());
+    }
+    let now = chrono::Utc::now().timestamp();
+    tokio::task::spawn_blocking(move || disk_space::storage_dashboard(&cfg.backup_path, &cfg.game_backup_paths, now))
+        .await
+        .map_err(|e| format!("Failed to read backup storage: {}", e))
+}
+
+/// Backups, restores, uploads and verification results for one game, newest first
+#[tauri::command]
+async fn get_backup_history(app_id: u32, limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<BackupHistoryEntry>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_backup_history(app_id, limit.unwrap_or(100))
+}
+
+/// Latest history entries across all games, for the backup timeline
+#[tauri::command]
+async fn get_recent_backups(limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<BackupHistoryEntry>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_recent_backup_history(limit.unwrap_or(50))
+}
+
+#[tauri::command]
+async fn get_backup_freshness(state: State<'_, AppState>) -> Result<Vec<BackupFreshness>, String> {
+    backup_freshness_report(&state)
+}
+
+// Backup totals per game, with `stale` set from the configured thresholds
+fn backup_freshness_report(state: &AppState) -> Result<Vec<BackupFreshness>, String> {
+    let (stale_days, min_plays) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.stale_backup_days, cfg.stale_backup_min_plays)
+    };
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let now = chrono::Utc::now().timestamp();
+    let mut report = AchievementDatabase::new(db_path)?
+        .get_backup_freshness(backup_freshness::window_start(now, stale_days))?;
+    backup_freshness::mark_stale(&mut report, now, stale_days, min_plays);
+    Ok(report)
+}
+
+/// Warn about regularly played games whose backups keep failing or being skipped. `warned` remembers
+/// the last successful backup each warning was about, so a game is only reported again after it changes.
+fn check_stale_backups(state: &AppState, app_handle: &tauri::AppHandle, warned: &mut HashMap<String, Option<i64>>) {
+    let report = match backup_freshness_report(state) {
+        Ok(report) => report,
+        Err(e) => {
+            app_log!("[Freshness] {}", e);
+            return;
+        }
+    };
+
+    warned.retain(|game_name, _| report.iter().any(|entry| entry.stale && &entry.game_name == game_name));
+    let newly_stale: Vec<&BackupFreshness> = report.iter()
+        .filter(|entry| entry.stale && warned.get(&entry.game_name) != Some(&entry.last_success))
+        .collect();
+    if newly_stale.is_empty() {
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for entry in &newly_stale {
+        app_log!("[Freshness] {} played {} times, last successful backup {}",
+            entry.game_name, entry.recent_attempts, backup_freshness::age_label(entry.last_success, now));
+        warned.insert(entry.game_name.clone(), entry.last_success);
+    }
+    publish_event(state, app_handle, AppEvent::StaleBackups {
+        games: newly_stale.iter().map(|entry| entry.game_name.clone()).collect(),
+    });
+    if state.config.lock_or_recover().get_all().notifications_enabled {
+        let lines: Vec<String> = newly_stale.iter()
+            .map(|entry| format!("{}: last backup {}", entry.game_name, backup_freshness::age_label(entry.last_success, now)))
+            .collect();
+        state.notification_manager.lock_or_recover().show_stale_backups(&lines);
+    }
+}
+
+/// Look for Steam games uninstalled since the last check (also while the app was closed). Each one gets an event
+/// and a notification; with backup_on_uninstall its saves, which usually outlive the game folder, get a final backup.
+async fn check_uninstalled_games(state: &AppState, app_handle: &tauri::AppHandle) {
+    let Some(db_path) = state.achievement_db_path.lock_or_recover().clone() else { return };
+    let scanned = tokio::task::spawn_blocking(|| {
+        SteamMonitor::find_steam_path().map(|steam_path| {
+            (steam_monitor::installed_apps(&steam_path), steam_monitor::unreachable_library_apps(&steam_path))
+        })
+    }).await;
+    let (apps, unreachable) = match scanned {
+        Ok(Ok(scanned)) => scanned,
+        Ok(Err(e)) => {
+            app_log!("[Uninstall] {}", e);
+            return;
+        }
+        Err(e) => {
+            app_log!("[Uninstall] Library scan failed: {}", e);
+            return;
+        }
+    };
+    // Only reachable libraries are scanned; games in one that's offline aren't counted as uninstalled
+    let installed: Vec<(u32, String)> = apps
+        .into_iter()
+        .filter(|installed| installed.is_game)
+        .map(|installed| (installed.app_id, installed.name))
+        .collect();
+    // No games found at all (Steam moved, or its own drive offline) would look like everything got uninstalled
+    if installed.is_empty() {
+        return;
+    }
+
+    let (uninstalled, excluded) = match AchievementDatabase::new(db_path) {
+        Ok(db) => {
+            let excluded: Vec<u32> = db.get_all_exclusions().map(|e| e.into_iter().map(|e| e.app_id).collect()).unwrap_or_default();
+            match db.record_installed_games(&installed, &unreachable) {
+                Ok(uninstalled) => (uninstalled, excluded),
+                Err(e) => {
+                    app_log!("[Uninstall] {}", e);
+                    return;
+                }
+            }
+        }
+        Err(e) => {
+            app_log!("[Uninstall] {}", e);
+            return;
+        }
+    };
+
+    let (notifications_enabled, final_backup) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.notifications_enabled, cfg.backup_on_uninstall && !cfg.ludusavi_path.is_empty() && !cfg.read_only_mode)
+    };
+    for (app_id, name) in uninstalled.into_iter().filter(|(app_id, _)| !excluded.contains(app_id)) {
+        app_log!("[Uninstall] {} ({}) is no longer installed", name, app_id);
+        publish_event(state, app_handle, AppEvent::GameUninstalled { app_id, name: name.clone(), final_backup });
+        if notifications_enabled {
+            state.notification_manager.lock_or_recover().show_game_uninstalled(&name, final_backup);
+        }
+        if final_backup {
+            if let Err(e) = archive_game(state, app_handle.clone(), app_id, name).await {
+                app_log!("[Uninstall] {}", e);
+            }
+        }
+    }
+}
+
+// Final backup of an uninstalled game's saves; it's only marked archived once that backup succeeded
+async fn archive_game(state: &AppState, app_handle: tauri::AppHandle, app_id: u32, name: String) -> Result<(), String> {
+    let started = chrono::Utc::now().timestamp();
+    handle_game_backup(name.clone(), Some(app_id), state, app_handle).await;
+
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let db = AchievementDatabase::new(db_path)?;
+    let backed_up = db.get_backup_history(app_id, 1)?
+        .first()
+        .is_some_and(|entry| entry.kind == "backup" && entry.success && entry.timestamp >= started);
+    if !backed_up {
+        return Err(format!("The final backup of {} didn't succeed, it stays unarchived", name));
+    }
+    db.mark_game_archived(app_id)?;
+    app_log!("[Uninstall] Archived {}", name);
+    Ok(())
+}
+
+/// Steam games that were uninstalled, and whether their saves got a final backup
+#[tauri::command]
+async fn get_uninstalled_games(state: State<'_, AppState>) -> Result<Vec<UninstalledGame>, String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    AchievementDatabase::new(db_path)?.get_uninstalled_games()
+}
+
+/// Back up an uninstalled game's saves one last time and mark it archived
+#[tauri::command]
+async fn archive_uninstalled_game(app_id: u32, state: State<'_, AppState>, app: tauri::AppHandle) -> Result<(), String> {
+    ensure_writable(&state)?;
+    configured_ludusavi_path(&state)?;
+    let db_path = state.achievement_db_path.lock_or_recover().clone()
+        .ok_or("Achievement database not initialized")?;
+    let game = AchievementDatabase::new(db_path)?.get_uninstalled_games()?
+        .into_iter()
+        .find(|game| game.app_id == app_id)
+        .ok_or_else(|| format!("App {} isn't an uninstalled game", app_id))?;
+    archive_game(&state, app, game.app_id, game.name).await
+}
+
+#[tauri::command]
+async fn run_backup_verification(state: State<'_, AppState>) -> Result<VerificationReport, String> {
+    run_verification(&state).await
+}
+
+#[tauri::command]
+async fn discover_save_paths(game_name: String, state: State<'_, AppState>) -> Result<Vec<SavePathCandidate>, String> {
+    let (ludusavi_path, game_executables, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.ludusavi_path, cfg.game_executables, cfg.ludusavi_timeout_secs)
+    };
+
+    // The manifest is optional - games unknown to Ludusavi still get process and heuristic suggestions
+    let manifest_entry = if ludusavi_path.is_empty() {
+        None
+    } else {
+        match LudusaviManager::new(ludusavi_path, String::new()).with_timeout(ludusavi_timeout).get_manifest_entry(&game_name).await {
+            Ok(entry) => entry,
+            Err(e) => {
+                app_log!("Could not read Ludusavi manifest for {}: {}", game_name, e);
+                None
+            }
+        }
+    };
+
+    tokio::task::spawn_blocking(move || {
+        SavePathDiscovery::new(&game_name).discover(manifest_entry.as_ref(), &game_executables)
+    })
+    .await
+    .map_err(|e| format!("Save path discovery failed: {}", e))
+}
+
+/// Back up and watch a discovered save folder. It becomes part of the game's Ludusavi custom game, so
+/// it's backed up even when the manifest doesn't know the game.
+#[tauri::command]
+async fn confirm_save_path(
+    game_name: String,
+    path: String,
+    state: State<'_, AppState>,
+    window: WebviewWindow,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+    if !std::path::Path::new(&path).is_dir() {
+        return Err(format!("Folder does not exist: {}", path));
+    }
+
+    ludusavi_config::add_custom_game_files(&configured_ludusavi_path(&state)?, &game_name, std::slice::from_ref(&path))?;
+
+    {
+        let mut config = state.config.lock_or_recover();
+        let mut cfg = config.get_all();
+        let paths = cfg.custom_save_paths.entry(game_name.clone()).or_default();
+        if !paths.iter().any(|p| p.eq_ignore_ascii_case(&path)) {
+            paths.push(path.clone());
+        }
+        config.set_all(cfg);
+    }
+
+    app_log!("Confirmed save path for {}: {}", game_name, path);
+
+    // Restart monitors so the new folder is watched
+    stop_monitors(&state).await;
+    start_monitors(&state, window).await;
+
+    Ok(())
+}
+
+fn find_cloud_destination(state: &AppState, destination_id: &str) -> Result<CloudDestination, String> {
+    let config = state.config.lock_or_recover();
+    config.get_all().cloud_destinations.into_iter()
+        .find(|d| d.id == destination_id)
+        .ok_or_else(|| format!("Unknown cloud destination: {}", destination_id))
+}
+
+/// Trust an SFTP server's host key on first use: fetch its fingerprint and pin it in the
+/// destination's config so later connections refuse a different key. Only called from commands
+/// the user runs on that destination, never from background uploads.
+async fn pin_sftp_host_key(state: &AppState, mut destination: CloudDestination) -> Result<CloudDestination, String> {
+    let CloudProvider::Sftp { host, port, host_key, .. } = &mut destination.provider else { return Ok(destination) };
+    if host_key.is_some() {
+        return Ok(destination);
+    }
+
+    let fingerprint = network::fetch_sftp_host_key(host, *port).await?;
+    app_log!("[Cloud] Pinned host key of {} for {}: {}", host, destination.name, fingerprint);
+    *host_key = Some(fingerprint.clone());
+
+    let mut config = state.config.lock_or_recover();
+    let mut cfg = config.get_all();
+    if let Some(CloudProvider::Sftp { host_key, .. }) = cfg.cloud_destinations.iter_mut()
+        .find(|d| d.id == destination.id)
+        .map(|d| &mut d.provider)
+    {
+        host_key.get_or_insert(fingerprint);
+    }
+    config.set_all(cfg);
+    Ok(destination)
+}
+
+#[tauri::command]
+async fn start_cloud_auth(destination_id: String, state: State<'_, AppState>) -> Result<DeviceAuthorization, String> {
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    cloud::start_authorization(&destination).await
+}
+
+#[tauri::command]
+async fn complete_cloud_auth(
+    destination_id: String,
+    authorization: DeviceAuthorization,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    cloud::complete_authorization(&destination, &authorization).await
+}
+
+#[tauri::command]
+fn sign_out_cloud_destination(destination_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    cloud::sign_out(&destination)
+}
+
+#[tauri::command]
+async fn test_cloud_destination(destination_id: String, state: State<'_, AppState>) -> Result<Option<StorageQuota>, String> {
+    let destination = pin_sftp_host_key(&state, find_cloud_destination(&state, &destination_id)?).await?;
+    cloud::test_connection(&destination).await
+}
+
+#[tauri::command]
+fn get_dropbox_authorize_url(destination_id: String, state: State<'_, AppState>) -> Result<String, String> {
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    match &destination.provider {
+        CloudProvider::Dropbox { app_key, .. } => Ok(DropboxClient::authorize_url(app_key)),
+        _ => Err(format!("{} is not a Dropbox destination", destination.name)),
+    }
+}
+
+#[tauri::command]
+async fn complete_dropbox_auth(destination_id: String, code: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    match &destination.provider {
+        CloudProvider::Dropbox { app_key, app_secret, .. } => {
+            DropboxClient::complete_authorization(&destination_id, app_key, app_secret, &code).await
+        }
+        _ => Err(format!("{} is not a Dropbox destination", destination.name)),
+    }
+}
+
+#[tauri::command]
+fn set_cloud_destination_secret(destination_id: String, secret: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    cloud::store_secret(&destination, &secret)
+}
+
+#[tauri::command]
+fn set_cloud_destination_enabled(destination_id: String, enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    let mut config = state.config.lock_or_recover();
+    let mut cfg = config.get_all();
+    let destination = cfg.cloud_destinations.iter_mut()
+        .find(|d| d.id == destination_id)
+        .ok_or_else(|| format!("Unknown cloud destination: {}", destination_id))?;
+    destination.enabled = enabled;
+    config.set_all(cfg);
+    Ok(())
+}
+
+#[tauri::command]
+async fn list_google_drive_folders(destination_id: String, state: State<'_, AppState>) -> Result<Vec<DriveFolder>, String> {
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    GoogleDriveClient::for_destination(&destination).await?.list_folders().await
+}
+
+#[tauri::command]
+async fn create_google_drive_folder(destination_id: String, name: String, state: State<'_, AppState>) -> Result<DriveFolder, String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    GoogleDriveClient::for_destination(&destination).await?.create_folder(&name).await
+}
+
+#[tauri::command]
+async fn upload_game_backup_to_cloud(
+    game_name: String,
+    destination_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<UploadResult>, String> {
+    ensure_writable(&state)?;
+    let destination = pin_sftp_host_key(&state, find_cloud_destination(&state, &destination_id)?).await?;
+    let backup_path = {
+        let config = state.config.lock_or_recover();
+        config.get_all().backup_path_for(&game_name)
+    };
+
+    let archive_name = backup_archive_name(&state, &game_name, "manual");
+    cloud::upload_game_backup(&backup_path, &game_name, &archive_name, &[destination]).await
+}
+
+/// Compare a game's local backup with its copy on an rclone destination: which side is newer,
+/// or whether both changed, and what can be done about it
+#[tauri::command]
+async fn get_cloud_conflict(game_name: String, destination_id: String, state: State<'_, AppState>) -> Result<CloudConflict, String> {
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    let backup_path = state.config.lock_or_recover().get_all().backup_path_for(&game_name);
+    cloud_conflicts::describe(&destination, &backup_path, &game_name).await
+}
+
+#[tauri::command]
+async fn resolve_cloud_conflict(
+    game_name: String,
+    destination_id: String,
+    action: ResolutionAction,
+    state: State<'_, AppState>,
+) -> Result<CloudConflict, String> {
+    ensure_writable(&state)?;
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    let backup_path = state.config.lock_or_recover().get_all().backup_path_for(&game_name);
+
+    app_log!("[Cloud] Resolving {} on {} with {:?}", game_name, destination.name, action);
+    match action {
+        ResolutionAction::Upload => {
+            let result = cloud::mirror_game_backup(&destination, &backup_path, &game_name).await;
+            if let Some(error) = result.error {
+                return Err(error);
+            }
+        }
+        ResolutionAction::Download => {
+            let folder_name = LudusaviManager::game_folder_name(&game_name);
+            rclone::copy_from_remote(&destination, &folder_name, &Path::new(&backup_path).join(&folder_name)).await?;
+        }
+    }
+
+    cloud_conflicts::describe(&destination, &backup_path, &game_name).await
+}
+
+/// Drive the cloud configured in Ludusavi itself; without a game name every game is synced
+#[tauri::command]
+async fn ludusavi_cloud_sync(
+    direction: CloudDirection,
+    game_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<LudusaviCloudResult, String> {
+    if direction == CloudDirection::Download {
+        ensure_writable(&state)?;
+    }
+
+    let (ludusavi_path, backup_path, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.ludusavi_path, cfg.backup_path, cfg.ludusavi_timeout_secs)
+    };
+    LudusaviManager::new(ludusavi_path, backup_path).with_timeout(ludusavi_timeout).cloud_sync(direction, game_name.as_deref()).await
+}
+
+#[tauri::command]
+async fn get_ludusavi_cloud_status(game_name: String, state: State<'_, AppState>) -> Result<LudusaviCloudStatus, String> {
+    let (ludusavi_path, backup_path, ludusavi_timeout) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.ludusavi_path.clone(), cfg.backup_path_for(&game_name), cfg.ludusavi_timeout_secs)
+    };
+    LudusaviManager::new(ludusavi_path, backup_path).with_timeout(ludusavi_timeout).cloud_status(&game_name).await
+}
+
+#[tauri::command]
+async fn get_cloud_quota(destination_id: String, state: State<'_, AppState>) -> Result<StorageQuota, String> {
+    let destination = find_cloud_destination(&state, &destination_id)?;
+    cloud::get_quota(&destination).await
+}
+
+#[tauri::command]
+fn get_pending_uploads(state: State<'_, AppState>) -> Vec<PendingUpload> {
+    state.pending_uploads.lock_or_recover().clone()
+}
+
+#[tauri::command]
+async fn test_syncthing_connection(state: State<'_, AppState>) -> Result<String, String> {
+    let settings = {
+        let config = state.config.lock_or_recover();
+        config.get_all().syncthing
+    };
+
+    if settings.api_key.is_empty() || settings.folder_id.is_empty() {
+        return Err("Enter the Syncthing API key and folder id first".to_string());
+    }
+
+    SyncthingClient::new(settings).test_connection().await
+}
+
+#[tauri::command]
+fn read_audio_file(file_path: String) -> Result<Vec<u8>, String> {
+    use std::fs;
+
+    app_log!("[OVERLAY DEBUG] Reading audio file: {}", file_path);
+
+    match fs::read(&file_path) {
+        Ok(bytes) => {
+            app_log!("[OVERLAY DEBUG] Successfully read {} bytes", bytes.len());
+            Ok(bytes)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to read audio file: {}", e);
+            app_log!("[OVERLAY DEBUG] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+#[tauri::command]
+async fn test_overlay(state: State<'_, AppState>) -> Result<(), String> {
+    // Use NotificationManager to show achievement on overlay
+    state.notification_manager.lock_or_recover().show_achievement_unlock(
+        "Test Game",
+        "First Steps",
+        "Complete the tutorial",
+        Some("https://cdn.cloudflare.steamstatic.com/steamcommunity/public/images/apps/default_icon.jpg"),
+        Some(85.0) // Uncommon rarity for testing
+    );
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_achievement_duration(state: State<'_, AppState>) -> Result<u32, String> {
+    let config = state.config.lock_or_recover();
+    Ok(config.get_all().achievement_duration)
+}
+
+#[tauri::command]
+async fn set_achievement_duration(duration: u32, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_writable(&state)?;
+    // Update in-memory duration for notification manager
+    *state.achievement_duration.lock_or_recover() = duration;
+
+    // Save to config file
+    let mut config = state.config.lock_or_recover();
+    let mut cfg = config.get_all();
+    cfg.achievement_duration = duration;
+    config.set_all(cfg);
+
+    app_log!("[Backend] Achievement duration set to {} seconds and saved to config", duration);
+    Ok(())
+}
+
+#[tauri::command]
+async fn reset_game_monitoring(state: State<'_, AppState>, window: WebviewWindow) -> Result<(), String> {
+    ensure_writable(&state)?;
+    app_log!("Resetting game monitoring...");
+
+    // Get current game info before stopping
+    let current_game = state.current_game.read().await.clone();
+
+    // Stop all monitors
+    stop_monitors(&state).await;
+
+    // Small delay to ensure clean stop
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    // Restart monitors
+    start_monitors(&state, window).await;
+
+    if let Some((game_name, _)) = current_game {
+        app_log!("Game monitoring reset for: {}", game_name);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_game_monitoring(state: State<'_, AppState>) -> Result<(), String> {
+    let game_info = state.current_game.write().await.take();
+
+    if let Some((game_name, app_id)) = game_info {
+        app_log!("Stopping monitoring for: {} (AppID: {})", game_name, app_id);
+
+        // Stop watching this specific game
+        if let Some(watcher) = state.achievement_watcher.read().await.as_ref() {
+            watcher.stop_watching_game(app_id);
+        }
+
+        Ok(())
+    } else {
+        Err("No game is currently being monitored".to_string())
+    }
+}
+
+#[tauri::command]
+async fn get_current_game(state: State<'_, AppState>) -> Result<Option<(String, u32)>, String> {
+    Ok(state.current_game.read().await.clone())
+}
+
+#[tauri::command]
+async fn sync_settings_to_overlay(achievement_settings: serde_json::Value, rarity_settings: serde_json::Value, app: tauri::AppHandle) -> Result<(), String> {
+    // Emit settings to ALL windows (including overlay)
+    app.emit("achievement-settings-sync", &achievement_settings)
+        .map_err(|e| format!("Failed to emit achievement settings: {}", e))?;
+
+    app.emit("rarity-settings-sync", &rarity_settings)
+        .map_err(|e| format!("Failed to emit rarity settings: {}", e))?;
 
     println!("[Backend] Settings synced to all windows");
     Ok(())
 }
 
-#[tauri::command]
-async fn test_rarity_notification(rarity: String, state: State<'_, AppState>) -> Result<(), String> {
-    // Map rarity percentage for testing
-    let (name, description, percentage) = match rarity.as_str() {
-        "Common" => ("Common Achievement", "30%+ of players have this", 35.0),
-        "Uncommon" => ("Uncommon Achievement", "20-29% of players have this", 25.0),
-        "Rare" => ("Rare Achievement", "13-19% of players have this", 15.0),
-        "Ultra Rare" => ("Ultra Rare Achievement", "5-12% of players have this", 8.0),
-        "Legendary" => ("Legendary Achievement", "0-4% of players have this", 2.0),
-        _ => ("Test Achievement", "Unknown rarity", 50.0),
+#[tauri::command]
+async fn test_rarity_notification(rarity: String, state: State<'_, AppState>) -> Result<(), String> {
+    // Map rarity percentage for testing
+    let (name, description, percentage) = match rarity.as_str() {
+        "Common" => ("Common Achievement", "30%+ of players have this", 35.0),
+        "Uncommon" => ("Uncommon Achievement", "20-29% of players have this", 25.0),
+        "Rare" => ("Rare Achievement", "13-19% of players have this", 15.0),
+        "Ultra Rare" => ("Ultra Rare Achievement", "5-12% of players have this", 8.0),
+        "Legendary" => ("Legendary Achievement", "0-4% of players have this", 2.0),
+        _ => ("Test Achievement", "Unknown rarity", 50.0),
+    };
+
+    // Use NotificationManager to show achievement on overlay with rarity percentage
+    state.notification_manager.lock_or_recover().show_achievement_unlock(
+        "Test Game",
+        name,
+        description,
+        Some("https://cdn.cloudflare.steamstatic.com/steamcommunity/public/images/apps/default_icon.jpg"),
+        Some(percentage)
+    );
+
+    Ok(())
+}
+
+// Helper functions
+fn get_steam_library_folders() -> Result<Vec<PathBuf>, String> {
+    let steam_path = PathBuf::from(r"C:\Program Files (x86)\Steam");
+    let mut folders = vec![steam_path.clone()];
+
+    let libraryfolders_path = steam_path.join("steamapps").join("libraryfolders.vdf");
+    if let Ok(contents) = std::fs::read_to_string(&libraryfolders_path) {
+        if let Ok(re) = regex::Regex::new(r#""path"\s+"([^"]+)""#) {
+            for cap in re.captures_iter(&contents) {
+                if let Some(path_match) = cap.get(1) {
+                    let path_str = path_match.as_str().replace("\\\\", "\\");
+                    let path = PathBuf::from(path_str);
+                    if path.exists() && !folders.contains(&path) {
+                        folders.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(folders)
+}
+
+fn parse_appmanifest_basic(manifest_path: &PathBuf) -> Option<(u32, String)> {
+    if let Ok(contents) = std::fs::read_to_string(manifest_path) {
+        let app_id_re = regex::Regex::new(r#""appid"\s+"(\d+)""#).ok()?;
+        let name_re = regex::Regex::new(r#""name"\s+"([^"]+)""#).ok()?;
+
+        let app_id = app_id_re.captures(&contents)
+            .and_then(|cap| cap.get(1))
+            .and_then(|m| m.as_str().parse::<u32>().ok())?;
+
+        let name = name_re.captures(&contents)
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())?;
+
+        Some((app_id, name))
+    } else {
+        None
+    }
+}
+
+// Helper function to export achievements for a game
+async fn export_achievements_internal(
+    app_id: u32,
+    game_name: &str,
+    state: &AppState,
+) -> Result<usize, String> {
+    // Get database
+    let db = {
+        let path_guard = state.achievement_db_path.lock_or_recover();
+        match &*path_guard {
+            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+            None => None,
+        }
+    };
+
+    let db = match db {
+        Some(db) => db,
+        None => return Err("Achievement database not initialized".to_string()),
+    };
+
+    // Get all achievements for this game
+    let all_achievements = db.get_game_achievements(app_id)?;
+
+    if !all_achievements.iter().any(|a| a.achieved) {
+        return Ok(0); // No achievements to export
+    }
+
+    let (_, unlocked_count) = save_achievement_backup(app_id, game_name, &all_achievements)?;
+
+    app_log!("Exported {} achievements for {}", unlocked_count, game_name);
+    Ok(unlocked_count)
+}
+
+// Helper function to write a game's unlocked achievements to its app id backup folder
+fn save_achievement_backup(app_id: u32, game_name: &str, achievements: &[Achievement]) -> Result<(PathBuf, usize), String> {
+    let (contents, unlocked) = achievement_export::render(ExportFormat::SteamUnlockTime, achievements)?;
+    let source = achievements.iter()
+        .find(|a| a.achieved)
+        .map(|a| a.source.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let path = achievement_backups::save(&BackupMetadata {
+        game_name: game_name.to_string(),
+        app_id,
+        source,
+        timestamp: chrono::Utc::now().timestamp(),
+        unlocked,
+    }, contents.as_bytes())?;
+    Ok((path, unlocked))
+}
+
+// Helper function to write the current unlock state of a game before it is overwritten by a restore
+fn save_pre_restore_achievements(game_name: &str, achievements: &[Achievement]) -> Result<PathBuf, String> {
+    // Same Steam API format as regular achievement backups so it can be restored directly
+    let mut steam_format = serde_json::Map::new();
+    for achievement in achievements.iter().filter(|a| a.achieved) {
+        steam_format.insert(
+            achievement.achievement_id.clone(),
+            serde_json::json!({ "UnlockTime": achievement.unlock_time.unwrap_or(0) })
+        );
+    }
+
+    let json_string = serde_json::to_string_pretty(&steam_format)
+        .map_err(|e| format!("Failed to serialize to JSON: {}", e))?;
+
+    let app_id = achievements.first().map(|a| a.app_id);
+    let file_stem = filenames::claim_stem(&user_folders::output_root().join("Steam Backup Monitor").join("Pre-restore"), game_name, app_id);
+
+    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let relative_path = Path::new("Steam Backup Monitor")
+        .join("Pre-restore")
+        .join(format!("{} {}.json", file_stem, timestamp));
+    let file_path = user_folders::write_file(&relative_path, json_string.as_bytes())?;
+
+    app_log!("Saved pre-restore snapshot for {} to {}", game_name, file_path.display());
+    Ok(file_path)
+}
+
+// Helper function to save backup date
+fn save_backup_date(game_name: &str) -> Result<(), String> {
+    let file_stem = filenames::claim_stem(&user_folders::output_root().join("Steam Save Monitor Backup dates"), game_name, None);
+
+    // File in the "Steam Save Monitor Backup dates" folder
+    let relative_path = Path::new("Steam Save Monitor Backup dates").join(format!("{}.json", file_stem));
+
+    // Get current date and time
+    let now = chrono::Local::now();
+    let date_time_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+
+    // Create JSON with single date (replaces existing)
+    let backup_data = serde_json::json!({
+        "date": date_time_str
+    });
+
+    // Serialize to JSON
+    let json_string = serde_json::to_string_pretty(&backup_data)
+        .map_err(|e| format!("Failed to serialize to JSON: {}", e))?;
+
+    // Write to file (overwrites if exists)
+    user_folders::write_file(&relative_path, json_string.as_bytes())?;
+
+    println!("Saved backup date for {}", game_name);
+    Ok(())
+}
+
+// Leaves the backup queue when dropped, also when a caller gives up waiting (e.g. the shutdown budget)
+struct QueuedBackup<'a> {
+    state: &'a AppState,
+    game_name: String,
+}
+
+impl Drop for QueuedBackup<'_> {
+    fn drop(&mut self) {
+        self.state.queued_backups.lock_or_recover().retain(|name| *name != self.game_name);
+    }
+}
+
+fn publish_backup_queue(state: &AppState, app_handle: &tauri::AppHandle) {
+    let queued = state.queued_backups.lock_or_recover().clone();
+    publish_event(state, app_handle, AppEvent::BackupQueueChanged { queued });
+}
+
+// Backups run one at a time, two Ludusavi runs at once fight over the backup folder. tokio's Mutex
+// is handed out first come first served, so waiting for backup_slot is the queue. Returns whether
+// this call backed the game up.
+async fn handle_game_backup(
+    game_name: String,
+    app_id: Option<u32>,
+    state: &AppState,
+    app_handle: tauri::AppHandle,
+) -> bool {
+    let _slot = match state.backup_slot.try_lock() {
+        Ok(slot) => slot,
+        Err(_) => {
+            {
+                let mut queued = state.queued_backups.lock_or_recover();
+                // The waiting backup will pick up the latest saves anyway
+                if queued.contains(&game_name) {
+                    app_log!("[Backup] {} is already queued", game_name);
+                    return false;
+                }
+                queued.push(game_name.clone());
+            }
+            app_log!("[Backup] {} queued behind a running backup", game_name);
+            let entry = QueuedBackup { state, game_name: game_name.clone() };
+            publish_backup_queue(state, &app_handle);
+
+            let slot = state.backup_slot.lock().await;
+            drop(entry);
+            publish_backup_queue(state, &app_handle);
+            slot
+        }
+    };
+
+    run_game_backup(game_name, app_id, state, app_handle).await
+}
+
+async fn run_game_backup(
+    game_name: String,
+    app_id: Option<u32>,
+    state: &AppState,
+    app_handle: tauri::AppHandle,
+) -> bool {
+    println!("Backing up: {}", game_name);
+
+    let (ludusavi_path, backup_path, notifications_enabled, cloud_destinations, syncthing_settings, dedup_enabled, ludusavi_cloud_enabled, ludusavi_timeout, retention, backup_cloud_sync, format, verify_backups) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        // A game with its own backup folder (another drive...) goes there instead of backup_path
+        let backup_path = cfg.backup_path_for(&game_name);
+        let destinations: Vec<CloudDestination> = cfg.cloud_destinations.into_iter().filter(|d| d.enabled).collect();
+        let retention = (cfg.backup_full_versions, cfg.backup_differential_versions);
+        let format = (cfg.ludusavi_backup_format, cfg.ludusavi_compression, cfg.ludusavi_compression_level);
+        (cfg.ludusavi_path, backup_path, cfg.notifications_enabled, destinations, cfg.syncthing, cfg.dedup_store_enabled, cfg.ludusavi_cloud_sync, cfg.ludusavi_timeout_secs, retention, cfg.ludusavi_backup_cloud_sync, format, cfg.verify_backups)
+    };
+
+    // With the dedup store on, Ludusavi keeps only the latest full backup and the store holds the
+    // versions the retention settings would have kept, so the older copies aren't on disk twice
+    let (ludusavi_retention, dedup_keep) = if dedup_enabled {
+        let keep = (retention.0 > 0).then(|| (retention.0 as usize) * (1 + retention.1 as usize));
+        ((1, 0), keep)
+    } else {
+        (retention, None)
+    };
+    if dedup_enabled {
+        // Versions from before the store was turned on are about to be pruned by Ludusavi; zip
+        // archives already in the store are skipped by the ingest
+        let snapshots = SnapshotManager::new(backup_path.clone());
+        let unstored = DedupStore::new(backup_path.clone()).list_snapshots(&game_name).map(|s| s.is_empty()).unwrap_or(true)
+            || snapshots.list_versions(&game_name)
+                .is_ok_and(|versions| versions.iter().any(|v| v.id.starts_with("backup-")))
+            || !snapshots.list_archive_versions(&game_name).is_empty();
+        if unstored {
+            ingest_into_dedup_store(&game_name, &backup_path, None).await;
+        }
+    }
+
+    let manager_path = ludusavi_path.clone();
+    let cancel = CancelToken::default();
+    let manager = LudusaviManager::new(ludusavi_path, backup_path.clone())
+        .with_timeout(ludusavi_timeout)
+        .with_retention(ludusavi_retention.0, ludusavi_retention.1)
+        .with_cloud_sync(backup_cloud_sync)
+        .with_format(format.0, format.1, format.2)
+        .with_verification(verify_backups)
+        .with_cancel(cancel.clone());
+
+    // Keep Syncthing from picking up half-written backup files
+    let syncthing = if syncthing_settings.enabled {
+        Some(SyncthingClient::new(syncthing_settings))
+    } else {
+        None
+    };
+    if let Some(ref client) = syncthing {
+        client.before_backup().await;
+    }
+
+    // Warn up front so a full NAS or drive isn't only discovered through a failed backup
+    let low_space_warned = check_backup_disk_space(state, &app_handle, &backup_path, notifications_enabled);
+
+    state.running_backups.lock_or_recover().insert(game_name.clone(), cancel);
+    publish_event(state, &app_handle, AppEvent::BackupStarted { game_name: game_name.clone(), app_id });
+    let mut progress = OperationProgress::start(state, &app_handle, "backup", game_name.clone());
+    progress.update("ludusavi", 0, Some(2), format!("Backing up {} with Ludusavi", game_name));
+    let backup_result = manager.backup(&game_name).await;
+    state.running_backups.lock_or_recover().remove(&game_name);
+
+    if let Some(ref client) = syncthing {
+        client.after_backup().await;
+    }
+
+    let succeeded = matches!(&backup_result, Ok(result) if result.success);
+    match backup_result {
+        Ok(result) => {
+            if result.success {
+                let files_backed_up = result.files_backed_up.unwrap_or(0);
+                let total_size = result.total_size.unwrap_or_default();
+
+                // Try to find app_id if not provided - look up in database by game name
+                let resolved_app_id = if app_id.is_some() {
+                    app_id
+                } else {
+                    // Try to find the game in the achievement database
+                    let db = {
+                        let path_guard = state.achievement_db_path.lock_or_recover();
+                        match &*path_guard {
+                            Some(path) => AchievementDatabase::new(path.clone()).ok(),
+                            None => None,
+                        }
+                    };
+
+                    if let Some(db) = db {
+                        // Get all games and find by name
+                        if let Ok(games) = db.get_all_games() {
+                            games.iter()
+                                .find(|g| g.game_name == game_name)
+                                .map(|g| g.app_id)
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                };
+
+                // Export achievements if we have an app_id
+                progress.update("achievements", 1, Some(2), format!("Backing up achievements of {}", game_name));
+                let achievements_count = if let Some(id) = resolved_app_id {
+                    export_achievements_internal(id, &game_name, state).await.unwrap_or(0)
+                } else {
+                    0
+                };
+
+                // Save backup date
+                let _ = save_backup_date(&game_name);
+                report_output_fallback(state);
+                record_backup_history(state, BackupHistoryEntry {
+                    id: None,
+                    game_name: game_name.clone(),
+                    app_id: resolved_app_id,
+                    kind: "backup".to_string(),
+                    timestamp: chrono::Utc::now().timestamp(),
+                    success: true,
+                    files_count: Some(files_backed_up as i64),
+                    total_size: Some(total_size.clone()),
+                    location: Some(backup_path.clone()),
+                    error: result.verification_error.as_ref().map(|e| match result.verified {
+                        Some(false) => format!("Verification failed: {}", e),
+                        _ => format!("Not verified: {}", e),
+                    }),
+                });
+
+                // The local backup is fine, but Ludusavi's cloud copy is now behind
+                if let Some(ref warning) = result.cloud_warning {
+                    app_log!("[Ludusavi] Cloud sync after backing up {} failed: {}", game_name, warning);
+                    if notifications_enabled {
+                        state.notification_manager.lock_or_recover().show_error(
+                            "Ludusavi Cloud Sync",
+                            &format!("{}\n{}", game_name, warning),
+                        );
+                    }
+                    publish_event(state, &app_handle, AppEvent::CloudUploadFinished {
+                        game_name: game_name.clone(),
+                        destination: "Ludusavi".to_string(),
+                        success: false,
+                        error: Some(warning.clone()),
+                    });
+                }
+
+                // This backup may be the one that pushed the destination under the threshold
+                if !low_space_warned {
+                    check_backup_disk_space(state, &app_handle, &backup_path, notifications_enabled);
+                }
+
+                // Fold the new snapshot into the deduplicated store while the caller still holds
+                // backup_slot, so garbage collection in delete_dedup_snapshot can't run alongside
+                if dedup_enabled {
+                    ingest_into_dedup_store(&game_name, &backup_path, dedup_keep).await;
+                }
+
+                // Let instances on the LAN know about the new backup
+                let lan = state.lan_sync.lock_or_recover().clone();
+                if let Some(lan) = lan {
+                    let index_backup_path = backup_path.clone();
+                    tauri::async_runtime::spawn(async move {
+                        push_backup_index(&lan, index_backup_path).await;
+                    });
+                }
+
+                // Users with a cloud set up in Ludusavi get the backup uploaded there too, unless the backup already synced it
+                if ludusavi_cloud_enabled && backup_cloud_sync != BackupCloudSync::Always {
+                    let cloud_manager = LudusaviManager::new(manager_path.clone(), backup_path.clone()).with_timeout(ludusavi_timeout);
+                    let cloud_game_name = game_name.clone();
+                    let state_clone = state.clone();
+                    let cloud_app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let error = match cloud_manager.cloud_sync(CloudDirection::Upload, Some(&cloud_game_name)).await {
+                            Ok(result) if result.success => {
+                                app_log!("[Ludusavi] Uploaded {} ({} files changed)", cloud_game_name, result.changed_files);
+                                None
+                            }
+                            Ok(result) => Some(result.error.unwrap_or_default()),
+                            Err(e) => Some(e),
+                        };
+                        if let Some(ref e) = error {
+                            app_log!("[Ludusavi] Cloud upload of {} failed: {}", cloud_game_name, e);
+                            if notifications_enabled {
+                                state_clone.notification_manager.lock_or_recover().show_error(
+                                    "Cloud Upload Failed",
+                                    &format!("{}\nLudusavi: {}", cloud_game_name, e),
+                                );
+                            }
+                        }
+                        publish_event(&state_clone, &cloud_app_handle, AppEvent::CloudUploadFinished {
+                            game_name: cloud_game_name,
+                            destination: "Ludusavi".to_string(),
+                            success: error.is_none(),
+                            error,
+                        });
+                    });
+                }
+
+                // Push the fresh backup off-site; destinations outside their upload window wait in the queue
+                let (due, deferred): (Vec<CloudDestination>, Vec<CloudDestination>) =
+                    cloud_destinations.into_iter().partition(|d| d.can_upload_now());
+                if !deferred.is_empty() {
+                    queue_deferred_uploads(state, &game_name, &deferred);
+                }
+                if !due.is_empty() {
+                    let state_clone = state.clone();
+                    let upload_backup_path = backup_path.clone();
+                    let upload_game_name = game_name.clone();
+                    let upload_app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        run_cloud_uploads(&state_clone, &upload_app_handle, &upload_backup_path, &upload_game_name, "backup", &due, notifications_enabled).await;
+                    });
+                }
+
+                run_script_hooks(state, ScriptEvent::BackupFinished {
+                    game_name: game_name.clone(),
+                    app_id: resolved_app_id,
+                    success: true,
+                    files_backed_up,
+                    total_size: total_size.clone(),
+                    backup_path: backup_path.clone(),
+                    error: None,
+                });
+                publish_event(state, &app_handle, AppEvent::BackupFinished {
+                    game_name: game_name.clone(),
+                    app_id: resolved_app_id,
+                    success: true,
+                    error: None,
+                });
+
+                if notifications_enabled {
+                    state.notification_manager.lock_or_recover().show_backup_success_with_achievements(
+                        &game_name,
+                        files_backed_up,
+                        &total_size,
+                        achievements_count,
+                        result.verified,
+                    );
+                }
+            } else if result.not_found.unwrap_or(false) {
+                record_failed_backup(state, &game_name, app_id, "Not found in the Ludusavi manifest");
+                if notifications_enabled {
+                    state.notification_manager.lock_or_recover().show_game_not_found(&game_name);
+                }
+
+                publish_event(state, &app_handle, AppEvent::GameNotFound { name: game_name.clone() });
+            } else {
+                let error = result.error.unwrap_or_else(|| "Unknown error".to_string());
+                record_failed_backup(state, &game_name, app_id, &error);
+                run_script_hooks(state, ScriptEvent::BackupFinished {
+                    game_name: game_name.clone(),
+                    app_id,
+                    success: false,
+                    files_backed_up: 0,
+                    total_size: String::new(),
+                    backup_path: backup_path.clone(),
+                    error: Some(error.clone()),
+                });
+                publish_event(state, &app_handle, AppEvent::BackupFinished {
+                    game_name: game_name.clone(),
+                    app_id,
+                    success: false,
+                    error: Some(error.clone()),
+                });
+
+                if notifications_enabled {
+                    state.notification_manager.lock_or_recover().show_backup_failed(&game_name, &error);
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("Backup error: {}", e);
+            record_failed_backup(state, &game_name, app_id, &e);
+            run_script_hooks(state, ScriptEvent::BackupFinished {
+                game_name: game_name.clone(),
+                app_id,
+                success: false,
+                files_backed_up: 0,
+                total_size: String::new(),
+                backup_path: backup_path.clone(),
+                error: Some(e.clone()),
+            });
+            publish_event(state, &app_handle, AppEvent::BackupFinished {
+                game_name: game_name.clone(),
+                app_id,
+                success: false,
+                error: Some(e.clone()),
+            });
+            if notifications_enabled {
+                state.notification_manager.lock_or_recover().show_error("Backup Error", &format!("Error backing up {}", game_name));
+            }
+        }
+    }
+    succeeded
+}
+
+/// Warn when the backup destination has less free space than configured. Returns whether it does.
+fn check_backup_disk_space(state: &AppState, app_handle: &tauri::AppHandle, backup_path: &str, notifications_enabled: bool) -> bool {
+    let threshold_mb = state.config.lock_or_recover().get_all().low_disk_space_mb;
+    if threshold_mb == 0 || backup_path.is_empty() {
+        return false;
+    }
+
+    let usage = match disk_space::usage(backup_path) {
+        Ok(usage) => usage,
+        Err(e) => {
+            app_log!("[DiskSpace] {}", e);
+            return false;
+        }
     };
+    if !disk_space::is_low(&usage, threshold_mb) {
+        return false;
+    }
 
-    // Use NotificationManager to show achievement on overlay with rarity percentage
-    state.notification_manager.lock().unwrap().show_achievement_unlock(
-        "Test Game",
-        name,
-        description,
-        Some("https://cdn.cloudflare.steamstatic.com/steamcommunity/public/images/apps/default_icon.jpg"),
-        Some(percentage)
-    );
+    let free = LudusaviManager::format_bytes(usage.free_bytes as i64);
+    app_log!("[DiskSpace] Only {} free on the backup destination {}", free, backup_path);
+    publish_event(state, app_handle, AppEvent::LowDiskSpace {
+        backup_path: backup_path.to_string(),
+        free_bytes: usage.free_bytes,
+        threshold_mb,
+    });
+    if notifications_enabled {
+        state.notification_manager.lock_or_recover().show_low_disk_space(backup_path, &free);
+    }
+    true
+}
 
-    Ok(())
+// Best-effort history entry; a missing database shouldn't fail the caller
+fn record_backup_history(state: &AppState, entry: BackupHistoryEntry) {
+    let db_path = state.achievement_db_path.lock_or_recover().clone();
+    let Some(db_path) = db_path else {
+        return;
+    };
+    if let Err(e) = AchievementDatabase::new(db_path).and_then(|db| db.add_backup_history(&entry).map(|_| ())) {
+        app_log!("[History] Failed to record {} for {}: {}", entry.kind, entry.game_name, e);
+    }
 }
 
-// Helper functions
-fn get_steam_library_folders() -> Result<Vec<PathBuf>, String> {
-    let steam_path = PathBuf::from(r"C:\Program Files (x86)\Steam");
-    let mut folders = vec![steam_path.clone()];
+fn record_failed_backup(state: &AppState, game_name: &str, app_id: Option<u32>, error: &str) {
+    record_backup_history(state, BackupHistoryEntry {
+        id: None,
+        game_name: game_name.to_string(),
+        app_id,
+        kind: "backup".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        success: false,
+        files_count: None,
+        total_size: None,
+        location: None,
+        error: Some(error.to_string()),
+    });
+}
 
-    let libraryfolders_path = steam_path.join("steamapps").join("libraryfolders.vdf");
-    if let Ok(contents) = std::fs::read_to_string(&libraryfolders_path) {
-        if let Ok(re) = regex::Regex::new(r#""path"\s+"([^"]+)""#) {
-            for cap in re.captures_iter(&contents) {
-                if let Some(path_match) = cap.get(1) {
-                    let path_str = path_match.as_str().replace("\\\\", "\\");
-                    let path = PathBuf::from(path_str);
-                    if path.exists() && !folders.contains(&path) {
-                        folders.push(path);
-                    }
+// Upload a game's backup to the given destinations and notify about failures
+
+async fn run_cloud_uploads(
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    backup_path: &str,
+    game_name: &str,
+    trigger: &str,
+    destinations: &[CloudDestination],
+    notifications_enabled: bool,
+) {
+    let archive_name = backup_archive_name(state, game_name, trigger);
+    // SFTP host keys are only pinned from the destination test or a manual upload; a server whose
+    // key nobody has confirmed yet fails here and is reported like any other failed upload
+    let results = cloud::upload_game_backup(backup_path, game_name, &archive_name, destinations).await;
+    let failures: Vec<String> = match results {
+        Ok(results) => {
+            // Archive uploads are remembered so verification can check they are still there
+            for result in results.iter().filter(|r| r.success) {
+                if let Some(ref remote_id) = result.remote_id {
+                    record_backup_history(state, BackupHistoryEntry {
+                        id: None,
+                        game_name: game_name.to_string(),
+                        app_id: None,
+                        kind: "upload".to_string(),
+                        timestamp: chrono::Utc::now().timestamp(),
+                        success: true,
+                        files_count: None,
+                        total_size: Some(result.bytes.to_string()),
+                        location: Some(format!("{}|{}", result.destination_id, remote_id)),
+                        error: None,
+                    });
                 }
             }
+            for result in &results {
+                publish_event(state, app_handle, AppEvent::CloudUploadFinished {
+                    game_name: game_name.to_string(),
+                    destination: result.destination_name.clone(),
+                    success: result.success,
+                    error: result.error.clone(),
+                });
+            }
+            results.into_iter()
+                .filter(|r| !r.success)
+                .map(|r| format!("{}: {}", r.destination_name, r.error.unwrap_or_default()))
+                .collect()
+        }
+        Err(e) => {
+            // Packaging failed before any destination was tried
+            for destination in destinations {
+                publish_event(state, app_handle, AppEvent::CloudUploadFinished {
+                    game_name: game_name.to_string(),
+                    destination: destination.name.clone(),
+                    success: false,
+                    error: Some(e.clone()),
+                });
+            }
+            vec![e]
         }
+    };
+    if !failures.is_empty() && notifications_enabled {
+        state.notification_manager.lock_or_recover().show_error(
+            "Cloud Upload Failed",
+            &format!("{}\n{}", game_name, failures.join("\n")),
+        );
     }
-
-    Ok(folders)
 }
 
-fn parse_appmanifest_basic(manifest_path: &PathBuf) -> Option<(u32, String)> {
-    if let Ok(contents) = std::fs::read_to_string(manifest_path) {
-        let app_id_re = regex::Regex::new(r#""appid"\s+"(\d+)""#).ok()?;
-        let name_re = regex::Regex::new(r#""name"\s+"([^"]+)""#).ok()?;
-
-        let app_id = app_id_re.captures(&contents)
-            .and_then(|cap| cap.get(1))
-            .and_then(|m| m.as_str().parse::<u32>().ok())?;
+// Verify local snapshots (and optionally the latest uploads), then report any problems
+async fn run_verification(state: &AppState) -> Result<VerificationReport, String> {
+    let (backup_path, notifications_enabled, verify_remote, destinations) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.backup_path, cfg.notifications_enabled, cfg.verify_remote_uploads, cfg.cloud_destinations)
+    };
+    if backup_path.is_empty() {
+        return Err("Backup path is not configured".to_string());
+    }
 
-        let name = name_re.captures(&contents)
-            .and_then(|cap| cap.get(1))
-            .map(|m| m.as_str().to_string())?;
+    let mut report = tokio::task::spawn_blocking(move || BackupVerifier::new(backup_path).verify_local())
+        .await
+        .map_err(|e| format!("Verification task failed: {}", e))??;
+
+    if verify_remote {
+        let db_path = state.achievement_db_path.lock_or_recover().clone();
+        let uploads = match db_path {
+            Some(path) => AchievementDatabase::new(path)?.get_latest_uploads()?,
+            None => Vec::new(),
+        };
+        verification::verify_remote_uploads(&uploads, &destinations, &mut report).await;
+    }
+    report.finished_at = chrono::Utc::now().timestamp();
+    verification::save_last_run(report.finished_at);
+
+    if !report.issues.is_empty() {
+        let mut games: Vec<&str> = report.issues.iter().map(|i| i.game_name.as_str()).collect();
+        games.sort();
+        games.dedup();
+        for game_name in &games {
+            let issues: Vec<String> = report.issues.iter()
+                .filter(|i| i.game_name == *game_name)
+                .map(|i| format!("{} ({}): {}", i.kind, i.snapshot_id, i.detail))
+                .collect();
+            record_backup_history(state, BackupHistoryEntry {
+                id: None,
+                game_name: game_name.to_string(),
+                app_id: None,
+                kind: "verification".to_string(),
+                timestamp: report.finished_at,
+                success: false,
+                files_count: Some(issues.len() as i64),
+                total_size: None,
+                location: None,
+                error: Some(issues.join("\n")),
+            });
+        }
 
-        Some((app_id, name))
-    } else {
-        None
+        if notifications_enabled {
+            state.notification_manager.lock_or_recover().show_error(
+                "Backup Verification Found Problems",
+                &format!("{} issue(s) in backups of {}", report.issues.len(), games.join(", ")),
+            );
+        }
     }
+
+    Ok(report)
 }
 
-// Helper function to export achievements for a game
-async fn export_achievements_internal(
-    app_id: u32,
-    game_name: &str,
-    state: &AppState,
-) -> Result<usize, String> {
-    use std::fs;
-    use std::io::Write;
+fn queue_deferred_uploads(state: &AppState, game_name: &str, destinations: &[CloudDestination]) {
+    let mut pending = state.pending_uploads.lock_or_recover();
+    for destination in destinations {
+        // One queued upload per game/destination is enough, it always sends the latest backup
+        if !pending.iter().any(|p| p.game_name == game_name && p.destination_id == destination.id) {
+            app_log!("[Cloud] Deferring upload of {} to {} until its upload window", game_name, destination.name);
+            pending.push(PendingUpload {
+                game_name: game_name.to_string(),
+                destination_id: destination.id.clone(),
+                queued_at: chrono::Utc::now().timestamp(),
+            });
+        }
+    }
+    cloud::save_pending_uploads(&pending);
+}
 
-    // Get database
-    let db = {
-        let path_guard = state.achievement_db_path.lock().unwrap();
-        match &*path_guard {
-            Some(path) => AchievementDatabase::new(path.clone()).ok(),
-            None => None,
+// Send queued uploads whose destination window is open now
+async fn process_pending_uploads(state: &AppState, app_handle: &tauri::AppHandle) {
+    let cfg = state.config.lock_or_recover().get_all();
+    let (notifications_enabled, destinations) = (cfg.notifications_enabled, cfg.cloud_destinations.clone());
+
+    let mut ready: Vec<PendingUpload> = {
+        let mut pending = state.pending_uploads.lock_or_recover();
+        let before = pending.len();
+        // Drop entries for destinations that were removed or disabled
+        pending.retain(|p| destinations.iter().any(|d| d.id == p.destination_id && d.enabled));
+        let (ready, waiting): (Vec<PendingUpload>, Vec<PendingUpload>) = pending.drain(..).partition(|p| {
+            destinations.iter().any(|d| d.id == p.destination_id && d.can_upload_now())
+        });
+        *pending = waiting;
+        if !ready.is_empty() || pending.len() != before {
+            cloud::save_pending_uploads(&pending);
         }
+        ready
     };
 
-    let db = match db {
-        Some(db) => db,
-        None => return Err("Achievement database not initialized".to_string()),
-    };
+    // Favorites go out first
+    let favorites: Vec<String> = state.achievement_db_path.lock_or_recover().clone()
+        .and_then(|path| AchievementDatabase::new(path).ok())
+        .and_then(|db| db.get_favorites().ok())
+        .map(|favorites| favorites.into_iter().map(|(_, name)| name).collect())
+        .unwrap_or_default();
+    ready.sort_by_key(|upload| !favorites.contains(&upload.game_name));
+
+    for upload in ready {
+        if let Some(destination) = destinations.iter().find(|d| d.id == upload.destination_id) {
+            app_log!("[Cloud] Upload window open, sending {} to {}", upload.game_name, destination.name);
+            let backup_path = cfg.backup_path_for(&upload.game_name);
+            run_cloud_uploads(state, app_handle, &backup_path, &upload.game_name, "deferred", std::slice::from_ref(destination), notifications_enabled).await;
+        }
+    }
+}
 
-    // Get all achievements for this game
-    let all_achievements = db.get_game_achievements(app_id)?;
+// Number an event and emit it on the shared channel; emitting under the lock keeps delivery in seq order
+fn publish_event(state: &AppState, app: &tauri::AppHandle, event: AppEvent) {
+    let mut bus = state.event_bus.lock_or_recover();
+    let sequenced = bus.record(event);
+    let _ = app.emit(app_events::EVENT_CHANNEL, &sequenced);
+}
 
-    // Filter only unlocked achievements
-    let unlocked: Vec<_> = all_achievements.iter()
-        .filter(|a| a.achieved)
-        .collect();
+/// Reports a long-running command's progress as AppEvent::Progress. Dropping it marks the operation done,
+/// so an early return doesn't leave it listed in get_active_operations.
+struct OperationProgress<'a> {
+    state: &'a AppState,
+    app: tauri::AppHandle,
+    progress: ProgressEvent,
+}
 
-    let unlocked_count = unlocked.len();
+impl<'a> OperationProgress<'a> {
+    fn start(state: &'a AppState, app: &tauri::AppHandle, operation: &str, message: String) -> Self {
+        let progress = state.active_operations.lock_or_recover().start(operation, message);
+        publish_event(state, app, AppEvent::Progress(progress.clone()));
+        Self { state, app: app.clone(), progress }
+    }
 
-    if unlocked_count == 0 {
-        return Ok(0); // No achievements to export
+    fn update(&mut self, stage: &str, current: u64, total: Option<u64>, message: String) {
+        self.progress.stage = stage.to_string();
+        self.progress.current = current;
+        self.progress.total = total;
+        self.progress.message = message;
+        self.publish();
     }
 
-    // Convert to Steam API format
-    let mut steam_format = serde_json::Map::new();
-    for achievement in unlocked {
-        let mut achievement_data = serde_json::Map::new();
-        achievement_data.insert(
-            "UnlockTime".to_string(),
-            serde_json::Value::Number(
-                serde_json::Number::from(achievement.unlock_time.unwrap_or(0))
-            )
-        );
-        steam_format.insert(
-            achievement.achievement_id.clone(),
-            serde_json::Value::Object(achievement_data)
-        );
+    fn finish(mut self, message: String) {
+        self.progress.message = message;
     }
 
-    let json_string = serde_json::to_string_pretty(&steam_format)
-        .map_err(|e| format!("Failed to serialize to JSON: {}", e))?;
+    fn publish(&self) {
+        self.state.active_operations.lock_or_recover().update(&self.progress);
+        publish_event(self.state, &self.app, AppEvent::Progress(self.progress.clone()));
+    }
+}
 
-    // Get Documents folder
-    let documents_dir = match dirs::document_dir() {
-        Some(dir) => dir,
-        None => return Err("Could not find Documents folder".to_string()),
-    };
+impl Drop for OperationProgress<'_> {
+    fn drop(&mut self) {
+        self.progress.stage = "done".to_string();
+        self.progress.done = true;
+        self.publish();
+    }
+}
 
-    // Create Steam Backup Monitor folder
-    let export_dir = documents_dir.join("Steam Backup Monitor");
-    if !export_dir.exists() {
-        fs::create_dir_all(&export_dir)
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
+// Hand an event to user scripts; off unless enabled since scripts can run commands
+fn run_script_hooks(state: &AppState, event: ScriptEvent) {
+    if state.config.lock_or_recover().get_all().scripting_enabled {
+        scripting::dispatch(event);
     }
+}
 
-    // Sanitize game name for filename
-    let safe_game_name: String = game_name.chars()
-        .map(|c| match c {
-            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c
-        })
-        .collect();
+// Post an unlock to the configured webhook in the background
+fn send_unlock_webhook(state: &AppState, event: &AchievementUnlockEvent) {
+    let settings = state.config.lock_or_recover().get_all().unlock_webhook;
+    if !settings.enabled || settings.url.trim().is_empty() {
+        return;
+    }
+    let payload = UnlockPayload {
+        event: "achievement_unlocked",
+        game_name: event.game_name.clone(),
+        app_id: event.app_id,
+        achievement_id: event.achievement_id.clone(),
+        display_name: event.display_name.clone(),
+        description: event.description.clone(),
+        icon_url: event.icon_url.clone(),
+        unlock_time: event.unlock_time,
+        global_unlock_percentage: event.global_unlock_percentage,
+    };
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = webhooks::send_unlock(&settings, &payload).await {
+            app_log!("[Webhook] {} ({}): {}", payload.display_name, payload.game_name, e);
+        }
+    });
+}
 
-    // Create file path
-    let file_path = export_dir.join(format!("{}.json", safe_game_name));
+// Celebrate a completion milestone (50%, 75%, perfect game...) crossed by this unlock
+fn announce_milestone(state: &AppState, db_path: &Path, event: &AchievementUnlockEvent) {
+    let (settings, notifications_enabled) = {
+        let cfg = state.config.lock_or_recover().get_all();
+        (cfg.milestone_notifications, cfg.notifications_enabled)
+    };
 
-    // Write to file (overwrites if exists)
-    let mut file = fs::File::create(&file_path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let result = AchievementDatabase::new(db_path.to_path_buf())
+        .and_then(|db| milestones::check_after_unlock(&db, &settings, event.app_id, &event.game_name));
+    match result {
+        Ok(Some(milestone)) => {
+            app_log!("[Milestones] {} reached {}% ({}/{})", milestone.game_name, milestone.milestone, milestone.unlocked, milestone.total);
+            if notifications_enabled {
+                let sound_path = if milestone.perfect { &settings.perfect_sound_path } else { &settings.sound_path };
+                state.notification_manager.lock_or_recover().show_milestone(&milestone, sound_path.as_deref());
+            }
+        }
+        Ok(None) => {}
+        Err(e) => app_log!("[Milestones] {}", e),
+    }
+}
 
-    file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+// Sum up the achievements of the session that just ended and show one recap card
+fn record_session_recap(state: &AppState, game_name: &str, app_id: u32) {
+    let Some(started_at) = state.session_started_at.lock_or_recover().take() else { return };
+    let Some(db_path) = state.achievement_db_path.lock_or_recover().clone() else { return };
+
+    let ended_at = chrono::Utc::now().timestamp();
+    let recap = match AchievementDatabase::new(db_path).and_then(|db| {
+        let recap = SessionRecap::build(&db, game_name, app_id, started_at, ended_at)?;
+        // Kept for the activity timeline
+        db.add_play_session(&PlaySession {
+            app_id,
+            game_name: game_name.to_string(),
+            started_at,
+            ended_at,
+            unlocked_count: recap.unlocked_count as u32,
+        })?;
+        Ok(recap)
+    }) {
+        Ok(recap) => recap,
+        Err(e) => {
+            app_log!("[Session] Failed to build recap for {}: {}", game_name, e);
+            return;
+        }
+    };
 
-    println!("Exported {} achievements for {}", unlocked_count, game_name);
-    Ok(unlocked_count)
+    app_log!("[Session] {} ended after {}, {} achievement(s) unlocked", game_name, recap.playtime_label(), recap.unlocked_count);
+    // A do-not-disturb game's recap waits for the exit backup, see finish_quiet_session
+    let notifications_enabled = state.config.lock_or_recover().get_all().notifications_enabled;
+    let quiet = state.notification_manager.lock_or_recover().is_quiet(game_name);
+    if notifications_enabled && !quiet && recap.unlocked_count > 0 {
+        state.notification_manager.lock_or_recover().show_session_recap(&recap);
+    }
+    *state.last_session_recap.lock_or_recover() = Some(recap);
 }
 
-// Helper function to save backup date
-fn save_backup_date(game_name: &str) -> Result<(), String> {
-    use std::fs;
-    use std::io::Write;
+// Show a do-not-disturb game's recap together with the notifications held back while it ran
+fn finish_quiet_session(state: &AppState) {
+    let Some((game_name, held)) = state.notification_manager.lock_or_recover().end_quiet_session() else { return };
+    let recap = state.last_session_recap.lock_or_recover().clone()
+        .filter(|recap| recap.game_name == game_name);
+
+    let notifications_enabled = state.config.lock_or_recover().get_all().notifications_enabled;
+    if notifications_enabled && (!held.is_empty() || recap.as_ref().is_some_and(|recap| recap.unlocked_count > 0)) {
+        state.notification_manager.lock_or_recover().show_quiet_recap(&game_name, recap.as_ref(), &held);
+    }
+}
 
-    // Get Documents folder
-    let documents_dir = match dirs::document_dir() {
-        Some(dir) => dir,
-        None => return Err("Could not find Documents folder".to_string()),
+// Quick backup of the running or recently played game before shutdown, logoff or sleep
+async fn handle_power_event(kind: PowerEventKind, state: &AppState, app_handle: tauri::AppHandle) {
+    let (enabled, budget_secs) = {
+        let config = state.config.lock_or_recover();
+        let cfg = config.get_all();
+        (cfg.backup_on_shutdown, cfg.shutdown_backup_budget_secs)
     };
 
-    // Create "Steam Save Monitor Backup dates" folder
-    let backup_dates_dir = documents_dir.join("Steam Save Monitor Backup dates");
-    if !backup_dates_dir.exists() {
-        fs::create_dir_all(&backup_dates_dir)
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    if !enabled {
+        return;
     }
 
-    // Sanitize game name for filename
-    let safe_game_name: String = game_name.chars()
-        .map(|c| match c {
-            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c
-        })
-        .collect();
+    let running = state.current_game.read().await.clone()
+        .map(|(name, app_id)| (name, if app_id == 0 { None } else { Some(app_id) }));
 
-    // Create file path
-    let file_path = backup_dates_dir.join(format!("{}.json", safe_game_name));
+    let target = running.or_else(|| {
+        let last = state.last_played_game.lock_or_recover().clone();
+        last.filter(|(_, _, ended_at)| chrono::Utc::now().timestamp() - ended_at <= RECENT_GAME_WINDOW_SECS)
+            .map(|(name, app_id, _)| (name, app_id))
+    });
 
-    // Get current date and time
-    let now = chrono::Local::now();
-    let date_time_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    let Some((game_name, app_id)) = target else {
+        app_log!("[PowerEvents] No running or recent game to back up before {}", kind);
+        return;
+    };
 
-    // Create JSON with single date (replaces existing)
-    let backup_data = serde_json::json!({
-        "date": date_time_str
+    app_log!("[PowerEvents] Backing up {} before {} ({}s budget)", game_name, kind, budget_secs);
+    let budget = tokio::time::Duration::from_secs(budget_secs as u64);
+    let started = tokio::time::Instant::now();
+
+    // A running backup only gets the budget to finish; queueing behind it could outlast the shutdown
+    let Ok(slot) = tokio::time::timeout(budget, state.backup_slot.clone().lock_owned()).await else {
+        app_log!("[PowerEvents] Another backup was still running after the {}s budget, continuing {}", budget_secs, kind);
+        return;
+    };
+
+    // The backup runs as its own task holding the slot, so running out of budget doesn't drop it
+    // halfway: it still removes its running_backups entry and resumes Syncthing once it stops
+    let backup_state = state.clone();
+    let backup_game = game_name.clone();
+    let mut backup = tokio::spawn(async move {
+        run_game_backup(backup_game, app_id, &backup_state, app_handle).await;
+        drop(slot);
     });
 
-    // Serialize to JSON
-    let json_string = serde_json::to_string_pretty(&backup_data)
-        .map_err(|e| format!("Failed to serialize to JSON: {}", e))?;
+    let remaining = budget.saturating_sub(started.elapsed());
+    match tokio::time::timeout(remaining, &mut backup).await {
+        Ok(_) => app_log!("[PowerEvents] Backup of {} finished before {}", game_name, kind),
+        Err(_) => {
+            app_log!("[PowerEvents] Backup of {} exceeded the {}s budget, cancelling it and continuing {}", game_name, budget_secs, kind);
+            if let Some(cancel) = state.running_backups.lock_or_recover().get(&game_name) {
+                cancel.cancel();
+            }
+        }
+    }
+}
 
-    // Write to file (overwrites if exists)
-    let mut file = fs::File::create(&file_path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+/// Name of an uploaded archive, from the configured naming template
+fn backup_archive_name(state: &AppState, game_name: &str, trigger: &str) -> String {
+    let template = state.config.lock_or_recover().get_all().backup_name_template;
+    let app_id = find_app_id_by_name(state, game_name);
+    let date = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+    filenames::render_backup_name(&template, game_name, app_id, trigger, &date)
+}
 
-    file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+// Games matched by executable have no Steam AppID, but the achievement database may know one
+fn find_app_id_by_name(state: &AppState, game_name: &str) -> Option<u32> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()?;
+    let games = AchievementDatabase::new(db_path).ok()?.get_all_games().ok()?;
+    games.iter().find(|g| g.game_name == game_name).map(|g| g.app_id)
+}
 
-    println!("Saved backup date for {}", game_name);
-    Ok(())
+fn find_game_name_by_app_id(state: &AppState, app_id: u32) -> Option<String> {
+    let db_path = state.achievement_db_path.lock_or_recover().clone()?;
+    let games = AchievementDatabase::new(db_path).ok()?.get_all_games().ok()?;
+    games.into_iter().find(|g| g.app_id == app_id).map(|g| g.game_name)
 }
 
-async fn handle_game_backup(
-    game_name: String,
-    app_id: Option<u32>,
-    state: &AppState,
-    app_handle: tauri::AppHandle,
-) {
-    println!("Backing up: {}", game_name);
+// Game whose configured executable is `path`; Explorer may pass it with different casing
+fn find_game_by_executable(state: &AppState, path: &str) -> Option<String> {
+    let executables = state.config.lock_or_recover().get_all().game_executables;
+    executables.into_iter()
+        .find(|(_, exe)| exe.eq_ignore_ascii_case(path))
+        .map(|(game_name, _)| game_name)
+}
 
-    let (ludusavi_path, backup_path, notifications_enabled) = {
-        let config = state.config.lock().unwrap();
-        let cfg = config.get_all();
-        (cfg.ludusavi_path, cfg.backup_path, cfg.notifications_enabled)
+// Act on a steamsavemonitor:// link or context-menu launch, from this process or a second one
+fn handle_launch_request(app: &tauri::AppHandle, request: LaunchRequest) {
+    app_log!("[Shell] Opened with {:?}", request);
+    show_main_window(app);
+    let Some(state) = app.try_state::<AppState>() else { return };
+    let state = state.inner().clone();
+
+    let game = match &request {
+        LaunchRequest::Achievements { .. } => None,
+        LaunchRequest::Backup { app_id } => Some((find_game_name_by_app_id(&state, *app_id), Some(*app_id), app_id.to_string())),
+        LaunchRequest::BackupExecutable { path } => Some((find_game_by_executable(&state, path), None, path.clone())),
     };
 
-    let manager = LudusaviManager::new(ludusavi_path, backup_path);
+    // Backups start here; the frontend only navigates
+    if let Some((game_name, app_id, requested)) = game {
+        let refused = match game_name {
+            Some(game_name) => match allow_link_backup(&state, &game_name) {
+                Ok(()) => {
+                    let app_handle = app.clone();
+                    let backup_state = state.clone();
+                    tauri::async_runtime::spawn(async move {
+                        handle_game_backup(game_name, app_id, &backup_state, app_handle).await;
+                    });
+                    None
+                }
+                Err(e) => Some(format!("Not backing up {}: {}", game_name, e)),
+            },
+            None => Some(format!("No known game for {}", requested)),
+        };
+        if let Some(message) = refused {
+            app_log!("[Shell] {}", message);
+            if state.config.lock_or_recover().get_all().notifications_enabled {
+                state.notification_manager.lock_or_recover().show_error("Backup Error", &message);
+            }
+        }
+    }
 
-    match manager.backup(&game_name).await {
-        Ok(result) => {
-            if result.success {
-                let files_backed_up = result.files_backed_up.unwrap_or(0);
-                let total_size = result.total_size.unwrap_or_default();
+    *state.pending_launch.lock_or_recover() = Some(request.clone());
+    publish_event(&state, app, AppEvent::LaunchRequested(request));
+}
 
-                // Try to find app_id if not provided - look up in database by game name
-                let resolved_app_id = if app_id.is_some() {
-                    app_id
-                } else {
-                    // Try to find the game in the achievement database
-                    let db = {
-                        let path_guard = state.achievement_db_path.lock().unwrap();
-                        match &*path_guard {
-                            Some(path) => AchievementDatabase::new(path.clone()).ok(),
-                            None => None,
-                        }
-                    };
+/// Whether a link or context menu entry may back up a game now: not in read-only mode, and not
+/// again within LINK_BACKUP_INTERVAL_SECS of the last one. Records the backup when allowed.
+fn allow_link_backup(state: &AppState, game_name: &str) -> Result<(), String> {
+    ensure_writable(state)?;
+    let now = chrono::Utc::now().timestamp();
+    let mut link_backups = state.link_backups.lock_or_recover();
+    if let Some(&last) = link_backups.get(game_name) {
+        let wait = LINK_BACKUP_INTERVAL_SECS - (now - last);
+        if wait > 0 {
+            return Err(format!("it was backed up from a link {} seconds ago, try again in {} seconds", now - last, wait));
+        }
+    }
+    link_backups.insert(game_name.to_string(), now);
+    Ok(())
+}
 
-                    if let Some(db) = db {
-                        // Get all games and find by name
-                        if let Ok(games) = db.get_all_games() {
-                            games.iter()
-                                .find(|g| g.game_name == game_name)
-                                .map(|g| g.app_id)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                };
+async fn handle_game_started(state: &AppState, app: &tauri::AppHandle, game: GameInfo) {
+    app_log!("Game started: {}", game.name);
 
-                // Export achievements if we have an app_id
-                let achievements_count = if let Some(id) = resolved_app_id {
-                    export_achievements_internal(id, &game_name, state).await.unwrap_or(0)
-                } else {
-                    0
-                };
+    // Update current game (use 0 for non-Steam games)
+    *state.current_game.write().await = Some((game.name.clone(), game.app_id.unwrap_or(0)));
+    *state.session_started_at.lock_or_recover() = Some(chrono::Utc::now().timestamp());
+
+    // Update tray menu asynchronously without blocking
+    let app_for_tray = app.clone();
+    let game_name_for_tray = game.name.clone();
+    tauri::async_runtime::spawn(async move {
+        update_tray_menu(&app_for_tray, Some(game_name_for_tray));
+    });
+
+    // Start watching achievements for this game
+    let app_id = game.app_id.or_else(|| find_app_id_by_name(state, &game.name));
+    match app_id {
+        Some(app_id) => {
+            if let Some(ref watcher) = *state.achievement_watcher.read().await {
+                let watcher = Arc::clone(watcher);
+                let game_name = game.name.clone();
+                tokio::spawn(async move {
+                    watcher.start_watching_game(app_id, game_name).await;
+                });
+            }
+        }
+        None => app_log!("Game {} not found in achievement database", game.name),
+    }
+
+    run_script_hooks(state, ScriptEvent::GameStarted { game_name: game.name.clone(), app_id: game.app_id });
+    publish_event(state, app, AppEvent::GameDetected { name: game.name.clone(), app_id: game.app_id });
+
+    let quiet_games = state.config.lock_or_recover().get_all().quiet_games;
+    if app_id.is_some_and(|app_id| quiet_games.contains(&app_id)) {
+        state.notification_manager.lock_or_recover().begin_quiet_session(&game.name);
+    }
 
-                // Save backup date
-                let _ = save_backup_date(&game_name);
+    let cfg = state.config.lock_or_recover().get_all();
+    let backup_path = cfg.backup_path_for(&game.name);
+    if cfg.snapshot_on_game_start && !cfg.ludusavi_path.is_empty() && !backup_path.is_empty() {
+        let ludusavi = LudusaviManager::new(cfg.ludusavi_path.clone(), backup_path.clone()).with_timeout(cfg.ludusavi_timeout_secs);
+        tokio::spawn(snapshot_before_play(ludusavi, SnapshotManager::new(backup_path), game.name.clone()));
+    }
+    if let Some(&minutes) = cfg.session_backup_minutes.get(&game.name).filter(|&&minutes| minutes > 0) {
+        start_session_backups(state, app, game.name.clone(), game.app_id, minutes);
+    }
 
-                if notifications_enabled {
-                    state.notification_manager.lock().unwrap().show_backup_success_with_achievements(
-                        &game_name,
-                        files_backed_up,
-                        &total_size,
-                        achievements_count,
-                    );
-                }
-            } else if result.not_found.unwrap_or(false) {
-                if notifications_enabled {
-                    state.notification_manager.lock().unwrap().show_game_not_found(&game_name);
-                }
+    if state.config.lock_or_recover().get_all().notifications_enabled {
+        state.notification_manager.lock_or_recover().show_game_detected(&game.name);
+    }
+}
 
-                // Send to frontend
-                let _ = app_handle.emit_all("game-not-found", serde_json::json!({ "name": game_name }));
-            } else {
-                if notifications_enabled {
-                    state.notification_manager.lock().unwrap().show_backup_failed(
-                        &game_name,
-                        &result.error.unwrap_or_else(|| "Unknown error".to_string()),
-                    );
-                }
+// Back up the running game every `minutes` until stop_session_backups
+fn start_session_backups(state: &AppState, app: &tauri::AppHandle, game_name: String, app_id: Option<u32>, minutes: u32) {
+    app_log!("[Session] Backing up {} every {} minutes while it runs", game_name, minutes);
+    let stop = CancelToken::default();
+    let task_stop = stop.clone();
+    let task_state = state.clone();
+    let app = app.clone();
+    let task = tokio::spawn(async move {
+        let period = tokio::time::Duration::from_secs(minutes as u64 * 60);
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(period) => {}
+                _ = task_stop.cancelled() => break,
             }
+            app_log!("[Session] Mid-session backup of {}", game_name);
+            handle_game_backup(game_name.clone(), app_id, &task_state, app.clone()).await;
         }
+    });
+
+    if let Some((previous, _)) = state.session_backups.lock_or_recover().replace((stop, task)) {
+        previous.cancel();
+    }
+}
+
+// Waits for a mid-session backup that's already running, so it doesn't overlap the exit backup
+async fn stop_session_backups(state: &AppState) {
+    let Some((stop, task)) = state.session_backups.lock_or_recover().take() else { return };
+    stop.cancel();
+    let _ = task.await;
+}
+
+// "Before I played" copy of a game's saves, restorable like the pre-restore snapshots
+async fn snapshot_before_play(ludusavi: LudusaviManager, snapshots: SnapshotManager, game_name: String) {
+    match snapshots.create_safety_snapshot(&ludusavi, &game_name, "pre-play").await {
+        Ok(Some(snapshot)) => app_log!("[Snapshot] Saved {} before play as {}", game_name, snapshot.id),
+        Ok(None) => return,
         Err(e) => {
-            eprintln!("Backup error: {}", e);
-            if notifications_enabled {
-                state.notification_manager.lock().unwrap().show_error("Backup Error", &format!("Error backing up {}", game_name));
+            app_log!("[Snapshot] No pre-play snapshot for {}: {}", game_name, e);
+            return;
+        }
+    }
+
+    match snapshots.prune_safety_snapshots(&game_name, "pre-play", PRE_PLAY_SNAPSHOTS_KEPT) {
+        Ok(0) => {}
+        Ok(pruned) => app_log!("[Snapshot] Removed {} old pre-play snapshot(s) of {}", pruned, game_name),
+        Err(e) => app_log!("[Snapshot] {}", e),
+    }
+}
+
+async fn handle_game_ended(state: &AppState, app: tauri::AppHandle, game: GameInfo) {
+    app_log!("Game ended: {}", game.name);
+
+    // Clear current game
+    *state.current_game.write().await = None;
+    *state.last_played_game.lock_or_recover() = Some((game.name.clone(), game.app_id, chrono::Utc::now().timestamp()));
+
+    // Update tray menu asynchronously without blocking
+    let app_for_tray = app.clone();
+    tauri::async_runtime::spawn(async move {
+        update_tray_menu(&app_for_tray, None);
+    });
+
+    // Stop watching achievements for this game
+    if let Some(app_id) = game.app_id.or_else(|| find_app_id_by_name(state, &game.name)) {
+        if let Some(ref watcher) = *state.achievement_watcher.read().await {
+            watcher.stop_watching_game(app_id);
+        }
+        record_session_recap(state, &game.name, app_id);
+        // Pick up screenshots taken at this session's unlocks
+        if let Err(e) = scan_screenshots(state, app_id) {
+            app_log!("[Screenshots] {}", e);
+        }
+    }
+
+    if state.config.lock_or_recover().get_all().notifications_enabled {
+        state.notification_manager.lock_or_recover().show_game_ended(&game.name);
+    }
+
+    run_script_hooks(state, ScriptEvent::GameEnded { game_name: game.name.clone(), app_id: game.app_id });
+    publish_event(state, &app, AppEvent::GameEnded { name: game.name.clone(), app_id: game.app_id });
+
+    stop_session_backups(state).await;
+    handle_game_backup(game.name, game.app_id, state, app).await;
+    finish_quiet_session(state);
+}
+
+// The detection strategies available right now; Steam may not be installed
+fn detection_strategies(db_path: Option<PathBuf>, game_executables: &HashMap<String, String>) -> Vec<Box<dyn DetectionStrategy>> {
+    let mut strategies: Vec<Box<dyn DetectionStrategy>> = Vec::new();
+    match SteamMonitor::new() {
+        Ok(mut monitor) => {
+            // Set database path for exclusions checking
+            if let Some(db_path) = db_path {
+                monitor.set_db_path(db_path);
+            }
+            strategies.push(Box::new(monitor));
+        }
+        Err(e) => {
+            app_log!("⚠ Steam not available: {}. Steam monitoring disabled.", e);
+            app_log!("   Make sure Steam is installed to enable automatic game detection.");
+        }
+    }
+    if !game_executables.is_empty() {
+        strategies.push(Box::new(ProcessMonitor::new(game_executables.clone())));
+        app_log!("✓ Process monitoring enabled for {} games", game_executables.len());
+    }
+    strategies
+}
+
+// At login the app can win the race against Steam; hold monitors back as configured
+async fn wait_for_startup_conditions(state: &AppState) {
+    let config = state.config.lock_or_recover().get_all();
+
+    if config.startup_delay_secs > 0 {
+        app_log!("Delaying monitor startup by {}s", config.startup_delay_secs);
+        tokio::time::sleep(tokio::time::Duration::from_secs(config.startup_delay_secs as u64)).await;
+    }
+
+    if config.wait_for_steam {
+        let waiting_since = tokio::time::Instant::now();
+        while !SteamMonitor::steam_process_running() {
+            if waiting_since.elapsed() >= STEAM_WAIT_LIMIT {
+                app_log!("⚠ Steam didn't start within {} minutes, starting monitors anyway", STEAM_WAIT_LIMIT.as_secs() / 60);
+                return;
             }
+            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
         }
+        app_log!("✓ Steam is running");
     }
 }
 
-async fn start_monitors(state: &AppState, window: Window) {
+async fn start_monitors(state: &AppState, window: WebviewWindow) {
     println!("Starting monitors...");
 
+    if *state.monitoring_paused.lock_or_recover() {
+        app_log!("Monitoring is paused, not starting monitors");
+        return;
+    }
+
     // Check if monitors are already running
-    {
-        let steam_handle = state.steam_handle.lock().unwrap();
-        if steam_handle.is_some() {
-            println!("WARNING: Steam monitor already running! Skipping start to prevent duplicates.");
-            return;
-        }
+    if state.supervisor.is_supervised(GAME_DETECTION_TASK) {
+        app_log!("WARNING: Game detection already running! Skipping start to prevent duplicates.");
+        return;
     }
 
     let config = {
-        let cfg = state.config.lock().unwrap();
+        let cfg = state.config.lock_or_recover();
         cfg.get_all()
     };
 
@@ -1440,274 +4933,79 @@ async fn start_monitors(state: &AppState, window: Window) {
         return;
     }
 
-    let app_handle = window.app_handle();
+    let app_handle = window.app_handle().clone();
     
-    // Start Steam monitor (monitors localconfig.vdf file)
+    // Steam games and the user's own executables are found by one detection service
     // No API keys or Steamworks required!
-    match SteamMonitor::new() {
-        Ok(mut monitor) => {
-            // Set database path for exclusions checking
-            if let Some(ref db_path) = *state.achievement_db_path.lock().unwrap() {
-                monitor.set_db_path(db_path.clone());
-            }
-
-            let (tx, mut rx) = mpsc::channel(10);
-            let state_clone = state.clone();
-            let app_clone = app_handle.clone();
+    let db_path = state.achievement_db_path.lock_or_recover().clone();
+    let game_executables = config.game_executables.clone();
+    let strategies = detection_strategies(db_path.clone(), &game_executables);
 
-            tokio::spawn(async move {
-                let mut monitor = monitor;
-                let mut paused = false;
+    if !strategies.is_empty() {
+        // The strategies built above serve the first run; restarts after a failure build fresh ones
+        let first_run = Mutex::new(Some(strategies));
+        let state_clone = state.clone();
+        let app_clone = app_handle.clone();
 
-                loop {
-                    tokio::select! {
-                        // Check for commands
-                        Some(cmd) = rx.recv() => {
-                            match cmd {
-                                MonitorCommand::Stop => {
-                                    println!("Steamworks monitor stopped");
-                                    break;
-                                }
-                                MonitorCommand::Pause => {
-                                    println!("Steamworks monitor paused");
-                                    paused = true;
-                                }
-                                MonitorCommand::Resume => {
-                                    println!("Steamworks monitor resumed");
-                                    paused = false;
-                                }
-                            }
-                        }
-                        // Check Steam if not paused
-                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(2)) => {
-                            if !paused {
-                                if let Some(event) = monitor.check_steam() {
-                                    match event {
-                                        steam_monitor::GameEvent::Ended(game) => {
-                                            println!("Steam game ended: {}", game.name);
-
-                                            // Clear current game
-                                            {
-                                                let mut current_game = state_clone.current_game.lock().unwrap();
-                                                *current_game = None;
-                                            }
-
-                                            // Update tray menu asynchronously without blocking
-                                            let app_for_tray = app_clone.clone();
-                                            tauri::async_runtime::spawn(async move {
-                                                update_tray_menu(&app_for_tray, None);
-                                            });
-
-                                            // Stop watching achievements for this game
-                                            if let Some(ref watcher) = *state_clone.achievement_watcher.lock().unwrap() {
-                                                watcher.stop_watching_game(game.app_id);
-                                            }
-
-                                            handle_game_backup(game.name, Some(game.app_id), &state_clone, app_clone.clone()).await;
-                                        }
-                                        steam_monitor::GameEvent::Started(game) => {
-                                            println!("Steam game started: {}", game.name);
-
-                                            // Update current game
-                                            {
-                                                let mut current_game = state_clone.current_game.lock().unwrap();
-                                                *current_game = Some((game.name.clone(), game.app_id));
-                                            }
-
-                                            // Update tray menu asynchronously without blocking
-                                            let app_for_tray = app_clone.clone();
-                                            let game_name_for_tray = game.name.clone();
-                                            tauri::async_runtime::spawn(async move {
-                                                update_tray_menu(&app_for_tray, Some(game_name_for_tray));
-                                            });
-
-                                            // Start watching achievements for this game
-                                            if let Some(ref watcher) = *state_clone.achievement_watcher.lock().unwrap() {
-                                                let watcher = Arc::clone(watcher);
-                                                let app_id = game.app_id;
-                                                let game_name = game.name.clone();
-                                                tokio::spawn(async move {
-                                                    watcher.start_watching_game(app_id, game_name).await;
-                                                });
-                                            }
-
-                                            // Get notification settings
-                                            let notifications_enabled = {
-                                                let config = state_clone.config.lock().unwrap();
-                                                config.get_all().notifications_enabled
-                                            };
-
-                                            if notifications_enabled {
-                                                state_clone.notification_manager.lock().unwrap().show_game_detected(&game.name);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        state.supervisor.supervise(GAME_DETECTION_TASK, move || {
+            let strategies = first_run.lock_or_recover().take()
+                .unwrap_or_else(|| detection_strategies(db_path.clone(), &game_executables));
+            let state = state_clone.clone();
+            let app = app_clone.clone();
+            async move {
+                let interval = tokio::time::Duration::from_secs(GAME_POLL_INTERVAL_SECS);
+                let (_detection, mut events) = GameDetection::start(strategies, interval);
+                while let Some(event) = events.recv().await {
+                    match event {
+                        GameEvent::Started(game) => handle_game_started(&state, &app, game).await,
+                        GameEvent::Ended(game) => handle_game_ended(&state, app.clone(), game).await,
                     }
                 }
-            });
+            }
+        });
+        app_log!("✓ Game detection started");
+    }
 
-            *state.steam_handle.lock().unwrap() = Some(tx);
-            println!("✓ Steam monitoring started (no API key needed!)");
-        }
-        Err(e) => {
-            println!("⚠ Steam not available: {}. Steam monitoring disabled.", e);
-            println!("   Make sure Steam is installed to enable automatic game detection.");
+    // Start watching manually configured save folders
+    if !config.custom_save_paths.is_empty() {
+        // Ludusavi backs games up by name, so the folders have to be in its settings too or a game
+        // its manifest doesn't know is never backed up
+        if !config.ludusavi_path.is_empty() {
+            for (game_name, paths) in &config.custom_save_paths {
+                if let Err(e) = ludusavi_config::add_custom_game_files(&config.ludusavi_path, game_name, paths) {
+                    app_log!("[Ludusavi] Couldn't add the save folders of {}: {}", game_name, e);
+                }
+            }
         }
-    }
-    
-    // Start process monitor
-    if !config.game_executables.is_empty() {
-        let (tx, mut rx) = mpsc::channel(1);
-        let game_exes = config.game_executables.clone();
+
+        let debounce = tokio::time::Duration::from_secs(config.save_watch_debounce_secs as u64);
+        let (watcher, mut settled_rx) = SaveDirectoryWatcher::start(&config.custom_save_paths, debounce);
         let state_clone = state.clone();
         let app_clone = app_handle.clone();
-        let notifications = config.notifications_enabled;
-        
+
         tokio::spawn(async move {
-            let mut monitor = ProcessMonitor::new(game_exes);
-            
-            tokio::select! {
-                _ = async {
-                    let mut check_count = 0;
-                    loop {
-                        check_count += 1;
-                        if check_count % 20 == 0 {
-                            println!("[ProcessMonitor] Loop running, check #{}", check_count);
-                        }
-                        if let Some(event) = monitor.check_processes().await {
-                            match event {
-                                process_monitor::GameEvent::Started(game) => {
-                                    println!("Process-monitored game detected: {}", game.name);
-
-                                    // Update current game (use 0 for non-Steam games)
-                                    {
-                                        let mut current_game = state_clone.current_game.lock().unwrap();
-                                        *current_game = Some((game.name.clone(), 0));
-                                    }
-
-                                    // Update tray menu asynchronously without blocking
-                                    let app_for_tray = app_clone.clone();
-                                    let game_name_for_tray = game.name.clone();
-                                    tauri::async_runtime::spawn(async move {
-                                        update_tray_menu(&app_for_tray, Some(game_name_for_tray));
-                                    });
-
-                                    // Start watching achievements for this game (lookup app_id from database)
-                                    let game_name_for_watcher = game.name.clone();
-                                    if let Some(ref watcher) = *state_clone.achievement_watcher.lock().unwrap() {
-                                        let watcher = Arc::clone(watcher);
-
-                                        // Try to find app_id from database
-                                        let db = {
-                                            let path_guard = state_clone.achievement_db_path.lock().unwrap();
-                                            match &*path_guard {
-                                                Some(path) => AchievementDatabase::new(path.clone()).ok(),
-                                                None => None,
-                                            }
-                                        };
-
-                                        if let Some(db) = db {
-                                            if let Ok(games) = db.get_all_games() {
-                                                if let Some(game_info) = games.iter().find(|g| g.game_name == game_name_for_watcher) {
-                                                    let app_id = game_info.app_id;
-                                                    tokio::spawn(async move {
-                                                        watcher.start_watching_game(app_id, game_name_for_watcher).await;
-                                                    });
-                                                    println!("Started watching achievements for {} (AppID: {})", game.name, app_id);
-                                                } else {
-                                                    println!("Game {} not found in achievement database", game.name);
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    // Pause Steam monitoring
-                                    let steam_tx_opt = {
-                                        let guard = state_clone.steam_handle.lock().unwrap();
-                                        guard.clone()
-                                    };
-
-                                    if let Some(steam_tx) = steam_tx_opt {
-                                        let _ = steam_tx.send(MonitorCommand::Pause).await;
-                                        println!("Paused Steam monitoring while {} is running", game.name);
-                                    }
-
-                                    if notifications {
-                                        state_clone.notification_manager.lock().unwrap().show_game_detected(&game.name);
-                                    }
-
-                                    let _ = app_clone.emit_all("game-detected", &game.name);
-                                }
-                                process_monitor::GameEvent::Ended(game) => {
-                                    println!("Process-monitored game ended: {}", game.name);
-
-                                    // Clear current game
-                                    {
-                                        let mut current_game = state_clone.current_game.lock().unwrap();
-                                        *current_game = None;
-                                    }
-
-                                    // Update tray menu asynchronously without blocking
-                                    let app_for_tray = app_clone.clone();
-                                    tauri::async_runtime::spawn(async move {
-                                        update_tray_menu(&app_for_tray, None);
-                                    });
-
-                                    // Stop watching achievements for this game (lookup app_id from database)
-                                    let game_name_for_stop = game.name.clone();
-                                    if let Some(ref watcher) = *state_clone.achievement_watcher.lock().unwrap() {
-                                        // Try to find app_id from database
-                                        let db = {
-                                            let path_guard = state_clone.achievement_db_path.lock().unwrap();
-                                            match &*path_guard {
-                                                Some(path) => AchievementDatabase::new(path.clone()).ok(),
-                                                None => None,
-                                            }
-                                        };
-
-                                        if let Some(db) = db {
-                                            if let Ok(games) = db.get_all_games() {
-                                                if let Some(game_info) = games.iter().find(|g| g.game_name == game_name_for_stop) {
-                                                    watcher.stop_watching_game(game_info.app_id);
-                                                    println!("Stopped watching achievements for {} (AppID: {})", game.name, game_info.app_id);
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    // Resume Steam monitoring
-                                    let steam_tx_opt = {
-                                        let guard = state_clone.steam_handle.lock().unwrap();
-                                        guard.clone()
-                                    };
-
-                                    if let Some(steam_tx) = steam_tx_opt {
-                                        let _ = steam_tx.send(MonitorCommand::Resume).await;
-                                        println!("Resumed Steam monitoring");
-                                    }
-
-                                    if notifications {
-                                        state_clone.notification_manager.lock().unwrap().show_game_ended(&game.name);
-                                    }
-
-                                    handle_game_backup(game.name, None, &state_clone, app_clone.clone()).await;
-                                }
-                            }
-                        }
-                        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                    }
-                } => {}
-                _ = rx.recv() => {
-                    println!("Process monitor stopped");
-                }
+            while let Some(game_name) = settled_rx.recv().await {
+                handle_game_backup(game_name, None, &state_clone, app_clone.clone()).await;
             }
         });
 
-        *state.process_handle.lock().unwrap() = Some(tx);
-        println!("✓ Process monitor started for {} games", config.game_executables.len());
+        *state.save_watcher.lock_or_recover() = Some(watcher);
+        app_log!("✓ Save folder watcher started for {} games", config.custom_save_paths.len());
+    }
+
+    // Exchange unlocks and backup indexes with other instances on the LAN
+    if config.lan_sync.enabled {
+        let db_path = state.achievement_db_path.lock_or_recover().clone();
+        match db_path {
+            Some(db_path) => match LanSync::start(config.lan_sync.clone(), db_path).await {
+                Ok(lan) => {
+                    *state.lan_sync.lock_or_recover() = Some(lan);
+                    app_log!("✓ LAN sync started");
+                }
+                Err(e) => app_log!("[LanSync] {}", e),
+            },
+            None => app_log!("[LanSync] Achievement database not initialized, LAN sync not started"),
+        }
     }
 
     println!("All monitors started successfully");
@@ -1717,22 +5015,27 @@ async fn stop_monitors(state: &AppState) {
     println!("Stopping monitors...");
 
     // Stop all achievement watchers first to prevent duplicate notifications
-    if let Some(ref watcher) = *state.achievement_watcher.lock().unwrap() {
+    if let Some(ref watcher) = *state.achievement_watcher.read().await {
         watcher.stop_all_watchers();
     }
 
-    // Stop Steam monitor
-    let steam_tx = state.steam_handle.lock().unwrap().take();
-    if let Some(tx) = steam_tx {
-        println!("Sending stop command to Steam monitor");
-        let _ = tx.send(MonitorCommand::Stop).await;
+    // Stop game detection
+    if state.supervisor.is_supervised(GAME_DETECTION_TASK) {
+        app_log!("Stopping game detection");
+        state.supervisor.stop(GAME_DETECTION_TASK);
+    }
+
+    // Stop save folder watcher
+    if let Some(watcher) = state.save_watcher.lock_or_recover().take() {
+        let pending = watcher.pending_count();
+        if pending > 0 {
+            app_log!("Save folder watcher stopped with {} game(s) still waiting for backup", pending);
+        }
     }
 
-    // Stop process monitor
-    let process_tx = state.process_handle.lock().unwrap().take();
-    if let Some(tx) = process_tx {
-        println!("Sending stop command to process monitor");
-        let _ = tx.send(true).await;
+    // Stop LAN sync (it is restarted with the new settings)
+    if let Some(lan) = state.lan_sync.lock_or_recover().take() {
+        lan.stop();
     }
 
     // Give monitors more time to shut down gracefully and complete any in-progress operations
@@ -1740,50 +5043,168 @@ async fn stop_monitors(state: &AppState) {
     println!("Monitors stopped");
 }
 
-fn create_tray() -> SystemTray {
-    let open = CustomMenuItem::new("open".to_string(), "Open Settings");
-    let quit = CustomMenuItem::new("quit".to_string(), "Quit");
-    let tray_menu = SystemTrayMenu::new()
-        .add_item(open)
-        .add_native_item(tauri::SystemTrayMenuItem::Separator)
-        .add_item(quit);
+// Serve the control pipe for the rest of the app's life; does nothing if it is already up
+fn start_control_pipe(state: &AppState, app: &tauri::AppHandle) {
+    {
+        let mut running = state.control_pipe_running.lock_or_recover();
+        if *running {
+            return;
+        }
+        *running = true;
+    }
 
-    SystemTray::new().with_menu(tray_menu)
+    let state = state.clone();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let handler_state = state.clone();
+        let result = control_pipe::serve(move |command| {
+            let state = handler_state.clone();
+            let app = app.clone();
+            async move { handle_control_command(&state, &app, command).await }
+        }).await;
+
+        if let Err(e) = result {
+            app_log!("[ControlPipe] {}", e);
+        }
+        *state.control_pipe_running.lock_or_recover() = false;
+    });
+    app_log!("✓ Control pipe listening on {}", control_pipe::PIPE_NAME);
 }
 
-fn update_tray_menu(app: &tauri::AppHandle, game_name: Option<String>) {
-    use tauri::SystemTraySubmenu;
+async fn handle_control_command(state: &AppState, app: &tauri::AppHandle, command: ControlCommand) -> Result<String, String> {
+    // Turning the setting off stops commands right away; the pipe itself goes with the app
+    if !state.config.lock_or_recover().get_all().control_pipe_enabled {
+        return Err("External control is turned off in settings".to_string());
+    }
+    app_log!("[ControlPipe] {:?}", command);
+
+    match command {
+        ControlCommand::Pause => {
+            let was_paused = std::mem::replace(&mut *state.monitoring_paused.lock_or_recover(), true);
+            if !was_paused {
+                stop_monitors(state).await;
+                publish_event(state, app, AppEvent::MonitoringPaused { paused: true });
+            }
+            Ok("Monitoring paused".to_string())
+        }
+        ControlCommand::Resume => {
+            let was_paused = std::mem::replace(&mut *state.monitoring_paused.lock_or_recover(), false);
+            if was_paused {
+                let window = app.get_webview_window("main").ok_or("Main window not found")?;
+                start_monitors(state, window).await;
+                publish_event(state, app, AppEvent::MonitoringPaused { paused: false });
+            }
+            Ok("Monitoring resumed".to_string())
+        }
+        ControlCommand::Backup { game } => {
+            ensure_writable(state)?;
+            let (game_name, app_id) = match game.parse::<u32>() {
+                Ok(app_id) => {
+                    let game_name = find_game_name_by_app_id(state, app_id)
+                        .ok_or_else(|| format!("No known game with app id {}", app_id))?;
+                    (game_name, Some(app_id))
+                }
+                Err(_) => (game.clone(), find_app_id_by_name(state, &game)),
+            };
 
-    let open = CustomMenuItem::new("open".to_string(), "Open Settings");
-    let quit = CustomMenuItem::new("quit".to_string(), "Quit");
+            let state = state.clone();
+            let app = app.clone();
+            let started = format!("Backup of {} started", game_name);
+            tauri::async_runtime::spawn(async move {
+                handle_game_backup(game_name, app_id, &state, app).await;
+            });
+            Ok(started)
+        }
+        ControlCommand::Status => {
+            let current_game = state.current_game.read().await.clone();
+            let status = ControlStatus {
+                paused: *state.monitoring_paused.lock_or_recover(),
+                monitoring: state.supervisor.is_supervised(GAME_DETECTION_TASK),
+                app_id: current_game.as_ref().map(|(_, app_id)| *app_id).filter(|app_id| *app_id != 0),
+                current_game: current_game.map(|(name, _)| name),
+            };
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+    }
+}
+
+const TRAY_ID: &str = "main";
 
-    let mut tray_menu = SystemTrayMenu::new();
+fn build_tray_menu(app: &tauri::AppHandle, game_name: Option<&str>) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
 
     // If a game is being monitored, add it to the menu
     if let Some(name) = game_name {
-        let game_item = CustomMenuItem::new("game".to_string(), format!("Monitoring: {}", name)).disabled();
-        let reset_monitoring = CustomMenuItem::new("reset_monitoring".to_string(), "Reset Monitoring");
-        let stop_monitoring = CustomMenuItem::new("stop_monitoring".to_string(), format!("Stop Monitoring {}", name));
-
-        let game_submenu = SystemTraySubmenu::new(
+        let game_submenu = Submenu::with_items(
+            app,
             format!("📊 {}", name),
-            SystemTrayMenu::new()
-                .add_item(reset_monitoring)
-                .add_item(stop_monitoring)
-        );
+            true,
+            &[
+                &MenuItem::with_id(app, "reset_monitoring", "Reset Monitoring", true, None::<&str>)?,
+                &MenuItem::with_id(app, "stop_monitoring", format!("Stop Monitoring {}", name), true, None::<&str>)?,
+            ],
+        )?;
+        menu.append(&game_submenu)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    menu.append(&MenuItem::with_id(app, "open", "Open Settings", true, None::<&str>)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn update_tray_menu(app: &tauri::AppHandle, game_name: Option<String>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    match build_tray_menu(app, game_name.as_deref()) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("Failed to build tray menu: {}", e),
+    }
+}
 
-        tray_menu = tray_menu
-            .add_submenu(game_submenu)
-            .add_native_item(tauri::SystemTrayMenuItem::Separator);
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
     }
+}
 
-    tray_menu = tray_menu
-        .add_item(open)
-        .add_native_item(tauri::SystemTrayMenuItem::Separator)
-        .add_item(quit);
+fn handle_tray_menu_event(app: &tauri::AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "open" => show_main_window(app),
+        "reset_monitoring" => {
+            let app_handle = app.clone();
+            let Some(window) = app.get_webview_window("main") else { return };
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    let _ = reset_game_monitoring(state, window).await;
+                }
+            });
+        }
+        "stop_monitoring" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    let _ = stop_game_monitoring(state).await;
+                    // Update tray menu to remove game
+                    update_tray_menu(&app_handle, None);
+                }
+            });
+        }
+        "quit" => {
+            // Exit through the run loop so a downloaded update gets installed
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
 
-    let tray_handle = app.tray_handle();
-    let _ = tray_handle.set_menu(tray_menu);
+fn handle_tray_icon_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+        show_main_window(tray.app_handle());
+    }
 }
 
 fn main() {
@@ -1792,9 +5213,9 @@ fn main() {
         let panic_msg = format!("PANIC: {:?}", panic_info);
         eprintln!("{}", panic_msg);
 
-        // Write to log file in Documents folder
-        if let Some(docs) = dirs::document_dir() {
-            let log_path = docs.join("Steam Backup Manager Crash.log");
+        // Write to log file in Documents folder (or the app data fallback)
+        {
+            let log_path = user_folders::output_root().join("Steam Backup Manager Crash.log");
             let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S");
             let log_msg = format!("[{}] {}\n", timestamp, panic_msg);
             let _ = std::fs::write(&log_path, log_msg);
@@ -1821,44 +5242,93 @@ fn main() {
     }));
 
     // Also set up file logging for regular messages
-    if let Some(docs) = dirs::document_dir() {
-        let log_path = docs.join("Steam Backup Manager Debug.log");
-        let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S");
-        let _ = std::fs::write(&log_path, format!("[{}] App starting...\n", timestamp));
-        println!("Logging to: {}", log_path.display());
+    {
+        logging::start();
+        println!("Logging to: {}", logging::log_path().display());
     }
 
+    // A new version that died during its first launch is swapped back for the previous one
+    let exe_path = std::env::current_exe().ok();
+    let first_launch = match update_rollback::check_on_startup(env!("CARGO_PKG_VERSION")) {
+        StartupCheck::RolledBack(version) => {
+            app_log!("Restored version {}, restarting", version);
+            if let Some(exe) = exe_path {
+                let _ = std::process::Command::new(exe).spawn();
+            }
+            return;
+        }
+        StartupCheck::FirstLaunch => true,
+        StartupCheck::Normal => false,
+    };
+
     tauri::Builder::default()
-        .setup(|app| {
+        // A second launch (a link, the context menu, the Start menu) hands its arguments to this instance
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            match shell_integration::from_args(args) {
+                Some(request) => handle_launch_request(app, request),
+                None => show_main_window(app),
+            }
+        }))
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(move |app| {
             // CRITICAL: Register state IMMEDIATELY with minimal setup
             // This prevents race conditions where frontend tries to access state before it's ready
             let config = Arc::new(Mutex::new(ConfigManager::new()));
 
             // Load achievement duration from config
-            let duration_from_config = config.lock().unwrap().get_all().achievement_duration;
+            let duration_from_config = config.lock_or_recover().get_all().achievement_duration;
             let achievement_duration = Arc::new(Mutex::new(duration_from_config));
 
             let state = AppState {
                 config: config.clone(),
-                steam_handle: Arc::new(Mutex::new(None)),
-                process_handle: Arc::new(Mutex::new(None)),
+                supervisor: Supervisor::default(),
                 notification_manager: Arc::new(Mutex::new(NotificationManager::new(achievement_duration.clone()))),
                 achievement_db_path: Arc::new(Mutex::new(None)),
-                achievement_watcher: Arc::new(Mutex::new(None)),
+                achievement_watcher: Arc::new(RwLock::new(None)),
                 overlay_manager: Arc::new(Mutex::new(OverlayManager::new())),
                 achievement_duration,
-                current_game: Arc::new(Mutex::new(None)),
+                current_game: Arc::new(RwLock::new(None)),
+                last_played_game: Arc::new(Mutex::new(None)),
+                save_watcher: Arc::new(Mutex::new(None)),
+                pending_uploads: Arc::new(Mutex::new(cloud::load_pending_uploads())),
+                lan_sync: Arc::new(Mutex::new(None)),
+                session_started_at: Arc::new(Mutex::new(None)),
+                last_session_recap: Arc::new(Mutex::new(None)),
+                event_bus: Arc::new(Mutex::new(EventBus::default())),
+                pending_update: Arc::new(Mutex::new(None)),
+                pending_launch: Arc::new(Mutex::new(None)),
+                monitoring_paused: Arc::new(Mutex::new(false)),
+                control_pipe_running: Arc::new(Mutex::new(false)),
+                running_backups: Arc::new(Mutex::new(HashMap::new())),
+                session_backups: Arc::new(Mutex::new(None)),
+                backup_slot: Arc::new(tokio::sync::Mutex::new(())),
+                queued_backups: Arc::new(Mutex::new(Vec::new())),
+                active_operations: Arc::new(Mutex::new(ActiveOperations::default())),
+                library_backup_pending: Arc::new(Mutex::new(false)),
+                link_backups: Arc::new(Mutex::new(HashMap::new())),
             };
 
             // Register state FIRST - before doing ANYTHING else
             app.manage(state.clone());
             println!("✓ State registered with Tauri (frontend can now access it safely)");
 
+            // The debug log already probed Documents; tell the user if it wasn't writable
+            report_output_fallback(&state);
+
+            // Achievement backups used to be named after the game; move them into per-app id folders
+            std::thread::spawn(|| {
+                let migrated = achievement_backups::migrate_all();
+                if migrated > 0 {
+                    app_log!("✓ Moved {} achievement backups into app id folders", migrated);
+                }
+            });
+
             // NOW create and show the main window - state is registered so frontend can safely call commands
-            let main_window = tauri::WindowBuilder::new(
+            let main_window = WebviewWindowBuilder::new(
                 app,
                 "main",
-                tauri::WindowUrl::App("index.html".into())
+                WebviewUrl::App("index.html".into())
             )
             .title("Steam Backup Manager")
             .inner_size(1100.0, 800.0)
@@ -1875,11 +5345,18 @@ fn main() {
             }
             println!("✓ Main window created and shown");
 
+            // The tray icon itself comes from tauri.conf.json; attach the menu and handlers
+            if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                tray.set_menu(Some(build_tray_menu(app.handle(), None)?))?;
+                tray.on_menu_event(handle_tray_menu_event);
+                tray.on_tray_icon_event(handle_tray_icon_event);
+            }
+
             // Now it's safe to initialize components
             // Initialize overlay manager
             {
-                let mut overlay = state.overlay_manager.lock().unwrap();
-                if let Err(e) = overlay.init(&app.app_handle()) {
+                let mut overlay = state.overlay_manager.lock_or_recover();
+                if let Err(e) = overlay.init(app.handle()) {
                     eprintln!("Failed to initialize overlay: {}", e);
                 } else {
                     println!("✓ Overlay initialized");
@@ -1888,21 +5365,21 @@ fn main() {
 
             // Set overlay in notification manager
             {
-                let mut notif = state.notification_manager.lock().unwrap();
+                let mut notif = state.notification_manager.lock_or_recover();
                 notif.set_overlay_manager(state.overlay_manager.clone());
                 println!("✓ Notification manager configured");
             }
 
-            // Listen for overlay-notifications-done event to auto-hide overlay
+            // Listen for overlay-notifications-done event to auto-hide overlay (also from a recreated overlay)
             let overlay_manager_for_listener = state.overlay_manager.clone();
-            if let Some(overlay_window) = app.get_window("overlay") {
-                overlay_window.listen("overlay-notifications-done", move |_event| {
-                    println!("[Overlay] Received notifications-done event, hiding overlay");
-                    if let Ok(overlay) = overlay_manager_for_listener.lock() {
-                        let _ = overlay.hide_overlay();
-                    }
-                });
+            app.listen_any("overlay-notifications-done", move |_event| {
+                app_log!("[Overlay] Received notifications-done event, hiding overlay");
+                if let Ok(overlay) = overlay_manager_for_listener.lock() {
+                    let _ = overlay.hide_overlay();
+                }
+            });
 
+            if let Some(overlay_window) = app.get_webview_window(overlay::OVERLAY_LABEL) {
                 // IMPORTANT: Send initial settings to overlay window
                 // This ensures the overlay has the correct settings even in production builds
                 // where localStorage is NOT shared between windows
@@ -1910,7 +5387,7 @@ fn main() {
 
                 // Send achievement settings (duration)
                 let achievement_settings = serde_json::json!({ "duration": 6 }); // Default value
-                if let Err(e) = overlay_window.emit("achievement-settings-sync", &achievement_settings) {
+                if let Err(e) = overlay_window.emit_to(overlay_window.label(), "achievement-settings-sync", &achievement_settings) {
                     eprintln!("Failed to emit initial achievement settings: {}", e);
                 }
 
@@ -1953,15 +5430,15 @@ fn main() {
                         "customFont": null
                     }
                 });
-                if let Err(e) = overlay_window.emit("rarity-settings-sync", &rarity_settings) {
+                if let Err(e) = overlay_window.emit_to(overlay_window.label(), "rarity-settings-sync", &rarity_settings) {
                     eprintln!("Failed to emit initial rarity settings: {}", e);
                 }
             }
 
             // Initialize achievement database
-            let db_path = app.path_resolver()
+            let db_path = app.path()
                 .app_data_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
+                .unwrap_or_else(|_| PathBuf::from("."))
                 .join("achievements.db");
 
             // Create parent directory if it doesn't exist
@@ -1982,19 +5459,19 @@ fn main() {
             };
 
             // Update state with database path
-            *state.achievement_db_path.lock().unwrap() = achievement_db_path_option.clone();
+            *state.achievement_db_path.lock_or_recover() = achievement_db_path_option.clone();
 
             // Initialize achievement watcher
             let steam_path = PathBuf::from(r"C:\Program Files (x86)\Steam");
             let steam_user_id_for_watcher = {
-                let config_guard = config.lock().unwrap();
+                let config_guard = config.lock_or_recover();
                 let cfg = config_guard.get_all();
                 cfg.steam_user_id
             };
             let achievement_watcher_option = achievement_db_path_option.as_ref().map(|_| {
                 // Create steam client for the watcher
                 let (api_key, steam_id_64) = {
-                    let config_guard = config.lock().unwrap();
+                    let config_guard = config.lock_or_recover();
                     let cfg = config_guard.get_all();
                     (cfg.steam_api_key, cfg.steam_id_64)
                 };
@@ -2010,11 +5487,50 @@ fn main() {
                 watcher.set_event_sender(unlock_tx);
 
                 // Spawn task to listen for achievement unlock events and emit them to frontend
-                let app_handle = app.app_handle();
+                let app_handle = app.handle().clone();
+                let feed_db_path = db_path.clone();
+                let milestone_state = state.clone();
                 std::thread::spawn(move || {
                     while let Ok(event) = unlock_rx.recv() {
                         println!("🏆 Achievement unlocked: {} - {}", event.game_name, event.display_name);
-                        let _ = app_handle.emit_all("achievement-unlocked", &event);
+
+                        // A simulated unlock is undone afterwards, so it isn't history and can't reach a milestone
+                        if event.source == SIMULATED_SOURCE {
+                            publish_event(&milestone_state, &app_handle, AppEvent::AchievementUnlocked(event));
+                            continue;
+                        }
+
+                        // Persist for the activity feed
+                        let entry = UnlockFeedEntry {
+                            id: None,
+                            app_id: event.app_id,
+                            game_name: event.game_name.clone(),
+                            achievement_id: event.achievement_id.clone(),
+                            display_name: event.display_name.clone(),
+                            description: event.description.clone(),
+                            icon_url: event.icon_url.clone(),
+                            unlock_time: event.unlock_time,
+                            source: event.source.clone(),
+                            global_unlock_percentage: event.global_unlock_percentage,
+                        };
+                        if let Err(e) = AchievementDatabase::new(feed_db_path.clone()).and_then(|db| db.add_unlock_to_feed(&entry)) {
+                            app_log!("[Feed] {}", e);
+                        }
+
+                        announce_milestone(&milestone_state, &feed_db_path, &event);
+
+                        run_script_hooks(&milestone_state, ScriptEvent::AchievementUnlocked {
+                            game_name: event.game_name.clone(),
+                            app_id: event.app_id,
+                            achievement_id: event.achievement_id.clone(),
+                            display_name: event.display_name.clone(),
+                            description: event.description.clone(),
+                            unlock_time: event.unlock_time,
+                            global_unlock_percentage: event.global_unlock_percentage.map(f64::from),
+                        });
+                        send_unlock_webhook(&milestone_state, &event);
+
+                        publish_event(&milestone_state, &app_handle, AppEvent::AchievementUnlocked(event));
                     }
                 });
 
@@ -2022,15 +5538,48 @@ fn main() {
             });
 
             // Update state with achievement watcher
-            *state.achievement_watcher.lock().unwrap() = achievement_watcher_option;
+            // Setup runs on the main thread outside the async runtime and nothing else holds the lock yet
+            *state.achievement_watcher.blocking_write() = achievement_watcher_option;
+
+            // Back up the current/recent game when Windows shuts down, logs off or sleeps
+            let (power_tx, power_rx) = channel::<power_events::PowerEvent>();
+            power_events::set_block_budget(state.config.lock_or_recover().get_all().shutdown_backup_budget_secs);
+            match power_events::start_power_listener(power_tx) {
+                Ok(()) => {
+                    let state_clone = state.clone();
+                    let app_handle = app.handle().clone();
+                    std::thread::spawn(move || {
+                        while let Ok(event) = power_rx.recv() {
+                            tauri::async_runtime::block_on(handle_power_event(event.kind, &state_clone, app_handle.clone()));
+                            let _ = event.done.send(());
+                        }
+                    });
+                }
+                Err(e) => app_log!("[PowerEvents] Failed to start power event listener: {}", e),
+            }
 
             // Initialize monitors
             let state_clone = state.clone();
             let window_clone = main_window.clone();
             tauri::async_runtime::spawn(async move {
+                if config::launched_at_login() {
+                    wait_for_startup_conditions(&state_clone).await;
+                }
                 start_monitors(&state_clone, window_clone).await;
             });
 
+            // Links and Explorer open the app through the registry, so keep it pointing at this executable
+            if let Err(e) = shell_integration::register_protocol() {
+                app_log!("[Shell] {}", e);
+            }
+            if let Some(request) = shell_integration::from_args(std::env::args()) {
+                handle_launch_request(app.handle(), request);
+            }
+
+            if state.config.lock_or_recover().get_all().control_pipe_enabled {
+                start_control_pipe(&state, app.handle());
+            }
+
             // Start periodic checking for pending games (every 10 minutes)
             let state_clone = state.clone();
             tauri::async_runtime::spawn(async move {
@@ -2038,88 +5587,246 @@ fn main() {
                 loop {
                     interval.tick().await;
 
-                    // Clone watcher Arc in a separate block to drop the mutex guard
-                    let watcher_opt = {
-                        let guard = state_clone.achievement_watcher.lock().unwrap();
-                        guard.as_ref().map(|w| Arc::clone(w))
-                    };
-
-                    if let Some(watcher) = watcher_opt {
+                    if let Some(watcher) = state_clone.achievement_watcher.read().await.as_ref() {
                         watcher.check_pending_games().await;
                     }
                 }
             });
 
-            Ok(())
-        })
-        .system_tray(create_tray())
-        .on_system_tray_event(|app, event| match event {
-            SystemTrayEvent::LeftClick { .. } => {
-                let window = app.get_window("main").unwrap();
-                window.show().unwrap();
-                window.set_focus().unwrap();
-            }
-            SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
-                "open" => {
-                    let window = app.get_window("main").unwrap();
-                    window.show().unwrap();
-                    window.set_focus().unwrap();
-                }
-                "reset_monitoring" => {
-                    let app_handle = app.clone();
-                    let window = app.get_window("main").unwrap();
-                    tauri::async_runtime::spawn(async move {
-                        if let Some(state) = app_handle.try_state::<AppState>() {
-                            let _ = reset_game_monitoring(state, window).await;
+            // Send uploads that were waiting for their destination's upload window
+            let state_clone = state.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+                loop {
+                    interval.tick().await;
+                    process_pending_uploads(&state_clone, &app_handle).await;
+                }
+            });
+
+            // Pick up achievements unlocked on the other PCs
+            let state_clone = state.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(600));
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = run_achievement_sync(&state_clone).await {
+                        app_log!("[AchievementSync] {}", e);
+                    }
+                }
+            });
+
+            // Push new unlocks to LAN peers every few seconds, and the backup index now and then
+            let state_clone = state.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15));
+                let mut pushed_until = 0;
+                let mut ticks: u64 = 0;
+                loop {
+                    interval.tick().await;
+                    push_lan_updates(&state_clone, &mut pushed_until).await;
+
+                    ticks += 1;
+                    if ticks % 20 == 0 {
+                        let lan = state_clone.lan_sync.lock_or_recover().clone();
+                        if let Some(lan) = lan {
+                            let backup_path = state_clone.config.lock_or_recover().get_all().backup_path;
+                            push_backup_index(&lan, backup_path).await;
                         }
-                    });
+                    }
                 }
-                "stop_monitoring" => {
-                    let app_handle = app.clone();
-                    tauri::async_runtime::spawn(async move {
-                        if let Some(state) = app_handle.try_state::<AppState>() {
-                            let _ = stop_game_monitoring(state).await;
-                            // Update tray menu to remove game
-                            update_tray_menu(&app_handle, None);
+            });
+
+            // Follow changes to the excluded Steam categories, in settings or in the Steam library
+            let state_clone = state.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+                let mut last_seen = None;
+                let mut steam_path = None;
+                loop {
+                    interval.tick().await;
+                    // Looked up until Steam is found, then kept
+                    if steam_path.is_none() {
+                        steam_path = SteamMonitor::find_steam_path().ok();
+                    }
+                    let Some(steam_path) = steam_path.as_deref() else { continue };
+                    let (categories, steam_user_id) = {
+                        let cfg = state_clone.config.lock_or_recover().get_all();
+                        (cfg.excluded_steam_categories, cfg.steam_user_id)
+                    };
+                    let modified = steam_accounts::userdata_dir(steam_path, steam_user_id.as_deref())
+                        .and_then(|dir| std::fs::metadata(steam_categories::sharedconfig_path(&dir)).map_err(|e| e.to_string()))
+                        .and_then(|m| m.modified().map_err(|e| e.to_string()))
+                        .ok();
+                    let seen = Some((categories, modified));
+                    if seen == last_seen {
+                        continue;
+                    }
+                    match sync_category_exclusions(&state_clone) {
+                        Ok(_) => last_seen = seen,
+                        Err(e) => app_log!("[Exclusions] {}", e),
+                    }
+                }
+            });
+
+            // Periodically re-check stored backups for bit-rot and missing files
+            let state_clone = state.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+                loop {
+                    interval.tick().await;
+                    let interval_hours = state_clone.config.lock_or_recover().get_all().verification_interval_hours;
+                    if verification::is_due(interval_hours) {
+                        if let Err(e) = run_verification(&state_clone).await {
+                            app_log!("[Verify] Scheduled verification failed: {}", e);
                         }
-                    });
+                    }
+                }
+            });
+
+            // Back up the whole library at the configured time
+            let state_clone = state.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut last_check = chrono::Local::now().naive_local();
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+                loop {
+                    interval.tick().await;
+                    check_library_backup_schedule(&state_clone, &app_handle, &mut last_check).await;
                 }
-                "quit" => {
-                    std::process::exit(0);
+            });
+
+            // Notice games uninstalled between checks or while the app was closed
+            let state_clone = state.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(600));
+                loop {
+                    interval.tick().await;
+                    check_uninstalled_games(&state_clone, &app_handle).await;
                 }
-                _ => {}
-            },
-            _ => {}
+            });
+
+            // Look for regularly played games that haven't been backed up in a while
+            let state_clone = state.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(6 * 3600));
+                let mut warned = HashMap::new();
+                loop {
+                    interval.tick().await;
+                    check_stale_backups(&state_clone, &app_handle, &mut warned);
+                }
+            });
+
+            // Running this long after an update counts as a successful first launch
+            if first_launch {
+                tauri::async_runtime::spawn(async {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(UPDATE_HEALTHY_AFTER_SECS)).await;
+                    update_rollback::mark_healthy();
+                });
+            }
+
+            Ok(())
         })
-        .on_window_event(|event| match event.event() {
-            tauri::WindowEvent::CloseRequested { api, .. } => {
-                event.window().hide().unwrap();
+        .register_uri_scheme_protocol(icon_cache::ICON_SCHEME, |_ctx, request| serve_cached_icon(&request))
+        .register_uri_scheme_protocol(achievement_screenshots::SCREENSHOT_SCHEME, |ctx, request| serve_screenshot(ctx.app_handle(), &request))
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                window.hide().unwrap();
                 api.prevent_close();
             }
-            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
             save_config,
+            get_setup_status,
+            detect_steam_setup,
+            find_ludusavi,
+            download_ludusavi,
+            check_backup_folder,
+            validate_steam_api_key,
+            complete_setup,
+            set_read_only_mode,
             browse_file,
             browse_folder,
             test_ludusavi,
             get_ludusavi_manifest,
+            get_ludusavi_customization,
+            save_ludusavi_custom_game,
+            remove_ludusavi_custom_game,
+            set_ludusavi_roots,
             get_all_achievements,
             get_game_achievements,
+            get_merged_game_achievements,
             update_achievement_status,
             sync_achievements,
+            sync_achievements_across_pcs,
+            get_lan_peers,
             add_manual_achievement,
             export_achievements,
             export_game_achievements,
+            choose_export_destination,
+            export_activity_timeline,
             search_steam_games,
             check_game_sources,
             add_game_from_source,
             remove_game_from_tracking,
+            find_duplicate_games,
+            consolidate_game_sources,
+            delete_game_source,
+            detect_achievement_dlc,
+            get_achievement_dlc_groups,
+            set_achievement_dlc,
+            get_completion_order,
+            get_achievement_tags,
+            set_achievement_tags,
+            scan_achievement_screenshots,
+            get_achievement_screenshots,
+            get_achievement_view_prefs,
+            set_achievement_view_prefs,
+            get_game_metadata,
+            set_game_metadata,
+            delete_game_metadata,
+            set_game_status,
+            get_game_steam_ids,
+            set_game_steam_id,
+            set_game_favorite,
+            get_recent_unlocks,
+            get_unlock_statistics,
+            get_last_session_recap,
+            export_sound_pack,
+            import_sound_pack,
+            list_sound_packs,
+            delete_sound_pack,
+            list_achievement_source_plugins,
+            get_achievement_source_plugins_folder,
+            get_achievement_source_health,
+            list_scripts,
+            get_missed_events,
+            get_active_operations,
+            take_launch_request,
+            get_diagnostics,
+            simulate_game_session,
+            get_scripts_folder,
+            test_unlock_webhook,
             get_all_exclusions,
             add_exclusion,
             remove_exclusion,
+            add_exclusions,
+            remove_exclusions,
+            exclude_non_game_apps,
+            export_exclusions,
+            import_exclusions,
+            get_steam_categories,
+            sync_steam_category_exclusions,
+            get_steam_executables,
+            get_steam_library,
+            get_ignored_executables,
+            ignore_executable,
+            unignore_executable,
             fetch_achievement_icon,
+            download_all_achievement_icons,
             test_overlay,
             test_rarity_notification,
             sync_settings_to_overlay,
@@ -2132,8 +5839,75 @@ fn main() {
             debug_log,
             read_audio_file,
             check_backup_exists,
-            restore_from_backup
+            get_output_location,
+            restore_from_backup,
+            get_backup_key_status,
+            generate_backup_key,
+            set_backup_key_passphrase,
+            export_backup_recovery_phrase,
+            verify_backup_key,
+            recover_backup_key,
+            list_achievement_backups,
+            delete_achievement_backup,
+            rename_achievement_backup,
+            list_game_snapshots,
+            list_backup_versions,
+            compare_backup_snapshots,
+            list_snapshot_restore_files,
+            restore_snapshot_files,
+            restore_game_saves,
+            restore_backup_version,
+            backup_game_now,
+            preview_backup,
+            backup_all_games,
+            next_scheduled_backup,
+            cancel_backup,
+            list_dedup_snapshots,
+            extract_dedup_snapshot,
+            delete_dedup_snapshot,
+            get_dedup_stats,
+            get_backup_stats,
+            get_storage_dashboard,
+            run_backup_verification,
+            get_backup_history,
+            get_recent_backups,
+            get_backup_freshness,
+            get_uninstalled_games,
+            archive_uninstalled_game,
+            discover_save_paths,
+            confirm_save_path,
+            start_cloud_auth,
+            complete_cloud_auth,
+            sign_out_cloud_destination,
+            test_cloud_destination,
+            get_dropbox_authorize_url,
+            complete_dropbox_auth,
+            set_cloud_destination_enabled,
+            set_cloud_destination_secret,
+            test_syncthing_connection,
+            get_pending_uploads,
+            list_google_drive_folders,
+            create_google_drive_folder,
+            upload_game_backup_to_cloud,
+            get_cloud_conflict,
+            resolve_cloud_conflict,
+            ludusavi_cloud_sync,
+            get_ludusavi_cloud_status,
+            get_cloud_quota,
+            check_for_update,
+            download_update,
+            get_update_status,
+            rollback_update
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Exiting normally also proves a freshly installed version works
+                if first_launch {
+                    update_rollback::mark_healthy();
+                }
+                apply_pending_update(app);
+            }
+        });
 }
\ No newline at end of file
@@ -2,13 +2,28 @@ use windows::Win32::Media::Audio::{PlaySoundA, SND_ALIAS, SND_ASYNC};
 use windows::core::PCSTR;
 use std::ffi::CString;
 use std::thread;
-use notify_rust::Notification;
+use notify_rust::{Notification, Timeout};
 use crate::overlay::OverlayManager;
+use steam_save_core::session_recap::SessionRecap;
+use steam_save_core::milestones::MilestoneEvent;
 use std::sync::{Arc, Mutex};
+use steam_save_core::locks::LockExt;
+use steam_save_core::app_log;
+use steam_save_core::icon_cache;
+
+// Achievement cards stay up at least this long in Big Picture, where they're read from a distance
+const BIG_PICTURE_MIN_DURATION_SECS: u32 = 10;
+
+// A do-not-disturb game being played: its notifications are held for the recap at exit
+struct QuietSession {
+    game_name: String,
+    held: Vec<String>,
+}
 
 pub struct NotificationManager {
     overlay_manager: Option<Arc<Mutex<OverlayManager>>>,
     achievement_duration: Arc<Mutex<u32>>,
+    quiet_session: Option<QuietSession>,
 }
 
 impl NotificationManager {
@@ -16,6 +31,33 @@ impl NotificationManager {
         Self {
             overlay_manager: None,
             achievement_duration,
+            quiet_session: None,
+        }
+    }
+
+    /// Keep `game_name`'s notifications back until `end_quiet_session`
+    pub fn begin_quiet_session(&mut self, game_name: &str) {
+        app_log!("[NotificationManager] Do not disturb while {} runs", game_name);
+        self.quiet_session = Some(QuietSession { game_name: game_name.to_string(), held: Vec::new() });
+    }
+
+    pub fn is_quiet(&self, game_name: &str) -> bool {
+        self.quiet_session.as_ref().is_some_and(|session| session.game_name == game_name)
+    }
+
+    /// The game and the lines held back during its session
+    pub fn end_quiet_session(&mut self) -> Option<(String, Vec<String>)> {
+        self.quiet_session.take().map(|session| (session.game_name, session.held))
+    }
+
+    // Record a line for the recap instead of showing it; false when the game isn't quiet
+    fn hold(&mut self, game_name: &str, line: String) -> bool {
+        match &mut self.quiet_session {
+            Some(session) if session.game_name == game_name => {
+                session.held.push(line);
+                true
+            }
+            _ => false,
         }
     }
 
@@ -24,10 +66,13 @@ impl NotificationManager {
     }
 
     fn play_notification_sound() {
+        Self::play_system_sound("SystemNotification");
+    }
+
+    fn play_system_sound(alias: &'static str) {
         thread::spawn(move || {
             unsafe {
-                // Play Windows default notification sound
-                let sound_alias = CString::new("SystemNotification").unwrap_or_default();
+                let sound_alias = CString::new(alias).unwrap_or_default();
                 let _ = PlaySoundA(
                     PCSTR(sound_alias.as_ptr() as *const u8),
                     None,
@@ -39,72 +84,153 @@ impl NotificationManager {
 
     fn show_notification(&self, title: &str, body: &str) {
         Self::play_notification_sound();
-        
+        self.show_toast(title, body, Timeout::Milliseconds(2500));
+    }
+
+    // Failures use the long toast duration and stay in the Action Center until dismissed,
+    // so they aren't missed like a transient success toast
+    fn show_failure(&self, title: &str, body: &str) {
+        Self::play_notification_sound();
+        self.show_toast(title, body, Timeout::Never);
+    }
+
+    // Windows toasts pull focus out of Steam's gamepad UI, so in Big Picture they go to the overlay
+    // (which never activates) or are dropped
+    fn show_toast(&self, title: &str, body: &str, timeout: Timeout) {
+        if OverlayManager::is_big_picture_active() {
+            let shown = self.overlay_manager.as_ref()
+                .and_then(|overlay_manager| overlay_manager.lock().ok())
+                .is_some_and(|overlay| overlay.show_overlay("toast", serde_json::json!({ "title": title, "body": body })).is_ok());
+            if !shown {
+                app_log!("[NotificationManager] Big Picture is active, skipped toast: {}", title);
+            }
+            return;
+        }
+
         let title = title.to_string();
         let body = body.to_string();
-        
+
         thread::spawn(move || {
             let _ = Notification::new()
                 .summary(&title)
                 .body(&body)
-                .timeout(2500)
+                .timeout(timeout)
                 .show();
         });
     }
 
-    pub fn show_backup_success(&self, game_name: &str, files_backed_up: usize, total_size: &str) {
+    pub fn show_backup_success(&mut self, game_name: &str, files_backed_up: usize, total_size: &str) {
+        if self.hold(game_name, format!("💾 {} files backed up ({})", files_backed_up, total_size)) {
+            return;
+        }
         let body = format!("✓ {} files backed up\nSize: {}", files_backed_up, total_size);
         self.show_notification("Game Save Monitor", &format!("{}\n{}", game_name, body));
     }
 
-    pub fn show_backup_success_with_achievements(&self, game_name: &str, files_backed_up: usize, total_size: &str, achievements_count: usize) {
-        let body = if achievements_count > 0 {
-            format!("✓ {} files backed up\nSize: {}\n🏆 {} achievements backed up", files_backed_up, total_size, achievements_count)
-        } else {
-            format!("✓ {} files backed up\nSize: {}", files_backed_up, total_size)
+    /// `verified` is the post-backup check: None when it didn't run
+    pub fn show_backup_success_with_achievements(&mut self, game_name: &str, files_backed_up: usize, total_size: &str, achievements_count: usize, verified: Option<bool>) {
+        let check = match verified {
+            Some(true) => " (verified)",
+            Some(false) => " - verification failed, the backup may be incomplete",
+            None => "",
         };
-        self.show_notification("Game Save Monitor", &format!("{}\n{}", game_name, body));
+        if self.hold(game_name, format!("💾 {} files backed up ({}){}", files_backed_up, total_size, check)) {
+            return;
+        }
+        let mut body = format!("✓ {} files backed up{}\nSize: {}", files_backed_up, check, total_size);
+        if achievements_count > 0 {
+            body.push_str(&format!("\n🏆 {} achievements backed up", achievements_count));
+        }
+        if verified == Some(false) {
+            self.show_failure("Game Save Monitor", &format!("{}\n{}", game_name, body));
+        } else {
+            self.show_notification("Game Save Monitor", &format!("{}\n{}", game_name, body));
+        }
     }
 
     pub fn show_game_detected(&self, game_name: &str) {
+        if self.is_quiet(game_name) {
+            return;
+        }
         self.show_notification("Game Save Monitor", &format!("{}\n▶ Game Detected - Monitoring saves & achievements...", game_name));
     }
 
     pub fn show_game_ended(&self, game_name: &str) {
-        let game_name = game_name.to_string();
-        
+        if self.is_quiet(game_name) {
+            return;
+        }
+        let body = format!("{}\n⏹ Game Ended - Preparing backup...", game_name);
+        if OverlayManager::is_big_picture_active() {
+            self.show_toast("Game Save Monitor", &body, Timeout::Milliseconds(2500));
+            return;
+        }
+
         thread::spawn(move || {
             thread::sleep(std::time::Duration::from_millis(300));
             
             let _ = Notification::new()
                 .summary("Game Save Monitor")
-                .body(&format!("{}\n⏹ Game Ended - Preparing backup...", game_name))
+                .body(&body)
                 .timeout(2500)
                 .show();
         });
     }
 
-    pub fn show_backup_failed(&self, game_name: &str, error: &str) {
+    pub fn show_backup_failed(&mut self, game_name: &str, error: &str) {
+        if self.hold(game_name, format!("✗ Backup failed: {}", error)) {
+            return;
+        }
         let body = format!("✗ Backup Failed\nError: {}", error);
+        self.show_failure("Game Save Monitor", &format!("{}\n{}", game_name, body));
+    }
+
+    pub fn show_low_disk_space(&self, backup_path: &str, free: &str) {
+        self.show_failure("Game Save Monitor", &format!("⚠ Backup drive almost full\nOnly {} free on {}", free, backup_path));
+    }
+
+    pub fn show_stale_backups(&self, lines: &[String]) {
+        self.show_failure("Game Save Monitor", &format!("⚠ Backups are out of date\n{}", lines.join("\n")));
+    }
+
+    pub fn show_game_uninstalled(&self, game_name: &str, final_backup: bool) {
+        let body = if final_backup {
+            "🗑 Uninstalled - Backing up its saves one last time..."
+        } else {
+            "🗑 Uninstalled - Archive its saves from the app"
+        };
         self.show_notification("Game Save Monitor", &format!("{}\n{}", game_name, body));
     }
 
-    pub fn show_game_not_found(&self, game_name: &str) {
+    pub fn show_game_not_found(&mut self, game_name: &str) {
+        if self.hold(game_name, "⚠ Not found in Ludusavi, add it in the Games tab".to_string()) {
+            return;
+        }
         self.show_notification("Game Save Monitor", &format!("{}\n⚠ Not found in Ludusavi\nAdd in Games tab", game_name));
     }
 
     pub fn show_error(&self, title: &str, message: &str) {
         let body = format!("⚠ {}", message);
-        self.show_notification("Game Save Monitor", &format!("{}\n{}", title, body));
+        self.show_failure("Game Save Monitor", &format!("{}\n{}", title, body));
     }
 
     pub fn show_achievement_unlock(&self, game_name: &str, achievement_name: &str, description: &str, icon_url: Option<&str>, global_unlock_percentage: Option<f32>) {
+        // Unlocks are in the database, the session recap counts them
+        if self.is_quiet(game_name) {
+            app_log!("[NotificationManager] Do not disturb, unlock of {} recorded silently", achievement_name);
+            return;
+        }
+
         // Get current duration from state
-        let duration_seconds = *self.achievement_duration.lock().unwrap();
+        let mut duration_seconds = *self.achievement_duration.lock_or_recover();
+        if OverlayManager::is_big_picture_active() {
+            duration_seconds = duration_seconds.max(BIG_PICTURE_MIN_DURATION_SECS);
+        }
 
         // Try to use overlay if available
         if let Some(overlay_manager) = &self.overlay_manager {
             if let Ok(overlay) = overlay_manager.lock() {
+                // A pre-downloaded icon shows instantly and without a connection
+                let icon_url = icon_url.map(|url| icon_cache::cached_name(url).map(|name| icon_cache::icon_url(&name)).unwrap_or_else(|| url.to_string()));
                 let notification_data = serde_json::json!({
                     "game_name": game_name,
                     "achievement_name": achievement_name,
@@ -128,4 +254,78 @@ impl NotificationManager {
         let body = format!("🏆 {}\n{}", achievement_name, description);
         self.show_notification(game_name, &body);
     }
-}
\ No newline at end of file
+
+    /// One card summing up a play session instead of repeating every unlock
+    pub fn show_session_recap(&self, recap: &SessionRecap) {
+        self.show_recap(&recap.game_name, Some(recap), &[]);
+    }
+
+    /// Recap of a do-not-disturb session, with the notifications held back while it ran
+    pub fn show_quiet_recap(&self, game_name: &str, recap: Option<&SessionRecap>, held: &[String]) {
+        self.show_recap(game_name, recap, held);
+    }
+
+    fn show_recap(&self, game_name: &str, recap: Option<&SessionRecap>, held: &[String]) {
+        let title = format!("{} - Session Recap", game_name);
+        let body = recap.map(SessionRecap::summary)
+            .into_iter()
+            .chain(held.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if let Some(overlay_manager) = &self.overlay_manager {
+            if let Ok(overlay) = overlay_manager.lock() {
+                let notification_data = serde_json::json!({
+                    "title": title,
+                    "body": body,
+                    "game_name": game_name,
+                    "icon_url": recap.and_then(|r| r.rarest.as_ref()).and_then(|a| a.icon_url.clone()),
+                });
+                if overlay.show_overlay("session-recap", notification_data).is_ok() {
+                    return;
+                }
+            }
+        }
+
+        self.show_notification(&title, &body);
+    }
+
+    /// Completion milestone card; a perfect game gets the platinum card and its own sound
+    pub fn show_milestone(&mut self, event: &MilestoneEvent, sound_path: Option<&str>) {
+        let label = if event.perfect {
+            "Perfect Game!".to_string()
+        } else {
+            format!("{}% Complete", event.milestone)
+        };
+        if self.hold(&event.game_name, format!("🏆 {}", label)) {
+            return;
+        }
+        let title = format!("{} - {}", event.game_name, label);
+        let body = format!("🏆 {} / {} achievements unlocked", event.unlocked, event.total);
+
+        // The overlay plays custom sounds itself
+        if sound_path.is_none() {
+            Self::play_system_sound(if event.perfect { "SystemExclamation" } else { "SystemAsterisk" });
+        }
+
+        if let Some(overlay_manager) = &self.overlay_manager {
+            if let Ok(overlay) = overlay_manager.lock() {
+                let notification_data = serde_json::json!({
+                    "title": title,
+                    "body": body,
+                    "game_name": event.game_name,
+                    "milestone": event.milestone,
+                    "unlocked": event.unlocked,
+                    "total": event.total,
+                    "perfect": event.perfect,
+                    "sound_path": sound_path,
+                });
+                if overlay.show_overlay("milestone", notification_data).is_ok() {
+                    return;
+                }
+            }
+        }
+
+        self.show_toast(&title, &body, Timeout::Milliseconds(4000));
+    }
+}
@@ -1,13 +1,31 @@
-use tauri::{Manager, Window};
+use tauri::{AppHandle, Emitter, Listener, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
 use windows::Win32::Foundation::{RECT, HWND};
 use windows::Win32::UI::WindowsAndMessaging::{
-    FindWindowW, GetWindowLongPtrW, GetWindowRect, SetWindowLongPtrW, GetWindowLongW,
+    FindWindowW, GetWindowLongPtrW, GetWindowRect, SetWindowLongPtrW, GetWindowLongW, IsWindowVisible,
     SetWindowLongW, GWL_STYLE, GWL_EXSTYLE, WS_POPUP, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
     WS_EX_TRANSPARENT, WS_EX_LAYERED, WINDOW_EX_STYLE, HWND_TOPMOST, SetWindowPos,
     SWP_NOMOVE, SWP_NOSIZE, SWP_NOACTIVATE,
 };
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use steam_save_core::locks::LockExt;
+use steam_save_core::app_log;
+
+pub const OVERLAY_LABEL: &str = "overlay";
+// Emitted by the overlay page once it listens for notifications
+const OVERLAY_READY_EVENT: &str = "overlay-ready";
+// Stop waiting for a recreated overlay that never reports ready
+const OVERLAY_LOAD_TIMEOUT: Duration = Duration::from_secs(15);
+// A hidden overlay unused this long is destroyed to give back the WebView2 memory it holds
+const OVERLAY_IDLE_RELEASE: Duration = Duration::from_secs(5 * 60);
+// Title of Steam's Big Picture / gamepad UI window
+const BIG_PICTURE_TITLE: &str = "Steam Big Picture Mode";
+// Cards are read from the couch in Big Picture, so they're drawn larger
+const BIG_PICTURE_SCALE: f64 = 1.5;
+
+type PendingNotifications = Arc<Mutex<Option<(Instant, Vec<(String, serde_json::Value)>)>>>;
 
 /// Represents the display mode of a window
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -17,28 +35,103 @@ pub enum WindowMode {
     Windowed,
 }
 
-/// Manages the overlay notification window
+/// Manages the overlay notification window. The window is looked up before every use and
+/// created again if it was closed or its webview died.
 pub struct OverlayManager {
-    overlay_window: Option<Window>,
+    app_handle: Option<AppHandle>,
+    // Some while a recreated overlay is still loading; notifications wait here until it's ready
+    pending: PendingNotifications,
+    last_shown: Arc<Mutex<Instant>>,
 }
 
 impl OverlayManager {
     pub fn new() -> Self {
         Self {
-            overlay_window: None,
+            app_handle: None,
+            pending: Arc::new(Mutex::new(None)),
+            last_shown: Arc::new(Mutex::new(Instant::now())),
         }
     }
 
     /// Initialize the overlay window
     pub fn init(&mut self, app_handle: &tauri::AppHandle) -> Result<(), String> {
-        // Get or create overlay window
-        match app_handle.get_window("overlay") {
-            Some(window) => {
-                self.overlay_window = Some(window);
-                Ok(())
+        self.app_handle = Some(app_handle.clone());
+        self.ensure_window()?;
+        self.start_idle_release(app_handle.clone());
+        Ok(())
+    }
+
+    // Destroy the overlay while it's hidden and idle; the next notification creates it again
+    fn start_idle_release(&self, app: AppHandle) {
+        let last_shown = self.last_shown.clone();
+        let pending = self.pending.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+
+                // Holding this keeps show_overlay from using the window while it's destroyed
+                let last_shown_at = last_shown.lock_or_recover();
+                if last_shown_at.elapsed() < OVERLAY_IDLE_RELEASE || pending.lock_or_recover().is_some() {
+                    continue;
+                }
+                let Some(window) = app.get_webview_window(OVERLAY_LABEL) else { continue };
+                if window.is_visible().unwrap_or(false) {
+                    continue;
+                }
+                app_log!("[Overlay] Releasing idle overlay window");
+                let _ = window.destroy();
+            }
+        });
+    }
+
+    /// The overlay window, recreated if it's missing or no longer responds
+    fn ensure_window(&self) -> Result<WebviewWindow, String> {
+        let app = self.app_handle.as_ref().ok_or("Overlay window not initialized")?;
+
+        if let Some(window) = app.get_webview_window(OVERLAY_LABEL) {
+            if window.is_visible().is_ok() {
+                return Ok(window);
             }
-            None => Err("Overlay window not found".to_string()),
+            // Still registered but the native window is gone
+            app_log!("[Overlay] Overlay window stopped responding, recreating it");
+            let _ = window.destroy();
+        } else {
+            app_log!("[Overlay] Creating overlay window");
         }
+
+        *self.pending.lock_or_recover() = Some((Instant::now(), Vec::new()));
+        let window = Self::create_window(app)?;
+
+        let pending = self.pending.clone();
+        let ready_window = window.clone();
+        app.once_any(OVERLAY_READY_EVENT, move |_| {
+            let queued = pending.lock_or_recover().take().map(|(_, queued)| queued).unwrap_or_default();
+            for (notification_type, data) in queued {
+                if let Err(e) = Self::present(&ready_window, &notification_type, data) {
+                    app_log!("[Overlay] {}", e);
+                }
+            }
+        });
+
+        Ok(window)
+    }
+
+    // Same settings as the overlay window in tauri.conf.json
+    fn create_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+        WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::App("overlay.html".into()))
+            .title("Overlay")
+            .inner_size(1920.0, 1080.0)
+            .position(0.0, 0.0)
+            .resizable(false)
+            .decorations(false)
+            .transparent(true)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .visible(false)
+            .focused(false)
+            .build()
+            .map_err(|e| format!("Failed to create overlay window: {}", e))
     }
 
     /// Detect the window mode of a given window by its title
@@ -97,6 +190,18 @@ impl OverlayManager {
         }
     }
 
+    /// Whether Steam is showing its Big Picture / gamepad UI, i.e. a controller-first session
+    pub fn is_big_picture_active() -> bool {
+        let wide_title: Vec<u16> = OsStr::new(BIG_PICTURE_TITLE)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        unsafe {
+            let hwnd = FindWindowW(None, windows::core::PCWSTR(wide_title.as_ptr()));
+            hwnd.0 != 0 && IsWindowVisible(hwnd).as_bool()
+        }
+    }
+
     /// Set window extended style to prevent activation/focus stealing and make click-through
     fn set_no_activate(hwnd: HWND) -> Result<(), String> {
         unsafe {
@@ -128,36 +233,63 @@ impl OverlayManager {
     }
 
     /// Show the overlay window with notification data
-    pub fn show_overlay(&self, notification_type: &str, data: serde_json::Value) -> Result<(), String> {
-        if let Some(window) = &self.overlay_window {
-            // Get HWND and set no-activate style
-            if let Ok(hwnd) = window.hwnd() {
-                let hwnd = HWND(hwnd.0 as isize);
-                Self::set_no_activate(hwnd)?;
+    pub fn show_overlay(&self, notification_type: &str, mut data: serde_json::Value) -> Result<(), String> {
+        if Self::is_big_picture_active() {
+            if let Some(fields) = data.as_object_mut() {
+                fields.insert("big_picture".to_string(), serde_json::json!(true));
+                fields.insert("scale".to_string(), serde_json::json!(BIG_PICTURE_SCALE));
             }
+        }
 
-            // Show the overlay window without activating it
-            window.show().map_err(|e| format!("Failed to show overlay: {}", e))?;
 
-            // Emit event to overlay window with notification data
-            window
-                .emit("show-notification", (notification_type, data))
-                .map_err(|e| format!("Failed to emit notification event: {}", e))?;
+        // Held until the notification is handed over so the idle release can't destroy the window midway
+        let mut last_shown = self.last_shown.lock_or_recover();
+        *last_shown = Instant::now();
 
-            Ok(())
-        } else {
-            Err("Overlay window not initialized".to_string())
+        let window = self.ensure_window()?;
+
+        // A recreated overlay can't receive events until its page has loaded
+        {
+            let mut pending = self.pending.lock_or_recover();
+            if let Some((since, queued)) = pending.as_mut() {
+                if since.elapsed() < OVERLAY_LOAD_TIMEOUT {
+                    queued.push((notification_type.to_string(), data));
+                    return Ok(());
+                }
+                app_log!("[Overlay] Overlay never reported ready, showing notifications directly");
+                *pending = None;
+            }
         }
+
+        Self::present(&window, notification_type, data)
+    }
+
+    fn present(window: &WebviewWindow, notification_type: &str, data: serde_json::Value) -> Result<(), String> {
+        // Get HWND and set no-activate style
+        if let Ok(hwnd) = window.hwnd() {
+            let hwnd = HWND(hwnd.0 as isize);
+            Self::set_no_activate(hwnd)?;
+        }
+
+        // Show the overlay window without activating it
+        window.show().map_err(|e| format!("Failed to show overlay: {}", e))?;
+
+        // Emit event to overlay window with notification data (emit() would reach every window)
+        window
+            .emit_to(window.label(), "show-notification", (notification_type, data))
+            .map_err(|e| format!("Failed to emit notification event: {}", e))?;
+
+        Ok(())
     }
 
     /// Hide the overlay window
     pub fn hide_overlay(&self) -> Result<(), String> {
-        if let Some(window) = &self.overlay_window {
+        let app = self.app_handle.as_ref().ok_or("Overlay window not initialized")?;
+        // Nothing to hide if the window is gone; it's recreated on the next notification
+        if let Some(window) = app.get_webview_window(OVERLAY_LABEL) {
             window.hide().map_err(|e| format!("Failed to hide overlay: {}", e))?;
-            Ok(())
-        } else {
-            Err("Overlay window not initialized".to_string())
         }
+        Ok(())
     }
 
     /// Check if we should use overlay or fallback to native notifications
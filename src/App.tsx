@@ -1,11 +1,12 @@
 import React, { useState, useEffect, useRef } from 'react';
-import { Settings, Save, FolderOpen, CheckCircle, AlertCircle, Info, GamepadIcon, Search, Trash2, X, Trophy, Download, RefreshCw, Plus, Ban } from 'lucide-react';
-import { invoke } from '@tauri-apps/api/tauri';
-import { listen, emit } from '@tauri-apps/api/event';
-import { ask } from '@tauri-apps/api/dialog';
+import { Settings, Save, FolderOpen, CheckCircle, AlertCircle, Info, GamepadIcon, Search, Trash2, X, Trophy, Download, RefreshCw, Plus, Ban, HardDrive } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { emit } from '@tauri-apps/api/event';
+import { ask } from '@tauri-apps/plugin-dialog';
 import { AchievementToastContainer } from './components/AchievementToast';
 import { RarityCustomizer } from './components/RarityCustomizer';
 import { RaritySettings, defaultRaritySettings, RarityTier } from './types/rarityTypes';
+import { listenAppEvents, LaunchRequest, ProgressEvent } from './types/appEvents';
 
 type Tab = 'settings' | 'games' | 'achievements' | 'exclusions' | 'customization';
 
@@ -18,6 +19,49 @@ interface Config {
   steamApiKey?: string;
   steamUserId?: string;
   steamId64?: string;
+  startupDelaySecs?: number;
+  waitForSteam?: boolean;
+  lowDiskSpaceMb?: number;
+  explorerContextMenu?: boolean;
+  excludedSteamCategories?: string[];
+  controlPipeEnabled?: boolean;
+  unlockWebhook?: WebhookSettings;
+  ludusaviTimeoutSecs?: number;
+  ludusaviBackupCloudSync?: 'ludusavi_default' | 'always' | 'never';
+  ludusaviBackupFormat?: 'ludusavi_default' | 'simple' | 'zip';
+  ludusaviCompression?: 'ludusavi_default' | 'none' | 'deflate' | 'bzip2' | 'zstd';
+  ludusaviCompressionLevel?: number | null;
+  backupFullVersions?: number;
+  backupDifferentialVersions?: number;
+  quietGames?: number[];
+  snapshotOnGameStart?: boolean;
+  sessionBackupMinutes?: Record<string, number>;
+  staleBackupDays?: number;
+  staleBackupMinPlays?: number;
+  gameBackupPaths?: Record<string, string>;
+  verifyBackups?: boolean;
+  backupOnUninstall?: boolean;
+  backupNameTemplate?: string;
+  libraryBackupSchedule?: LibraryBackupSchedule | null;
+}
+
+// Back up every installed game at a local time, weekday 0 = Monday, null = every day
+interface LibraryBackupSchedule {
+  time: string;
+  weekday?: number | null;
+}
+
+interface ScheduledBackupInfo {
+  next_run: number | null;
+  waiting_for_game: boolean;
+}
+
+// Achievement unlocks POSTed to a URL; template is JSON with {{field}} placeholders
+interface WebhookSettings {
+  enabled: boolean;
+  url: string;
+  template: string;
+  secret: string;
 }
 
 interface Achievement {
@@ -35,6 +79,7 @@ interface Achievement {
   source: string;
   last_updated: number;
   global_unlock_percentage?: number;
+  sources?: string[]; // every source reporting it, in the merged view
 }
 
 interface GameAchievementSummary {
@@ -44,6 +89,41 @@ interface GameAchievementSummary {
   unlocked_achievements: number;
   source: string;
   last_updated: number;
+  metadata?: GameMetadata | null;
+  status: GameStatus;
+  status_manual: boolean;
+  favorite: boolean;
+}
+
+// A Steam screenshot taken around an achievement's unlock
+interface AchievementScreenshot {
+  id: number;
+  achievement_id: string;
+  taken_at: number;
+  url: string;
+  full_url: string;
+}
+
+type AchievementSort = 'default' | 'rarity' | 'unlock_date' | 'locked_first';
+type AchievementFilter = 'all' | 'locked' | 'unlocked';
+
+// Kept per game by the backend so every window and restart shows the list the same way
+interface AchievementViewPrefs {
+  sort_by: AchievementSort;
+  filter: AchievementFilter;
+}
+
+const DEFAULT_VIEW_PREFS: AchievementViewPrefs = { sort_by: 'default', filter: 'all' };
+
+type GameStatus = 'backlog' | 'playing' | 'finished' | 'completed' | 'abandoned';
+
+interface GameMetadata {
+  app_id: number;
+  notes: string;
+  custom_cover_path?: string | null;
+  sort_title?: string | null;
+  platform_tags: string[];
+  updated_at: number;
 }
 
 interface SteamGameSearchResult {
@@ -67,6 +147,207 @@ interface Exclusion {
   app_id: number;
   name: string;
   added_at: number;
+  category?: string | null; // Steam category it was imported from
+}
+
+interface DetectedExecutable {
+  exe_name: string;
+  app_id: number;
+  game_name: string;
+}
+
+interface IgnoredExecutable extends DetectedExecutable {
+  added_at: number;
+}
+
+// get_achievement_source_health: can a tracked game's achievements still be watched
+interface SourceHealth {
+  app_id: number;
+  game_name: string;
+  recorded_source: string;
+  recorded_file: string | null;
+  available_sources: string[];
+  watcher_source: string | null;
+  can_attach: boolean;
+  watching: boolean;
+  problem: string | null;
+}
+
+// A timestamped backup Ludusavi keeps for a game (list_backup_versions)
+interface BackupVersion {
+  id: string;
+  created_at: number;
+  file_count: number;
+  total_bytes: number;
+}
+
+// Files a backup of the game would save, from a Ludusavi dry run
+interface BackupPreview {
+  game_name: string;
+  found: boolean;
+  files: { path: string; bytes: number; change?: string }[];
+  total_bytes: number;
+  total_size: string;
+}
+
+// An installed Steam app, described by Steam's appinfo.vdf
+interface InstalledSteamApp {
+  app_id: number;
+  name: string;
+  app_type: string | null;
+  install_dir: string;
+  executables: string[];
+  is_game: boolean;
+}
+
+// Roots and custom games from Ludusavi's own config.yaml
+interface LudusaviRoot {
+  path: string;
+  store: string;
+}
+
+interface LudusaviCustomGame {
+  name: string;
+  integration: 'override' | 'extend';
+  files: string[];
+  registry: string[];
+}
+
+interface LudusaviCustomization {
+  config_path: string;
+  roots: LudusaviRoot[];
+  custom_games: LudusaviCustomGame[];
+}
+
+const EMPTY_CUSTOM_GAME_FORM = { name: '', integration: 'override' as const, files: '', registry: '' };
+
+// Outcome of simulate_game_session, one entry per pipeline stage
+interface SimulationReport {
+  game_name: string;
+  steps: { name: string; success: boolean; detail: string }[];
+}
+
+interface BackupKeyStatus {
+  configured: boolean;
+  from_passphrase: boolean;
+  created_at: number | null;
+  key_available: boolean;
+  error: string | null;
+}
+
+interface SteamCategory {
+  name: string;
+  app_ids: number[];
+}
+
+interface SourceSet {
+  source: string;
+  total: number;
+  unlocked: number;
+  last_updated: number;
+  missing_unlocks: string[];
+}
+
+interface DuplicateGame {
+  app_id: number;
+  game_name: string;
+  sources: SourceSet[];
+  suggested_source: string;
+}
+
+interface AchievementBackup {
+  app_id: number;
+  game_name: string;
+  source: string;
+  unlocked: number;
+  modified: number;
+  path: string;
+}
+
+interface DlcGroup {
+  dlc_app_id: number | null; // null for the base game
+  name: string;
+  total: number;
+  unlocked: number;
+  achievement_ids: string[];
+}
+
+interface SuggestedAchievement extends Achievement {
+  tags: string[];
+  reason: string;
+}
+
+// Tags with a meaning for the suggested completion order
+const ACHIEVEMENT_TAGS = ['missable', 'grindy'];
+
+// A backup, restore, upload or verification result from the backup_history table
+interface BackupHistoryEntry {
+  id: number;
+  game_name: string;
+  app_id: number | null;
+  kind: string;
+  timestamp: number;
+  success: boolean;
+  files_count: number | null;
+  total_size: string | null;
+  location: string | null;
+  error: string | null;
+}
+
+// Backup totals of one game; stale when it keeps being played without a successful backup
+interface BackupFreshness {
+  game_name: string;
+  app_id: number | null;
+  successful_backups: number;
+  failed_backups: number;
+  last_attempt: number;
+  last_success: number | null;
+  recent_attempts: number;
+  stale: boolean;
+}
+
+interface BackupStats {
+  backup_path: string;
+  backup_size_bytes: number;
+  disk: { total_bytes: number; free_bytes: number; used_bytes: number } | null;
+  low_space_threshold_mb: number;
+  low_space: boolean;
+}
+
+// Per-game usage of the backup folders, largest first
+interface StorageDashboard {
+  games: {
+    game_name: string;
+    backup_path: string;
+    size_bytes: number;
+    versions: number;
+    last_backup_at: number | null;
+    last_backup_age_secs: number | null;
+  }[];
+  dedup_bytes: number;
+  total_bytes: number;
+  disk: { total_bytes: number; free_bytes: number; used_bytes: number } | null;
+}
+
+const formatBytes = (bytes: number) => {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit++;
+  }
+  return unit === 0 ? `${bytes} B` : `${size.toFixed(1)} ${units[unit]}`;
+};
+
+type ExportFormat = 'steam_unlock_time' | 'goldberg' | 'csv' | 'native';
+
+interface ExportResult {
+  path: string;
+  format: ExportFormat;
+  achievements_written: number;
+  unlocked_written: number;
+  bytes_written: number;
 }
 
 function App() {
@@ -93,9 +374,32 @@ function App() {
   // Achievement state
   const [achievementGames, setAchievementGames] = useState<GameAchievementSummary[]>([]);
   const [selectedGame, setSelectedGame] = useState<GameAchievementSummary | null>(null);
+  const [mergeSources, setMergeSources] = useState(() => localStorage.getItem('mergeSources') === 'true');
   const [gameAchievements, setGameAchievements] = useState<Achievement[]>([]);
+  const [dlcGroups, setDlcGroups] = useState<DlcGroup[]>([]);
+  const [dlcFilter, setDlcFilter] = useState<number | 'base' | 'all'>('all');
+  const [viewPrefs, setViewPrefs] = useState<AchievementViewPrefs>(DEFAULT_VIEW_PREFS);
+  const [screenshots, setScreenshots] = useState<AchievementScreenshot[]>([]);
+  const [scanningScreenshots, setScanningScreenshots] = useState(false);
+  const [simulationReport, setSimulationReport] = useState<SimulationReport | null>(null);
+  const [simulating, setSimulating] = useState(false);
+  const [restoringGame, setRestoringGame] = useState<string | null>(null);
+  const [sessionBackupGame, setSessionBackupGame] = useState('');
+  const [sessionBackupInterval, setSessionBackupInterval] = useState(30);
+  const [sourceHealth, setSourceHealth] = useState<SourceHealth[] | null>(null);
+  const [checkingSources, setCheckingSources] = useState(false);
+  const [backupVersions, setBackupVersions] = useState<{ gameName: string; versions: BackupVersion[] } | null>(null);
+  const [backupPreview, setBackupPreview] = useState<BackupPreview | null>(null);
+  const [runningBackups, setRunningBackups] = useState<string[]>([]);
+  const [queuedBackups, setQueuedBackups] = useState<string[]>([]);
+  const [operations, setOperations] = useState<ProgressEvent[]>([]);
+  const [backingUpLibrary, setBackingUpLibrary] = useState(false);
+  const [detectingDlc, setDetectingDlc] = useState(false);
+  const [achievementTags, setAchievementTags] = useState<{ [achievementId: string]: string[] }>({});
+  const [completionOrder, setCompletionOrder] = useState<SuggestedAchievement[] | null>(null);
   const [loadingAchievements, setLoadingAchievements] = useState(false);
   const [syncingAchievements, setSyncingAchievements] = useState(false);
+  const [downloadingIcons, setDownloadingIcons] = useState(false);
   const [showManualAddForm, setShowManualAddForm] = useState(false);
 
   // Steam game search state
@@ -106,6 +410,17 @@ function App() {
 
   // Icon cache state - stores base64 data URLs
   const [iconCache, setIconCache] = useState<{ [url: string]: string }>({});
+  const [exportFormat, setExportFormat] = useState<ExportFormat>('steam_unlock_time');
+  const [timelineMonth, setTimelineMonth] = useState('');
+  const [timelineFormat, setTimelineFormat] = useState<'json' | 'csv'>('csv');
+  const [duplicateGames, setDuplicateGames] = useState<DuplicateGame[]>([]);
+  const [achievementBackups, setAchievementBackups] = useState<AchievementBackup[]>([]);
+  const [renamingBackup, setRenamingBackup] = useState<{ appId: number; name: string } | null>(null);
+  const [backupStats, setBackupStats] = useState<BackupStats | null>(null);
+  const [storageDashboard, setStorageDashboard] = useState<StorageDashboard | null>(null);
+  const [nextLibraryBackup, setNextLibraryBackup] = useState<ScheduledBackupInfo | null>(null);
+  const [recentBackups, setRecentBackups] = useState<BackupHistoryEntry[]>([]);
+  const [backupFreshness, setBackupFreshness] = useState<Record<string, BackupFreshness>>({});
 
   // Edit achievement modal state
   const [editingAchievement, setEditingAchievement] = useState<Achievement | null>(null);
@@ -144,88 +459,564 @@ function App() {
   const [exclusionSearchResults, setExclusionSearchResults] = useState<SteamGameSearchResult[]>([]);
   const [searchingExclusions, setSearchingExclusions] = useState(false);
   const exclusionSearchTimerRef = useRef<NodeJS.Timeout | null>(null);
+  const [steamCategories, setSteamCategories] = useState<SteamCategory[]>([]);
+  const [detectedExecutables, setDetectedExecutables] = useState<DetectedExecutable[] | null>(null);
+  const [ignoredExecutables, setIgnoredExecutables] = useState<IgnoredExecutable[]>([]);
+  const [executableFilter, setExecutableFilter] = useState('');
+  const [scanningExecutables, setScanningExecutables] = useState(false);
+  const [steamLibrary, setSteamLibrary] = useState<InstalledSteamApp[] | null>(null);
+  const [loadingSteamLibrary, setLoadingSteamLibrary] = useState(false);
+  const [selectedLibraryApps, setSelectedLibraryApps] = useState<number[]>([]);
+  const [ludusaviCustomization, setLudusaviCustomization] = useState<LudusaviCustomization | null>(null);
+  const [customGameForm, setCustomGameForm] = useState<{ name: string; integration: 'override' | 'extend'; files: string; registry: string }>(EMPTY_CUSTOM_GAME_FORM);
+  const [missingLudusaviGame, setMissingLudusaviGame] = useState<string | null>(null);
+  const [newRoot, setNewRoot] = useState<LudusaviRoot>({ path: '', store: 'steam' });
+  const [selectedExclusions, setSelectedExclusions] = useState<number[]>([]);
+  const [backupKeyStatus, setBackupKeyStatus] = useState<BackupKeyStatus | null>(null);
+  const [backupPassphrase, setBackupPassphrase] = useState('');
+  const [recoveryPhrase, setRecoveryPhrase] = useState<string | null>(null);
+  const [recoveryInput, setRecoveryInput] = useState('');
+
+  const groupGamesByLetter = (games: string[]) => {
+    const groups: { [key: string]: string[] } = {};
+    
+    if (!games || games.length === 0) return groups;
+    
+    games.forEach(game => {
+      if (!game || game.length === 0) return;
+      
+      const firstChar = game.charAt(0).toUpperCase();
+      const key = /[0-9]/.test(firstChar) ? '0-9' : firstChar;
+      
+      if (!groups[key]) {
+        groups[key] = [];
+      }
+      groups[key].push(game);
+    });
+    
+    // Sort each group
+    Object.keys(groups).forEach(key => {
+      groups[key].sort();
+    });
+    
+    return groups;
+  };
+
+  const toggleSection = (section: string) => {
+    const newExpanded = new Set(expandedSections);
+    if (newExpanded.has(section)) {
+      newExpanded.delete(section);
+    } else {
+      newExpanded.add(section);
+    }
+    setExpandedSections(newExpanded);
+  };
+
+  // Exclusions functions
+  const loadExclusions = async () => {
+    setLoadingExclusions(true);
+    try {
+      const result = await invoke<Exclusion[]>('get_all_exclusions');
+      setExclusions(result);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to load exclusions: ${error}`
+      });
+    } finally {
+      setLoadingExclusions(false);
+    }
+  };
+
+  const handleAddExclusion = async (appId: number, name: string) => {
+    try {
+      await invoke('add_exclusion', { appId, name });
+      setMessage({
+        type: 'success',
+        text: `Added ${name} to exclusions`
+      });
+      loadExclusions();
+      setExclusionSearchQuery('');
+      setExclusionSearchResults([]);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to add exclusion: ${error}`
+      });
+    }
+  };
+
+  const loadSteamCategories = async () => {
+    try {
+      setSteamCategories(await invoke<SteamCategory[]>('get_steam_categories'));
+    } catch (error) {
+      console.error('Failed to load Steam categories:', error);
+      setSteamCategories([]);
+    }
+  };
+
+  const handleToggleQuietGame = async (appId: number) => {
+    const current = config.quietGames || [];
+    const updatedConfig = {
+      ...config,
+      quietGames: current.includes(appId)
+        ? current.filter(id => id !== appId)
+        : [...current, appId]
+    };
+    setConfig(updatedConfig);
+
+    try {
+      await invoke('save_config', { config: updatedConfig });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to save do not disturb setting: ${error}`
+      });
+    }
+  };
+
+  const handleToggleExcludedCategory = async (category: string) => {
+    const current = config.excludedSteamCategories || [];
+    const updatedConfig = {
+      ...config,
+      excludedSteamCategories: current.includes(category)
+        ? current.filter(c => c !== category)
+        : [...current, category]
+    };
+    setConfig(updatedConfig);
+
+    try {
+      await invoke('save_config', { config: updatedConfig });
+      const result = await invoke<{ added: number; removed: number }>('sync_steam_category_exclusions');
+      setMessage({
+        type: 'success',
+        text: `Synced Steam categories: ${result.added} added, ${result.removed} removed`
+      });
+      setTimeout(() => setMessage(null), 3000);
+      loadExclusions();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to sync Steam categories: ${error}`
+      });
+    }
+  };
+
+  const loadBackupKeyStatus = async () => {
+    try {
+      setBackupKeyStatus(await invoke<BackupKeyStatus>('get_backup_key_status'));
+    } catch (error) {
+      console.error('Failed to load backup key status:', error);
+    }
+  };
+
+  // Generate a key, or derive it from the passphrase when one is entered, then show its recovery phrase
+  const handleCreateBackupKey = async (fromPassphrase: boolean) => {
+    try {
+      const phrase = fromPassphrase
+        ? await invoke<string>('set_backup_key_passphrase', { passphrase: backupPassphrase, replace: false })
+        : await invoke<string>('generate_backup_key', { replace: false });
+      setBackupPassphrase('');
+      setRecoveryPhrase(phrase);
+      loadBackupKeyStatus();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to set up backup key: ${error}`
+      });
+    }
+  };
+
+  const handleShowRecoveryPhrase = async () => {
+    try {
+      setRecoveryPhrase(await invoke<string>('export_backup_recovery_phrase'));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to read backup key: ${error}`
+      });
+    }
+  };
+
+  const handleVerifyBackupKey = async () => {
+    try {
+      await invoke('verify_backup_key', { passphrase: backupPassphrase || null });
+      setBackupPassphrase('');
+      setMessage({
+        type: 'success',
+        text: 'Backup key verified, restores will work'
+      });
+      setTimeout(() => setMessage(null), 3000);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Backup key check failed: ${error}`
+      });
+    }
+  };
+
+  const handleRecoverBackupKey = async () => {
+    try {
+      await invoke('recover_backup_key', { phrase: recoveryInput });
+      setRecoveryInput('');
+      setMessage({
+        type: 'success',
+        text: 'Backup key recovered'
+      });
+      setTimeout(() => setMessage(null), 3000);
+      loadBackupKeyStatus();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to recover backup key: ${error}`
+      });
+    }
+  };
+
+  const loadIgnoredExecutables = async () => {
+    try {
+      setIgnoredExecutables(await invoke<IgnoredExecutable[]>('get_ignored_executables'));
+    } catch (error) {
+      console.error('Failed to load ignored executables:', error);
+    }
+  };
+
+  const handleScanExecutables = async () => {
+    setScanningExecutables(true);
+    try {
+      setDetectedExecutables(await invoke<DetectedExecutable[]>('get_steam_executables'));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to list Steam executables: ${error}`
+      });
+    } finally {
+      setScanningExecutables(false);
+    }
+  };
+
+  const loadLudusaviCustomization = async () => {
+    try {
+      setLudusaviCustomization(await invoke<LudusaviCustomization>('get_ludusavi_customization'));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to read Ludusavi settings: ${error}`
+      });
+    }
+  };
+
+  const handleSaveCustomGame = async () => {
+    const lines = (text: string) => text.split('\n').map(line => line.trim()).filter(line => line.length > 0);
+    const game: LudusaviCustomGame = {
+      name: customGameForm.name.trim(),
+      integration: customGameForm.integration,
+      files: lines(customGameForm.files),
+      registry: lines(customGameForm.registry),
+    };
+    // A game whose backup failed with "not found" is backed up again as soon as it's defined
+    const backupNow = game.name === missingLudusaviGame;
+    try {
+      await invoke('save_ludusavi_custom_game', { game, backupNow });
+      setMessage({
+        type: 'success',
+        text: backupNow ? `Saved ${game.name} to Ludusavi's custom games and backed it up` : `Saved ${game.name} to Ludusavi's custom games`
+      });
+      if (backupNow) setMissingLudusaviGame(null);
+      setCustomGameForm(EMPTY_CUSTOM_GAME_FORM);
+      loadLudusaviCustomization();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to save custom game: ${error}`
+      });
+    }
+  };
+
+  const handleRemoveCustomGame = async (name: string) => {
+    try {
+      await invoke('remove_ludusavi_custom_game', { name });
+      loadLudusaviCustomization();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to remove custom game: ${error}`
+      });
+    }
+  };
+
+  const handleSaveLudusaviRoots = async (roots: LudusaviRoot[]) => {
+    try {
+      await invoke('set_ludusavi_roots', { roots });
+      loadLudusaviCustomization();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to save Ludusavi roots: ${error}`
+      });
+    }
+  };
+
+  const handleLoadSteamLibrary = async () => {
+    setLoadingSteamLibrary(true);
+    try {
+      setSteamLibrary(await invoke<InstalledSteamApp[]>('get_steam_library'));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to read the Steam library: ${error}`
+      });
+    } finally {
+      setLoadingSteamLibrary(false);
+    }
+  };
+
+  const handleIgnoreExecutable = async (exe: DetectedExecutable) => {
+    try {
+      await invoke('ignore_executable', { exeName: exe.exe_name, appId: exe.app_id, gameName: exe.game_name });
+      loadIgnoredExecutables();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to ignore ${exe.exe_name}: ${error}`
+      });
+    }
+  };
+
+  const handleUnignoreExecutable = async (exeName: string) => {
+    try {
+      await invoke('unignore_executable', { exeName });
+      loadIgnoredExecutables();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to stop ignoring ${exeName}: ${error}`
+      });
+    }
+  };
+
+  const handleExcludeSelectedApps = async () => {
+    try {
+      const added = await invoke<number>('add_exclusions', { appIds: selectedLibraryApps });
+      setMessage({
+        type: 'success',
+        text: `Excluded ${added} new app${added === 1 ? '' : 's'}`
+      });
+      setSelectedLibraryApps([]);
+      loadExclusions();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to add exclusions: ${error}`
+      });
+    }
+  };
+
+  const handleExcludeNonGameApps = async () => {
+    try {
+      const added = await invoke<number>('exclude_non_game_apps');
+      setMessage({
+        type: 'success',
+        text: added > 0 ? `Excluded ${added} non-game app${added === 1 ? '' : 's'}` : 'All non-game apps were already excluded'
+      });
+      loadExclusions();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to exclude non-game apps: ${error}`
+      });
+    }
+  };
+
+  const handleRemoveSelectedExclusions = async () => {
+    try {
+      const removed = await invoke<number>('remove_exclusions', { appIds: selectedExclusions });
+      setMessage({
+        type: 'success',
+        text: `Removed ${removed} exclusion${removed === 1 ? '' : 's'}`
+      });
+      setSelectedExclusions([]);
+      loadExclusions();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to remove exclusions: ${error}`
+      });
+    }
+  };
+
+  const handleExportExclusions = async () => {
+    try {
+      const path = await invoke<string | null>('export_exclusions');
+      if (path) {
+        setMessage({ type: 'success', text: `Exclusions exported to ${path}` });
+      }
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to export exclusions: ${error}`
+      });
+    }
+  };
+
+  const handleImportExclusions = async () => {
+    try {
+      const added = await invoke<number | null>('import_exclusions');
+      if (added !== null) {
+        setMessage({ type: 'success', text: `Imported ${added} new exclusion${added === 1 ? '' : 's'}` });
+        loadExclusions();
+      }
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to import exclusions: ${error}`
+      });
+    }
+  };
+
+  const handleRemoveExclusion = async (appId: number, name: string) => {
+    try {
+      await invoke('remove_exclusion', { appId });
+      setMessage({
+        type: 'success',
+        text: `Removed ${name} from exclusions`
+      });
+      loadExclusions();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to remove exclusion: ${error}`
+      });
+    }
+  };
 
-  const groupGamesByLetter = (games: string[]) => {
-    const groups: { [key: string]: string[] } = {};
-    
-    if (!games || games.length === 0) return groups;
-    
-    games.forEach(game => {
-      if (!game || game.length === 0) return;
-      
-      const firstChar = game.charAt(0).toUpperCase();
-      const key = /[0-9]/.test(firstChar) ? '0-9' : firstChar;
-      
-      if (!groups[key]) {
-        groups[key] = [];
-      }
-      groups[key].push(game);
-    });
-    
-    // Sort each group
-    Object.keys(groups).forEach(key => {
-      groups[key].sort();
-    });
-    
-    return groups;
+  // Duplicate game functions
+  const loadDuplicateGames = async () => {
+    try {
+      const result = await invoke<DuplicateGame[]>('find_duplicate_games');
+      setDuplicateGames(result);
+    } catch (error) {
+      console.error('Failed to find duplicate games:', error);
+    }
   };
 
-  const toggleSection = (section: string) => {
-    const newExpanded = new Set(expandedSections);
-    if (newExpanded.has(section)) {
-      newExpanded.delete(section);
-    } else {
-      newExpanded.add(section);
+  // Without automatic final backups, offer one when a game's uninstall is noticed
+  const handleGameUninstalled = async (appId: number, name: string) => {
+    const confirmed = await ask(
+      `${name} was uninstalled.\n\nBack up its saves one last time and archive it?`,
+      { title: 'Steam Backup Manager', type: 'info' }
+    );
+    if (!confirmed) return;
+
+    try {
+      await invoke('archive_uninstalled_game', { appId });
+      setMessage({ type: 'success', text: `Archived the saves of ${name}` });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to archive ${name}: ${error}`
+      });
     }
-    setExpandedSections(newExpanded);
   };
 
-  // Exclusions functions
-  const loadExclusions = async () => {
-    setLoadingExclusions(true);
+  const handleConsolidateGame = async (game: DuplicateGame, keepSource: string) => {
+    const confirmed = await ask(
+      `Merge every source of ${game.game_name} into ${keepSource}?\n\n` +
+      `Unlocks from the other sources are kept and their rows are removed.`,
+      { title: 'Steam Backup Manager', type: 'info' }
+    );
+    if (!confirmed) return;
+
     try {
-      const result = await invoke<Exclusion[]>('get_all_exclusions');
-      setExclusions(result);
+      const removed = await invoke<number>('consolidate_game_sources', { appId: game.app_id, keepSource });
+      setMessage({
+        type: 'success',
+        text: `Consolidated ${game.game_name} into ${keepSource} (${removed} redundant entries removed)`
+      });
+      loadDuplicateGames();
+      loadAllAchievements();
     } catch (error) {
       setMessage({
         type: 'error',
-        text: `Failed to load exclusions: ${error}`
+        text: `Failed to consolidate ${game.game_name}: ${error}`
       });
-    } finally {
-      setLoadingExclusions(false);
     }
   };
 
-  const handleAddExclusion = async (appId: number, name: string) => {
+  const handleDeleteGameSource = async (game: DuplicateGame, set: SourceSet) => {
+    const confirmed = await ask(
+      `Delete the ${set.source} achievements of ${game.game_name}?` +
+      (set.unlocked > 0
+        ? `\n\nIts ${set.unlocked} unlocks are removed too. Use "Keep this" on another source to carry them over.`
+        : ''),
+      { title: 'Steam Backup Manager', type: 'warning' }
+    );
+    if (!confirmed) return;
+
     try {
-      await invoke('add_exclusion', { appId, name });
+      await invoke<number>('delete_game_source', { appId: game.app_id, source: set.source });
       setMessage({
         type: 'success',
-        text: `Added ${name} to exclusions`
+        text: `Deleted ${set.source} achievements of ${game.game_name}`
       });
-      loadExclusions();
-      setExclusionSearchQuery('');
-      setExclusionSearchResults([]);
+      loadDuplicateGames();
+      loadAllAchievements();
     } catch (error) {
       setMessage({
         type: 'error',
-        text: `Failed to add exclusion: ${error}`
+        text: `Failed to delete ${set.source} achievements: ${error}`
       });
     }
   };
 
-  const handleRemoveExclusion = async (appId: number, name: string) => {
+  // Achievement backup functions
+  const loadAchievementBackups = async () => {
     try {
-      await invoke('remove_exclusion', { appId });
+      const result = await invoke<AchievementBackup[]>('list_achievement_backups');
+      setAchievementBackups(result);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to load achievement backups: ${error}`
+      });
+    }
+  };
+
+  const handleDeleteAchievementBackup = async (backup: AchievementBackup) => {
+    const confirmed = await ask(
+      `Delete the achievement backup for ${backup.game_name}?\n\nThis can't be undone.`,
+      { title: 'Steam Backup Manager', type: 'warning' }
+    );
+    if (!confirmed) return;
+
+    try {
+      await invoke('delete_achievement_backup', { appId: backup.app_id });
       setMessage({
         type: 'success',
-        text: `Removed ${name} from exclusions`
+        text: `Deleted backup for ${backup.game_name}`
       });
-      loadExclusions();
+      loadAchievementBackups();
     } catch (error) {
       setMessage({
         type: 'error',
-        text: `Failed to remove exclusion: ${error}`
+        text: `Failed to delete backup: ${error}`
+      });
+    }
+  };
+
+  const handleRenameAchievementBackup = async () => {
+    if (!renamingBackup) return;
+    try {
+      await invoke('rename_achievement_backup', {
+        appId: renamingBackup.appId,
+        gameName: renamingBackup.name
+      });
+      setRenamingBackup(null);
+      loadAchievementBackups();
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to rename backup: ${error}`
       });
     }
   };
@@ -261,6 +1052,7 @@ function App() {
   useEffect(() => {
     loadConfig();
     loadAllAchievements(); // Load achievements on app start for the tab badge
+    openLaunchRequest(); // The app may have been started from a link
     loadExclusions(); // Load exclusions on app start for the tab badge
 
     // Load achievement duration from backend
@@ -275,26 +1067,75 @@ function App() {
         achievementDurationLoadedRef.current = true; // Mark as loaded even on error
       });
 
-    // Listen for game not found events
-    const unsubscribeNotFound = listen('game-not-found', (event: any) => {
-      setMessage({
-        type: 'error',
-        text: `Game "${event.payload.name}" not found in Ludusavi manifest. Please add it manually in the Games tab.`
-      });
-    });
-
-    // Listen for game detected events
-    const unsubscribeDetected = listen('game-detected', (event: any) => {
-      setMessage({
-        type: 'success',
-        text: `Game Save Monitor detected: ${event.payload}`
-      });
-      setTimeout(() => setMessage(null), 5000);
+    // Progress events aren't replayed, so pick up whatever is already running
+    invoke<ProgressEvent[]>('get_active_operations')
+      .then(setOperations)
+      .catch(error => console.error('Failed to load running operations:', error));
+
+    // Listen for game detected / not found events
+    const unsubscribeGameEvents = listenAppEvents('app-messages', (event) => {
+      if (event.type === 'progress') {
+        const progress = event.payload;
+        setOperations(current => progress.done
+          ? current.filter(op => op.operation_id !== progress.operation_id)
+          : [...current.filter(op => op.operation_id !== progress.operation_id), progress]);
+      } else if (event.type === 'game_not_found') {
+        const name = event.payload.name;
+        setMissingLudusaviGame(name);
+        setCustomGameForm(form => form.name.trim() ? form : { ...EMPTY_CUSTOM_GAME_FORM, name });
+        setMessage({
+          type: 'error',
+          text: `Game "${name}" not found in Ludusavi manifest. Add its save paths under Ludusavi Custom Games in the Games tab and it will be backed up.`
+        });
+      } else if (event.type === 'game_uninstalled') {
+        if (event.payload.final_backup) {
+          setMessage({ type: 'success', text: `${event.payload.name} was uninstalled, backing up its saves one last time` });
+        } else {
+          handleGameUninstalled(event.payload.app_id, event.payload.name);
+        }
+      } else if (event.type === 'launch_requested') {
+        openLaunchRequest();
+      } else if (event.type === 'monitoring_paused') {
+        setMessage({
+          type: 'success',
+          text: event.payload.paused ? 'Monitoring paused by an external tool' : 'Monitoring resumed'
+        });
+        setTimeout(() => setMessage(null), 5000);
+      } else if (event.type === 'backup_started') {
+        setRunningBackups(current => [...current.filter(name => name !== event.payload.game_name), event.payload.game_name]);
+      } else if (event.type === 'backup_finished') {
+        setRunningBackups(current => current.filter(name => name !== event.payload.game_name));
+        loadRecentBackups();
+      } else if (event.type === 'backup_queue_changed') {
+        setQueuedBackups(event.payload.queued);
+      } else if (event.type === 'cloud_upload_finished' && !event.payload.success) {
+        setMessage({
+          type: 'error',
+          text: `Upload of ${event.payload.game_name} to ${event.payload.destination} failed: ${event.payload.error}`
+        });
+      } else if (event.type === 'low_disk_space') {
+        setMessage({
+          type: 'error',
+          text: `Only ${formatBytes(event.payload.free_bytes)} free on ${event.payload.backup_path}. Backups may start failing.`
+        });
+        loadBackupStats();
+      } else if (event.type === 'stale_backups') {
+        setMessage({
+          type: 'error',
+          text: `No recent successful backup for ${event.payload.games.join(', ')}. Check them in the Games tab.`
+        });
+        loadRecentBackups();
+      } else if (event.type === 'game_detected') {
+        setMessage({
+          type: 'success',
+          text: `Game Save Monitor detected: ${event.payload.name}`
+        });
+        setTimeout(() => setMessage(null), 5000);
+      }
     });
 
     return () => {
-      unsubscribeNotFound.then(fn => fn());
-      unsubscribeDetected.then(fn => fn());
+      unsubscribeGameEvents.then(fn => fn());
     };
   }, []);
 
@@ -331,6 +1172,25 @@ function App() {
     });
   }, [raritySettings]);
 
+  // Refresh the backup list whenever the achievements tab is opened
+  useEffect(() => {
+    if (activeTab === 'achievements') {
+      loadAchievementBackups();
+      loadDuplicateGames();
+    }
+    if (activeTab === 'settings') {
+      loadBackupKeyStatus();
+    }
+    if (activeTab === 'games') {
+      loadRecentBackups();
+    }
+    if (activeTab === 'exclusions') {
+      loadSteamCategories();
+      loadIgnoredExecutables();
+      loadExclusions(); // picks up changes synced from Steam in the background
+    }
+  }, [activeTab]);
+
   // Debounced search effect
   useEffect(() => {
     // Clear previous timer
@@ -408,7 +1268,8 @@ function App() {
   // Listen for achievement unlock events and update UI
   useEffect(() => {
     const setupListener = async () => {
-      const unlisten = await listen<Achievement>('achievement-unlocked', (event) => {
+      const unlisten = await listenAppEvents('app-achievements', (event) => {
+        if (event.type !== 'achievement_unlocked') return;
         const unlockedAch = event.payload;
         console.log('🏆 Achievement unlocked event received in App:', unlockedAch);
 
@@ -453,11 +1314,54 @@ function App() {
     try {
       const loadedConfig = await invoke<Config>('get_config');
       setConfig(loadedConfig);
+      if (loadedConfig.backupPath) {
+        loadBackupStats();
+      }
+      loadNextLibraryBackup();
     } catch (error) {
       console.error('Failed to load config:', error);
     }
   };
 
+  const loadRecentBackups = async () => {
+    try {
+      setRecentBackups(await invoke<BackupHistoryEntry[]>('get_recent_backups', { limit: 30 }));
+      const freshness = await invoke<BackupFreshness[]>('get_backup_freshness');
+      setBackupFreshness(Object.fromEntries(freshness.map(entry => [entry.game_name, entry])));
+    } catch (error) {
+      console.error('Failed to load backup history:', error);
+    }
+  };
+
+  const loadNextLibraryBackup = async () => {
+    try {
+      setNextLibraryBackup(await invoke<ScheduledBackupInfo>('next_scheduled_backup'));
+    } catch (error) {
+      console.error('Failed to load the backup schedule:', error);
+    }
+  };
+
+  const handleToggleStorageDashboard = async () => {
+    if (storageDashboard) {
+      setStorageDashboard(null);
+      return;
+    }
+    try {
+      setStorageDashboard(await invoke<StorageDashboard>('get_storage_dashboard'));
+    } catch (error) {
+      setMessage({ type: 'error', text: `Failed to read backup storage: ${error}` });
+    }
+  };
+
+  const loadBackupStats = async () => {
+    try {
+      setBackupStats(await invoke<BackupStats>('get_backup_stats'));
+    } catch (error) {
+      console.error('Failed to load backup stats:', error);
+      setBackupStats(null);
+    }
+  };
+
   const loadLudusaviManifest = async () => {
     if (!config.ludusaviPath) {
       setMessage({
@@ -512,6 +1416,8 @@ function App() {
         type: 'success',
         text: 'Configuration saved successfully! Monitoring will restart.'
       });
+      loadBackupStats();
+      loadNextLibraryBackup();
     } catch (error) {
       setMessage({
         type: 'error',
@@ -575,47 +1481,197 @@ function App() {
     } catch (error) {
       setMessage({
         type: 'error',
-        text: `Failed to test Ludusavi: ${error}`
+        text: `Failed to test Ludusavi: ${error}`
+      });
+    } finally {
+      setTestingLudusavi(false);
+    }
+  };
+
+  const handleBrowseGameExe = async (gameName: string) => {
+    try {
+      const path = await invoke<string | null>('browse_file');
+      if (path) {
+        const updatedConfig = {
+          ...config,
+          gameExecutables: {
+            ...config.gameExecutables,
+            [gameName]: path
+          }
+        };
+        
+        setConfig(updatedConfig);
+        
+        // Auto-save
+        setSaving(true);
+        try {
+          await invoke('save_config', { config: updatedConfig });
+          setMessage({
+            type: 'success',
+            text: `Added executable for ${gameName} and saved configuration`
+          });
+          setTimeout(() => setMessage(null), 3000);
+        } catch (error) {
+          setMessage({
+            type: 'error',
+            text: `Failed to save configuration: ${error}`
+          });
+        } finally {
+          setSaving(false);
+        }
+      }
+    } catch (error) {
+      console.error('Failed to browse file:', error);
+    }
+  };
+
+  const handleRestoreGameSaves = async (gameName: string) => {
+    setRestoringGame(gameName);
+    try {
+      const result = await invoke<{ success: boolean; files_restored?: number; total_size?: string; error?: string }>('restore_game_saves', { gameName });
+      setMessage(result.success
+        ? { type: 'success', text: `Restored ${result.files_restored ?? 0} file(s) (${result.total_size ?? '0 B'}) for ${gameName}` }
+        : { type: 'error', text: `Restore of ${gameName} failed: ${result.error ?? 'Unknown error'}` });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to restore ${gameName}: ${error}`
+      });
+    } finally {
+      setRestoringGame(null);
+    }
+  };
+
+  // Roll back to one particular backup; the current saves are kept in a pre-restore snapshot first
+  const handleRestoreBackupVersion = async (gameName: string, version: BackupVersion) => {
+    const confirmed = await ask(
+      `Restore ${gameName} to its backup from ${new Date(version.created_at * 1000).toLocaleString()}?\n\n` +
+      `The current saves are snapshotted first.`,
+      { title: 'Steam Backup Manager', type: 'warning' }
+    );
+    if (!confirmed) return;
+
+    setRestoringGame(gameName);
+    try {
+      const result = await invoke<{ success: boolean; files_restored?: number; total_size?: string; error?: string }>(
+        'restore_backup_version', { gameName, backupId: version.id }
+      );
+      setMessage(result.success
+        ? { type: 'success', text: `Restored ${result.files_restored ?? 0} file(s) (${result.total_size ?? '0 B'}) for ${gameName}` }
+        : { type: 'error', text: `Restore of ${gameName} failed: ${result.error ?? 'Unknown error'}` });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to restore ${gameName}: ${error}`
+      });
+    } finally {
+      setRestoringGame(null);
+    }
+  };
+
+  const handleCheckSourceHealth = async () => {
+    setCheckingSources(true);
+    try {
+      setSourceHealth(await invoke<SourceHealth[]>('get_achievement_source_health'));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to check achievement sources: ${error}`
+      });
+    } finally {
+      setCheckingSources(false);
+    }
+  };
+
+  // Progress shows in the running backups banner, the result through the usual backup notification
+  const handleBackupGameNow = async (gameName: string) => {
+    try {
+      await invoke('backup_game_now', { gameName });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to back up ${gameName}: ${error}`
+      });
+    }
+  };
+
+  // Back up every installed Steam game Ludusavi knows, one after another; progress shows in the banner
+  const handleBackupAllGames = async () => {
+    setBackingUpLibrary(true);
+    try {
+      const summary = await invoke<{ backed_up: number; failed: string[]; excluded: number; not_in_manifest: string[] }>('backup_all_games');
+      setMessage({
+        type: 'success',
+        text: `Backed up ${summary.backed_up} installed games` +
+          (summary.failed.length > 0 ? `. Failed: ${summary.failed.join(', ')}` : '') +
+          (summary.not_in_manifest.length > 0 ? `. Not in Ludusavi's manifest: ${summary.not_in_manifest.join(', ')}` : '')
+      });
+      loadRecentBackups();
+    } catch (error) {
+      setMessage({ type: 'error', text: `Failed to back up the library: ${error}` });
+    } finally {
+      setBackingUpLibrary(false);
+    }
+  };
+
+  const handleToggleBackupVersions = async (gameName: string) => {
+    if (backupVersions?.gameName === gameName) {
+      setBackupVersions(null);
+      return;
+    }
+    try {
+      setBackupVersions({ gameName, versions: await invoke<BackupVersion[]>('list_backup_versions', { gameName }) });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to list backups of ${gameName}: ${error}`
+      });
+    }
+  };
+
+  const handleToggleBackupPreview = async (gameName: string) => {
+    if (backupPreview?.game_name === gameName) {
+      setBackupPreview(null);
+      return;
+    }
+    try {
+      setBackupPreview(await invoke<BackupPreview>('preview_backup', { gameName }));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to preview the backup of ${gameName}: ${error}`
       });
-    } finally {
-      setTestingLudusavi(false);
     }
   };
 
-  const handleBrowseGameExe = async (gameName: string) => {
+  // Back a game up to its own folder (another drive...), or back to the global backup path with null
+  const handleSetGameBackupPath = async (gameName: string, path: string | null) => {
+    const { [gameName]: _, ...rest } = config.gameBackupPaths || {};
+    const updatedConfig = {
+      ...config,
+      gameBackupPaths: path ? { ...rest, [gameName]: path } : rest
+    };
+    setConfig(updatedConfig);
     try {
-      const path = await invoke<string | null>('browse_file');
+      await invoke('save_config', { config: updatedConfig });
+      setMessage({
+        type: 'success',
+        text: path ? `${gameName} now backs up to ${path}` : `${gameName} backs up to the default folder again`
+      });
+      setTimeout(() => setMessage(null), 3000);
+    } catch (error) {
+      setMessage({ type: 'error', text: `Failed to save backup folder: ${error}` });
+    }
+  };
+
+  const handleBrowseGameBackupPath = async (gameName: string) => {
+    try {
+      const path = await invoke<string | null>('browse_folder');
       if (path) {
-        const updatedConfig = {
-          ...config,
-          gameExecutables: {
-            ...config.gameExecutables,
-            [gameName]: path
-          }
-        };
-        
-        setConfig(updatedConfig);
-        
-        // Auto-save
-        setSaving(true);
-        try {
-          await invoke('save_config', { config: updatedConfig });
-          setMessage({
-            type: 'success',
-            text: `Added executable for ${gameName} and saved configuration`
-          });
-          setTimeout(() => setMessage(null), 3000);
-        } catch (error) {
-          setMessage({
-            type: 'error',
-            text: `Failed to save configuration: ${error}`
-          });
-        } finally {
-          setSaving(false);
-        }
+        await handleSetGameBackupPath(gameName, path);
       }
     } catch (error) {
-      console.error('Failed to browse file:', error);
+      console.error('Failed to browse folder:', error);
     }
   };
 
@@ -649,26 +1705,69 @@ function App() {
     }
   };
 
-  const loadAllAchievements = async () => {
+  const loadAllAchievements = async (merged: boolean = mergeSources) => {
     setLoadingAchievements(true);
     try {
-      const games = await invoke<GameAchievementSummary[]>('get_all_achievements');
+      const games = await invoke<GameAchievementSummary[]>('get_all_achievements', { merged });
       setAchievementGames(games);
+      return games;
     } catch (error) {
       setMessage({
         type: 'error',
         text: `Failed to load achievements: ${error}`
       });
+      return [];
     } finally {
       setLoadingAchievements(false);
     }
   };
 
+  // Go where a steamsavemonitor:// link or the Explorer context menu pointed; the backend starts any backup
+  const openLaunchRequest = async () => {
+    const request = await invoke<LaunchRequest | null>('take_launch_request').catch(() => null);
+    if (!request) return;
+
+    if (request.action === 'backup_executable') {
+      setMessage({ type: 'success', text: `Backing up saves for ${request.path}` });
+      return;
+    }
+
+    setActiveTab('achievements');
+    const games = await loadAllAchievements();
+    const game = games.find(g => g.app_id === request.app_id);
+    if (!game) {
+      setMessage({ type: 'error', text: `No game with app ID ${request.app_id} in the library` });
+      return;
+    }
+    loadGameAchievements(game);
+    if (request.action === 'backup') {
+      setMessage({ type: 'success', text: `Backing up ${game.game_name}...` });
+    }
+  };
+
   const loadGameAchievements = async (game: GameAchievementSummary) => {
     setLoadingAchievements(true);
     setSelectedGame(game);
+    setDlcFilter('all');
+    setCompletionOrder(null);
+    invoke<AchievementViewPrefs>('get_achievement_view_prefs', { appId: game.app_id })
+      .then(setViewPrefs)
+      .catch(() => setViewPrefs(DEFAULT_VIEW_PREFS));
+    invoke<AchievementScreenshot[]>('get_achievement_screenshots', { appId: game.app_id })
+      .then(setScreenshots)
+      .catch(() => setScreenshots([]));
+    invoke<DlcGroup[]>('get_achievement_dlc_groups', { appId: game.app_id })
+      .then(setDlcGroups)
+      .catch(() => setDlcGroups([]));
+    invoke<{ [achievementId: string]: string[] }>('get_achievement_tags', { appId: game.app_id })
+      .then(setAchievementTags)
+      .catch(() => setAchievementTags({}));
     try {
-      const achievements = await invoke<Achievement[]>('get_game_achievements', { appId: game.app_id });
+      // The merged view lists each achievement once; otherwise show only the selected source's rows
+      const achievements = mergeSources
+        ? await invoke<Achievement[]>('get_merged_game_achievements', { appId: game.app_id })
+        : (await invoke<Achievement[]>('get_game_achievements', { appId: game.app_id }))
+            .filter(a => a.source === game.source);
       // Debug: Log first achievement to see icon URLs
       if (achievements.length > 0) {
         console.log('First achievement data:', achievements[0]);
@@ -686,6 +1785,14 @@ function App() {
     }
   };
 
+  const handleToggleMergeSources = (merged: boolean) => {
+    setMergeSources(merged);
+    localStorage.setItem('mergeSources', String(merged));
+    setSelectedGame(null);
+    setGameAchievements([]);
+    loadAllAchievements(merged);
+  };
+
   const handleSyncAchievements = async () => {
     setSyncingAchievements(true);
     try {
@@ -706,6 +1813,33 @@ function App() {
     }
   };
 
+  const handleDownloadAllIcons = async () => {
+    setDownloadingIcons(true);
+    try {
+      const result = await invoke<{
+        total: number;
+        downloaded: number;
+        already_cached: number;
+        failed: number;
+        bytes_downloaded: number;
+        cache_bytes: number;
+      }>('download_all_achievement_icons');
+      setMessage({
+        type: result.failed > 0 ? 'error' : 'success',
+        text: `Downloaded ${result.downloaded} icons (${formatBytes(result.bytes_downloaded)}), ${result.already_cached} already cached` +
+          (result.failed > 0 ? `, ${result.failed} failed` : '') +
+          `. Icon cache: ${formatBytes(result.cache_bytes)}`
+      });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to download icons: ${error}`
+      });
+    } finally {
+      setDownloadingIcons(false);
+    }
+  };
+
   const handleAddGameToTracking = async (game: SteamGameSearchResult) => {
     try {
       setCheckingSources(true);
@@ -756,7 +1890,8 @@ function App() {
 
       // Check if backup exists for this game
       const backupPath = await invoke<string | null>('check_backup_exists', {
-        gameName: sourceSelectionGame.name
+        gameName: sourceSelectionGame.name,
+        appId: sourceSelectionGame.app_id
       });
 
       if (backupPath) {
@@ -812,13 +1947,21 @@ function App() {
 
   const handleExportGameAchievements = async (appId: number, gameName: string) => {
     try {
-      const result = await invoke<string>('export_game_achievements', {
+      const destination = await invoke<string | null>('choose_export_destination', {
+        gameName: gameName,
+        format: exportFormat
+      });
+      if (!destination) return;
+
+      const result = await invoke<ExportResult>('export_game_achievements', {
         appId: appId,
-        gameName: gameName
+        gameName: gameName,
+        format: exportFormat,
+        destination: destination
       });
       setMessage({
         type: 'success',
-        text: result
+        text: `Exported ${result.achievements_written} achievements (${result.unlocked_written} unlocked) to: ${result.path}`
       });
     } catch (error) {
       setMessage({
@@ -828,6 +1971,19 @@ function App() {
     }
   };
 
+  // Sessions, backups and unlocks of one month (or everything) in one file under Documents
+  const handleExportActivityTimeline = async () => {
+    try {
+      const result = await invoke<{ path: string; events_written: number }>('export_activity_timeline', {
+        format: timelineFormat,
+        month: timelineMonth || null,
+      });
+      setMessage({ type: 'success', text: `Exported ${result.events_written} events to: ${result.path}` });
+    } catch (error) {
+      setMessage({ type: 'error', text: `Failed to export the activity timeline: ${error}` });
+    }
+  };
+
   const handleRemoveGame = async (appId: number, gameName: string, event: React.MouseEvent) => {
     event.stopPropagation(); // Prevent opening game details when clicking remove
 
@@ -854,7 +2010,7 @@ function App() {
     }
   };
 
-  // Helper function to get achievement icon (fetched through backend)
+  // Helper function to get achievement icon (cached on disk by the backend, served over the icon protocol)
   const getAchievementIcon = async (url: string | undefined): Promise<string | undefined> => {
     if (!url) return undefined;
 
@@ -865,10 +2021,10 @@ function App() {
 
     // Fetch through backend
     try {
-      const base64Data = await invoke<string>('fetch_achievement_icon', { url });
+      const iconUrl = await invoke<string>('fetch_achievement_icon', { url });
       // Cache it
-      setIconCache(prev => ({ ...prev, [url]: base64Data }));
-      return base64Data;
+      setIconCache(prev => ({ ...prev, [url]: iconUrl }));
+      return iconUrl;
     } catch (error) {
       console.error('Failed to fetch icon:', error);
       return undefined;
@@ -900,6 +2056,156 @@ function App() {
     }
   };
 
+  const handleScanScreenshots = async (appId: number) => {
+    setScanningScreenshots(true);
+    try {
+      const count = await invoke<number>('scan_achievement_screenshots', { appId });
+      setScreenshots(await invoke<AchievementScreenshot[]>('get_achievement_screenshots', { appId }));
+      setMessage({
+        type: 'success',
+        text: count > 0 ? `Found ${count} screenshot(s) taken at unlocks` : 'No screenshots taken near any unlock'
+      });
+      setTimeout(() => setMessage(null), 3000);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to scan screenshots: ${error}`
+      });
+    } finally {
+      setScanningScreenshots(false);
+    }
+  };
+
+  const handleSimulateSession = async (appId: number) => {
+    setSimulating(true);
+    setSimulationReport(null);
+    try {
+      setSimulationReport(await invoke<SimulationReport>('simulate_game_session', { appId }));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to simulate session: ${error}`
+      });
+    } finally {
+      setSimulating(false);
+    }
+  };
+
+  const screenshotsOf = (achievementId: string) =>
+    screenshots.filter(s => s.achievement_id === achievementId);
+
+  const handleDetectDlc = async (appId: number) => {
+    setDetectingDlc(true);
+    try {
+      const groups = await invoke<DlcGroup[]>('detect_achievement_dlc', { appId });
+      setDlcGroups(groups);
+      setMessage({
+        type: 'success',
+        text: groups.length > 1
+          ? `Found achievements for ${groups.length - 1} DLC${groups.length > 2 ? 's' : ''}`
+          : 'All achievements belong to the base game'
+      });
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to detect DLC: ${error}`
+      });
+    } finally {
+      setDetectingDlc(false);
+    }
+  };
+
+  const handleSetAchievementDlc = async (achievement: Achievement, dlcAppId: number | null) => {
+    try {
+      const groups = await invoke<DlcGroup[]>('set_achievement_dlc', {
+        appId: achievement.app_id,
+        achievementId: achievement.achievement_id,
+        dlcAppId
+      });
+      setDlcGroups(groups);
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to move achievement: ${error}`
+      });
+    }
+  };
+
+  const handleToggleCompletionOrder = async (appId: number) => {
+    if (completionOrder) {
+      setCompletionOrder(null);
+      return;
+    }
+    try {
+      setCompletionOrder(await invoke<SuggestedAchievement[]>('get_completion_order', { appId }));
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to build the completion order: ${error}`
+      });
+    }
+  };
+
+  const handleToggleAchievementTag = async (achievement: Achievement, tag: string) => {
+    const current = achievementTags[achievement.achievement_id] || [];
+    const tags = current.includes(tag) ? current.filter(t => t !== tag) : [...current, tag];
+    try {
+      await invoke('set_achievement_tags', {
+        appId: achievement.app_id,
+        achievementId: achievement.achievement_id,
+        tags
+      });
+      setAchievementTags({ ...achievementTags, [achievement.achievement_id]: tags });
+      if (completionOrder) {
+        setCompletionOrder(await invoke<SuggestedAchievement[]>('get_completion_order', { appId: achievement.app_id }));
+      }
+    } catch (error) {
+      setMessage({
+        type: 'error',
+        text: `Failed to update tags: ${error}`
+      });
+    }
+  };
+
+  // The DLC group an achievement is in, for the filter and the edit dialog
+  const dlcGroupOf = (achievementId: string) =>
+    dlcGroups.find(g => g.achievement_ids.includes(achievementId));
+
+  const handleChangeViewPrefs = async (appId: number, prefs: AchievementViewPrefs) => {
+    setViewPrefs(prefs);
+    try {
+      await invoke('set_achievement_view_prefs', { appId, prefs });
+    } catch (error) {
+      console.error('Failed to save view preferences:', error);
+    }
+  };
+
+  const sortAchievements = (achievements: Achievement[], sortBy: AchievementSort) => {
+    switch (sortBy) {
+      case 'rarity':
+        // Achievements without rarity data go last
+        return [...achievements].sort((a, b) =>
+          (a.global_unlock_percentage ?? 101) - (b.global_unlock_percentage ?? 101));
+      case 'unlock_date':
+        return [...achievements].sort((a, b) => (b.unlock_time ?? 0) - (a.unlock_time ?? 0));
+      case 'locked_first':
+        return [...achievements].sort((a, b) => Number(a.achieved) - Number(b.achieved));
+      default:
+        return achievements;
+    }
+  };
+
+  const visibleAchievements = sortAchievements(
+    gameAchievements.filter(a => {
+      if (viewPrefs.filter === 'locked' && a.achieved) return false;
+      if (viewPrefs.filter === 'unlocked' && !a.achieved) return false;
+      if (dlcFilter === 'all') return true;
+      const group = dlcGroupOf(a.achievement_id);
+      return dlcFilter === 'base' ? !group || group.dlc_app_id === null : group?.dlc_app_id === dlcFilter;
+    }),
+    viewPrefs.sort_by
+  );
+
   const handleCloseEditModal = () => {
     setEditingAchievement(null);
     setEditAchieved(false);
@@ -976,6 +2282,7 @@ function App() {
   };
 
   const configuredGames = Object.keys(config.gameExecutables);
+  const webhook: WebhookSettings = config.unlockWebhook ?? { enabled: false, url: '', template: '', secret: '' };
 
   return (
     <div className="min-h-screen bg-[#0a0e1a] text-white">
@@ -1103,6 +2410,41 @@ function App() {
 
       <div className="max-w-7xl mx-auto px-8 py-8">
         {/* Message Banner */}
+        {runningBackups.map(gameName => (
+          <div key={gameName} className="rounded-xl p-4 mb-6 flex items-center gap-3 border shadow-lg bg-blue-950/50 border-blue-600/50 text-blue-100">
+            <RefreshCw className="w-5 h-5 text-blue-400 animate-spin flex-shrink-0" />
+            <span className="flex-1 font-medium">
+              {operations.find(op => op.operation === 'backup' && op.message.includes(gameName))?.message ?? `Backing up ${gameName}`}...
+            </span>
+            <button
+              onClick={() => invoke('cancel_backup', { gameName }).catch(error => setMessage({ type: 'error', text: `Failed to cancel backup: ${error}` }))}
+              className="px-3 py-1 rounded-lg text-sm font-semibold hover:bg-white/10 border border-blue-500/40"
+            >
+              Cancel
+            </button>
+          </div>
+        ))}
+        {operations.filter(op => op.operation !== 'backup').map(op => (
+          <div key={op.operation_id} className="rounded-xl p-4 mb-6 border shadow-lg bg-blue-950/50 border-blue-600/50 text-blue-100">
+            <div className="flex items-center gap-3">
+              <RefreshCw className="w-5 h-5 text-blue-400 animate-spin flex-shrink-0" />
+              <span className="flex-1 font-medium truncate">{op.message}</span>
+              {op.total !== null && <span className="text-sm text-blue-300">{op.current} / {op.total}</span>}
+            </div>
+            {op.total !== null && op.total > 0 && (
+              <div className="mt-3 h-1.5 bg-[#0f1420] rounded-full overflow-hidden">
+                <div className="h-full bg-blue-500 transition-all" style={{ width: `${Math.min(100, (op.current / op.total) * 100)}%` }} />
+              </div>
+            )}
+          </div>
+        ))}
+        {queuedBackups.length > 0 && (
+          <div className="rounded-xl p-4 mb-6 flex items-center gap-3 border shadow-lg bg-blue-950/30 border-blue-600/30 text-blue-200">
+            <Save className="w-5 h-5 text-blue-400 flex-shrink-0" />
+            <span className="flex-1 font-medium">Waiting to back up: {queuedBackups.join(', ')}</span>
+          </div>
+        )}
+
         {message && (
           <div className={`rounded-xl p-4 mb-6 flex items-center gap-3 border shadow-lg ${
             message.type === 'success' 
@@ -1152,51 +2494,338 @@ function App() {
                   Ludusavi Executable Path
                   <span className="text-red-400">*</span>
                 </label>
-                <div className="flex gap-3">
+                <div className="flex gap-3">
+                  <input
+                    type="text"
+                    value={config.ludusaviPath}
+                    onChange={(e) => setConfig({ ...config, ludusaviPath: e.target.value })}
+                    placeholder="C:\Program Files\Ludusavi\ludusavi.exe"
+                    className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-3.5 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition-all font-mono text-sm"
+                  />
+                  <button
+                    onClick={handleBrowseLudusavi}
+                    className="bg-blue-600 hover:bg-blue-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-blue-500/20 border border-blue-500/30"
+                  >
+                    Browse
+                  </button>
+                  <button
+                    onClick={handleTestLudusavi}
+                    disabled={testingLudusavi}
+                    className="bg-emerald-600 hover:bg-emerald-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-emerald-500/20 disabled:opacity-50 disabled:cursor-not-allowed border border-emerald-500/30"
+                  >
+                    {testingLudusavi ? 'Testing...' : 'Test'}
+                  </button>
+                </div>
+              </div>
+
+              {/* Backup Path */}
+              <div className="space-y-3">
+                <label className="block text-sm font-semibold text-gray-200 flex items-center gap-2">
+                  <Save className="w-4 h-4 text-blue-400" />
+                  Backup Directory
+                  <span className="text-red-400">*</span>
+                </label>
+                <div className="flex gap-3">
+                  <input
+                    type="text"
+                    value={config.backupPath}
+                    onChange={(e) => setConfig({ ...config, backupPath: e.target.value })}
+                    placeholder="C:\GameBackups"
+                    className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-3.5 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition-all font-mono text-sm"
+                  />
+                  <button
+                    onClick={handleBrowseBackup}
+                    className="bg-blue-600 hover:bg-blue-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-blue-500/20 border border-blue-500/30"
+                  >
+                    Browse
+                  </button>
+                </div>
+                {backupStats?.disk && (
+                  <p className={`text-xs ${backupStats.low_space ? 'text-red-400' : 'text-gray-400'}`}>
+                    Backups use {formatBytes(backupStats.backup_size_bytes)} &middot; {formatBytes(backupStats.disk.free_bytes)} free of {formatBytes(backupStats.disk.total_bytes)}
+                    {backupStats.low_space && ' - running low on space'}
+                    {' '}
+                    <button onClick={handleToggleStorageDashboard} className="text-blue-400 hover:text-blue-300">
+                      {storageDashboard ? 'Hide usage by game' : 'Usage by game'}
+                    </button>
+                  </p>
+                )}
+                {storageDashboard && (
+                  <div className="max-h-72 overflow-y-auto divide-y divide-[#2a3142] border border-[#2a3142] rounded-lg">
+                    {storageDashboard.games.map((game) => (
+                      <div key={`${game.backup_path}/${game.game_name}`} className="px-4 py-2 text-sm" title={game.backup_path}>
+                        <div className="flex items-center justify-between gap-4">
+                          <span className="text-gray-300 truncate">{game.game_name}</span>
+                          <span className="text-gray-400 flex-shrink-0">{formatBytes(game.size_bytes)}</span>
+                        </div>
+                        <div className="flex items-center justify-between gap-4 text-xs text-gray-500 mt-1">
+                          <span>{game.versions} version(s)</span>
+                          <span>
+                            {game.last_backup_age_secs === null
+                              ? 'No backup found'
+                              : game.last_backup_age_secs < 86400
+                                ? 'Backed up today'
+                                : `Backed up ${Math.floor(game.last_backup_age_secs / 86400)} day(s) ago`}
+                          </span>
+                        </div>
+                        <div className="h-1 bg-[#0f1420] rounded mt-1">
+                          <div
+                            className="h-1 bg-blue-500 rounded"
+                            style={{ width: `${storageDashboard.total_bytes > 0 ? (game.size_bytes / storageDashboard.total_bytes) * 100 : 0}%` }}
+                          />
+                        </div>
+                      </div>
+                    ))}
+                    {storageDashboard.dedup_bytes > 0 && (
+                      <div className="px-4 py-2 text-sm flex items-center justify-between gap-4">
+                        <span className="text-gray-300">Deduplicated store</span>
+                        <span className="text-gray-400 flex-shrink-0">{formatBytes(storageDashboard.dedup_bytes)}</span>
+                      </div>
+                    )}
+                  </div>
+                )}
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Warn when free space drops below (MB, 0 = off)</p>
+                  <input
+                    type="number"
+                    min={0}
+                    value={config.lowDiskSpaceMb ?? 1024}
+                    onChange={(e) => setConfig({ ...config, lowDiskSpaceMb: Math.max(0, parseInt(e.target.value) || 0) })}
+                    className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                  />
+                </div>
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Warn when a game played regularly has no backup for (days, 0 = off)</p>
+                  <input
+                    type="number"
+                    min={0}
+                    value={config.staleBackupDays ?? 7}
+                    onChange={(e) => setConfig({ ...config, staleBackupDays: Math.max(0, parseInt(e.target.value) || 0) })}
+                    className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                  />
+                </div>
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Sessions in that time that count as playing regularly</p>
+                  <input
+                    type="number"
+                    min={1}
+                    value={config.staleBackupMinPlays ?? 3}
+                    onChange={(e) => setConfig({ ...config, staleBackupMinPlays: Math.max(1, parseInt(e.target.value) || 1) })}
+                    className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                  />
+                </div>
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Stop a backup or restore that hangs after (seconds, 0 = never)</p>
+                  <input
+                    type="number"
+                    min={0}
+                    value={config.ludusaviTimeoutSecs ?? 600}
+                    onChange={(e) => setConfig({ ...config, ludusaviTimeoutSecs: Math.max(0, parseInt(e.target.value) || 0) })}
+                    className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                  />
+                </div>
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Sync Ludusavi's cloud after each backup</p>
+                  <select
+                    value={config.ludusaviBackupCloudSync ?? 'ludusavi_default'}
+                    onChange={(e) => setConfig({ ...config, ludusaviBackupCloudSync: e.target.value as Config['ludusaviBackupCloudSync'] })}
+                    className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                  >
+                    <option value="ludusavi_default">As set in Ludusavi</option>
+                    <option value="always">Always</option>
+                    <option value="never">Never</option>
+                  </select>
+                </div>
+                <div className="flex items-center justify-between gap-4">
+                  <div>
+                    <p className="text-sm text-gray-400">Backup format</p>
+                    {config.ludusaviBackupFormat === 'zip' && (
+                      <p className="text-xs text-gray-500 mt-1">Snapshot comparison and restoring single files need the simple format</p>
+                    )}
+                  </div>
+                  <select
+                    value={config.ludusaviBackupFormat ?? 'ludusavi_default'}
+                    onChange={(e) => setConfig({ ...config, ludusaviBackupFormat: e.target.value as Config['ludusaviBackupFormat'] })}
+                    className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                  >
+                    <option value="ludusavi_default">As set in Ludusavi</option>
+                    <option value="simple">Simple (plain files)</option>
+                    <option value="zip">Zip archive</option>
+                  </select>
+                </div>
+                {config.ludusaviBackupFormat !== 'simple' && (
+                  <div className="flex items-center justify-between gap-4">
+                    <p className="text-sm text-gray-400">Zip compression and level (empty = default)</p>
+                    <div className="flex gap-2">
+                      <select
+                        value={config.ludusaviCompression ?? 'ludusavi_default'}
+                        onChange={(e) => setConfig({ ...config, ludusaviCompression: e.target.value as Config['ludusaviCompression'], ludusaviCompressionLevel: null })}
+                        className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                      >
+                        <option value="ludusavi_default">As set in Ludusavi</option>
+                        <option value="none">None (fastest)</option>
+                        <option value="deflate">Deflate</option>
+                        <option value="bzip2">Bzip2</option>
+                        <option value="zstd">Zstd</option>
+                      </select>
+                      <input
+                        type="number"
+                        min={config.ludusaviCompression === 'zstd' ? -7 : 1}
+                        max={config.ludusaviCompression === 'zstd' ? 22 : 9}
+                        disabled={!['deflate', 'bzip2', 'zstd'].includes(config.ludusaviCompression ?? '')}
+                        value={config.ludusaviCompressionLevel ?? ''}
+                        onChange={(e) => setConfig({ ...config, ludusaviCompressionLevel: e.target.value === '' ? null : parseInt(e.target.value) })}
+                        className="w-20 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500 disabled:opacity-50"
+                      />
+                    </div>
+                  </div>
+                )}
+                <div className="flex items-center justify-between gap-4">
+                  <p className="text-sm text-gray-400">Full backups kept per game (0 = Ludusavi's own setting)</p>
+                  <input
+                    type="number"
+                    min={0}
+                    value={config.backupFullVersions ?? 0}
+                    onChange={(e) => setConfig({ ...config, backupFullVersions: Math.max(0, parseInt(e.target.value) || 0) })}
+                    className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                  />
+                </div>
+                {(config.backupFullVersions ?? 0) > 0 && (
+                  <div className="flex items-center justify-between gap-4">
+                    <p className="text-sm text-gray-400">Differential backups kept on top of each full one</p>
+                    <input
+                      type="number"
+                      min={0}
+                      value={config.backupDifferentialVersions ?? 0}
+                      onChange={(e) => setConfig({ ...config, backupDifferentialVersions: Math.max(0, parseInt(e.target.value) || 0) })}
+                      className="w-28 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                    />
+                  </div>
+                )}
+                <label className="flex items-center justify-between gap-4 cursor-pointer" title="Flags backups that came out truncated or with damaged zip entries">
+                  <p className="text-sm text-gray-400">Verify each backup after Ludusavi writes it</p>
+                  <input
+                    type="checkbox"
+                    checked={config.verifyBackups ?? true}
+                    onChange={(e) => setConfig({ ...config, verifyBackups: e.target.checked })}
+                    className="w-4 h-4 accent-blue-500"
+                  />
+                </label>
+                <label className="flex items-center justify-between gap-4 cursor-pointer" title="Saves usually stay behind when a game is uninstalled">
+                  <p className="text-sm text-gray-400">Back up and archive a game's saves when it's uninstalled</p>
                   <input
-                    type="text"
-                    value={config.ludusaviPath}
-                    onChange={(e) => setConfig({ ...config, ludusaviPath: e.target.value })}
-                    placeholder="C:\Program Files\Ludusavi\ludusavi.exe"
-                    className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-3.5 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition-all font-mono text-sm"
+                    type="checkbox"
+                    checked={config.backupOnUninstall ?? false}
+                    onChange={(e) => setConfig({ ...config, backupOnUninstall: e.target.checked })}
+                    className="w-4 h-4 accent-blue-500"
                   />
-                  <button
-                    onClick={handleBrowseLudusavi}
-                    className="bg-blue-600 hover:bg-blue-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-blue-500/20 border border-blue-500/30"
-                  >
-                    Browse
-                  </button>
-                  <button
-                    onClick={handleTestLudusavi}
-                    disabled={testingLudusavi}
-                    className="bg-emerald-600 hover:bg-emerald-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-emerald-500/20 disabled:opacity-50 disabled:cursor-not-allowed border border-emerald-500/30"
-                  >
-                    {testingLudusavi ? 'Testing...' : 'Test'}
-                  </button>
-                </div>
-              </div>
-
-              {/* Backup Path */}
-              <div className="space-y-3">
-                <label className="block text-sm font-semibold text-gray-200 flex items-center gap-2">
-                  <Save className="w-4 h-4 text-blue-400" />
-                  Backup Directory
-                  <span className="text-red-400">*</span>
                 </label>
-                <div className="flex gap-3">
+                <label className="flex items-center justify-between gap-4" title="Placeholders: {game}, {date}, {appid}, {trigger}">
+                  <p className="text-sm text-gray-400">Cloud archive name</p>
                   <input
                     type="text"
-                    value={config.backupPath}
-                    onChange={(e) => setConfig({ ...config, backupPath: e.target.value })}
-                    placeholder="C:\GameBackups"
-                    className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-3.5 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition-all font-mono text-sm"
+                    value={config.backupNameTemplate ?? '{game} {date}'}
+                    onChange={(e) => setConfig({ ...config, backupNameTemplate: e.target.value })}
+                    placeholder="{game} {date}"
+                    className="w-64 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 font-mono text-sm"
                   />
-                  <button
-                    onClick={handleBrowseBackup}
-                    className="bg-blue-600 hover:bg-blue-500 px-6 py-3.5 rounded-lg font-semibold transition-all shadow-lg hover:shadow-blue-500/20 border border-blue-500/30"
-                  >
-                    Browse
-                  </button>
+                </label>
+                <div className="flex items-center justify-between gap-4">
+                  <div>
+                    <p className="text-sm text-gray-400">Back up all installed games</p>
+                    {nextLibraryBackup?.waiting_for_game ? (
+                      <p className="text-xs text-gray-500 mt-1">Due, starts when the running game closes</p>
+                    ) : nextLibraryBackup?.next_run && (
+                      <p className="text-xs text-gray-500 mt-1">Next run {new Date(nextLibraryBackup.next_run * 1000).toLocaleString()}</p>
+                    )}
+                  </div>
+                  <div className="flex gap-2">
+                    <select
+                      value={!config.libraryBackupSchedule ? 'off' : String(config.libraryBackupSchedule.weekday ?? 'daily')}
+                      onChange={(e) => setConfig({
+                        ...config,
+                        libraryBackupSchedule: e.target.value === 'off' ? null : {
+                          time: config.libraryBackupSchedule?.time ?? '03:00',
+                          weekday: e.target.value === 'daily' ? null : parseInt(e.target.value),
+                        },
+                      })}
+                      className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                    >
+                      <option value="off">Never</option>
+                      <option value="daily">Every day</option>
+                      {['Mondays', 'Tuesdays', 'Wednesdays', 'Thursdays', 'Fridays', 'Saturdays', 'Sundays'].map((day, index) => (
+                        <option key={day} value={index}>{day}</option>
+                      ))}
+                    </select>
+                    {config.libraryBackupSchedule && (
+                      <input
+                        type="time"
+                        value={config.libraryBackupSchedule.time}
+                        onChange={(e) => setConfig({ ...config, libraryBackupSchedule: { ...config.libraryBackupSchedule!, time: e.target.value } })}
+                        className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                      />
+                    )}
+                  </div>
+                </div>
+                <label className="flex items-center justify-between gap-4 cursor-pointer" title="Restore it from the game's snapshots if a session breaks your save">
+                  <p className="text-sm text-gray-400">Snapshot saves when a game starts (last 3 kept)</p>
+                  <input
+                    type="checkbox"
+                    checked={config.snapshotOnGameStart ?? false}
+                    onChange={(e) => setConfig({ ...config, snapshotOnGameStart: e.target.checked })}
+                    className="w-4 h-4 accent-blue-500"
+                  />
+                </label>
+                <div className="space-y-2">
+                  <p className="text-sm text-gray-400">Back up while playing (game name as detected, every N minutes)</p>
+                  {Object.entries(config.sessionBackupMinutes || {}).map(([gameName, minutes]) => (
+                    <div key={gameName} className="flex items-center justify-between gap-4 bg-[#0f1420] rounded-lg px-3 py-2 border border-[#2a3142]">
+                      <span className="text-sm text-white truncate">{gameName}</span>
+                      <div className="flex items-center gap-3 flex-shrink-0">
+                        <span className="text-sm text-gray-400">every {minutes} min</span>
+                        <button
+                          onClick={() => {
+                            const { [gameName]: _, ...rest } = config.sessionBackupMinutes || {};
+                            setConfig({ ...config, sessionBackupMinutes: rest });
+                          }}
+                          className="p-1.5 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30"
+                          title="Stop backing up while playing"
+                        >
+                          <Trash2 className="w-4 h-4 text-red-400" />
+                        </button>
+                      </div>
+                    </div>
+                  ))}
+                  <div className="flex gap-2">
+                    <input
+                      type="text"
+                      value={sessionBackupGame}
+                      onChange={(e) => setSessionBackupGame(e.target.value)}
+                      placeholder="Game name"
+                      className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500"
+                    />
+                    <input
+                      type="number"
+                      min={1}
+                      value={sessionBackupInterval}
+                      onChange={(e) => setSessionBackupInterval(Math.max(1, parseInt(e.target.value) || 1))}
+                      className="w-20 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                    />
+                    <button
+                      onClick={() => {
+                        if (!sessionBackupGame.trim()) return;
+                        setConfig({
+                          ...config,
+                          sessionBackupMinutes: { ...(config.sessionBackupMinutes || {}), [sessionBackupGame.trim()]: sessionBackupInterval }
+                        });
+                        setSessionBackupGame('');
+                      }}
+                      className="px-3 py-2 bg-blue-600 hover:bg-blue-500 rounded-lg border border-blue-500/30"
+                      title="Add"
+                    >
+                      <Plus className="w-4 h-4" />
+                    </button>
+                  </div>
                 </div>
               </div>
 
@@ -1234,7 +2863,7 @@ function App() {
                   className="w-full bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-3.5 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition-all font-mono text-sm"
                 />
                 <p className="text-xs text-gray-400">
-                  Your Steam3 ID number (find it in C:\Program Files (x86)\Steam\userdata\). If not set, the first user will be used.
+                  Your Steam3 ID number (find it in C:\Program Files (x86)\Steam\userdata\). If not set, the account most recently signed in to Steam is used.
                 </p>
               </div>
 
@@ -1277,6 +2906,44 @@ function App() {
                 </button>
               </div>
 
+              {/* Startup conditions, only used when launched at login */}
+              {config.autoStart && (
+                <div className="bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142] space-y-4">
+                  <div className="flex items-center justify-between gap-4">
+                    <div>
+                      <h3 className="font-semibold text-white text-base">Startup Delay</h3>
+                      <p className="text-sm text-gray-400 mt-1">Seconds to wait after login before monitoring starts</p>
+                    </div>
+                    <input
+                      type="number"
+                      min={0}
+                      max={600}
+                      value={config.startupDelaySecs ?? 0}
+                      onChange={(e) => setConfig({ ...config, startupDelaySecs: Math.max(0, parseInt(e.target.value) || 0) })}
+                      className="w-24 bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-right focus:outline-none focus:border-blue-500"
+                    />
+                  </div>
+                  <div className="flex items-center justify-between gap-4">
+                    <div>
+                      <h3 className="font-semibold text-white text-base">Wait for Steam</h3>
+                      <p className="text-sm text-gray-400 mt-1">Start monitoring only once Steam is running</p>
+                    </div>
+                    <button
+                      onClick={() => setConfig({ ...config, waitForSteam: !config.waitForSteam })}
+                      className={`relative w-16 h-9 rounded-full transition-all shadow-inner ${
+                        config.waitForSteam ? 'bg-blue-600' : 'bg-gray-700'
+                      }`}
+                    >
+                      <div
+                        className={`absolute top-1 left-1 w-7 h-7 bg-white rounded-full shadow-lg transition-transform ${
+                          config.waitForSteam ? 'transform translate-x-7' : ''
+                        }`}
+                      />
+                    </button>
+                  </div>
+                </div>
+              )}
+
               {/* Notifications Toggle */}
               <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
                 <div>
@@ -1297,6 +2964,190 @@ function App() {
                 </button>
               </div>
 
+              {/* Explorer Context Menu Toggle */}
+              <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
+                <div>
+                  <h3 className="font-semibold text-white text-base">Explorer Context Menu</h3>
+                  <p className="text-sm text-gray-400 mt-1">Add "Back up saves" when right-clicking a game's .exe</p>
+                </div>
+                <button
+                  onClick={() => setConfig({ ...config, explorerContextMenu: !config.explorerContextMenu })}
+                  className={`relative w-16 h-9 rounded-full transition-all shadow-inner ${
+                    config.explorerContextMenu ? 'bg-blue-600' : 'bg-gray-700'
+                  }`}
+                >
+                  <div
+                    className={`absolute top-1 left-1 w-7 h-7 bg-white rounded-full shadow-lg transition-transform ${
+                      config.explorerContextMenu ? 'transform translate-x-7' : ''
+                    }`}
+                  />
+                </button>
+              </div>
+
+              {/* Control Pipe Toggle */}
+              <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
+                <div>
+                  <h3 className="font-semibold text-white text-base">External Control</h3>
+                  <p className="text-sm text-gray-400 mt-1">Let scripts and launchers send pause, resume, backup and status commands to <code>\\.\pipe\SteamSaveMonitor</code></p>
+                </div>
+                <button
+                  onClick={() => setConfig({ ...config, controlPipeEnabled: !config.controlPipeEnabled })}
+                  className={`relative w-16 h-9 rounded-full transition-all shadow-inner ${
+                    config.controlPipeEnabled ? 'bg-blue-600' : 'bg-gray-700'
+                  }`}
+                >
+                  <div
+                    className={`absolute top-1 left-1 w-7 h-7 bg-white rounded-full shadow-lg transition-transform ${
+                      config.controlPipeEnabled ? 'transform translate-x-7' : ''
+                    }`}
+                  />
+                </button>
+              </div>
+
+              {/* Unlock Webhook */}
+              <div className="bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142] space-y-3">
+                <div className="flex items-center justify-between">
+                  <div>
+                    <h3 className="font-semibold text-white text-base">Unlock Webhook</h3>
+                    <p className="text-sm text-gray-400 mt-1">POST every achievement unlock to Discord or your own server</p>
+                  </div>
+                  <button
+                    onClick={() => setConfig({ ...config, unlockWebhook: { ...webhook, enabled: !webhook.enabled } })}
+                    className={`relative w-16 h-9 rounded-full transition-all shadow-inner ${
+                      webhook.enabled ? 'bg-blue-600' : 'bg-gray-700'
+                    }`}
+                  >
+                    <div
+                      className={`absolute top-1 left-1 w-7 h-7 bg-white rounded-full shadow-lg transition-transform ${
+                        webhook.enabled ? 'transform translate-x-7' : ''
+                      }`}
+                    />
+                  </button>
+                </div>
+                <input
+                  type="text"
+                  value={webhook.url}
+                  onChange={(e) => setConfig({ ...config, unlockWebhook: { ...webhook, url: e.target.value } })}
+                  placeholder="https://discord.com/api/webhooks/..."
+                  className="w-full bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                />
+                <textarea
+                  value={webhook.template}
+                  onChange={(e) => setConfig({ ...config, unlockWebhook: { ...webhook, template: e.target.value } })}
+                  placeholder='Body template, empty = all fields. e.g. {"content": "🏆 {{game_name}}: {{display_name}}"}'
+                  rows={3}
+                  className="w-full bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm font-mono focus:outline-none focus:border-blue-500"
+                />
+                <div className="flex gap-3">
+                  <input
+                    type="password"
+                    value={webhook.secret}
+                    onChange={(e) => setConfig({ ...config, unlockWebhook: { ...webhook, secret: e.target.value } })}
+                    placeholder="Signing secret (optional)"
+                    className="flex-1 bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                  />
+                  <button
+                    onClick={async () => {
+                      try {
+                        await invoke('test_unlock_webhook', { settings: webhook });
+                        setMessage({ type: 'success', text: 'Test unlock sent to the webhook' });
+                      } catch (error) {
+                        setMessage({ type: 'error', text: `Webhook test failed: ${error}` });
+                      }
+                    }}
+                    disabled={!webhook.url.trim()}
+                    className="flex items-center gap-2 px-3 py-1.5 bg-[#1a1f3a] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300 disabled:opacity-50"
+                  >
+                    Send Test
+                  </button>
+                </div>
+                <p className="text-xs text-gray-500">
+                  Fields: game_name, app_id, achievement_id, display_name, description, icon_url, unlock_time, global_unlock_percentage.
+                  With a secret, requests carry X-Webhook-Timestamp and X-Signature-256 (sha256= HMAC of "timestamp.body").
+                </p>
+              </div>
+
+              {/* Backup Encryption Key */}
+              <div className="bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142] space-y-3">
+                <div>
+                  <h3 className="font-semibold text-white text-base">Backup Encryption Key</h3>
+                  <p className="text-sm text-gray-400 mt-1">
+                    {!backupKeyStatus?.configured
+                      ? 'Set up the key encrypted backups use. Enter a passphrase (12+ characters) or generate a random key.'
+                      : backupKeyStatus.key_available
+                        ? `Key ${backupKeyStatus.from_passphrase ? 'derived from your passphrase' : 'generated'}${backupKeyStatus.created_at ? ` on ${new Date(backupKeyStatus.created_at * 1000).toLocaleDateString()}` : ''}, stored for this Windows user. Restores check it first.`
+                        : `Restores are blocked: ${backupKeyStatus.error}`}
+                  </p>
+                </div>
+                <div className="flex gap-3">
+                  <input
+                    type="password"
+                    value={backupPassphrase}
+                    onChange={(e) => setBackupPassphrase(e.target.value)}
+                    placeholder={backupKeyStatus?.from_passphrase ? 'Passphrase to check' : 'Passphrase'}
+                    className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-2 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 text-sm"
+                  />
+                  {!backupKeyStatus?.configured ? (
+                    <>
+                      <button
+                        onClick={() => handleCreateBackupKey(true)}
+                        disabled={backupPassphrase.length < 12}
+                        className="bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50 text-sm"
+                      >
+                        Use Passphrase
+                      </button>
+                      <button
+                        onClick={() => handleCreateBackupKey(false)}
+                        className="bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] text-sm"
+                      >
+                        Generate Key
+                      </button>
+                    </>
+                  ) : (
+                    <>
+                      <button
+                        onClick={handleVerifyBackupKey}
+                        className="bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] text-sm"
+                      >
+                        Verify
+                      </button>
+                      <button
+                        onClick={handleShowRecoveryPhrase}
+                        disabled={!backupKeyStatus.key_available}
+                        className="bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50 text-sm"
+                      >
+                        Recovery Phrase
+                      </button>
+                    </>
+                  )}
+                </div>
+                {recoveryPhrase && (
+                  <div className="bg-amber-500/10 border border-amber-500/40 rounded-lg p-4">
+                    <p className="text-sm text-amber-300 mb-2">Write this down and keep it somewhere safe. It's the only way to restore encrypted backups on another PC or after reinstalling Windows.</p>
+                    <p className="font-mono text-white text-sm break-all select-all">{recoveryPhrase}</p>
+                    <button onClick={() => setRecoveryPhrase(null)} className="text-xs text-gray-400 hover:text-white mt-2">I've saved it</button>
+                  </div>
+                )}
+                {(!backupKeyStatus?.configured || !backupKeyStatus.key_available) && (
+                  <div className="flex gap-3">
+                    <input
+                      type="text"
+                      value={recoveryInput}
+                      onChange={(e) => setRecoveryInput(e.target.value)}
+                      placeholder="Recovery phrase (XXXX-XXXX-...)"
+                      className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-4 py-2 text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 font-mono text-sm"
+                    />
+                    <button
+                      onClick={handleRecoverBackupKey}
+                      disabled={!recoveryInput.trim()}
+                      className="bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50 text-sm"
+                    >
+                      Recover
+                    </button>
+                  </div>
+                )}
+              </div>
+
               {/* Save Button */}
               <div className="pt-3">
                 <button
@@ -1367,11 +3218,73 @@ function App() {
                 </h3>
                 <div className="space-y-3">
                   {configuredGames.map(gameName => (
-                    <div key={gameName} className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg p-4 flex items-center justify-between hover:border-emerald-500/30 transition-all">
+                    <div key={gameName}>
+                    <div className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg p-4 flex items-center justify-between hover:border-emerald-500/30 transition-all">
                       <div className="flex-1 min-w-0">
                         <p className="font-semibold text-white text-base">{gameName}</p>
                         <p className="text-sm text-gray-400 truncate font-mono mt-1">{config.gameExecutables[gameName]}</p>
+                        {config.gameBackupPaths?.[gameName] && (
+                          <p className="text-xs text-blue-300 mt-1 flex items-center gap-1">
+                            <HardDrive className="w-3 h-3 flex-shrink-0" />
+                            <span className="truncate font-mono">{config.gameBackupPaths[gameName]}</span>
+                            <button
+                              onClick={() => handleSetGameBackupPath(gameName, null)}
+                              className="text-gray-500 hover:text-red-400"
+                              title="Use the default backup folder"
+                            >
+                              <X className="w-3 h-3" />
+                            </button>
+                          </p>
+                        )}
+                        {backupFreshness[gameName] && (
+                          <p className={`text-xs mt-1 ${backupFreshness[gameName].stale ? 'text-amber-400' : 'text-gray-500'}`}>
+                            {backupFreshness[gameName].successful_backups} backup(s)
+                            {backupFreshness[gameName].failed_backups > 0 && `, ${backupFreshness[gameName].failed_backups} failed`}
+                            {' · last successful '}
+                            {backupFreshness[gameName].last_success
+                              ? new Date(backupFreshness[gameName].last_success! * 1000).toLocaleString()
+                              : 'never'}
+                            {backupFreshness[gameName].stale && ' - out of date'}
+                          </p>
+                        )}
                       </div>
+                      <button
+                        onClick={() => handleBackupGameNow(gameName)}
+                        disabled={runningBackups.includes(gameName) || queuedBackups.includes(gameName)}
+                        className="ml-4 p-2.5 text-emerald-400 hover:text-emerald-300 hover:bg-emerald-950/50 rounded-lg transition-all border border-transparent hover:border-emerald-500/30 disabled:opacity-50"
+                        title="Back up now"
+                      >
+                        <Save className={`w-5 h-5 ${runningBackups.includes(gameName) ? 'animate-pulse' : ''}`} />
+                      </button>
+                      <button
+                        onClick={() => handleToggleBackupPreview(gameName)}
+                        className="ml-4 p-2.5 text-emerald-400 hover:text-emerald-300 hover:bg-emerald-950/50 rounded-lg transition-all border border-transparent hover:border-emerald-500/30"
+                        title="Preview which files a backup would save"
+                      >
+                        <Search className="w-5 h-5" />
+                      </button>
+                      <button
+                        onClick={() => handleBrowseGameBackupPath(gameName)}
+                        className="ml-4 p-2.5 text-blue-400 hover:text-blue-300 hover:bg-blue-950/50 rounded-lg transition-all border border-transparent hover:border-blue-500/30"
+                        title="Back up this game to a different folder"
+                      >
+                        <HardDrive className="w-5 h-5" />
+                      </button>
+                      <button
+                        onClick={() => handleToggleBackupVersions(gameName)}
+                        className="ml-4 p-2.5 text-emerald-400 hover:text-emerald-300 hover:bg-emerald-950/50 rounded-lg transition-all border border-transparent hover:border-emerald-500/30"
+                        title="Show backup versions"
+                      >
+                        <FolderOpen className="w-5 h-5" />
+                      </button>
+                      <button
+                        onClick={() => handleRestoreGameSaves(gameName)}
+                        disabled={restoringGame !== null}
+                        className="ml-4 p-2.5 text-blue-400 hover:text-blue-300 hover:bg-blue-950/50 rounded-lg transition-all border border-transparent hover:border-blue-500/30 disabled:opacity-50"
+                        title="Restore saves from the latest backup"
+                      >
+                        <RefreshCw className={`w-5 h-5 ${restoringGame === gameName ? 'animate-spin' : ''}`} />
+                      </button>
                       <button
                         onClick={() => handleRemoveGameExe(gameName)}
                         className="ml-4 p-2.5 text-red-400 hover:text-red-300 hover:bg-red-950/50 rounded-lg transition-all border border-transparent hover:border-red-500/30"
@@ -1380,6 +3293,100 @@ function App() {
                         <Trash2 className="w-5 h-5" />
                       </button>
                     </div>
+                    {backupVersions?.gameName === gameName && (
+                      <div className="mt-1 ml-4 divide-y divide-[#2a3142] border border-[#2a3142] rounded-lg">
+                        {[...backupVersions.versions].reverse().map((version) => (
+                          <div key={version.id} className="px-4 py-2 flex items-center justify-between text-sm">
+                            <span className="text-gray-300">{new Date(version.created_at * 1000).toLocaleString()}</span>
+                            <div className="flex items-center gap-3">
+                              <span className="text-gray-500">
+                                {version.file_count} file(s), {(version.total_bytes / 1024 / 1024).toFixed(2)} MB
+                              </span>
+                              <button
+                                onClick={() => handleRestoreBackupVersion(gameName, version)}
+                                disabled={restoringGame !== null}
+                                className="px-2 py-1 text-xs text-blue-400 hover:text-blue-300 hover:bg-blue-950/40 rounded disabled:opacity-50"
+                              >
+                                Restore
+                              </button>
+                            </div>
+                          </div>
+                        ))}
+                      </div>
+                    )}
+                    {backupPreview?.game_name === gameName && (
+                      <div className="mt-1 ml-4 border border-[#2a3142] rounded-lg">
+                        <p className="px-4 py-2 text-sm text-gray-300 border-b border-[#2a3142]">
+                          {backupPreview.found
+                            ? `${backupPreview.files.length} file(s), ${backupPreview.total_size} would be backed up`
+                            : 'Ludusavi found no saves for this game'}
+                        </p>
+                        <div className="max-h-60 overflow-y-auto divide-y divide-[#2a3142]">
+                          {backupPreview.files.map((file) => (
+                            <div key={file.path} className="px-4 py-1.5 flex items-center justify-between gap-4 text-xs">
+                              <span className="text-gray-400 font-mono truncate" title={file.path}>{file.path}</span>
+                              <span className="text-gray-500 flex-shrink-0">
+                                {(file.bytes / 1024).toFixed(1)} KB{file.change && ` · ${file.change}`}
+                              </span>
+                            </div>
+                          ))}
+                        </div>
+                      </div>
+                    )}
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
+            {/* Backup Timeline */}
+            {recentBackups.length > 0 && (
+              <div className="bg-[#1a1f3a] rounded-xl p-8 border border-[#2a3142] shadow-xl">
+                <div className="flex items-center justify-between gap-4 mb-4">
+                  <h3 className="text-xl font-bold text-white">Backup Timeline</h3>
+                  <div className="flex items-center gap-2" title="Play sessions, backups and unlocks, for one month or all time">
+                    <input
+                      type="month"
+                      value={timelineMonth}
+                      onChange={(e) => setTimelineMonth(e.target.value)}
+                      className="bg-[#0f1420] border border-[#2a3142] rounded-lg px-2 py-1.5 text-sm text-white focus:outline-none focus:border-blue-500"
+                    />
+                    <select
+                      value={timelineFormat}
+                      onChange={(e) => setTimelineFormat(e.target.value as 'json' | 'csv')}
+                      className="bg-[#0f1420] border border-[#2a3142] rounded-lg px-2 py-1.5 text-sm text-white focus:outline-none focus:border-blue-500"
+                    >
+                      <option value="csv">CSV</option>
+                      <option value="json">JSON</option>
+                    </select>
+                    <button
+                      onClick={handleExportActivityTimeline}
+                      className="px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300"
+                    >
+                      Export Activity
+                    </button>
+                  </div>
+                </div>
+                <div className="max-h-80 overflow-y-auto divide-y divide-[#2a3142] border border-[#2a3142] rounded-lg">
+                  {recentBackups.map((entry) => (
+                    <div key={entry.id} className="p-3 flex items-start gap-3">
+                      {entry.success ? (
+                        <CheckCircle className="w-4 h-4 text-emerald-400 flex-shrink-0 mt-0.5" />
+                      ) : (
+                        <AlertCircle className="w-4 h-4 text-red-400 flex-shrink-0 mt-0.5" />
+                      )}
+                      <div className="min-w-0 flex-1">
+                        <p className="text-sm text-white truncate">
+                          {entry.game_name} <span className="text-xs text-gray-500">· {entry.kind}</span>
+                        </p>
+                        <p className={`text-xs mt-0.5 truncate ${entry.success ? 'text-gray-500' : 'text-red-400'}`} title={entry.error ?? undefined}>
+                          {new Date(entry.timestamp * 1000).toLocaleString()}
+                          {entry.files_count !== null && ` · ${entry.files_count} files`}
+                          {entry.total_size && ` · ${entry.total_size}`}
+                          {entry.error && ` · ${entry.error}`}
+                        </p>
+                      </div>
+                    </div>
                   ))}
                 </div>
               </div>
@@ -1388,9 +3395,20 @@ function App() {
             {/* Available Games */}
             <div className="bg-[#1a1f3a] rounded-xl border border-[#2a3142] shadow-xl overflow-hidden">
               <div className="p-6 border-b border-[#2a3142] bg-[#13172a]">
-                <h3 className="text-lg font-bold text-white">
-                  Available Games in Ludusavi Manifest
-                </h3>
+                <div className="flex items-center justify-between gap-4">
+                  <h3 className="text-lg font-bold text-white">
+                    Available Games in Ludusavi Manifest
+                  </h3>
+                  <button
+                    onClick={handleBackupAllGames}
+                    disabled={backingUpLibrary}
+                    className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300 disabled:opacity-50"
+                    title="Back up every installed Steam game Ludusavi knows"
+                  >
+                    <Save className={`w-4 h-4 ${backingUpLibrary ? 'animate-pulse' : ''}`} />
+                    Back Up All Installed Games
+                  </button>
+                </div>
                 {ludusaviGames.length > 0 && (
                   <p className="text-sm text-gray-400 mt-2">
                     Showing <span className="text-blue-400 font-semibold">{filteredGames.length}</span> of <span className="text-blue-400 font-semibold">{ludusaviGames.length}</span> games
@@ -1458,13 +3476,166 @@ function App() {
                                 </div>
                               );
                             })}
-                          </div>
-                        )}
+                          </div>
+                        )}
+                      </div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            </div>
+
+            {/* Ludusavi Custom Games */}
+            <div className="bg-[#1a1f3a] rounded-xl p-8 border border-[#2a3142] shadow-xl">
+              <div className="flex items-center justify-between mb-4">
+                <div>
+                  <h3 className="text-xl font-bold text-white">Ludusavi Custom Games</h3>
+                  <p className="text-gray-400 text-sm mt-1">
+                    Edit Ludusavi's own roots and custom games, e.g. to add save locations for a game Ludusavi couldn't find
+                  </p>
+                </div>
+                <button
+                  onClick={loadLudusaviCustomization}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300"
+                >
+                  <RefreshCw className="w-4 h-4" />
+                  {ludusaviCustomization ? 'Reload' : 'Load Ludusavi Settings'}
+                </button>
+              </div>
+
+              {ludusaviCustomization && (
+                <div className="space-y-6">
+                  <p className="text-xs text-gray-500">{ludusaviCustomization.config_path}</p>
+
+                  {/* Roots */}
+                  <div>
+                    <h4 className="text-sm font-semibold text-gray-200 mb-2">Roots</h4>
+                    <div className="space-y-2 mb-2">
+                      {ludusaviCustomization.roots.map((root) => (
+                        <div key={root.path} className="bg-[#0f1420] rounded-lg p-3 border border-[#2a3142] flex items-center justify-between">
+                          <p className="text-sm text-white truncate">{root.path} <span className="text-xs text-gray-500">({root.store})</span></p>
+                          <button
+                            onClick={() => handleSaveLudusaviRoots(ludusaviCustomization.roots.filter(r => r.path !== root.path))}
+                            className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30"
+                            title="Remove root"
+                          >
+                            <Trash2 className="w-4 h-4 text-red-400" />
+                          </button>
+                        </div>
+                      ))}
+                    </div>
+                    <div className="flex gap-2">
+                      <input
+                        type="text"
+                        value={newRoot.path}
+                        onChange={(e) => setNewRoot({ ...newRoot, path: e.target.value })}
+                        placeholder="C:\Games"
+                        className="flex-1 bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500"
+                      />
+                      <select
+                        value={newRoot.store}
+                        onChange={(e) => setNewRoot({ ...newRoot, store: e.target.value })}
+                        className="bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                      >
+                        {['steam', 'epic', 'gog', 'gogGalaxy', 'ea', 'ubisoft', 'microsoft', 'heroic', 'lutris', 'prime', 'otherHome', 'otherWine', 'other'].map((store) => (
+                          <option key={store} value={store}>{store}</option>
+                        ))}
+                      </select>
+                      <button
+                        onClick={() => {
+                          handleSaveLudusaviRoots([...ludusaviCustomization.roots, { path: newRoot.path.trim(), store: newRoot.store }]);
+                          setNewRoot({ path: '', store: newRoot.store });
+                        }}
+                        disabled={!newRoot.path.trim() || ludusaviCustomization.roots.some(r => r.path === newRoot.path.trim())}
+                        className="flex items-center gap-2 px-3 py-2 bg-blue-600 hover:bg-blue-500 rounded-lg text-sm text-white disabled:opacity-50"
+                      >
+                        <Plus className="w-4 h-4" />
+                        Add Root
+                      </button>
+                    </div>
+                  </div>
+
+                  {/* Custom games */}
+                  <div>
+                    <h4 className="text-sm font-semibold text-gray-200 mb-2">Custom Games ({ludusaviCustomization.custom_games.length})</h4>
+                    <div className="space-y-2 mb-4">
+                      {ludusaviCustomization.custom_games.map((game) => (
+                        <div key={game.name} className="bg-[#0f1420] rounded-lg p-3 border border-[#2a3142] flex items-center justify-between">
+                          <button
+                            onClick={() => setCustomGameForm({
+                              name: game.name,
+                              integration: game.integration,
+                              files: game.files.join('\n'),
+                              registry: game.registry.join('\n'),
+                            })}
+                            className="min-w-0 text-left"
+                            title="Edit"
+                          >
+                            <p className="text-sm text-white truncate">{game.name}</p>
+                            <p className="text-xs text-gray-500 mt-0.5">
+                              {game.integration} · {game.files.length} file path{game.files.length === 1 ? '' : 's'} · {game.registry.length} registry key{game.registry.length === 1 ? '' : 's'}
+                            </p>
+                          </button>
+                          <button
+                            onClick={() => handleRemoveCustomGame(game.name)}
+                            className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30"
+                            title="Remove custom game"
+                          >
+                            <Trash2 className="w-4 h-4 text-red-400" />
+                          </button>
+                        </div>
+                      ))}
+                    </div>
+
+                    <div className="bg-[#0f1420] rounded-lg p-4 border border-[#2a3142] space-y-3">
+                      {missingLudusaviGame && (
+                        <p className="text-xs text-amber-400">
+                          Ludusavi couldn't find {missingLudusaviGame}. Saving it here backs it up right away.
+                        </p>
+                      )}
+                      <div className="flex gap-2">
+                        <input
+                          type="text"
+                          value={customGameForm.name}
+                          onChange={(e) => setCustomGameForm({ ...customGameForm, name: e.target.value })}
+                          placeholder="Game name (as in the Ludusavi manifest to override or extend it)"
+                          className="flex-1 bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500"
+                        />
+                        <select
+                          value={customGameForm.integration}
+                          onChange={(e) => setCustomGameForm({ ...customGameForm, integration: e.target.value as 'override' | 'extend' })}
+                          className="bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                        >
+                          <option value="override">Override</option>
+                          <option value="extend">Extend</option>
+                        </select>
                       </div>
-                    ))}
+                      <textarea
+                        value={customGameForm.files}
+                        onChange={(e) => setCustomGameForm({ ...customGameForm, files: e.target.value })}
+                        placeholder="Save file paths, one per line (e.g. <home>/Documents/My Game/Saves)"
+                        rows={3}
+                        className="w-full bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500"
+                      />
+                      <textarea
+                        value={customGameForm.registry}
+                        onChange={(e) => setCustomGameForm({ ...customGameForm, registry: e.target.value })}
+                        placeholder="Registry keys, one per line (optional)"
+                        rows={2}
+                        className="w-full bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500"
+                      />
+                      <button
+                        onClick={handleSaveCustomGame}
+                        disabled={!customGameForm.name.trim()}
+                        className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-500 rounded-lg text-sm font-semibold text-white disabled:opacity-50"
+                      >
+                        <Save className="w-4 h-4" />
+                        {ludusaviCustomization.custom_games.some(g => g.name === customGameForm.name.trim()) ? 'Update Custom Game' : 'Add Custom Game'}
+                      </button>
+                    </div>
                   </div>
-                )}
-              </div>
+                </div>
+              )}
             </div>
           </div>
         )}
@@ -1532,6 +3703,26 @@ function App() {
                     </div>
                     <h3 className="text-lg font-bold text-white">Filter Games</h3>
                   </div>
+                  <div className="flex items-center gap-4">
+                    <button
+                      onClick={handleDownloadAllIcons}
+                      disabled={downloadingIcons}
+                      title="Download the icons of every tracked achievement so they show instantly and offline"
+                      className="flex items-center gap-1.5 text-sm text-gray-300 hover:text-white disabled:opacity-50 transition-colors"
+                    >
+                      <Download className="w-4 h-4" />
+                      {downloadingIcons ? 'Downloading icons...' : 'Download all icons'}
+                    </button>
+                    <label className="flex items-center gap-2 text-sm text-gray-300 cursor-pointer" title="Show each game once, combining all of its sources">
+                      <input
+                        type="checkbox"
+                        checked={mergeSources}
+                        onChange={(e) => handleToggleMergeSources(e.target.checked)}
+                        className="w-4 h-4 accent-blue-500"
+                      />
+                      Merge sources
+                    </label>
+                  </div>
                 </div>
 
                 {/* Filter Input */}
@@ -1643,6 +3834,60 @@ function App() {
                       </p>
                     </div>
                     <div className="flex items-center gap-3">
+                      <select
+                        value={exportFormat}
+                        onChange={(e) => setExportFormat(e.target.value as ExportFormat)}
+                        className="bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white focus:outline-none focus:border-blue-500"
+                      >
+                        <option value="steam_unlock_time">Steam UnlockTime</option>
+                        <option value="goldberg">Goldberg</option>
+                        <option value="csv">CSV</option>
+                        <option value="native">App JSON</option>
+                      </select>
+                      <button
+                        onClick={() => handleToggleQuietGame(selectedGame.app_id)}
+                        title="Hold unlock and backup notifications while this game runs and show them in one recap when it closes"
+                        className={`flex items-center gap-2 px-4 py-2 rounded-lg font-semibold transition-all border-2 ${
+                          (config.quietGames || []).includes(selectedGame.app_id) ? 'bg-blue-600 border-blue-500/30' : 'bg-[#1a1f3a] hover:bg-[#232946] border-[#2a3142]'
+                        }`}
+                      >
+                        <Ban className="w-4 h-4" />
+                        Do Not Disturb
+                      </button>
+                      <button
+                        onClick={() => handleToggleCompletionOrder(selectedGame.app_id)}
+                        className={`flex items-center gap-2 px-4 py-2 rounded-lg font-semibold transition-all border-2 ${
+                          completionOrder ? 'bg-blue-600 border-blue-500/30' : 'bg-[#1a1f3a] hover:bg-[#232946] border-[#2a3142]'
+                        }`}
+                      >
+                        <Trophy className="w-4 h-4" />
+                        Suggested Order
+                      </button>
+                      <button
+                        onClick={() => handleDetectDlc(selectedGame.app_id)}
+                        disabled={detectingDlc}
+                        className="flex items-center gap-2 bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50"
+                      >
+                        <RefreshCw className={`w-4 h-4 ${detectingDlc ? 'animate-spin' : ''}`} />
+                        Detect DLC
+                      </button>
+                      <button
+                        onClick={() => handleScanScreenshots(selectedGame.app_id)}
+                        disabled={scanningScreenshots}
+                        className="flex items-center gap-2 bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50"
+                      >
+                        <RefreshCw className={`w-4 h-4 ${scanningScreenshots ? 'animate-spin' : ''}`} />
+                        Find Screenshots
+                      </button>
+                      <button
+                        onClick={() => handleSimulateSession(selectedGame.app_id)}
+                        disabled={simulating}
+                        title="Start, unlock, end and back up this game without launching it, to check your setup"
+                        className="flex items-center gap-2 bg-[#1a1f3a] hover:bg-[#232946] px-4 py-2 rounded-lg font-semibold transition-all border-2 border-[#2a3142] disabled:opacity-50"
+                      >
+                        <GamepadIcon className={`w-4 h-4 ${simulating ? 'animate-pulse' : ''}`} />
+                        {simulating ? 'Simulating...' : 'Simulate Session'}
+                      </button>
                       <button
                         onClick={() => handleExportGameAchievements(selectedGame.app_id, selectedGame.game_name)}
                         className="flex items-center gap-2 bg-emerald-600 hover:bg-emerald-500 px-4 py-2 rounded-lg font-semibold transition-all shadow-lg hover:shadow-emerald-500/20 border border-emerald-500/30"
@@ -1661,6 +3906,79 @@ function App() {
                       </button>
                     </div>
                   </div>
+
+                  {simulationReport && simulationReport.game_name === selectedGame.game_name && (
+                    <div className="mt-4 bg-[#0f1420] p-4 rounded-lg border-2 border-[#2a3142]">
+                      <div className="flex items-center justify-between mb-2">
+                        <h4 className="font-semibold text-white text-sm">Simulated session</h4>
+                        <button onClick={() => setSimulationReport(null)} className="p-1 hover:bg-white/10 rounded">
+                          <X className="w-4 h-4" />
+                        </button>
+                      </div>
+                      {simulationReport.steps.map(step => (
+                        <div key={step.name} className="flex items-start gap-2 text-sm py-1">
+                          {step.success
+                            ? <CheckCircle className="w-4 h-4 text-emerald-400 flex-shrink-0 mt-0.5" />
+                            : <AlertCircle className="w-4 h-4 text-red-400 flex-shrink-0 mt-0.5" />}
+                          <span className="text-white font-medium">{step.name}</span>
+                          <span className="text-gray-400">{step.detail}</span>
+                        </div>
+                      ))}
+                    </div>
+                  )}
+
+                  {/* Sort and filter, remembered per game */}
+                  {!completionOrder && (
+                    <div className="flex flex-wrap gap-3 mt-4">
+                      <select
+                        value={viewPrefs.sort_by}
+                        onChange={(e) => handleChangeViewPrefs(selectedGame.app_id, { ...viewPrefs, sort_by: e.target.value as AchievementSort })}
+                        className="bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                      >
+                        <option value="default">Default order</option>
+                        <option value="rarity">Rarest first</option>
+                        <option value="unlock_date">Recently unlocked</option>
+                        <option value="locked_first">Locked first</option>
+                      </select>
+                      <select
+                        value={viewPrefs.filter}
+                        onChange={(e) => handleChangeViewPrefs(selectedGame.app_id, { ...viewPrefs, filter: e.target.value as AchievementFilter })}
+                        className="bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm focus:outline-none focus:border-blue-500"
+                      >
+                        <option value="all">All achievements</option>
+                        <option value="locked">Locked only</option>
+                        <option value="unlocked">Unlocked only</option>
+                      </select>
+                    </div>
+                  )}
+
+                  {/* Completion per DLC, click to filter */}
+                  {dlcGroups.length > 1 && (
+                    <div className="flex flex-wrap gap-2 mt-4">
+                      <button
+                        onClick={() => setDlcFilter('all')}
+                        className={`px-3 py-1.5 rounded-lg text-sm border-2 transition-all ${
+                          dlcFilter === 'all' ? 'border-blue-500 text-blue-300' : 'border-[#2a3142] text-gray-400 hover:text-gray-200'
+                        }`}
+                      >
+                        All
+                      </button>
+                      {dlcGroups.map(group => {
+                        const key = group.dlc_app_id ?? 'base';
+                        return (
+                          <button
+                            key={key}
+                            onClick={() => setDlcFilter(key)}
+                            className={`px-3 py-1.5 rounded-lg text-sm border-2 transition-all ${
+                              dlcFilter === key ? 'border-blue-500 text-blue-300' : 'border-[#2a3142] text-gray-400 hover:text-gray-200'
+                            }`}
+                          >
+                            {group.name} <span className="text-gray-500">{group.unlocked}/{group.total}</span>
+                          </button>
+                        );
+                      })}
+                    </div>
+                  )}
                 </div>
 
                 <div className="max-h-[600px] overflow-y-auto p-6 space-y-3">
@@ -1669,12 +3987,37 @@ function App() {
                       <div className="inline-block animate-spin rounded-full h-8 w-8 border-3 border-gray-700 border-t-blue-500 mb-3"></div>
                       <p className="text-gray-400">Loading achievements...</p>
                     </div>
-                  ) : gameAchievements.length === 0 ? (
+                  ) : completionOrder ? (
+                    completionOrder.length === 0 ? (
+                      <div className="text-center py-12 text-gray-400">
+                        <p>Everything is unlocked</p>
+                      </div>
+                    ) : (
+                      completionOrder.map((suggestion, index) => (
+                        <div
+                          key={suggestion.achievement_id}
+                          onClick={() => handleAchievementClick(suggestion)}
+                          className="flex items-center gap-4 p-4 rounded-lg border-2 bg-[#0f1420] border-[#2a3142] hover:border-blue-500/60 cursor-pointer transition-all"
+                        >
+                          <span className="w-8 text-right text-lg font-bold text-gray-500">{index + 1}</span>
+                          <div className="flex-1 min-w-0">
+                            <h4 className="font-semibold text-white">{suggestion.display_name}</h4>
+                            {suggestion.description && (
+                              <p className="text-sm text-gray-400 truncate">{suggestion.description}</p>
+                            )}
+                          </div>
+                          <span className={`text-xs ${suggestion.tags.includes('missable') ? 'text-red-400' : 'text-gray-400'}`}>
+                            {suggestion.reason}
+                          </span>
+                        </div>
+                      ))
+                    )
+                  ) : visibleAchievements.length === 0 ? (
                     <div className="text-center py-12 text-gray-400">
                       <p>No achievement details found</p>
                     </div>
                   ) : (
-                    gameAchievements.map(achievement => (
+                    visibleAchievements.map(achievement => (
                       <div
                         key={achievement.achievement_id}
                         onClick={() => handleAchievementClick(achievement)}
@@ -1706,6 +4049,19 @@ function App() {
                                 Unlocked: {new Date(achievement.unlock_time * 1000).toLocaleString()}
                               </p>
                             )}
+                            {screenshotsOf(achievement.achievement_id).length > 0 && (
+                              <img
+                                src={screenshotsOf(achievement.achievement_id)[0].url}
+                                alt="Screenshot at unlock"
+                                className="mt-2 h-16 rounded border border-[#2a3142]"
+                              />
+                            )}
+                            {achievement.sources && (
+                              <p className="text-xs text-gray-500 mt-1">
+                                {achievement.achieved ? `Unlocked via ${achievement.source}` : 'Locked'}
+                                {achievement.sources.length > 1 && ` • Seen in ${achievement.sources.join(', ')}`}
+                              </p>
+                            )}
                           </div>
                           {achievement.achieved && (
                             <CheckCircle className="flex-shrink-0 w-6 h-6 text-emerald-400" />
@@ -1717,6 +4073,175 @@ function App() {
                 </div>
               </div>
             )}
+
+            {/* Achievement source health */}
+            <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+              <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center gap-2">
+                  <div className="p-1.5 bg-blue-600/20 rounded-lg border border-blue-500/30">
+                    <Info className="w-5 h-5 text-blue-400" />
+                  </div>
+                  <h3 className="text-lg font-bold text-white">Source Health</h3>
+                </div>
+                <button
+                  onClick={handleCheckSourceHealth}
+                  disabled={checkingSources}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300 disabled:opacity-50"
+                >
+                  <RefreshCw className={`w-4 h-4 ${checkingSources ? 'animate-spin' : ''}`} />
+                  {sourceHealth ? 'Check Again' : 'Check Sources'}
+                </button>
+              </div>
+              {sourceHealth === null ? (
+                <p className="text-sm text-gray-400">Checks that every tracked game's achievement file is still where its source keeps it.</p>
+              ) : sourceHealth.every(health => !health.problem) ? (
+                <p className="text-sm text-emerald-400">All {sourceHealth.length} tracked games can be watched.</p>
+              ) : (
+                <div className="space-y-2">
+                  {sourceHealth.filter(health => health.problem).map((health) => (
+                    <div key={`${health.app_id}-${health.recorded_source}`} className="bg-[#0f1420] rounded-lg p-3 border border-amber-500/30">
+                      <p className="font-medium text-white text-sm">{health.game_name}</p>
+                      <p className="text-xs text-amber-400 mt-1">{health.problem}</p>
+                      <p className="text-xs text-gray-500 mt-1">
+                        AppID: {health.app_id}
+                        {health.available_sources.length > 0 && ` • files found for ${health.available_sources.join(', ')}`}
+                        {health.watching && ' • watched now'}
+                      </p>
+                    </div>
+                  ))}
+                  <p className="text-xs text-gray-500">
+                    {sourceHealth.filter(health => !health.problem).length} other tracked games are fine.
+                  </p>
+                </div>
+              )}
+            </div>
+
+            {/* Games stored under more than one source */}
+            {duplicateGames.length > 0 && (
+              <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+                <div className="flex items-center gap-2 mb-4">
+                  <div className="p-1.5 bg-amber-600/20 rounded-lg border border-amber-500/30">
+                    <AlertCircle className="w-5 h-5 text-amber-400" />
+                  </div>
+                  <h3 className="text-lg font-bold text-white">Duplicate Games ({duplicateGames.length})</h3>
+                </div>
+                <div className="space-y-3">
+                  {duplicateGames.map((game) => (
+                    <div key={game.app_id} className="bg-[#0f1420] rounded-lg p-4 border border-[#2a3142]">
+                      <p className="font-medium text-white text-sm">{game.game_name}</p>
+                      <p className="text-xs text-gray-500 mt-1">AppID: {game.app_id}</p>
+                      <div className="mt-3 space-y-2">
+                        {game.sources.map((set) => (
+                          <div key={set.source} className="flex items-center justify-between gap-4">
+                            <div className="flex-1 min-w-0 text-sm">
+                              <span className="text-white">{set.source}</span>
+                              {set.source === game.suggested_source && (
+                                <span className="ml-2 text-xs text-emerald-400">most recent</span>
+                              )}
+                              <p className="text-xs text-gray-500">
+                                {set.unlocked}/{set.total} unlocked • updated {new Date(set.last_updated * 1000).toLocaleString()}
+                                {set.missing_unlocks.length > 0 && (
+                                  <span className="text-amber-400"> • missing {set.missing_unlocks.length} unlocks found elsewhere</span>
+                                )}
+                              </p>
+                            </div>
+                            <button
+                              onClick={() => handleConsolidateGame(game, set.source)}
+                              className="px-3 py-1.5 bg-blue-600/20 hover:bg-blue-600/30 rounded-lg border border-blue-500/30 text-xs text-blue-300 transition-all"
+                              title={`Merge the other sources into ${set.source}`}
+                            >
+                              Keep this
+                            </button>
+                            <button
+                              onClick={() => handleDeleteGameSource(game, set)}
+                              className="p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 transition-all"
+                              title={`Delete the ${set.source} achievements`}
+                            >
+                              <Trash2 className="w-4 h-4 text-red-400" />
+                            </button>
+                          </div>
+                        ))}
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
+            {/* Achievement Backups */}
+            {achievementBackups.length > 0 && (
+              <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+                <div className="flex items-center gap-2 mb-4">
+                  <div className="p-1.5 bg-emerald-600/20 rounded-lg border border-emerald-500/30">
+                    <Save className="w-5 h-5 text-emerald-400" />
+                  </div>
+                  <h3 className="text-lg font-bold text-white">Achievement Backups ({achievementBackups.length})</h3>
+                </div>
+                <div className="space-y-2">
+                  {achievementBackups.map((backup) => (
+                    <div
+                      key={backup.app_id}
+                      className="bg-[#0f1420] rounded-lg p-4 border border-[#2a3142] hover:border-emerald-500/30 transition-all group"
+                    >
+                      <div className="flex items-center justify-between">
+                        <div className="flex-1 min-w-0">
+                          {renamingBackup?.appId === backup.app_id ? (
+                            <input
+                              type="text"
+                              value={renamingBackup.name}
+                              autoFocus
+                              onChange={(e) => setRenamingBackup({ appId: backup.app_id, name: e.target.value })}
+                              onKeyDown={(e) => {
+                                if (e.key === 'Enter') handleRenameAchievementBackup();
+                                if (e.key === 'Escape') setRenamingBackup(null);
+                              }}
+                              className="w-full bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-1.5 text-white text-sm focus:outline-none focus:border-blue-500"
+                            />
+                          ) : (
+                            <p
+                              className="font-medium text-white text-sm truncate cursor-text"
+                              title="Click to rename"
+                              onClick={() => setRenamingBackup({ appId: backup.app_id, name: backup.game_name })}
+                            >
+                              {backup.game_name}
+                            </p>
+                          )}
+                          <p className="text-xs text-gray-500 mt-1">
+                            AppID: {backup.app_id} • {backup.unlocked} unlocked • {backup.source} • {new Date(backup.modified * 1000).toLocaleString()}
+                          </p>
+                        </div>
+                        {renamingBackup?.appId === backup.app_id ? (
+                          <div className="ml-4 flex items-center gap-2">
+                            <button
+                              onClick={handleRenameAchievementBackup}
+                              className="p-2 bg-emerald-600/20 hover:bg-emerald-600/30 rounded-lg border border-emerald-500/30 transition-all"
+                              title="Save name"
+                            >
+                              <CheckCircle className="w-4 h-4 text-emerald-400" />
+                            </button>
+                            <button
+                              onClick={() => setRenamingBackup(null)}
+                              className="p-2 bg-[#2a3142] hover:bg-[#343c52] rounded-lg border border-[#2a3142] transition-all"
+                              title="Cancel"
+                            >
+                              <X className="w-4 h-4 text-gray-400" />
+                            </button>
+                          </div>
+                        ) : (
+                          <button
+                            onClick={() => handleDeleteAchievementBackup(backup)}
+                            className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 hover:border-red-500/50 transition-all group-hover:scale-105"
+                            title="Delete backup"
+                          >
+                            <Trash2 className="w-4 h-4 text-red-400" />
+                          </button>
+                        )}
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
           </div>
         )}
 
@@ -1792,6 +4317,190 @@ function App() {
               )}
             </div>
 
+            {/* Steam Categories */}
+            <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+              <div className="flex items-center gap-2 mb-4">
+                <div className="p-1.5 bg-red-600/20 rounded-lg border border-red-500/30">
+                  <Ban className="w-5 h-5 text-red-400" />
+                </div>
+                <h3 className="text-lg font-bold text-white">Exclude Steam Categories</h3>
+              </div>
+              <p className="text-sm text-gray-400 mb-4">
+                Every game in a selected Steam library category is excluded, and the list follows the category as you change it in Steam.
+              </p>
+              {steamCategories.length === 0 ? (
+                <p className="text-sm text-gray-500">No categories found in your Steam library</p>
+              ) : (
+                <div className="flex flex-wrap gap-2">
+                  {steamCategories.map((category) => {
+                    const selected = (config.excludedSteamCategories || []).includes(category.name);
+                    return (
+                      <button
+                        key={category.name}
+                        onClick={() => handleToggleExcludedCategory(category.name)}
+                        className={`px-3 py-1.5 rounded-lg text-sm border transition-all ${
+                          selected
+                            ? 'bg-red-600/20 border-red-500/50 text-red-300'
+                            : 'bg-[#0f1420] border-[#2a3142] text-gray-300 hover:border-red-500/30'
+                        }`}
+                      >
+                        {category.name} ({category.app_ids.length})
+                      </button>
+                    );
+                  })}
+                </div>
+              )}
+            </div>
+
+            {/* Installed Steam Apps */}
+            <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+              <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center gap-2">
+                  <div className="p-1.5 bg-blue-600/20 rounded-lg border border-blue-500/30">
+                    <GamepadIcon className="w-5 h-5 text-blue-400" />
+                  </div>
+                  <h3 className="text-lg font-bold text-white">
+                    Installed Steam Apps{steamLibrary ? ` (${steamLibrary.length})` : ''}
+                  </h3>
+                </div>
+                <button
+                  onClick={handleLoadSteamLibrary}
+                  disabled={loadingSteamLibrary}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300 disabled:opacity-50"
+                >
+                  <RefreshCw className={`w-4 h-4 ${loadingSteamLibrary ? 'animate-spin' : ''}`} />
+                  {steamLibrary ? 'Reload' : 'Show Installed Apps'}
+                </button>
+              </div>
+              <p className="text-sm text-gray-400 mb-4">
+                Names, types and launch executables come from Steam's app cache. Apps that aren't games are never monitored.
+              </p>
+
+              <div className="flex flex-wrap gap-2 mb-4">
+                <button
+                  onClick={handleExcludeNonGameApps}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 text-sm text-red-300"
+                >
+                  <Ban className="w-4 h-4" />
+                  Exclude All Non-Game Apps
+                </button>
+                {selectedLibraryApps.length > 0 && (
+                  <button
+                    onClick={handleExcludeSelectedApps}
+                    className="flex items-center gap-2 px-3 py-1.5 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 text-sm text-red-300"
+                  >
+                    <Ban className="w-4 h-4" />
+                    Exclude Selected ({selectedLibraryApps.length})
+                  </button>
+                )}
+              </div>
+
+              {steamLibrary && (
+                <div className="max-h-64 overflow-y-auto divide-y divide-[#2a3142] border border-[#2a3142] rounded-lg">
+                  {steamLibrary.map((app) => (
+                    <div key={app.app_id} className="p-3 flex items-center justify-between">
+                      <div className="flex items-center gap-3 min-w-0">
+                        <input
+                          type="checkbox"
+                          checked={selectedLibraryApps.includes(app.app_id)}
+                          disabled={exclusions.some(e => e.app_id === app.app_id)}
+                          onChange={(e) => setSelectedLibraryApps(e.target.checked
+                            ? [...selectedLibraryApps, app.app_id]
+                            : selectedLibraryApps.filter(id => id !== app.app_id))}
+                          className="w-4 h-4 accent-blue-500 flex-shrink-0"
+                        />
+                        <div className="min-w-0">
+                          <p className={`text-sm truncate ${app.is_game ? 'text-white' : 'text-gray-500'}`}>{app.name}</p>
+                          <p className="text-xs text-gray-500 mt-0.5 truncate">
+                            AppID: {app.app_id}
+                            {app.executables.length > 0 ? ` · ${app.executables.join(', ')}` : ' · no launch options'}
+                            {exclusions.some(e => e.app_id === app.app_id) && ' · excluded'}
+                          </p>
+                        </div>
+                      </div>
+                      <span className={`ml-4 px-2 py-0.5 rounded text-xs flex-shrink-0 ${app.is_game ? 'bg-blue-600/20 text-blue-300' : 'bg-gray-700/40 text-gray-400'}`}>
+                        {app.app_type ?? 'unknown'}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+
+            {/* Ignored Executables */}
+            <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
+              <div className="flex items-center justify-between mb-4">
+                <div className="flex items-center gap-2">
+                  <div className="p-1.5 bg-red-600/20 rounded-lg border border-red-500/30">
+                    <Ban className="w-5 h-5 text-red-400" />
+                  </div>
+                  <h3 className="text-lg font-bold text-white">Ignored Executables ({ignoredExecutables.length})</h3>
+                </div>
+                <button
+                  onClick={handleScanExecutables}
+                  disabled={scanningExecutables}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300 disabled:opacity-50"
+                >
+                  <RefreshCw className={`w-4 h-4 ${scanningExecutables ? 'animate-spin' : ''}`} />
+                  {detectedExecutables ? 'Rescan' : 'Show Detected Executables'}
+                </button>
+              </div>
+              <p className="text-sm text-gray-400 mb-4">
+                Soundtracks, dedicated servers and SDK tools are skipped automatically. Ignore any other executable that shouldn't count as its game running.
+              </p>
+
+              {ignoredExecutables.length > 0 && (
+                <div className="space-y-2 mb-4">
+                  {ignoredExecutables.map((exe) => (
+                    <div key={exe.exe_name} className="bg-[#0f1420] rounded-lg p-3 border border-[#2a3142] flex items-center justify-between">
+                      <div className="min-w-0">
+                        <p className="font-medium text-white text-sm truncate">{exe.exe_name}</p>
+                        <p className="text-xs text-gray-500 mt-0.5">{exe.game_name} (AppID: {exe.app_id})</p>
+                      </div>
+                      <button
+                        onClick={() => handleUnignoreExecutable(exe.exe_name)}
+                        className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30"
+                        title="Stop ignoring"
+                      >
+                        <Trash2 className="w-4 h-4 text-red-400" />
+                      </button>
+                    </div>
+                  ))}
+                </div>
+              )}
+
+              {detectedExecutables && (
+                <>
+                  <input
+                    type="text"
+                    value={executableFilter}
+                    onChange={(e) => setExecutableFilter(e.target.value)}
+                    placeholder="Filter by game or executable..."
+                    className="w-full bg-[#0f1420] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white text-sm placeholder-gray-500 focus:outline-none focus:border-blue-500 mb-2"
+                  />
+                  <div className="max-h-64 overflow-y-auto divide-y divide-[#2a3142] border border-[#2a3142] rounded-lg">
+                    {detectedExecutables
+                      .filter(exe => !ignoredExecutables.some(i => i.exe_name.toLowerCase() === exe.exe_name.toLowerCase()))
+                      .filter(exe => `${exe.game_name} ${exe.exe_name}`.toLowerCase().includes(executableFilter.toLowerCase()))
+                      .slice(0, 100)
+                      .map((exe) => (
+                        <button
+                          key={exe.exe_name}
+                          onClick={() => handleIgnoreExecutable(exe)}
+                          className="w-full p-3 hover:bg-[#13172a] flex items-center justify-between group text-left"
+                        >
+                          <div className="min-w-0">
+                            <p className="text-sm text-white group-hover:text-red-400 truncate">{exe.exe_name}</p>
+                            <p className="text-xs text-gray-500 mt-0.5">{exe.game_name}</p>
+                          </div>
+                          <Ban className="w-4 h-4 text-red-400 opacity-0 group-hover:opacity-100 flex-shrink-0 ml-2" />
+                        </button>
+                      ))}
+                  </div>
+                </>
+              )}
+            </div>
+
             {/* Excluded Apps List */}
             {loadingExclusions ? (
               <div className="bg-[#1a1f3a] rounded-xl p-12 border border-[#2a3142] shadow-xl text-center">
@@ -1802,15 +4511,49 @@ function App() {
               <div className="bg-[#1a1f3a] rounded-xl p-12 border border-[#2a3142] shadow-xl text-center">
                 <Ban className="w-16 h-16 mx-auto mb-4 opacity-50 text-gray-600" />
                 <p className="text-gray-400 font-medium mb-4">No exclusions added yet</p>
-                <p className="text-sm text-gray-500">Search for a Steam app above to exclude it from monitoring</p>
+                <p className="text-sm text-gray-500 mb-4">Search for a Steam app above to exclude it from monitoring</p>
+                <button
+                  onClick={handleImportExclusions}
+                  className="inline-flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300"
+                >
+                  <FolderOpen className="w-4 h-4" />
+                  Import List
+                </button>
               </div>
             ) : (
               <div className="bg-[#1a1f3a] rounded-xl p-5 border border-[#2a3142] shadow-xl">
-                <div className="flex items-center gap-2 mb-4">
-                  <div className="p-1.5 bg-red-600/20 rounded-lg border border-red-500/30">
-                    <Ban className="w-5 h-5 text-red-400" />
+                <div className="flex items-center justify-between mb-4">
+                  <div className="flex items-center gap-2">
+                    <div className="p-1.5 bg-red-600/20 rounded-lg border border-red-500/30">
+                      <Ban className="w-5 h-5 text-red-400" />
+                    </div>
+                    <h3 className="text-lg font-bold text-white">Excluded Apps ({exclusions.length})</h3>
+                  </div>
+                  <div className="flex items-center gap-2">
+                    {selectedExclusions.length > 0 && (
+                      <button
+                        onClick={handleRemoveSelectedExclusions}
+                        className="flex items-center gap-2 px-3 py-1.5 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 text-sm text-red-300"
+                      >
+                        <Trash2 className="w-4 h-4" />
+                        Remove Selected ({selectedExclusions.length})
+                      </button>
+                    )}
+                    <button
+                      onClick={handleImportExclusions}
+                      className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300"
+                    >
+                      <FolderOpen className="w-4 h-4" />
+                      Import
+                    </button>
+                    <button
+                      onClick={handleExportExclusions}
+                      className="flex items-center gap-2 px-3 py-1.5 bg-[#0f1420] hover:bg-[#13172a] rounded-lg border border-[#2a3142] text-sm text-gray-300"
+                    >
+                      <Download className="w-4 h-4" />
+                      Export
+                    </button>
                   </div>
-                  <h3 className="text-lg font-bold text-white">Excluded Apps ({exclusions.length})</h3>
                 </div>
                 <div className="space-y-2">
                   {exclusions.map((exclusion) => (
@@ -1819,19 +4562,34 @@ function App() {
                       className="bg-[#0f1420] rounded-lg p-4 border border-[#2a3142] hover:border-red-500/30 transition-all group"
                     >
                       <div className="flex items-center justify-between">
+                        {!exclusion.category && (
+                          <input
+                            type="checkbox"
+                            checked={selectedExclusions.includes(exclusion.app_id)}
+                            onChange={(e) => setSelectedExclusions(e.target.checked
+                              ? [...selectedExclusions, exclusion.app_id]
+                              : selectedExclusions.filter(id => id !== exclusion.app_id))}
+                            className="w-4 h-4 accent-blue-500 mr-3 flex-shrink-0"
+                          />
+                        )}
                         <div className="flex-1 min-w-0">
                           <p className="font-medium text-white text-sm truncate">
                             {exclusion.name}
                           </p>
-                          <p className="text-xs text-gray-500 mt-1">AppID: {exclusion.app_id}</p>
+                          <p className="text-xs text-gray-500 mt-1">
+                            AppID: {exclusion.app_id}
+                            {exclusion.category && ` · From Steam category "${exclusion.category}"`}
+                          </p>
                         </div>
-                        <button
-                          onClick={() => handleRemoveExclusion(exclusion.app_id, exclusion.name)}
-                          className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 hover:border-red-500/50 transition-all group-hover:scale-105"
-                          title="Remove from exclusions"
-                        >
-                          <Trash2 className="w-4 h-4 text-red-400" />
-                        </button>
+                        {!exclusion.category && (
+                          <button
+                            onClick={() => handleRemoveExclusion(exclusion.app_id, exclusion.name)}
+                            className="ml-4 p-2 bg-red-600/20 hover:bg-red-600/30 rounded-lg border border-red-500/30 hover:border-red-500/50 transition-all group-hover:scale-105"
+                            title="Remove from exclusions"
+                          >
+                            <Trash2 className="w-4 h-4 text-red-400" />
+                          </button>
+                        )}
                       </div>
                     </div>
                   ))}
@@ -2025,6 +4783,64 @@ function App() {
 
             {/* Modal Body */}
             <div className="p-6 space-y-6">
+              {/* Tags used by the suggested completion order */}
+              <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
+                <div>
+                  <h4 className="font-semibold text-white text-base">Tags</h4>
+                  <p className="text-sm text-gray-400 mt-1">Missable achievements are suggested first, grindy ones last</p>
+                </div>
+                <div className="flex gap-2">
+                  {ACHIEVEMENT_TAGS.map(tag => {
+                    const active = (achievementTags[editingAchievement.achievement_id] || []).includes(tag);
+                    return (
+                      <button
+                        key={tag}
+                        onClick={() => handleToggleAchievementTag(editingAchievement, tag)}
+                        className={`px-3 py-1.5 rounded-lg text-sm capitalize border-2 transition-all ${
+                          active ? 'bg-blue-600 border-blue-500/30 text-white' : 'border-[#2a3142] text-gray-400 hover:text-gray-200'
+                        }`}
+                      >
+                        {tag}
+                      </button>
+                    );
+                  })}
+                </div>
+              </div>
+
+              {/* Screenshots taken around the unlock */}
+              {screenshotsOf(editingAchievement.achievement_id).length > 0 && (
+                <div className="bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
+                  <h4 className="font-semibold text-white text-base mb-3">Your Screenshots At Unlock</h4>
+                  <div className="grid grid-cols-2 gap-3">
+                    {screenshotsOf(editingAchievement.achievement_id).map(screenshot => (
+                      <a key={screenshot.id} href={screenshot.full_url} target="_blank" rel="noreferrer">
+                        <img src={screenshot.url} alt="" className="w-full rounded border border-[#2a3142] hover:border-blue-500/60" />
+                        <p className="text-xs text-gray-500 mt-1">{new Date(screenshot.taken_at * 1000).toLocaleString()}</p>
+                      </a>
+                    ))}
+                  </div>
+                </div>
+              )}
+
+              {/* DLC the achievement belongs to */}
+              {dlcGroups.length > 1 && (
+                <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
+                  <div>
+                    <h4 className="font-semibold text-white text-base">Belongs To</h4>
+                    <p className="text-sm text-gray-400 mt-1">Fix the DLC if detection got it wrong</p>
+                  </div>
+                  <select
+                    value={dlcGroupOf(editingAchievement.achievement_id)?.dlc_app_id ?? ''}
+                    onChange={(e) => handleSetAchievementDlc(editingAchievement, e.target.value === '' ? null : Number(e.target.value))}
+                    className="bg-[#1a1f3a] border-2 border-[#2a3142] rounded-lg px-3 py-2 text-white focus:outline-none focus:border-blue-500"
+                  >
+                    {dlcGroups.map(group => (
+                      <option key={group.dlc_app_id ?? 'base'} value={group.dlc_app_id ?? ''}>{group.name}</option>
+                    ))}
+                  </select>
+                </div>
+              )}
+
               {/* Achievement Status Toggle */}
               <div className="flex items-center justify-between bg-[#0f1420] p-5 rounded-lg border-2 border-[#2a3142]">
                 <div>
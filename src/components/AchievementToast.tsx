@@ -1,21 +1,9 @@
 import React, { useEffect, useState, useRef } from 'react';
-import { listen } from '@tauri-apps/api/event';
-import { convertFileSrc, invoke } from '@tauri-apps/api/tauri';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 import { Trophy } from 'lucide-react';
 import './AchievementToast.css';
 import { RaritySettings, defaultRaritySettings, calculateRarity, formatPercentage, getRarityColor } from '../types/rarityTypes';
-
-interface AchievementUnlockEvent {
-  app_id: number;
-  game_name: string;
-  achievement_id: string;
-  display_name: string;
-  description: string;
-  icon_url?: string;
-  unlock_time: number;
-  source: string;
-  global_unlock_percentage?: number;
-}
+import { AchievementUnlockEvent, listenAppEvents } from '../types/appEvents';
 
 interface ToastData extends AchievementUnlockEvent {
   id: number;
@@ -58,7 +46,8 @@ export function AchievementToastContainer() {
 
   useEffect(() => {
     // Listen for achievement unlock events from Tauri backend
-    const unlisten = listen<AchievementUnlockEvent>('achievement-unlocked', (event) => {
+    const unlisten = listenAppEvents('achievement-toasts', (event) => {
+      if (event.type !== 'achievement_unlocked') return;
       console.log('🏆 Achievement unlocked event received:', event.payload);
 
       // Add to queue
@@ -1,6 +1,6 @@
 import React, { useEffect, useState, useRef } from 'react';
 import { listen, emit } from '@tauri-apps/api/event';
-import { convertFileSrc, invoke } from '@tauri-apps/api/tauri';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 import { Trophy, CheckCircle, AlertCircle, GamepadIcon, Save } from 'lucide-react';
 import { RaritySettings, defaultRaritySettings, calculateRarity, formatPercentage } from '../types/rarityTypes';
 
@@ -17,6 +17,13 @@ interface NotificationData {
   error?: string;
   global_unlock_percentage?: number;
   duration_seconds?: number;
+  milestone?: number;
+  unlocked?: number;
+  total?: number;
+  perfect?: boolean;
+  sound_path?: string | null;
+  big_picture?: boolean; // Steam's gamepad UI is showing
+  scale?: number;
 }
 
 interface OverlayNotification extends NotificationData {
@@ -26,6 +33,51 @@ interface OverlayNotification extends NotificationData {
   customIconUrl?: string; // Blob URL for custom icon image
 }
 
+// Play a user-chosen sound file (read through Tauri since the overlay can't load local paths)
+function playSoundFile(customSoundPath: string) {
+  // Read audio file via Tauri command and create blob URL
+  invoke<number[]>('read_audio_file', { filePath: customSoundPath })
+    .then((bytes) => {
+      invoke('debug_log', { message: `Read ${bytes.length} bytes from audio file` });
+
+      // Convert bytes to Uint8Array
+      const uint8Array = new Uint8Array(bytes);
+
+      // Detect MIME type from file extension
+      let mimeType = 'audio/mpeg'; // default for MP3
+      const ext = customSoundPath.toLowerCase().split('.').pop();
+      if (ext === 'wav') mimeType = 'audio/wav';
+      else if (ext === 'ogg') mimeType = 'audio/ogg';
+      else if (ext === 'flac') mimeType = 'audio/flac';
+      else if (ext === 'aac') mimeType = 'audio/aac';
+
+      // Create blob from bytes
+      const blob = new Blob([uint8Array], { type: mimeType });
+      const blobUrl = URL.createObjectURL(blob);
+
+      invoke('debug_log', { message: `Created blob URL: ${blobUrl}` });
+      invoke('debug_log', { message: `MIME type: ${mimeType}` });
+
+      // Play audio
+      const audio = new Audio(blobUrl);
+      audio.volume = 1.0;
+
+      audio.play()
+        .then(() => {
+          invoke('debug_log', { message: '✓ Custom sound played successfully' });
+          // Clean up blob URL after playing
+          audio.onended = () => URL.revokeObjectURL(blobUrl);
+        })
+        .catch((error) => {
+          invoke('debug_log', { message: `✗ Failed to play custom sound: ${error.toString()}` });
+          URL.revokeObjectURL(blobUrl);
+        });
+    })
+    .catch((error) => {
+      invoke('debug_log', { message: `✗ Failed to read audio file: ${error}` });
+    });
+}
+
 function Overlay() {
   const [notifications, setNotifications] = useState<OverlayNotification[]>([]);
   const notificationIdCounter = useRef(0);
@@ -111,6 +163,9 @@ function Overlay() {
       localStorage.setItem('raritySettings', JSON.stringify(settings));
     });
 
+    // Tell the backend we can take notifications; it holds them back while a recreated overlay loads
+    Promise.all([unlistenNotification, unlistenRaritySync]).then(() => emit('overlay-ready'));
+
     return () => {
       unlistenNotification.then(fn => fn());
       unlistenRaritySync.then(fn => fn());
@@ -280,47 +335,7 @@ function Overlay() {
         invoke('debug_log', { message: `Custom sound path: ${customSoundPath}` });
 
         if (customSoundPath) {
-          // Read audio file via Tauri command and create blob URL
-          invoke<number[]>('read_audio_file', { filePath: customSoundPath })
-            .then((bytes) => {
-              invoke('debug_log', { message: `Read ${bytes.length} bytes from audio file` });
-
-              // Convert bytes to Uint8Array
-              const uint8Array = new Uint8Array(bytes);
-
-              // Detect MIME type from file extension
-              let mimeType = 'audio/mpeg'; // default for MP3
-              const ext = customSoundPath.toLowerCase().split('.').pop();
-              if (ext === 'wav') mimeType = 'audio/wav';
-              else if (ext === 'ogg') mimeType = 'audio/ogg';
-              else if (ext === 'flac') mimeType = 'audio/flac';
-              else if (ext === 'aac') mimeType = 'audio/aac';
-
-              // Create blob from bytes
-              const blob = new Blob([uint8Array], { type: mimeType });
-              const blobUrl = URL.createObjectURL(blob);
-
-              invoke('debug_log', { message: `Created blob URL: ${blobUrl}` });
-              invoke('debug_log', { message: `MIME type: ${mimeType}` });
-
-              // Play audio
-              const audio = new Audio(blobUrl);
-              audio.volume = 1.0;
-
-              audio.play()
-                .then(() => {
-                  invoke('debug_log', { message: '✓ Custom sound played successfully' });
-                  // Clean up blob URL after playing
-                  audio.onended = () => URL.revokeObjectURL(blobUrl);
-                })
-                .catch((error) => {
-                  invoke('debug_log', { message: `✗ Failed to play custom sound: ${error.toString()}` });
-                  URL.revokeObjectURL(blobUrl);
-                });
-            })
-            .catch((error) => {
-              invoke('debug_log', { message: `✗ Failed to read audio file: ${error}` });
-            });
+          playSoundFile(customSoundPath);
         } else {
           invoke('debug_log', { message: 'No custom sound configured - notification will be silent' });
         }
@@ -333,6 +348,11 @@ function Overlay() {
       }
     }
 
+    // Milestones bring their own sound; without one the backend already played a Windows sound
+    if (notificationData.type === 'milestone' && notificationData.sound_path) {
+      playSoundFile(notificationData.sound_path);
+    }
+
     // Add notification
     setNotifications(prev => [...prev, newNotification]);
 
@@ -346,7 +366,9 @@ function Overlay() {
     // Remove notification after duration (use duration from notification payload, default to 6 seconds for achievements)
     const duration = notificationData.type === 'achievement'
       ? (notificationData.duration_seconds || 6) * 1000
-      : 3000;
+      : notificationData.type === 'milestone'
+        ? (notificationData.perfect ? 9000 : 6000)
+        : notificationData.big_picture ? 6000 : 3000;
     setTimeout(() => {
       // Trigger exit animation
       setNotifications(prev =>
@@ -398,6 +420,8 @@ function OverlayNotificationCard({ notification, duration, raritySettings }: { n
     position = raritySettings[rarity].position;
     scaling = raritySettings[rarity].scaling / 100;
   }
+  // Larger cards when read from the couch in Big Picture
+  scaling *= notification.scale || 1;
 
   // Position classes mapping
   const positionClasses: Record<string, string> = {
@@ -435,6 +459,17 @@ function OverlayNotificationCard({ notification, duration, raritySettings }: { n
           />
         );
 
+      case 'milestone':
+        return (
+          <MilestoneNotification
+            gameName={notification.game_name || ''}
+            milestone={notification.milestone || 0}
+            unlocked={notification.unlocked || 0}
+            total={notification.total || 0}
+            perfect={notification.perfect || false}
+          />
+        );
+
       case 'game-detected':
         return (
           <GameDetectedNotification gameName={notification.game_name || ''} />
@@ -648,6 +683,60 @@ function AchievementNotification({
   );
 }
 
+// Completion milestone notification, platinum styled for a perfect game
+function MilestoneNotification({
+  gameName,
+  milestone,
+  unlocked,
+  total,
+  perfect,
+}: {
+  gameName: string;
+  milestone: number;
+  unlocked: number;
+  total: number;
+  perfect: boolean;
+}) {
+  if (perfect) {
+    return (
+      <div className="platinum-card relative overflow-hidden rounded-xl border-2 border-slate-200/80 shadow-2xl p-5 min-w-[380px]">
+        <div className="platinum-shine absolute inset-0 pointer-events-none" />
+        <div className="relative flex items-center gap-4">
+          <div className="platinum-trophy p-3 bg-white/20 rounded-full">
+            <Trophy className="w-8 h-8 text-white" />
+          </div>
+          <div className="flex-1">
+            <p className="text-slate-100 font-bold text-xs tracking-widest uppercase">Perfect Game</p>
+            <p className="text-white font-semibold text-lg">{gameName}</p>
+            <p className="text-slate-200 text-xs">All {total} achievements unlocked</p>
+          </div>
+        </div>
+      </div>
+    );
+  }
+
+  return (
+    <div className="bg-gradient-to-br from-[#1a1f3a] to-[#2a2f4a] rounded-xl border-2 border-amber-400/60 shadow-2xl backdrop-blur-sm p-5 min-w-[350px]">
+      <div className="flex items-center gap-3">
+        <div className="p-2 bg-amber-400/20 rounded-lg">
+          <Trophy className="w-5 h-5 text-amber-300" />
+        </div>
+        <div className="flex-1">
+          <p className="text-amber-300 font-semibold text-sm">{milestone}% Complete</p>
+          <p className="text-white font-medium mb-2">{gameName}</p>
+          <div className="h-1.5 bg-gray-700 rounded-full overflow-hidden">
+            <div
+              className="h-full bg-gradient-to-r from-amber-400 to-yellow-300"
+              style={{ width: `${total > 0 ? (unlocked / total) * 100 : 0}%` }}
+            />
+          </div>
+          <p className="text-gray-400 text-xs mt-1">{unlocked} / {total} achievements</p>
+        </div>
+      </div>
+    </div>
+  );
+}
+
 // Game detected notification
 function GameDetectedNotification({ gameName }: { gameName: string }) {
   return (
@@ -1,7 +1,7 @@
 import React, { useState } from 'react';
 import { Settings, Upload, X, Volume2, Type } from 'lucide-react';
 import { RarityTier, RarityCustomization, NotificationPosition, ScalingOption } from '../types/rarityTypes';
-import { invoke } from '@tauri-apps/api/tauri';
+import { invoke } from '@tauri-apps/api/core';
 
 interface RarityCustomizerProps {
   rarity: RarityTier;
@@ -0,0 +1,104 @@
+// Typed events from the backend, all delivered on the single 'app-event' channel
+
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
+
+export const APP_EVENT_CHANNEL = 'app-event';
+
+export interface AchievementUnlockEvent {
+  app_id: number;
+  game_name: string;
+  achievement_id: string;
+  display_name: string;
+  description: string;
+  icon_url?: string;
+  unlock_time: number;
+  source: string;
+  global_unlock_percentage?: number;
+}
+
+// What a steamsavemonitor:// link or the Explorer context menu asked for
+export type LaunchRequest =
+  | { action: 'backup'; app_id: number }
+  | { action: 'achievements'; app_id: number }
+  | { action: 'backup_executable'; path: string };
+
+// Progress of one run of a long-running command; total is null while the amount of work is unknown
+export interface ProgressEvent {
+  operation_id: string;
+  operation: string;
+  stage: string;
+  current: number;
+  total: number | null;
+  message: string;
+  done: boolean;
+}
+
+export type AppEvent =
+  | { type: 'game_detected'; payload: { name: string; app_id: number | null } }
+  | { type: 'game_ended'; payload: { name: string; app_id: number | null } }
+  | { type: 'game_not_found'; payload: { name: string } }
+  | { type: 'game_uninstalled'; payload: { app_id: number; name: string; final_backup: boolean } }
+  | { type: 'backup_started'; payload: { game_name: string; app_id: number | null } }
+  | { type: 'backup_finished'; payload: { game_name: string; app_id: number | null; success: boolean; error: string | null } }
+  | { type: 'backup_queue_changed'; payload: { queued: string[] } }
+  | { type: 'cloud_upload_finished'; payload: { game_name: string; destination: string; success: boolean; error: string | null } }
+  | { type: 'low_disk_space'; payload: { backup_path: string; free_bytes: number; threshold_mb: number } }
+  | { type: 'stale_backups'; payload: { games: string[] } }
+  | { type: 'achievement_unlocked'; payload: AchievementUnlockEvent }
+  | { type: 'launch_requested'; payload: LaunchRequest }
+  | { type: 'monitoring_paused'; payload: { paused: boolean } }
+  | { type: 'progress'; payload: ProgressEvent };
+
+// seq increases by one per event and restarts at 1 when the backend restarts
+export type SequencedEvent = AppEvent & { seq: number; timestamp: number };
+
+/**
+ * Subscribe to backend events. The last seen seq is kept in sessionStorage under `key`,
+ * so after a reload the events emitted while the page was gone are replayed first.
+ */
+export async function listenAppEvents(key: string, handler: (event: SequencedEvent) => void): Promise<UnlistenFn> {
+  const storageKey = `appEventSeq:${key}`;
+  const stored = sessionStorage.getItem(storageKey);
+  let lastSeq = stored ? Number(stored) : 0;
+  const firstLoad = stored === null;
+  let replaying = true;
+  const pending: SequencedEvent[] = [];
+
+  const deliver = (event: SequencedEvent) => {
+    if (event.seq <= lastSeq) return;
+    lastSeq = event.seq;
+    sessionStorage.setItem(storageKey, String(lastSeq));
+    handler(event);
+  };
+
+  // Listen before asking for missed events so nothing falls in between; live events wait for the replay
+  const unlisten = await listen<SequencedEvent>(APP_EVENT_CHANNEL, (event) => {
+    if (replaying) {
+      pending.push(event.payload);
+    } else {
+      deliver(event.payload);
+    }
+  });
+
+  try {
+    const missed = await invoke<SequencedEvent[]>('get_missed_events', { since: lastSeq });
+    if (firstLoad) {
+      // Nothing was missed yet; only start counting from the latest event
+      lastSeq = missed.length > 0 ? missed[missed.length - 1].seq : 0;
+      sessionStorage.setItem(storageKey, String(lastSeq));
+    } else {
+      // The backend restarted and its numbering started over
+      if (missed.length > 0 && missed[0].seq <= lastSeq) {
+        lastSeq = 0;
+      }
+      missed.forEach(deliver);
+    }
+  } catch (error) {
+    console.error('[Events] Failed to fetch missed events:', error);
+  }
+  replaying = false;
+  pending.sort((a, b) => a.seq - b.seq).forEach(deliver);
+
+  return unlisten;
+}